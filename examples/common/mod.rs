@@ -37,6 +37,9 @@ pub fn setup_log_file(platform: &str) -> Result<LogFile, std::io::Error> {
 }
 
 /// Map platform name to file extension
+///
+/// Mirrors `PackageType::extension()`; examples cannot link the bundler
+/// binary crate, so keep this in sync with `src/bundler/platform/package_type.rs`.
 pub fn platform_extension(platform: &str) -> &'static str {
     match platform {
         "deb" => "deb",
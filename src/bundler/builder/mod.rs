@@ -47,13 +47,16 @@
 //!
 //! - [`checksum`] - SHA256 checksum calculation for artifacts
 //! - [`orchestrator`] - Main [`Bundler`] struct and bundling operations
+//! - [`platform_bundler`] - [`PlatformBundler`] trait for custom formats
 //! - [`signing`] - Code signing setup (macOS keychain management)
 //! - [`tool_detection`] - External tool availability checking
 
 mod checksum;
 mod orchestrator;
+mod platform_bundler;
 mod signing;
 mod tool_detection;
 
 // Re-export the main Bundler type for backwards compatibility
 pub use orchestrator::Bundler;
+pub use platform_bundler::PlatformBundler;
@@ -5,10 +5,20 @@
 
 use crate::{
     bail,
-    bundler::{BundledArtifact, PackageType, Result, Settings, error::ErrorExt},
+    bundler::{
+        BundleEvent, BundleStage, BundledArtifact, PackageType, ProgressReporter, Result, Settings,
+        error::ErrorExt,
+        hooks::{Hook, run_for_package},
+    },
 };
 
-use super::{checksum::calculate_sha256, tool_detection::HAS_MAKENSIS};
+use super::{
+    checksum::calculate_sha256,
+    platform_bundler::PlatformBundler,
+    tool_detection::{HAS_MAKENSIS, tool_versions},
+};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Main bundler orchestrator.
 ///
@@ -21,6 +31,8 @@ use super::{checksum::calculate_sha256, tool_detection::HAS_MAKENSIS};
 /// - **macOS**: Creates .app bundles and .dmg disk images
 /// - **Windows**: Creates .msi and .exe (NSIS) installers
 ///
+/// Further formats can be added with [`register`](Self::register).
+///
 /// # Examples
 ///
 /// ```no_run
@@ -43,6 +55,7 @@ use super::{checksum::calculate_sha256, tool_detection::HAS_MAKENSIS};
 /// ```
 pub struct Bundler {
     settings: Settings,
+    custom: Vec<Arc<dyn PlatformBundler>>,
     #[cfg(target_os = "macos")]
     _temp_keychain: Option<kodegen_bundler_sign::macos::TempKeychain>,
 }
@@ -51,6 +64,10 @@ impl std::fmt::Debug for Bundler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut debug_struct = f.debug_struct("Bundler");
         debug_struct.field("settings", &self.settings);
+        debug_struct.field(
+            "custom",
+            &self.custom.iter().map(|bundler| bundler.name()).collect::<Vec<_>>(),
+        );
         #[cfg(target_os = "macos")]
         debug_struct.field(
             "_temp_keychain",
@@ -63,6 +80,9 @@ impl std::fmt::Debug for Bundler {
 impl Bundler {
     /// Creates a new bundler with the given settings.
     ///
+    /// Configured icons are validated and SVG icons rasterized into PNGs
+    /// here, before any package is built.
+    ///
     /// # Arguments
     ///
     /// * `settings` - Bundler configuration from `SettingsBuilder`
@@ -77,17 +97,69 @@ impl Bundler {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn new(settings: Settings) -> Result<Self> {
+    pub async fn new(mut settings: Settings) -> Result<Self> {
+        crate::bundler::resources::icons::validate_icons(settings.bundle_settings())?;
+        crate::bundler::resources::svg::rasterize_icons(&mut settings).await?;
+
         #[cfg(target_os = "macos")]
         let _temp_keychain = super::signing::setup_macos_signing().await?;
 
         Ok(Self {
             settings,
+            custom: Vec::new(),
             #[cfg(target_os = "macos")]
             _temp_keychain,
         })
     }
 
+    /// Registers a callback receiving [`BundleEvent`]s while bundling.
+    ///
+    /// Platform bundlers report their stages (building, copying, signing,
+    /// compressing) with percentages where the amount of work is known.
+    /// The callback runs on the bundling task and should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use kodegen_bundler_release::bundler::{Bundler, Settings};
+    ///
+    /// # async fn example(settings: Settings) -> kodegen_bundler_release::bundler::Result<()> {
+    /// let bundler = Bundler::new(settings).await?.on_progress(|event| {
+    ///     println!("{:?} {:?} {:?}% {}", event.package_type, event.stage, event.percent, event.message);
+    /// });
+    /// let artifacts = bundler.bundle().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_progress(mut self, callback: impl Fn(&BundleEvent) + Send + Sync + 'static) -> Self {
+        self.settings.set_progress(ProgressReporter::new(callback));
+        self
+    }
+
+    /// Registers a [`PlatformBundler`] for [`PackageType::Custom`] packages.
+    ///
+    /// A later registration with the same name replaces the earlier one.
+    /// Registered formats supported on the host are part of the defaults of
+    /// [`bundle`](Self::bundle).
+    pub fn register(mut self, bundler: impl PlatformBundler + 'static) -> Self {
+        self.custom.retain(|registered| registered.name() != bundler.name());
+        self.custom.push(Arc::new(bundler));
+        self
+    }
+
+    /// Returns the bundler together with a stream of its [`BundleEvent`]s.
+    ///
+    /// Alternative to [`on_progress`](Self::on_progress) for consumers that
+    /// process events on another task. Events are buffered without bound.
+    pub fn progress_channel(self) -> (Self, tokio::sync::mpsc::UnboundedReceiver<BundleEvent>) {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let bundler = self.on_progress(move |event| {
+            // The consumer may stop listening at any time
+            let _ = sender.send(event.clone());
+        });
+        (bundler, receiver)
+    }
+
     /// Executes bundling operations for default platform types.
     ///
     /// Automatically determines which package types to create based on:
@@ -164,18 +236,43 @@ impl Bundler {
     ///
     /// Attempting to create a package type unsupported on the current platform
     /// will return an error.
+    #[tracing::instrument(name = "bundle", skip_all, fields(types = ?types))]
     pub async fn bundle_types(&self, types: &[PackageType]) -> Result<Vec<BundledArtifact>> {
         let mut artifacts = Vec::new();
+        let git_commit = self.source_commit().await;
 
+        let progress = self.settings.progress();
         for package_type in types {
+            progress.report(
+                *package_type,
+                BundleStage::Preparing,
+                Some(0),
+                format!("Bundling {}", package_type),
+            );
+            run_for_package(&self.settings, Hook::PreBundle, *package_type, &[], &[]).await?;
             let paths = match package_type {
+                PackageType::Custom(name) => self.bundle_custom(name)?,
                 #[cfg(target_os = "linux")]
                 PackageType::Deb => {
-                    crate::bundler::platform::linux::debian::bundle_project(&self.settings).await?
+                    use crate::bundler::platform::linux::lint::{Linter, lint_package};
+                    let paths =
+                        crate::bundler::platform::linux::debian::bundle_project(&self.settings)
+                            .await?;
+                    for path in &paths {
+                        lint_package(Linter::Lintian, path, self.settings.lint()).await?;
+                    }
+                    paths
                 }
                 #[cfg(target_os = "linux")]
                 PackageType::Rpm => {
-                    crate::bundler::platform::linux::rpm::bundle_project(&self.settings).await?
+                    use crate::bundler::platform::linux::lint::{Linter, lint_package};
+                    let paths =
+                        crate::bundler::platform::linux::rpm::bundle_project(&self.settings)
+                            .await?;
+                    for path in &paths {
+                        lint_package(Linter::Rpmlint, path, self.settings.lint()).await?;
+                    }
+                    paths
                 }
                 #[cfg(target_os = "linux")]
                 PackageType::AppImage => {
@@ -198,6 +295,11 @@ impl Bundler {
                 PackageType::Exe => {
                     crate::bundler::platform::windows::nsis::bundle_project(&self.settings).await?
                 }
+                #[cfg(target_os = "linux")]
+                PackageType::Portable => {
+                    crate::bundler::platform::windows::portable::bundle_project(&self.settings)
+                        .await?
+                }
                 #[cfg(not(any(target_os = "linux", target_os = "macos")))]
                 _ => {
                     bail!(
@@ -232,17 +334,79 @@ impl Bundler {
                 );
             };
 
+            let artifact_paths = paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            run_for_package(
+                &self.settings,
+                Hook::PostBundle,
+                *package_type,
+                &paths,
+                &[
+                    ("KODEGEN_ARTIFACT_PATHS", artifact_paths),
+                    ("KODEGEN_ARTIFACT_SIZE", size.to_string()),
+                    ("KODEGEN_ARTIFACT_SHA256", checksum.clone()),
+                ],
+            )
+            .await?;
+
+            progress.report(
+                *package_type,
+                BundleStage::Finished,
+                Some(100),
+                format!("Created {}", paths[0].display()),
+            );
             artifacts.push(BundledArtifact {
                 package_type: *package_type,
                 paths,
                 size,
                 checksum,
+                target: self.settings.target().to_string(),
+                identifier: self.settings.bundle_settings().identifier.clone(),
+                version: self.settings.version_string().to_string(),
+                built_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                git_commit: git_commit.clone(),
+                tool_versions: tool_versions(*package_type, &self.settings).await,
             });
         }
 
         Ok(artifacts)
     }
 
+    /// Builds a [`PackageType::Custom`] package with its registered bundler.
+    fn bundle_custom(&self, name: &str) -> Result<Vec<PathBuf>> {
+        let Some(bundler) = self.custom.iter().find(|bundler| bundler.name() == name) else {
+            bail!("No bundler registered for package type {}", name);
+        };
+        if !bundler.supports_host() {
+            bail!(
+                "Package type {} cannot be built on {} (supported: {})",
+                name,
+                std::env::consts::OS,
+                bundler.supported_os().join(", ")
+            );
+        }
+        bundler.check(&self.settings)?;
+        bundler.bundle(&self.settings)
+    }
+
+    /// Commit checked out in the source directory, if it is a git repository.
+    async fn source_commit(&self) -> Option<String> {
+        let output = tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(self.settings.source_directory()?)
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .await
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// Returns a reference to the bundler settings.
     pub fn settings(&self) -> &Settings {
         &self.settings
@@ -259,6 +423,18 @@ impl Bundler {
         }
 
         // Otherwise determine based on platform + available toolchains
+        let mut types = self.default_builtin_types();
+        types.extend(
+            self.custom
+                .iter()
+                .filter(|bundler| bundler.supports_host())
+                .map(|bundler| PackageType::Custom(bundler.name())),
+        );
+        types
+    }
+
+    /// Built-in package types for the host platform and its toolchains.
+    fn default_builtin_types(&self) -> Vec<PackageType> {
         if cfg!(target_os = "linux") {
             let mut types = vec![
                 PackageType::Deb,
@@ -268,10 +444,10 @@ impl Bundler {
 
             // Add Windows cross-compilation if makensis available
             if *HAS_MAKENSIS {
-                log::debug!("makensis detected - enabling Windows NSIS cross-compilation");
+                tracing::debug!("makensis detected - enabling Windows NSIS cross-compilation");
                 types.push(PackageType::Exe);
             } else {
-                log::debug!("makensis not available - skipping NSIS installer");
+                tracing::debug!("makensis not available - skipping NSIS installer");
             }
 
             types
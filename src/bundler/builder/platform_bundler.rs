@@ -0,0 +1,138 @@
+//! Extension point for package formats outside this crate.
+//!
+//! A [`PlatformBundler`] registered with [`Bundler::register`] builds
+//! [`PackageType::Custom`] packages alongside the built-in formats, with the
+//! same progress reporting, checksums and artifact metadata.
+//!
+//! [`Bundler::register`]: super::Bundler::register
+
+use crate::bundler::{Result, Settings};
+use std::path::PathBuf;
+
+/// A package format provided by a downstream crate.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kodegen_bundler_release::bundler::{Bundler, PackageType, PlatformBundler, Result, Settings};
+/// use std::path::PathBuf;
+///
+/// struct EnterpriseInstaller;
+///
+/// impl PlatformBundler for EnterpriseInstaller {
+///     fn name(&self) -> &'static str {
+///         "enterprise"
+///     }
+///
+///     fn supported_os(&self) -> &'static [&'static str] {
+///         &["linux"]
+///     }
+///
+///     fn bundle(&self, settings: &Settings) -> Result<Vec<PathBuf>> {
+///         let path = settings.project_out_directory().join("bundle/enterprise/app.pkg");
+///         // ... write the installer ...
+///         Ok(vec![path])
+///     }
+/// }
+///
+/// # async fn example(settings: Settings) -> Result<()> {
+/// let bundler = Bundler::new(settings).await?.register(EnterpriseInstaller);
+/// let artifacts = bundler.bundle_types(&[PackageType::Custom("enterprise")]).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait PlatformBundler: Send + Sync {
+    /// Name of the format, used as its [`PackageType::Custom`] value.
+    ///
+    /// [`PackageType::Custom`]: crate::bundler::PackageType::Custom
+    fn name(&self) -> &'static str;
+
+    /// Host operating systems (as in [`std::env::consts::OS`]) the format
+    /// can be built on; empty for any.
+    fn supported_os(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Checks that the tools the format needs are available.
+    ///
+    /// Runs before [`bundle`](Self::bundle); the default accepts any host.
+    fn check(&self, _settings: &Settings) -> Result<()> {
+        Ok(())
+    }
+
+    /// Creates the package and returns its files, main artifact first.
+    fn bundle(&self, settings: &Settings) -> Result<Vec<PathBuf>>;
+
+    /// Whether the format can be built on the current host.
+    fn supports_host(&self) -> bool {
+        let supported = self.supported_os();
+        supported.is_empty() || supported.contains(&std::env::consts::OS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::{Bundler, PackageSettings, PackageType, SettingsBuilder};
+
+    struct Archive;
+
+    impl PlatformBundler for Archive {
+        fn name(&self) -> &'static str {
+            "archive"
+        }
+
+        fn bundle(&self, settings: &Settings) -> Result<Vec<PathBuf>> {
+            let path = settings.project_out_directory().join("app.archive");
+            std::fs::write(&path, b"archive")?;
+            Ok(vec![path])
+        }
+    }
+
+    struct Unsupported;
+
+    impl PlatformBundler for Unsupported {
+        fn name(&self) -> &'static str {
+            "unsupported"
+        }
+
+        fn supported_os(&self) -> &'static [&'static str] {
+            &["plan9"]
+        }
+
+        fn bundle(&self, _settings: &Settings) -> Result<Vec<PathBuf>> {
+            unreachable!("not supported on this host")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_bundler() {
+        let out = tempfile::tempdir().unwrap();
+        let settings = SettingsBuilder::new()
+            .project_out_directory(out.path())
+            .package_settings(PackageSettings {
+                product_name: "app".into(),
+                version: "1.0.0".into(),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let bundler = Bundler::new(settings)
+            .await
+            .unwrap()
+            .register(Archive)
+            .register(Unsupported);
+
+        let artifacts = bundler
+            .bundle_types(&[PackageType::Custom("archive")])
+            .await
+            .unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].package_type, PackageType::Custom("archive"));
+        assert_eq!(artifacts[0].size, 7);
+
+        for name in ["unsupported", "missing"] {
+            assert!(bundler.bundle_types(&[PackageType::Custom(name)]).await.is_err());
+        }
+    }
+}
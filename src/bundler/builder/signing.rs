@@ -34,7 +34,7 @@ pub async fn setup_macos_signing() -> Result<Option<kodegen_bundler_sign::macos:
                 ))
             })?;
 
-        log::info!("Importing certificate from APPLE_CERTIFICATE environment variable");
+        tracing::info!("Importing certificate from APPLE_CERTIFICATE environment variable");
         let keychain = kodegen_bundler_sign::macos::TempKeychain::from_certificate_bytes(
             &cert_bytes,
             &password,
@@ -44,7 +44,7 @@ pub async fn setup_macos_signing() -> Result<Option<kodegen_bundler_sign::macos:
             crate::bundler::Error::GenericError(format!("Failed to import certificate: {}", e))
         })?;
 
-        log::info!("✓ Certificate imported to temporary keychain");
+        tracing::info!("✓ Certificate imported to temporary keychain");
         return Ok(Some(keychain));
     }
 
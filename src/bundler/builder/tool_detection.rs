@@ -3,6 +3,12 @@
 //! This module provides compile-time and runtime detection of external tools
 //! required for various bundling operations (e.g., makensis for Windows NSIS installers).
 
+use crate::bundler::{
+    PackageType, Settings,
+    settings::AppImageBackend,
+    utils::{tool_pins, tools},
+};
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 /// Check if makensis is available for NSIS installer creation.
@@ -10,16 +16,16 @@ use std::sync::LazyLock;
 /// Cached result to avoid repeated subprocess calls during bundling.
 pub static HAS_MAKENSIS: LazyLock<bool> = LazyLock::new(|| match which::which("makensis") {
     Ok(path) => {
-        log::debug!("Found makensis at: {}", path.display());
+        tracing::debug!("Found makensis at: {}", path.display());
 
         match std::process::Command::new(&path).arg("-VERSION").output() {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout);
-                log::info!("✓ makensis available: {}", version.trim());
+                tracing::info!("✓ makensis available: {}", version.trim());
                 true
             }
             Ok(output) => {
-                log::warn!(
+                tracing::warn!(
                     "makensis found at {} but -VERSION check failed (exit code: {:?}). \
                          NSIS installers will be skipped. \
                          Stderr: {}",
@@ -30,7 +36,7 @@ pub static HAS_MAKENSIS: LazyLock<bool> = LazyLock::new(|| match which::which("m
                 false
             }
             Err(e) => {
-                log::warn!(
+                tracing::warn!(
                     "makensis found at {} but failed to execute: {}. \
                          NSIS installers will be skipped. \
                          Check file permissions.",
@@ -42,10 +48,50 @@ pub static HAS_MAKENSIS: LazyLock<bool> = LazyLock::new(|| match which::which("m
         }
     }
     Err(e) => {
-        log::debug!(
+        tracing::debug!(
             "makensis not found in PATH: {}. NSIS installers will be skipped.",
             e
         );
         false
     }
 });
+
+/// Versions of the tools involved in bundling `package_type`, by tool name.
+///
+/// Always includes the bundler itself and, when on PATH, rustc. AppImage
+/// tools are recorded with their configured or built-in pinned release.
+pub async fn tool_versions(package_type: PackageType, settings: &Settings) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::from([(
+        env!("CARGO_PKG_NAME").to_string(),
+        env!("CARGO_PKG_VERSION").to_string(),
+    )]);
+    if let Some(rustc) = tools::find_system_tool(&["rustc"], "--version").await {
+        versions.insert("rustc".to_string(), rustc.version);
+    }
+
+    match package_type {
+        PackageType::Exe => {
+            if let Some(makensis) = tools::find_system_tool(&["makensis"], "-VERSION").await {
+                versions.insert("makensis".to_string(), makensis.version);
+            }
+        }
+        PackageType::AppImage => {
+            let appimage = &settings.bundle_settings().appimage;
+            let release = |pinned: &Option<String>, default: &str| pinned.clone().unwrap_or_else(|| default.to_string());
+            if appimage.backend == AppImageBackend::Linuxdeploy {
+                versions.insert(
+                    "linuxdeploy".to_string(),
+                    release(&appimage.linuxdeploy_version, tool_pins::LINUXDEPLOY_RELEASE),
+                );
+            }
+            if appimage.backend == AppImageBackend::Appimagetool || appimage.runtime_file.is_some() {
+                versions.insert(
+                    "appimagetool".to_string(),
+                    release(&appimage.appimagetool_version, tool_pins::APPIMAGETOOL_RELEASE),
+                );
+            }
+        }
+        _ => {}
+    }
+    versions
+}
@@ -0,0 +1,141 @@
+//! User hook commands around bundling stages.
+//!
+//! Hooks are configured in `[package.metadata.bundle.hooks]` (see
+//! [`HookSettings`]) and let projects add steps such as extra validation or
+//! telemetry uploads without changing the bundler.
+
+use super::settings::HookSettings;
+use super::{PackageType, Result, Settings};
+use std::path::{Path, PathBuf};
+
+/// A bundling stage a hook can run at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Before the binary is compiled
+    PreBuild,
+    /// After the binary is compiled
+    PostBuild,
+    /// Before each package is created
+    PreBundle,
+    /// After each package is created
+    PostBundle,
+    /// After an artifact is signed
+    PostSign,
+}
+
+impl Hook {
+    /// Setting name of the hook, also passed as `KODEGEN_HOOK`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::PreBuild => "pre_build",
+            Hook::PostBuild => "post_build",
+            Hook::PreBundle => "pre_bundle",
+            Hook::PostBundle => "post_bundle",
+            Hook::PostSign => "post_sign",
+        }
+    }
+
+    fn command<'a>(&self, hooks: &'a HookSettings) -> Option<&'a str> {
+        match self {
+            Hook::PreBuild => hooks.pre_build.as_deref(),
+            Hook::PostBuild => hooks.post_build.as_deref(),
+            Hook::PreBundle => hooks.pre_bundle.as_deref(),
+            Hook::PostBundle => hooks.post_bundle.as_deref(),
+            Hook::PostSign => hooks.post_sign.as_deref(),
+        }
+    }
+}
+
+/// Runs the command configured for `hook`, if any, in `dir` with `env` added
+/// to the environment
+///
+/// The command's output goes to stderr, keeping stdout for artifact paths.
+pub async fn run(hooks: &HookSettings, hook: Hook, dir: &Path, env: &[(&str, String)]) -> Result<()> {
+    let Some(command) = hook.command(hooks) else {
+        return Ok(());
+    };
+    tracing::info!("Running {} hook: {}", hook.name(), command);
+
+    let mut process = if cfg!(windows) {
+        let mut process = tokio::process::Command::new("cmd");
+        process.arg("/C");
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c");
+        process
+    };
+    let status = process
+        .arg(command)
+        .current_dir(dir)
+        .env("KODEGEN_HOOK", hook.name())
+        .envs(env.iter().map(|(name, value)| (name, value)))
+        .stdout(std::io::stderr())
+        .status()
+        .await
+        .map_err(|e| {
+            super::Error::GenericError(format!("Failed to run {} hook `{}`: {}", hook.name(), command, e))
+        })?;
+    if !status.success() {
+        return Err(super::Error::GenericError(format!(
+            "{} hook `{}` failed with {}",
+            hook.name(),
+            command,
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Runs a packaging `hook` for the `artifacts` of `package_type`
+///
+/// Adds the product, package type and main artifact to `env`, and runs in
+/// the source directory of `settings`.
+pub async fn run_for_package(
+    settings: &Settings,
+    hook: Hook,
+    package_type: PackageType,
+    artifacts: &[PathBuf],
+    env: &[(&str, String)],
+) -> Result<()> {
+    let mut vars = vec![
+        ("KODEGEN_PRODUCT_NAME", settings.product_name().to_string()),
+        ("KODEGEN_VERSION", settings.version_string().to_string()),
+        ("KODEGEN_PACKAGE_TYPE", package_type.short_name().to_string()),
+    ];
+    if let Some(main) = artifacts.first() {
+        vars.push(("KODEGEN_ARTIFACT_PATH", main.display().to_string()));
+    }
+    vars.extend(env.iter().cloned());
+
+    let dir = settings
+        .source_directory()
+        .unwrap_or_else(|| settings.project_out_directory());
+    run(&settings.bundle_settings().hooks, hook, dir, &vars).await
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = HookSettings {
+            post_bundle: Some("echo \"$KODEGEN_HOOK $KODEGEN_ARTIFACT_PATH\" > hook.txt".into()),
+            post_sign: Some("exit 3".into()),
+            ..Default::default()
+        };
+
+        // Unconfigured hooks do nothing
+        run(&hooks, Hook::PreBuild, dir.path(), &[]).await.unwrap();
+
+        let env = [("KODEGEN_ARTIFACT_PATH", "app.deb".to_string())];
+        run(&hooks, Hook::PostBundle, dir.path(), &env).await.unwrap();
+        let written = std::fs::read_to_string(dir.path().join("hook.txt")).unwrap();
+        assert_eq!(written, "post_bundle app.deb\n");
+
+        let err = run(&hooks, Hook::PostSign, dir.path(), &[]).await.unwrap_err();
+        assert!(err.to_string().contains("post_sign hook `exit 3` failed"));
+    }
+}
@@ -32,13 +32,15 @@
 //! ```no_run
 //! use kodegen_bundler_release::bundler::{Bundler, SettingsBuilder};
 //!
+//! # async fn example() -> kodegen_bundler_release::bundler::Result<()> {
 //! let settings = SettingsBuilder::new()
 //!     .project_out_directory("target/release")
 //!     .build()?;
 //!
-//! let bundler = Bundler::new(settings)?;
-//! let artifacts = bundler.bundle()?;
-//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! let bundler = Bundler::new(settings).await?;
+//! let artifacts = bundler.bundle().await?;
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! # Code Signing
@@ -52,25 +54,29 @@
 
 mod builder;
 mod error;
+pub mod hooks;
 mod patch;
 pub mod platform;
-mod resources;
+mod progress;
+pub mod resources;
 mod settings;
-mod utils;
+pub mod utils;
 
 // Public re-exports
-pub use builder::Bundler;
+pub use builder::{Bundler, PlatformBundler};
 pub use error::{Error, Result};
-pub use platform::PackageType;
+pub use platform::{PackageType, ParsePackageTypeError};
+pub use progress::{BundleEvent, BundleStage, ProgressReporter};
 pub use settings::{
-    BundleBinary, BundleSettings, PackageSettings,
-    Settings, SettingsBuilder,
+    Arch, BundleBinary, BundleSettings, PackageSettings,
+    LintMode, Settings, SettingsBuilder, SkipPhases,
 };
 
 /// A bundled artifact result containing metadata about created installers.
 ///
 /// This struct is returned after successful bundling operations and contains
-/// information about the generated installer packages.
+/// information about the generated installer packages. It serializes to the
+/// JSON written by `--artifact-json`.
 ///
 /// # Fields
 ///
@@ -78,19 +84,21 @@ pub use settings::{
 /// - `paths`: All files created as part of this bundle (main package + metadata files)
 /// - `size`: Total size of the main artifact in bytes
 /// - `checksum`: SHA-256 checksum for integrity verification
+/// - `target`, `identifier`, `version`: What the package contains
+/// - `built_at`, `git_commit`, `tool_versions`: How it was built
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use kodegen_bundler_release::bundler::{Bundler, Settings, SettingsBuilder, PackageSettings};
 ///
-/// # fn example() -> kodegen_bundler_release::bundler::Result<()> {
+/// # async fn example() -> kodegen_bundler_release::bundler::Result<()> {
 /// # let settings = SettingsBuilder::new()
 /// #     .project_out_directory("target/release")
 /// #     .package_settings(PackageSettings::default())
 /// #     .build()?;
-/// let bundler = Bundler::new(settings)?;
-/// let artifacts = bundler.bundle()?;
+/// let bundler = Bundler::new(settings).await?;
+/// let artifacts = bundler.bundle().await?;
 ///
 /// for artifact in artifacts {
 ///     println!("Created {}: {} bytes",
@@ -102,7 +110,7 @@ pub use settings::{
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 #[allow(dead_code)] // Public API - Fields preserved for external consumers
 pub struct BundledArtifact {
     /// The package type that was created (e.g., Deb, Rpm, Dmg, Msi).
@@ -121,4 +129,22 @@ pub struct BundledArtifact {
     ///
     /// This can be published alongside the artifact for users to verify downloads.
     pub checksum: String,
+
+    /// Target triple (or architecture) of the bundled binaries.
+    pub target: String,
+
+    /// Bundle identifier from the bundle settings, if set.
+    pub identifier: Option<String>,
+
+    /// Version of the packaged application.
+    pub version: String,
+
+    /// When the package was created, in RFC 3339 format (UTC).
+    pub built_at: String,
+
+    /// Commit of the source directory, if it is a git checkout.
+    pub git_commit: Option<String>,
+
+    /// Versions of the bundler, compiler and packaging tools, by tool name.
+    pub tool_versions: std::collections::BTreeMap<String, String>,
 }
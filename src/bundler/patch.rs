@@ -50,7 +50,7 @@ use std::path::Path;
 /// If marker is not found, this logs a debug message and returns Ok(()).
 /// This is intentional - the feature is optional.
 pub fn patch_binary(binary_path: &Path, package_type: &PackageType) -> Result<()> {
-    log::debug!(
+    tracing::debug!(
         "Attempting to patch binary {:?} with package type: {}",
         binary_path,
         package_type.short_name()
@@ -63,7 +63,7 @@ pub fn patch_binary(binary_path: &Path, package_type: &PackageType) -> Result<()
 
     // Check minimum size for format detection
     if data.len() < 16 {
-        log::warn!(
+        tracing::warn!(
             "Binary {:?} is too small (< 16 bytes), skipping patch",
             binary_path
         );
@@ -78,38 +78,38 @@ pub fn patch_binary(binary_path: &Path, package_type: &PackageType) -> Result<()
 
     match goblin::peek_bytes(hint_bytes) {
         Ok(goblin::Hint::Elf(_)) => {
-            log::debug!("Detected ELF binary format");
+            tracing::debug!("Detected ELF binary format");
             patch_binary_data(&mut data, package_type, "ELF")?;
         }
         Ok(goblin::Hint::Mach(_)) | Ok(goblin::Hint::MachFat(_)) => {
-            log::debug!("Detected Mach-O binary format");
+            tracing::debug!("Detected Mach-O binary format");
             patch_binary_data(&mut data, package_type, "Mach-O")?;
         }
         Ok(goblin::Hint::PE) => {
-            log::debug!("Detected PE binary format");
+            tracing::debug!("Detected PE binary format");
             patch_binary_data(&mut data, package_type, "PE")?;
         }
         Ok(goblin::Hint::COFF) => {
-            log::warn!("Binary is COFF object file, not executable. Skipping patch.");
+            tracing::warn!("Binary is COFF object file, not executable. Skipping patch.");
             return Ok(());
         }
         Ok(goblin::Hint::Archive) => {
-            log::warn!("Binary is archive file, not executable. Skipping patch.");
+            tracing::warn!("Binary is archive file, not executable. Skipping patch.");
             return Ok(());
         }
         Ok(goblin::Hint::Unknown(magic)) => {
-            log::warn!(
+            tracing::warn!(
                 "Unknown binary format (magic: {:#x}), skipping patch",
                 magic
             );
             return Ok(());
         }
         Ok(_) => {
-            log::warn!("Unsupported binary format variant. Skipping patch.");
+            tracing::warn!("Unsupported binary format variant. Skipping patch.");
             return Ok(());
         }
         Err(e) => {
-            log::warn!("Failed to detect binary format: {}. Skipping patch.", e);
+            tracing::warn!("Failed to detect binary format: {}. Skipping patch.", e);
             return Ok(());
         }
     }
@@ -117,7 +117,7 @@ pub fn patch_binary(binary_path: &Path, package_type: &PackageType) -> Result<()
     // Write patched binary back to disk
     std::fs::write(binary_path, data).fs_context("writing patched binary", binary_path)?;
 
-    log::info!(
+    tracing::info!(
         "Successfully patched binary {:?} with package type: {}",
         binary_path,
         package_type.short_name()
@@ -161,21 +161,21 @@ fn patch_binary_data(data: &mut [u8], package_type: &PackageType, format_name: &
             data[write_pos..write_pos + package_type_bytes.len()]
                 .copy_from_slice(package_type_bytes);
 
-            log::debug!(
+            tracing::debug!(
                 "Patched {} binary: wrote '{}' at offset {}",
                 format_name,
                 package_type.short_name(),
                 write_pos
             );
         } else {
-            log::warn!(
+            tracing::warn!(
                 "Marker found but insufficient space to write package type (need {} bytes at offset {})",
                 package_type_bytes.len(),
                 write_pos
             );
         }
     } else {
-        log::debug!(
+        tracing::debug!(
             "Marker not found in {} binary. Skipping patch (this is optional).",
             format_name
         );
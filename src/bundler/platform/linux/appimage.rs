@@ -4,16 +4,28 @@
 use crate::{
     bail,
     bundler::{
+        BundleStage, PackageType,
         error::{Context, ErrorExt, Result},
-        settings::Settings,
-        utils::http,
+        platform::linux::{freedesktop, shared_libs},
+        resources::{files, locales},
+        settings::{AppImageBackend, Settings},
+        utils::{http, tool_pins, tools},
     },
 };
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tokio::io::AsyncWriteExt;
 
-const LINUXDEPLOY_BASE_URL: &str =
-    "https://github.com/linuxdeploy/linuxdeploy/releases/download/continuous";
+const LINUXDEPLOY_RELEASES_URL: &str =
+    "https://github.com/linuxdeploy/linuxdeploy/releases/download";
+
+const APPIMAGETOOL_RELEASES_URL: &str =
+    "https://github.com/AppImage/appimagetool/releases/download";
+
+const GSTREAMER_PLUGIN_BASE_URL: &str =
+    "https://raw.githubusercontent.com/linuxdeploy/linuxdeploy-plugin-gstreamer";
+
+/// File name linuxdeploy looks the GStreamer plugin up by
+const GSTREAMER_PLUGIN: &str = "linuxdeploy-plugin-gstreamer.sh";
 
 /// Bundle project as AppImage.
 ///
@@ -21,11 +33,16 @@ const LINUXDEPLOY_BASE_URL: &str =
 ///
 /// # Process
 ///
-/// 1. Downloads linuxdeploy tool (cached in .tools/)
+/// 1. Downloads linuxdeploy and/or appimagetool (cached per pinned version
+///    in `~/.cache/kodegen-bundler/tools`, verified against the pinned or
+///    `tool_sha256` checksums), falling back to system installs when the
+///    download fails
 /// 2. Creates AppDir structure (usr/bin, usr/lib)
-/// 3. Copies binaries and resources
+/// 3. Copies binaries and resources, bundling non-system shared libraries
+///    into usr/lib
 /// 4. Generates .desktop file
-/// 5. Invokes linuxdeploy to create AppImage
+/// 5. Invokes linuxdeploy and/or appimagetool (with an optional custom
+///    runtime file) to create AppImage
 ///
 /// # Returns
 ///
@@ -42,21 +59,73 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         ),
     };
 
-    log::info!("Building AppImage for {}", settings.product_name());
-    log::debug!("Using architecture: {}", arch);
+    tracing::info!("Building AppImage for {}", settings.product_name());
+    tracing::debug!("Using architecture: {}", arch);
 
     // 2. Setup directories
     let output_dir = settings.project_out_directory().join("bundle/appimage");
-    let tools_dir = output_dir.join(".tools");
+    // Shared across projects; the output directory only without a cache directory
+    let tools_dir = tools::cache_dir().unwrap_or_else(|| output_dir.join(".tools"));
 
     tokio::fs::create_dir_all(&tools_dir)
         .await
         .fs_context("creating tools directory", &tools_dir)?;
 
-    // 3. Download linuxdeploy
-    let linuxdeploy = download_linuxdeploy(&tools_dir, arch)
-        .await
-        .context("failed to download linuxdeploy tool")?;
+    let progress = settings.progress();
+    progress.report(
+        PackageType::AppImage,
+        BundleStage::Preparing,
+        Some(5),
+        "Resolving AppImage tools",
+    );
+
+    // 3. Download the tools (or use system ones if the download is blocked).
+    // appimagetool packs the AppDir for its own backend and whenever a
+    // runtime file is pinned; linuxdeploy then only deploys the AppDir.
+    let appimage_settings = &settings.bundle_settings().appimage;
+    let linuxdeploy = match appimage_settings.backend {
+        AppImageBackend::Linuxdeploy => Some(
+            resolve_tool(
+                &tools_dir,
+                "linuxdeploy",
+                LINUXDEPLOY_RELEASES_URL,
+                appimage_settings
+                    .linuxdeploy_version
+                    .as_deref()
+                    .unwrap_or(tool_pins::LINUXDEPLOY_RELEASE),
+                arch,
+                &appimage_settings.tool_sha256,
+            )
+            .await?,
+        ),
+        AppImageBackend::Appimagetool => None,
+    };
+    let gstreamer_plugin_dir = if appimage_settings.bundle_media_framework {
+        if linuxdeploy.is_none() {
+            bail!("bundle_media_framework requires the linuxdeploy AppImage backend");
+        }
+        Some(download_gstreamer_plugin(&tools_dir, appimage_settings).await?)
+    } else {
+        None
+    };
+    let appimagetool = if linuxdeploy.is_none() || appimage_settings.runtime_file.is_some() {
+        Some(
+            resolve_tool(
+                &tools_dir,
+                "appimagetool",
+                APPIMAGETOOL_RELEASES_URL,
+                appimage_settings
+                    .appimagetool_version
+                    .as_deref()
+                    .unwrap_or(tool_pins::APPIMAGETOOL_RELEASE),
+                arch,
+                &appimage_settings.tool_sha256,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
 
     // 4. Create AppDir structure
     let app_dir = output_dir.join(format!("{}.AppDir", settings.product_name()));
@@ -80,7 +149,16 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
     }
 
     // 5. Copy binaries
-    for binary in settings.binaries() {
+    let mut bundled_binaries = Vec::new();
+    let binary_count = settings.binaries().len();
+    for (index, binary) in settings.binaries().iter().enumerate() {
+        progress.report_items(
+            PackageType::AppImage,
+            BundleStage::Copying,
+            (20, 40),
+            (index, binary_count),
+            format!("Copying {}", binary.name()),
+        );
         let src = settings.binary_path(binary);
         let dst = bin_dir.join(binary.name());
 
@@ -94,9 +172,31 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
             use std::os::unix::fs::PermissionsExt;
             tokio::fs::set_permissions(&dst, std::fs::Permissions::from_mode(0o755)).await?;
         }
+        bundled_binaries.push(dst);
+    }
+
+    // Bundle non-system shared libraries (libssl etc.) into usr/lib
+    if settings.skip_phases().dylib_bundling {
+        tracing::info!("Skipping shared library bundling (--skip-dylib-bundling)");
+    } else {
+        shared_libs::bundle_shared_libraries(&bundled_binaries, &lib_dir, "$ORIGIN/../lib").await?;
     }
 
+    if appimage_settings.bundle_xdg_open {
+        bundle_xdg_open(&bin_dir).await?;
+    }
+
+    // Resources in usr/share/<name>, as in the deb and rpm packages
+    files::copy_resources(settings, &app_dir.join(files::linux_resource_dir(settings))).await?;
+    locales::copy_catalogs(settings, &app_dir.join(locales::LINUX_LOCALE_DIR)).await?;
+
     // 6. Create desktop file
+    progress.report(
+        PackageType::AppImage,
+        BundleStage::Building,
+        Some(50),
+        "Generating desktop entry and icons",
+    );
     create_desktop_file(settings, &app_dir).await?;
 
     // 7. Copy icon (if available)
@@ -122,7 +222,13 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         }
     }
 
-    // 8. Invoke linuxdeploy
+    // 8. Invoke linuxdeploy and/or appimagetool
+    progress.report(
+        PackageType::AppImage,
+        BundleStage::Compressing,
+        Some(60),
+        "Packing AppImage",
+    );
     let appimage_path = output_dir.join(format!(
         "{}-{}-{}.AppImage",
         settings.product_name(),
@@ -134,19 +240,88 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         .to_str()
         .context("AppDir path contains invalid UTF-8")?;
 
-    // Execute extracted linuxdeploy binary (no FUSE required)
-    let status = tokio::process::Command::new(&linuxdeploy)
-        .env("OUTPUT", &appimage_path)
-        .env("ARCH", arch)
-        .args(["--appdir", app_dir_str, "--output", "appimage"])
-        .status()
-        .await
-        .map_err(|e| {
+    let update_information = settings
+        .bundle_settings()
+        .appimage
+        .update_information
+        .as_deref()
+        .map(|info| update_information(info, arch))
+        .transpose()?;
+
+    if let Some(info) = &update_information {
+        tracing::info!("Embedding update information: {}", info);
+    }
+
+    if let Some(linuxdeploy) = &linuxdeploy {
+        // Execute extracted linuxdeploy binary (no FUSE required)
+        let mut command = tokio::process::Command::new(linuxdeploy);
+        command
+            // Lets a system linuxdeploy AppImage run without FUSE
+            .env("APPIMAGE_EXTRACT_AND_RUN", "1")
+            .env("ARCH", arch)
+            .args(["--appdir", app_dir_str]);
+        if let Some(plugin_dir) = &gstreamer_plugin_dir {
+            // linuxdeploy looks plugins up on PATH
+            let mut path = std::ffi::OsString::from(plugin_dir);
+            if let Some(system_path) = std::env::var_os("PATH") {
+                path.push(":");
+                path.push(system_path);
+            }
+            command.env("PATH", path).args(["--plugin", "gstreamer"]);
+        }
+        if appimagetool.is_none() {
+            if let Some(info) = &update_information {
+                // The appimage plugin embeds this and writes <output>.zsync
+                command.env("LDAI_UPDATE_INFORMATION", info);
+            }
+            command
+                .env("OUTPUT", &appimage_path)
+                .args(["--output", "appimage"]);
+        }
+        let status = command.status().await.map_err(|e| {
             crate::bundler::Error::GenericError(format!("Failed to execute linuxdeploy: {}", e))
         })?;
 
-    if !status.success() {
-        bail!("linuxdeploy failed with exit code: {:?}", status.code());
+        if !status.success() {
+            bail!("linuxdeploy failed with exit code: {:?}", status.code());
+        }
+    }
+
+    if let Some(appimagetool) = &appimagetool {
+        // Without linuxdeploy nothing creates the AppRun entry point
+        #[cfg(unix)]
+        if linuxdeploy.is_none() {
+            let main_binary = settings
+                .binaries()
+                .iter()
+                .find(|b| b.main())
+                .context("no main binary found")?;
+            tokio::fs::symlink(format!("usr/bin/{}", main_binary.name()), app_dir.join("AppRun"))
+                .await
+                .fs_context("creating AppRun link", &app_dir)?;
+        }
+
+        let mut command = tokio::process::Command::new(appimagetool);
+        command.env("APPIMAGE_EXTRACT_AND_RUN", "1").env("ARCH", arch);
+        if let Some(runtime_file) = &appimage_settings.runtime_file {
+            command.arg("--runtime-file").arg(runtime_file);
+        }
+        if let Some(info) = &update_information {
+            // appimagetool writes <output>.zsync alongside
+            command.arg("--updateinformation").arg(info);
+        }
+        let status = command
+            .arg(&app_dir)
+            .arg(&appimage_path)
+            .status()
+            .await
+            .map_err(|e| {
+                crate::bundler::Error::GenericError(format!("Failed to execute appimagetool: {}", e))
+            })?;
+
+        if !status.success() {
+            bail!("appimagetool failed with exit code: {:?}", status.code());
+        }
     }
 
     // 9. Set final permissions
@@ -156,53 +331,167 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         tokio::fs::set_permissions(&appimage_path, std::fs::Permissions::from_mode(0o755)).await?;
     }
 
-    log::info!("✓ Created AppImage: {}", appimage_path.display());
+    tracing::info!("✓ Created AppImage: {}", appimage_path.display());
+
+    let mut paths = vec![appimage_path];
+    if update_information.is_some() {
+        paths.push(ensure_zsync(&paths[0]).await?);
+    }
 
-    Ok(vec![appimage_path])
+    Ok(paths)
 }
 
-/// Download and extract linuxdeploy tool.
+/// Validates the update information string and substitutes `{arch}`.
 ///
-/// Downloads the linuxdeploy AppImage from GitHub and extracts it to avoid FUSE dependency.
-/// Returns path to the extracted AppRun binary.
-async fn download_linuxdeploy(tools_dir: &Path, arch: &str) -> Result<PathBuf> {
-    let appimage_name = format!("linuxdeploy-{}.AppImage", arch);
-    let appimage_path = tools_dir.join(&appimage_name);
-    let extracted_dir = tools_dir.join(format!("linuxdeploy-{}-extracted", arch));
-    let extracted_binary = extracted_dir.join("AppRun");
+/// Accepts the formats AppImageUpdate understands:
+/// `zsync|<url>`, `gh-releases-zsync|<owner>|<repo>|<tag>|<file>` and
+/// `pling-v1-zsync|<product id>|<file>`.
+fn update_information(info: &str, arch: &str) -> Result<String> {
+    let info = info.replace("{arch}", arch);
+    let fields: Vec<&str> = info.split('|').collect();
+    let expected = match fields[0] {
+        "zsync" => 2,
+        "gh-releases-zsync" => 5,
+        "pling-v1-zsync" => 3,
+        other => bail!(
+            "Unknown AppImage update information type '{}' (expected zsync, gh-releases-zsync or pling-v1-zsync)",
+            other
+        ),
+    };
+    if fields.len() != expected || fields.iter().any(|f| f.is_empty()) {
+        bail!(
+            "AppImage update information '{}' needs {} non-empty '|'-separated fields",
+            info,
+            expected
+        );
+    }
+    Ok(info)
+}
 
-    // Return early if already extracted
-    if extracted_binary.exists() {
-        log::debug!("linuxdeploy already extracted at {:?}", extracted_binary);
-        return Ok(extracted_binary);
+/// Returns the `.zsync` file for `appimage`, running zsyncmake if the
+/// packing tool did not write one.
+pub async fn ensure_zsync(appimage: &Path) -> Result<PathBuf> {
+    let file_name = appimage
+        .file_name()
+        .context("invalid AppImage path")?
+        .to_string_lossy()
+        .into_owned();
+    let zsync = appimage.with_file_name(format!("{}.zsync", file_name));
+    if zsync.exists() {
+        return Ok(zsync);
     }
 
-    // Download AppImage if not already cached
-    if !appimage_path.exists() {
-        log::info!("Downloading linuxdeploy for {}...", arch);
+    let Some(zsyncmake) = tools::find_system_tool(&["zsyncmake"], "-V").await else {
+        bail!(
+            "{} was not written and zsyncmake is not installed",
+            zsync.display()
+        );
+    };
+    // -u is the URL of the AppImage relative to the .zsync file
+    let status = tokio::process::Command::new(&zsyncmake.path)
+        .arg("-u")
+        .arg(&file_name)
+        .arg("-o")
+        .arg(&zsync)
+        .arg(appimage)
+        .status()
+        .await
+        .fs_context("running zsyncmake", &zsyncmake.path)?;
+    if !status.success() {
+        bail!("zsyncmake failed with exit code: {:?}", status.code());
+    }
+    Ok(zsync)
+}
 
-        let url = format!("{}/{}", LINUXDEPLOY_BASE_URL, appimage_name);
-        let data = http::download(&url).await?;
+/// Downloads `tool` (or falls back to a system install) and returns its
+/// executable.
+///
+/// Downloads are only run when their SHA-256 is pinned or configured in
+/// `checksums`; a download that doesn't match is an error, never a fallback.
+async fn resolve_tool(
+    tools_dir: &Path,
+    tool: &str,
+    releases_url: &str,
+    version: &str,
+    arch: &str,
+    checksums: &HashMap<String, String>,
+) -> Result<PathBuf> {
+    let cache_name = format!("{}-{}-{}.AppImage", tool, version, arch);
+    let Some(expected_sha256) = tool_pins::expected_sha256(&cache_name, checksums) else {
+        if let Some(path) = find_system_tool(tool, arch).await {
+            tracing::warn!("No SHA-256 pinned for {}; using system {}", cache_name, path.display());
+            return Ok(path);
+        }
+        bail!(
+            "no SHA-256 pinned for {}: add it to appimage.tool_sha256 or install {} on PATH",
+            cache_name,
+            tool
+        );
+    };
 
-        tokio::fs::write(&appimage_path, data)
-            .await
-            .fs_context("writing linuxdeploy AppImage", &appimage_path)?;
+    match download_tool(tools_dir, tool, releases_url, version, arch, expected_sha256).await? {
+        Some(path) => Ok(path),
+        None => find_system_tool(tool, arch).await.with_context(|| {
+            format!("failed to download {} and no system {} found on PATH", tool, tool)
+        }),
+    }
+}
 
-        // Make executable on Unix
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            tokio::fs::set_permissions(&appimage_path, std::fs::Permissions::from_mode(0o755)).await?;
-        }
+/// Download and extract an AppImage-packaged tool.
+///
+/// Downloads `<tool>-<arch>.AppImage` from the given release and extracts it
+/// to avoid a FUSE dependency. The AppImage is checked against
+/// `expected_sha256` when downloaded and again before a cached copy is
+/// extracted. Returns path to the extracted AppRun binary, or `None` when
+/// the download failed.
+async fn download_tool(
+    tools_dir: &Path,
+    tool: &str,
+    releases_url: &str,
+    version: &str,
+    arch: &str,
+    expected_sha256: &str,
+) -> Result<Option<PathBuf>> {
+    let appimage_name = format!("{}-{}.AppImage", tool, arch);
+    let cache_stem = format!("{}-{}-{}", tool, version, arch);
+    let appimage_path = tools_dir.join(format!("{}.AppImage", cache_stem));
+    let extracted_dir = tools_dir.join(format!("{}-extracted", cache_stem));
+    let extracted_binary = extracted_dir.join("AppRun");
+
+    // Return early if already extracted from a verified AppImage
+    if extracted_binary.exists() && cached_file_verified(&appimage_path, expected_sha256).await {
+        tracing::debug!("{} already extracted at {:?}", tool, extracted_binary);
+        return Ok(Some(extracted_binary));
     }
 
-    // Extract AppImage using built-in --appimage-extract (official method for Docker/CI)
-    // This is the recommended approach from AppImage documentation for environments without FUSE
-    log::info!("Extracting linuxdeploy for {} using --appimage-extract...", arch);
+    // Download AppImage if not already cached
+    if !cached_file_verified(&appimage_path, expected_sha256).await {
+        tracing::info!("Downloading {} {} for {}...", tool, version, arch);
+
+        let url = http::tool_url(tool, releases_url, &format!("{}/{}", version, appimage_name));
+        let data = match http::download(&url).await {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!("Failed to download {} {}: {}", tool, version, e);
+                return Ok(None);
+            }
+        };
+        http::verify_sha256(&url, &http::sha256_hex(&data), expected_sha256)?;
+        write_atomically(&appimage_path, &data).await?;
+    }
 
+    // Extract AppImage using built-in --appimage-extract (official method for Docker/CI)
+    // This is the recommended approach from AppImage documentation for environments without FUSE.
+    // Extract into a private directory: the cache is shared with concurrent runs
+    tracing::info!("Extracting {} for {} using --appimage-extract...", tool, arch);
+
+    let staging = tempfile::Builder::new()
+        .prefix(".extract-")
+        .tempdir_in(tools_dir)
+        .fs_context("creating extraction directory", tools_dir)?;
     let extract_status = tokio::process::Command::new(&appimage_path)
         .arg("--appimage-extract")
-        .current_dir(tools_dir)
+        .current_dir(staging.path())
         .status()
         .await
         .map_err(|e| {
@@ -215,63 +504,171 @@ async fn download_linuxdeploy(tools_dir: &Path, arch: &str) -> Result<PathBuf> {
 
     // AppImage --appimage-extract creates squashfs-root directory
     // Rename it to our expected directory name
-    let squashfs_root = tools_dir.join("squashfs-root");
+    let squashfs_root = staging.path().join("squashfs-root");
     if !squashfs_root.exists() {
         bail!("AppImage extraction did not create squashfs-root directory");
     }
 
+    if extracted_dir.exists() {
+        tokio::fs::remove_dir_all(&extracted_dir)
+            .await
+            .fs_context("removing stale extracted tool", &extracted_dir)?;
+    }
     tokio::fs::rename(&squashfs_root, &extracted_dir)
         .await
         .fs_context("renaming extracted AppImage", &extracted_dir)?;
 
     if !extracted_binary.exists() {
-        bail!("AppRun not found in extracted linuxdeploy");
+        bail!("AppRun not found in extracted {}", tool);
     }
 
-    log::info!("✓ linuxdeploy extracted to {:?}", extracted_dir);
+    tracing::info!("✓ {} extracted to {:?}", tool, extracted_dir);
 
-    Ok(extracted_binary)
+    Ok(Some(extracted_binary))
 }
 
-/// Create .desktop file for the AppImage.
+/// Whether the cached download at `path` exists and matches `expected_sha256`
 ///
-/// Generates a freedesktop.org compliant desktop entry with application metadata.
-async fn create_desktop_file(settings: &Settings, app_dir: &Path) -> Result<()> {
-    let desktop_file = app_dir.join(format!("{}.desktop", settings.product_name()));
-    let mut file = tokio::fs::File::create(&desktop_file)
-        .await
-        .fs_context("creating desktop file", &desktop_file)?;
-
-    file.write_all(b"[Desktop Entry]\n").await?;
-    file.write_all(b"Type=Application\n").await?;
-    file.write_all(format!("Name={}\n", settings.product_name()).as_bytes())
-        .await?;
-
-    // Find main binary name
-    let main_binary = settings
-        .binaries()
-        .iter()
-        .find(|b| b.main())
-        .context("no main binary found")?;
-
-    file.write_all(format!("Exec={}\n", main_binary.name()).as_bytes())
-        .await?;
-    file.write_all(format!("Icon={}\n", settings.product_name()).as_bytes())
-        .await?;
-
-    // Optional fields from bundle settings
-    let bundle = settings.bundle_settings();
-
-    if !settings.description().is_empty() {
-        file.write_all(format!("Comment={}\n", settings.description()).as_bytes())
-            .await?;
+/// A cached file failing verification is removed so it is downloaded again.
+async fn cached_file_verified(path: &Path, expected: &str) -> bool {
+    let Ok(data) = tokio::fs::read(path).await else {
+        return false;
+    };
+    let name = path.display().to_string();
+    if let Err(e) = http::verify_sha256(&name, &http::sha256_hex(&data), expected) {
+        tracing::warn!("Discarding cached download: {}", e);
+        let _ = tokio::fs::remove_file(path).await;
+        return false;
+    }
+    true
+}
+
+/// Writes an executable `path` via a temporary file, so concurrent runs
+/// sharing the tool cache never see a partial download
+async fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut temp = tempfile::NamedTempFile::new_in(dir).fs_context("creating temporary file", dir)?;
+    std::io::Write::write_all(&mut temp, data).fs_context("writing downloaded tool", path)?;
+
+    // Make executable on Unix
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(temp.path(), std::fs::Permissions::from_mode(0o755))
+            .fs_context("setting executable permission", path)?;
     }
 
-    if let Some(category) = bundle.category.as_ref().and_then(|c| c.linux.as_ref()) {
-        file.write_all(format!("Categories={}\n", category).as_bytes())
-            .await?;
+    temp.persist(path)
+        .map_err(|e| e.error)
+        .fs_context("writing downloaded tool", path)?;
+    Ok(())
+}
+
+/// Downloads linuxdeploy-plugin-gstreamer at its pinned commit and returns
+/// the directory holding it, for linuxdeploy's PATH.
+///
+/// The plugin copies the host's GStreamer plugins into the AppDir and sets
+/// up the environment for them in AppRun. The script is checked against the
+/// checksum pinned for the commit, or configured in `tool_sha256`.
+async fn download_gstreamer_plugin(
+    tools_dir: &Path,
+    appimage_settings: &crate::bundler::settings::AppImageSettings,
+) -> Result<PathBuf> {
+    let commit = appimage_settings
+        .gstreamer_plugin_commit
+        .as_deref()
+        .or_else(|| tool_pins::pinned_gstreamer_plugin_commit())
+        .context("no linuxdeploy-plugin-gstreamer commit pinned: set appimage.gstreamer_plugin_commit")?;
+    let cache_name = format!("linuxdeploy-plugin-gstreamer-{}.sh", commit);
+    let expected_sha256 = tool_pins::expected_sha256(&cache_name, &appimage_settings.tool_sha256)
+        .with_context(|| format!("no SHA-256 pinned for {}: add it to appimage.tool_sha256", cache_name))?;
+
+    // One directory per commit, as linuxdeploy needs the plugin's own name
+    let plugin_dir = tools_dir.join(format!("linuxdeploy-plugin-gstreamer-{}", commit));
+    let plugin_path = plugin_dir.join(GSTREAMER_PLUGIN);
+    if cached_file_verified(&plugin_path, expected_sha256).await {
+        return Ok(plugin_dir);
     }
 
-    file.write_all(b"Terminal=false\n").await?;
+    tracing::info!("Downloading linuxdeploy-plugin-gstreamer {}...", commit);
+    let url = http::tool_url(
+        "linuxdeploy-plugin-gstreamer",
+        GSTREAMER_PLUGIN_BASE_URL,
+        &format!("{}/{}", commit, GSTREAMER_PLUGIN),
+    );
+    let data = http::download_verified(&url, expected_sha256).await?;
+    tokio::fs::create_dir_all(&plugin_dir)
+        .await
+        .fs_context("creating plugin directory", &plugin_dir)?;
+    write_atomically(&plugin_path, &data).await?;
+    Ok(plugin_dir)
+}
+
+/// Copies the host's `xdg-open` script (xdg-utils) into the AppDir.
+async fn bundle_xdg_open(bin_dir: &Path) -> Result<()> {
+    let xdg_open = tools::find_system_tool(&["xdg-open"], "--version")
+        .await
+        .context("bundle_xdg_open requires xdg-open (xdg-utils) on PATH")?;
+
+    let dst = bin_dir.join("xdg-open");
+    tokio::fs::copy(&xdg_open.path, &dst)
+        .await
+        .fs_context("copying xdg-open", &dst)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&dst, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+    tracing::info!("Bundled xdg-open from {}", xdg_open.path.display());
     Ok(())
 }
+
+/// Locate a system-installed linuxdeploy or appimagetool.
+///
+/// Checks `<tool>` and `<tool>-<arch>.AppImage` on PATH and verifies the
+/// candidate runs and identifies itself as the tool.
+async fn find_system_tool(tool: &str, arch: &str) -> Option<PathBuf> {
+    let appimage_name = format!("{}-{}.AppImage", tool, arch);
+    let found = tools::find_system_tool(&[tool, &appimage_name], "--version").await?;
+
+    if !found.version.to_lowercase().contains(tool) {
+        tracing::warn!(
+            "Ignoring {}: unexpected version output '{}'",
+            found.path.display(),
+            found.version
+        );
+        return None;
+    }
+
+    tracing::info!("Using system {} at {} ({})", tool, found.path.display(), found.version);
+    Some(found.path)
+}
+
+/// Create .desktop file for the AppImage.
+///
+/// linuxdeploy requires exactly one desktop entry at the AppDir root.
+async fn create_desktop_file(settings: &Settings, app_dir: &Path) -> Result<()> {
+    let desktop_file = app_dir.join(format!("{}.desktop", settings.product_name()));
+    freedesktop::write_desktop_entry(settings, &desktop_file).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_information() {
+        assert_eq!(
+            update_information(
+                "gh-releases-zsync|cyrup-ai|kodegen|latest|kodegen-*-{arch}.AppImage.zsync",
+                "x86_64"
+            )
+            .unwrap(),
+            "gh-releases-zsync|cyrup-ai|kodegen|latest|kodegen-*-x86_64.AppImage.zsync"
+        );
+        assert!(update_information("zsync|https://example.com/kodegen.AppImage.zsync", "x86_64").is_ok());
+        assert!(update_information("gh-releases-zsync|cyrup-ai|kodegen", "x86_64").is_err());
+        assert!(update_information("bintray-zsync|a|b|c|d", "x86_64").is_err());
+    }
+}
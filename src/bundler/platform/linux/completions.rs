@@ -0,0 +1,158 @@
+//! Shell completion script installation for Linux packages.
+//!
+//! Scripts come from the `completions` setting or, with `generate`, from
+//! running the main binary with `--completions <shell>`. They are installed
+//! for the main binary's command name to each shell's vendor directory.
+
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    settings::Settings,
+};
+use std::path::Path;
+
+/// Shells with a completion install location
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    /// GNU Bash
+    Bash,
+    /// Z shell
+    Zsh,
+    /// fish
+    Fish,
+}
+
+impl Shell {
+    /// All supported shells
+    pub const ALL: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+    /// Name passed to `--completions`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+}
+
+/// Distribution family, which decides where zsh looks for vendor completions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distro {
+    /// Debian and derivatives (`/usr/share/zsh/vendor-completions`)
+    Debian,
+    /// Fedora, RHEL and derivatives (`/usr/share/zsh/site-functions`)
+    RedHat,
+}
+
+/// A completion script ready to be packaged
+#[derive(Debug)]
+pub struct CompletionFile {
+    /// Absolute install path
+    pub install_path: String,
+    /// Script contents
+    pub contents: Vec<u8>,
+}
+
+/// Install path of `command`'s completion script for `shell`
+pub fn install_path(shell: Shell, distro: Distro, command: &str) -> String {
+    match (shell, distro) {
+        (Shell::Bash, _) => format!("/usr/share/bash-completion/completions/{}", command),
+        (Shell::Zsh, Distro::Debian) => format!("/usr/share/zsh/vendor-completions/_{}", command),
+        (Shell::Zsh, Distro::RedHat) => format!("/usr/share/zsh/site-functions/_{}", command),
+        (Shell::Fish, _) => format!("/usr/share/fish/vendor_completions.d/{}.fish", command),
+    }
+}
+
+/// Collects the configured and generated completion scripts.
+pub async fn completion_files(settings: &Settings, distro: Distro) -> Result<Vec<CompletionFile>> {
+    let completions = &settings.bundle_settings().completions;
+    let main_binary = settings
+        .binaries()
+        .iter()
+        .find(|b| b.main())
+        .context("no main binary found")?;
+
+    let mut files = Vec::new();
+    for shell in Shell::ALL {
+        let configured = match shell {
+            Shell::Bash => &completions.bash,
+            Shell::Zsh => &completions.zsh,
+            Shell::Fish => &completions.fish,
+        };
+        let contents = match configured {
+            Some(path) => tokio::fs::read(path)
+                .await
+                .fs_context("reading completion script", path)?,
+            None if completions.generate => {
+                generate(&settings.binary_path(main_binary), shell).await?
+            }
+            None => continue,
+        };
+        files.push(CompletionFile {
+            install_path: install_path(shell, distro, main_binary.name()),
+            contents,
+        });
+    }
+    Ok(files)
+}
+
+/// Writes the completion scripts below `data_dir` (the package root)
+pub async fn install_completions(settings: &Settings, data_dir: &Path, distro: Distro) -> Result<()> {
+    for file in completion_files(settings, distro).await? {
+        let dest = data_dir.join(file.install_path.trim_start_matches('/'));
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .fs_context("creating completion directory", parent)?;
+        }
+        tokio::fs::write(&dest, file.contents)
+            .await
+            .fs_context("writing completion script", &dest)?;
+    }
+    Ok(())
+}
+
+/// Runs `binary --completions <shell>` and returns its stdout
+async fn generate(binary: &Path, shell: Shell) -> Result<Vec<u8>> {
+    let output = tokio::process::Command::new(binary)
+        .args(["--completions", shell.name()])
+        .output()
+        .await
+        .fs_context("running binary to generate completions", binary)?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(Error::GenericError(format!(
+            "{} --completions {} failed ({:?}): {}",
+            binary.display(),
+            shell.name(),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_path() {
+        assert_eq!(
+            install_path(Shell::Bash, Distro::Debian, "kodegen"),
+            "/usr/share/bash-completion/completions/kodegen"
+        );
+        assert_eq!(
+            install_path(Shell::Zsh, Distro::Debian, "kodegen"),
+            "/usr/share/zsh/vendor-completions/_kodegen"
+        );
+        assert_eq!(
+            install_path(Shell::Zsh, Distro::RedHat, "kodegen"),
+            "/usr/share/zsh/site-functions/_kodegen"
+        );
+        assert_eq!(
+            install_path(Shell::Fish, Distro::RedHat, "kodegen"),
+            "/usr/share/fish/vendor_completions.d/kodegen.fish"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Fedora COPR build submission.
+//!
+//! Uploads the source RPM through the COPR API v3 and, unless disabled,
+//! polls the build until every chroot has finished.
+
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    settings::CoprSettings,
+};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Public Fedora COPR instance
+const DEFAULT_COPR_URL: &str = "https://copr.fedorainfracloud.org";
+
+/// Interval between build status requests
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// COPR API credentials
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Credentials {
+    login: String,
+    token: String,
+    url: Option<String>,
+}
+
+impl Credentials {
+    /// Reads credentials from the environment, then from `~/.config/copr`
+    fn load() -> Result<Self> {
+        if let Some(credentials) = Self::from_env(|name| std::env::var(name).ok()) {
+            return Ok(credentials);
+        }
+
+        let path = dirs::config_dir()
+            .context("cannot locate the config directory for COPR credentials")?
+            .join("copr");
+        let contents = std::fs::read_to_string(&path).map_err(|_| {
+            Error::GenericError(format!(
+                "COPR credentials not found: set COPR_LOGIN (or COPR_USERNAME) and COPR_TOKEN or create {}",
+                path.display()
+            ))
+        })?;
+        parse_config(&contents)
+            .with_context(|| format!("{} lacks login or token", path.display()))
+    }
+
+    /// Credentials from `COPR_TOKEN` and `COPR_LOGIN`, else `COPR_USERNAME`
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let login = var("COPR_LOGIN").or_else(|| var("COPR_USERNAME"))?;
+        let token = var("COPR_TOKEN")?;
+        Some(Self { login, token, url: None })
+    }
+}
+
+/// Parses the `copr-cli` INI config
+fn parse_config(contents: &str) -> Option<Credentials> {
+    let mut credentials = Credentials::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "login" => credentials.login = value,
+            "token" => credentials.token = value,
+            "copr_url" => credentials.url = Some(value),
+            _ => {}
+        }
+    }
+    (!credentials.login.is_empty() && !credentials.token.is_empty()).then_some(credentials)
+}
+
+/// Submits `srpm` to the configured project and optionally waits for it.
+pub async fn submit_build(srpm: &Path, copr: &CoprSettings) -> Result<()> {
+    let (owner, project) = copr
+        .project
+        .split_once('/')
+        .with_context(|| format!("COPR project '{}' must be owner/name", copr.project))?;
+    let credentials = Credentials::load()?;
+    let base_url = copr
+        .url
+        .as_deref()
+        .or(credentials.url.as_deref())
+        .unwrap_or(DEFAULT_COPR_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    let mut options = json!({ "ownername": owner, "projectname": project });
+    if !copr.chroots.is_empty() {
+        options["chroots"] = json!(copr.chroots);
+    }
+    let file_name = srpm
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .context("invalid SRPM path")?;
+    let package = tokio::fs::read(srpm).await.fs_context("reading SRPM", srpm)?;
+    let (content_type, body) = multipart_body(&options.to_string(), &file_name, &package);
+
+    tracing::info!("Submitting {} to COPR project {}", file_name, copr.project);
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api_3/build/create/upload", base_url))
+        .basic_auth(&credentials.login, Some(&credentials.token))
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(body)
+        .send()
+        .await?;
+    let build = api_response(response).await?;
+    let build_id = build["id"]
+        .as_u64()
+        .context("COPR response has no build id")?;
+    tracing::info!("COPR build {}: {}/coprs/build/{}/", build_id, base_url, build_id);
+
+    if !copr.wait {
+        return Ok(());
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(copr.timeout_minutes * 60);
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let response = client
+            .get(format!("{}/api_3/build/{}", base_url, build_id))
+            .send()
+            .await?;
+        let state = api_response(response).await?["state"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        tracing::debug!("COPR build {} is {}", build_id, state);
+
+        match state.as_str() {
+            "succeeded" | "forked" => {
+                tracing::info!("✓ COPR build {} succeeded", build_id);
+                return Ok(());
+            }
+            "failed" | "canceled" | "skipped" => {
+                return Err(Error::GenericError(format!(
+                    "COPR build {} {}: {}/coprs/build/{}/",
+                    build_id, state, base_url, build_id
+                )));
+            }
+            _ if Instant::now() >= deadline => {
+                return Err(Error::GenericError(format!(
+                    "COPR build {} still {} after {} minutes",
+                    build_id, state, copr.timeout_minutes
+                )));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the JSON body, turning COPR's `{"error": ...}` replies into errors
+async fn api_response(response: reqwest::Response) -> Result<Value> {
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = body["error"].as_str().unwrap_or("no error message");
+        return Err(Error::GenericError(format!("COPR API returned {}: {}", status, message)));
+    }
+    Ok(body)
+}
+
+/// Encodes the `json` options and `pkgs` file parts of an upload request
+fn multipart_body(options: &str, file_name: &str, package: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!("kodegen-{}", uuid::Uuid::new_v4().simple());
+    let mut body = Vec::with_capacity(package.len() + 512);
+    body.extend(
+        format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"json\"\r\n\
+             Content-Type: application/json\r\n\r\n{options}\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"pkgs\"; filename=\"{file_name}\"\r\n\
+             Content-Type: application/x-rpm\r\n\r\n",
+            b = boundary,
+        )
+        .bytes(),
+    );
+    body.extend_from_slice(package);
+    body.extend(format!("\r\n--{}--\r\n", boundary).bytes());
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = "[copr-cli]\nlogin = abc\nusername = dev\ntoken = xyz\ncopr_url = https://copr.example.org\n";
+        assert_eq!(
+            parse_config(config),
+            Some(Credentials {
+                login: "abc".into(),
+                token: "xyz".into(),
+                url: Some("https://copr.example.org".into()),
+            })
+        );
+        assert_eq!(parse_config("[copr-cli]\nlogin = abc\n"), None);
+    }
+
+    #[test]
+    fn test_from_env() {
+        fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+            move |name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        }
+        let credentials = |login: &str| Credentials {
+            login: login.into(),
+            token: "xyz".into(),
+            url: None,
+        };
+
+        let login = [("COPR_LOGIN", "abc"), ("COPR_USERNAME", "dev"), ("COPR_TOKEN", "xyz")];
+        assert_eq!(Credentials::from_env(env(&login)), Some(credentials("abc")));
+        let username = [("COPR_USERNAME", "dev"), ("COPR_TOKEN", "xyz")];
+        assert_eq!(Credentials::from_env(env(&username)), Some(credentials("dev")));
+        assert_eq!(Credentials::from_env(env(&[("COPR_USERNAME", "dev")])), None);
+    }
+
+    #[test]
+    fn test_multipart_body() {
+        let (content_type, body) = multipart_body("{}", "kodegen-1.0.0-1.src.rpm", b"RPM");
+        let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(body.contains("filename=\"kodegen-1.0.0-1.src.rpm\"\r\nContent-Type: application/x-rpm\r\n\r\nRPM\r\n"));
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+}
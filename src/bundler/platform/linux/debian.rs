@@ -7,15 +7,26 @@
 //! A .deb file is an ar archive containing:
 //! - debian-binary: Format version (2.0)
 //! - control.tar.gz: Package metadata (control, md5sums, scripts)
-//! - data.tar.gz: Files to install
+//! - data.tar.{gz,xz,zst}: Files to install, compressed as `compression` sets
 
 use crate::bundler::{
+    BundleStage, PackageType,
     error::{Context, Error, ErrorExt, Result},
+    platform::linux::{
+        completions, freedesktop, man_pages,
+        shared_libs::bundle_shared_libraries,
+        shlibdeps::{compute_depends, merge_depends},
+    },
+    resources::{files, license, locales},
     settings::{Arch, Settings},
-    utils::fs::{copy_custom_files, copy_file},
+    utils::{
+        fs::{copy_custom_files, copy_file},
+        progress_bar,
+    },
 };
 use flate2::{Compression, write::GzEncoder};
 use std::{
+    collections::BTreeMap,
     fs::{self as std_fs, File},
     io::{self, Write},
     path::{Path, PathBuf},
@@ -28,6 +39,7 @@ use walkdir::WalkDir;
 pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
     // Map architecture
     let arch = arch_to_debian(settings.binary_arch())?;
+    let package = package_name(settings);
 
     // Create package name: {product}_{version}_{arch}.deb
     let package_base_name = format!(
@@ -51,10 +63,17 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
 
     let package_path = base_dir.join(&package_name);
 
-    log::info!("Bundling {} ({})", package_name, package_path.display());
+    tracing::info!("Bundling {} ({})", package_name, package_path.display());
+    let progress = settings.progress();
 
     // Generate data directory (binaries, resources, desktop file)
-    let data_dir = generate_data(settings, &package_dir)
+    progress.report(
+        PackageType::Deb,
+        BundleStage::Copying,
+        Some(10),
+        "Copying binaries and resources",
+    );
+    let data_dir = generate_data(settings, &package, &package_dir)
         .await
         .context("failed to generate data directory")?;
 
@@ -63,14 +82,37 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         .await
         .context("failed to copy custom files")?;
 
+    // Derive Depends from the linked shared libraries if requested
+    let computed_depends = if settings.bundle_settings().deb.auto_depends {
+        let binaries: Vec<PathBuf> = settings
+            .binaries()
+            .iter()
+            .map(|bin| data_dir.join("usr/bin").join(bin.name()))
+            .collect();
+        compute_depends(&binaries, &data_dir)
+            .await
+            .context("failed to compute package dependencies")?
+    } else {
+        Vec::new()
+    };
+
     // Generate control directory
+    progress.report(
+        PackageType::Deb,
+        BundleStage::Building,
+        Some(40),
+        "Generating control files",
+    );
     let control_dir = package_dir.join("control");
-    generate_control_file(settings, arch, &control_dir, &data_dir)
+    generate_control_file(settings, &package, arch, &control_dir, &data_dir, computed_depends)
         .await
         .context("failed to generate control file")?;
     generate_scripts(settings, &control_dir)
         .await
         .context("failed to generate control scripts")?;
+    generate_conffiles(settings, &control_dir, &data_dir)
+        .await
+        .context("failed to generate conffiles")?;
     generate_md5sums(&control_dir, &data_dir)
         .await
         .context("failed to generate md5sums file")?;
@@ -82,16 +124,26 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         .fs_context("creating debian-binary file", &debian_binary_path)?;
 
     // Create tar.gz archives
-    let control_tar_gz = tar_and_gzip_dir(control_dir)
+    progress.report(
+        PackageType::Deb,
+        BundleStage::Compressing,
+        Some(70),
+        "Compressing package archives",
+    );
+    let data_compression = DataCompression::from_settings(settings)?;
+    let control_tar_gz = tar_dir(control_dir, DataCompression::Gzip(6))
         .await
         .context("failed to tar/gzip control directory")?;
-    let data_tar_gz = tar_and_gzip_dir(data_dir)
-        .await
-        .context("failed to tar/gzip data directory")?;
+    let data_tar = progress_bar::spin(
+        &format!("Compressing data.{}", data_compression.extension()),
+        tar_dir(data_dir, data_compression),
+    )
+    .await
+    .context("failed to tar/compress data directory")?;
 
     // Create final ar archive
     create_ar_archive(
-        vec![debian_binary_path, control_tar_gz, data_tar_gz],
+        vec![debian_binary_path, control_tar_gz, data_tar],
         &package_path,
     )
     .await
@@ -101,11 +153,12 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
 }
 
 /// Generate data directory with all files to be installed.
-async fn generate_data(settings: &Settings, package_dir: &Path) -> Result<PathBuf> {
+async fn generate_data(settings: &Settings, package: &str, package_dir: &Path) -> Result<PathBuf> {
     let data_dir = package_dir.join("data");
     let bin_dir = data_dir.join("usr/bin");
 
     // Copy all binaries
+    let mut binaries = Vec::new();
     for bin in settings.binaries() {
         let bin_path = settings.binary_path(bin);
         let dest = bin_dir.join(bin.name());
@@ -121,13 +174,35 @@ async fn generate_data(settings: &Settings, package_dir: &Path) -> Result<PathBu
                 .await
                 .fs_context("setting executable permission", &dest)?;
         }
+        binaries.push(dest);
+    }
+
+    // Bundle non-system shared libraries into a private lib directory
+    if settings.bundle_settings().deb.bundle_shared_libs && !settings.skip_phases().dylib_bundling {
+        let lib_dir = data_dir.join("usr/lib").join(settings.product_name());
+        let rpath = format!("$ORIGIN/../lib/{}", settings.product_name());
+        bundle_shared_libraries(&binaries, &lib_dir, &rpath).await?;
     }
 
-    // Generate desktop file
+    // Copy resources to usr/share/<name>
+    files::copy_resources(settings, &data_dir.join(files::linux_resource_dir(settings))).await?;
+    locales::copy_catalogs(settings, &data_dir.join(locales::LINUX_LOCALE_DIR)).await?;
+
+    // Generate desktop file and the optional autostart entry
     generate_desktop_file(settings, &data_dir).await?;
+    generate_autostart_entry(settings, &data_dir).await?;
 
     // Generate compressed changelog if provided
-    generate_changelog(settings, &data_dir).await?;
+    generate_changelog(settings, package, &data_dir).await?;
+
+    // Machine-readable copyright file from the crate's license
+    generate_copyright(settings, package, &data_dir).await?;
+
+    // Install man pages to usr/share/man
+    man_pages::install_man_pages(settings, &data_dir).await?;
+
+    // Install shell completions
+    completions::install_completions(settings, &data_dir, completions::Distro::Debian).await?;
 
     Ok(data_dir)
 }
@@ -137,53 +212,48 @@ async fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> Result<(
     let desktop_path = data_dir
         .join("usr/share/applications")
         .join(format!("{}.desktop", settings.product_name()));
+    freedesktop::write_desktop_entry(settings, &desktop_path).await
+}
 
-    // Clone necessary data for the blocking task
-    let product_name = settings.product_name().to_string();
-    let short_description = settings.bundle_settings().short_description.clone();
-    let category = settings
-        .bundle_settings()
-        .category
-        .as_ref()
-        .and_then(|c| c.linux.clone());
-
-    tokio::task::spawn_blocking(move || -> Result<()> {
-        if let Some(parent) = desktop_path.parent() {
-            std_fs::create_dir_all(parent).fs_context("creating desktop file directory", parent)?;
-        }
-        let mut file =
-            File::create(&desktop_path).fs_context("creating desktop file", &desktop_path)?;
-
-        writeln!(file, "[Desktop Entry]")?;
-        writeln!(file, "Type=Application")?;
-        writeln!(file, "Name={}", product_name)?;
-        writeln!(file, "Exec={}", product_name)?;
-        writeln!(file, "Terminal=false")?;
-
-        // Optional fields from settings
-        if let Some(desc) = short_description.as_ref() {
-            writeln!(file, "Comment={}", desc)?;
-        }
-        if let Some(category) = category.as_ref() {
-            writeln!(file, "Categories={}", category)?;
-        }
-
-        file.flush()?;
-        Ok(())
-    })
-    .await
-    .map_err(|e| Error::GenericError(format!("Desktop file generation task failed: {}", e)))??;
+/// Generate etc/xdg/autostart/<name>.desktop when autostart is enabled.
+///
+/// Being under /etc it becomes a conffile: `apt purge` deletes it, and after
+/// `apt remove` its TryExec keeps sessions from launching the missing binary.
+async fn generate_autostart_entry(settings: &Settings, data_dir: &Path) -> Result<()> {
+    let Some(path) = freedesktop::autostart_path(settings) else {
+        return Ok(());
+    };
+    let dest = data_dir.join(path);
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .fs_context("creating autostart directory", parent)?;
+    }
+    tokio::fs::write(&dest, freedesktop::render_autostart_entry(settings)?)
+        .await
+        .fs_context("writing autostart entry", &dest)
+}
 
-    Ok(())
+/// Generate usr/share/doc/<package>/copyright when the crate declares a license
+async fn generate_copyright(settings: &Settings, package: &str, data_dir: &Path) -> Result<()> {
+    let Some(copyright) = license::debian_copyright(settings)? else {
+        return Ok(());
+    };
+    let dest = data_dir.join(format!("usr/share/doc/{}/copyright", package));
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .fs_context("creating doc directory", parent)?;
+    }
+    tokio::fs::write(&dest, copyright)
+        .await
+        .fs_context("writing copyright file", &dest)
 }
 
-/// Generate compressed changelog at usr/share/doc/<name>/changelog.gz
-async fn generate_changelog(settings: &Settings, data_dir: &Path) -> Result<()> {
+/// Generate compressed changelog at usr/share/doc/<package>/changelog.gz
+async fn generate_changelog(settings: &Settings, package: &str, data_dir: &Path) -> Result<()> {
     if let Some(changelog_path) = &settings.bundle_settings().deb.changelog {
-        let dest = data_dir.join(format!(
-            "usr/share/doc/{}/changelog.gz",
-            settings.product_name()
-        ));
+        let dest = data_dir.join(format!("usr/share/doc/{}/changelog.gz", package));
 
         let src_path = changelog_path.clone();
         let dest_path = dest.clone();
@@ -214,32 +284,43 @@ async fn generate_changelog(settings: &Settings, data_dir: &Path) -> Result<()>
 /// Generate control file with package metadata.
 async fn generate_control_file(
     settings: &Settings,
+    package: &str,
     arch: &str,
     control_dir: &Path,
     data_dir: &Path,
+    computed_depends: Vec<String>,
 ) -> Result<()> {
     let control_path = control_dir.join("control");
 
     // Clone all data needed for blocking task
-    let package = settings.product_name().to_lowercase().replace(' ', "-");
+    let package = package.to_string();
     let version = settings.version_string().to_string();
     let arch = arch.to_string();
     let size_kb = calculate_dir_size(data_dir).await? / 1024;
-    let maintainer = settings
-        .authors()
-        .map(|a| a.join(", "))
+    let deb = &settings.bundle_settings().deb;
+    let maintainer = deb
+        .maintainer
+        .clone()
+        .or_else(|| settings.authors().map(|a| a.join(", ")))
         .or_else(|| settings.bundle_settings().publisher.clone())
         .unwrap_or_else(|| "Unknown".to_string());
     let section = settings.bundle_settings().deb.section.clone();
     let priority = settings.bundle_settings().deb.priority.clone();
-    let homepage = settings.homepage().map(|s| s.to_string());
-    let depends = settings.bundle_settings().deb.depends.clone();
+    let homepage = deb
+        .homepage
+        .clone()
+        .or_else(|| settings.homepage().map(|s| s.to_string()));
+    let vcs_git = deb.vcs_git.clone();
+    let bugs = deb.bugs.clone();
+    let extra_fields = extra_control_fields(&deb.extra_fields)?;
+    let manual_depends = settings.bundle_settings().deb.depends.as_deref().unwrap_or_default();
+    let depends = merge_depends(manual_depends, computed_depends);
     let recommends = settings.bundle_settings().deb.recommends.clone();
     let provides = settings.bundle_settings().deb.provides.clone();
     let conflicts = settings.bundle_settings().deb.conflicts.clone();
     let replaces = settings.bundle_settings().deb.replaces.clone();
     let short_description = settings.bundle_settings().short_description.clone();
-    let long_description = settings.bundle_settings().long_description.clone();
+    let extended_description = extended_description(settings);
 
     tokio::task::spawn_blocking(move || -> Result<()> {
         if let Some(parent) = control_path.parent() {
@@ -268,7 +349,15 @@ async fn generate_control_file(
             writeln!(file, "Homepage: {}", homepage)?;
         }
 
-        if let Some(depends) = depends {
+        if let Some(vcs_git) = vcs_git {
+            writeln!(file, "Vcs-Git: {}", vcs_git)?;
+        }
+
+        if let Some(bugs) = bugs {
+            writeln!(file, "Bugs: {}", bugs)?;
+        }
+
+        if !depends.is_empty() {
             writeln!(file, "Depends: {}", depends.join(", "))?;
         }
 
@@ -288,18 +377,13 @@ async fn generate_control_file(
             writeln!(file, "Replaces: {}", replaces.join(", "))?;
         }
 
+        for field in extra_fields {
+            writeln!(file, "{}", field)?;
+        }
+
         let short = short_description.as_deref().unwrap_or("(no description)");
         writeln!(file, "Description: {}", short)?;
-
-        if let Some(long) = long_description {
-            for line in long.lines() {
-                if line.trim().is_empty() {
-                    writeln!(file, " .")?;
-                } else {
-                    writeln!(file, " {}", line.trim())?;
-                }
-            }
-        }
+        file.write_all(extended_description.as_bytes())?;
 
         file.flush()?;
         Ok(())
@@ -310,6 +394,148 @@ async fn generate_control_file(
     Ok(())
 }
 
+/// Control fields the bundler writes itself, which `extra_fields` may not set
+const GENERATED_FIELDS: &[&str] = &[
+    "Package",
+    "Version",
+    "Architecture",
+    "Installed-Size",
+    "Maintainer",
+    "Section",
+    "Priority",
+    "Homepage",
+    "Vcs-Git",
+    "Bugs",
+    "Depends",
+    "Recommends",
+    "Provides",
+    "Conflicts",
+    "Replaces",
+    "Description",
+];
+
+/// `extra_fields` as control file lines; multi-line values are folded into
+/// continuation lines
+fn extra_control_fields(fields: &BTreeMap<String, String>) -> Result<Vec<String>> {
+    fields
+        .iter()
+        .map(|(name, value)| {
+            if let Some(generated) = GENERATED_FIELDS
+                .iter()
+                .find(|generated| generated.eq_ignore_ascii_case(name))
+            {
+                return Err(Error::GenericError(format!(
+                    "deb.extra_fields cannot set {}; use its dedicated setting",
+                    generated
+                )));
+            }
+            if name.is_empty()
+                || name.starts_with(['#', '-'])
+                || !name.chars().all(|c| c.is_ascii_graphic() && c != ':')
+            {
+                return Err(Error::GenericError(format!(
+                    "invalid control field name {:?} in deb.extra_fields",
+                    name
+                )));
+            }
+            Ok(format!("{}: {}", name, fold_continuation(value.trim_end())))
+        })
+        .collect()
+}
+
+/// Indents every line after the first of `value` as a continuation line,
+/// with blank lines as ` .`
+fn fold_continuation(value: &str) -> String {
+    let mut lines = value.lines();
+    let mut folded = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        match line.trim_end() {
+            "" => folded.push_str("\n ."),
+            line => {
+                folded.push_str("\n ");
+                folded.push_str(line);
+            }
+        }
+    }
+    folded
+}
+
+/// Lines of the extended `Description` below the synopsis, each ending in a
+/// newline: `deb.extended_description` as written, else `long_description`
+/// with indentation removed.
+pub(super) fn extended_description(settings: &Settings) -> String {
+    let bundle = settings.bundle_settings();
+    let (text, keep_indent) = match (&bundle.deb.extended_description, &bundle.long_description) {
+        (Some(text), _) => (text, true),
+        (None, Some(text)) => (text, false),
+        (None, None) => return String::new(),
+    };
+
+    let mut description = String::new();
+    for line in text.trim_end().lines() {
+        let line = if keep_indent { line.trim_end() } else { line.trim() };
+        if line.is_empty() {
+            description.push_str(" .\n");
+        } else {
+            description.push(' ');
+            description.push_str(line);
+            description.push('\n');
+        }
+    }
+    description
+}
+
+/// Generate the conffiles list so dpkg keeps user-modified configuration.
+async fn generate_conffiles(settings: &Settings, control_dir: &Path, data_dir: &Path) -> Result<()> {
+    let conffiles = conffile_entries(data_dir, &settings.bundle_settings().deb.conffiles)?;
+    if conffiles.is_empty() {
+        return Ok(());
+    }
+
+    let conffiles_path = control_dir.join("conffiles");
+    tokio::fs::create_dir_all(control_dir)
+        .await
+        .fs_context("creating control directory", control_dir)?;
+    let mut contents = conffiles.join("\n");
+    contents.push('\n');
+    tokio::fs::write(&conffiles_path, contents)
+        .await
+        .fs_context("writing conffiles", &conffiles_path)?;
+    Ok(())
+}
+
+/// Absolute paths of every file under `etc/` in `data_dir` plus `configured`,
+/// sorted and deduplicated.
+///
+/// Configured paths must be files shipped in the package.
+fn conffile_entries(data_dir: &Path, configured: &[PathBuf]) -> Result<Vec<String>> {
+    let mut entries = std::collections::BTreeSet::new();
+
+    let etc_dir = data_dir.join("etc");
+    if etc_dir.is_dir() {
+        for entry in WalkDir::new(&etc_dir) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let rel_path = entry.path().strip_prefix(data_dir)?;
+                entries.insert(format!("/{}", rel_path.display()));
+            }
+        }
+    }
+
+    for path in configured {
+        let rel_path = path.strip_prefix("/").unwrap_or(path);
+        if !data_dir.join(rel_path).is_file() {
+            return Err(Error::GenericError(format!(
+                "conffile {} is not a file installed by the package",
+                path.display()
+            )));
+        }
+        entries.insert(format!("/{}", rel_path.display()));
+    }
+
+    Ok(entries.into_iter().collect())
+}
+
 /// Generate MD5 checksums for all files in data directory.
 async fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> Result<()> {
     let md5sums_path = control_dir.join("md5sums");
@@ -404,41 +630,26 @@ async fn generate_scripts(settings: &Settings, control_dir: &Path) -> Result<()>
 }
 
 /// Create tar.gz archive from directory.
-async fn tar_and_gzip_dir(src_dir: PathBuf) -> Result<PathBuf> {
-    let dest_path = src_dir.with_extension("tar.gz");
-    let tar_gz = tokio::fs::File::create(&dest_path)
+async fn tar_dir(src_dir: PathBuf, compression: DataCompression) -> Result<PathBuf> {
+    let dest_path = src_dir.with_extension(compression.extension());
+    let tar_file = tokio::fs::File::create(&dest_path)
         .await
-        .fs_context("creating tar.gz file", &dest_path)?;
-    let std_file = tar_gz.into_std().await;
+        .fs_context("creating tar file", &dest_path)?;
+    let std_file = tar_file.into_std().await;
 
     tokio::task::spawn_blocking(move || {
-        let enc = GzEncoder::new(std_file, Compression::default());
-        let mut tar = tar::Builder::new(enc);
-
-        for entry in WalkDir::new(&src_dir) {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path == src_dir {
-                continue;
+        let mut finished = match compression {
+            DataCompression::Gzip(level) => {
+                write_tar(&src_dir, GzEncoder::new(std_file, Compression::new(level)))?.finish()?
             }
-
-            let rel_path = path.strip_prefix(&src_dir)?;
-            let metadata = std::fs::metadata(path)?; // Use blocking fs in spawn_blocking
-
-            let mut header = tar::Header::new_gnu();
-            header.set_metadata_in_mode(&metadata, HeaderMode::Deterministic);
-
-            if entry.file_type().is_dir() {
-                tar.append_data(&mut header, rel_path, &mut io::empty())?;
-            } else {
-                let mut file = std::fs::File::open(path)?; // Use blocking fs
-                tar.append_data(&mut header, rel_path, &mut file)?;
+            DataCompression::Xz(level) => {
+                write_tar(&src_dir, liblzma::write::XzEncoder::new(std_file, level))?.finish()?
             }
-        }
-
-        let enc = tar.into_inner()?;
-        let mut finished = enc.finish()?;
+            DataCompression::Zstd(level) => {
+                write_tar(&src_dir, zstd::Encoder::new(std_file, level)?)?.finish()?
+            }
+            DataCompression::None => write_tar(&src_dir, std_file)?,
+        };
         finished.flush()?;
         Ok(dest_path)
     })
@@ -446,6 +657,36 @@ async fn tar_and_gzip_dir(src_dir: PathBuf) -> Result<PathBuf> {
     .map_err(|e| Error::GenericError(format!("Join error: {}", e)))?
 }
 
+/// Writes the contents of `src_dir` as a tar archive into `writer` and
+/// returns the writer
+fn write_tar<W: Write>(src_dir: &Path, writer: W) -> Result<W> {
+    let mut tar = tar::Builder::new(writer);
+
+    for entry in WalkDir::new(src_dir) {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path == src_dir {
+            continue;
+        }
+
+        let rel_path = path.strip_prefix(src_dir)?;
+        let metadata = std::fs::metadata(path)?; // Use blocking fs in spawn_blocking
+
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata_in_mode(&metadata, HeaderMode::Deterministic);
+
+        if entry.file_type().is_dir() {
+            tar.append_data(&mut header, rel_path, &mut io::empty())?;
+        } else {
+            let mut file = std::fs::File::open(path)?; // Use blocking fs
+            tar.append_data(&mut header, rel_path, &mut file)?;
+        }
+    }
+
+    Ok(tar.into_inner()?)
+}
+
 /// Create ar archive (final .deb package).
 async fn create_ar_archive(files: Vec<PathBuf>, dest: &Path) -> Result<()> {
     let tokio_file = tokio::fs::File::create(dest)
@@ -468,6 +709,61 @@ async fn create_ar_archive(files: Vec<PathBuf>, dest: &Path) -> Result<()> {
     .map_err(|e| Error::GenericError(format!("Join error: {}", e)))?
 }
 
+/// Compression of a .deb member archive, with its level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataCompression {
+    Gzip(u32),
+    Xz(u32),
+    Zstd(i32),
+    None,
+}
+
+impl DataCompression {
+    /// Compression of `data.tar` configured by `deb.compression` and
+    /// `deb.compression_level`
+    fn from_settings(settings: &Settings) -> Result<Self> {
+        let deb = &settings.bundle_settings().deb;
+        let level = deb.compression_level;
+        let (compression, max) = match deb.compression.as_deref() {
+            Some("gzip") | None => (Self::Gzip(level.unwrap_or(6)), 9),
+            Some("xz") => (Self::Xz(level.unwrap_or(6)), 9),
+            Some("zstd") => (Self::Zstd(level.unwrap_or(19) as i32), 22),
+            Some("none") => (Self::None, u32::MAX),
+            Some(other) => {
+                return Err(Error::GenericError(format!(
+                    "Unknown Debian compression '{}'. Expected gzip, xz, zstd or none",
+                    other
+                )));
+            }
+        };
+        if let Some(level) = level
+            && (level > max || (level == 0 && matches!(compression, Self::Zstd(_))))
+        {
+            return Err(Error::GenericError(format!(
+                "Debian compression level {} is out of range for {}",
+                level,
+                deb.compression.as_deref().unwrap_or("gzip")
+            )));
+        }
+        Ok(compression)
+    }
+
+    /// Extension of an archive compressed this way
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Gzip(_) => "tar.gz",
+            Self::Xz(_) => "tar.xz",
+            Self::Zstd(_) => "tar.zst",
+            Self::None => "tar",
+        }
+    }
+}
+
+/// Debian package name: the product name in lowercase, with spaces as dashes
+pub(super) fn package_name(settings: &Settings) -> String {
+    settings.product_name().to_lowercase().replace(' ', "-")
+}
+
 /// Map Rust architecture to Debian architecture string.
 fn arch_to_debian(arch: Arch) -> Result<&'static str> {
     match arch {
@@ -500,3 +796,79 @@ async fn calculate_dir_size(dir: &Path) -> Result<u64> {
     .await
     .map_err(|e| Error::GenericError(format!("Join error: {}", e)))?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conffile_entries() {
+        let data_dir = tempfile::tempdir().unwrap();
+        std_fs::create_dir_all(data_dir.path().join("etc/myapp")).unwrap();
+        std_fs::create_dir_all(data_dir.path().join("usr/share/myapp")).unwrap();
+        std_fs::write(data_dir.path().join("etc/myapp/config.toml"), "").unwrap();
+        std_fs::write(data_dir.path().join("usr/share/myapp/defaults.toml"), "").unwrap();
+
+        let entries = conffile_entries(
+            data_dir.path(),
+            &[
+                PathBuf::from("/usr/share/myapp/defaults.toml"),
+                PathBuf::from("/etc/myapp/config.toml"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entries, vec!["/etc/myapp/config.toml", "/usr/share/myapp/defaults.toml"]);
+
+        assert!(conffile_entries(data_dir.path(), &[PathBuf::from("/etc/missing.conf")]).is_err());
+    }
+    #[test]
+    fn test_package_name() {
+        use crate::bundler::settings::{PackageSettings, SettingsBuilder};
+        let settings = SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "Kodegen App".into(),
+                version: "1.0.0".into(),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(package_name(&settings), "kodegen-app");
+    }
+
+    #[test]
+    fn test_extra_control_fields() {
+        let fields = BTreeMap::from([
+            ("X-Team".to_string(), "platform".to_string()),
+            ("X-Notes".to_string(), "first\n\nsecond\n".to_string()),
+        ]);
+        assert_eq!(
+            extra_control_fields(&fields).unwrap(),
+            ["X-Notes: first\n .\n second", "X-Team: platform"]
+        );
+
+        let generated = BTreeMap::from([("depends".to_string(), "libc6".to_string())]);
+        assert!(extra_control_fields(&generated).unwrap_err().to_string().contains("Depends"));
+        let invalid = BTreeMap::from([("X Team".to_string(), "platform".to_string())]);
+        assert!(extra_control_fields(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tar_dir_zstd() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        std_fs::create_dir_all(data_dir.join("usr/bin")).unwrap();
+        std_fs::write(data_dir.join("usr/bin/app"), "binary").unwrap();
+
+        let archive = tar_dir(data_dir, DataCompression::Zstd(3)).await.unwrap();
+        assert_eq!(archive, dir.path().join("data.tar.zst"));
+
+        let decoder = zstd::Decoder::new(File::open(&archive).unwrap()).unwrap();
+        let paths: Vec<PathBuf> = tar::Archive::new(decoder)
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().into_owned())
+            .collect();
+        assert!(paths.contains(&PathBuf::from("usr/bin/app")));
+    }
+}
@@ -0,0 +1,72 @@
+//! Automatic RPM `Requires:` generation, matching rpmbuild's `elfdeps`.
+//!
+//! rpmbuild derives requirements from the ELF headers of packaged files:
+//! every DT_NEEDED soname becomes `libfoo.so.1()(64bit)` and every needed
+//! symbol version becomes `libfoo.so.1(VERSION)(64bit)`. The rpm crate does
+//! not run those generators, so the same names are computed here. They are
+//! satisfied by the Provides every distribution library package carries,
+//! whatever the package is called.
+
+use crate::bundler::error::{ErrorExt, Result};
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+
+/// Computes rpmbuild-style `Requires:` for the ELF files in `files`.
+///
+/// Sonames in `bundled_sonames` ship inside the package itself and are not
+/// required. Non-ELF and static files contribute nothing.
+pub fn elf_requires(files: &[PathBuf], bundled_sonames: &HashSet<String>) -> Result<Vec<String>> {
+    let mut requires = BTreeSet::new();
+
+    for file in files {
+        let buffer = std::fs::read(file).fs_context("reading packaged file", file)?;
+        let Ok(goblin::Object::Elf(elf)) = goblin::Object::parse(&buffer) else {
+            continue;
+        };
+        if elf.dynamic.is_none() {
+            continue;
+        }
+        let mark = if elf.is_64 { "(64bit)" } else { "" };
+
+        for soname in &elf.libraries {
+            if !bundled_sonames.contains(*soname) {
+                requires.insert(format!("{}(){}", soname, mark));
+            }
+        }
+
+        if let Some(verneed) = &elf.verneed {
+            for need in verneed.iter() {
+                let Some(soname) = elf.dynstrtab.get_at(need.vn_file) else {
+                    continue;
+                };
+                if bundled_sonames.contains(soname) {
+                    continue;
+                }
+                for aux in need.iter() {
+                    if let Some(version) = elf.dynstrtab.get_at(aux.vna_name) {
+                        requires.insert(format!("{}({}){}", soname, version, mark));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(requires.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elf_requires_of_test_binary() {
+        let exe = std::env::current_exe().unwrap();
+        let requires = elf_requires(std::slice::from_ref(&exe), &HashSet::new()).unwrap();
+        assert!(requires.iter().any(|r| r.starts_with("libc.so.6()")));
+        assert!(requires.iter().any(|r| r.starts_with("libc.so.6(GLIBC_")));
+
+        let bundled = HashSet::from(["libc.so.6".to_string()]);
+        let requires = elf_requires(&[exe], &bundled).unwrap();
+        assert!(!requires.iter().any(|r| r.starts_with("libc.so.6")));
+    }
+}
@@ -0,0 +1,283 @@
+//! Desktop entry rendering shared by the AppImage, Debian and RPM bundlers.
+
+use crate::bundler::{
+    error::{Context, ErrorExt, Result},
+    settings::Settings,
+};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// System-wide XDG autostart directory, relative to the filesystem root
+const AUTOSTART_DIR: &str = "etc/xdg/autostart";
+
+/// Renders the application's `.desktop` file.
+///
+/// `Exec` runs the main binary; it takes `%U` when the application handles
+/// URL schemes and `%F` when it only opens files.
+pub fn render_desktop_entry(settings: &Settings) -> Result<String> {
+    let bundle = settings.bundle_settings();
+    let desktop = &bundle.desktop;
+    let main_binary = settings
+        .binaries()
+        .iter()
+        .find(|b| b.main())
+        .context("no main binary found")?;
+
+    let mime_types = mime_types(settings);
+    let exec = if !bundle.url_schemes.is_empty() {
+        format!("{} %U", main_binary.name())
+    } else if !mime_types.is_empty() {
+        format!("{} %F", main_binary.name())
+    } else {
+        main_binary.name().to_string()
+    };
+
+    let mut entry = String::from("[Desktop Entry]\n");
+    entry.push_str("Type=Application\n");
+    push_value(&mut entry, "Name", settings.product_name());
+    if let Some(generic_name) = &desktop.generic_name {
+        push_value(&mut entry, "GenericName", generic_name);
+    }
+
+    let comment = bundle
+        .short_description
+        .as_deref()
+        .unwrap_or(settings.description());
+    if !comment.is_empty() {
+        push_value(&mut entry, "Comment", comment);
+    }
+
+    push_value(&mut entry, "Exec", &exec);
+    push_value(&mut entry, "Icon", settings.product_name());
+    push_value(&mut entry, "Terminal", if desktop.terminal { "true" } else { "false" });
+
+    if let Some(category) = bundle.category.as_ref().and_then(|c| c.linux.as_ref()) {
+        let categories: Vec<&str> = category.split(';').filter(|c| !c.is_empty()).collect();
+        push_list(&mut entry, "Categories", &categories);
+    }
+    push_list(&mut entry, "Keywords", &desktop.keywords);
+    push_list(&mut entry, "MimeType", &mime_types);
+
+    if let Some(wm_class) = &desktop.startup_wm_class {
+        push_value(&mut entry, "StartupWMClass", wm_class);
+    }
+    if let Some(notify) = desktop.startup_notify {
+        push_value(&mut entry, "StartupNotify", if notify { "true" } else { "false" });
+    }
+
+    let action_ids: Vec<&str> = desktop.actions.iter().map(|a| a.id.as_str()).collect();
+    push_list(&mut entry, "Actions", &action_ids);
+    for action in &desktop.actions {
+        let _ = writeln!(entry, "\n[Desktop Action {}]", action.id);
+        push_value(&mut entry, "Name", &action.name);
+        push_value(&mut entry, "Exec", &action.exec);
+        if let Some(icon) = &action.icon {
+            push_value(&mut entry, "Icon", icon);
+        }
+    }
+
+    Ok(entry)
+}
+
+/// Writes the rendered desktop entry to `path`, creating parent directories.
+pub async fn write_desktop_entry(settings: &Settings, path: &Path) -> Result<()> {
+    let contents = render_desktop_entry(settings)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .fs_context("creating desktop file directory", parent)?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .fs_context("writing desktop file", path)?;
+    Ok(())
+}
+
+/// Install path of the autostart entry, relative to the filesystem root;
+/// `None` unless `desktop.autostart` is enabled.
+pub fn autostart_path(settings: &Settings) -> Option<PathBuf> {
+    settings.bundle_settings().desktop.autostart.then(|| {
+        Path::new(AUTOSTART_DIR).join(format!("{}.desktop", settings.product_name()))
+    })
+}
+
+/// Renders the XDG autostart entry that starts the main binary at login.
+///
+/// `TryExec` makes sessions skip the entry once the binary is gone.
+pub fn render_autostart_entry(settings: &Settings) -> Result<String> {
+    let bundle = settings.bundle_settings();
+    let main_binary = settings
+        .binaries()
+        .iter()
+        .find(|b| b.main())
+        .context("no main binary found")?;
+    let binary = format!("/usr/bin/{}", main_binary.name());
+    let exec = match &bundle.desktop.autostart_args {
+        Some(args) => format!("{} {}", binary, args),
+        None => binary.clone(),
+    };
+
+    let mut entry = String::from("[Desktop Entry]\n");
+    entry.push_str("Type=Application\n");
+    push_value(&mut entry, "Name", settings.product_name());
+    let comment = bundle
+        .short_description
+        .as_deref()
+        .unwrap_or(settings.description());
+    if !comment.is_empty() {
+        push_value(&mut entry, "Comment", comment);
+    }
+    push_value(&mut entry, "TryExec", &binary);
+    push_value(&mut entry, "Exec", &exec);
+    push_value(&mut entry, "Icon", settings.product_name());
+    push_value(&mut entry, "Terminal", "false");
+    push_value(&mut entry, "X-GNOME-Autostart-enabled", "true");
+    Ok(entry)
+}
+
+/// MIME types from the desktop settings, file associations and URL schemes
+fn mime_types(settings: &Settings) -> Vec<String> {
+    let bundle = settings.bundle_settings();
+    let mut types: Vec<String> = Vec::new();
+    let derived = bundle
+        .file_associations
+        .iter()
+        .filter_map(|association| association.mime_type.clone())
+        .chain(
+            bundle
+                .url_schemes
+                .iter()
+                .flat_map(|scheme| scheme.schemes.iter())
+                .map(|scheme| format!("x-scheme-handler/{}", scheme)),
+        );
+    for mime_type in bundle.desktop.mime_types.iter().cloned().chain(derived) {
+        if !types.contains(&mime_type) {
+            types.push(mime_type);
+        }
+    }
+    types
+}
+
+fn push_value(entry: &mut String, key: &str, value: &str) {
+    let _ = writeln!(entry, "{}={}", key, escape(value));
+}
+
+/// Writes a `;`-terminated list, omitting the key when the list is empty
+fn push_list<S: AsRef<str>>(entry: &mut String, key: &str, values: &[S]) {
+    if values.is_empty() {
+        return;
+    }
+    let _ = write!(entry, "{}=", key);
+    for value in values {
+        let _ = write!(entry, "{};", escape(value.as_ref()).replace(';', "\\;"));
+    }
+    entry.push('\n');
+}
+
+/// Escapes a value per the Desktop Entry Specification
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::settings::{
+        BundleBinary, BundleSettings, FileAssociation, PackageSettings,
+        SettingsBuilder, UrlScheme,
+    };
+
+    #[test]
+    fn test_render_desktop_entry() {
+        let mut bundle = BundleSettings::default();
+        bundle.file_associations.push(FileAssociation {
+            ext: vec!["kdg".into()],
+            mime_type: Some("application/x-kodegen".into()),
+            ..Default::default()
+        });
+        bundle.url_schemes.push(UrlScheme {
+            schemes: vec!["kodegen".into()],
+            ..Default::default()
+        });
+        bundle.desktop.terminal = true;
+        bundle.desktop.keywords = vec!["code".into()];
+        bundle.desktop.actions.push(
+            toml::from_str(r#"
+                id = "new-window"
+                name = "New Window"
+                exec = "kodegen --new-window"
+            "#)
+            .unwrap(),
+        );
+
+        let settings = SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "Kodegen".into(),
+                version: "1.0.0".into(),
+                ..Default::default()
+            })
+            .bundle_settings(bundle)
+            .binaries(vec![BundleBinary::new("kodegen".into(), true)])
+            .build()
+            .unwrap();
+
+        let entry = render_desktop_entry(&settings).unwrap();
+        assert!(entry.contains("Exec=kodegen %U\n"));
+        assert!(entry.contains("Terminal=true\n"));
+        assert!(entry.contains("Keywords=code;\n"));
+        assert!(entry.contains("MimeType=application/x-kodegen;x-scheme-handler/kodegen;\n"));
+        assert!(entry.contains("Actions=new-window;\n"));
+        assert!(entry.ends_with("[Desktop Action new-window]\nName=New Window\nExec=kodegen --new-window\n"));
+    }
+
+    #[test]
+    fn test_render_autostart_entry() {
+        let mut bundle = BundleSettings::default();
+        bundle.desktop.autostart = true;
+        bundle.desktop.autostart_args = Some("--minimized".into());
+
+        let settings = SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "kodegen".into(),
+                version: "1.0.0".into(),
+                description: "Code generator".into(),
+                ..Default::default()
+            })
+            .bundle_settings(bundle)
+            .binaries(vec![BundleBinary::new("kodegen".into(), true)])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            autostart_path(&settings),
+            Some(PathBuf::from("etc/xdg/autostart/kodegen.desktop"))
+        );
+        assert_eq!(
+            render_autostart_entry(&settings).unwrap(),
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name=kodegen\n\
+             Comment=Code generator\n\
+             TryExec=/usr/bin/kodegen\n\
+             Exec=/usr/bin/kodegen --minimized\n\
+             Icon=kodegen\n\
+             Terminal=false\n\
+             X-GNOME-Autostart-enabled=true\n"
+        );
+    }
+
+    #[test]
+    fn test_push_list_and_escape() {
+        let mut entry = String::new();
+        push_list(&mut entry, "Keywords", &["code", "a;b"]);
+        push_list::<&str>(&mut entry, "MimeType", &[]);
+        push_value(&mut entry, "Comment", "line one\nline two");
+        assert_eq!(entry, "Keywords=code;a\\;b;\nComment=line one\\nline two\n");
+    }
+}
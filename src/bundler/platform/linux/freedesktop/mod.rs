@@ -2,6 +2,12 @@
 //!
 //! This module handles creation of .desktop files for Linux applications.
 
+mod desktop_entry;
+
+pub use desktop_entry::{
+    autostart_path, render_autostart_entry, render_desktop_entry, write_desktop_entry,
+};
+
 use crate::bundler::error::{ErrorExt, Result};
 use crate::bundler::resources::icons::{IconInfo, find_icon_for_size, load_and_resize};
 use std::path::{Path, PathBuf};
@@ -9,7 +15,7 @@ use std::path::{Path, PathBuf};
 /// Copy icons to freedesktop.org standard locations
 ///
 /// Creates directory structure:
-/// ```
+/// ```text
 /// /usr/share/icons/hicolor/
 ///   ├── 16x16/apps/{app_name}.png
 ///   ├── 32x32/apps/{app_name}.png
@@ -61,7 +67,7 @@ pub async fn install_icons(
                 .await
                 .fs_context("saving icon", &dest)?;
 
-            log::debug!("Installed {}x{} icon to {}", size, size, dest.display());
+            tracing::debug!("Installed {}x{} icon to {}", size, size, dest.display());
             installed.push(dest);
         }
     }
@@ -0,0 +1,187 @@
+//! Post-bundle validation of .deb and .rpm packages.
+//!
+//! Runs `lintian` on Debian packages and `rpmlint` on RPMs when enabled via
+//! [`LintMode`]. Both linters are installed in the bundler Docker image;
+//! on a native host a missing linter only produces a warning.
+
+use crate::bundler::{
+    error::{Error, ErrorExt, Result},
+    settings::LintMode,
+    utils::tools::find_system_tool,
+};
+use std::path::Path;
+
+/// Package linters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Linter {
+    /// Debian's lintian
+    Lintian,
+    /// RPM's rpmlint
+    Rpmlint,
+}
+
+impl Linter {
+    /// Executable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Linter::Lintian => "lintian",
+            Linter::Rpmlint => "rpmlint",
+        }
+    }
+}
+
+/// Severity of a linter finding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// Reported with `E:`
+    Error,
+    /// Reported with `W:`
+    Warning,
+}
+
+/// A single error or warning reported by a linter
+#[derive(Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// Whether the finding is an error or a warning
+    pub severity: Severity,
+    /// The linter's line, without the severity marker
+    pub message: String,
+}
+
+/// Lints `package` according to `mode`.
+///
+/// Findings are logged as warnings. In [`LintMode::Strict`] any error fails
+/// the bundle; warnings never do.
+#[tracing::instrument(name = "verify", skip_all, fields(linter = ?linter, path = %package.display()))]
+pub async fn lint_package(linter: Linter, package: &Path, mode: LintMode) -> Result<()> {
+    if mode == LintMode::Off {
+        return Ok(());
+    }
+
+    let Some(tool) = find_system_tool(&[linter.name()], "--version").await else {
+        tracing::warn!(
+            "{} is not installed; skipping validation of {}",
+            linter.name(),
+            package.display()
+        );
+        return Ok(());
+    };
+
+    tracing::info!("Validating {} with {}", package.display(), linter.name());
+    let mut command = tokio::process::Command::new(&tool.path);
+    if linter == Linter::Lintian {
+        // lintian refuses to run as root, which CI containers often are
+        command.arg("--allow-root");
+    }
+    let output = command
+        .arg(package)
+        .output()
+        .await
+        .fs_context("running package linter", &tool.path)?;
+
+    // Both linters exit non-zero when they find errors, so the exit status
+    // only matters when nothing could be parsed
+    let findings = parse_findings(&String::from_utf8_lossy(&output.stdout));
+    if findings.is_empty() && !output.status.success() {
+        tracing::warn!(
+            "{} exited with {:?}: {}",
+            linter.name(),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Ok(());
+    }
+
+    for finding in &findings {
+        let marker = match finding.severity {
+            Severity::Error => "E",
+            Severity::Warning => "W",
+        };
+        tracing::warn!("{}: {}: {}", linter.name(), marker, finding.message);
+    }
+
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    let warnings = findings.len() - errors;
+    tracing::info!(
+        "{}: {} error(s), {} warning(s) in {}",
+        linter.name(),
+        errors,
+        warnings,
+        package.display()
+    );
+
+    if mode == LintMode::Strict && errors > 0 {
+        return Err(Error::GenericError(format!(
+            "{} reported {} error(s) in {}",
+            linter.name(),
+            errors,
+            package.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Parses errors and warnings from lintian or rpmlint output.
+///
+/// lintian prints `E: pkg: tag detail`; rpmlint prints
+/// `pkg.rpm: E: tag detail`. Info, pedantic and overridden lines are ignored.
+pub fn parse_findings(output: &str) -> Vec<Finding> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (severity, message) = if let Some(rest) = line.strip_prefix("E: ") {
+                (Severity::Error, rest.to_string())
+            } else if let Some(rest) = line.strip_prefix("W: ") {
+                (Severity::Warning, rest.to_string())
+            } else if let Some((package, rest)) = line.split_once(": E: ") {
+                (Severity::Error, format!("{}: {}", package, rest))
+            } else if let Some((package, rest)) = line.split_once(": W: ") {
+                (Severity::Warning, format!("{}: {}", package, rest))
+            } else {
+                return None;
+            };
+            Some(Finding { severity, message })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lintian_findings() {
+        let output = "E: kodegen: no-copyright-file\n\
+                      W: kodegen: binary-without-manpage [usr/bin/kodegen]\n\
+                      I: kodegen: description-synopsis-might-not-be-phrased-properly\n\
+                      N: 1 tag overridden\n";
+        assert_eq!(
+            parse_findings(output),
+            vec![
+                Finding {
+                    severity: Severity::Error,
+                    message: "kodegen: no-copyright-file".into(),
+                },
+                Finding {
+                    severity: Severity::Warning,
+                    message: "kodegen: binary-without-manpage [usr/bin/kodegen]".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rpmlint_findings() {
+        let output = "kodegen.x86_64: E: no-changelogname-tag\n\
+                      kodegen.x86_64: W: no-documentation\n\
+                      1 packages and 0 specfiles checked; 1 errors, 1 warnings.\n";
+        let findings = parse_findings(output);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].message, "kodegen.x86_64: no-changelogname-tag");
+        assert_eq!(findings[1].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,184 @@
+//! Man page installation for Linux packages.
+//!
+//! Pages come from the `man_pages` setting (roff files such as `kodegen.1`)
+//! or, with `generate_man_page`, are rendered from the main binary's
+//! `--help` output in the style of help2man. Every page is gzip-compressed
+//! as Debian policy requires and installed to `/usr/share/man/man<section>/`.
+
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    settings::Settings,
+};
+use flate2::{Compression, write::GzEncoder};
+use std::io::Write;
+use std::path::Path;
+
+/// A compressed man page ready to be packaged
+#[derive(Debug)]
+pub struct ManPage {
+    /// Absolute install path, e.g. `/usr/share/man/man1/kodegen.1.gz`
+    pub install_path: String,
+    /// Gzip-compressed roff source
+    pub contents: Vec<u8>,
+}
+
+/// Collects the configured and generated man pages.
+pub async fn man_pages(settings: &Settings) -> Result<Vec<ManPage>> {
+    let bundle = settings.bundle_settings();
+    let mut pages = Vec::new();
+
+    for source in &bundle.man_pages {
+        let file_name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .with_context(|| format!("invalid man page path {}", source.display()))?;
+        let data = tokio::fs::read(source)
+            .await
+            .fs_context("reading man page", source)?;
+
+        // Already-compressed pages are installed as they are
+        let (name, contents) = match file_name.strip_suffix(".gz") {
+            Some(name) => (name.to_string(), data),
+            None => (file_name.clone(), gzip(&data)?),
+        };
+        let section = man_section(&name).with_context(|| {
+            format!(
+                "man page {} has no section suffix (expected e.g. {}.1)",
+                source.display(),
+                name
+            )
+        })?;
+        // Subsections like 3pm live in the directory of their base section
+        pages.push(ManPage {
+            install_path: format!("/usr/share/man/man{}/{}.gz", &section[..1], name),
+            contents,
+        });
+    }
+
+    if bundle.generate_man_page {
+        let main_binary = settings
+            .binaries()
+            .iter()
+            .find(|b| b.main())
+            .context("no main binary found")?;
+        let help = help_output(&settings.binary_path(main_binary)).await?;
+        let page = render_man_page(
+            main_binary.name(),
+            settings.version_string(),
+            settings.description(),
+            &help,
+        );
+        pages.push(ManPage {
+            install_path: format!("/usr/share/man/man1/{}.1.gz", main_binary.name()),
+            contents: gzip(page.as_bytes())?,
+        });
+    }
+
+    Ok(pages)
+}
+
+/// Writes the man pages below `data_dir` (the package root)
+pub async fn install_man_pages(settings: &Settings, data_dir: &Path) -> Result<()> {
+    for page in man_pages(settings).await? {
+        let dest = data_dir.join(page.install_path.trim_start_matches('/'));
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .fs_context("creating man page directory", parent)?;
+        }
+        tokio::fs::write(&dest, page.contents)
+            .await
+            .fs_context("writing man page", &dest)?;
+    }
+    Ok(())
+}
+
+/// Section of a man page file name (`kodegen.1` -> `1`, `kodegen-daemon.8` -> `8`)
+fn man_section(name: &str) -> Option<&str> {
+    let (_, section) = name.rsplit_once('.')?;
+    section
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some(section)
+}
+
+/// Runs `binary --help`
+async fn help_output(binary: &Path) -> Result<String> {
+    let output = tokio::process::Command::new(binary)
+        .arg("--help")
+        .output()
+        .await
+        .fs_context("running binary to generate man page", binary)?;
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "{} --help exited with {:?}; cannot generate a man page",
+            binary.display(),
+            output.status.code()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Renders `--help` output as a section 1 roff page
+fn render_man_page(name: &str, version: &str, description: &str, help: &str) -> String {
+    let mut page = format!(
+        ".TH \"{}\" 1 \"\" \"{} {}\" \"User Commands\"\n",
+        name.to_uppercase(),
+        name,
+        version
+    );
+    page.push_str(".SH NAME\n");
+    if description.is_empty() {
+        page.push_str(&format!("{}\n", escape_roff(name)));
+    } else {
+        page.push_str(&format!("{} \\- {}\n", escape_roff(name), escape_roff(description)));
+    }
+    page.push_str(".SH DESCRIPTION\n.nf\n");
+    for line in help.lines() {
+        page.push_str(&escape_roff(line));
+        page.push('\n');
+    }
+    page.push_str(".fi\n");
+    page
+}
+
+/// Escapes backslashes and protects lines that roff would read as requests
+fn escape_roff(line: &str) -> String {
+    let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    // Maximum compression as Debian policy asks; the header carries no mtime,
+    // so the output is reproducible
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_man_section() {
+        assert_eq!(man_section("kodegen.1"), Some("1"));
+        assert_eq!(man_section("kodegen-daemon.8"), Some("8"));
+        assert_eq!(man_section("kodegen.3pm"), Some("3pm"));
+        assert_eq!(man_section("README.md"), None);
+        assert_eq!(man_section("kodegen"), None);
+    }
+
+    #[test]
+    fn test_render_man_page() {
+        let help = "Usage: kodegen [OPTIONS]\n.hidden\n  -h, --help";
+        let page = render_man_page("kodegen", "1.0.0", "Code generator", help);
+        assert!(page.starts_with(".TH \"KODEGEN\" 1 \"\" \"kodegen 1.0.0\" \"User Commands\"\n"));
+        assert!(page.contains("kodegen \\- Code generator\n"));
+        assert!(page.contains("\\&.hidden\n"));
+        assert!(page.contains("  \\-h, \\-\\-help\n"));
+    }
+}
@@ -5,8 +5,10 @@
 //!
 //! # Supported Distributions
 //!
-//! - **Debian/Ubuntu**: `.deb` packages via [`debian`] module
-//! - **Fedora/RHEL/CentOS**: `.rpm` packages via [`rpm`] module
+//! - **Debian/Ubuntu**: `.deb` packages via [`debian`] module, with optional
+//!   Launchpad PPA uploads via [`ppa`]
+//! - **Fedora/RHEL/CentOS**: `.rpm` packages via [`rpm`] module, plus an optional
+//!   `.src.rpm` via [`srpm`] that [`copr`] can submit to Fedora COPR
 //! - **Universal**: AppImage portable format via [`appimage`] module
 //!
 //! # Build Requirements
@@ -28,8 +30,30 @@
 //!
 //! The [`freedesktop`] module provides FreeDesktop.org specification support
 //! for `.desktop` files, icons, and MIME types.
+//!
+//! # Shared Libraries
+//!
+//! The [`shared_libs`] module bundles non-system `.so` dependencies into
+//! AppImages (always) and into .deb/.rpm packages (opt-in via
+//! `bundle_shared_libs`). The [`shlibdeps`] module derives .deb `Depends:`
+//! from the libraries that stay on the system (opt-in via `auto_depends`),
+//! and [`elfdeps`] generates .rpm `Requires:` the way rpmbuild does.
+//!
+//! # Validation
+//!
+//! The [`lint`] module optionally runs `lintian` and `rpmlint` on the built
+//! packages.
 
 pub mod appimage;
+pub mod completions;
+pub mod copr;
 pub mod debian;
+pub mod elfdeps;
 pub mod freedesktop;
+pub mod lint;
+pub mod man_pages;
+pub mod ppa;
 pub mod rpm;
+pub mod shared_libs;
+pub mod shlibdeps;
+pub mod srpm;
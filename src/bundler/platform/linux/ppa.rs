@@ -0,0 +1,365 @@
+//! Launchpad PPA publishing.
+//!
+//! Launchpad only accepts signed Debian source packages and builds them
+//! without network access. The source package is therefore made from a
+//! `git archive` of the committed sources plus `cargo vendor` output, with a
+//! generated `debian/` directory whose `rules` build offline. One upload is
+//! made per configured Ubuntu series, with `~<series>1` appended to the
+//! version so the uploads do not collide.
+//!
+//! Requires `git`, `cargo`, `dpkg-buildpackage` (dpkg-dev) and `dput`.
+
+use super::debian::extended_description;
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    settings::{PpaSettings, Settings},
+};
+use flate2::{Compression, write::GzEncoder};
+use std::fmt::Write as _;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Builds the source package and uploads it for every configured series.
+pub async fn publish_ppa(settings: &Settings, ppa: &PpaSettings) -> Result<()> {
+    if ppa.series.is_empty() {
+        return Err(Error::GenericError(
+            "deb.ppa.series must name at least one Ubuntu series".into(),
+        ));
+    }
+    let source_dir = settings
+        .source_directory()
+        .context("PPA publishing needs the project source directory")?;
+
+    let package = super::debian::package_name(settings);
+    let upstream_version = debian_upstream_version(settings.version_string());
+    let work_dir = settings.project_out_directory().join("bundle/ppa");
+    if work_dir.exists() {
+        tokio::fs::remove_dir_all(&work_dir)
+            .await
+            .fs_context("removing previous PPA build tree", &work_dir)?;
+    }
+    tokio::fs::create_dir_all(&work_dir)
+        .await
+        .fs_context("creating PPA build tree", &work_dir)?;
+
+    tracing::info!("Preparing source package {} {}", package, upstream_version);
+    let tree_name = format!("{}-{}", package, upstream_version);
+    let tree = work_dir.join(&tree_name);
+    export_sources(source_dir, &work_dir, &tree_name).await?;
+    vendor_dependencies(&tree).await?;
+
+    // The orig tarball must not contain debian/ and is shared by all series
+    let orig = work_dir.join(format!("{}_{}.orig.tar.gz", package, upstream_version));
+    let (orig_path, tree_path, name) = (orig.clone(), tree.clone(), tree_name.clone());
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let file =
+            std::fs::File::create(&orig_path).fs_context("creating orig tarball", &orig_path)?;
+        let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::best()));
+        archive.append_dir_all(&name, &tree_path)?;
+        archive.into_inner()?.finish()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::GenericError(format!("Orig tarball task failed: {}", e)))??;
+
+    let maintainer = ppa
+        .maintainer
+        .clone()
+        .or_else(|| settings.bundle_settings().deb.maintainer.clone())
+        .or_else(|| settings.authors().and_then(|a| a.first().cloned()))
+        .context(
+            "deb.ppa.maintainer or deb.maintainer is required when the package has no authors",
+        )?;
+
+    let debian_dir = tree.join("debian");
+    tokio::fs::create_dir_all(debian_dir.join("source"))
+        .await
+        .fs_context("creating debian directory", &debian_dir)?;
+    write_file(
+        &debian_dir.join("control"),
+        &render_control(settings, &package, &maintainer),
+    )
+    .await?;
+    write_file(&debian_dir.join("rules"), &render_rules(settings, &package)).await?;
+    write_file(&debian_dir.join("source/format"), "3.0 (quilt)\n").await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let rules = debian_dir.join("rules");
+        tokio::fs::set_permissions(&rules, std::fs::Permissions::from_mode(0o755))
+            .await
+            .fs_context("setting executable permission", &rules)?;
+    }
+
+    let date = chrono::Utc::now().to_rfc2822();
+    for (index, series) in ppa.series.iter().enumerate() {
+        let version = format!("{}-{}~{}1", upstream_version, ppa.revision, series);
+        let changelog = render_changelog(&package, &version, series, &maintainer, &date);
+        write_file(&debian_dir.join("changelog"), &changelog).await?;
+
+        // Only the first upload carries the orig tarball; later series reuse it
+        let mut build = Command::new("dpkg-buildpackage");
+        build
+            .args(["-S", "-d"])
+            .arg(if index == 0 { "-sa" } else { "-sd" })
+            .current_dir(&tree);
+        if let Some(key) = &ppa.signing_key {
+            build.arg(format!("--sign-key={}", key));
+        }
+        run(&mut build, "dpkg-buildpackage").await?;
+
+        let changes = work_dir.join(format!("{}_{}_source.changes", package, version));
+        tracing::info!("Uploading {} to ppa:{}", version, ppa.ppa);
+        run(
+            Command::new("dput")
+                .arg(format!("ppa:{}", ppa.ppa))
+                .arg(&changes),
+            "dput",
+        )
+        .await?;
+    }
+
+    tracing::info!(
+        "✓ Uploaded {} to ppa:{} for {}",
+        package,
+        ppa.ppa,
+        ppa.series.join(", ")
+    );
+    Ok(())
+}
+
+/// Unpacks `git archive HEAD` into `work_dir/tree_name`
+async fn export_sources(source_dir: &Path, work_dir: &Path, tree_name: &str) -> Result<()> {
+    let archive = work_dir.join("upstream.tar");
+    run(
+        Command::new("git")
+            .arg("archive")
+            .arg("--format=tar")
+            .arg(format!("--prefix={}/", tree_name))
+            .arg("-o")
+            .arg(&archive)
+            .arg("HEAD")
+            .current_dir(source_dir),
+        "git archive",
+    )
+    .await?;
+
+    let (archive_path, dest) = (archive.clone(), work_dir.to_path_buf());
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let file = std::fs::File::open(&archive_path)
+            .fs_context("opening source archive", &archive_path)?;
+        tar::Archive::new(file).unpack(&dest)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::GenericError(format!("Source extraction task failed: {}", e)))??;
+
+    tokio::fs::remove_file(&archive)
+        .await
+        .fs_context("removing source archive", &archive)
+}
+
+/// Vendors crates.io dependencies and points cargo at them
+async fn vendor_dependencies(tree: &Path) -> Result<()> {
+    let output = Command::new("cargo")
+        .args(["vendor", "--locked", "vendor"])
+        .current_dir(tree)
+        .output()
+        .await
+        .fs_context("running cargo vendor", tree)?;
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "cargo vendor failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // cargo vendor prints the source replacement config to add
+    let config_path = tree.join(".cargo/config.toml");
+    let mut config = tokio::fs::read_to_string(&config_path)
+        .await
+        .unwrap_or_default();
+    if !config.is_empty() && !config.ends_with('\n') {
+        config.push('\n');
+    }
+    config.push_str(&String::from_utf8_lossy(&output.stdout));
+    write_file(&config_path, &config).await
+}
+
+/// Debian upstream versions sort `~` before everything, matching semver pre-releases
+fn debian_upstream_version(version: &str) -> String {
+    version.replacen('-', "~", 1)
+}
+
+fn render_control(settings: &Settings, package: &str, maintainer: &str) -> String {
+    let bundle = settings.bundle_settings();
+    let deb = &bundle.deb;
+
+    let mut control = String::new();
+    let _ = writeln!(control, "Source: {}", package);
+    let _ = writeln!(
+        control,
+        "Section: {}",
+        deb.section.as_deref().unwrap_or("utils")
+    );
+    let _ = writeln!(
+        control,
+        "Priority: {}",
+        deb.priority.as_deref().unwrap_or("optional")
+    );
+    let _ = writeln!(control, "Maintainer: {}", maintainer);
+    control.push_str("Build-Depends: debhelper-compat (= 13), cargo, rustc\n");
+    control.push_str("Standards-Version: 4.6.2\n");
+    if let Some(homepage) = deb.homepage.as_deref().or(settings.homepage()) {
+        let _ = writeln!(control, "Homepage: {}", homepage);
+    }
+    if let Some(vcs_git) = &deb.vcs_git {
+        let _ = writeln!(control, "Vcs-Git: {}", vcs_git);
+    }
+
+    let _ = write!(control, "\nPackage: {}\nArchitecture: any\n", package);
+    let mut depends = vec![
+        "${shlibs:Depends}".to_string(),
+        "${misc:Depends}".to_string(),
+    ];
+    depends.extend(deb.depends.iter().flatten().cloned());
+    let _ = writeln!(control, "Depends: {}", depends.join(", "));
+    for (field, values) in [
+        ("Recommends", &deb.recommends),
+        ("Provides", &deb.provides),
+        ("Conflicts", &deb.conflicts),
+        ("Replaces", &deb.replaces),
+    ] {
+        if let Some(values) = values {
+            let _ = writeln!(control, "{}: {}", field, values.join(", "));
+        }
+    }
+
+    let short = bundle
+        .short_description
+        .as_deref()
+        .unwrap_or(settings.description());
+    let _ = writeln!(
+        control,
+        "Description: {}",
+        if short.is_empty() {
+            "(no description)"
+        } else {
+            short
+        }
+    );
+    control.push_str(&extended_description(settings));
+    control
+}
+
+fn render_rules(settings: &Settings, package: &str) -> String {
+    // Launchpad builds from source; prebuilt external_bin sidecars can't ship
+    let bins: String = settings
+        .binaries()
+        .iter()
+        .filter(|b| !b.is_sidecar())
+        .map(|b| format!(" --bin {}", b.name()))
+        .collect();
+    let installs: String = settings
+        .binaries()
+        .iter()
+        .filter(|b| !b.is_sidecar())
+        .map(|b| {
+            format!(
+                "\tinstall -Dm0755 target/release/{0} debian/{1}/usr/bin/{0}\n",
+                b.name(),
+                package
+            )
+        })
+        .collect();
+
+    format!(
+        "#!/usr/bin/make -f\n\
+         \n\
+         export CARGO_HOME = $(CURDIR)/debian/cargo-home\n\
+         \n\
+         %:\n\
+         \tdh $@\n\
+         \n\
+         override_dh_auto_configure:\n\
+         \n\
+         override_dh_auto_build:\n\
+         \tcargo build --release --offline --locked{}\n\
+         \n\
+         override_dh_auto_test:\n\
+         \n\
+         override_dh_auto_install:\n\
+         {}\
+         \n\
+         override_dh_auto_clean:\n\
+         \trm -rf target debian/cargo-home\n",
+        bins, installs
+    )
+}
+
+fn render_changelog(
+    package: &str,
+    version: &str,
+    series: &str,
+    maintainer: &str,
+    date: &str,
+) -> String {
+    format!(
+        "{} ({}) {}; urgency=medium\n\n  * Release {}.\n\n -- {}  {}\n",
+        package, version, series, version, maintainer, date
+    )
+}
+
+async fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .fs_context("creating directory", parent)?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .fs_context("writing source package file", path)
+}
+
+/// Runs a command, failing with its stderr on a non-zero exit
+async fn run(command: &mut Command, name: &str) -> Result<()> {
+    let output = command
+        .output()
+        .await
+        .map_err(|e| Error::GenericError(format!("Failed to run {}: {}", name, e)))?;
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "{} failed ({:?}): {}",
+            name,
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debian_upstream_version() {
+        assert_eq!(debian_upstream_version("1.2.3"), "1.2.3");
+        assert_eq!(debian_upstream_version("1.2.3-beta.1"), "1.2.3~beta.1");
+    }
+
+    #[test]
+    fn test_render_changelog() {
+        let changelog = render_changelog(
+            "kodegen",
+            "1.0.0-1~noble1",
+            "noble",
+            "Dev <dev@example.com>",
+            "Thu, 15 Oct 2026 08:00:00 +0000",
+        );
+        assert_eq!(
+            changelog,
+            "kodegen (1.0.0-1~noble1) noble; urgency=medium\n\n  * Release 1.0.0-1~noble1.\n\n -- Dev <dev@example.com>  Thu, 15 Oct 2026 08:00:00 +0000\n"
+        );
+    }
+}
@@ -4,10 +4,17 @@
 //! dependencies, and file structure.
 
 use crate::bundler::{
+    BundleStage, PackageType,
     error::{Error, ErrorExt, Result},
+    platform::linux::{
+        completions, copr, elfdeps::elf_requires, freedesktop, man_pages,
+        shared_libs::bundle_shared_libraries, srpm,
+    },
+    resources::{files, license, locales},
     settings::{Arch, Settings},
+    utils::progress_bar,
 };
-use std::{io::Write, path::PathBuf};
+use std::{collections::HashSet, io::Write, path::PathBuf};
 
 /// Bundle project as RPM package
 pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
@@ -27,14 +34,14 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         }
     };
 
-    log::info!("Building RPM package for {}", settings.product_name());
-
-    // Determine license from bundle settings or use default
-    let license = settings
-        .bundle_settings()
-        .copyright
-        .as_deref()
-        .unwrap_or("Unknown");
+    tracing::info!("Building RPM package for {}", settings.product_name());
+    let progress = settings.progress();
+    progress.report(
+        PackageType::Rpm,
+        BundleStage::Building,
+        Some(10),
+        "Generating package metadata",
+    );
 
     // Get summary (short description)
     let summary = settings
@@ -50,16 +57,24 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         Some("xz") => rpm::CompressionType::Xz,
         Some("zstd") => rpm::CompressionType::Zstd,
         Some("bzip2") => rpm::CompressionType::Bzip2,
-        _ => rpm::CompressionType::Gzip, // default
+        Some("none") => rpm::CompressionType::None,
+        None => rpm::CompressionType::Gzip, // default
+        Some(other) => {
+            return Err(Error::GenericError(format!(
+                "Unknown RPM compression '{}'. Expected gzip, xz, zstd, bzip2 or none",
+                other
+            )));
+        }
     };
 
     let build_config = rpm::BuildConfig::default().compression(compression);
 
-    // Create PackageBuilder
+    // Create PackageBuilder; the Name tag also names the %license directory
+    let name = settings.product_name();
     let mut builder = rpm::PackageBuilder::new(
-        settings.product_name(),
+        name,
         settings.version_string(),
-        license,
+        &license_tag(settings),
         arch,
         summary,
     )
@@ -72,14 +87,22 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         builder = builder.description(desc);
     }
 
-    if let Some(homepage) = settings.homepage() {
-        builder = builder.url(homepage);
+    if let Some(url) = url_tag(settings) {
+        builder = builder.url(url);
     }
 
-    if let Some(vendor) = settings.bundle_settings().publisher.as_ref() {
+    if let Some(vendor) = vendor_tag(settings) {
         builder = builder.vendor(vendor);
     }
 
+    if let Some(packager) = &settings.rpm_settings().packager {
+        builder = builder.packager(packager);
+    }
+
+    if let Some(group) = &settings.rpm_settings().group {
+        builder = builder.group(group);
+    }
+
     // Add dependencies
     if let Some(depends) = &settings.rpm_settings().depends {
         for dep_str in depends {
@@ -121,12 +144,117 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         }
     }
 
+    // Add suggests
+    if let Some(suggests) = &settings.rpm_settings().suggests {
+        for sug_str in suggests {
+            let dep = parse_dependency(sug_str)?;
+            builder = builder.suggests(dep);
+        }
+    }
+
+    // Add supplements
+    if let Some(supplements) = &settings.rpm_settings().supplements {
+        for sup_str in supplements {
+            let dep = parse_dependency(sup_str)?;
+            builder = builder.supplements(dep);
+        }
+    }
+
+    // Add enhances
+    if let Some(enhances) = &settings.rpm_settings().enhances {
+        for enh_str in enhances {
+            let dep = parse_dependency(enh_str)?;
+            builder = builder.enhances(dep);
+        }
+    }
+
+    // Binaries are packaged from their build location, or from a staging
+    // copy when shared library bundling has to patch their RPATH
+    progress.report(
+        PackageType::Rpm,
+        BundleStage::Copying,
+        Some(30),
+        "Adding binaries and resources",
+    );
+    let mut binary_sources: Vec<(PathBuf, &str)> = settings
+        .binaries()
+        .iter()
+        .map(|binary| (settings.binary_path(binary), binary.name()))
+        .collect();
+
+    let mut bundled_libraries = Vec::new();
+    if settings.rpm_settings().bundle_shared_libs && !settings.skip_phases().dylib_bundling {
+        let staging_dir = settings.project_out_directory().join("bundle/rpm/staging");
+        if staging_dir.exists() {
+            tokio::fs::remove_dir_all(&staging_dir)
+                .await
+                .fs_context("removing old RPM staging directory", &staging_dir)?;
+        }
+        let staged_bin_dir = staging_dir.join("bin");
+        tokio::fs::create_dir_all(&staged_bin_dir)
+            .await
+            .fs_context("creating RPM staging directory", &staged_bin_dir)?;
+
+        for (src_path, name) in &mut binary_sources {
+            let staged = staged_bin_dir.join(&**name);
+            tokio::fs::copy(&*src_path, &staged)
+                .await
+                .fs_context("staging binary", &*src_path)?;
+            *src_path = staged;
+        }
+
+        let staged: Vec<PathBuf> = binary_sources.iter().map(|(path, _)| path.clone()).collect();
+        let rpath = format!("$ORIGIN/../lib/{}", settings.product_name());
+        bundled_libraries =
+            bundle_shared_libraries(&staged, &staging_dir.join("lib"), &rpath).await?;
+
+        for library in &bundled_libraries {
+            let Some(file_name) = library.file_name() else {
+                continue;
+            };
+            let dest_path = format!(
+                "/usr/lib/{}/{}",
+                settings.product_name(),
+                file_name.to_string_lossy()
+            );
+            let content = tokio::fs::read(library)
+                .await
+                .fs_context("reading shared library", library)?;
+            builder = builder.with_file_contents(
+                content,
+                rpm::FileOptions::new(&dest_path)
+                    .mode(rpm::FileMode::regular(0o755))
+                    .user("root")
+                    .group("root"),
+            )?;
+        }
+    }
+
+    // Add rpmbuild-style ELF requirements
+    if settings.rpm_settings().auto_requires {
+        let bundled_sonames: HashSet<String> = bundled_libraries
+            .iter()
+            .filter_map(|library| library.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        let mut files: Vec<PathBuf> = binary_sources.iter().map(|(path, _)| path.clone()).collect();
+        files.extend(bundled_libraries.iter().cloned());
+
+        let manual = settings.rpm_settings().depends.as_deref().unwrap_or_default();
+        for requirement in elf_requires(&files, &bundled_sonames)? {
+            if manual.iter().any(|dep| dep.split_whitespace().next() == Some(requirement.as_str())) {
+                continue;
+            }
+            tracing::debug!("Adding auto requirement: {}", requirement);
+            builder = builder.requires(rpm::Dependency::any(requirement));
+        }
+    }
+
     // Add binaries
-    for binary in settings.binaries() {
-        let src_path = settings.binary_path(binary);
-        let dest_path = format!("/usr/bin/{}", binary.name());
+    for (src_path, name) in binary_sources {
+        let dest_path = format!("/usr/bin/{}", name);
 
-        log::debug!("Adding binary: {} -> {}", src_path.display(), dest_path);
+        tracing::debug!("Adding binary: {} -> {}", src_path.display(), dest_path);
 
         // Read binary content
         let content = tokio::fs::read(&src_path)
@@ -143,6 +271,102 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         )?;
     }
 
+    // Add resources to /usr/share/<name> and translations to /usr/share/locale
+    let resource_dir = files::linux_resource_dir(settings);
+    let mut resources: Vec<files::ResourceFile> = files::resource_files(settings)?
+        .into_iter()
+        .map(|file| files::ResourceFile {
+            target: resource_dir.join(&file.target),
+            source: file.source,
+        })
+        .collect();
+    resources.extend(
+        locales::localizations(settings)?.catalogs_in(std::path::Path::new(locales::LINUX_LOCALE_DIR)),
+    );
+    for file in resources {
+        let dest_path = format!("/{}", file.target.display());
+        let content = tokio::fs::read(&file.source)
+            .await
+            .fs_context("reading resource", &file.source)?;
+        builder = builder.with_file_contents(
+            content,
+            rpm::FileOptions::new(&dest_path)
+                .mode(rpm::FileMode::regular(resource_mode(&file.source)))
+                .user("root")
+                .group("root"),
+        )?;
+    }
+
+    // Add license texts as %license files
+    let license_dir = license::rpm_license_dir(name);
+    for file in license::license_files(settings)? {
+        let dest_path = format!("/{}", license_dir.join(&file.target).display());
+        let content = tokio::fs::read(&file.source)
+            .await
+            .fs_context("reading license file", &file.source)?;
+        builder = builder.with_file_contents(
+            content,
+            rpm::FileOptions::new(&dest_path)
+                .mode(rpm::FileMode::regular(0o644))
+                .user("root")
+                .group("root")
+                .is_license(),
+        )?;
+    }
+
+    // Add desktop entry, preferring a custom one
+    let desktop_entry = match &settings.rpm_settings().desktop_template {
+        Some(template) => tokio::fs::read(template)
+            .await
+            .fs_context("reading desktop template", template)?,
+        None => freedesktop::render_desktop_entry(settings)?.into_bytes(),
+    };
+    builder = builder.with_file_contents(
+        desktop_entry,
+        rpm::FileOptions::new(format!(
+            "/usr/share/applications/{}.desktop",
+            settings.product_name()
+        ))
+        .mode(rpm::FileMode::regular(0o644))
+        .user("root")
+        .group("root"),
+    )?;
+
+    // Add the autostart entry; erasing the package removes it
+    if let Some(path) = freedesktop::autostart_path(settings) {
+        builder = builder.with_file_contents(
+            freedesktop::render_autostart_entry(settings)?.into_bytes(),
+            rpm::FileOptions::new(format!("/{}", path.display()))
+                .mode(rpm::FileMode::regular(0o644))
+                .user("root")
+                .group("root")
+                .is_config_noreplace(),
+        )?;
+    }
+
+    // Add man pages
+    for page in man_pages::man_pages(settings).await? {
+        builder = builder.with_file_contents(
+            page.contents,
+            rpm::FileOptions::new(&page.install_path)
+                .mode(rpm::FileMode::regular(0o644))
+                .user("root")
+                .group("root")
+                .is_doc(),
+        )?;
+    }
+
+    // Add shell completions
+    for file in completions::completion_files(settings, completions::Distro::RedHat).await? {
+        builder = builder.with_file_contents(
+            file.contents,
+            rpm::FileOptions::new(&file.install_path)
+                .mode(rpm::FileMode::regular(0o644))
+                .user("root")
+                .group("root"),
+        )?;
+    }
+
     // Add custom files from RpmSettings
     for (dest, src) in &settings.rpm_settings().files {
         let content = tokio::fs::read(src)
@@ -188,13 +412,20 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
     }
 
     // Build the package
-    let pkg = tokio::task::spawn_blocking(move || {
+    progress.report(
+        PackageType::Rpm,
+        BundleStage::Compressing,
+        Some(70),
+        "Compressing package payload",
+    );
+    let build = tokio::task::spawn_blocking(move || {
         builder
             .build()
             .map_err(|e| Error::GenericError(format!("Failed to build RPM package: {}", e)))
-    })
-    .await
-    .map_err(|e| Error::GenericError(format!("Task join error: {}", e)))??;
+    });
+    let pkg = progress_bar::spin("Compressing RPM payload", build)
+        .await
+        .map_err(|e| Error::GenericError(format!("Task join error: {}", e)))??;
 
     // Create output directory
     let output_dir = settings.project_out_directory().join("bundle/rpm");
@@ -224,9 +455,43 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
 
     file.flush().fs_context("flushing RPM file", &output_path)?;
 
-    log::info!("✓ Created RPM: {}", output_path.display());
+    tracing::info!("✓ Created RPM: {}", output_path.display());
+
+    let mut paths = vec![output_path];
+    let copr_settings = settings.rpm_settings().copr.as_ref();
+    if settings.rpm_settings().srpm || copr_settings.is_some() {
+        let srpm_path = srpm::bundle_srpm(settings).await?;
+        if let Some(copr_settings) = copr_settings {
+            copr::submit_build(&srpm_path, copr_settings).await?;
+        }
+        paths.push(srpm_path);
+    }
+
+    Ok(paths)
+}
+
+/// `License` tag: `rpm.license`, else the crate's SPDX expression with
+/// Cargo's deprecated `/` separator as `OR`
+pub(super) fn license_tag(settings: &Settings) -> String {
+    match (&settings.rpm_settings().license, settings.license()) {
+        (Some(license), _) => license.clone(),
+        (None, Some(expression)) => expression.replace('/', " OR "),
+        (None, None) => "Unknown".to_string(),
+    }
+}
 
-    Ok(vec![output_path])
+/// `URL` tag: `rpm.url`, else the crate's homepage
+pub(super) fn url_tag(settings: &Settings) -> Option<&str> {
+    settings.rpm_settings().url.as_deref().or(settings.homepage())
+}
+
+/// `Vendor` tag: `rpm.vendor`, else the bundle publisher
+pub(super) fn vendor_tag(settings: &Settings) -> Option<&str> {
+    settings
+        .rpm_settings()
+        .vendor
+        .as_deref()
+        .or(settings.bundle_settings().publisher.as_deref())
 }
 
 /// Parse a dependency string into an rpm::Dependency
@@ -268,6 +533,15 @@ fn parse_dependency(dep_str: &str) -> Result<rpm::Dependency> {
     }
 }
 
+/// Installed mode of a resource: executable files stay executable
+fn resource_mode(path: &std::path::Path) -> u16 {
+    use std::os::unix::fs::PermissionsExt;
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.permissions().mode() & 0o111 != 0 => 0o755,
+        _ => 0o644,
+    }
+}
+
 /// Extension trait for accessing RPM settings
 trait SettingsExt {
     fn rpm_settings(&self) -> &crate::bundler::settings::RpmSettings;
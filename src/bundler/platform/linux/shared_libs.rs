@@ -0,0 +1,259 @@
+//! Shared library dependency discovery and bundling for Linux packages.
+//!
+//! The Linux counterpart of `macos/dylib.rs`: finds the non-system `.so`
+//! dependencies of the bundled binaries, copies them into a private lib
+//! directory and points the binaries at it with an `$ORIGIN`-relative
+//! RPATH, so packages run on systems missing e.g. libssl.
+
+use crate::bundler::error::{Error, ErrorExt, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Libraries that are part of the base system and must never be bundled.
+///
+/// glibc and the dynamic loader are tied to the host kernel and to each
+/// other; the graphics, audio and font stacks are tied to host drivers and
+/// configuration. Matched against the soname without its version suffix.
+const SYSTEM_LIBRARIES: &[&str] = &[
+    // glibc and the loader
+    "ld-linux",
+    "ld-linux-x86-64",
+    "ld-linux-aarch64",
+    "linux-vdso",
+    "libc",
+    "libm",
+    "libdl",
+    "libpthread",
+    "librt",
+    "libresolv",
+    "libutil",
+    "libnsl",
+    "libanl",
+    "libBrokenLocale",
+    "libmvec",
+    "libgcc_s",
+    // Graphics drivers and display servers
+    "libGL",
+    "libGLX",
+    "libGLdispatch",
+    "libEGL",
+    "libGLESv2",
+    "libOpenGL",
+    "libdrm",
+    "libgbm",
+    "libvulkan",
+    "libX11",
+    "libX11-xcb",
+    "libxcb",
+    "libwayland-client",
+    "libwayland-egl",
+    // Audio and fonts
+    "libasound",
+    "libpulse",
+    "libjack",
+    "libfontconfig",
+    "libfreetype",
+];
+
+/// A shared library found by `ldd`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedLibrary {
+    /// Soname as recorded in DT_NEEDED (e.g. `libssl.so.3`)
+    pub soname: String,
+    /// Resolved location on the build host; `None` if `ldd` reported "not found"
+    pub path: Option<PathBuf>,
+}
+
+/// Bundles the non-system shared libraries of `binaries` into `lib_dir`.
+///
+/// Each binary is rewritten in place to use `rpath` (e.g.
+/// `$ORIGIN/../lib`), and every bundled library gets `$ORIGIN` so the
+/// libraries find each other. Dependencies are the transitive closure
+/// reported by `ldd`, so the binaries must be runnable by the build host's
+/// loader (true for native and in-container builds).
+///
+/// # Returns
+/// The bundled library paths inside `lib_dir`.
+pub async fn bundle_shared_libraries(
+    binaries: &[PathBuf],
+    lib_dir: &Path,
+    rpath: &str,
+) -> Result<Vec<PathBuf>> {
+    let mut seen = HashSet::new();
+    let mut libraries = Vec::new();
+
+    for binary in binaries {
+        if !is_dynamic_elf(binary)? {
+            tracing::debug!("{} is not a dynamic ELF binary, skipping", binary.display());
+            continue;
+        }
+
+        tracing::info!("Discovering shared library dependencies for {}", binary.display());
+        for library in ldd(binary).await? {
+            if is_system_library(&library.soname) || !seen.insert(library.soname.clone()) {
+                continue;
+            }
+            let Some(path) = library.path else {
+                return Err(Error::GenericError(format!(
+                    "{} needs {}, which is not installed on the build host",
+                    binary.display(),
+                    library.soname
+                )));
+            };
+            tracing::debug!("  - {} ({})", library.soname, path.display());
+            libraries.push((library.soname, path));
+        }
+    }
+
+    if libraries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tokio::fs::create_dir_all(lib_dir)
+        .await
+        .fs_context("creating shared library directory", lib_dir)?;
+
+    let mut bundled = Vec::with_capacity(libraries.len());
+    for (soname, path) in libraries {
+        // Copy under the soname the loader asks for, following symlinks
+        let dest = lib_dir.join(&soname);
+        tokio::fs::copy(&path, &dest)
+            .await
+            .fs_context("copying shared library", &path)?;
+        set_rpath(&dest, "$ORIGIN").await?;
+        bundled.push(dest);
+    }
+
+    for binary in binaries {
+        if is_dynamic_elf(binary)? {
+            set_rpath(binary, rpath).await?;
+        }
+    }
+
+    tracing::info!("Bundled {} shared libraries into {}", bundled.len(), lib_dir.display());
+    Ok(bundled)
+}
+
+/// Whether `path` is an ELF file with dynamic dependencies
+pub fn is_dynamic_elf(path: &Path) -> Result<bool> {
+    let buffer = std::fs::read(path).fs_context("reading binary", path)?;
+    Ok(match goblin::Object::parse(&buffer) {
+        Ok(goblin::Object::Elf(elf)) => elf.dynamic.is_some() && !elf.libraries.is_empty(),
+        _ => false,
+    })
+}
+
+/// Determines if a soname belongs to the base system.
+fn is_system_library(soname: &str) -> bool {
+    let stem = soname.split(".so").next().unwrap_or(soname);
+    SYSTEM_LIBRARIES.contains(&stem)
+}
+
+/// Runs `ldd` and parses the resolved dependencies
+pub async fn ldd(binary: &Path) -> Result<Vec<SharedLibrary>> {
+    let output = Command::new("ldd")
+        .arg(binary)
+        .output()
+        .await
+        .fs_context("failed to run ldd", binary)?;
+
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "ldd failed for {}: {}",
+            binary.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(parse_ldd(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses `ldd` output lines of the form `libfoo.so.1 => /path/libfoo.so.1 (0x...)`
+///
+/// Lines without `=>` (the vdso and the loader itself) are ignored.
+fn parse_ldd(output: &str) -> Vec<SharedLibrary> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (soname, target) = line.trim().split_once("=>")?;
+            let target = target.trim();
+            let path = if target.starts_with("not found") {
+                None
+            } else {
+                let path = target.split(" (").next()?.trim();
+                (!path.is_empty()).then(|| PathBuf::from(path))
+            };
+            Some(SharedLibrary {
+                soname: soname.trim().to_string(),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Replaces the RPATH/RUNPATH of an ELF file using `patchelf`
+async fn set_rpath(path: &Path, rpath: &str) -> Result<()> {
+    let output = Command::new("patchelf")
+        .arg("--set-rpath")
+        .arg(rpath)
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| {
+            Error::GenericError(format!(
+                "Failed to run patchelf (required to bundle shared libraries; install the `patchelf` package): {}",
+                e
+            ))
+        })?;
+
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "patchelf --set-rpath {} failed for {}: {}",
+            rpath,
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldd() {
+        let output = "\tlinux-vdso.so.1 (0x00007ffd5a7f1000)\n\
+                      \tlibssl.so.3 => /lib/x86_64-linux-gnu/libssl.so.3 (0x00007f1e2c000000)\n\
+                      \tlibfoo.so.1 => not found\n\
+                      \tlibc.so.6 => /lib/x86_64-linux-gnu/libc.so.6 (0x00007f1e2bc00000)\n\
+                      \t/lib64/ld-linux-x86-64.so.2 (0x00007f1e2c2d0000)\n";
+        assert_eq!(
+            parse_ldd(output),
+            vec![
+                SharedLibrary {
+                    soname: "libssl.so.3".to_string(),
+                    path: Some(PathBuf::from("/lib/x86_64-linux-gnu/libssl.so.3")),
+                },
+                SharedLibrary {
+                    soname: "libfoo.so.1".to_string(),
+                    path: None,
+                },
+                SharedLibrary {
+                    soname: "libc.so.6".to_string(),
+                    path: Some(PathBuf::from("/lib/x86_64-linux-gnu/libc.so.6")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_system_library() {
+        assert!(is_system_library("libc.so.6"));
+        assert!(is_system_library("libGL.so.1"));
+        assert!(is_system_library("ld-linux-x86-64.so.2"));
+        assert!(!is_system_library("libssl.so.3"));
+        assert!(!is_system_library("libcrypto.so.3"));
+    }
+}
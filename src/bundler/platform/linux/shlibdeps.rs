@@ -0,0 +1,219 @@
+//! Automatic Debian `Depends:` calculation, in the spirit of `dpkg-shlibdeps`.
+//!
+//! Every shared library a packaged binary loads is mapped to the Debian
+//! package that installed it (`dpkg -S`). glibc additionally gets a minimum
+//! version taken from the highest `GLIBC_x.y` symbol version the binaries
+//! require, so packages built on a newer system refuse to install where
+//! they could not run.
+
+use super::shared_libs::{self, SharedLibrary};
+use crate::bundler::error::{ErrorExt, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Computes `Depends:` entries for the ELF binaries in `binaries`.
+///
+/// Libraries resolved inside `data_dir` (bundled via `bundle_shared_libs`)
+/// are not dependencies. Libraries not owned by any installed package are
+/// skipped with a warning, since no package could satisfy them.
+pub async fn compute_depends(binaries: &[PathBuf], data_dir: &Path) -> Result<Vec<String>> {
+    let mut owners: HashMap<PathBuf, Option<String>> = HashMap::new();
+    // Package name -> minimum version, if any
+    let mut depends: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut glibc_version: Option<Vec<u32>> = None;
+
+    for binary in binaries {
+        if !shared_libs::is_dynamic_elf(binary)? {
+            continue;
+        }
+
+        if let Some(version) = required_glibc_version(binary)?
+            && glibc_version.as_ref().is_none_or(|current| &version > current)
+        {
+            glibc_version = Some(version);
+        }
+
+        for SharedLibrary { soname, path } in shared_libs::ldd(binary).await? {
+            let Some(path) = path else {
+                tracing::warn!(
+                    "{} needs {}, which is not installed; it cannot be added to Depends",
+                    binary.display(),
+                    soname
+                );
+                continue;
+            };
+            if path.starts_with(data_dir) {
+                continue; // Bundled with the package
+            }
+
+            if !owners.contains_key(&path) {
+                let owner = owning_package(&path).await;
+                owners.insert(path.clone(), owner);
+            }
+            match &owners[&path] {
+                Some(package) => {
+                    let minimum = soname
+                        .starts_with("libc.so")
+                        .then(|| glibc_version.as_deref().map(format_version))
+                        .flatten();
+                    let entry = depends.entry(package.clone()).or_default();
+                    if minimum.is_some() {
+                        *entry = minimum;
+                    }
+                }
+                None => tracing::warn!(
+                    "{} ({}) is not owned by any Debian package; bundle it with bundle_shared_libs",
+                    soname,
+                    path.display()
+                ),
+            }
+        }
+    }
+
+    let depends: Vec<String> = depends
+        .into_iter()
+        .map(|(package, minimum)| match minimum {
+            Some(version) => format!("{} (>= {})", package, version),
+            None => package,
+        })
+        .collect();
+    tracing::debug!("Computed Depends: {}", depends.join(", "));
+    Ok(depends)
+}
+
+/// Merges computed dependencies into the manual list.
+///
+/// Manual entries come first and win: a computed entry is dropped when the
+/// manual list already names its package.
+pub fn merge_depends(manual: &[String], computed: Vec<String>) -> Vec<String> {
+    let mut merged = manual.to_vec();
+    for entry in computed {
+        let name = package_name(&entry);
+        if !merged.iter().any(|existing| package_name(existing) == name) {
+            merged.push(entry);
+        }
+    }
+    merged
+}
+
+/// Package name of a Depends entry (`libc6 (>= 2.31)` -> `libc6`)
+fn package_name(entry: &str) -> &str {
+    entry
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ':')
+        .next()
+        .unwrap_or(entry)
+}
+
+/// Highest `GLIBC_x.y` version needed by an ELF binary
+fn required_glibc_version(binary: &Path) -> Result<Option<Vec<u32>>> {
+    let buffer = std::fs::read(binary).fs_context("reading binary", binary)?;
+    let Ok(goblin::Object::Elf(elf)) = goblin::Object::parse(&buffer) else {
+        return Ok(None);
+    };
+    let Some(verneed) = &elf.verneed else {
+        return Ok(None);
+    };
+
+    Ok(verneed
+        .iter()
+        .flat_map(|need| need.iter().collect::<Vec<_>>())
+        .filter_map(|aux| elf.dynstrtab.get_at(aux.vna_name))
+        .filter_map(parse_glibc_version)
+        .max())
+}
+
+/// Parses `GLIBC_2.34` into `[2, 34]`; `GLIBC_PRIVATE` and others give `None`
+fn parse_glibc_version(name: &str) -> Option<Vec<u32>> {
+    name.strip_prefix("GLIBC_")?
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+fn format_version(version: &[u32]) -> String {
+    version
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Finds the installed package owning `path` with `dpkg -S`
+///
+/// With merged /usr, dpkg may record `/lib/...` while ldd reports
+/// `/usr/lib/...` (or the reverse), so both spellings and the canonical
+/// path are tried.
+async fn owning_package(path: &Path) -> Option<String> {
+    let mut candidates = vec![path.to_path_buf()];
+    if let Ok(canonical) = path.canonicalize() {
+        candidates.push(canonical);
+    }
+    for candidate in candidates.clone() {
+        let text = candidate.to_string_lossy().into_owned();
+        if let Some(rest) = text.strip_prefix("/usr") {
+            candidates.push(PathBuf::from(rest));
+        } else {
+            candidates.push(PathBuf::from(format!("/usr{}", text)));
+        }
+    }
+
+    for candidate in candidates {
+        let Ok(output) = Command::new("dpkg").arg("-S").arg(&candidate).output().await else {
+            tracing::warn!("dpkg not available; cannot compute Depends");
+            return None;
+        };
+        if output.status.success()
+            && let Some(package) = parse_dpkg_search(&String::from_utf8_lossy(&output.stdout))
+        {
+            return Some(package);
+        }
+    }
+    None
+}
+
+/// Extracts the package from `dpkg -S` output (`libssl3:amd64: /usr/lib/...`)
+fn parse_dpkg_search(output: &str) -> Option<String> {
+    let line = output.lines().find(|line| !line.starts_with("diversion"))?;
+    let (packages, _) = line.split_once(": ")?;
+    let package = packages.split(", ").next()?;
+    let name = package.split(':').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpkg_search() {
+        assert_eq!(
+            parse_dpkg_search("libssl3:amd64: /usr/lib/x86_64-linux-gnu/libssl.so.3\n").as_deref(),
+            Some("libssl3")
+        );
+        assert_eq!(
+            parse_dpkg_search("libc6:amd64, libc6:i386: /lib/libc.so.6\n").as_deref(),
+            Some("libc6")
+        );
+        assert_eq!(parse_dpkg_search("").as_deref(), None);
+    }
+
+    #[test]
+    fn test_parse_glibc_version() {
+        assert_eq!(parse_glibc_version("GLIBC_2.34"), Some(vec![2, 34]));
+        assert_eq!(parse_glibc_version("GLIBC_2.2.5"), Some(vec![2, 2, 5]));
+        assert_eq!(parse_glibc_version("GLIBC_PRIVATE"), None);
+        assert_eq!(parse_glibc_version("OPENSSL_3.0.0"), None);
+        assert!(parse_glibc_version("GLIBC_2.34") > parse_glibc_version("GLIBC_2.4"));
+    }
+
+    #[test]
+    fn test_merge_depends() {
+        let manual = vec!["libc6 (>= 2.31)".to_string(), "ca-certificates".to_string()];
+        let computed = vec!["libc6 (>= 2.34)".to_string(), "libssl3".to_string()];
+        assert_eq!(
+            merge_depends(&manual, computed),
+            vec!["libc6 (>= 2.31)", "ca-certificates", "libssl3"]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Source RPM (.src.rpm) generation.
+//!
+//! Fedora and COPR build from source packages rather than binary RPMs. The
+//! SRPM pairs a `git archive` tarball of the project with a generated spec
+//! that builds the binaries with cargo and installs them together with the
+//! desktop entry. It is assembled with `rpmbuild -bs`.
+
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    platform::linux::{
+        freedesktop,
+        rpm::{license_tag, url_tag, vendor_tag},
+    },
+    settings::Settings,
+    utils::tools::find_system_tool,
+};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Builds `<name>-<version>-<release>.src.rpm` into `bundle/rpm/`.
+///
+/// Only committed sources are packaged (`git archive HEAD`).
+pub async fn bundle_srpm(settings: &Settings) -> Result<PathBuf> {
+    let source_dir = settings
+        .source_directory()
+        .context("SRPM generation needs the project source directory")?;
+    let rpmbuild = find_system_tool(&["rpmbuild"], "--version")
+        .await
+        .context("rpmbuild is required to build a source RPM")?;
+
+    tracing::info!("Building source RPM for {}", settings.product_name());
+
+    let output_dir = settings.project_out_directory().join("bundle/rpm");
+    let top_dir = output_dir.join("srpm");
+    if top_dir.exists() {
+        tokio::fs::remove_dir_all(&top_dir)
+            .await
+            .fs_context("removing previous SRPM build tree", &top_dir)?;
+    }
+    for dir in ["SOURCES", "SPECS", "SRPMS"] {
+        let path = top_dir.join(dir);
+        tokio::fs::create_dir_all(&path)
+            .await
+            .fs_context("creating SRPM build tree", &path)?;
+    }
+
+    let prefix = format!("{}-{}", settings.product_name(), settings.version_string());
+    let tarball = top_dir.join("SOURCES").join(format!("{}.tar.gz", prefix));
+    let output = Command::new("git")
+        .arg("archive")
+        .arg("--format=tar.gz")
+        .arg(format!("--prefix={}/", prefix))
+        .arg("-o")
+        .arg(&tarball)
+        .arg("HEAD")
+        .current_dir(source_dir)
+        .output()
+        .await
+        .fs_context("running git archive", source_dir)?;
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "git archive failed in {}: {}",
+            source_dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let spec_path = top_dir
+        .join("SPECS")
+        .join(format!("{}.spec", settings.product_name()));
+    tokio::fs::write(&spec_path, render_spec(settings)?)
+        .await
+        .fs_context("writing spec file", &spec_path)?;
+
+    let output = Command::new(&rpmbuild.path)
+        .arg("-bs")
+        .arg("--define")
+        .arg(format!("_topdir {}", top_dir.display()))
+        .arg(&spec_path)
+        .output()
+        .await
+        .fs_context("running rpmbuild", &rpmbuild.path)?;
+    if !output.status.success() {
+        return Err(Error::GenericError(format!(
+            "rpmbuild -bs failed ({:?}): {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let built = find_srpm(&top_dir.join("SRPMS")).await?;
+    let srpm_path = output_dir.join(built.file_name().context("invalid SRPM path")?);
+    tokio::fs::rename(&built, &srpm_path)
+        .await
+        .fs_context("moving source RPM", &built)?;
+
+    tracing::info!("✓ Created source RPM: {}", srpm_path.display());
+    Ok(srpm_path)
+}
+
+/// Finds the single `.src.rpm` written by rpmbuild
+async fn find_srpm(dir: &Path) -> Result<PathBuf> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .fs_context("reading SRPMS directory", dir)?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.to_string_lossy().ends_with(".src.rpm") {
+            return Ok(path);
+        }
+    }
+    Err(Error::GenericError(format!(
+        "rpmbuild produced no .src.rpm in {}",
+        dir.display()
+    )))
+}
+
+/// Renders the spec file for the source RPM
+fn render_spec(settings: &Settings) -> Result<String> {
+    let bundle = settings.bundle_settings();
+    let rpm = &bundle.rpm;
+    let summary = bundle
+        .short_description
+        .as_deref()
+        .unwrap_or(settings.description());
+    let summary = if summary.is_empty() { "(no description)" } else { summary };
+
+    let mut spec = String::new();
+    let _ = writeln!(spec, "Name:           {}", settings.product_name());
+    let _ = writeln!(spec, "Version:        {}", settings.version_string());
+    let _ = writeln!(spec, "Release:        {}", rpm.release);
+    if rpm.epoch != 0 {
+        let _ = writeln!(spec, "Epoch:          {}", rpm.epoch);
+    }
+    let _ = writeln!(spec, "Summary:        {}", summary);
+    let _ = writeln!(spec, "License:        {}", license_tag(settings));
+    if let Some(url) = url_tag(settings) {
+        let _ = writeln!(spec, "URL:            {}", url);
+    }
+    if let Some(vendor) = vendor_tag(settings) {
+        let _ = writeln!(spec, "Vendor:         {}", vendor);
+    }
+    if let Some(packager) = &rpm.packager {
+        let _ = writeln!(spec, "Packager:       {}", packager);
+    }
+    if let Some(group) = &rpm.group {
+        let _ = writeln!(spec, "Group:          {}", group);
+    }
+    spec.push_str("Source0:        %{name}-%{version}.tar.gz\n\n");
+    spec.push_str("BuildRequires:  cargo\nBuildRequires:  rust\n");
+
+    let relations = [
+        ("Requires", &rpm.depends),
+        ("Recommends", &rpm.recommends),
+        ("Suggests", &rpm.suggests),
+        ("Supplements", &rpm.supplements),
+        ("Enhances", &rpm.enhances),
+        ("Provides", &rpm.provides),
+        ("Conflicts", &rpm.conflicts),
+        ("Obsoletes", &rpm.obsoletes),
+    ];
+    for (tag, entries) in relations {
+        for entry in entries.iter().flatten() {
+            let _ = writeln!(spec, "{}:{}{}", tag, " ".repeat(16 - tag.len() - 1), entry);
+        }
+    }
+
+    let description = bundle
+        .long_description
+        .as_deref()
+        .unwrap_or(summary);
+    let _ = write!(spec, "\n%description\n{}\n\n", description);
+
+    spec.push_str("%prep\n%autosetup\n\n");
+
+    // The spec builds from source; prebuilt external_bin sidecars can't ship
+    let binaries: Vec<_> = settings.binaries().iter().filter(|b| !b.is_sidecar()).collect();
+    spec.push_str("%build\ncargo build --release --locked");
+    for binary in &binaries {
+        let _ = write!(spec, " --bin {}", binary.name());
+    }
+    spec.push_str("\n\n%install\n");
+    for binary in &binaries {
+        let _ = writeln!(
+            spec,
+            "install -Dm0755 target/release/{0} %{{buildroot}}%{{_bindir}}/{0}",
+            binary.name()
+        );
+    }
+
+    let mut desktop_entry = match &rpm.desktop_template {
+        Some(template) => {
+            std::fs::read_to_string(template).fs_context("reading desktop template", template)?
+        }
+        None => freedesktop::render_desktop_entry(settings)?,
+    };
+    if !desktop_entry.ends_with('\n') {
+        desktop_entry.push('\n');
+    }
+    let desktop_file = format!("%{{_datadir}}/applications/{}.desktop", settings.product_name());
+    let _ = write!(
+        spec,
+        "mkdir -p %{{buildroot}}%{{_datadir}}/applications\n\
+         cat > %{{buildroot}}{} <<'DESKTOP_ENTRY'\n{}DESKTOP_ENTRY\n",
+        desktop_file, desktop_entry
+    );
+
+    for (section, script) in [
+        ("pre", &rpm.pre_install_script),
+        ("post", &rpm.post_install_script),
+        ("preun", &rpm.pre_remove_script),
+        ("postun", &rpm.post_remove_script),
+    ] {
+        if let Some(path) = script {
+            let body = std::fs::read_to_string(path).fs_context("reading install script", path)?;
+            let _ = write!(spec, "\n%{}\n{}\n", section, body.trim_end());
+        }
+    }
+
+    spec.push_str("\n%files\n");
+    for binary in &binaries {
+        let _ = writeln!(spec, "%{{_bindir}}/{}", binary.name());
+    }
+    let _ = writeln!(spec, "{}", desktop_file);
+
+    Ok(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::settings::{BundleBinary, BundleSettings, PackageSettings, SettingsBuilder};
+
+    #[test]
+    fn test_render_spec() {
+        let mut bundle = BundleSettings {
+            publisher: Some("Cyrup".into()),
+            ..Default::default()
+        };
+        bundle.rpm.vendor = Some("Cyrup AI".into());
+        bundle.rpm.depends = Some(vec!["openssl-libs".into()]);
+        bundle.rpm.suggests = Some(vec!["git".into()]);
+
+        let settings = SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "kodegen".into(),
+                version: "1.2.3".into(),
+                description: "Code generator".into(),
+                license: Some("MIT/Apache-2.0".into()),
+                ..Default::default()
+            })
+            .bundle_settings(bundle)
+            .binaries(vec![BundleBinary::new("kodegen".into(), true)])
+            .build()
+            .unwrap();
+
+        let spec = render_spec(&settings).unwrap();
+        assert!(spec.starts_with("Name:           kodegen\nVersion:        1.2.3\nRelease:        1\n"));
+        assert!(spec.contains("License:        MIT OR Apache-2.0\n"));
+        assert!(spec.contains("Vendor:         Cyrup AI\n"));
+        assert!(spec.contains("Requires:       openssl-libs\n"));
+        assert!(spec.contains("Suggests:       git\n"));
+        assert!(spec.contains("cargo build --release --locked --bin kodegen\n"));
+        assert!(spec.contains(
+            "install -Dm0755 target/release/kodegen %{buildroot}%{_bindir}/kodegen\n"
+        ));
+        assert!(spec.ends_with("%files\n%{_bindir}/kodegen\n%{_datadir}/applications/kodegen.desktop\n"));
+    }
+}
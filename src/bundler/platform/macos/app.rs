@@ -2,8 +2,11 @@
 #![allow(dead_code)] // Public API - items may be used by external consumers
 
 use crate::bundler::{
+    BundleStage, PackageType,
     error::{Context, ErrorExt, Result},
-    settings::Settings,
+    hooks::{self, Hook},
+    resources::{files, license, locales},
+    settings::{BundleBinary, FileAssociation, Settings, UrlScheme},
     utils::fs,
 };
 use std::path::{Path, PathBuf};
@@ -27,7 +30,7 @@ pub async fn bundle_project(
         .join("bundle/macos")
         .join(&app_name);
 
-    log::info!("Bundling {} at {}", app_name, app_bundle_path.display());
+    tracing::info!("Bundling {} at {}", app_name, app_bundle_path.display());
 
     // Remove old bundle if it exists
     if app_bundle_path.exists() {
@@ -48,6 +51,14 @@ pub async fn bundle_project(
         .await
         .fs_context("failed to create Resources directory", &resources_dir)?;
 
+    let progress = settings.progress();
+    progress.report(
+        PackageType::MacOsBundle,
+        BundleStage::Building,
+        Some(10),
+        "Generating icons and Info.plist",
+    );
+
     // Create icon file - use pre-made ICNS if available, otherwise convert from PNGs
     let icon_filename = format!("{}.icns", settings.product_name());
     let icon_path = resources_dir.join(&icon_filename);
@@ -66,28 +77,88 @@ pub async fn bundle_project(
             .context("failed to create app icon")?;
     }
 
+    // Copy document type icons referenced by CFBundleDocumentTypes
+    copy_document_icons(&resources_dir, settings).await?;
+
     // Create Info.plist
     create_info_plist(&contents_dir, Some(&icon_path), settings).await?;
 
+    // Identity for re-signing Mach-O files after load path rewrites
+    let identity = super::sign::resolve_signing_identity(settings, runtime_identity);
+    let rewrite_identity = super::sign::rewrite_identity(settings, identity);
+
     // Copy frameworks if configured
-    copy_frameworks(&contents_dir, settings).await?;
+    progress.report(
+        PackageType::MacOsBundle,
+        BundleStage::Copying,
+        Some(30),
+        "Copying binaries, frameworks and libraries",
+    );
+    let frameworks =
+        super::frameworks::copy_frameworks(&contents_dir, settings, rewrite_identity).await?;
 
     // Copy binaries and set executable permissions
     copy_binaries(&macos_dir, settings).await?;
 
+    // Point binaries at the bundled frameworks (before dylib bundling)
+    let mut rewritten =
+        super::frameworks::link_frameworks(&contents_dir, settings, &frameworks).await?;
+
     // Bundle dynamic library dependencies
-    super::dylib::bundle_dylib_dependencies(&macos_dir, &contents_dir, settings).await?;
+    if settings.skip_phases().dylib_bundling {
+        tracing::info!("Skipping dylib bundling (--skip-dylib-bundling)");
+    } else {
+        rewritten.extend(
+            super::dylib::bundle_dylib_dependencies(&macos_dir, &contents_dir, settings).await?,
+        );
+    }
+
+    // install_name_tool invalidated the signatures of everything it touched
+    super::sign::resign_rewritten(&rewritten, rewrite_identity).await?;
 
-    // Copy custom files
+    // Copy resources and custom files
+    files::copy_resources(settings, &resources_dir).await?;
+    locales::copy_macos_localizations(settings, &resources_dir).await?;
+    license::copy_licenses(settings, &resources_dir).await?;
     copy_custom_files(&contents_dir, settings).await?;
 
-    // Sign if identity provided (from TempKeychain via APPLE_CERTIFICATE env var)
-    if let Some(identity) = runtime_identity {
+    // Embed launchd jobs for SMAppService registration
+    super::launchd::write_launch_agents(&contents_dir, settings).await?;
+
+    // Sign if identity provided (from TempKeychain via APPLE_CERTIFICATE env var,
+    // or ad-hoc when --dev-sign is set)
+    if let Some(identity) = identity
+        && !settings.skip_phases().sign
+    {
+        progress.report_path(
+            PackageType::MacOsBundle,
+            BundleStage::Signing,
+            Some(70),
+            "Signing app bundle",
+            &app_bundle_path,
+        );
         super::sign::sign_app(&app_bundle_path, identity, settings).await?;
+        hooks::run_for_package(
+            settings,
+            Hook::PostSign,
+            PackageType::MacOsBundle,
+            std::slice::from_ref(&app_bundle_path),
+            &[],
+        )
+        .await?;
     }
 
-    // Notarize if configured and credentials available
-    if super::sign::should_notarize(settings).await {
+    // Notarize if configured and credentials available (ad-hoc signatures can't be notarized)
+    if identity != Some(super::sign::AD_HOC_IDENTITY)
+        && super::sign::should_notarize(settings).await
+    {
+        progress.report_path(
+            PackageType::MacOsBundle,
+            BundleStage::Signing,
+            Some(80),
+            "Notarizing app bundle",
+            &app_bundle_path,
+        );
         super::sign::notarize_app(&app_bundle_path, settings).await?;
     }
 
@@ -162,6 +233,25 @@ async fn create_info_plist(
         dict.insert("NSHumanReadableCopyright".into(), copyright.clone().into());
     }
 
+    // File associations and URL schemes
+    let bundle_settings = settings.bundle_settings();
+    if !bundle_settings.file_associations.is_empty() {
+        dict.insert(
+            "CFBundleDocumentTypes".into(),
+            document_types(&bundle_settings.file_associations).into(),
+        );
+        let exported = exported_types(&bundle_settings.file_associations);
+        if !exported.is_empty() {
+            dict.insert("UTExportedTypeDeclarations".into(), exported.into());
+        }
+    }
+    if !bundle_settings.url_schemes.is_empty() {
+        dict.insert(
+            "CFBundleURLTypes".into(),
+            url_types(&bundle_settings.url_schemes, bundle_identifier(settings)?).into(),
+        );
+    }
+
     // Write the plist to disk
     let plist_path = contents_dir.join("Info.plist");
     Value::Dictionary(dict)
@@ -171,6 +261,151 @@ async fn create_info_plist(
     Ok(())
 }
 
+/// Builds the CFBundleDocumentTypes array
+fn document_types(associations: &[FileAssociation]) -> Vec<plist::Value> {
+    associations
+        .iter()
+        .map(|association| {
+            let mut dict = plist::Dictionary::new();
+            dict.insert("CFBundleTypeName".into(), association.display_name().into());
+            dict.insert(
+                "CFBundleTypeRole".into(),
+                association.role.as_plist_value().into(),
+            );
+            dict.insert(
+                "LSHandlerRank".into(),
+                association.rank.as_plist_value().into(),
+            );
+            dict.insert(
+                "CFBundleTypeExtensions".into(),
+                string_array(association.extensions()),
+            );
+            if !association.content_types.is_empty() {
+                dict.insert(
+                    "LSItemContentTypes".into(),
+                    string_array(association.content_types.iter().map(String::as_str)),
+                );
+            }
+            if let Some(mime_type) = &association.mime_type {
+                dict.insert(
+                    "CFBundleTypeMIMETypes".into(),
+                    string_array([mime_type.as_str()]),
+                );
+            }
+            if let Some(icon) = association.icon.as_ref().and_then(|i| i.file_name()) {
+                dict.insert(
+                    "CFBundleTypeIconFile".into(),
+                    icon.to_string_lossy().into_owned().into(),
+                );
+            }
+            plist::Value::Dictionary(dict)
+        })
+        .collect()
+}
+
+/// Builds UTExportedTypeDeclarations for associations that own custom UTIs
+fn exported_types(associations: &[FileAssociation]) -> Vec<plist::Value> {
+    associations
+        .iter()
+        .filter(|association| association.exported)
+        .flat_map(|association| {
+            association.content_types.iter().map(move |identifier| {
+                let mut tags = plist::Dictionary::new();
+                tags.insert(
+                    "public.filename-extension".into(),
+                    string_array(association.extensions()),
+                );
+                if let Some(mime_type) = &association.mime_type {
+                    tags.insert("public.mime-type".into(), mime_type.clone().into());
+                }
+
+                let mut dict = plist::Dictionary::new();
+                dict.insert("UTTypeIdentifier".into(), identifier.clone().into());
+                dict.insert(
+                    "UTTypeDescription".into(),
+                    association
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| association.display_name())
+                        .into(),
+                );
+                dict.insert("UTTypeConformsTo".into(), string_array(["public.data"]));
+                dict.insert("UTTypeTagSpecification".into(), tags.into());
+                if let Some(icon) = association.icon.as_ref().and_then(|i| i.file_name()) {
+                    dict.insert(
+                        "UTTypeIconFile".into(),
+                        icon.to_string_lossy().into_owned().into(),
+                    );
+                }
+                plist::Value::Dictionary(dict)
+            })
+        })
+        .collect()
+}
+
+/// Builds the CFBundleURLTypes array
+fn url_types(schemes: &[UrlScheme], bundle_identifier: &str) -> Vec<plist::Value> {
+    schemes
+        .iter()
+        .map(|scheme| {
+            let mut dict = plist::Dictionary::new();
+            dict.insert(
+                "CFBundleURLName".into(),
+                scheme.name.as_deref().unwrap_or(bundle_identifier).into(),
+            );
+            dict.insert(
+                "CFBundleURLSchemes".into(),
+                string_array(scheme.schemes.iter().map(|s| s.trim_end_matches("://"))),
+            );
+            dict.insert(
+                "CFBundleTypeRole".into(),
+                scheme.role.as_plist_value().into(),
+            );
+            plist::Value::Dictionary(dict)
+        })
+        .collect()
+}
+
+fn string_array<'a>(values: impl IntoIterator<Item = &'a str>) -> plist::Value {
+    plist::Value::Array(values.into_iter().map(|v| v.into()).collect())
+}
+
+/// Copies document type icons into the Resources directory
+async fn copy_document_icons(resources_dir: &Path, settings: &Settings) -> Result<()> {
+    for icon in settings
+        .bundle_settings()
+        .file_associations
+        .iter()
+        .filter_map(|a| a.icon.as_ref())
+    {
+        let name = icon.file_name().ok_or_else(|| {
+            crate::bundler::error::Error::GenericError(format!(
+                "Invalid document icon path: {}",
+                icon.display()
+            ))
+        })?;
+        let dst = resources_dir.join(name);
+        fs::copy_file(icon, &dst)
+            .await
+            .with_context(|| format!("failed to copy document icon {}", icon.display()))?;
+    }
+    Ok(())
+}
+
+/// `Contents` subdirectory a binary is bundled in.
+///
+/// Main binary (kodegen_install) goes in MacOS/ - launchable by user/system.
+/// `external_bin` sidecars go there too, next to the executable that spawns
+/// them. Other binaries (kodegen, kodegend) go in Resources/ - extracted
+/// during install.
+pub fn binary_dir(binary: &BundleBinary) -> &'static str {
+    if binary.main() || binary.is_sidecar() {
+        "MacOS"
+    } else {
+        "Resources"
+    }
+}
+
 /// Copies binaries to the MacOS directory and sets executable permissions
 async fn copy_binaries(macos_dir: &Path, settings: &Settings) -> Result<()> {
     // Get Resources directory for bundled binaries
@@ -185,12 +420,9 @@ async fn copy_binaries(macos_dir: &Path, settings: &Settings) -> Result<()> {
         let src = settings.binary_path(binary);
         let bin_name = binary.name();
 
-        // Main binary (kodegen_install) goes in MacOS/ - launchable by user/system
-        // Other binaries (kodegen, kodegend) go in Resources/ - extracted during install
-        let dst = if binary.main() {
-            macos_dir.join(bin_name)
-        } else {
-            resources_dir.join(bin_name)
+        let dst = match binary_dir(binary) {
+            "MacOS" => macos_dir.join(bin_name),
+            _ => resources_dir.join(bin_name),
         };
 
         fs::copy_file(&src, &dst)
@@ -209,83 +441,6 @@ async fn copy_binaries(macos_dir: &Path, settings: &Settings) -> Result<()> {
     Ok(())
 }
 
-/// Copies frameworks to the Frameworks directory
-async fn copy_frameworks(contents_dir: &Path, settings: &Settings) -> Result<()> {
-    let frameworks = match &settings.bundle_settings().macos.frameworks {
-        Some(f) if !f.is_empty() => f,
-        _ => return Ok(()), // No frameworks to copy
-    };
-
-    let frameworks_dir = contents_dir.join("Frameworks");
-    tokio_fs::create_dir_all(&frameworks_dir)
-        .await
-        .fs_context("failed to create Frameworks directory", &frameworks_dir)?;
-
-    for framework in frameworks {
-        if framework.ends_with(".framework") {
-            // Copy .framework bundle
-            let src = PathBuf::from(framework);
-            let name = src.file_name().ok_or_else(|| {
-                crate::bundler::error::Error::GenericError(format!(
-                    "Invalid framework path: {}",
-                    framework
-                ))
-            })?;
-            let dst = frameworks_dir.join(name);
-            fs::copy_dir(&src, &dst)
-                .await
-                .with_context(|| format!("failed to copy framework {}", framework))?;
-        } else if framework.ends_with(".dylib") {
-            // Copy .dylib file
-            let src = PathBuf::from(framework);
-            let name = src.file_name().ok_or_else(|| {
-                crate::bundler::error::Error::GenericError(format!(
-                    "Invalid dylib path: {}",
-                    framework
-                ))
-            })?;
-            let dst = frameworks_dir.join(name);
-            fs::copy_file(&src, &dst)
-                .await
-                .with_context(|| format!("failed to copy dylib {}", framework))?;
-        } else {
-            // Search standard framework locations
-            copy_framework_from_standard_locations(&frameworks_dir, framework).await?;
-        }
-    }
-    Ok(())
-}
-
-/// Searches for a framework in standard macOS locations
-async fn copy_framework_from_standard_locations(dest_dir: &Path, framework: &str) -> Result<()> {
-    let framework_name = format!("{}.framework", framework);
-
-    // Search paths in order of preference
-    let mut search_paths = vec![
-        PathBuf::from("/Library/Frameworks"),
-        PathBuf::from("/Network/Library/Frameworks"),
-    ];
-
-    // Add user's home directory if available
-    if let Ok(home) = std::env::var("HOME") {
-        search_paths.insert(0, PathBuf::from(home).join("Library/Frameworks"));
-    }
-
-    for search_path in search_paths {
-        let src = search_path.join(&framework_name);
-        if src.exists() {
-            let dst = dest_dir.join(&framework_name);
-            fs::copy_dir(&src, &dst).await?;
-            return Ok(());
-        }
-    }
-
-    Err(crate::bundler::error::Error::GenericError(format!(
-        "Framework not found: {}",
-        framework
-    )))
-}
-
 /// Copies custom files to the bundle
 async fn copy_custom_files(contents_dir: &Path, settings: &Settings) -> Result<()> {
     for (dest_path, src_path) in &settings.bundle_settings().macos.files {
@@ -1,33 +1,39 @@
 //! DMG format conversion utilities.
 //!
 //! Handles conversion between DMG formats, particularly:
-//! - UDRW (read-write) to UDZO (compressed read-only)
+//! - UDRW (read-write) to a compressed read-only format (UDZO, ULFO, ULMO)
 //!
 //! The conversion workflow is necessary because DMG customization requires
 //! a read-write format, but the final distribution should use compressed format.
 
-use crate::bundler::error::Result;
+use crate::bundler::{
+    error::Result,
+    settings::DmgFormat,
+    utils::{interrupt, progress_bar},
+};
 use std::path::Path;
 use tokio::fs::{remove_file, rename};
 
-/// Convert read-write DMG (UDRW) to compressed read-only (UDZO)
+/// Convert read-write DMG (UDRW) to a compressed read-only format
 ///
 /// This must be done AFTER customizations are applied and the DMG is detached.
 /// The conversion creates a new compressed DMG and replaces the original.
 ///
 /// # Process
 /// 1. Create temporary output path for compressed DMG
-/// 2. Run hdiutil convert with UDZO format
+/// 2. Run hdiutil convert with the configured format
 /// 3. Remove original UDRW DMG
 /// 4. Rename compressed DMG to original path
 ///
+/// The same applies to ULFO and ULMO, which are also read-only.
+///
 /// # Background
 /// We cannot customize a UDZO DMG because it's compressed and read-only.
 /// Changes made to a mounted UDZO with -readwrite are stored in a shadow
 /// file which is discarded on detach. The correct workflow is:
 /// UDRW → customize → detach → convert to UDZO.
-pub async fn convert_dmg_to_compressed(dmg_path: &Path) -> Result<()> {
-    log::info!("Converting DMG to compressed format...");
+pub async fn convert_dmg_to_compressed(dmg_path: &Path, format: DmgFormat) -> Result<()> {
+    tracing::info!("Converting DMG to compressed format...");
 
     let dmg_str = dmg_path.to_str().ok_or_else(|| {
         crate::bundler::Error::GenericError("DMG path contains non-UTF8 characters".into())
@@ -41,10 +47,19 @@ pub async fn convert_dmg_to_compressed(dmg_path: &Path) -> Result<()> {
         )
     })?;
 
-    // Convert UDRW → UDZO
-    let output = tokio::process::Command::new("hdiutil")
-        .args(["convert", dmg_str, "-format", "UDZO", "-o", compressed_str])
-        .output()
+    // Convert UDRW → compressed
+    let format = format.as_hdiutil_format();
+    let child = tokio::process::Command::new("hdiutil")
+        .args(["convert", dmg_str, "-format", format, "-o", compressed_str])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            crate::bundler::Error::GenericError(format!("Failed to convert DMG: {}", e))
+        })?;
+    let _interrupt = interrupt::kill_on_interrupt(&child);
+    let convert = child.wait_with_output();
+    let output = progress_bar::spin(format!("Compressing disk image ({})", format), convert)
         .await
         .map_err(|e| {
             crate::bundler::Error::GenericError(format!("Failed to convert DMG: {}", e))
@@ -58,11 +73,11 @@ pub async fn convert_dmg_to_compressed(dmg_path: &Path) -> Result<()> {
         )));
     }
 
-    // Replace UDRW with UDZO
+    // Replace UDRW with compressed image
     remove_file(dmg_path).await?;
     rename(&compressed_path, dmg_path).await?;
 
-    log::info!("✓ DMG converted to compressed UDZO format");
+    tracing::info!("✓ DMG converted to compressed {} format", format);
 
     Ok(())
 }
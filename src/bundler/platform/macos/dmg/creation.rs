@@ -9,7 +9,7 @@
 use crate::bundler::{
     error::{Context, ErrorExt, Result},
     settings::Settings,
-    utils::fs,
+    utils::{fs, interrupt, progress_bar},
 };
 use std::path::{Path, PathBuf};
 use tokio::fs::remove_file;
@@ -34,12 +34,12 @@ pub async fn find_or_create_app_bundle(
         .join(&app_name);
 
     if expected_path.exists() && expected_path.is_dir() {
-        log::debug!("Using existing .app bundle: {}", expected_path.display());
+        tracing::debug!("Using existing .app bundle: {}", expected_path.display());
         return Ok(expected_path);
     }
 
     // Create .app bundle using existing app bundler
-    log::info!("Creating .app bundle for DMG...");
+    tracing::info!("Creating .app bundle for DMG...");
     use super::super::app;
     let paths = app::bundle_project(settings, runtime_identity).await?;
 
@@ -101,7 +101,7 @@ pub async fn create_dmg(
         .ok_or_else(|| crate::bundler::Error::GenericError("Invalid app bundle path".into()))?;
     let staged_app = staging_path.join(app_name);
 
-    log::debug!("Copying .app to staging: {}", staged_app.display());
+    tracing::debug!("Copying .app to staging: {}", staged_app.display());
     fs::copy_dir(app_bundle, &staged_app)
         .await
         .with_context(|| {
@@ -113,11 +113,16 @@ pub async fn create_dmg(
 
     // Sign and notarize the .app bundle BEFORE creating the DMG
     // This ensures the .app inside the DMG is properly signed and notarized
-    if let Some(identity) = runtime_identity {
+    let identity = super::super::sign::resolve_signing_identity(settings, runtime_identity);
+    if let Some(identity) = identity
+        && !settings.skip_phases().sign
+    {
         super::super::sign::sign_app(&staged_app, identity, settings).await?;
     }
 
-    if super::super::sign::should_notarize(settings).await {
+    if identity != Some(super::super::sign::AD_HOC_IDENTITY)
+        && super::super::sign::should_notarize(settings).await
+    {
         super::super::sign::notarize_app(&staged_app, settings).await?;
     }
 
@@ -130,14 +135,17 @@ pub async fn create_dmg(
     }
 
     // Determine if customization is needed
-    let dmg_settings = &settings.bundle_settings().dmg;
-    let needs_customization =
-        dmg_settings.background.is_some() || dmg_settings.window_size.is_some();
+    let needs_customization = super::customization::needs_customization(settings);
 
-    // Choose format: UDRW if customizing (so changes persist), UDZO if not
-    let dmg_format = if needs_customization { "UDRW" } else { "UDZO" };
+    // Choose format: UDRW if customizing (so changes persist), final format if not
+    let dmg_settings = &settings.bundle_settings().dmg;
+    let dmg_format = if needs_customization {
+        "UDRW"
+    } else {
+        dmg_settings.format.as_hdiutil_format()
+    };
 
-    log::info!("Creating DMG with format {}...", dmg_format);
+    tracing::info!("Creating DMG with format {}...", dmg_format);
 
     let staging_str = staging_path.to_str().ok_or_else(|| {
         crate::bundler::Error::GenericError(
@@ -151,7 +159,7 @@ pub async fn create_dmg(
         )
     })?;
 
-    let output = tokio::process::Command::new("hdiutil")
+    let child = tokio::process::Command::new("hdiutil")
         .args([
             "create",
             "-volname",
@@ -159,11 +167,21 @@ pub async fn create_dmg(
             "-srcfolder",
             staging_str,
             "-ov", // Overwrite if exists
+            "-fs",
+            dmg_settings.filesystem.as_hdiutil_fs(),
             "-format",
-            dmg_format, // UDRW if customizing, UDZO if not
+            dmg_format, // UDRW if customizing, final format if not
             dmg_str,
         ])
-        .output()
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            crate::bundler::Error::GenericError(format!("Failed to execute hdiutil command: {}", e))
+        })?;
+    let _interrupt = interrupt::kill_on_interrupt(&child);
+    let create = child.wait_with_output();
+    let output = progress_bar::spin(format!("Creating {} disk image", dmg_format), create)
         .await
         .map_err(|e| {
             crate::bundler::Error::GenericError(format!("Failed to execute hdiutil command: {}", e))
@@ -177,7 +195,7 @@ pub async fn create_dmg(
         )));
     }
 
-    log::info!("✓ Created {} DMG: {}", dmg_format, dmg_path.display());
+    tracing::info!("✓ Created {} DMG: {}", dmg_format, dmg_path.display());
 
     // tempfile automatically cleans up staging directory
     drop(temp_dir);
@@ -188,6 +206,7 @@ pub async fn create_dmg(
 /// Check if DMG should be signed
 ///
 /// Sign DMG when:
+/// - ✅ Signing is not skipped (`--skip-sign`)
 /// - ✅ `signing_identity` is configured in MacOsSettings
 /// - ✅ Identity is NOT "-" (ad-hoc signature marker)
 ///
@@ -195,6 +214,10 @@ pub async fn create_dmg(
 /// The "-" identity is Apple's marker for ad-hoc signatures (self-signing).
 /// We skip external signing for ad-hoc signatures to avoid errors.
 pub fn should_sign_dmg(settings: &Settings) -> bool {
+    if settings.skip_phases().sign {
+        return false;
+    }
+
     if let Some(identity) = &settings.bundle_settings().macos.signing_identity {
         identity != "-"
     } else {
@@ -3,22 +3,28 @@
 //! Handles all DMG customization features including:
 //! - Mounting DMG in read-write mode
 //! - Copying background images
+//! - Setting the custom volume icon
 //! - Running AppleScript to set window properties
 //! - Detaching DMG after customization
 
-use crate::bundler::{error::Result, settings::Settings, utils::fs};
+use crate::bundler::{
+    error::Result,
+    settings::{DmgSettings, Settings},
+    utils::{fs, interrupt},
+};
 use std::path::{Path, PathBuf};
 use tokio::fs::copy;
 use tokio::time::Duration;
 
-/// Apply DMG customizations (background image and window size)
+/// Apply DMG customizations (background image, window size and volume icon)
 ///
 /// # Process
 /// 1. Mount DMG in read-write mode
 /// 2. Copy background image to .background folder (if configured)
-/// 3. Run AppleScript to customize window appearance
-/// 4. Wait for .DS_Store file to be created
-/// 5. Detach DMG
+/// 3. Install .VolumeIcon.icns and flag the volume root (if configured)
+/// 4. Run AppleScript to customize window appearance (if layout configured)
+/// 5. Wait for .DS_Store file to be created
+/// 6. Detach DMG
 ///
 /// # Background
 /// DMG appearance customization requires:
@@ -26,13 +32,23 @@ use tokio::time::Duration;
 /// - Using AppleScript to set Finder window properties
 /// - The .DS_Store file persists these settings when DMG is unmounted
 pub async fn apply_dmg_customizations(dmg_path: &Path, settings: &Settings) -> Result<()> {
-    log::info!("Applying DMG customizations...");
+    tracing::info!("Applying DMG customizations...");
 
     let dmg_settings = &settings.bundle_settings().dmg;
 
     // Step 1: Mount DMG in read-write mode
     let volume_name = settings.product_name();
     let mount_point = mount_dmg_rw(dmg_path, volume_name).await?;
+    let mounted = {
+        let mount_point = mount_point.clone();
+        interrupt::on_interrupt(move || {
+            let _ = std::process::Command::new("hdiutil")
+                .arg("detach")
+                .arg(&mount_point)
+                .arg("-force")
+                .output();
+        })
+    };
 
     // Step 2: Copy background image if configured
     if let Some(bg_path) = &dmg_settings.background {
@@ -46,19 +62,90 @@ pub async fn apply_dmg_customizations(dmg_path: &Path, settings: &Settings) -> R
         let dest_bg = bg_dir.join(bg_filename);
         copy(bg_path, &dest_bg).await?;
 
-        log::debug!("Copied background image to {}", dest_bg.display());
+        tracing::debug!("Copied background image to {}", dest_bg.display());
     }
 
-    // Step 3: Run AppleScript to customize window
-    let window_size = dmg_settings.window_size.unwrap_or((600, 400));
-    let has_background = dmg_settings.background.is_some();
+    // Step 3: Install custom volume icon if configured
+    if let Some(icon_path) = &dmg_settings.volume_icon {
+        set_volume_icon(&mount_point, icon_path).await?;
+    }
 
-    run_dmg_applescript(volume_name, settings, window_size, has_background).await?;
+    // Step 4: Run AppleScript to customize window
+    let has_background = dmg_settings.background.is_some();
+    if has_background || has_window_layout(dmg_settings) {
+        let window_size = dmg_settings.window_size.unwrap_or((600, 400));
+        run_dmg_applescript(volume_name, settings, window_size, has_background).await?;
+    }
 
-    // Step 4: Detach DMG
+    // Step 5: Detach DMG
     detach_dmg(volume_name).await?;
+    drop(mounted);
+
+    tracing::info!("✓ DMG customizations applied");
+
+    Ok(())
+}
+
+/// Check if the DMG needs appearance customization
+///
+/// Customization runs when a background, window layout or volume icon is
+/// configured and the phase has not been skipped (`--skip-customization`).
+pub fn needs_customization(settings: &Settings) -> bool {
+    let dmg_settings = &settings.bundle_settings().dmg;
+    !settings.skip_phases().customization
+        && (dmg_settings.background.is_some()
+            || has_window_layout(dmg_settings)
+            || dmg_settings.volume_icon.is_some())
+}
+
+/// Check if any Finder window layout setting is configured
+fn has_window_layout(dmg_settings: &DmgSettings) -> bool {
+    dmg_settings.window_size.is_some()
+        || dmg_settings.app_position.is_some()
+        || dmg_settings.applications_position.is_some()
+        || dmg_settings.icon_size.is_some()
+}
+
+/// `com.apple.FinderInfo` with only the `kHasCustomIcon` (0x0400) Finder flag set
+///
+/// FinderInfo is 32 bytes; the Finder flags are the big-endian u16 at offset 8.
+const CUSTOM_ICON_FINDER_INFO: &str =
+    "0000000000000000040000000000000000000000000000000000000000000000";
+
+/// Install `.VolumeIcon.icns` on a mounted volume and flag the volume root
+/// so Finder displays it.
+async fn set_volume_icon(mount_point: &Path, icon_path: &Path) -> Result<()> {
+    let is_icns = icon_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("icns"));
+    if !is_icns {
+        return Err(crate::bundler::Error::GenericError(format!(
+            "DMG volume icon must be an .icns file: {}",
+            icon_path.display()
+        )));
+    }
 
-    log::info!("✓ DMG customizations applied");
+    let dest_icon = mount_point.join(".VolumeIcon.icns");
+    copy(icon_path, &dest_icon).await?;
+    tracing::debug!("Copied volume icon to {}", dest_icon.display());
+
+    let output = tokio::process::Command::new("xattr")
+        .args(["-wx", "com.apple.FinderInfo", CUSTOM_ICON_FINDER_INFO])
+        .arg(mount_point)
+        .output()
+        .await
+        .map_err(|e| {
+            crate::bundler::Error::GenericError(format!("Failed to run xattr: {}", e))
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(crate::bundler::Error::GenericError(format!(
+            "Failed to set custom icon attribute on DMG volume: {}",
+            stderr
+        )));
+    }
 
     Ok(())
 }
@@ -67,7 +154,7 @@ pub async fn apply_dmg_customizations(dmg_path: &Path, settings: &Settings) -> R
 ///
 /// Returns the mount point path
 async fn mount_dmg_rw(dmg_path: &Path, volume_name: &str) -> Result<PathBuf> {
-    log::debug!("Mounting DMG for customization...");
+    tracing::debug!("Mounting DMG for customization...");
 
     let dmg_str = dmg_path.to_str().ok_or_else(|| {
         crate::bundler::Error::GenericError("DMG path contains non-UTF8 characters".into())
@@ -94,7 +181,7 @@ async fn mount_dmg_rw(dmg_path: &Path, volume_name: &str) -> Result<PathBuf> {
     let max_retries = 10;
     for i in 0..max_retries {
         if mount_point.exists() {
-            log::debug!("DMG mounted at {}", mount_point.display());
+            tracing::debug!("DMG mounted at {}", mount_point.display());
             return Ok(mount_point);
         }
         tokio::time::sleep(Duration::from_millis(500)).await;
@@ -133,11 +220,16 @@ async fn run_dmg_applescript(
     window_size: (u32, u32),
     has_background: bool,
 ) -> Result<()> {
-    log::debug!("Running AppleScript to customize DMG window...");
+    tracing::debug!("Running AppleScript to customize DMG window...");
 
     let app_name = format!("{}.app", settings.product_name());
     let (width, height) = window_size;
 
+    let dmg_settings = &settings.bundle_settings().dmg;
+    let (app_x, app_y) = dmg_settings.app_position.unwrap_or((180, 170));
+    let (apps_x, apps_y) = dmg_settings.applications_position.unwrap_or((480, 170));
+    let icon_size = dmg_settings.icon_size.unwrap_or(72).clamp(16, 512);
+
     // Escape strings for safe AppleScript interpolation
     let escaped_volume = escape_applescript_string(volume_name);
     let escaped_app = escape_applescript_string(&app_name);
@@ -169,10 +261,10 @@ async fn run_dmg_applescript(
                 set bounds of container window to {{100, 100, {right}, {bottom}}}
                 set viewOptions to icon view options of container window
                 set arrangement of viewOptions to not arranged
-                set icon size of viewOptions to 72
+                set icon size of viewOptions to {icon_size}
                 {background_clause}
-                set position of item "{app_name}" to {{180, 170}}
-                set position of item "Applications" to {{480, 170}}
+                set position of item "{app_name}" to {{{app_x}, {app_y}}}
+                set position of item "Applications" to {{{apps_x}, {apps_y}}}
                 close
                 open
                 update without registering applications
@@ -205,7 +297,7 @@ async fn run_dmg_applescript(
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        log::warn!("AppleScript execution had issues: {}", stderr);
+        tracing::warn!("AppleScript execution had issues: {}", stderr);
         // Don't fail - appearance customization is non-critical
     }
 
@@ -214,7 +306,7 @@ async fn run_dmg_applescript(
 
 /// Detach (unmount) DMG
 async fn detach_dmg(volume_name: &str) -> Result<()> {
-    log::debug!("Detaching DMG...");
+    tracing::debug!("Detaching DMG...");
 
     let mount_point = format!("/Volumes/{}", volume_name);
 
@@ -229,7 +321,7 @@ async fn detach_dmg(volume_name: &str) -> Result<()> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        log::warn!("DMG detach had issues: {}", stderr);
+        tracing::warn!("DMG detach had issues: {}", stderr);
         // Try force detach
         tokio::process::Command::new("hdiutil")
             .args(["detach", &mount_point, "-force"])
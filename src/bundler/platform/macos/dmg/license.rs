@@ -0,0 +1,217 @@
+//! Click-through license agreement (EULA) for DMG images.
+//!
+//! When a DMG carries a software license agreement, Finder shows it when the
+//! image is opened and only mounts the volume once the user presses "Agree".
+//! The agreement lives in classic Mac resources embedded in the UDIF image:
+//! - `LPic` - maps languages to resource IDs
+//! - `STR#` - localized button labels and prompt text
+//! - `TEXT` / `RTF ` - the license body
+//!
+//! The resources are written as an XML plist and attached with
+//! `hdiutil udifrez`, which operates on the final compressed image.
+
+use crate::bundler::error::{ErrorExt, Result};
+use std::path::Path;
+
+/// Resource ID used for all license resources (English region).
+const LICENSE_RESOURCE_ID: &str = "5000";
+
+/// Button labels and prompt shown by Finder (order is fixed by the format).
+const ENGLISH_LABELS: [&str; 9] = [
+    "English",
+    "Agree",
+    "Disagree",
+    "Print",
+    "Save...",
+    "If you agree with the terms of this license, press \"Agree\" to install the software. \
+     If you do not agree, press \"Disagree\".",
+    "Software License Agreement",
+    "Save",
+    "",
+];
+
+/// Attach a license agreement to an existing DMG
+///
+/// Accepts plain text (`.txt` or anything else) or RTF (`.rtf`) licenses.
+/// Must run after the DMG has been converted to its final format and before
+/// the DMG is signed, since `udifrez` rewrites the image.
+pub async fn attach_license(dmg_path: &Path, license_path: &Path) -> Result<()> {
+    tracing::info!(
+        "Attaching license agreement from {}",
+        license_path.display()
+    );
+
+    let license = tokio::fs::read(license_path)
+        .await
+        .fs_context("reading DMG license file", license_path)?;
+
+    let is_rtf = license_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("rtf"));
+
+    let resources = build_license_resources(&license, is_rtf);
+
+    let temp_dir = tempfile::tempdir().map_err(|e| {
+        crate::bundler::Error::GenericError(format!(
+            "Failed to create temporary directory for DMG license: {}",
+            e
+        ))
+    })?;
+    let plist_path = temp_dir.path().join("license.plist");
+    plist::Value::Dictionary(resources)
+        .to_file_xml(&plist_path)
+        .map_err(crate::bundler::error::Error::Plist)?;
+
+    let output = tokio::process::Command::new("hdiutil")
+        .arg("udifrez")
+        .arg("-xml")
+        .arg(&plist_path)
+        .arg("")
+        .arg("-quiet")
+        .arg(dmg_path)
+        .output()
+        .await
+        .map_err(|e| {
+            crate::bundler::Error::GenericError(format!("Failed to run hdiutil udifrez: {}", e))
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(crate::bundler::Error::GenericError(format!(
+            "Failed to attach license to DMG: {}",
+            stderr
+        )));
+    }
+
+    tracing::info!("✓ License agreement attached to {}", dmg_path.display());
+
+    Ok(())
+}
+
+/// Build the resource dictionary understood by `hdiutil udifrez -xml`
+fn build_license_resources(license: &[u8], is_rtf: bool) -> plist::Dictionary {
+    let mut resources = plist::Dictionary::new();
+
+    // LPic: default language 0, one entry (language 0 -> resource offset 0, single-byte text)
+    let lpic = vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    resources.insert("LPic".into(), resource_array(lpic, ""));
+
+    resources.insert(
+        "STR#".into(),
+        resource_array(encode_labels(&ENGLISH_LABELS), "English"),
+    );
+
+    if is_rtf {
+        resources.insert(
+            "RTF ".into(),
+            resource_array(license.to_vec(), "English SLA"),
+        );
+    } else {
+        let text = String::from_utf8_lossy(license);
+        resources.insert(
+            "TEXT".into(),
+            resource_array(to_mac_text(&text), "English SLA"),
+        );
+    }
+
+    resources
+}
+
+/// Wrap raw resource bytes in the single-element array `udifrez` expects
+fn resource_array(data: Vec<u8>, name: &str) -> plist::Value {
+    let mut entry = plist::Dictionary::new();
+    entry.insert("Attributes".into(), "0x0000".into());
+    entry.insert("Data".into(), plist::Value::Data(data));
+    entry.insert("ID".into(), LICENSE_RESOURCE_ID.into());
+    entry.insert("Name".into(), name.into());
+    plist::Value::Array(vec![plist::Value::Dictionary(entry)])
+}
+
+/// Encode a `STR#` resource: big-endian count followed by Pascal strings
+fn encode_labels(labels: &[&str]) -> Vec<u8> {
+    let mut data = (labels.len() as u16).to_be_bytes().to_vec();
+    for label in labels {
+        let bytes = to_mac_text(label);
+        let len = bytes.len().min(u8::MAX as usize);
+        data.push(len as u8);
+        data.extend_from_slice(&bytes[..len]);
+    }
+    data
+}
+
+/// Convert text to the classic Mac form used by `TEXT` resources
+///
+/// Line endings become carriage returns. Characters outside ASCII have no
+/// reliable single-byte mapping and are replaced with `?`; use an RTF license
+/// for non-ASCII text.
+fn to_mac_text(text: &str) -> Vec<u8> {
+    let normalized = text.replace("\r\n", "\r").replace('\n', "\r");
+    let lossy = normalized.chars().any(|c| !c.is_ascii());
+    if lossy {
+        tracing::warn!(
+            "License text contains non-ASCII characters; use an .rtf license to preserve them"
+        );
+    }
+    normalized
+        .chars()
+        .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource_data(resources: &plist::Dictionary, key: &str) -> Vec<u8> {
+        let entry = resources[key].as_array().unwrap()[0].as_dictionary().unwrap();
+        assert_eq!(entry["ID"].as_string(), Some(LICENSE_RESOURCE_ID));
+        entry["Data"].as_data().unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_to_mac_text() {
+        assert_eq!(to_mac_text("MIT\r\nLicense\nEnd"), b"MIT\rLicense\rEnd");
+        // No single-byte mapping outside ASCII
+        assert_eq!(to_mac_text("Café ©"), b"Caf? ?");
+    }
+
+    #[test]
+    fn test_encode_labels() {
+        let data = encode_labels(&ENGLISH_LABELS);
+        assert_eq!(&data[..2], &[0, 9]);
+        assert_eq!(&data[2..10], b"\x07English");
+        assert_eq!(&data[10..16], b"\x05Agree");
+        // The empty last label is a zero length byte
+        assert_eq!(data.last(), Some(&0));
+
+        let long = "x".repeat(300);
+        let data = encode_labels(&[&long]);
+        assert_eq!(data.len(), 2 + 1 + 255);
+        assert_eq!(data[2], 255);
+    }
+
+    #[test]
+    fn test_build_license_resources() {
+        let text = build_license_resources(b"Terms\n", false);
+        // One language (0) at resource offset 0
+        assert_eq!(resource_data(&text, "LPic"), [0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(resource_data(&text, "STR#"), encode_labels(&ENGLISH_LABELS));
+        assert_eq!(resource_data(&text, "TEXT"), b"Terms\r");
+        assert!(!text.contains_key("RTF "));
+
+        let rtf = build_license_resources(b"{\\rtf1 Terms}", true);
+        assert_eq!(resource_data(&rtf, "RTF "), b"{\\rtf1 Terms}");
+        assert!(!rtf.contains_key("TEXT"));
+    }
+
+    #[tokio::test]
+    async fn test_attach_license_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("LICENSE.txt");
+        let err = attach_license(&dir.path().join("app.dmg"), &missing)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::bundler::Error::Fs { ref path, .. } if *path == missing), "{}", err);
+    }
+}
@@ -10,19 +10,28 @@
 //! This module is organized into logical submodules:
 //! - `creation` - Core DMG creation using hdiutil
 //! - `customization` - DMG appearance customization (background, window size)
-//! - `conversion` - Format conversion (UDRW → UDZO)
+//! - `conversion` - Format conversion (UDRW → UDZO/ULFO/ULMO)
+//! - `license` - Click-through license agreement (EULA)
 
 mod conversion;
 mod creation;
 mod customization;
+mod license;
 
-use crate::bundler::{error::Result, settings::Settings, utils::fs};
+use crate::bundler::{
+    BundleStage, PackageType,
+    error::Result,
+    hooks::{self, Hook},
+    settings::Settings,
+    utils::fs,
+};
 use std::path::PathBuf;
 
 // Re-export public functions from submodules
 pub use conversion::convert_dmg_to_compressed;
 pub use creation::{create_dmg, find_or_create_app_bundle, should_sign_dmg};
-pub use customization::apply_dmg_customizations;
+pub use customization::{apply_dmg_customizations, needs_customization};
+pub use license::attach_license;
 
 /// Bundle project as DMG disk image
 ///
@@ -32,16 +41,19 @@ pub use customization::apply_dmg_customizations;
 /// 3. Copy .app into staging directory
 /// 4. Sign and notarize the staged .app (Task 12 integration)
 /// 5. Create Applications symlink for drag-to-install
-/// 6. Generate DMG using hdiutil with UDZO compression
-/// 7. Sign DMG if signing identity configured
-/// 8. Clean up temporary files
+/// 6. Generate DMG using hdiutil with the configured format (UDZO by default)
+/// 7. Attach license agreement if configured
+/// 8. Sign DMG if signing identity configured
+/// 9. Write Sparkle appcast.xml if configured
+/// 10. Clean up temporary files
 ///
 /// # Arguments
 /// * `settings` - Bundle configuration
 /// * `runtime_identity` - Optional signing identity from TempKeychain (via APPLE_CERTIFICATE env var)
 ///
 /// # Returns
-/// Vector containing path to created DMG file.
+/// Vector containing path to created DMG file, followed by the Sparkle
+/// appcast when one was generated.
 ///
 /// # Example
 /// ```no_run
@@ -62,12 +74,19 @@ pub async fn bundle_project(
     settings: &Settings,
     runtime_identity: Option<&str>,
 ) -> Result<Vec<PathBuf>> {
-    log::info!("Creating DMG for {}", settings.product_name());
+    tracing::info!("Creating DMG for {}", settings.product_name());
 
     // Step 1: Find or create .app bundle
     let app_bundle_path = find_or_create_app_bundle(settings, runtime_identity).await?;
 
     // Step 2: Prepare DMG output directory
+    let progress = settings.progress();
+    progress.report(
+        PackageType::Dmg,
+        BundleStage::Building,
+        Some(30),
+        "Creating disk image",
+    );
     let output_dir = settings.project_out_directory().join("bundle/dmg");
     fs::create_dir_all(&output_dir, false).await?;
 
@@ -75,19 +94,47 @@ pub async fn bundle_project(
     let dmg_path = create_dmg(settings, &app_bundle_path, &output_dir, runtime_identity).await?;
 
     // Step 4: Apply customizations if configured
-    let dmg_settings = &settings.bundle_settings().dmg;
-    let needs_customization =
-        dmg_settings.background.is_some() || dmg_settings.window_size.is_some();
-
-    if needs_customization {
+    if needs_customization(settings) {
+        progress.report(
+            PackageType::Dmg,
+            BundleStage::Compressing,
+            Some(60),
+            "Applying layout and compressing disk image",
+        );
         apply_dmg_customizations(&dmg_path, settings).await?;
-        convert_dmg_to_compressed(&dmg_path).await?;
+        convert_dmg_to_compressed(&dmg_path, settings.bundle_settings().dmg.format).await?;
+    }
+
+    // Step 5: Attach license agreement if configured (rewrites the image, so before signing)
+    if let Some(license_path) = &settings.bundle_settings().dmg.license {
+        attach_license(&dmg_path, license_path).await?;
     }
 
-    // Step 5: Sign DMG if configured
+    // Step 6: Sign DMG if configured
     if should_sign_dmg(settings) {
+        progress.report_path(
+            PackageType::Dmg,
+            BundleStage::Signing,
+            Some(85),
+            "Signing disk image",
+            &dmg_path,
+        );
         super::sign::sign_dmg(&dmg_path, settings).await?;
+        hooks::run_for_package(
+            settings,
+            Hook::PostSign,
+            PackageType::Dmg,
+            std::slice::from_ref(&dmg_path),
+            &[],
+        )
+        .await?;
+    }
+
+    // Step 7: Sparkle appcast, signed over the final DMG bytes
+    let mut paths = vec![dmg_path];
+    if let Some(sparkle) = &settings.bundle_settings().macos.sparkle {
+        paths.push(super::sparkle::write_appcast(&paths[0], sparkle, settings).await?);
     }
 
-    Ok(vec![dmg_path])
+    Ok(paths)
 }
@@ -24,11 +24,15 @@ use tokio::fs as tokio_fs;
 /// * `macos_dir` - Path to Contents/MacOS directory
 /// * `contents_dir` - Path to Contents directory
 /// * `settings` - Bundle configuration
+///
+/// # Returns
+/// Every Mach-O rewritten by `install_name_tool` (bundled dylibs first,
+/// then binaries), whose signatures must be replaced.
 pub async fn bundle_dylib_dependencies(
     macos_dir: &Path,
     contents_dir: &Path,
     settings: &Settings,
-) -> Result<()> {
+) -> Result<Vec<PathBuf>> {
     let frameworks_dir = contents_dir.join("Frameworks");
 
     // Create Frameworks directory if it doesn't exist
@@ -40,76 +44,206 @@ pub async fn bundle_dylib_dependencies(
 
     // Track processed dylibs across all binaries to avoid duplicates
     let mut processed = HashSet::new();
+    let mut rewritten_binaries = Vec::new();
+    let search_paths = library_search_paths(settings);
 
     // Process each binary in the bundle
     for binary in settings.binaries() {
-        let binary_path = if binary.main() {
-            macos_dir.join(binary.name())
-        } else {
-            contents_dir.join("Resources").join(binary.name())
+        let binary_path = match super::app::binary_dir(binary) {
+            "MacOS" => macos_dir.join(binary.name()),
+            dir => contents_dir.join(dir).join(binary.name()),
         };
 
-        log::info!("Discovering dylib dependencies for {}", binary.name());
-
-        // Get non-system dylib dependencies
-        let deps = get_dylib_dependencies(&binary_path)?;
-        let non_system: Vec<String> = deps
-            .into_iter()
-            .filter(|d| !is_system_dylib(d))
-            .collect();
+        tracing::info!("Discovering dylib dependencies for {}", binary.name());
+
+        // Relative references resolve against where the binary was built,
+        // not against its copy inside the bundle
+        let load_commands = get_load_commands(&binary_path)?;
+        let original_path = settings.binary_path(binary);
+        let executable_dir = original_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let context = LoadContext::new(
+            &executable_dir,
+            &executable_dir,
+            &load_commands.rpaths,
+            &[],
+            &search_paths,
+        );
+
+        // Resolve non-system dylib dependencies
+        let mut non_system = Vec::new();
+        for dep in load_commands.libs.iter().filter(|d| !is_system_dylib(d)) {
+            if !is_relative_reference(dep) {
+                non_system.push(resolve_dylib_path(dep, &context.search_paths)?);
+                continue;
+            }
+            match resolve_dependency(dep, &context) {
+                Some(path) => {
+                    tracing::debug!("  - {} ({})", dep, path.display());
+                    non_system.push(path);
+                }
+                None => {
+                    tracing::debug!("  - {} (unresolved, assumed to be in the bundle)", dep);
+                }
+            }
+        }
 
         if !non_system.is_empty() {
-            log::info!("Found {} non-system dylibs for {}", non_system.len(), binary.name());
-            for dylib in &non_system {
-                log::debug!("  - {}", dylib);
-            }
+            tracing::info!("Found {} non-system dylibs for {}", non_system.len(), binary.name());
         }
 
         // Bundle each non-system dylib recursively
-        for dylib_path_str in non_system {
-            let dylib_path = resolve_dylib_path(&dylib_path_str)?;
-            bundle_dylib_and_deps(&dylib_path, &frameworks_dir, &mut processed).await?;
+        for dylib_path in non_system {
+            bundle_dylib_and_deps(&dylib_path, &frameworks_dir, &mut processed, &context).await?;
         }
 
         // Fix binary's load paths to use @rpath
         if !processed.is_empty() {
-            fix_binary_dylib_paths(&binary_path, &processed).await?;
+            fix_binary_dylib_paths(&binary_path, &processed, &context).await?;
+            rewritten_binaries.push(binary_path);
         }
     }
 
     if !processed.is_empty() {
-        log::info!("Bundled {} unique dylibs into Frameworks/", processed.len());
+        tracing::info!("Bundled {} unique dylibs into Frameworks/", processed.len());
     }
 
-    Ok(())
+    // Bundled copies live at Frameworks/<file name> (see bundle_dylib_and_deps)
+    let mut rewritten: Vec<PathBuf> = processed
+        .iter()
+        .filter_map(|dylib| dylib.file_name())
+        .map(|name| frameworks_dir.join(name))
+        .collect();
+    rewritten.extend(rewritten_binaries);
+
+    Ok(rewritten)
+}
+
+/// Dylib and rpath load commands of a Mach-O binary
+#[derive(Debug, Default)]
+struct LoadCommands {
+    /// LC_LOAD_DYLIB (and weak/reexport) paths in first-seen order
+    libs: Vec<String>,
+    /// LC_RPATH entries in first-seen order
+    rpaths: Vec<String>,
+}
+
+impl LoadCommands {
+    fn extend(&mut self, macho: &goblin::mach::MachO<'_>) {
+        for lib in macho.libs.iter().map(|lib| lib.to_string()) {
+            if !self.libs.contains(&lib) {
+                self.libs.push(lib);
+            }
+        }
+        for rpath in macho.rpaths.iter().map(|rpath| rpath.to_string()) {
+            if !self.rpaths.contains(&rpath) {
+                self.rpaths.push(rpath);
+            }
+        }
+    }
+}
+
+/// Where dyld would look for the relative references of one Mach-O
+#[derive(Debug)]
+struct LoadContext {
+    /// Directory of the Mach-O itself (`@loader_path`)
+    loader_dir: PathBuf,
+    /// Directory of the main executable (`@executable_path`)
+    executable_dir: PathBuf,
+    /// Expanded rpaths: the Mach-O's own first, then those of its loaders
+    rpaths: Vec<PathBuf>,
+    /// Fallback directories for absolute paths that do not exist
+    search_paths: Vec<PathBuf>,
+}
+
+impl LoadContext {
+    /// Builds the context for a Mach-O in `loader_dir`
+    ///
+    /// `@loader_path` and `@executable_path` inside `rpaths` are expanded
+    /// here; `inherited` are the already expanded rpaths of the loading chain,
+    /// which dyld also searches.
+    fn new(
+        loader_dir: &Path,
+        executable_dir: &Path,
+        rpaths: &[String],
+        inherited: &[PathBuf],
+        search_paths: &[PathBuf],
+    ) -> Self {
+        let mut context = LoadContext {
+            loader_dir: loader_dir.to_path_buf(),
+            executable_dir: executable_dir.to_path_buf(),
+            rpaths: Vec::new(),
+            search_paths: search_paths.to_vec(),
+        };
+        let own: Vec<PathBuf> = rpaths
+            .iter()
+            .map(|rpath| context.expand(rpath))
+            .collect();
+        for rpath in own.into_iter().chain(inherited.iter().cloned()) {
+            if !context.rpaths.contains(&rpath) {
+                context.rpaths.push(rpath);
+            }
+        }
+        context
+    }
+
+    /// Replaces a leading `@loader_path`/`@executable_path` with a directory
+    fn expand(&self, path: &str) -> PathBuf {
+        if let Some(rest) = path.strip_prefix("@loader_path") {
+            self.loader_dir.join(rest.trim_start_matches('/'))
+        } else if let Some(rest) = path.strip_prefix("@executable_path") {
+            self.executable_dir.join(rest.trim_start_matches('/'))
+        } else {
+            PathBuf::from(path)
+        }
+    }
 }
 
 /// Extracts dynamic library dependencies from a Mach-O binary using goblin.
 ///
-/// Returns a list of dylib paths as strings.
+/// For fat (universal) binaries every architecture slice is parsed and the
+/// dependencies are unioned, since slices may link different libraries
+/// (e.g. an x86_64-only helper dylib). Returns a list of dylib paths as
+/// strings in first-seen order.
 fn get_dylib_dependencies(binary_path: &Path) -> Result<Vec<String>> {
+    Ok(get_load_commands(binary_path)?.libs)
+}
+
+/// Extracts dylib and rpath load commands, unioned across fat slices
+fn get_load_commands(binary_path: &Path) -> Result<LoadCommands> {
     let buffer = std::fs::read(binary_path)
         .fs_context("failed to read binary", binary_path)?;
 
+    let mut commands = LoadCommands::default();
     match goblin::Object::parse(&buffer)
         .map_err(|e| crate::bundler::error::Error::GenericError(format!("failed to parse binary with goblin: {}", e)))?
     {
-        goblin::Object::Mach(goblin::mach::Mach::Binary(macho)) => {
-            Ok(macho.libs.iter().map(|s| s.to_string()).collect())
-        }
-        goblin::Object::Mach(goblin::mach::Mach::Fat(_fat)) => {
-            // For fat binaries, parse the first architecture
-            // All architectures should have the same dylib dependencies
-            if let Ok(goblin::Object::Mach(goblin::mach::Mach::Binary(macho))) = goblin::Object::parse(&buffer) {
-                return Ok(macho.libs.iter().map(|s| s.to_string()).collect());
+        goblin::Object::Mach(goblin::mach::Mach::Binary(macho)) => commands.extend(&macho),
+        goblin::Object::Mach(goblin::mach::Mach::Fat(fat)) => {
+            for (index, arch) in fat.into_iter().enumerate() {
+                match arch {
+                    Ok(goblin::mach::SingleArch::MachO(macho)) => commands.extend(&macho),
+                    Ok(goblin::mach::SingleArch::Archive(_)) => {
+                        tracing::debug!("Skipping static archive slice {} in {}", index, binary_path.display());
+                    }
+                    Err(e) => {
+                        return Err(crate::bundler::error::Error::GenericError(format!(
+                            "failed to parse architecture slice {} of {}: {}",
+                            index,
+                            binary_path.display(),
+                            e
+                        )));
+                    }
+                }
             }
-            Ok(vec![])
         }
         _ => {
-            log::warn!("Binary {} is not a Mach-O file, skipping dylib discovery", binary_path.display());
-            Ok(vec![])
+            tracing::warn!("Binary {} is not a Mach-O file, skipping dylib discovery", binary_path.display());
         }
     }
+    Ok(commands)
 }
 
 /// Determines if a dylib path is a system library that should NOT be bundled.
@@ -122,27 +256,79 @@ fn get_dylib_dependencies(binary_path: &Path) -> Result<Vec<String>> {
 /// Non-system libraries include:
 /// - /opt/homebrew/ - Homebrew packages
 /// - /usr/local/Cellar/ - Homebrew Cellar
-/// - Relative paths or @rpath (already bundled)
+/// - @rpath, @loader_path and @executable_path references (see
+///   [`resolve_dependency`])
 fn is_system_dylib(path: &str) -> bool {
     path == "self" ||
     path.starts_with("/System/") ||
-    path.starts_with("/usr/lib/") ||
-    path.starts_with("@rpath") ||
-    path.starts_with("@executable_path") ||
-    path.starts_with("@loader_path")
+    path.starts_with("/usr/lib/")
+}
+
+/// Whether a load path is relative to an rpath, the loader or the executable
+fn is_relative_reference(path: &str) -> bool {
+    path.starts_with("@rpath/")
+        || path.starts_with("@loader_path/")
+        || path.starts_with("@executable_path/")
+}
+
+/// Resolves a load path to the file dyld would load.
+///
+/// `@loader_path` and `@executable_path` are taken from `context`; `@rpath`
+/// tries each of the context's rpaths in order. Returns `None` when a
+/// relative reference matches no existing file (typically a library that is
+/// already inside the bundle) or an absolute path cannot be found.
+fn resolve_dependency(dep: &str, context: &LoadContext) -> Option<PathBuf> {
+    if let Some(rest) = dep.strip_prefix("@rpath/") {
+        // Frameworks are copied whole by frameworks.rs, never as loose dylibs
+        if rest.contains(".framework/") {
+            return None;
+        }
+        return context
+            .rpaths
+            .iter()
+            .map(|rpath| rpath.join(rest))
+            .find(|candidate| candidate.is_file());
+    }
+    if dep.starts_with("@loader_path/") || dep.starts_with("@executable_path/") {
+        return Some(context.expand(dep)).filter(|candidate| candidate.is_file());
+    }
+    resolve_dylib_path(dep, &context.search_paths).ok()
+}
+
+/// Homebrew prefixes: Apple Silicon, then Intel
+const HOMEBREW_PREFIXES: [&str; 2] = ["/opt/homebrew", "/usr/local"];
+
+/// Library directories searched after `dylib_search_paths`
+const DEFAULT_LIBRARY_DIRS: [&str; 3] = ["/opt/homebrew/lib", "/usr/local/lib", "/opt/local/lib"];
+
+/// Directories searched for dylibs missing from their recorded path
+fn library_search_paths(settings: &Settings) -> Vec<PathBuf> {
+    settings
+        .bundle_settings()
+        .macos
+        .dylib_search_paths
+        .iter()
+        .cloned()
+        .chain(DEFAULT_LIBRARY_DIRS.iter().map(PathBuf::from))
+        .collect()
 }
 
 /// Resolves a dylib path string to an actual filesystem path.
 ///
 /// Handles:
-/// - Absolute paths: returned as-is
+/// - Absolute paths: returned as-is when they exist
+/// - Homebrew `opt/<formula>` links that are missing: the formula's Cellar
+/// - Other missing absolute paths and unresolved wildcards: the file name
+///   is looked up in `search_paths`
 /// - Relative paths: error
-/// - @rpath, @executable_path: error (should be filtered earlier)
-fn resolve_dylib_path(path_str: &str) -> Result<PathBuf> {
+/// - @rpath, @executable_path: error (see [`resolve_dependency`])
+fn resolve_dylib_path(path_str: &str, search_paths: &[PathBuf]) -> Result<PathBuf> {
     // Handle wildcard paths from Homebrew (e.g., /opt/homebrew/*/lib/libpcre2.dylib)
     if path_str.contains('*') {
         // Try to resolve the wildcard
-        if let Some(resolved) = resolve_wildcard_path(path_str) {
+        if let Some(resolved) = resolve_wildcard_path(path_str)
+            .or_else(|| find_in_search_paths(path_str, search_paths))
+        {
             return Ok(resolved);
         }
         return Err(crate::bundler::error::Error::GenericError(format!(
@@ -160,54 +346,96 @@ fn resolve_dylib_path(path_str: &str) -> Result<PathBuf> {
         )));
     }
 
-    if !path.exists() {
-        return Err(crate::bundler::error::Error::GenericError(format!(
-            "Dylib not found: {}",
-            path_str
-        )));
+    if path.exists() {
+        return Ok(path);
     }
 
-    Ok(path)
+    // The opt/ symlink may be gone (formula unlinked) while the Cellar remains
+    if let Some(resolved) = resolve_opt_path(path_str)
+        .or_else(|| find_in_search_paths(path_str, search_paths))
+    {
+        tracing::debug!("Resolved missing dylib {} to {}", path_str, resolved.display());
+        return Ok(resolved);
+    }
+
+    Err(crate::bundler::error::Error::GenericError(format!(
+        "Dylib not found: {} (searched {})",
+        path_str,
+        search_paths
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
 }
 
 /// Resolves wildcard paths by checking common locations.
 ///
-/// For paths like "/opt/homebrew/*/lib/libpcre2.dylib", checks:
-/// - /opt/homebrew/Cellar/*/lib/libpcre2.dylib
+/// For paths like "/opt/homebrew/*/lib/libpcre2.dylib" or
+/// "/usr/local/*/lib/libpcre2.dylib", checks the prefix's Cellar
+/// (`<prefix>/Cellar/<formula>/<version>/lib/libpcre2.dylib`). Any other
+/// pattern (e.g. MacPorts' `/opt/local/*/...`) is globbed as written.
 fn resolve_wildcard_path(path_str: &str) -> Option<PathBuf> {
-    // Replace * with Cellar/* pattern for Homebrew
-    if let Some(after_homebrew) = path_str.strip_prefix("/opt/homebrew/*/") {
-        // Try Cellar pattern
-        let pattern = format!("/opt/homebrew/Cellar/*/{}", after_homebrew);
-        if let Ok(entries) = glob::glob(&pattern) {
-            // Return first match
-            for entry in entries.flatten() {
-                if entry.exists() {
-                    return Some(entry);
-                }
-            }
-        }
-    }
-    None
+    let cellar_pattern = HOMEBREW_PREFIXES.iter().find_map(|prefix| {
+        let after_prefix = path_str.strip_prefix(&format!("{}/*/", prefix))?;
+        Some(format!("{}/Cellar/*/*/{}", prefix, after_prefix))
+    });
+
+    [cellar_pattern, Some(path_str.to_string())]
+        .into_iter()
+        .flatten()
+        .find_map(|pattern| first_glob_match(&pattern))
+}
+
+/// Maps a Homebrew `<prefix>/opt/<formula>/...` path to the formula's Cellar
+fn resolve_opt_path(path_str: &str) -> Option<PathBuf> {
+    HOMEBREW_PREFIXES.iter().find_map(|prefix| {
+        let after_opt = path_str.strip_prefix(&format!("{}/opt/", prefix))?;
+        let (formula, rest) = after_opt.split_once('/')?;
+        first_glob_match(&format!("{}/Cellar/{}/*/{}", prefix, formula, rest))
+    })
+}
+
+/// Looks up a dylib's file name in `search_paths`, in order
+fn find_in_search_paths(path_str: &str, search_paths: &[PathBuf]) -> Option<PathBuf> {
+    let file_name = Path::new(path_str).file_name()?;
+    search_paths
+        .iter()
+        .map(|dir| dir.join(file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// First existing path matching a glob pattern
+fn first_glob_match(pattern: &str) -> Option<PathBuf> {
+    glob::glob(pattern)
+        .ok()?
+        .flatten()
+        .find(|entry| entry.exists())
 }
 
 /// Recursively bundles a dylib and its dependencies into the Frameworks directory.
 ///
+/// Dependencies referenced through `@rpath`, `@loader_path` or
+/// `@executable_path` are resolved against the dylib's own location and
+/// rpaths, followed by the rpaths of the binaries that loaded it.
+///
 /// # Arguments
 /// * `dylib_path` - Path to the dylib to bundle
 /// * `frameworks_dir` - Destination Frameworks directory
 /// * `processed` - Set of already-processed dylib paths (to avoid duplicates)
+/// * `loader` - Load context of the binary or dylib that references this one
 async fn bundle_dylib_and_deps(
     dylib_path: &Path,
     frameworks_dir: &Path,
     processed: &mut HashSet<PathBuf>,
+    loader: &LoadContext,
 ) -> Result<()> {
     // Skip if already processed
     if processed.contains(dylib_path) {
         return Ok(());
     }
 
-    log::debug!("Bundling dylib: {}", dylib_path.display());
+    tracing::debug!("Bundling dylib: {}", dylib_path.display());
 
     // Mark as processed
     processed.insert(dylib_path.to_path_buf());
@@ -226,57 +454,77 @@ async fn bundle_dylib_and_deps(
         .fs_context("failed to copy dylib to Frameworks", dylib_path)?;
 
     // Get this dylib's dependencies
-    let deps = get_dylib_dependencies(dylib_path)?;
-    let non_system: Vec<String> = deps
-        .into_iter()
-        .filter(|d| !is_system_dylib(d))
-        .collect();
+    let load_commands = get_load_commands(dylib_path)?;
+    let loader_dir = dylib_path.parent().unwrap_or(Path::new("/"));
+    let context = LoadContext::new(
+        loader_dir,
+        &loader.executable_dir,
+        &load_commands.rpaths,
+        &loader.rpaths,
+        &loader.search_paths,
+    );
 
     // Recursively bundle dependencies
-    for dep_path_str in non_system {
-        if let Ok(dep_path) = resolve_dylib_path(&dep_path_str) {
-            Box::pin(bundle_dylib_and_deps(&dep_path, frameworks_dir, processed)).await?;
+    for dep in load_commands.libs.iter().filter(|d| !is_system_dylib(d)) {
+        match resolve_dependency(dep, &context) {
+            Some(dep_path) => {
+                Box::pin(bundle_dylib_and_deps(&dep_path, frameworks_dir, processed, &context)).await?;
+            }
+            None => tracing::debug!("Unresolved dependency {} of {}", dep, dylib_path.display()),
         }
     }
 
     // Fix this dylib's internal load paths
-    fix_dylib_internal_paths(&dest_path, processed).await?;
+    fix_dylib_internal_paths(&dest_path, processed, &context).await?;
 
     Ok(())
 }
 
+/// Load path a dependency gets once bundled, if it was bundled
+///
+/// Only dependencies that resolve to a dylib copied into Frameworks are
+/// rewritten, so already-correct references (e.g. `@rpath/Foo.framework/...`
+/// from framework bundling) are left alone.
+fn bundled_load_path(dep: &str, processed: &HashSet<PathBuf>, context: &LoadContext) -> Option<String> {
+    if is_system_dylib(dep) {
+        return None;
+    }
+    let resolved = resolve_dependency(dep, context)?;
+    if !processed.contains(&resolved) {
+        return None;
+    }
+    let new_path = format!("@rpath/{}", resolved.file_name()?.to_string_lossy());
+    (new_path != dep).then_some(new_path)
+}
+
 /// Rewrites a dylib's internal load paths to use @rpath.
 ///
 /// This fixes the dylib's dependencies to point to @rpath instead of absolute paths.
 async fn fix_dylib_internal_paths(
     dylib_path: &Path,
-    _processed: &HashSet<PathBuf>,
+    processed: &HashSet<PathBuf>,
+    context: &LoadContext,
 ) -> Result<()> {
     // Get dependencies
     let deps = get_dylib_dependencies(dylib_path)?;
 
     for dep in deps {
-        if is_system_dylib(&dep) {
-            continue; // Keep system libs as-is
-        }
+        let Some(new_path) = bundled_load_path(&dep, processed, context) else {
+            continue; // Keep system and unbundled libs as-is
+        };
 
-        // Extract filename from dependency path
-        if let Some(filename) = PathBuf::from(&dep).file_name() {
-            let new_path = format!("@rpath/{}", filename.to_string_lossy());
-
-            // Use install_name_tool to rewrite the load path
-            let status = Command::new("install_name_tool")
-                .arg("-change")
-                .arg(&dep)
-                .arg(&new_path)
-                .arg(dylib_path)
-                .status()
-                .fs_context("failed to run install_name_tool", dylib_path)?;
-
-            if !status.success() {
-                log::warn!("install_name_tool failed for {}: {} -> {}",
-                    dylib_path.display(), dep, new_path);
-            }
+        // Use install_name_tool to rewrite the load path
+        let status = Command::new("install_name_tool")
+            .arg("-change")
+            .arg(&dep)
+            .arg(&new_path)
+            .arg(dylib_path)
+            .status()
+            .fs_context("failed to run install_name_tool", dylib_path)?;
+
+        if !status.success() {
+            tracing::warn!("install_name_tool failed for {}: {} -> {}",
+                dylib_path.display(), dep, new_path);
         }
     }
 
@@ -287,47 +535,44 @@ async fn fix_dylib_internal_paths(
 ///
 /// # Arguments
 /// * `binary_path` - Path to the binary to fix
-/// * `_processed` - Set of dylibs that were bundled (for filtering)
+/// * `processed` - Set of dylibs that were bundled (for filtering)
+/// * `context` - Load context of the binary's original location
 async fn fix_binary_dylib_paths(
     binary_path: &Path,
-    _processed: &HashSet<PathBuf>,
+    processed: &HashSet<PathBuf>,
+    context: &LoadContext,
 ) -> Result<()> {
-    log::info!("Fixing dylib paths for {}", binary_path.display());
+    tracing::info!("Fixing dylib paths for {}", binary_path.display());
 
     // Get binary's dependencies
     let deps = get_dylib_dependencies(binary_path)?;
 
-    // Rewrite each non-system dependency to use @rpath
+    // Rewrite each bundled dependency to use @rpath
     for dep in deps {
-        if is_system_dylib(&dep) {
+        let Some(new_path) = bundled_load_path(&dep, processed, context) else {
             continue;
-        }
+        };
 
-        // Extract filename
-        if let Some(filename) = PathBuf::from(&dep).file_name() {
-            let new_path = format!("@rpath/{}", filename.to_string_lossy());
-
-            log::debug!("  Rewriting: {} -> {}", dep, new_path);
-
-            let status = Command::new("install_name_tool")
-                .arg("-change")
-                .arg(&dep)
-                .arg(&new_path)
-                .arg(binary_path)
-                .status()
-                .fs_context("failed to run install_name_tool", binary_path)?;
-
-            if !status.success() {
-                return Err(crate::bundler::error::Error::GenericError(format!(
-                    "install_name_tool failed for {}: {} -> {}",
-                    binary_path.display(), dep, new_path
-                )));
-            }
+        tracing::debug!("  Rewriting: {} -> {}", dep, new_path);
+
+        let status = Command::new("install_name_tool")
+            .arg("-change")
+            .arg(&dep)
+            .arg(&new_path)
+            .arg(binary_path)
+            .status()
+            .fs_context("failed to run install_name_tool", binary_path)?;
+
+        if !status.success() {
+            return Err(crate::bundler::error::Error::GenericError(format!(
+                "install_name_tool failed for {}: {} -> {}",
+                binary_path.display(), dep, new_path
+            )));
         }
     }
 
     // Add rpath pointing to @executable_path/../Frameworks
-    log::debug!("  Adding rpath: @executable_path/../Frameworks");
+    tracing::debug!("  Adding rpath: @executable_path/../Frameworks");
 
     let status = Command::new("install_name_tool")
         .arg("-add_rpath")
@@ -338,7 +583,7 @@ async fn fix_binary_dylib_paths(
 
     if !status.success() {
         // This might fail if rpath already exists - that's OK
-        log::debug!("  rpath may already exist (this is OK)");
+        tracing::debug!("  rpath may already exist (this is OK)");
     }
 
     Ok(())
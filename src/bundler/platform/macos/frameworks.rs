@@ -0,0 +1,263 @@
+//! Framework bundling for macOS .app bundles.
+//!
+//! Copies the frameworks listed in `MacOsSettings.frameworks` into
+//! `Contents/Frameworks`, preserving the `Versions/` symlink structure,
+//! rewrites each framework's install name to `@rpath/...`, points the
+//! bundled binaries at the copies, and re-signs every modified framework
+//! so its signature matches the rewritten Mach-O.
+
+use crate::bundler::{
+    error::{Context, ErrorExt, Result},
+    settings::Settings,
+    utils::fs,
+};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// A framework copied into `Contents/Frameworks`
+#[derive(Debug)]
+pub struct BundledFramework {
+    /// Install name the framework had before bundling
+    /// (e.g. `/Library/Frameworks/Foo.framework/Versions/A/Foo`)
+    pub original_install_name: Option<String>,
+    /// Install name after bundling (`@rpath/Foo.framework/Versions/A/Foo`)
+    pub install_name: String,
+    /// Bundled framework directory
+    pub path: PathBuf,
+}
+
+/// Copies configured frameworks and `.dylib` files into `Contents/Frameworks`.
+///
+/// `.framework` entries are copied with their symlinks intact and get an
+/// `@rpath` install name; entries without an extension are looked up in the
+/// standard framework locations. Frameworks whose install name changes are
+/// re-signed with `identity`. Returns the bundled `.framework`s for
+/// [`link_frameworks`].
+pub async fn copy_frameworks(
+    contents_dir: &Path,
+    settings: &Settings,
+    identity: &str,
+) -> Result<Vec<BundledFramework>> {
+    let frameworks = match &settings.bundle_settings().macos.frameworks {
+        Some(f) if !f.is_empty() => f,
+        _ => return Ok(Vec::new()), // No frameworks to copy
+    };
+
+    let frameworks_dir = contents_dir.join("Frameworks");
+    tokio::fs::create_dir_all(&frameworks_dir)
+        .await
+        .fs_context("failed to create Frameworks directory", &frameworks_dir)?;
+
+    let mut bundled = Vec::new();
+    for framework in frameworks {
+        if framework.ends_with(".dylib") {
+            // Copy .dylib file; dylib bundling rewrites references to it
+            let src = PathBuf::from(framework);
+            let name = src.file_name().ok_or_else(|| {
+                crate::bundler::error::Error::GenericError(format!(
+                    "Invalid dylib path: {}",
+                    framework
+                ))
+            })?;
+            let dst = frameworks_dir.join(name);
+            fs::copy_file(&src, &dst)
+                .await
+                .with_context(|| format!("failed to copy dylib {}", framework))?;
+            continue;
+        }
+
+        let src = if framework.ends_with(".framework") {
+            PathBuf::from(framework)
+        } else {
+            find_framework(framework)?
+        };
+        bundled.push(bundle_framework(&src, &frameworks_dir, identity).await?);
+    }
+    Ok(bundled)
+}
+
+/// Points the bundle's binaries at the bundled frameworks.
+///
+/// Rewrites every load command referencing a framework's original install
+/// name to its `@rpath` name and adds `@executable_path/../Frameworks` as
+/// an rpath. Must run after the binaries are copied and before dylib
+/// bundling, which would otherwise copy framework binaries as loose dylibs.
+///
+/// Returns the rewritten binaries, which need re-signing.
+pub async fn link_frameworks(
+    contents_dir: &Path,
+    settings: &Settings,
+    frameworks: &[BundledFramework],
+) -> Result<Vec<PathBuf>> {
+    let mut rewritten = Vec::new();
+    if frameworks.is_empty() {
+        return Ok(rewritten);
+    }
+
+    for binary in settings.binaries() {
+        let binary_path = contents_dir
+            .join(super::app::binary_dir(binary))
+            .join(binary.name());
+
+        for framework in frameworks {
+            let Some(original) = &framework.original_install_name else {
+                continue;
+            };
+            if original == &framework.install_name {
+                continue;
+            }
+            // -change is a no-op when the binary doesn't link the framework
+            install_name_tool(&["-change", original, &framework.install_name], &binary_path)
+                .await?;
+        }
+
+        // Fails harmlessly if the rpath already exists
+        let _ = install_name_tool(
+            &["-add_rpath", "@executable_path/../Frameworks"],
+            &binary_path,
+        )
+        .await;
+        rewritten.push(binary_path);
+    }
+
+    Ok(rewritten)
+}
+
+/// Copies one `.framework` and rewrites its install name.
+async fn bundle_framework(
+    src: &Path,
+    frameworks_dir: &Path,
+    identity: &str,
+) -> Result<BundledFramework> {
+    let name = src
+        .file_name()
+        .ok_or_else(|| {
+            crate::bundler::error::Error::GenericError(format!(
+                "Invalid framework path: {}",
+                src.display()
+            ))
+        })?
+        .to_owned();
+    let stem = Path::new(&name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let dst = frameworks_dir.join(&name);
+
+    tracing::info!("Bundling framework {}", src.display());
+    if dst.exists() {
+        tokio::fs::remove_dir_all(&dst)
+            .await
+            .fs_context("failed to remove previously bundled framework", &dst)?;
+    }
+    fs::copy_dir(src, &dst)
+        .await
+        .with_context(|| format!("failed to copy framework {}", src.display()))?;
+
+    // Foo.framework/Foo is a symlink to Versions/Current/Foo; resolve it so the
+    // install name names the concrete version like the linker does
+    let binary = dst.join(&stem);
+    let canonical_binary = binary
+        .canonicalize()
+        .fs_context("framework binary not found", &binary)?;
+    let canonical_dst = dst
+        .canonicalize()
+        .fs_context("failed to resolve framework path", &dst)?;
+    let relative = canonical_binary
+        .strip_prefix(&canonical_dst)
+        .map_err(|_| {
+            crate::bundler::error::Error::GenericError(format!(
+                "Framework binary {} points outside the framework",
+                binary.display()
+            ))
+        })?;
+
+    let install_name = format!(
+        "@rpath/{}/{}",
+        name.to_string_lossy(),
+        relative.to_string_lossy()
+    );
+    let original_install_name = install_name_of(&canonical_binary)?;
+
+    if original_install_name.as_deref() != Some(install_name.as_str()) {
+        install_name_tool(&["-id", &install_name], &canonical_binary).await?;
+        // install_name_tool invalidated the framework's signature
+        super::sign::resign(&dst, identity).await?;
+    }
+
+    Ok(BundledFramework {
+        original_install_name,
+        install_name,
+        path: dst,
+    })
+}
+
+/// Searches for a framework in standard macOS locations
+fn find_framework(framework: &str) -> Result<PathBuf> {
+    let framework_name = format!("{}.framework", framework);
+
+    // Search paths in order of preference
+    let mut search_paths = vec![
+        PathBuf::from("/Library/Frameworks"),
+        PathBuf::from("/Network/Library/Frameworks"),
+    ];
+
+    // Add user's home directory if available
+    if let Ok(home) = std::env::var("HOME") {
+        search_paths.insert(0, PathBuf::from(home).join("Library/Frameworks"));
+    }
+
+    search_paths
+        .into_iter()
+        .map(|dir| dir.join(&framework_name))
+        .find(|candidate| candidate.exists())
+        .ok_or_else(|| {
+            crate::bundler::error::Error::GenericError(format!(
+                "Framework not found: {}",
+                framework
+            ))
+        })
+}
+
+/// Reads the LC_ID_DYLIB install name of a Mach-O dylib
+fn install_name_of(binary: &Path) -> Result<Option<String>> {
+    let buffer = std::fs::read(binary).fs_context("failed to read framework binary", binary)?;
+    let object = goblin::Object::parse(&buffer).map_err(|e| {
+        crate::bundler::error::Error::GenericError(format!(
+            "failed to parse {}: {}",
+            binary.display(),
+            e
+        ))
+    })?;
+
+    Ok(match object {
+        goblin::Object::Mach(goblin::mach::Mach::Binary(macho)) => macho.name.map(str::to_string),
+        goblin::Object::Mach(goblin::mach::Mach::Fat(fat)) => fat
+            .into_iter()
+            .filter_map(|arch| arch.ok())
+            .find_map(|arch| match arch {
+                goblin::mach::SingleArch::MachO(macho) => macho.name.map(str::to_string),
+                _ => None,
+            }),
+        _ => None,
+    })
+}
+
+async fn install_name_tool(args: &[&str], binary: &Path) -> Result<()> {
+    let output = Command::new("install_name_tool")
+        .args(args)
+        .arg(binary)
+        .output()
+        .await
+        .fs_context("failed to run install_name_tool", binary)?;
+
+    if !output.status.success() {
+        return Err(crate::bundler::error::Error::GenericError(format!(
+            "install_name_tool {} failed for {}: {}",
+            args.join(" "),
+            binary.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
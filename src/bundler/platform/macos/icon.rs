@@ -3,15 +3,27 @@
 #![cfg(target_os = "macos")]
 
 use crate::bundler::error::{ErrorExt, Result};
-use crate::bundler::resources::icons::{IconInfo, find_icon_for_size, load_and_resize};
+use crate::bundler::resources::icons::{ICNS_SIZES, IconInfo, find_icon_for_size, load_and_resize, missing_sizes};
 use icns::{IconFamily, IconType, Image as IconsImage};
 use std::path::Path;
 use tokio::task;
 
 /// Create ICNS file from source icons using unified infrastructure
+///
+/// Used when no pre-made `icon.icns` exists, so projects only maintain PNG
+/// sources. Sizes without an exact PNG are scaled from the nearest one.
 pub async fn create_icns_file(icons: &[IconInfo], output: &Path) -> Result<()> {
     let mut family = IconFamily::new();
 
+    let missing = missing_sizes(icons, &ICNS_SIZES);
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|size| format!("{}x{}", size, size)).collect();
+        tracing::warn!(
+            "No PNG icon for {} in the generated .icns; scaling the nearest size instead",
+            missing.join(", ")
+        );
+    }
+
     let icon_types = [
         (IconType::RGBA32_16x16, 16, "16x16"),
         (IconType::RGBA32_16x16_2x, 32, "16x16@2x"),
@@ -28,7 +40,7 @@ pub async fn create_icns_file(icons: &[IconInfo], output: &Path) -> Result<()> {
 
     for (icon_type, size, name) in icon_types {
         if let Some(icon_info) = find_icon_for_size(icons, size) {
-            log::debug!("Adding {} from {}", name, icon_info.path.display());
+            tracing::debug!("Adding {} from {}", name, icon_info.path.display());
 
             let rgba = load_and_resize(&icon_info.path, size, size)?;
 
@@ -50,7 +62,7 @@ pub async fn create_icns_file(icons: &[IconInfo], output: &Path) -> Result<()> {
                     ))
                 })?;
         } else {
-            log::warn!("No suitable source icon for {}", name);
+            tracing::warn!("No suitable source icon for {}", name);
         }
     }
 
@@ -70,6 +82,6 @@ pub async fn create_icns_file(icons: &[IconInfo], output: &Path) -> Result<()> {
         crate::bundler::Error::GenericError(format!("ICNS encoding task failed: {}", e))
     })??;
 
-    log::info!("Created ICNS file: {}", output.display());
+    tracing::info!("Created ICNS file: {}", output.display());
     Ok(())
 }
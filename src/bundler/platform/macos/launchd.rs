@@ -0,0 +1,117 @@
+//! launchd job plists embedded in the .app bundle.
+//!
+//! Jobs are written to `Contents/Library/LaunchAgents` or
+//! `Contents/Library/LaunchDaemons`, the locations `SMAppService` reads
+//! from. `BundleProgram` is relative to the bundle, so the job keeps
+//! working wherever the user installs the app.
+
+use crate::bundler::{
+    error::{Context, ErrorExt, Result},
+    settings::{LaunchAgentSettings, Settings},
+};
+use std::path::{Path, PathBuf};
+
+/// Writes a plist for every configured launch agent into `contents_dir`.
+///
+/// Returns the paths of the written plists.
+pub async fn write_launch_agents(contents_dir: &Path, settings: &Settings) -> Result<Vec<PathBuf>> {
+    let agents = &settings.bundle_settings().macos.launch_agents;
+    let bundle_identifier = settings.bundle_settings().identifier.as_deref();
+    let mut written = Vec::with_capacity(agents.len());
+
+    for agent in agents {
+        if agent.label.is_empty() || agent.label.contains('/') {
+            return Err(crate::bundler::Error::GenericError(format!(
+                "Invalid launch agent label '{}': must be non-empty and contain no '/'",
+                agent.label
+            )));
+        }
+
+        let program = bundle_program(agent, settings)?;
+        let dict = job_dictionary(agent, &program, bundle_identifier);
+
+        let dir = contents_dir.join("Library").join(if agent.daemon {
+            "LaunchDaemons"
+        } else {
+            "LaunchAgents"
+        });
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .fs_context("failed to create launchd directory", &dir)?;
+
+        let plist_path = dir.join(format!("{}.plist", agent.label));
+        plist::Value::Dictionary(dict)
+            .to_file_xml(&plist_path)
+            .map_err(crate::bundler::Error::Plist)
+            .with_context(|| format!("failed to write launchd plist for {}", agent.label))?;
+
+        tracing::info!("Embedded launchd job {}", plist_path.display());
+        written.push(plist_path);
+    }
+
+    Ok(written)
+}
+
+/// Bundle-relative path of the job's executable.
+///
+/// Matches the layout of `app::copy_binaries`: the main binary and sidecars
+/// live in `Contents/MacOS`, all others in `Contents/Resources`.
+fn bundle_program(agent: &LaunchAgentSettings, settings: &Settings) -> Result<String> {
+    let binary = match &agent.program {
+        Some(name) => settings.binaries().iter().find(|b| b.name() == name),
+        None => settings.binaries().iter().find(|b| b.main()),
+    }
+    .ok_or_else(|| {
+        crate::bundler::Error::GenericError(format!(
+            "Launch agent {} runs binary '{}', which is not part of the bundle",
+            agent.label,
+            agent.program.as_deref().unwrap_or("<main>")
+        ))
+    })?;
+
+    Ok(format!(
+        "Contents/{}/{}",
+        super::app::binary_dir(binary),
+        binary.name()
+    ))
+}
+
+fn job_dictionary(
+    agent: &LaunchAgentSettings,
+    program: &str,
+    bundle_identifier: Option<&str>,
+) -> plist::Dictionary {
+    let mut dict = plist::Dictionary::new();
+    dict.insert("Label".into(), agent.label.clone().into());
+    dict.insert("BundleProgram".into(), program.into());
+
+    // launchd passes ProgramArguments verbatim as argv, including argv[0]
+    let arguments = std::iter::once(program.to_string())
+        .chain(agent.arguments.iter().cloned())
+        .map(plist::Value::from)
+        .collect::<Vec<_>>();
+    dict.insert("ProgramArguments".into(), arguments.into());
+
+    dict.insert("RunAtLoad".into(), agent.run_at_load.into());
+    dict.insert("KeepAlive".into(), agent.keep_alive.into());
+
+    if !agent.environment.is_empty() {
+        let mut environment = plist::Dictionary::new();
+        let mut vars: Vec<_> = agent.environment.iter().collect();
+        vars.sort();
+        for (key, value) in vars {
+            environment.insert(key.clone(), value.clone().into());
+        }
+        dict.insert("EnvironmentVariables".into(), environment.into());
+    }
+
+    // Lets System Settings > Login Items attribute the job to the app
+    if let Some(identifier) = bundle_identifier {
+        dict.insert(
+            "AssociatedBundleIdentifiers".into(),
+            vec![plist::Value::from(identifier)].into(),
+        );
+    }
+
+    dict
+}
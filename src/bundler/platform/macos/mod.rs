@@ -44,6 +44,9 @@
 pub mod app;
 pub mod dmg;
 pub mod dylib;
+pub mod frameworks;
 pub mod icon;
+pub mod launchd;
 pub mod sign;
+pub mod sparkle;
 pub mod universal;
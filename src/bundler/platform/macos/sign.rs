@@ -3,15 +3,41 @@
 //! This module provides integration between the bundler and the kodegen_sign
 //! package, adapting bundler Settings to the sign crate's API.
 
-use crate::bundler::{error::Result, settings::Settings};
-use std::path::Path;
+use crate::bundler::{
+    error::{ErrorExt, Result},
+    settings::Settings,
+};
+use std::path::{Path, PathBuf};
+
+/// Signing identity marker for ad-hoc signatures.
+pub const AD_HOC_IDENTITY: &str = "-";
+
+/// Resolve the identity used to sign the .app bundle
+///
+/// Prefers the identity imported from APPLE_CERTIFICATE. When none is
+/// available and `dev_sign` is enabled, falls back to an ad-hoc signature
+/// so the app runs locally on Apple Silicon.
+pub fn resolve_signing_identity<'a>(
+    settings: &Settings,
+    runtime_identity: Option<&'a str>,
+) -> Option<&'a str> {
+    match runtime_identity {
+        Some(identity) => Some(identity),
+        None if settings.bundle_settings().macos.dev_sign => {
+            tracing::info!("No signing identity available, falling back to ad-hoc signing (--dev-sign)");
+            Some(AD_HOC_IDENTITY)
+        }
+        None => None,
+    }
+}
 
 /// Sign a macOS app bundle using kodegen_sign
 ///
 /// This function:
 /// 1. Signs the bundle using the provided identity
 /// 2. Calls kodegen_bundler_sign::macos::sign_with_entitlements with hardened runtime
-/// 3. Uses entitlements from conventional path if present
+/// 3. Uses the configured entitlements file, or one generated from
+///    `entitlement_presets` (see [`resolve_entitlements`])
 ///
 /// # Arguments
 /// * `app_bundle` - Path to the .app bundle to sign
@@ -34,32 +60,129 @@ use std::path::Path;
 /// # Ok(())
 /// # }
 /// ```
+#[tracing::instrument(name = "sign", skip_all, fields(path = %app_bundle.display()))]
 pub async fn sign_app(app_bundle: &Path, identity: &str, settings: &Settings) -> Result<()> {
-    log::info!(
+    tracing::info!(
         "Signing {} with identity '{}'",
         app_bundle.display(),
         identity
     );
 
-    // Get entitlements path if configured
-    let entitlements = settings.bundle_settings().macos.entitlements.as_deref();
+    // Get entitlements path if configured or generated from presets
+    let entitlements = resolve_entitlements(settings)?;
+    let entitlements = entitlements.as_deref();
 
-    // Sign with hardened runtime and deep signing for all nested components
+    // Hardened runtime only matters for notarization, which ad-hoc
+    // signatures can never pass - leave it off so local runs aren't
+    // restricted by library validation
+    let hardened_runtime = identity != AD_HOC_IDENTITY;
+
+    // Sign with deep signing for all nested components
     kodegen_bundler_sign::macos::sign_with_entitlements(
         app_bundle,
         identity,
         entitlements,
-        true, // hardened_runtime = true
+        hardened_runtime,
         true, // deep = true (signs all bundled dylibs/frameworks)
     )
     .await
     .map_err(|e| crate::bundler::Error::GenericError(format!("Code signing failed: {}", e)))?;
 
-    log::info!("✓ Successfully signed {} and all nested components", app_bundle.display());
+    tracing::info!("✓ Successfully signed {} and all nested components", app_bundle.display());
 
     Ok(())
 }
 
+/// Replace the signature of a single Mach-O file or bundle
+///
+/// Used after `install_name_tool` rewrites, which invalidate the existing
+/// signature. Not deep: nested code must be signed first.
+pub async fn resign(path: &Path, identity: &str) -> Result<()> {
+    let output = tokio::process::Command::new("codesign")
+        .args(["--force", "--sign", identity])
+        .arg(path)
+        .output()
+        .await
+        .fs_context("failed to run codesign", path)?;
+
+    if !output.status.success() {
+        return Err(crate::bundler::Error::GenericError(format!(
+            "codesign --force failed for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Identity used to re-sign Mach-O files after `install_name_tool`
+///
+/// The resolved signing identity when signing runs, ad-hoc otherwise:
+/// an invalidated signature makes Apple Silicon refuse to load the file,
+/// while an ad-hoc one keeps unsigned development builds runnable.
+pub fn rewrite_identity<'a>(settings: &Settings, identity: Option<&'a str>) -> &'a str {
+    match identity {
+        Some(identity) if !settings.skip_phases().sign => identity,
+        _ => AD_HOC_IDENTITY,
+    }
+}
+
+/// Re-sign every Mach-O rewritten by `install_name_tool`
+///
+/// Files are signed in the given order after removing duplicates; callers
+/// list dependencies (dylibs) before the binaries loading them.
+pub async fn resign_rewritten(paths: &[PathBuf], identity: &str) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for path in paths.iter().filter(|p| seen.insert(*p)) {
+        tracing::debug!("Re-signing rewritten {}", path.display());
+        resign(path, identity).await?;
+    }
+    if !seen.is_empty() {
+        tracing::info!("✓ Re-signed {} rewritten Mach-O file(s)", seen.len());
+    }
+    Ok(())
+}
+
+/// Entitlements file to sign with
+///
+/// An explicit `entitlements` file wins. Otherwise, when presets are
+/// configured, their entitlements are written to
+/// `bundle/macos/{ProductName}.entitlements` and that path is returned.
+pub fn resolve_entitlements(settings: &Settings) -> Result<Option<PathBuf>> {
+    let macos = &settings.bundle_settings().macos;
+    if let Some(path) = &macos.entitlements {
+        if !macos.entitlement_presets.is_empty() {
+            tracing::warn!(
+                "Both entitlements and entitlement_presets are set; using {}",
+                path.display()
+            );
+        }
+        return Ok(Some(path.clone()));
+    }
+    if macos.entitlement_presets.is_empty() {
+        return Ok(None);
+    }
+
+    let mut dict = plist::Dictionary::new();
+    for key in macos
+        .entitlement_presets
+        .iter()
+        .flat_map(|preset| preset.entitlements())
+    {
+        dict.insert((*key).to_string(), true.into());
+    }
+
+    let dir = settings.project_out_directory().join("bundle/macos");
+    std::fs::create_dir_all(&dir).fs_context("failed to create bundle directory", &dir)?;
+    let path = dir.join(format!("{}.entitlements", settings.product_name()));
+    plist::Value::Dictionary(dict)
+        .to_file_xml(&path)
+        .map_err(crate::bundler::Error::Plist)?;
+
+    tracing::debug!("Generated entitlements from presets: {}", path.display());
+    Ok(Some(path))
+}
+
 /// Notarize a macOS app bundle with Apple
 ///
 /// This function:
@@ -93,13 +216,14 @@ pub async fn sign_app(app_bundle: &Path, identity: &str, settings: &Settings) ->
 /// export APPLE_API_ISSUER="xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx"
 /// export APPLE_API_KEY_PATH="~/.keys/AuthKey_ABCD123456.p8"
 /// ```
+#[tracing::instrument(name = "sign", skip_all, fields(path = %app_bundle.display(), notarize = true))]
 pub async fn notarize_app(app_bundle: &Path, settings: &Settings) -> Result<()> {
     if settings.bundle_settings().macos.skip_notarization {
-        log::info!("Notarization disabled (skip_notarization = true)");
+        tracing::info!("Notarization disabled (skip_notarization = true)");
         return Ok(());
     }
 
-    log::info!("Notarizing {}", app_bundle.display());
+    tracing::info!("Notarizing {}", app_bundle.display());
 
     // If APPLE_API_KEY_CONTENT is set, write to file and use that path directly
     let auth = if let Some(key_path) = kodegen_bundler_sign::macos::ensure_api_key_file()
@@ -136,22 +260,40 @@ pub async fn notarize_app(app_bundle: &Path, settings: &Settings) -> Result<()>
     let wait = true;
 
     // Notarize (will also staple unless skip_stapling is set)
-    kodegen_bundler_sign::macos::notarize(app_bundle, &auth, wait)
-        .await
-        .map_err(|e| crate::bundler::Error::GenericError(format!("Notarization failed: {}", e)))?;
+    crate::bundler::utils::retry::retry("Notarization submission", is_transient_submission_error, || {
+        kodegen_bundler_sign::macos::notarize(app_bundle, &auth, wait)
+    })
+    .await
+    .map_err(|e| crate::bundler::Error::GenericError(format!("Notarization failed: {}", e)))?;
 
-    log::info!("✓ Successfully notarized {}", app_bundle.display());
+    tracing::info!("✓ Successfully notarized {}", app_bundle.display());
 
     Ok(())
 }
 
+/// Whether a notarization failure is a failed upload worth submitting again
+///
+/// Rejected credentials and a rejected app are permanent.
+fn is_transient_submission_error(error: &kodegen_bundler_sign::SetupError) -> bool {
+    const PERMANENT: [&str; 5] = ["UNAUTHORIZED", "401", "FORBIDDEN", "403", "invalidPEMDocument"];
+    match error {
+        kodegen_bundler_sign::SetupError::CommandExecution(message) => {
+            message.starts_with("Notarization submission failed")
+                && !PERMANENT.iter().any(|code| message.contains(code))
+        }
+        _ => false,
+    }
+}
+
 /// Check if an app should be notarized
 ///
 /// Returns true if:
+/// - neither notarization nor signing is skipped (`--skip-notarize`, `--skip-sign`)
 /// - skip_notarization is false
 /// - Notarization credentials are available in environment
 pub async fn should_notarize(settings: &Settings) -> bool {
-    !settings.bundle_settings().macos.skip_notarization
+    !settings.skip_phases().skips_notarization()
+        && !settings.bundle_settings().macos.skip_notarization
         && kodegen_bundler_sign::macos::NotarizationAuth::from_env()
             .await
             .is_ok()
@@ -184,16 +326,17 @@ pub async fn should_notarize(settings: &Settings) -> bool {
 /// # Ok(())
 /// # }
 /// ```
+#[tracing::instrument(name = "sign", skip_all, fields(path = %dmg_path.display()))]
 pub async fn sign_dmg(dmg_path: &Path, settings: &Settings) -> Result<()> {
     let identity = match &settings.bundle_settings().macos.signing_identity {
         Some(id) => id,
         None => {
-            log::info!("No signing identity configured, skipping DMG signing");
+            tracing::info!("No signing identity configured, skipping DMG signing");
             return Ok(());
         }
     };
 
-    log::info!(
+    tracing::info!(
         "Signing DMG {} with identity '{}'",
         dmg_path.display(),
         identity
@@ -210,7 +353,7 @@ pub async fn sign_dmg(dmg_path: &Path, settings: &Settings) -> Result<()> {
     .await
     .map_err(|e| crate::bundler::Error::GenericError(format!("DMG signing failed: {}", e)))?;
 
-    log::info!("✓ Successfully signed DMG: {}", dmg_path.display());
+    tracing::info!("✓ Successfully signed DMG: {}", dmg_path.display());
 
     Ok(())
 }
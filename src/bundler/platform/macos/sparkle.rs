@@ -0,0 +1,190 @@
+//! Sparkle appcast generation.
+//!
+//! Writes `appcast.xml` next to the final DMG with a single `<item>` for
+//! the bundled version. The enclosure is signed with Sparkle's EdDSA
+//! (ed25519) key over the exact DMG bytes, so the feed must be generated
+//! after the DMG is signed and will not change again.
+
+use crate::bundler::{
+    error::{ErrorExt, Result},
+    settings::{Settings, SparkleSettings},
+};
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use std::path::{Path, PathBuf};
+
+/// Default environment variable holding the base64 EdDSA private key
+pub const DEFAULT_PRIVATE_KEY_ENV: &str = "SPARKLE_PRIVATE_KEY";
+
+/// Writes `appcast.xml` for `dmg_path` into the DMG's directory.
+///
+/// Returns the path of the written feed.
+pub async fn write_appcast(
+    dmg_path: &Path,
+    sparkle: &SparkleSettings,
+    settings: &Settings,
+) -> Result<PathBuf> {
+    let key_env = sparkle
+        .private_key_env
+        .as_deref()
+        .unwrap_or(DEFAULT_PRIVATE_KEY_ENV);
+    let encoded_key = std::env::var(key_env).map_err(|_| {
+        crate::bundler::Error::GenericError(format!(
+            "Sparkle appcast requires an EdDSA private key in {} (export it with `generate_keys -x`)",
+            key_env
+        ))
+    })?;
+    let signing_key = parse_private_key(&encoded_key)?;
+
+    let dmg = tokio::fs::read(dmg_path)
+        .await
+        .fs_context("failed to read DMG for Sparkle signature", dmg_path)?;
+    let signature = base64::engine::general_purpose::STANDARD
+        .encode(signing_key.sign(&dmg).to_bytes());
+
+    let file_name = dmg_path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .ok_or_else(|| crate::bundler::Error::GenericError("Invalid DMG path".into()))?;
+    let version = settings.version_string();
+    let expand = |template: &str| {
+        template
+            .replace("{version}", version)
+            .replace("{file}", &file_name)
+    };
+
+    let item = AppcastItem {
+        version,
+        download_url: expand(&sparkle.download_url),
+        release_notes_url: sparkle.release_notes_url.as_deref().map(expand),
+        minimum_system_version: sparkle
+            .minimum_system_version
+            .as_deref()
+            .or(settings.bundle_settings().macos.minimum_system_version.as_deref()),
+        channel: sparkle.channel.as_deref(),
+        length: dmg.len() as u64,
+        signature: &signature,
+        pub_date: chrono::Utc::now().to_rfc2822(),
+    };
+
+    let appcast_path = dmg_path.with_file_name("appcast.xml");
+    tokio::fs::write(&appcast_path, render_appcast(settings.product_name(), &item))
+        .await
+        .fs_context("failed to write Sparkle appcast", &appcast_path)?;
+
+    tracing::info!("✓ Wrote Sparkle appcast: {}", appcast_path.display());
+    Ok(appcast_path)
+}
+
+/// Decodes a Sparkle private key.
+///
+/// `generate_keys -x` exports the 32-byte seed; keys exported by Sparkle
+/// 1.x contain the seed followed by the 32-byte public key.
+fn parse_private_key(encoded: &str) -> Result<SigningKey> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| {
+            crate::bundler::Error::GenericError(format!("Invalid Sparkle private key: {}", e))
+        })?;
+
+    let seed: [u8; 32] = match bytes.len() {
+        32 | 64 => bytes[..32].try_into().expect("slice is 32 bytes"),
+        len => {
+            return Err(crate::bundler::Error::GenericError(format!(
+                "Invalid Sparkle private key: expected 32 or 64 bytes, got {}",
+                len
+            )));
+        }
+    };
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+struct AppcastItem<'a> {
+    version: &'a str,
+    download_url: String,
+    release_notes_url: Option<String>,
+    minimum_system_version: Option<&'a str>,
+    channel: Option<&'a str>,
+    length: u64,
+    signature: &'a str,
+    pub_date: String,
+}
+
+fn render_appcast(title: &str, item: &AppcastItem<'_>) -> String {
+    let mut optional = String::new();
+    if let Some(url) = &item.release_notes_url {
+        optional.push_str(&format!(
+            "      <sparkle:releaseNotesLink>{}</sparkle:releaseNotesLink>\n",
+            escape_xml(url)
+        ));
+    }
+    if let Some(version) = item.minimum_system_version {
+        optional.push_str(&format!(
+            "      <sparkle:minimumSystemVersion>{}</sparkle:minimumSystemVersion>\n",
+            escape_xml(version)
+        ));
+    }
+    if let Some(channel) = item.channel {
+        optional.push_str(&format!(
+            "      <sparkle:channel>{}</sparkle:channel>\n",
+            escape_xml(channel)
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:sparkle="http://www.andymatuschak.org/xml-namespaces/sparkle">
+  <channel>
+    <title>{title}</title>
+    <item>
+      <title>{version}</title>
+      <pubDate>{pub_date}</pubDate>
+      <sparkle:version>{version}</sparkle:version>
+      <sparkle:shortVersionString>{version}</sparkle:shortVersionString>
+{optional}      <enclosure url="{url}" length="{length}" type="application/octet-stream" sparkle:edSignature="{signature}"/>
+    </item>
+  </channel>
+</rss>
+"#,
+        title = escape_xml(title),
+        version = escape_xml(item.version),
+        pub_date = item.pub_date,
+        optional = optional,
+        url = escape_xml(&item.download_url),
+        length = item.length,
+        signature = item.signature,
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_appcast() {
+        let item = AppcastItem {
+            version: "1.2.0",
+            download_url: "https://example.com/dl?v=1.2.0&f=App.dmg".into(),
+            release_notes_url: None,
+            minimum_system_version: Some("11.0"),
+            channel: None,
+            length: 42,
+            signature: "c2ln",
+            pub_date: "Thu, 15 Oct 2026 00:00:00 +0000".into(),
+        };
+        let xml = render_appcast("App", &item);
+        assert!(xml.contains("<sparkle:version>1.2.0</sparkle:version>"));
+        assert!(xml.contains("<sparkle:minimumSystemVersion>11.0</sparkle:minimumSystemVersion>"));
+        assert!(xml.contains(r#"url="https://example.com/dl?v=1.2.0&amp;f=App.dmg""#));
+        assert!(xml.contains(r#"length="42""#));
+        assert!(!xml.contains("releaseNotesLink"));
+    }
+}
@@ -84,7 +84,7 @@ pub fn create_universal_binaries(workspace_root: &Path, output_dir: &Path) -> Re
         )
     })?;
 
-    log::info!(
+    tracing::info!(
         "Creating universal binaries (x86_64 + aarch64) for {} binaries",
         ALL_BINARIES.len()
     );
@@ -98,7 +98,7 @@ pub fn create_universal_binaries(workspace_root: &Path, output_dir: &Path) -> Re
 
         // Verify both architecture binaries exist
         if !x86_64_bin.exists() {
-            log::warn!(
+            tracing::warn!(
                 "Skipping {}: x86_64 binary not found at {}",
                 binary_name,
                 x86_64_bin.display()
@@ -106,7 +106,7 @@ pub fn create_universal_binaries(workspace_root: &Path, output_dir: &Path) -> Re
             continue;
         }
         if !aarch64_bin.exists() {
-            log::warn!(
+            tracing::warn!(
                 "Skipping {}: aarch64 binary not found at {}",
                 binary_name,
                 aarch64_bin.display()
@@ -142,7 +142,7 @@ pub fn create_universal_binaries(workspace_root: &Path, output_dir: &Path) -> Re
             .output()?;
 
         let info = String::from_utf8_lossy(&verify.stdout);
-        log::info!("✓ {}: {}", binary_name, info.trim());
+        tracing::info!("✓ {}: {}", binary_name, info.trim());
 
         universal_binaries.push(universal_bin);
     }
@@ -151,7 +151,7 @@ pub fn create_universal_binaries(workspace_root: &Path, output_dir: &Path) -> Re
         anyhow::bail!("No universal binaries were created. Verify both architecture builds exist.");
     }
 
-    log::info!(
+    tracing::info!(
         "Successfully created {} universal binaries",
         universal_binaries.len()
     );
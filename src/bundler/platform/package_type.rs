@@ -0,0 +1,317 @@
+//! Canonical package type names, extensions and host requirements.
+//!
+//! Every mapping between a [`PackageType`] and a string (CLI `--platform`
+//! values, container arguments, artifact extensions, display names) lives
+//! here so the CLI, Docker path and artifact discovery cannot drift apart.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Supported package types for bundling.
+///
+/// Represents the different installer formats that can be created by the bundler.
+/// Each platform supports specific package types.
+///
+/// # Platform Support
+///
+/// - **Linux**: [`Deb`](Self::Deb), [`Rpm`](Self::Rpm), [`AppImage`](Self::AppImage)
+/// - **macOS**: [`MacOsBundle`](Self::MacOsBundle), [`Dmg`](Self::Dmg)
+/// - **Windows**: [`Exe`](Self::Exe), [`Portable`](Self::Portable)
+///
+/// [`Custom`](Self::Custom) formats are provided by a
+/// [`PlatformBundler`](crate::bundler::PlatformBundler) registered on the
+/// [`Bundler`](crate::bundler::Bundler).
+///
+/// # Examples
+///
+/// ```no_run
+/// use kodegen_bundler_release::bundler::PackageType;
+///
+/// // Get all package types for current platform
+/// let types = PackageType::all_for_current_platform();
+///
+/// for pkg_type in types {
+///     println!("Creating {} package", pkg_type);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PackageType {
+    /// macOS application bundle (.app).
+    ///
+    /// Creates a `.app` bundle for macOS with proper Info.plist and structure.
+    MacOsBundle,
+
+    /// macOS DMG disk image (.dmg).
+    ///
+    /// Creates a distributable disk image containing the .app bundle.
+    /// Requires [`MacOsBundle`](Self::MacOsBundle) to be built first.
+    Dmg,
+
+    /// Windows executable installer (.exe).
+    ///
+    /// Creates a Windows installer using NSIS.
+    Exe,
+
+    /// Windows portable archive (.zip).
+    ///
+    /// Contains the executables and a marker file telling the application
+    /// to keep its settings next to the binary.
+    Portable,
+
+    /// Debian package (.deb).
+    ///
+    /// Creates a Debian package for Ubuntu, Debian, and derivatives.
+    Deb,
+
+    /// RPM package (.rpm).
+    ///
+    /// Creates an RPM package for Fedora, RHEL, CentOS, and derivatives.
+    Rpm,
+
+    /// Linux AppImage (.AppImage).
+    ///
+    /// Creates a portable, self-contained executable for Linux.
+    AppImage,
+
+    /// Format provided by a registered [`PlatformBundler`](crate::bundler::PlatformBundler).
+    ///
+    /// Holds the bundler's [`name`](crate::bundler::PlatformBundler::name).
+    /// Built natively after the built-in types, so it can package their
+    /// artifacts.
+    Custom(&'static str),
+}
+
+impl PackageType {
+    /// Every built-in package type, in bundling priority order.
+    pub const ALL: [PackageType; 7] = [
+        PackageType::Deb,
+        PackageType::Rpm,
+        PackageType::AppImage,
+        PackageType::MacOsBundle,
+        PackageType::Exe,
+        PackageType::Portable,
+        PackageType::Dmg,
+    ];
+
+    /// Returns alternative names accepted when parsing, besides
+    /// [`short_name`](Self::short_name).
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            PackageType::MacOsBundle => &["macos-bundle"],
+            PackageType::Exe => &["nsis"],
+            _ => &[],
+        }
+    }
+
+    /// Returns whether a file extension (without the dot) belongs to this
+    /// package type's artifact.
+    ///
+    /// Case-insensitive. AppImages are also accepted without an extension,
+    /// since they are plain executables.
+    pub fn matches_extension(&self, extension: Option<&str>) -> bool {
+        match extension {
+            Some(ext) => ext.eq_ignore_ascii_case(self.extension()),
+            None => *self == PackageType::AppImage,
+        }
+    }
+
+    /// Returns the short name for this package type.
+    ///
+    /// This is the lowercase identifier used in CLI output and file paths.
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            PackageType::MacOsBundle => "app",
+            PackageType::Dmg => "dmg",
+            PackageType::Exe => "exe",
+            PackageType::Portable => "portable",
+            PackageType::Deb => "deb",
+            PackageType::Rpm => "rpm",
+            PackageType::AppImage => "appimage",
+            PackageType::Custom(name) => name,
+        }
+    }
+
+    /// Returns the file extension of the produced artifact, without the dot.
+    ///
+    /// Matches the case used on disk, e.g. `AppImage` rather than `appimage`.
+    /// Custom formats use their name.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PackageType::MacOsBundle => "app",
+            PackageType::Dmg => "dmg",
+            PackageType::Exe => "exe",
+            PackageType::Portable => "zip",
+            PackageType::Deb => "deb",
+            PackageType::Rpm => "rpm",
+            PackageType::AppImage => "AppImage",
+            PackageType::Custom(name) => name,
+        }
+    }
+
+    /// Returns the host OS (as in [`std::env::consts::OS`]) whose native
+    /// tooling builds this package type.
+    ///
+    /// Other hosts need a container to build it. Custom formats are always
+    /// built on the current host.
+    pub fn required_host_os(&self) -> &'static str {
+        match self {
+            PackageType::Deb | PackageType::Rpm | PackageType::AppImage => "linux",
+            PackageType::Dmg | PackageType::MacOsBundle => "macos",
+            PackageType::Exe | PackageType::Portable => "windows",
+            PackageType::Custom(_) => std::env::consts::OS,
+        }
+    }
+
+    /// Returns a human-readable name for this package type.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PackageType::Deb => "Debian Package (.deb)",
+            PackageType::Rpm => "RedHat Package (.rpm)",
+            PackageType::AppImage => "Linux AppImage",
+            PackageType::Dmg => "macOS Disk Image (.dmg)",
+            PackageType::MacOsBundle => "macOS Application Bundle (.app)",
+            PackageType::Exe => "Windows NSIS Installer (.exe)",
+            PackageType::Portable => "Windows Portable ZIP (.zip)",
+            PackageType::Custom(name) => name,
+        }
+    }
+
+    /// Returns the priority for bundling order.
+    ///
+    /// Lower numbers are bundled first. This ensures dependencies are built
+    /// before dependent types (e.g., .app before .dmg).
+    ///
+    /// # Priority Values
+    ///
+    /// - `0`: Independent packages (deb, rpm, nsis, portable, app, appimage)
+    /// - `1`: Dependent packages (dmg - requires .app)
+    /// - `2`: Custom packages
+    pub fn priority(&self) -> u32 {
+        match self {
+            PackageType::MacOsBundle => 0,
+            PackageType::Exe => 0,
+            PackageType::Portable => 0,
+            PackageType::Deb => 0,
+            PackageType::Rpm => 0,
+            PackageType::AppImage => 0,
+            PackageType::Dmg => 1, // Requires .app to be built first
+            PackageType::Custom(_) => 2,
+        }
+    }
+
+    /// Returns all package types available on the current platform.
+    ///
+    /// Automatically detects the operating system and returns appropriate
+    /// package types.
+    ///
+    /// # Returns
+    ///
+    /// - **Linux**: `[Deb, Rpm, AppImage]`
+    /// - **macOS**: `[MacOsBundle, Dmg]`
+    /// - **Windows**: `[Nsis]`
+    /// - **Other**: `[]` (empty)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use kodegen_bundler_release::bundler::PackageType;
+    ///
+    /// let types = PackageType::all_for_current_platform();
+    /// println!("Available package types: {:?}", types);
+    /// ```
+    pub fn all_for_current_platform() -> Vec<PackageType> {
+        #[cfg(target_os = "linux")]
+        {
+            vec![PackageType::Deb, PackageType::Rpm, PackageType::AppImage]
+        }
+        #[cfg(target_os = "macos")]
+        {
+            vec![PackageType::MacOsBundle, PackageType::Dmg]
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        {
+            vec![]
+        }
+    }
+}
+
+impl fmt::Display for PackageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.short_name())
+    }
+}
+
+/// Serializes as the [`short_name`](PackageType::short_name).
+impl serde::Serialize for PackageType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.short_name())
+    }
+}
+
+/// Comma-separated list of all accepted package type names, for messages.
+pub fn valid_names() -> String {
+    PackageType::ALL
+        .iter()
+        .flat_map(|t| std::iter::once(t.short_name()).chain(t.aliases().iter().copied()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Error returned when parsing an unknown package type name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unsupported platform '{0}'. Valid: {valid}", valid = valid_names())]
+pub struct ParsePackageTypeError(pub String);
+
+impl FromStr for PackageType {
+    type Err = ParsePackageTypeError;
+
+    /// Parses a package type name case-insensitively.
+    ///
+    /// Accepts the [`short_name`](Self::short_name) and
+    /// [`aliases`](Self::aliases) of every variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        PackageType::ALL
+            .into_iter()
+            .find(|t| t.short_name() == name || t.aliases().contains(&name.as_str()))
+            .ok_or_else(|| ParsePackageTypeError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for package_type in PackageType::ALL {
+            assert_eq!(package_type.short_name().parse::<PackageType>(), Ok(package_type));
+            assert_eq!(package_type.to_string().parse::<PackageType>(), Ok(package_type));
+            assert_eq!(
+                package_type.short_name().to_uppercase().parse::<PackageType>(),
+                Ok(package_type)
+            );
+            for alias in package_type.aliases() {
+                assert_eq!(alias.parse::<PackageType>(), Ok(package_type));
+            }
+        }
+        assert!("msi".parse::<PackageType>().is_err());
+    }
+
+    #[test]
+    fn test_extensions_round_trip() {
+        for package_type in PackageType::ALL {
+            assert!(package_type.matches_extension(Some(package_type.extension())));
+            assert!(package_type.matches_extension(Some(&package_type.extension().to_lowercase())));
+            // Extensions identify exactly one package type
+            let owners = PackageType::ALL
+                .iter()
+                .filter(|t| t.matches_extension(Some(package_type.extension())))
+                .count();
+            assert_eq!(owners, 1);
+        }
+        assert!(PackageType::AppImage.matches_extension(None));
+        assert!(!PackageType::Deb.matches_extension(None));
+    }
+}
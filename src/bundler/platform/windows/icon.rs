@@ -3,12 +3,36 @@
 //! Converts PNG source images to ICO format with multiple sizes
 //! for different Windows contexts (taskbar, alt-tab, etc.).
 
-#![cfg(windows)]
-
 use crate::bundler::error::{ErrorExt, Result};
-use crate::bundler::resources::icons::{IconInfo, find_icon_for_size, load_and_resize};
+use crate::bundler::resources::icons::{
+    ICO_SIZES, IconInfo, find_icon_for_size, load_and_resize, missing_sizes,
+};
+use crate::bundler::settings::Settings;
 use ico::{IconDir, IconDirEntry, IconImage, ResourceType};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// File name of the generated icon in the package's output directory
+const GENERATED_ICON: &str = "icon.ico";
+
+/// Returns the application icon for the executables and the installer.
+///
+/// The configured `ico` (or NSIS `installer_icon`) wins. Otherwise the icon
+/// is generated from the PNG icons into `output_dir`, so projects only
+/// maintain PNG sources. `None` when there are no icons at all.
+pub async fn application_icon(settings: &Settings, output_dir: &Path) -> Result<Option<PathBuf>> {
+    let bundle = settings.bundle_settings();
+    if let Some(icon) = bundle.ico.as_ref().or(bundle.windows.nsis.installer_icon.as_ref()) {
+        return Ok(Some(icon.clone()));
+    }
+    if bundle.icon.is_none() {
+        return Ok(None);
+    }
+
+    let icons = settings.icon_files()?;
+    let output = output_dir.join(GENERATED_ICON);
+    create_ico_file(&icons, &output).await?;
+    Ok(Some(output))
+}
 
 /// Create ICO file from source icons
 ///
@@ -20,20 +44,28 @@ use std::path::Path;
 /// - 64x64, 128x128: Extra large icons
 /// - 256x256: Windows Vista+ high-res icons
 ///
+/// Sizes without an exact PNG are scaled from the nearest one.
+///
 /// # Example
-/// ```rust
+/// ```rust,ignore
 /// let icons = load_icons(&icon_paths)?;
-/// create_ico_file(&icons, Path::new("app.ico"))?;
+/// create_ico_file(&icons, Path::new("app.ico")).await?;
 /// ```
 pub async fn create_ico_file(icons: &[IconInfo], output: &Path) -> Result<()> {
     let mut icon_dir = IconDir::new(ResourceType::Icon);
 
-    // Windows standard icon sizes
-    let sizes = [16, 24, 32, 48, 64, 128, 256];
+    let missing = missing_sizes(icons, &ICO_SIZES);
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|size| format!("{}x{}", size, size)).collect();
+        tracing::warn!(
+            "No PNG icon for {} in the generated .ico; scaling the nearest size instead",
+            missing.join(", ")
+        );
+    }
 
-    for size in sizes {
+    for size in ICO_SIZES {
         if let Some(icon_info) = find_icon_for_size(icons, size) {
-            log::debug!("Adding {}x{} from {}", size, size, icon_info.path.display());
+            tracing::debug!("Adding {}x{} from {}", size, size, icon_info.path.display());
 
             // Load and resize to exact dimensions
             let rgba = load_and_resize(&icon_info.path, size, size)?;
@@ -50,7 +82,7 @@ pub async fn create_ico_file(icons: &[IconInfo], output: &Path) -> Result<()> {
             })?;
             icon_dir.add_entry(entry);
         } else {
-            log::warn!("No suitable source icon for {}x{}", size, size);
+            tracing::warn!("No suitable source icon for {}x{}", size, size);
         }
     }
 
@@ -63,6 +95,39 @@ pub async fn create_ico_file(icons: &[IconInfo], output: &Path) -> Result<()> {
         .write(file)
         .map_err(|e| crate::bundler::Error::GenericError(format!("writing ICO data: {}", e)))?;
 
-    log::info!("Created ICO file: {}", output.display());
+    tracing::info!("Created ICO file: {}", output.display());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::{BundleBinary, BundleSettings, PackageSettings, SettingsBuilder};
+
+    #[tokio::test]
+    async fn test_application_icon_generated() {
+        let dir = tempfile::tempdir().unwrap();
+        let png = dir.path().join("icon_32x32.png");
+        image::RgbaImage::new(32, 32).save(&png).unwrap();
+
+        let settings = SettingsBuilder::new()
+            .project_out_directory(dir.path())
+            .package_settings(PackageSettings {
+                product_name: "app".into(),
+                version: "1.0.0".into(),
+                ..Default::default()
+            })
+            .bundle_settings(BundleSettings {
+                icon: Some(vec![png]),
+                ..Default::default()
+            })
+            .binaries(vec![BundleBinary::new("app".into(), true)])
+            .build()
+            .unwrap();
+
+        let icon = application_icon(&settings, dir.path()).await.unwrap().unwrap();
+        let ico = IconDir::read(std::fs::File::open(&icon).unwrap()).unwrap();
+        let sizes: Vec<u32> = ico.entries().iter().map(|entry| entry.width()).collect();
+        assert_eq!(sizes, ICO_SIZES);
+    }
+}
@@ -1,4 +1,4 @@
-//! Windows bundling support for NSIS installers.
+//! Windows bundling support for NSIS installers and portable archives.
 //!
 //! This module provides bundling implementations for Windows installer creation
 //! using NSIS (Nullsoft Scriptable Install System).
@@ -6,6 +6,7 @@
 //! # Supported Formats
 //!
 //! - **NSIS Installer (.exe)**: via [`nsis`] module using NSIS
+//! - **Portable ZIP (.zip)**: via [`portable`] module, no installer required
 //!
 //! # Build Requirements
 //!
@@ -18,6 +19,7 @@
 //!
 //! Bundles are created in `target/release/bundle/`:
 //! - `bundle/nsis/MyApp_1.0.0_x64-setup.exe` - NSIS installer
+//! - `bundle/portable/MyApp-1.0.0-x64-portable.zip` - Portable ZIP
 //!
 //! # Code Signing
 //!
@@ -25,9 +27,16 @@
 //! For comprehensive signing setup, see the
 //! [`kodegen_sign`](../../../../sign/index.html) crate.
 //!
+//! # Executable Resources
+//!
+//! The [`resources`] module embeds a `VERSIONINFO` resource and the application
+//! icon into the built `.exe` files before they are packaged.
+//!
 //! # Icon Conversion
 //!
-//! The [`icon`] module (Windows-only) handles PNG to ICO conversion for Windows icons.
+//! The [`icon`] module generates a multi-resolution `.ico` from the PNG icons
+//! when no `icon.ico` is configured. It is used for both the installer icon
+//! and the icon embedded into the executables.
 //!
 //! # Installer Customization
 //!
@@ -39,8 +48,14 @@
 //! compression = "lzma"
 //! ```
 
+#[cfg(target_os = "linux")]
+pub mod icon;
 #[cfg(target_os = "linux")]
 pub mod nsis;
 #[cfg(target_os = "linux")]
+pub mod portable;
+#[cfg(target_os = "linux")]
+pub mod resources;
+#[cfg(target_os = "linux")]
 pub mod sign;
 pub mod util;
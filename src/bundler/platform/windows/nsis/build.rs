@@ -3,7 +3,7 @@
 //! Compiles NSI scripts into Windows installer executables using makensis.
 
 use crate::bundler::error::{Error, ErrorExt, Result};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 /// Run makensis to compile NSI script into installer executable.
 ///
@@ -18,10 +18,10 @@ use std::path::{Path, PathBuf};
 /// # Platform-specific behavior
 /// - Windows: Uses `makensis.exe` from the NSIS installation
 /// - Unix: Uses system `makensis` command
-pub async fn run_makensis(_nsis_path: &Path, nsi_path: &Path, output_path: &Path) -> Result<()> {
-    log::info!("Running makensis...");
+pub async fn run_makensis(nsis_path: &Path, nsi_path: &Path, output_path: &Path) -> Result<()> {
+    tracing::info!("Running makensis...");
 
-    let makensis = PathBuf::from("makensis");
+    let makensis = nsis_path.join("makensis");
 
     // Ensure output directory exists
     if let Some(parent) = output_path.parent() {
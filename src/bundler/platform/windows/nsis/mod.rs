@@ -15,11 +15,13 @@ mod build;
 mod script;
 mod template;
 mod toolset;
-mod utils;
+pub(super) mod utils;
 
-use super::sign;
+use super::{icon, resources, sign};
 use crate::bundler::{
-    error::{Context, ErrorExt, Result},
+    BundleStage, PackageType,
+    error::{Context, Error, ErrorExt, Result},
+    hooks::{self, Hook},
     settings::Settings,
 };
 use std::path::PathBuf;
@@ -42,7 +44,14 @@ use std::path::PathBuf;
 ///
 /// Vector containing the path to the generated installer .exe file
 pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
-    log::info!("Building NSIS installer for {}", settings.product_name());
+    tracing::info!("Building NSIS installer for {}", settings.product_name());
+    let progress = settings.progress();
+    progress.report(
+        PackageType::Exe,
+        BundleStage::Preparing,
+        Some(5),
+        "Locating makensis",
+    );
 
     // Get NSIS toolset
     let nsis_path = toolset::get_nsis_toolset().await?;
@@ -59,8 +68,32 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         .await
         .fs_context("creating NSIS output directory", &output_dir)?;
 
+    // Patch version info and icon into the binaries before they are packaged
+    progress.report(
+        PackageType::Exe,
+        BundleStage::Building,
+        Some(15),
+        "Embedding resources and generating installer script",
+    );
+    let app_icon = icon::application_icon(settings, &output_dir).await?;
+    let resource_settings = settings.clone();
+    let resource_icon = app_icon.clone();
+    tokio::task::spawn_blocking(move || {
+        resources::embed_resources(&resource_settings, resource_icon.as_deref())
+    })
+    .await
+    .map_err(|e| Error::GenericError(format!("Resource embedding task failed: {}", e)))??;
+
     // Generate NSI script
-    let nsi_path = script::generate_nsi_script(settings, arch, &output_dir).await?;
+    let installer_icon = settings
+        .bundle_settings()
+        .windows
+        .nsis
+        .installer_icon
+        .clone()
+        .or(app_icon);
+    let nsi_path =
+        script::generate_nsi_script(settings, arch, &output_dir, installer_icon.as_deref()).await?;
 
     // Create installer name
     let installer_name = format!(
@@ -75,16 +108,37 @@ pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
         .join(&installer_name);
 
     // Run makensis
+    progress.report(
+        PackageType::Exe,
+        BundleStage::Compressing,
+        Some(30),
+        "Compressing installer with makensis",
+    );
     build::run_makensis(&nsis_path, &nsi_path, &installer_path).await?;
 
     // Sign the installer if configured
     if sign::should_sign(settings) {
+        progress.report_path(
+            PackageType::Exe,
+            BundleStage::Signing,
+            Some(90),
+            "Signing installer",
+            &installer_path,
+        );
         sign::sign_file(&installer_path, settings)
             .await
             .context("signing NSIS installer")?;
+        hooks::run_for_package(
+            settings,
+            Hook::PostSign,
+            PackageType::Exe,
+            std::slice::from_ref(&installer_path),
+            &[],
+        )
+        .await?;
     }
 
-    log::info!("✓ Created NSIS installer: {}", installer_path.display());
+    tracing::info!("✓ Created NSIS installer: {}", installer_path.display());
 
     Ok(vec![installer_path])
 }
@@ -5,11 +5,15 @@
 
 use super::{template::NSI_TEMPLATE, utils};
 use crate::bundler::{
-    error::{Error, Result},
-    settings::Settings,
+    error::{Error, ErrorExt, Result},
+    resources::{
+        files::{self, ResourceFile},
+        license, locales,
+    },
+    settings::{BundleTypeRole, HandlerRank, NSISInstallerMode, Settings},
 };
 use handlebars::Handlebars;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Generate NSI installer script from template.
 ///
@@ -20,6 +24,7 @@ use std::path::Path;
 /// - `settings` - Bundler settings containing product metadata and paths
 /// - `arch` - Target architecture string (e.g., "x64", "x86", "arm64")
 /// - `output_dir` - Directory to write the generated .nsi file
+/// - `installer_icon` - Icon of the installer and uninstaller, if any
 ///
 /// # Returns
 /// Path to the generated installer.nsi file
@@ -27,7 +32,8 @@ pub async fn generate_nsi_script(
     settings: &Settings,
     arch: &str,
     output_dir: &Path,
-) -> Result<std::path::PathBuf> {
+    installer_icon: Option<&Path>,
+) -> Result<PathBuf> {
     let mut handlebars = Handlebars::new();
     handlebars.register_escape_fn(handlebars::no_escape);
 
@@ -38,12 +44,18 @@ pub async fn generate_nsi_script(
         return Err(Error::GenericError("No binaries found to bundle".into()));
     }
 
-    // Collect all binary paths for template (with .exe extension for Windows)
+    // Collect all binary paths for template (with .exe extension for Windows).
+    // Sidecars are installed without their target suffix.
     let binary_files: Vec<_> = binaries
         .iter()
         .map(|b| {
             let path = settings.binary_path(b);
-            path.with_extension("exe").display().to_string()
+            let file_name = format!("{}.exe", b.name());
+            serde_json::json!({
+                "source": path.with_extension("exe").display().to_string(),
+                "install_path": format!("$INSTDIR\\{}", file_name),
+                "file_name": file_name,
+            })
         })
         .collect();
 
@@ -64,16 +76,23 @@ pub async fn generate_nsi_script(
         .as_deref()
         .unwrap_or("Unknown Publisher");
 
-    // Install directory based on install mode
+    // Install directory based on install mode. x64 and ARM64 binaries go to
+    // the native Program Files, x86 binaries to Program Files (x86).
+    let program_files = if arch == "x86" {
+        "$PROGRAMFILES"
+    } else {
+        "$PROGRAMFILES64"
+    };
     let install_dir = match settings.bundle_settings().windows.nsis.install_mode {
         crate::bundler::settings::NSISInstallerMode::PerMachine => {
-            format!("$PROGRAMFILES64\\{}", settings.product_name())
+            format!("{}\\{}", program_files, settings.product_name())
         }
         crate::bundler::settings::NSISInstallerMode::CurrentUser => {
             format!("$LOCALAPPDATA\\{}", settings.product_name())
         }
+        // Fallback only; MultiUser sets $INSTDIR for the chosen scope
         crate::bundler::settings::NSISInstallerMode::Both => {
-            format!("$PROGRAMFILES64\\{}", settings.product_name())
+            format!("{}\\{}", program_files, settings.product_name())
         }
     };
 
@@ -103,10 +122,60 @@ pub async fn generate_nsi_script(
         data["sidebar_image"] = serde_json::json!(sidebar.display().to_string());
     }
 
-    if let Some(icon) = &nsis_settings.installer_icon {
+    if let Some(icon) = installer_icon {
         data["installer_icon"] = serde_json::json!(icon.display().to_string());
     }
 
+    // Installer languages; more than one adds a language selection dialog
+    let mut languages = Vec::new();
+    for language in nsis_settings.languages.iter().flatten() {
+        let name = utils::map_language(language)?;
+        if !languages.contains(&name) {
+            languages.push(name);
+        }
+    }
+    if languages.is_empty() {
+        languages.push("English");
+    }
+    data["language_selection"] = serde_json::json!(languages.len() > 1);
+    data["languages"] = serde_json::json!(languages);
+
+    // File associations and URL protocol handlers (registry ProgIDs)
+    let associations = file_associations(settings);
+    let schemes = url_schemes(settings);
+    data["has_associations"] = serde_json::json!(!associations.is_empty() || !schemes.is_empty());
+    data["file_associations"] = serde_json::json!(associations);
+    data["url_schemes"] = serde_json::json!(schemes);
+
+    // Resources keep their relative paths below $INSTDIR, translations go to
+    // locales\ and license texts next to the executable
+    let mut resource_files = files::resource_files(settings)?;
+    resource_files.extend(license::license_files(settings)?);
+    resource_files.extend(
+        locales::localizations(settings)?.catalogs_in(Path::new(locales::WINDOWS_LOCALE_DIR)),
+    );
+    let (resources, resource_dirs) = resource_entries(&resource_files);
+    data["resources"] = serde_json::json!(resources);
+    data["resource_dirs"] = serde_json::json!(resource_dirs);
+
+    data["multi_user"] = serde_json::json!(nsis_settings.install_mode == NSISInstallerMode::Both);
+    data["add_to_path"] = serde_json::json!(nsis_settings.add_to_path);
+
+    if let Some(license) = &nsis_settings.license {
+        let license = license_file(license, output_dir).await?;
+        data["license"] = serde_json::json!(license.display().to_string());
+    }
+
+    // Custom fragments and plugins
+    let display_paths = |paths: &[PathBuf]| -> Vec<String> {
+        paths.iter().map(|p| p.display().to_string()).collect()
+    };
+    data["plugin_dirs"] = serde_json::json!(display_paths(&nsis_settings.plugins));
+    data["custom_includes"] = serde_json::json!(display_paths(&nsis_settings.custom_includes));
+    if let Some(section) = &nsis_settings.preinstall_section {
+        data["preinstall_section"] = serde_json::json!(section.display().to_string());
+    }
+
     // Render template
     handlebars
         .register_template_string("installer.nsi", NSI_TEMPLATE)
@@ -122,3 +191,324 @@ pub async fn generate_nsi_script(
 
     Ok(nsi_path)
 }
+
+/// Template entries for the resources and the directories they create,
+/// deepest first so the uninstaller can remove them once empty
+fn resource_entries(resources: &[ResourceFile]) -> (Vec<serde_json::Value>, Vec<String>) {
+    let install_path = |path: &Path| {
+        let mut install_path = "$INSTDIR".to_string();
+        for component in path.iter() {
+            install_path.push('\\');
+            install_path.push_str(&component.to_string_lossy());
+        }
+        install_path
+    };
+    let mut dirs = std::collections::BTreeSet::new();
+    let entries = resources
+        .iter()
+        .map(|file| {
+            let dir = file.target.parent().unwrap_or(Path::new(""));
+            for ancestor in dir.ancestors().filter(|a| !a.as_os_str().is_empty()) {
+                dirs.insert(install_path(ancestor));
+            }
+            serde_json::json!({
+                "source": file.source.display().to_string(),
+                "out_dir": install_path(dir),
+                "install_path": install_path(&file.target),
+            })
+        })
+        .collect();
+    let mut dirs: Vec<String> = dirs.into_iter().collect();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.matches('\\').count()));
+    (entries, dirs)
+}
+
+/// Returns the RTF file for the license page, converting plain text into
+/// `output_dir/license.rtf`.
+async fn license_file(license: &Path, output_dir: &Path) -> Result<PathBuf> {
+    if license
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("rtf"))
+    {
+        return Ok(license.to_path_buf());
+    }
+
+    let text = tokio::fs::read_to_string(license)
+        .await
+        .fs_context("reading license file", license)?;
+    let rtf_path = output_dir.join("license.rtf");
+    tokio::fs::write(&rtf_path, utils::text_to_rtf(&text))
+        .await
+        .fs_context("writing license RTF", &rtf_path)?;
+    Ok(rtf_path)
+}
+
+/// Template data for the registry entries of each associated extension.
+///
+/// Associations with role `None` only declare a type and are skipped. The
+/// extension's default handler is only claimed for ranks `Default` and
+/// `Owner`; the ProgID is always added to `OpenWithProgids`.
+fn file_associations(settings: &Settings) -> Vec<serde_json::Value> {
+    let product = &settings.product_name().replace(' ', "");
+    settings
+        .bundle_settings()
+        .file_associations
+        .iter()
+        .filter(|association| association.role != BundleTypeRole::None)
+        .flat_map(|association| {
+            let icon = association
+                .icon
+                .as_ref()
+                .filter(|icon| icon.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ico")));
+            association.extensions().map(move |ext| {
+                let prog_id = format!("{}.{}", product, ext);
+                // Handlebars treats `\{{` as an escape, so keys are built here
+                let mut entry = serde_json::json!({
+                    "ext": ext,
+                    "prog_id": prog_id,
+                    "prog_id_key": format!("Software\\Classes\\{}", prog_id),
+                    "name": association.display_name(),
+                    "claim_default": matches!(association.rank, HandlerRank::Default | HandlerRank::Owner),
+                });
+                if let Some(mime_type) = &association.mime_type {
+                    entry["mime_type"] = serde_json::json!(mime_type);
+                }
+                if let Some(icon) = icon {
+                    entry["icon"] = serde_json::json!(icon.display().to_string());
+                    entry["icon_file"] = serde_json::json!(format!("{}.ico", prog_id));
+                    entry["icon_path"] = serde_json::json!(format!("$INSTDIR\\{}.ico", prog_id));
+                }
+                entry
+            })
+        })
+        .collect()
+}
+
+/// Template data for each URL protocol handler.
+fn url_schemes(settings: &Settings) -> Vec<serde_json::Value> {
+    settings
+        .bundle_settings()
+        .url_schemes
+        .iter()
+        .filter(|scheme| scheme.role != BundleTypeRole::None)
+        .flat_map(|scheme| {
+            scheme.schemes.iter().map(move |name| {
+                serde_json::json!({
+                    "key": format!("Software\\Classes\\{}", name),
+                    "name": scheme.name.as_deref().unwrap_or(name),
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::settings::{
+        BundleBinary, BundleSettings, FileAssociation, PackageSettings, SettingsBuilder, UrlScheme,
+    };
+
+    /// Renders the x64 installer script for `bundle` and returns its contents
+    async fn render(bundle: BundleSettings) -> String {
+        render_for(bundle, "x64").await
+    }
+
+    async fn render_for(bundle: BundleSettings, arch: &str) -> String {
+        let settings = SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "kodegen".into(),
+                version: "1.2.3".into(),
+                ..Default::default()
+            })
+            .bundle_settings(bundle)
+            .binaries(vec![BundleBinary::new("kodegen".into(), true)])
+            .build()
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = generate_nsi_script(&settings, arch, dir.path(), None)
+            .await
+            .unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        String::from_utf8(bytes[3..].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_resource_entries() {
+        let resources = [
+            ResourceFile {
+                source: PathBuf::from("/src/app/config/themes/dark.toml"),
+                target: PathBuf::from("config/themes/dark.toml"),
+            },
+            ResourceFile {
+                source: PathBuf::from("/src/app/README.md"),
+                target: PathBuf::from("README.md"),
+            },
+        ];
+        let (entries, dirs) = resource_entries(&resources);
+        assert_eq!(entries[0]["out_dir"], "$INSTDIR\\config\\themes");
+        assert_eq!(entries[0]["install_path"], "$INSTDIR\\config\\themes\\dark.toml");
+        assert_eq!(entries[1]["out_dir"], "$INSTDIR");
+        assert_eq!(dirs, ["$INSTDIR\\config\\themes", "$INSTDIR\\config"]);
+    }
+
+    #[tokio::test]
+    async fn test_custom_fragments() {
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.plugins = vec!["installer/plugins".into()];
+        bundle.windows.nsis.custom_includes = vec!["installer/hooks.nsh".into()];
+        bundle.windows.nsis.preinstall_section = Some("installer/preinstall.nsh".into());
+
+        let script = render(bundle).await;
+        assert!(script.contains("!addplugindir \"installer/plugins\"\n"));
+        assert!(script.contains("!include \"installer/hooks.nsh\"\n"));
+        assert!(script.contains(
+            "Section \"-PreInstall\"\n  !include \"installer/preinstall.nsh\"\nSectionEnd"
+        ));
+        assert!(script.find("-PreInstall").unwrap() < script.find("\"MainSection\"").unwrap());
+
+        let script = render(BundleSettings::default()).await;
+        assert!(!script.contains("!addplugindir"));
+        assert!(!script.contains("-PreInstall"));
+    }
+
+    #[tokio::test]
+    async fn test_languages() {
+        let script = render(BundleSettings::default()).await;
+        assert!(script.contains("!insertmacro MUI_LANGUAGE \"English\"\n"));
+        assert!(!script.contains("MUI_LANGDLL_DISPLAY"));
+
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.languages = Some(vec!["en-US".into(), "de-DE".into(), "en-GB".into()]);
+        let script = render(bundle).await;
+        assert!(script.contains(
+            "!insertmacro MUI_LANGUAGE \"English\"\n!insertmacro MUI_LANGUAGE \"German\"\n"
+        ));
+        assert_eq!(script.matches("MUI_LANGUAGE \"English\"").count(), 1);
+        assert!(script.contains("!insertmacro MUI_LANGDLL_DISPLAY"));
+    }
+
+    #[tokio::test]
+    async fn test_file_associations() {
+        let bundle = BundleSettings {
+            file_associations: vec![
+                FileAssociation {
+                    ext: vec!["kdg".into()],
+                    name: Some("Kodegen Project".into()),
+                    mime_type: Some("application/x-kodegen-project".into()),
+                    ..Default::default()
+                },
+                FileAssociation {
+                    ext: vec!["json".into()],
+                    rank: HandlerRank::Alternate,
+                    ..Default::default()
+                },
+            ],
+            url_schemes: vec![UrlScheme {
+                schemes: vec!["kodegen".into()],
+                name: Some("Kodegen Link".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let script = render(bundle).await;
+        assert!(script.contains(
+            "WriteRegStr ${CLASSES_ROOT} \"Software\\Classes\\.kdg\" \"\" \"kodegen.kdg\"\n"
+        ));
+        assert!(script.contains(
+            "WriteRegStr ${CLASSES_ROOT} \"Software\\Classes\\.kdg\" \"Content Type\" \"application/x-kodegen-project\"\n"
+        ));
+        assert!(script.contains(
+            "WriteRegStr ${CLASSES_ROOT} \"Software\\Classes\\kodegen.kdg\" \"\" \"Kodegen Project\"\n"
+        ));
+        // Alternate handlers are only offered in "Open with"
+        assert!(!script.contains("\"Software\\Classes\\.json\" \"\" \"kodegen.json\""));
+        assert!(script.contains(
+            "WriteRegStr ${CLASSES_ROOT} \"Software\\Classes\\.json\\OpenWithProgids\" \"kodegen.json\" \"\"\n"
+        ));
+        assert!(script.contains(
+            "WriteRegStr ${CLASSES_ROOT} \"Software\\Classes\\kodegen\" \"URL Protocol\" \"\"\n"
+        ));
+        assert!(script.contains("DeleteRegKey ${CLASSES_ROOT} \"Software\\Classes\\kodegen\"\n"));
+        assert!(
+            script.contains("DeleteRegKey ${CLASSES_ROOT} \"Software\\Classes\\kodegen.kdg\"\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_to_path() {
+        let script = render(BundleSettings::default()).await;
+        assert!(!script.contains("MUI_PAGE_COMPONENTS"));
+        assert!(!script.contains("AddedToPath"));
+
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.add_to_path = true;
+        let script = render(bundle).await;
+        assert!(script.contains("!insertmacro MUI_PAGE_COMPONENTS\n"));
+        assert!(script.contains("Section \"Add to PATH\" SEC_PATH\n"));
+        assert!(script.contains("${WordAdd} \"$0\" \";\" \"+$INSTDIR\" $0\n"));
+        assert!(script.contains("${WordAdd} \"$0\" \";\" \"-$INSTDIR\" $0\n"));
+    }
+
+    #[tokio::test]
+    async fn test_license_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let license = dir.path().join("LICENSE");
+        std::fs::write(&license, "MIT License\n").unwrap();
+
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.license = Some(license);
+        let script = render(bundle).await;
+        let page = script
+            .lines()
+            .find_map(|line| line.strip_prefix("!insertmacro MUI_PAGE_LICENSE "))
+            .unwrap();
+        assert!(page.ends_with("license.rtf\""));
+
+        let script = render(BundleSettings::default()).await;
+        assert!(!script.contains("MUI_PAGE_LICENSE"));
+    }
+
+    #[tokio::test]
+    async fn test_both_install_mode() {
+        let script = render(BundleSettings::default()).await;
+        assert!(!script.contains("MultiUser.nsh"));
+        assert!(!script.contains("MULTIUSER_INIT"));
+
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.install_mode = NSISInstallerMode::Both;
+        bundle.windows.nsis.languages = Some(vec!["en-US".into(), "fr-FR".into()]);
+        let script = render(bundle).await;
+        assert!(
+            script.find("!include \"MultiUser.nsh\"").unwrap()
+                < script.find("!include \"MUI2.nsh\"").unwrap()
+        );
+        assert!(script.contains("!insertmacro MULTIUSER_PAGE_INSTALLMODE\n"));
+        // One .onInit serves both MultiUser and the language dialog
+        assert_eq!(script.matches("Function .onInit").count(), 1);
+        assert!(
+            script.contains("  !insertmacro MULTIUSER_INIT\n  !insertmacro MUI_LANGDLL_DISPLAY\n")
+        );
+        assert!(
+            script.contains("  !insertmacro MULTIUSER_UNINIT\n  !insertmacro MUI_UNGETLANGUAGE\n")
+        );
+        assert!(script.contains("\"InstallMode\" \"$MultiUser.InstallMode\"\n"));
+    }
+
+    #[tokio::test]
+    async fn test_architectures() {
+        let mut bundle = BundleSettings::default();
+        bundle.windows.nsis.install_mode = NSISInstallerMode::PerMachine;
+
+        let script = render_for(bundle.clone(), "arm64").await;
+        assert!(script.contains("!define ARCH \"arm64\"\n"));
+        assert!(script.contains("!define INSTALL_DIR \"$PROGRAMFILES64\\kodegen\"\n"));
+        assert!(script.contains("${IfNot} ${IsNativeARM64}"));
+
+        let script = render_for(bundle, "x86").await;
+        assert!(script.contains("!define INSTALL_DIR \"$PROGRAMFILES\\kodegen\"\n"));
+    }
+}
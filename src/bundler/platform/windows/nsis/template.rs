@@ -8,11 +8,16 @@
 /// This template generates a complete NSIS installer script with:
 /// - Modern UI wizard interface
 /// - Configurable compression algorithms
-/// - Multiple architecture support
-/// - Per-user or per-machine installation modes
+/// - x86, x64 and ARM64 installers with architecture checks
+/// - Per-user, per-machine or user-selected (MultiUser) installation modes
 /// - Start Menu shortcuts
 /// - Windows registry integration
 /// - Full uninstaller support
+/// - Custom includes, plugins and a pre-install section
+/// - Multiple languages with a language selection dialog
+/// - File association and URL protocol registration
+/// - Optional PATH registration
+/// - Optional license page
 pub const NSI_TEMPLATE: &str = r#"
 ; NSIS Installer Script
 ; Generated by kodegen_release
@@ -31,10 +36,40 @@ pub const NSI_TEMPLATE: &str = r#"
   SetCompressor /SOLID "{{compression}}"
 !endif
 
+{{#if multi_user}}
+; Install scope chosen at install time (must precede MUI2.nsh)
+!define MULTIUSER_EXECUTIONLEVEL Highest
+!define MULTIUSER_MUI
+!define MULTIUSER_INSTALLMODE_COMMANDLINE
+!define MULTIUSER_INSTALLMODE_INSTDIR "${PRODUCT_NAME}"
+!define MULTIUSER_INSTALLMODE_DEFAULT_REGISTRY_KEY "Software\${PRODUCT_NAME}"
+!define MULTIUSER_INSTALLMODE_DEFAULT_REGISTRY_VALUENAME "InstallMode"
+!define MULTIUSER_INSTALLMODE_INSTDIR_REGISTRY_KEY "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}"
+!define MULTIUSER_INSTALLMODE_INSTDIR_REGISTRY_VALUENAME "InstallLocation"
+!define MULTIUSER_INSTALLMODE_FUNCTION SetInstallMode
+!define MULTIUSER_INSTALLMODE_UNFUNCTION un.SetInstallMode
+!if "{{arch}}" != "x86"
+  !define MULTIUSER_USE_PROGRAMFILES64
+!endif
+!include "MultiUser.nsh"
+
+{{/if}}
 ; Modern UI
 !include "MUI2.nsh"
 !include "x64.nsh"
 !include "FileFunc.nsh"
+!include "WordFunc.nsh"
+!include "WinMessages.nsh"
+
+; Plugins
+{{#each plugin_dirs}}
+!addplugindir "{{this}}"
+{{/each}}
+
+; Custom Includes
+{{#each custom_includes}}
+!include "{{this}}"
+{{/each}}
 
 ; Custom Branding Images
 {{#if header_image}}
@@ -50,18 +85,54 @@ pub const NSI_TEMPLATE: &str = r#"
 !define MUI_ICON "{{installer_icon}}"
 {{/if}}
 
+; Registry roots for the install scope: uninstall entry, file associations
+; and URL protocols, and the environment key holding PATH. In "both" mode
+; MultiUser sets the shell context, so SHCTX resolves at runtime.
+!if "{{install_mode}}" == "perMachine"
+  !define UNINST_ROOT "HKLM"
+  !define CLASSES_ROOT "HKLM"
+  !define ENV_ROOT "HKLM"
+  !define ENV_KEY "SYSTEM\CurrentControlSet\Control\Session Manager\Environment"
+!else if "{{install_mode}}" == "both"
+  !define UNINST_ROOT "SHCTX"
+  !define CLASSES_ROOT "SHCTX"
+  !define ENV_ROOT "SHCTX"
+  !define ENV_KEY "$EnvKey"
+!else
+  !define UNINST_ROOT "HKCU"
+  !define CLASSES_ROOT "HKCU"
+  !define ENV_ROOT "HKCU"
+  !define ENV_KEY "Environment"
+!endif
+{{#if multi_user}}
+
+Var EnvKey
+
+!macro SET_ENV_KEY
+  StrCpy $EnvKey "Environment"
+  StrCmp $MultiUser.InstallMode "AllUsers" 0 +2
+    StrCpy $EnvKey "SYSTEM\CurrentControlSet\Control\Session Manager\Environment"
+!macroend
+
+Function SetInstallMode
+  !insertmacro SET_ENV_KEY
+FunctionEnd
+
+Function un.SetInstallMode
+  !insertmacro SET_ENV_KEY
+FunctionEnd
+{{/if}}
+
 ; General Settings
 Name "${PRODUCT_NAME}"
 OutFile "${OUTPUT_FILE}"
 InstallDir "${INSTALL_DIR}"
 
-; Request admin for perMachine, user for currentUser
+; Request admin for perMachine, user for currentUser (MultiUser decides for both)
 !if "{{install_mode}}" == "perMachine"
   RequestExecutionLevel admin
 !else if "{{install_mode}}" == "currentUser"
   RequestExecutionLevel user
-!else
-  RequestExecutionLevel admin
 !endif
 
 ; Interface Settings
@@ -70,10 +141,29 @@ InstallDir "${INSTALL_DIR}"
 !define MUI_ICON "${NSISDIR}\Contrib\Graphics\Icons\modern-install.ico"
 {{/unless}}
 !define MUI_UNICON "${NSISDIR}\Contrib\Graphics\Icons\modern-uninstall.ico"
+{{#if language_selection}}
+
+; Remember the selected language for the uninstaller and upgrades
+!define MUI_LANGDLL_REGISTRY_ROOT "HKCU"
+!define MUI_LANGDLL_REGISTRY_KEY "Software\${PRODUCT_NAME}"
+!define MUI_LANGDLL_REGISTRY_VALUENAME "Installer Language"
+{{/if}}
 
 ; Pages
 !insertmacro MUI_PAGE_WELCOME
+{{#if license}}
+!insertmacro MUI_PAGE_LICENSE "{{license}}"
+{{/if}}
+{{#if add_to_path}}
+!insertmacro MUI_PAGE_COMPONENTS
+{{/if}}
+{{#if multi_user}}
+!insertmacro MULTIUSER_PAGE_INSTALLMODE
+{{/if}}
 !insertmacro MUI_PAGE_DIRECTORY
+!ifmacrodef CUSTOM_PAGES
+  !insertmacro CUSTOM_PAGES
+!endif
 !insertmacro MUI_PAGE_INSTFILES
 !insertmacro MUI_PAGE_FINISH
 
@@ -82,7 +172,50 @@ InstallDir "${INSTALL_DIR}"
 !insertmacro MUI_UNPAGE_INSTFILES
 
 ; Languages
-!insertmacro MUI_LANGUAGE "English"
+{{#each languages}}
+!insertmacro MUI_LANGUAGE "{{this}}"
+{{/each}}
+{{#if language_selection}}
+!insertmacro MUI_RESERVEFILE_LANGDLL
+{{/if}}
+
+; Refuse to run on the wrong architecture and use the native registry view,
+; so 64-bit installs do not write to WOW6432Node
+!macro INIT_ARCH
+  !if "${ARCH}" == "arm64"
+    ${IfNot} ${IsNativeARM64}
+      MessageBox MB_ICONSTOP "${PRODUCT_NAME} requires Windows on ARM64." /SD IDOK
+      Abort
+    ${EndIf}
+    SetRegView 64
+  !else if "${ARCH}" == "x64"
+    ${IfNot} ${RunningX64}
+      MessageBox MB_ICONSTOP "${PRODUCT_NAME} requires 64-bit Windows." /SD IDOK
+      Abort
+    ${EndIf}
+    SetRegView 64
+  !endif
+!macroend
+
+Function .onInit
+  !insertmacro INIT_ARCH
+  {{#if multi_user}}
+  !insertmacro MULTIUSER_INIT
+  {{/if}}
+  {{#if language_selection}}
+  !insertmacro MUI_LANGDLL_DISPLAY
+  {{/if}}
+FunctionEnd
+
+Function un.onInit
+  !insertmacro INIT_ARCH
+  {{#if multi_user}}
+  !insertmacro MULTIUSER_UNINIT
+  {{/if}}
+  {{#if language_selection}}
+  !insertmacro MUI_UNGETLANGUAGE
+  {{/if}}
+FunctionEnd
 
 ; Version Info
 VIProductVersion "{{version_nsis}}"
@@ -92,14 +225,29 @@ VIAddVersionKey "CompanyName" "${PRODUCT_PUBLISHER}"
 VIAddVersionKey "FileDescription" "${PRODUCT_NAME} Installer"
 VIAddVersionKey "FileVersion" "${PRODUCT_VERSION}"
 
+{{#if preinstall_section}}
+; Pre-install Section (hidden)
+Section "-PreInstall"
+  !include "{{preinstall_section}}"
+SectionEnd
+
+{{/if}}
 ; Installation Section
 Section "MainSection" SEC01
+  SectionIn RO
   SetOutPath "$INSTDIR"
   
   ; Copy all binaries
   {{#each binary_files}}
-  File "{{this}}"
+  File "/oname={{file_name}}" "{{source}}"
   {{/each}}
+
+  ; Copy resources
+  {{#each resources}}
+  SetOutPath "{{out_dir}}"
+  File "{{source}}"
+  {{/each}}
+  SetOutPath "$INSTDIR"
   
   ; Create uninstaller
   WriteUninstaller "$INSTDIR\Uninstall.exe"
@@ -111,13 +259,46 @@ Section "MainSection" SEC01
   CreateShortcut "$SMPROGRAMS\${PRODUCT_NAME}\Uninstall.lnk" \
     "$INSTDIR\Uninstall.exe"
   
+  ; Register file associations
+  {{#each file_associations}}
+  {{#if icon}}
+  File "/oname={{icon_file}}" "{{icon}}"
+  {{/if}}
+  {{#if claim_default}}
+  WriteRegStr ${CLASSES_ROOT} "Software\Classes\.{{ext}}" "" "{{prog_id}}"
+  {{/if}}
+  {{#if mime_type}}
+  WriteRegStr ${CLASSES_ROOT} "Software\Classes\.{{ext}}" "Content Type" "{{mime_type}}"
+  {{/if}}
+  WriteRegStr ${CLASSES_ROOT} "Software\Classes\.{{ext}}\OpenWithProgids" "{{prog_id}}" ""
+  WriteRegStr ${CLASSES_ROOT} "{{prog_id_key}}" "" "{{name}}"
+  {{#if icon}}
+  WriteRegStr ${CLASSES_ROOT} "{{prog_id_key}}\DefaultIcon" "" "{{icon_path}}"
+  {{else}}
+  WriteRegStr ${CLASSES_ROOT} "{{prog_id_key}}\DefaultIcon" "" "$INSTDIR\${BINARY_NAME}.exe,0"
+  {{/if}}
+  WriteRegStr ${CLASSES_ROOT} "{{prog_id_key}}\shell\open\command" "" '"$INSTDIR\${BINARY_NAME}.exe" "%1"'
+  {{/each}}
+
+  ; Register URL protocol handlers
+  {{#each url_schemes}}
+  WriteRegStr ${CLASSES_ROOT} "{{key}}" "" "URL:{{name}}"
+  WriteRegStr ${CLASSES_ROOT} "{{key}}" "URL Protocol" ""
+  WriteRegStr ${CLASSES_ROOT} "{{key}}\DefaultIcon" "" "$INSTDIR\${BINARY_NAME}.exe,0"
+  WriteRegStr ${CLASSES_ROOT} "{{key}}\shell\open\command" "" '"$INSTDIR\${BINARY_NAME}.exe" "%1"'
+  {{/each}}
+  {{#if has_associations}}
+
+  ; Refresh Explorer icons and handlers (SHCNE_ASSOCCHANGED)
+  System::Call 'shell32::SHChangeNotify(i 0x08000000, i 0, p 0, p 0)'
+  {{/if}}
+
+  {{#if multi_user}}
+  ; Record the chosen scope for the uninstaller and upgrades
+  WriteRegStr SHCTX "Software\${PRODUCT_NAME}" "InstallMode" "$MultiUser.InstallMode"
+
+  {{/if}}
   ; Write registry uninstall information
-  !if "{{install_mode}}" == "perMachine"
-    !define UNINST_ROOT "HKLM"
-  !else
-    !define UNINST_ROOT "HKCU"
-  !endif
-  
   WriteRegStr ${UNINST_ROOT} \
     "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}" \
     "DisplayName" "${PRODUCT_NAME}"
@@ -142,26 +323,96 @@ Section "MainSection" SEC01
     "EstimatedSize" "$0"
 SectionEnd
 
+{{#if add_to_path}}
+; Optional PATH registration (checkbox on the components page)
+Section "Add to PATH" SEC_PATH
+  ReadRegStr $0 ${ENV_ROOT} "${ENV_KEY}" "Path"
+  ; Never rewrite a PATH that did not fit in an NSIS string
+  StrLen $1 $0
+  IntOp $2 ${NSIS_MAX_STRLEN} - 1
+  IntCmp $1 $2 0 +3 0
+    DetailPrint "PATH is too long to update; add $INSTDIR manually"
+    Goto path_done
+  ${WordAdd} "$0" ";" "+$INSTDIR" $0
+  WriteRegExpandStr ${ENV_ROOT} "${ENV_KEY}" "Path" "$0"
+  WriteRegDWORD ${UNINST_ROOT} \
+    "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}" \
+    "AddedToPath" 1
+  SendMessage ${HWND_BROADCAST} ${WM_SETTINGCHANGE} 0 "STR:Environment" /TIMEOUT=5000
+  path_done:
+SectionEnd
+
+{{/if}}
 ; Uninstaller Section
 Section "Uninstall"
   ; Remove uninstaller
   Delete "$INSTDIR\Uninstall.exe"
 
   ; Remove all installed binaries
-  Delete "$INSTDIR\${BINARY_NAME}.exe"
+  {{#each binary_files}}
+  Delete "{{install_path}}"
+  {{/each}}
+
+  ; Remove resources
+  {{#each resources}}
+  Delete "{{install_path}}"
+  {{/each}}
+  {{#each resource_dirs}}
+  RMDir "{{this}}"
+  {{/each}}
   
+  ; Unregister file associations, keeping extensions claimed by other apps
+  {{#each file_associations}}
+  {{#if icon}}
+  Delete "{{icon_path}}"
+  {{/if}}
+  DeleteRegKey ${CLASSES_ROOT} "{{prog_id_key}}"
+  DeleteRegValue ${CLASSES_ROOT} "Software\Classes\.{{ext}}\OpenWithProgids" "{{prog_id}}"
+  ReadRegStr $0 ${CLASSES_ROOT} "Software\Classes\.{{ext}}" ""
+  StrCmp $0 "{{prog_id}}" 0 +2
+    DeleteRegValue ${CLASSES_ROOT} "Software\Classes\.{{ext}}" ""
+  DeleteRegKey /ifempty ${CLASSES_ROOT} "Software\Classes\.{{ext}}\OpenWithProgids"
+  DeleteRegKey /ifempty ${CLASSES_ROOT} "Software\Classes\.{{ext}}"
+  {{/each}}
+
+  ; Unregister URL protocol handlers
+  {{#each url_schemes}}
+  DeleteRegKey ${CLASSES_ROOT} "{{key}}"
+  {{/each}}
+  {{#if has_associations}}
+  System::Call 'shell32::SHChangeNotify(i 0x08000000, i 0, p 0, p 0)'
+  {{/if}}
+
   ; Remove Start Menu shortcuts
   Delete "$SMPROGRAMS\${PRODUCT_NAME}\${PRODUCT_NAME}.lnk"
   Delete "$SMPROGRAMS\${PRODUCT_NAME}\Uninstall.lnk"
   RMDir "$SMPROGRAMS\${PRODUCT_NAME}"
   
+  {{#if add_to_path}}
+  ; Remove the install directory from PATH if the installer added it
+  ReadRegDWORD $1 ${UNINST_ROOT} \
+    "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}" "AddedToPath"
+  StrCmp $1 "1" 0 path_removed
+    ReadRegStr $0 ${ENV_ROOT} "${ENV_KEY}" "Path"
+    StrLen $1 $0
+    IntOp $2 ${NSIS_MAX_STRLEN} - 1
+    IntCmp $1 $2 path_removed 0 path_removed
+    ${WordAdd} "$0" ";" "-$INSTDIR" $0
+    WriteRegExpandStr ${ENV_ROOT} "${ENV_KEY}" "Path" "$0"
+    SendMessage ${HWND_BROADCAST} ${WM_SETTINGCHANGE} 0 "STR:Environment" /TIMEOUT=5000
+  path_removed:
+
+  {{/if}}
   ; Remove registry keys
-  !if "{{install_mode}}" == "perMachine"
-    DeleteRegKey HKLM "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}"
-  !else
-    DeleteRegKey HKCU "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}"
-  !endif
-  
+  DeleteRegKey ${UNINST_ROOT} "Software\Microsoft\Windows\CurrentVersion\Uninstall\${PRODUCT_NAME}"
+  {{#if multi_user}}
+  DeleteRegKey SHCTX "Software\${PRODUCT_NAME}"
+  {{/if}}
+
+  {{#if language_selection}}
+  DeleteRegKey HKCU "Software\${PRODUCT_NAME}"
+  {{/if}}
+
   ; Remove installation directory
   RMDir "$INSTDIR"
 SectionEnd
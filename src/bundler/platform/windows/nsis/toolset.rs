@@ -2,30 +2,61 @@
 //!
 //! Locates system-installed makensis binary on Linux/macOS.
 
-use crate::bundler::error::{Error, Result};
+use crate::bundler::{
+    error::{Error, Result},
+    utils::tools,
+};
 use std::path::PathBuf;
 
+/// Oldest NSIS release whose script syntax the generated installers use
+const MIN_NSIS_VERSION: (u32, u32) = (3, 0);
+
 /// Get NSIS toolset.
 ///
 /// Locates system-installed makensis on Linux/macOS.
 ///
 /// Returns the path to the NSIS directory containing makensis executable.
 pub async fn get_nsis_toolset() -> Result<PathBuf> {
-    get_nsis_unix()
+    get_nsis_unix().await
 }
 
 /// Locate system-installed makensis on Unix systems.
-fn get_nsis_unix() -> Result<PathBuf> {
+///
+/// Verifies the installed version is NSIS 3.0 or later.
+async fn get_nsis_unix() -> Result<PathBuf> {
     // On Linux/macOS, find system-installed makensis
-    match which::which("makensis") {
-        Ok(path) => {
-            let bin_dir = path.parent().ok_or_else(|| {
-                Error::GenericError("makensis path has no parent directory".into())
-            })?;
-            Ok(bin_dir.to_path_buf())
+    let tool = tools::find_system_tool(&["makensis"], "-VERSION")
+        .await
+        .ok_or_else(|| {
+            Error::GenericError(
+                "makensis not found. Please install NSIS (e.g., apt-get install nsis)".into(),
+            )
+        })?;
+
+    match tool.version_number() {
+        Some(version) if version >= MIN_NSIS_VERSION => {
+            tracing::info!("Using makensis {} at {}", tool.version, tool.path.display());
+        }
+        Some(_) => {
+            return Err(Error::GenericError(format!(
+                "makensis {} at {} is too old; NSIS {}.{} or later is required",
+                tool.version,
+                tool.path.display(),
+                MIN_NSIS_VERSION.0,
+                MIN_NSIS_VERSION.1
+            )));
+        }
+        None => {
+            tracing::warn!(
+                "Could not parse makensis version '{}'; assuming NSIS 3.x",
+                tool.version
+            );
         }
-        Err(_) => Err(Error::GenericError(
-            "makensis not found. Please install NSIS (e.g., apt-get install nsis)".into(),
-        )),
     }
+
+    let bin_dir = tool
+        .path
+        .parent()
+        .ok_or_else(|| Error::GenericError("makensis path has no parent directory".into()))?;
+    Ok(bin_dir.to_path_buf())
 }
@@ -49,6 +49,68 @@ pub fn map_install_mode(mode: NSISInstallerMode) -> &'static str {
     }
 }
 
+/// Locale codes and the NSIS Modern UI language file for each.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("de", "German"),
+    ("fr", "French"),
+    ("es", "Spanish"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("pt", "Portuguese"),
+    ("pt-br", "PortugueseBR"),
+    ("ru", "Russian"),
+    ("uk", "Ukrainian"),
+    ("pl", "Polish"),
+    ("cs", "Czech"),
+    ("sk", "Slovak"),
+    ("hu", "Hungarian"),
+    ("ro", "Romanian"),
+    ("bg", "Bulgarian"),
+    ("el", "Greek"),
+    ("tr", "Turkish"),
+    ("sv", "Swedish"),
+    ("nb", "Norwegian"),
+    ("no", "Norwegian"),
+    ("da", "Danish"),
+    ("fi", "Finnish"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("zh-cn", "SimpChinese"),
+    ("zh-hans", "SimpChinese"),
+    ("zh-tw", "TradChinese"),
+    ("zh-hant", "TradChinese"),
+    ("ar", "Arabic"),
+    ("he", "Hebrew"),
+    ("th", "Thai"),
+    ("vi", "Vietnamese"),
+    ("id", "Indonesian"),
+];
+
+/// Map a language to its NSIS Modern UI language name.
+///
+/// Accepts locale codes (`"de-DE"`, `"pt-BR"`, `"zh-CN"`) and NSIS names
+/// (`"German"`). A code with an unknown region falls back to the language
+/// alone, so `"en-GB"` maps to `"English"`.
+pub fn map_language(language: &str) -> Result<&'static str> {
+    let code = language.to_lowercase().replace('_', "-");
+    let lookup = |code: &str| {
+        LANGUAGES
+            .iter()
+            .find(|(c, name)| *c == code || name.eq_ignore_ascii_case(code))
+            .map(|(_, name)| *name)
+    };
+
+    lookup(&code)
+        .or_else(|| {
+            code.split_once('-')
+                .and_then(|(primary, _)| lookup(primary))
+        })
+        .ok_or_else(|| {
+            Error::GenericError(format!("Unsupported NSIS installer language: {}", language))
+        })
+}
+
 /// Format version string for NSIS VIProductVersion.
 ///
 /// NSIS requires exactly 4 numeric parts (major.minor.patch.build).
@@ -77,6 +139,35 @@ pub fn format_version_for_nsis(version: &str) -> Result<String> {
     }
 }
 
+/// Convert plain text to RTF for the NSIS license page.
+///
+/// The license page renders RTF regardless of the installer's code page, so
+/// non-ASCII characters are written as `\uN?` escapes. Text is shown in a
+/// fixed-width font to keep the layout of license files.
+pub fn text_to_rtf(text: &str) -> String {
+    let mut rtf =
+        String::from("{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\fmodern Consolas;}}\\f0\\fs18\n");
+    for c in text.chars() {
+        match c {
+            '\\' => rtf.push_str("\\\\"),
+            '{' => rtf.push_str("\\{"),
+            '}' => rtf.push_str("\\}"),
+            '\n' => rtf.push_str("\\par\n"),
+            '\r' => {}
+            '\t' => rtf.push_str("\\tab "),
+            c if c.is_ascii() => rtf.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    rtf.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+    rtf.push('}');
+    rtf
+}
+
 /// Write file with UTF-8 BOM (required by NSIS).
 ///
 /// NSIS requires installer scripts to be encoded with UTF-8 BOM (byte order mark).
@@ -97,3 +188,31 @@ pub async fn write_utf8_bom(path: &Path, content: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_language() {
+        assert_eq!(map_language("en-US").unwrap(), "English");
+        assert_eq!(map_language("en_GB").unwrap(), "English");
+        assert_eq!(map_language("de-DE").unwrap(), "German");
+        assert_eq!(map_language("pt-BR").unwrap(), "PortugueseBR");
+        assert_eq!(map_language("pt-PT").unwrap(), "Portuguese");
+        assert_eq!(map_language("zh-CN").unwrap(), "SimpChinese");
+        assert_eq!(map_language("TradChinese").unwrap(), "TradChinese");
+        assert!(map_language("tlh").is_err());
+    }
+
+    #[test]
+    fn test_text_to_rtf() {
+        let rtf = text_to_rtf("MIT {c} \\ Grüße\r\nline 2\n");
+        assert!(rtf.starts_with("{\\rtf1\\ansi"));
+        assert!(
+            rtf.ends_with("\\f0\\fs18\nMIT \\{c\\} \\\\ Gr\\u252?\\u223?e\\par\nline 2\\par\n}")
+        );
+        // Characters outside the BMP become UTF-16 surrogate pairs
+        assert!(text_to_rtf("😀").ends_with("\\u-10179?\\u-8704?}"));
+    }
+}
@@ -0,0 +1,214 @@
+//! Windows portable ZIP packages.
+//!
+//! Packs the built executables into
+//! `bundle/portable/<product>-<version>-<arch>-portable.zip` together with a
+//! marker file. Applications that find the marker next to their executable
+//! should keep their settings there instead of in `%APPDATA%`, so the folder
+//! can run from a USB stick without installation.
+
+use super::{icon, nsis::utils::map_arch, resources, sign};
+use crate::bundler::{
+    BundleStage, PackageType,
+    error::{Context, Error, ErrorExt, Result},
+    hooks::{self, Hook},
+    resources::{files::copy_resources, license, locales},
+    settings::Settings,
+    utils::{fs::copy_file, progress_bar},
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Default name of the marker file placed next to the executables
+pub const DEFAULT_PORTABLE_MARKER: &str = "portable.txt";
+
+/// Bundles the project into a portable ZIP archive.
+///
+/// # Returns
+///
+/// Vector containing the path to the generated .zip file
+pub async fn bundle_project(settings: &Settings) -> Result<Vec<PathBuf>> {
+    tracing::info!("Building portable ZIP for {}", settings.product_name());
+
+    let arch = map_arch(settings.binary_arch())?;
+    let output_dir = settings.project_out_directory().join("bundle/portable");
+    let folder = format!(
+        "{}-{}-{}-portable",
+        settings.product_name(),
+        settings.version_string(),
+        arch
+    );
+
+    // Stage copies so signing leaves the build output untouched
+    let staging = output_dir.join(&folder);
+    if staging.exists() {
+        tokio::fs::remove_dir_all(&staging)
+            .await
+            .fs_context("removing previous portable staging directory", &staging)?;
+    }
+    tokio::fs::create_dir_all(&staging)
+        .await
+        .fs_context("creating portable staging directory", &staging)?;
+
+    let progress = settings.progress();
+    progress.report(
+        PackageType::Portable,
+        BundleStage::Building,
+        Some(10),
+        "Embedding resources",
+    );
+    let app_icon = icon::application_icon(settings, &output_dir).await?;
+    let resource_settings = settings.clone();
+    tokio::task::spawn_blocking(move || {
+        resources::embed_resources(&resource_settings, app_icon.as_deref())
+    })
+    .await
+    .map_err(|e| Error::GenericError(format!("Resource embedding task failed: {}", e)))??;
+
+    let mut files = Vec::new();
+    let binary_count = settings.binaries().len();
+    for (index, binary) in settings.binaries().iter().enumerate() {
+        let source = settings.binary_path(binary).with_extension("exe");
+        // Sidecars drop their target suffix
+        let name = format!("{}.exe", binary.name());
+        progress.report_items(
+            PackageType::Portable,
+            BundleStage::Copying,
+            (20, 60),
+            (index, binary_count),
+            format!("Copying {}", name),
+        );
+        let dest = staging.join(&name);
+        tokio::fs::copy(&source, &dest)
+            .await
+            .fs_context("copying executable", &source)?;
+        if sign::should_sign(settings) {
+            progress.report_items(
+                PackageType::Portable,
+                BundleStage::Signing,
+                (20, 60),
+                (index, binary_count),
+                format!("Signing {}", name),
+            );
+            sign::sign_file(&dest, settings)
+                .await
+                .context("signing portable executable")?;
+            hooks::run_for_package(
+                settings,
+                Hook::PostSign,
+                PackageType::Portable,
+                std::slice::from_ref(&dest),
+                &[],
+            )
+            .await?;
+        }
+        files.push(dest);
+    }
+
+    let marker_name = settings
+        .bundle_settings()
+        .windows
+        .portable_marker
+        .as_deref()
+        .unwrap_or(DEFAULT_PORTABLE_MARKER);
+    let marker = staging.join(marker_name);
+    tokio::fs::write(
+        &marker,
+        format!(
+            "{} keeps its settings in this folder while this file is present.\r\n",
+            settings.product_name()
+        ),
+    )
+    .await
+    .fs_context("writing portable marker", &marker)?;
+    files.push(marker);
+    files.extend(copy_resources(settings, &staging).await?);
+    files.extend(license::copy_licenses(settings, &staging).await?);
+    for catalog in locales::localizations(settings)?.catalogs_in(&staging.join(locales::WINDOWS_LOCALE_DIR)) {
+        copy_file(&catalog.source, &catalog.target).await?;
+        files.push(catalog.target);
+    }
+
+    progress.report(
+        PackageType::Portable,
+        BundleStage::Compressing,
+        Some(60),
+        "Compressing portable ZIP",
+    );
+    let zip_path = output_dir.join(format!("{}.zip", folder));
+    let archive_path = zip_path.clone();
+    let zip_staging = staging.clone();
+    let zip = tokio::task::spawn_blocking(move || write_zip(&archive_path, &folder, &zip_staging, &files));
+    progress_bar::spin("Compressing portable ZIP", zip)
+        .await
+        .map_err(|e| Error::GenericError(format!("ZIP creation task failed: {}", e)))??;
+
+    tokio::fs::remove_dir_all(&staging)
+        .await
+        .fs_context("removing portable staging directory", &staging)?;
+
+    tracing::info!("✓ Created portable ZIP: {}", zip_path.display());
+    Ok(vec![zip_path])
+}
+
+/// Writes `files` into `zip_path` under a top-level `folder/`, keeping
+/// their paths relative to `staging`
+fn write_zip(zip_path: &Path, folder: &str, staging: &Path, files: &[PathBuf]) -> Result<()> {
+    let file = std::fs::File::create(zip_path).fs_context("creating portable ZIP", zip_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+
+    zip.add_directory(format!("{}/", folder), options)?;
+    for path in files {
+        let name = path.strip_prefix(staging)?;
+        let name: Vec<_> = name.iter().map(|part| part.to_string_lossy()).collect();
+        zip.start_file(format!("{}/{}", folder, name.join("/")), options)?;
+        let data = std::fs::read(path).fs_context("reading file for portable ZIP", path)?;
+        zip.write_all(&data)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_write_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("kodegen.exe");
+        let marker = dir.path().join(DEFAULT_PORTABLE_MARKER);
+        std::fs::write(&exe, b"MZ").unwrap();
+        std::fs::write(&marker, b"portable").unwrap();
+        let resource = dir.path().join("config/default.toml");
+        std::fs::create_dir(dir.path().join("config")).unwrap();
+        std::fs::write(&resource, b"").unwrap();
+
+        let zip_path = dir.path().join("kodegen-1.0.0-x64-portable.zip");
+        write_zip(&zip_path, "kodegen-1.0.0-x64-portable", dir.path(), &[exe, marker, resource]).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        let mut names: Vec<_> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "kodegen-1.0.0-x64-portable/",
+                "kodegen-1.0.0-x64-portable/config/default.toml",
+                "kodegen-1.0.0-x64-portable/kodegen.exe",
+                "kodegen-1.0.0-x64-portable/portable.txt",
+            ]
+        );
+
+        let mut contents = Vec::new();
+        archive
+            .by_name("kodegen-1.0.0-x64-portable/kodegen.exe")
+            .unwrap()
+            .read_to_end(&mut contents)
+            .unwrap();
+        assert_eq!(contents, b"MZ");
+    }
+}
@@ -0,0 +1,840 @@
+//! Version and icon resources for Windows executables.
+//!
+//! Patches the built `.exe` with a `VERSIONINFO` resource (product name,
+//! version, copyright) and the application icon, like `rcedit` does, so
+//! Explorer's Properties dialog and the taskbar show them even when the
+//! binary is run outside the installer.
+//!
+//! The PE file is edited in pure Rust: the existing resource tree is read,
+//! its icon and version entries are replaced, and the rebuilt tree is
+//! appended as a new `.rsrc` section. Other resources (e.g. the manifest)
+//! are kept. Signed binaries are left untouched since appending a section
+//! would invalidate the signature.
+
+use crate::bundler::{
+    error::{Context, Error, ErrorExt, Result},
+    settings::Settings,
+};
+use std::path::Path;
+
+const RT_ICON: u16 = 3;
+const RT_GROUP_ICON: u16 = 14;
+const RT_VERSION: u16 = 16;
+
+/// en-US, the language `rc.exe` uses by default
+const LANG_EN_US: u16 = 0x0409;
+/// Unicode code page, paired with [`LANG_EN_US`] in the version resource
+const CODEPAGE_UNICODE: u16 = 1200;
+
+const IMAGE_DIRECTORY_ENTRY_RESOURCE: usize = 2;
+const IMAGE_DIRECTORY_ENTRY_SECURITY: usize = 4;
+const SECTION_HEADER_SIZE: usize = 40;
+/// `IMAGE_SCN_CNT_INITIALIZED_DATA | IMAGE_SCN_MEM_READ`
+const RSRC_CHARACTERISTICS: u32 = 0x4000_0040;
+
+/// A resource type or name: either an integer ID or a string
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ResourceId {
+    // Named entries sort before ID entries, as the PE format requires
+    Name(String),
+    Id(u16),
+}
+
+/// One leaf of the resource tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Resource {
+    kind: ResourceId,
+    name: ResourceId,
+    lang: u16,
+    data: Vec<u8>,
+}
+
+/// Embeds version information and the application icon into every binary.
+///
+/// `icon` is the path resolved by [`application_icon`](super::icon::application_icon).
+pub fn embed_resources(settings: &Settings, icon: Option<&Path>) -> Result<()> {
+    if settings.bundle_settings().windows.skip_resources {
+        return Ok(());
+    }
+
+    let icon = icon
+        .map(|path| std::fs::read(path).fs_context("reading application icon", path))
+        .transpose()?;
+
+    // Sidecars are prebuilt third-party executables; keep their resources
+    for binary in settings.binaries().iter().filter(|binary| !binary.is_sidecar()) {
+        let path = settings.binary_path(binary).with_extension("exe");
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .context("invalid binary path")?;
+        let version = version_info(settings, &file_name);
+        embed_in_file(&path, &version, icon.as_deref())?;
+    }
+    Ok(())
+}
+
+fn embed_in_file(path: &Path, version: &[u8], icon: Option<&[u8]>) -> Result<()> {
+    let mut image = std::fs::read(path).fs_context("reading Windows executable", path)?;
+    let pe = PeLayout::parse(&image)
+        .map_err(|reason| Error::GenericError(format!("{}: {}", path.display(), reason)))?;
+
+    if pe.data_directory(&image, IMAGE_DIRECTORY_ENTRY_SECURITY).1 != 0 {
+        tracing::warn!(
+            "{} is already signed; not embedding version and icon resources",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let existing = pe
+        .read_resources(&image)
+        .map_err(|reason| Error::GenericError(format!("{}: {}", path.display(), reason)))?;
+    let mut resources = existing.clone();
+    resources.retain(|r| {
+        r.kind != ResourceId::Id(RT_VERSION)
+            && (icon.is_none()
+                || (r.kind != ResourceId::Id(RT_ICON) && r.kind != ResourceId::Id(RT_GROUP_ICON)))
+    });
+    resources.push(Resource {
+        kind: ResourceId::Id(RT_VERSION),
+        name: ResourceId::Id(1),
+        lang: LANG_EN_US,
+        data: version.to_vec(),
+    });
+    if let Some(icon) = icon {
+        resources.extend(icon_resources(icon).map_err(|reason| {
+            Error::GenericError(format!("invalid application icon: {}", reason))
+        })?);
+    }
+
+    // Both the installer and the portable ZIP embed into the same binaries
+    resources.sort_by(|a, b| (&a.kind, &a.name, a.lang).cmp(&(&b.kind, &b.name, b.lang)));
+    if resources == existing {
+        tracing::debug!("{} already has these resources", path.display());
+        return Ok(());
+    }
+
+    pe.append_resource_section(&mut image, &resources)
+        .map_err(|reason| Error::GenericError(format!("{}: {}", path.display(), reason)))?;
+    std::fs::write(path, &image).fs_context("writing Windows executable", path)?;
+    tracing::info!(
+        "Embedded version and icon resources into {}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Splits a version into the four 16-bit fields of `VS_FIXEDFILEINFO`.
+///
+/// Pre-release and build suffixes are ignored (`1.2.3-beta.1` → 1.2.3.0).
+fn version_parts(version: &str) -> [u16; 4] {
+    let mut parts = [0u16; 4];
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    for (part, field) in core.split('.').zip(parts.iter_mut()) {
+        *field = part.parse().unwrap_or(0);
+    }
+    parts
+}
+
+/// Builds the `VS_VERSIONINFO` resource for one binary
+fn version_info(settings: &Settings, file_name: &str) -> Vec<u8> {
+    let bundle = settings.bundle_settings();
+    let [major, minor, patch, build] = version_parts(settings.version_string());
+    let version_ms = (major as u32) << 16 | minor as u32;
+    let version_ls = (patch as u32) << 16 | build as u32;
+
+    let mut fixed = Vec::with_capacity(52);
+    for value in [
+        0xFEEF_04BD, // signature
+        0x0001_0000, // structure version
+        version_ms,  // file version
+        version_ls,
+        version_ms, // product version
+        version_ls,
+        0x3F,        // flags mask
+        0,           // flags
+        0x0004_0004, // VOS_NT_WINDOWS32
+        1,           // VFT_APP
+        0,           // subtype
+        0,           // date
+        0,
+    ] {
+        fixed.extend(value.to_le_bytes());
+    }
+
+    let description = bundle
+        .short_description
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .unwrap_or(settings.description());
+    let description = if description.is_empty() {
+        settings.product_name()
+    } else {
+        description
+    };
+    let mut strings = vec![
+        ("FileDescription", description),
+        ("FileVersion", settings.version_string()),
+        ("InternalName", file_name.trim_end_matches(".exe")),
+        ("OriginalFilename", file_name),
+        ("ProductName", settings.product_name()),
+        ("ProductVersion", settings.version_string()),
+    ];
+    if let Some(publisher) = &bundle.publisher {
+        strings.push(("CompanyName", publisher));
+    }
+    if let Some(copyright) = &bundle.copyright {
+        strings.push(("LegalCopyright", copyright));
+    }
+    let strings: Vec<_> = strings
+        .into_iter()
+        .map(|(key, value)| version_node(key, NodeValue::Text(value), &[]))
+        .collect();
+
+    let table_key = format!("{:04X}{:04X}", LANG_EN_US, CODEPAGE_UNICODE);
+    let table = version_node(&table_key, NodeValue::None, &strings);
+    let string_info = version_node("StringFileInfo", NodeValue::None, &[table]);
+
+    let mut translation = LANG_EN_US.to_le_bytes().to_vec();
+    translation.extend(CODEPAGE_UNICODE.to_le_bytes());
+    let var = version_node("Translation", NodeValue::Binary(&translation), &[]);
+    let var_info = version_node("VarFileInfo", NodeValue::None, &[var]);
+
+    version_node(
+        "VS_VERSION_INFO",
+        NodeValue::Binary(&fixed),
+        &[string_info, var_info],
+    )
+}
+
+enum NodeValue<'a> {
+    None,
+    Binary(&'a [u8]),
+    Text(&'a str),
+}
+
+/// Encodes one `VS_VERSIONINFO` block: length, value length, type, key,
+/// value and children, each aligned to 32 bits
+fn version_node(key: &str, value: NodeValue, children: &[Vec<u8>]) -> Vec<u8> {
+    let (value_bytes, value_length, kind) = match value {
+        NodeValue::None => (Vec::new(), 0, 0u16),
+        NodeValue::Binary(data) => (data.to_vec(), data.len(), 0),
+        NodeValue::Text(text) => {
+            let encoded = utf16z(text);
+            // Text lengths are counted in UTF-16 units, terminator included
+            let units = encoded.len() / 2;
+            (encoded, units, 1)
+        }
+    };
+
+    let mut node = vec![0u8; 6];
+    node[2..4].copy_from_slice(&(value_length as u16).to_le_bytes());
+    node[4..6].copy_from_slice(&kind.to_le_bytes());
+    node.extend(utf16z(key));
+    pad4(&mut node);
+    node.extend(value_bytes);
+    for child in children {
+        pad4(&mut node);
+        node.extend(child);
+    }
+    let length = node.len() as u16;
+    node[0..2].copy_from_slice(&length.to_le_bytes());
+    node
+}
+
+/// Splits an `.ico` file into `RT_ICON` images and one `RT_GROUP_ICON`
+fn icon_resources(ico: &[u8]) -> std::result::Result<Vec<Resource>, String> {
+    if ico.len() < 6 || read_u16(ico, 0) != 0 || read_u16(ico, 2) != 1 {
+        return Err("not an .ico file".into());
+    }
+    let count = read_u16(ico, 4);
+    if count == 0 {
+        return Err("icon contains no images".into());
+    }
+
+    let mut group = ico[..6].to_vec();
+    let mut resources = Vec::new();
+    for index in 0..count {
+        let entry = 6 + index as usize * 16;
+        let header = ico
+            .get(entry..entry + 16)
+            .ok_or("truncated icon directory")?;
+        let size = read_u32(header, 8) as usize;
+        let offset = read_u32(header, 12) as usize;
+        let image = ico
+            .get(offset..offset + size)
+            .ok_or("icon image outside the file")?;
+
+        // GRPICONDIRENTRY replaces the file offset with the RT_ICON id
+        let id = index + 1;
+        group.extend_from_slice(&header[..12]);
+        group.extend(id.to_le_bytes());
+        resources.push(Resource {
+            kind: ResourceId::Id(RT_ICON),
+            name: ResourceId::Id(id),
+            lang: LANG_EN_US,
+            data: image.to_vec(),
+        });
+    }
+    resources.push(Resource {
+        kind: ResourceId::Id(RT_GROUP_ICON),
+        name: ResourceId::Id(1),
+        lang: LANG_EN_US,
+        data: group,
+    });
+    Ok(resources)
+}
+
+/// Offsets of the PE headers needed to add a section
+struct PeLayout {
+    coff_header: usize,
+    optional_header: usize,
+    data_directories: usize,
+    directory_count: usize,
+    section_table: usize,
+    section_count: usize,
+    section_alignment: u32,
+    file_alignment: u32,
+    size_of_headers: usize,
+}
+
+impl PeLayout {
+    fn parse(image: &[u8]) -> std::result::Result<Self, String> {
+        if image.len() < 0x40 || &image[..2] != b"MZ" {
+            return Err("not a PE executable (missing MZ header)".into());
+        }
+        let pe_offset = read_u32(image, 0x3C) as usize;
+        if image.get(pe_offset..pe_offset + 4) != Some(b"PE\0\0") {
+            return Err("not a PE executable (missing PE signature)".into());
+        }
+        let coff_header = pe_offset + 4;
+        let optional_header = coff_header + 20;
+        if image.len() < optional_header + 0x60 {
+            return Err("truncated PE headers".into());
+        }
+        let section_count = read_u16(image, coff_header + 2) as usize;
+        let optional_size = read_u16(image, coff_header + 16) as usize;
+        let (directory_count_offset, data_directories) = match read_u16(image, optional_header) {
+            0x10B => (optional_header + 92, optional_header + 96),
+            0x20B => (optional_header + 108, optional_header + 112),
+            magic => return Err(format!("unknown optional header magic {:#x}", magic)),
+        };
+        let section_table = optional_header + optional_size;
+        if image.len() < section_table + section_count * SECTION_HEADER_SIZE {
+            return Err("truncated section table".into());
+        }
+
+        Ok(Self {
+            coff_header,
+            optional_header,
+            data_directories,
+            directory_count: read_u32(image, directory_count_offset) as usize,
+            section_table,
+            section_count,
+            section_alignment: read_u32(image, optional_header + 32),
+            file_alignment: read_u32(image, optional_header + 36),
+            size_of_headers: read_u32(image, optional_header + 60) as usize,
+        })
+    }
+
+    /// RVA and size of a data directory entry
+    fn data_directory(&self, image: &[u8], index: usize) -> (u32, u32) {
+        if index >= self.directory_count {
+            return (0, 0);
+        }
+        let offset = self.data_directories + index * 8;
+        (read_u32(image, offset), read_u32(image, offset + 4))
+    }
+
+    /// Maps an RVA to a file offset through the section table
+    fn rva_to_offset(&self, image: &[u8], rva: u32) -> Option<usize> {
+        (0..self.section_count).find_map(|index| {
+            let header = self.section_table + index * SECTION_HEADER_SIZE;
+            let virtual_address = read_u32(image, header + 12);
+            let virtual_size = read_u32(image, header + 8).max(read_u32(image, header + 16));
+            let raw_pointer = read_u32(image, header + 20);
+            (rva >= virtual_address && rva - virtual_address < virtual_size)
+                .then(|| (raw_pointer + rva - virtual_address) as usize)
+        })
+    }
+
+    /// Flattens the existing resource tree into its leaves
+    fn read_resources(&self, image: &[u8]) -> std::result::Result<Vec<Resource>, String> {
+        let (rva, size) = self.data_directory(image, IMAGE_DIRECTORY_ENTRY_RESOURCE);
+        if rva == 0 || size == 0 {
+            return Ok(Vec::new());
+        }
+        let base = self
+            .rva_to_offset(image, rva)
+            .ok_or("resource directory outside any section")?;
+        let section = image
+            .get(base..)
+            .ok_or("resource directory outside the file")?;
+
+        let mut resources = Vec::new();
+        for (kind, names) in read_directory(section, 0)? {
+            let Entry::Directory(names) = names else {
+                return Err("resource type entry is not a directory".into());
+            };
+            for (name, langs) in read_directory(section, names)? {
+                let Entry::Directory(langs) = langs else {
+                    return Err("resource name entry is not a directory".into());
+                };
+                for (lang, leaf) in read_directory(section, langs)? {
+                    let (ResourceId::Id(lang), Entry::Data(leaf)) = (lang, leaf) else {
+                        return Err("malformed resource language entry".into());
+                    };
+                    let entry = section
+                        .get(leaf..leaf + 16)
+                        .ok_or("resource data entry outside the section")?;
+                    let data_size = read_u32(entry, 4) as usize;
+                    let data = self
+                        .rva_to_offset(image, read_u32(entry, 0))
+                        .and_then(|offset| image.get(offset..offset + data_size))
+                        .ok_or("resource data outside the file")?;
+                    resources.push(Resource {
+                        kind: kind.clone(),
+                        name: name.clone(),
+                        lang,
+                        data: data.to_vec(),
+                    });
+                }
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Appends `resources` as a new `.rsrc` section and points the resource
+    /// data directory at it
+    fn append_resource_section(
+        &self,
+        image: &mut Vec<u8>,
+        resources: &[Resource],
+    ) -> std::result::Result<(), String> {
+        if self.directory_count <= IMAGE_DIRECTORY_ENTRY_RESOURCE {
+            return Err("PE header has no resource data directory".into());
+        }
+        let header = self.section_table + self.section_count * SECTION_HEADER_SIZE;
+        let first_raw = (0..self.section_count)
+            .map(|index| read_u32(image, self.section_table + index * SECTION_HEADER_SIZE + 20))
+            .filter(|&pointer| pointer != 0)
+            .min()
+            .unwrap_or(u32::MAX) as usize;
+        if header + SECTION_HEADER_SIZE > self.size_of_headers.min(first_raw) {
+            return Err("no room in the PE headers for a resource section".into());
+        }
+
+        let section_end = (0..self.section_count)
+            .map(|index| {
+                let header = self.section_table + index * SECTION_HEADER_SIZE;
+                read_u32(image, header + 12)
+                    + read_u32(image, header + 8).max(read_u32(image, header + 16))
+            })
+            .max()
+            .unwrap_or(0);
+        let virtual_address = align(section_end, self.section_alignment);
+        let data = build_resource_section(resources, virtual_address);
+        let raw_size = align(data.len() as u32, self.file_alignment);
+        // Appending after any overlay (e.g. a COFF symbol table) keeps its offsets valid
+        let raw_pointer = align(image.len() as u32, self.file_alignment);
+
+        image.resize(raw_pointer as usize, 0);
+        image.extend_from_slice(&data);
+        image.resize((raw_pointer + raw_size) as usize, 0);
+
+        let mut section = [0u8; SECTION_HEADER_SIZE];
+        section[..5].copy_from_slice(b".rsrc");
+        section[8..12].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        section[12..16].copy_from_slice(&virtual_address.to_le_bytes());
+        section[16..20].copy_from_slice(&raw_size.to_le_bytes());
+        section[20..24].copy_from_slice(&raw_pointer.to_le_bytes());
+        section[36..40].copy_from_slice(&RSRC_CHARACTERISTICS.to_le_bytes());
+        image[header..header + SECTION_HEADER_SIZE].copy_from_slice(&section);
+
+        write_u16(image, self.coff_header + 2, self.section_count as u16 + 1);
+        let initialized = read_u32(image, self.optional_header + 8);
+        write_u32(image, self.optional_header + 8, initialized + raw_size);
+        let size_of_image = align(virtual_address + data.len() as u32, self.section_alignment);
+        write_u32(image, self.optional_header + 56, size_of_image);
+        let directory = self.data_directories + IMAGE_DIRECTORY_ENTRY_RESOURCE * 8;
+        write_u32(image, directory, virtual_address);
+        write_u32(image, directory + 4, data.len() as u32);
+
+        // Only drivers and boot-time DLLs need a valid checksum; keep it
+        // consistent when the linker set one
+        if read_u32(image, self.optional_header + 64) != 0 {
+            let checksum = pe_checksum(image, self.optional_header + 64);
+            write_u32(image, self.optional_header + 64, checksum);
+        }
+        Ok(())
+    }
+}
+
+/// Target of a resource directory entry, as an offset into the section
+enum Entry {
+    Directory(usize),
+    Data(usize),
+}
+
+/// Reads the entries of the `IMAGE_RESOURCE_DIRECTORY` at `offset`
+fn read_directory(
+    section: &[u8],
+    offset: usize,
+) -> std::result::Result<Vec<(ResourceId, Entry)>, String> {
+    let header = section
+        .get(offset..offset + 16)
+        .ok_or("resource directory outside the section")?;
+    let count = read_u16(header, 12) as usize + read_u16(header, 14) as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let entry = section
+            .get(offset + 16 + index * 8..offset + 24 + index * 8)
+            .ok_or("truncated resource directory")?;
+        let (name, target) = (read_u32(entry, 0), read_u32(entry, 4));
+        let id = if name & 0x8000_0000 != 0 {
+            let at = (name & 0x7FFF_FFFF) as usize;
+            let length = section.get(at..at + 2).map(|b| read_u16(b, 0) as usize);
+            let units: Vec<u16> = length
+                .and_then(|length| section.get(at + 2..at + 2 + length * 2))
+                .ok_or("resource name outside the section")?
+                .as_chunks::<2>()
+                .0
+                .iter()
+                .map(|unit| u16::from_le_bytes(*unit))
+                .collect();
+            ResourceId::Name(String::from_utf16_lossy(&units))
+        } else {
+            ResourceId::Id(name as u16)
+        };
+        let target = if target & 0x8000_0000 != 0 {
+            Entry::Directory((target & 0x7FFF_FFFF) as usize)
+        } else {
+            Entry::Data(target as usize)
+        };
+        entries.push((id, target));
+    }
+    Ok(entries)
+}
+
+/// Serializes a resource tree (type → name → language) for a section
+/// loaded at `virtual_address`.
+///
+/// Layout: all directory tables, then name strings, data entries and the
+/// 8-byte aligned data.
+fn build_resource_section(resources: &[Resource], virtual_address: u32) -> Vec<u8> {
+    use std::collections::BTreeMap;
+
+    let mut tree: BTreeMap<&ResourceId, BTreeMap<&ResourceId, BTreeMap<u16, &[u8]>>> =
+        BTreeMap::new();
+    for resource in resources {
+        tree.entry(&resource.kind)
+            .or_default()
+            .entry(&resource.name)
+            .or_default()
+            .insert(resource.lang, &resource.data);
+    }
+
+    let directory_size = |entries: usize| 16 + entries * 8;
+    let mut tables_size = directory_size(tree.len());
+    let mut leaf_count = 0;
+    for names in tree.values() {
+        tables_size += directory_size(names.len());
+        for langs in names.values() {
+            tables_size += directory_size(langs.len());
+            leaf_count += langs.len();
+        }
+    }
+
+    let mut strings = Vec::new();
+    let mut string_offsets = BTreeMap::new();
+    for id in tree
+        .iter()
+        .flat_map(|(kind, names)| std::iter::once(*kind).chain(names.keys().copied()))
+    {
+        if let ResourceId::Name(name) = id
+            && !string_offsets.contains_key(name)
+        {
+            string_offsets.insert(name.clone(), tables_size + strings.len());
+            let units: Vec<u16> = name.encode_utf16().collect();
+            strings.extend((units.len() as u16).to_le_bytes());
+            strings.extend(units.iter().flat_map(|unit| unit.to_le_bytes()));
+        }
+    }
+    let mut entries_offset = tables_size + strings.len();
+    entries_offset += entries_offset.wrapping_neg() % 4;
+    let mut data_offset = entries_offset + leaf_count * 16;
+
+    let mut tables = Vec::with_capacity(tables_size);
+    let mut data_entries = Vec::with_capacity(leaf_count * 16);
+    let mut data = Vec::new();
+    let name_field = |id: &ResourceId| match id {
+        ResourceId::Name(name) => 0x8000_0000 | string_offsets[name] as u32,
+        ResourceId::Id(id) => *id as u32,
+    };
+    let write_header = |tables: &mut Vec<u8>, ids: &[&ResourceId]| {
+        let named = ids
+            .iter()
+            .filter(|id| matches!(id, ResourceId::Name(_)))
+            .count();
+        tables.extend([0u8; 12]);
+        tables.extend((named as u16).to_le_bytes());
+        tables.extend(((ids.len() - named) as u16).to_le_bytes());
+    };
+
+    // Breadth-first: the root, then every name directory, then every
+    // language directory, so child offsets are known up front
+    let mut next = directory_size(tree.len());
+    let root: Vec<_> = tree.keys().copied().collect();
+    write_header(&mut tables, &root);
+    for (kind, names) in &tree {
+        tables.extend(name_field(kind).to_le_bytes());
+        tables.extend((0x8000_0000 | next as u32).to_le_bytes());
+        next += directory_size(names.len());
+    }
+    for names in tree.values() {
+        let ids: Vec<_> = names.keys().copied().collect();
+        write_header(&mut tables, &ids);
+        for (name, langs) in names {
+            tables.extend(name_field(name).to_le_bytes());
+            tables.extend((0x8000_0000 | next as u32).to_le_bytes());
+            next += directory_size(langs.len());
+        }
+    }
+    for langs in tree.values().flat_map(|names| names.values()) {
+        tables.extend([0u8; 12]);
+        tables.extend(0u16.to_le_bytes());
+        tables.extend((langs.len() as u16).to_le_bytes());
+        for (lang, bytes) in langs {
+            tables.extend((*lang as u32).to_le_bytes());
+            tables.extend(((entries_offset + data_entries.len()) as u32).to_le_bytes());
+
+            data_offset += data_offset.wrapping_neg() % 8;
+            data_entries.extend((virtual_address + data_offset as u32).to_le_bytes());
+            data_entries.extend((bytes.len() as u32).to_le_bytes());
+            data_entries.extend([0u8; 8]);
+            data.resize(data_offset - entries_offset - leaf_count * 16, 0);
+            data.extend_from_slice(bytes);
+            data_offset += bytes.len();
+        }
+    }
+
+    let mut section = tables;
+    section.extend(strings);
+    section.resize(entries_offset, 0);
+    section.extend(data_entries);
+    section.extend(data);
+    section
+}
+
+/// Computes the optional header `CheckSum` as `imagehlp` does
+fn pe_checksum(image: &[u8], checksum_offset: usize) -> u32 {
+    let mut sum: u64 = 0;
+    for (index, chunk) in image.chunks(2).enumerate() {
+        if index * 2 == checksum_offset || index * 2 == checksum_offset + 2 {
+            continue;
+        }
+        let word = u16::from_le_bytes([chunk[0], chunk.get(1).copied().unwrap_or(0)]);
+        sum += word as u64;
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum = (sum & 0xFFFF) + (sum >> 16);
+    sum as u32 + image.len() as u32
+}
+
+fn utf16z(text: &str) -> Vec<u8> {
+    text.encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect()
+}
+
+fn pad4(buffer: &mut Vec<u8>) {
+    buffer.resize(buffer.len().next_multiple_of(4), 0);
+}
+
+fn align(value: u32, alignment: u32) -> u32 {
+    value.next_multiple_of(alignment.max(1))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn write_u16(data: &mut [u8], offset: usize, value: u16) {
+    data[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+    data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::settings::{
+        BundleBinary, BundleSettings, PackageSettings, SettingsBuilder,
+    };
+
+    const RT_MANIFEST: u16 = 24;
+
+    /// A PE32+ image with a single `.text` section
+    fn minimal_pe() -> Vec<u8> {
+        let mut image = vec![0u8; 0x400];
+        image[..2].copy_from_slice(b"MZ");
+        write_u32(&mut image, 0x3C, 0x40);
+        image[0x40..0x44].copy_from_slice(b"PE\0\0");
+        write_u16(&mut image, 0x44, 0x8664);
+        write_u16(&mut image, 0x46, 1);
+        write_u16(&mut image, 0x54, 0xF0);
+        write_u16(&mut image, 0x56, 0x22);
+
+        let optional = 0x58;
+        write_u16(&mut image, optional, 0x20B);
+        write_u32(&mut image, optional + 16, 0x1000);
+        image[optional + 24..optional + 32].copy_from_slice(&0x1_4000_0000u64.to_le_bytes());
+        write_u32(&mut image, optional + 32, 0x1000);
+        write_u32(&mut image, optional + 36, 0x200);
+        write_u16(&mut image, optional + 48, 6);
+        write_u32(&mut image, optional + 56, 0x2000);
+        write_u32(&mut image, optional + 60, 0x200);
+        write_u16(&mut image, optional + 68, 3);
+        write_u32(&mut image, optional + 108, 16);
+
+        let text = optional + 0xF0;
+        image[text..text + 5].copy_from_slice(b".text");
+        write_u32(&mut image, text + 8, 0x10);
+        write_u32(&mut image, text + 12, 0x1000);
+        write_u32(&mut image, text + 16, 0x200);
+        write_u32(&mut image, text + 20, 0x200);
+        write_u32(&mut image, text + 36, 0x6000_0020);
+        image[0x200] = 0xC3;
+        image
+    }
+
+    /// An .ico with two (fake) images
+    fn test_icon() -> Vec<u8> {
+        let images: [&[u8]; 2] = [b"sixteen", b"thirty-two"];
+        let mut ico = vec![0, 0, 1, 0, 2, 0];
+        let mut offset = 6 + 16 * images.len();
+        for (size, image) in [16u8, 32].into_iter().zip(images) {
+            ico.extend([size, size, 0, 0, 1, 0, 32, 0]);
+            ico.extend((image.len() as u32).to_le_bytes());
+            ico.extend((offset as u32).to_le_bytes());
+            offset += image.len();
+        }
+        for image in images {
+            ico.extend_from_slice(image);
+        }
+        ico
+    }
+
+    #[test]
+    fn test_version_parts() {
+        assert_eq!(version_parts("1.2.3"), [1, 2, 3, 0]);
+        assert_eq!(version_parts("1.2.3-beta.1"), [1, 2, 3, 0]);
+        assert_eq!(version_parts("4.5.6.7.8"), [4, 5, 6, 7]);
+    }
+
+    fn test_settings() -> Settings {
+        SettingsBuilder::new()
+            .project_out_directory("target/release")
+            .package_settings(PackageSettings {
+                product_name: "Kodegen".into(),
+                version: "1.2.3".into(),
+                ..Default::default()
+            })
+            .bundle_settings(BundleSettings {
+                copyright: Some("Copyright © 2026 Example".into()),
+                ..Default::default()
+            })
+            .binaries(vec![BundleBinary::new("kodegen".into(), true)])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_version_info() {
+        let settings = test_settings();
+        let info = version_info(&settings, "kodegen.exe");
+        assert_eq!(read_u16(&info, 0) as usize, info.len());
+        assert_eq!(read_u16(&info, 2), 52);
+        assert_eq!(&info[6..6 + 32], utf16z("VS_VERSION_INFO").as_slice());
+        // VS_FIXEDFILEINFO follows the padded key
+        assert_eq!(read_u32(&info, 40), 0xFEEF_04BD);
+        assert_eq!(read_u32(&info, 48), 1 << 16 | 2);
+        assert_eq!(read_u32(&info, 52), 3 << 16);
+
+        let contains = |text: &str| {
+            let needle = utf16z(text);
+            info.windows(needle.len()).any(|window| window == needle)
+        };
+        assert!(contains("040904B0"));
+        assert!(contains("ProductName") && contains("Kodegen"));
+        assert!(contains("Copyright © 2026 Example"));
+        assert!(contains("OriginalFilename") && contains("kodegen.exe"));
+    }
+
+    #[test]
+    fn test_embed_in_file() {
+        let mut image = minimal_pe();
+        let manifest = Resource {
+            kind: ResourceId::Id(RT_MANIFEST),
+            name: ResourceId::Id(1),
+            lang: LANG_EN_US,
+            data: b"<assembly/>".to_vec(),
+        };
+        let stale_icon = Resource {
+            kind: ResourceId::Id(RT_ICON),
+            name: ResourceId::Name("OLD".into()),
+            lang: 0,
+            data: b"old".to_vec(),
+        };
+        PeLayout::parse(&image)
+            .unwrap()
+            .append_resource_section(&mut image, &[manifest.clone(), stale_icon])
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kodegen.exe");
+        std::fs::write(&path, &image).unwrap();
+        let version = version_info(&test_settings(), "kodegen.exe");
+        embed_in_file(&path, &version, Some(&test_icon())).unwrap();
+
+        let image = std::fs::read(&path).unwrap();
+        embed_in_file(&path, &version, Some(&test_icon())).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), image);
+
+        let pe = goblin::pe::PE::parse(&image).unwrap();
+        assert_eq!(pe.sections.len(), 3);
+        assert_eq!(pe.sections[2].virtual_address, 0x3000);
+
+        let resources = PeLayout::parse(&image)
+            .unwrap()
+            .read_resources(&image)
+            .unwrap();
+        let find = |kind: u16, name: u16| {
+            resources
+                .iter()
+                .find(|r| r.kind == ResourceId::Id(kind) && r.name == ResourceId::Id(name))
+                .map(|r| r.data.as_slice())
+        };
+        assert_eq!(resources.len(), 5);
+        assert_eq!(find(RT_MANIFEST, 1), Some(&manifest.data[..]));
+        assert_eq!(find(RT_VERSION, 1), Some(&version[..]));
+        assert_eq!(find(RT_ICON, 1), Some(&b"sixteen"[..]));
+        assert_eq!(find(RT_ICON, 2), Some(&b"thirty-two"[..]));
+
+        let group = find(RT_GROUP_ICON, 1).unwrap();
+        assert_eq!(group.len(), 6 + 2 * 14);
+        assert_eq!(read_u16(group, 4), 2);
+        assert_eq!(group[6], 16);
+        assert_eq!(read_u16(group, 6 + 12), 1);
+        assert_eq!(read_u16(group, 6 + 14 + 12), 2);
+    }
+}
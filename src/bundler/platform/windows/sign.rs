@@ -24,8 +24,15 @@ use std::path::Path;
 ///
 /// # Example
 /// ```no_run
+/// # use kodegen_bundler_release::bundler::{Result, Settings};
+/// # use kodegen_bundler_release::bundler::platform::windows::sign::sign_file;
+/// # use std::path::Path;
+/// # async fn example(settings: Settings) -> Result<()> {
 /// sign_file(Path::new("MyApp_1.0.0_x64.msi"), &settings).await?;
+/// # Ok(())
+/// # }
 /// ```
+#[tracing::instrument(name = "sign", skip_all, fields(path = %binary_path.display()))]
 pub async fn sign_file(binary_path: &Path, settings: &Settings) -> Result<()> {
     let windows = &settings.bundle_settings().windows;
 
@@ -33,12 +40,12 @@ pub async fn sign_file(binary_path: &Path, settings: &Settings) -> Result<()> {
     let cert_path = match &windows.cert_path {
         Some(path) => path,
         None => {
-            log::info!("No certificate configured (cert_path), skipping Windows signing");
+            tracing::info!("No certificate configured (cert_path), skipping Windows signing");
             return Ok(());
         }
     };
 
-    log::info!("Signing {} with Authenticode", binary_path.display());
+    tracing::info!("Signing {} with Authenticode", binary_path.display());
 
     // Build SignConfig from WindowsSettings
     let sign_config = kodegen_bundler_sign::windows::SignConfig {
@@ -67,7 +74,7 @@ pub async fn sign_file(binary_path: &Path, settings: &Settings) -> Result<()> {
             crate::bundler::Error::GenericError(format!("Hash generation failed: {}", e))
         })?;
 
-    log::info!(
+    tracing::info!(
         "✓ Successfully signed {} (SHA-256: {})",
         binary_path.display(),
         &hash[..16]
@@ -78,7 +85,8 @@ pub async fn sign_file(binary_path: &Path, settings: &Settings) -> Result<()> {
 
 /// Check if Windows signing is configured
 ///
-/// Returns true if cert_path is set in WindowsSettings
+/// Returns true if cert_path is set in WindowsSettings and signing
+/// is not skipped (`--skip-sign`)
 pub fn should_sign(settings: &Settings) -> bool {
-    settings.bundle_settings().windows.cert_path.is_some()
+    !settings.skip_phases().sign && settings.bundle_settings().windows.cert_path.is_some()
 }
@@ -0,0 +1,164 @@
+//! Bundling progress reporting for library consumers.
+//!
+//! Register a callback with [`Bundler::on_progress`](super::Bundler::on_progress),
+//! or receive events from [`Bundler::progress_channel`](super::Bundler::progress_channel).
+//! Every package type reports [`BundleStage::Preparing`] first and
+//! [`BundleStage::Finished`] last; the stages in between depend on the
+//! format. Percentages are per package type and never decrease.
+
+use crate::bundler::PackageType;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Pipeline stage of a [`BundleEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BundleStage {
+    /// Resolving tools and staging directories
+    Preparing,
+    /// Generating package metadata, scripts and resources
+    Building,
+    /// Copying binaries, libraries and resources into the package layout
+    Copying,
+    /// Code signing
+    Signing,
+    /// Writing and compressing the package archive
+    Compressing,
+    /// The package is complete
+    Finished,
+}
+
+/// Progress event emitted while bundling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleEvent {
+    /// Package type being bundled
+    pub package_type: PackageType,
+    /// Current stage
+    pub stage: BundleStage,
+    /// Completion of this package type (0-100), when known
+    pub percent: Option<u8>,
+    /// Human-readable description of the step
+    pub message: String,
+    /// Artifact the step works on, when it concerns one (e.g. the file
+    /// about to be signed)
+    pub path: Option<PathBuf>,
+}
+
+type Callback = dyn Fn(&BundleEvent) + Send + Sync;
+
+/// Delivers [`BundleEvent`]s to the registered callback, if any
+#[derive(Clone, Default)]
+pub struct ProgressReporter {
+    callback: Option<Arc<Callback>>,
+}
+
+impl std::fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("callback", &self.callback.as_ref().map(|_| "<callback>"))
+            .finish()
+    }
+}
+
+impl ProgressReporter {
+    /// Creates a reporter calling `callback` for every event
+    pub fn new(callback: impl Fn(&BundleEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+        }
+    }
+
+    /// Reports `stage` of `package_type`
+    pub fn report(
+        &self,
+        package_type: PackageType,
+        stage: BundleStage,
+        percent: Option<u8>,
+        message: impl Into<String>,
+    ) {
+        self.emit(package_type, stage, percent, message.into(), None);
+    }
+
+    /// Reports `stage` of `package_type` working on the artifact at `path`
+    pub fn report_path(
+        &self,
+        package_type: PackageType,
+        stage: BundleStage,
+        percent: Option<u8>,
+        message: impl Into<String>,
+        path: &Path,
+    ) {
+        self.emit(package_type, stage, percent, message.into(), Some(path));
+    }
+
+    fn emit(
+        &self,
+        package_type: PackageType,
+        stage: BundleStage,
+        percent: Option<u8>,
+        message: String,
+        path: Option<&Path>,
+    ) {
+        if let Some(callback) = &self.callback {
+            callback(&BundleEvent {
+                package_type,
+                stage,
+                percent: percent.map(|p| p.min(100)),
+                message,
+                path: path.map(Path::to_path_buf),
+            });
+        }
+    }
+
+    /// Reports item `done` of `total` within the `from..to` percent range
+    ///
+    /// Used for loops over files, e.g. copying 3 of 10 binaries during the
+    /// 20-40% copy stage reports 26%.
+    pub fn report_items(
+        &self,
+        package_type: PackageType,
+        stage: BundleStage,
+        (from, to): (u8, u8),
+        (done, total): (usize, usize),
+        message: impl Into<String>,
+    ) {
+        let span = to.saturating_sub(from) as usize;
+        let percent = from as usize + span * done / total.max(1);
+        self.report(package_type, stage, Some(percent as u8), message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_report_items() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let reporter = ProgressReporter::new(move |event| {
+            sink.lock().unwrap().push(event.percent);
+        });
+
+        reporter.report_items(
+            PackageType::Deb,
+            BundleStage::Copying,
+            (20, 40),
+            (3, 10),
+            "copy",
+        );
+        reporter.report_items(
+            PackageType::Deb,
+            BundleStage::Copying,
+            (20, 40),
+            (0, 0),
+            "none",
+        );
+        reporter.report(PackageType::Deb, BundleStage::Finished, Some(150), "done");
+        assert_eq!(*events.lock().unwrap(), [Some(26), Some(20), Some(100)]);
+
+        // Without a callback reporting is a no-op
+        ProgressReporter::default().report(PackageType::Deb, BundleStage::Building, None, "x");
+    }
+}
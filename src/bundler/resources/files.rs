@@ -0,0 +1,142 @@
+//! Bundling of the `resources` glob patterns.
+//!
+//! Every file matched by `resources` is installed below one resource
+//! directory per package type, keeping its path relative to the project
+//! (`config/default.toml` stays `config/default.toml`):
+//!
+//! | Package | Resource directory |
+//! |---------|--------------------|
+//! | .app / .dmg | `Contents/Resources/` |
+//! | .deb / .rpm | `/usr/share/<product>/` |
+//! | AppImage | `usr/share/<product>/` inside the AppDir |
+//! | NSIS / portable ZIP | the install directory, next to the executable |
+//!
+//! Patterns are relative to the project directory. A pattern matching a
+//! directory includes its files recursively, and a pattern matching nothing
+//! is an error so typos fail the build instead of shipping without files.
+
+use crate::bundler::error::{Context, ErrorExt, Result};
+use crate::bundler::settings::Settings;
+use crate::bundler::utils::fs::copy_file;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+/// A resource file to bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceFile {
+    /// File on disk
+    pub source: PathBuf,
+
+    /// Path below the resource directory
+    pub target: PathBuf,
+}
+
+/// Resource directory of Linux packages, relative to the filesystem root
+pub fn linux_resource_dir(settings: &Settings) -> PathBuf {
+    Path::new("usr/share").join(settings.product_name())
+}
+
+/// Expands the `resources` patterns of `settings`, sorted by target path.
+pub fn resource_files(settings: &Settings) -> Result<Vec<ResourceFile>> {
+    let Some(patterns) = &settings.bundle_settings().resources else {
+        return Ok(Vec::new());
+    };
+    expand(patterns, &base_dir(settings)?)
+}
+
+/// Directory patterns are relative to: the project, or the working directory
+pub fn base_dir(settings: &Settings) -> Result<PathBuf> {
+    match settings.source_directory() {
+        Some(dir) => Ok(dir.to_path_buf()),
+        None => std::env::current_dir().fs_context("resolving resource base directory", Path::new(".")),
+    }
+}
+
+/// Copies the resources of `settings` below `dest_dir` and returns the
+/// copied files.
+pub async fn copy_resources(settings: &Settings, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
+    for file in resource_files(settings)? {
+        let dest = dest_dir.join(&file.target);
+        copy_file(&file.source, &dest)
+            .await
+            .with_context(|| format!("failed to copy resource {}", file.source.display()))?;
+        copied.push(dest);
+    }
+    if !copied.is_empty() {
+        tracing::info!("Bundled {} resource files into {}", copied.len(), dest_dir.display());
+    }
+    Ok(copied)
+}
+
+/// Expands `patterns` relative to `base`
+pub fn expand(patterns: &[String], base: &Path) -> Result<Vec<ResourceFile>> {
+    let mut files = BTreeMap::new();
+    for path in super::glob_paths(patterns, base, "resource")? {
+        if path.is_dir() {
+            for entry in walkdir::WalkDir::new(&path) {
+                let entry = entry?;
+                if entry.file_type().is_file() {
+                    files.insert(target_path(entry.path(), base), entry.into_path());
+                }
+            }
+        } else {
+            files.insert(target_path(&path, base), path);
+        }
+    }
+
+    Ok(files
+        .into_iter()
+        .map(|(target, source)| ResourceFile { source, target })
+        .collect())
+}
+
+/// Path of `path` below the resource directory: relative to `base`, with
+/// `..` and root components dropped for files outside of it
+fn target_path(path: &Path, base: &Path) -> PathBuf {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for file in ["config/default.toml", "config/themes/dark.toml", "templates/page.html", "README.md"] {
+            let path = base.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+
+        let patterns = ["config/**/*".to_string(), "templates".to_string(), "*.md".to_string()];
+        let targets: Vec<PathBuf> = expand(&patterns, base)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.target)
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                "README.md",
+                "config/default.toml",
+                "config/themes/dark.toml",
+                "templates/page.html"
+            ]
+            .map(PathBuf::from)
+        );
+
+        let error = expand(&["assets/*.bin".to_string()], base).unwrap_err();
+        assert!(error.to_string().contains("matched no files"));
+        assert_eq!(target_path(Path::new("/other/data.json"), base), PathBuf::from("other/data.json"));
+    }
+}
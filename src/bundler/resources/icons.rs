@@ -11,7 +11,7 @@
 //! |----------|---------------|----------------|
 //! | Linux | PNG (copied) | 32, 64, 128, 256, 512 |
 //! | macOS | ICNS | 16, 32, 64, 128, 256, 512, 1024 |
-//! | Windows | ICO | 16, 32, 48, 64, 128, 256 |
+//! | Windows | ICO | 16, 24, 32, 48, 64, 128, 256 |
 //!
 //! # Configuration
 //!
@@ -39,6 +39,7 @@
 //! 2. Nearest size (prefer larger over smaller for downscaling quality)
 //! 3. Square icons over non-square (penalized by 10000 in scoring)
 
+use crate::bundler::BundleSettings;
 use crate::bundler::error::Result;
 use std::path::{Path, PathBuf};
 
@@ -146,7 +147,7 @@ pub fn load_icons(icon_paths: &[PathBuf]) -> Result<Vec<IconInfo>> {
 
     for path in icon_paths {
         if !path.exists() {
-            log::warn!("Icon path does not exist: {}", path.display());
+            tracing::warn!("Icon path does not exist: {}", path.display());
             continue;
         }
 
@@ -163,7 +164,7 @@ pub fn load_icons(icon_paths: &[PathBuf]) -> Result<Vec<IconInfo>> {
             height: img.height(),
         });
 
-        log::debug!(
+        tracing::debug!(
             "Loaded icon: {}x{} from {}",
             img.width(),
             img.height(),
@@ -178,6 +179,123 @@ pub fn load_icons(icon_paths: &[PathBuf]) -> Result<Vec<IconInfo>> {
     Ok(icons)
 }
 
+/// Pixel sizes stored in a generated macOS `.icns`.
+pub const ICNS_SIZES: [u32; 7] = [16, 32, 64, 128, 256, 512, 1024];
+
+/// Pixel sizes stored in a generated Windows `.ico`.
+pub const ICO_SIZES: [u32; 7] = [16, 24, 32, 48, 64, 128, 256];
+
+/// Returns the target sizes with no exactly matching square icon.
+///
+/// Those sizes are still produced, but by scaling the nearest icon chosen by
+/// [`find_icon_for_size`], which blurs small sizes and pixelates large ones.
+///
+/// # Arguments
+///
+/// * `icons` - Available source icons
+/// * `sizes` - Target sizes (square)
+pub fn missing_sizes(icons: &[IconInfo], sizes: &[u32]) -> Vec<u32> {
+    sizes
+        .iter()
+        .copied()
+        .filter(|&size| !icons.iter().any(|icon| icon.is_square() && icon.width == size))
+        .collect()
+}
+
+/// Checks every configured icon before bundling starts.
+///
+/// Icons in `icon` must exist, be PNG or SVG, be square and match the size
+/// their file name declares; `icns` and `ico` must exist and have the right
+/// extension. All problems are reported together, so broken icons fail the
+/// run instead of ending up in the packages.
+///
+/// # Errors
+///
+/// Returns `GenericError` listing each problem, e.g.
+/// `icon_128x128.png is 120x120, expected 128x128`.
+pub fn validate_icons(settings: &BundleSettings) -> Result<()> {
+    let mut problems = Vec::new();
+    for path in settings.icon.iter().flatten() {
+        if let Some(problem) = icon_problem(path) {
+            problems.push(format!("{} {}", path.display(), problem));
+        }
+    }
+    for (path, extension) in [(&settings.icns, "icns"), (&settings.ico, "ico")] {
+        if let Some(path) = path
+            && let Some(problem) = file_problem(path, &[extension])
+        {
+            problems.push(format!("{} {}", path.display(), problem));
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(crate::bundler::Error::GenericError(format!(
+        "invalid icons:\n  {}\nRe-export each PNG as a square image at the size its name declares, or provide a single icon.svg instead",
+        problems.join("\n  ")
+    )))
+}
+
+/// Describes what is wrong with an entry of the `icon` list, if anything.
+///
+/// The message follows the path, e.g. `is 120x120, expected 128x128`.
+pub fn icon_problem(path: &Path) -> Option<String> {
+    if let Some(problem) = file_problem(path, &["png", "svg"]) {
+        return Some(problem);
+    }
+    if super::svg::is_svg(path) {
+        return super::svg::load(path).err().map(|e| e.to_string());
+    }
+
+    let (width, height) = match image::image_dimensions(path) {
+        Ok(dimensions) => dimensions,
+        Err(e) => return Some(format!("is not a readable PNG: {}", e)),
+    };
+    if width != height {
+        return Some(format!("is {}x{}; icons must be square", width, height));
+    }
+    match named_icon_size(path) {
+        Some(expected) if expected != width => Some(format!(
+            "is {}x{}, expected {}x{}",
+            width, height, expected, expected
+        )),
+        _ => None,
+    }
+}
+
+/// Reports a missing file or one without any of `extensions`
+fn file_problem(path: &Path, extensions: &[&str]) -> Option<String> {
+    if !path.exists() {
+        return Some("does not exist".to_string());
+    }
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if extensions.contains(&extension.as_str()) {
+        return None;
+    }
+    let expected: Vec<String> = extensions.iter().map(|e| format!(".{}", e)).collect();
+    Some(format!(
+        "has an unsupported format; expected {}",
+        expected.join(" or ")
+    ))
+}
+
+/// Pixel size an icon's file name declares (`icon_32x32.png` is 32,
+/// `icon_32x32@2x.png` is 64)
+pub fn named_icon_size(path: &Path) -> Option<u32> {
+    let stem = path.file_stem()?.to_str()?;
+    let (stem, scale) = match stem.strip_suffix("@2x") {
+        Some(stem) => (stem, 2),
+        None => (stem, 1),
+    };
+    let (width, height) = stem.rsplit('_').next()?.split_once('x')?;
+    let size: u32 = width.parse().ok()?;
+    (height.parse::<u32>().ok()? == size).then_some(size * scale)
+}
+
 /// Finds the best icon for a target size using heuristic selection.
 ///
 /// Uses a scoring algorithm to select the most appropriate icon:
@@ -294,3 +412,64 @@ pub fn load_and_resize(
 
     Ok(resized.to_rgba8())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon(width: u32, height: u32) -> IconInfo {
+        IconInfo {
+            path: PathBuf::from(format!("icon_{}x{}.png", width, height)),
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_missing_sizes() {
+        let icons = [icon(32, 32), icon(128, 128), icon(512, 256)];
+        assert_eq!(missing_sizes(&icons, &ICNS_SIZES), vec![16, 64, 256, 512, 1024]);
+        assert!(missing_sizes(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_named_icon_size() {
+        assert_eq!(named_icon_size(Path::new("assets/img/icon_32x32.png")), Some(32));
+        assert_eq!(named_icon_size(Path::new("icon_128x128@2x.png")), Some(256));
+        assert_eq!(named_icon_size(Path::new("icon.png")), None);
+        assert_eq!(named_icon_size(Path::new("icon_16x32.png")), None);
+    }
+
+    #[test]
+    fn test_validate_icons() {
+        let dir = tempfile::tempdir().unwrap();
+        let save = |name: &str, width: u32, height: u32| {
+            let path = dir.path().join(name);
+            image::RgbaImage::new(width, height).save(&path).unwrap();
+            path
+        };
+        let good = save("icon_32x32.png", 32, 32);
+        let mislabeled = save("icon_128x128.png", 120, 120);
+        let stretched = save("icon.png", 64, 32);
+        let jpeg = dir.path().join("icon.jpg");
+        std::fs::write(&jpeg, b"").unwrap();
+
+        assert_eq!(icon_problem(&good), None);
+        assert_eq!(icon_problem(&mislabeled).unwrap(), "is 120x120, expected 128x128");
+        assert_eq!(icon_problem(&stretched).unwrap(), "is 64x32; icons must be square");
+        assert_eq!(
+            icon_problem(&jpeg).unwrap(),
+            "has an unsupported format; expected .png or .svg"
+        );
+        assert_eq!(icon_problem(&dir.path().join("gone.png")).unwrap(), "does not exist");
+
+        let settings = BundleSettings {
+            icon: Some(vec![good.clone(), mislabeled]),
+            ico: Some(good),
+            ..Default::default()
+        };
+        let error = validate_icons(&settings).unwrap_err().to_string();
+        assert!(error.contains("icon_128x128.png is 120x120, expected 128x128"));
+        assert!(error.contains("icon_32x32.png has an unsupported format; expected .ico"));
+    }
+}
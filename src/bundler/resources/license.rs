@@ -0,0 +1,202 @@
+//! Installation of the package's license texts.
+//!
+//! The texts come from Cargo.toml `license-file`, or the LICENSE files next
+//! to it, and the SPDX expression from `license`:
+//!
+//! | Package | Location |
+//! |---------|----------|
+//! | .deb | `/usr/share/doc/<package>/copyright`, in the machine-readable format |
+//! | .rpm | `/usr/share/licenses/<package>/`, marked `%license` |
+//! | .app / .dmg | `Contents/Resources/` |
+//! | NSIS / portable ZIP | the install directory, next to the executable |
+//!
+//! License files keep their file names (`LICENSE-MIT`, `COPYING`).
+
+use super::files::ResourceFile;
+use crate::bundler::error::{Context, ErrorExt, Result};
+use crate::bundler::settings::Settings;
+use crate::bundler::utils::fs::copy_file;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Machine-readable debian/copyright format
+const COPYRIGHT_FORMAT: &str = "https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/";
+
+/// License files of `settings`, with their file names as targets.
+pub fn license_files(settings: &Settings) -> Result<Vec<ResourceFile>> {
+    settings
+        .license_files()
+        .iter()
+        .map(|source| {
+            let name = source.file_name().context("invalid license file path")?;
+            Ok(ResourceFile {
+                source: source.clone(),
+                target: PathBuf::from(name),
+            })
+        })
+        .collect()
+}
+
+/// Copies the license files into `dest_dir` and returns the copies.
+pub async fn copy_licenses(settings: &Settings, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
+    for file in license_files(settings)? {
+        let dest = dest_dir.join(&file.target);
+        copy_file(&file.source, &dest)
+            .await
+            .with_context(|| format!("failed to copy license {}", file.source.display()))?;
+        copied.push(dest);
+    }
+    Ok(copied)
+}
+
+/// Directory of RPM `%license` files of the package named `name`, relative
+/// to the filesystem root
+pub fn rpm_license_dir(name: &str) -> PathBuf {
+    Path::new("usr/share/licenses").join(name)
+}
+
+/// Renders `/usr/share/doc/<package>/copyright` in the machine-readable
+/// format; `None` without a license expression or license files.
+pub fn debian_copyright(settings: &Settings) -> Result<Option<String>> {
+    let files = settings.license_files();
+    let expression = settings.license();
+    if expression.is_none() && files.is_empty() {
+        return Ok(None);
+    }
+
+    let mut copyright = String::new();
+    let _ = writeln!(copyright, "Format: {}", COPYRIGHT_FORMAT);
+    let _ = writeln!(copyright, "Upstream-Name: {}", settings.product_name());
+    let authors = settings.authors().unwrap_or_default();
+    if !authors.is_empty() {
+        let _ = writeln!(copyright, "Upstream-Contact: {}", authors.join("\n "));
+    }
+    if let Some(homepage) = settings.homepage() {
+        let _ = writeln!(copyright, "Source: {}", homepage);
+    }
+
+    let holders = match &settings.bundle_settings().copyright {
+        Some(copyright) => copyright.clone(),
+        None if !authors.is_empty() => authors.join("\n "),
+        None => settings.product_name().to_string(),
+    };
+    let expression = expression.map(debian_expression);
+    let _ = write!(
+        copyright,
+        "\nFiles: *\nCopyright: {}\nLicense: {}\n",
+        holders,
+        expression.as_deref().unwrap_or("UNKNOWN")
+    );
+
+    // One stand-alone paragraph with the full text per license file
+    for file in files {
+        let text = std::fs::read_to_string(file).fs_context("reading license file", file)?;
+        let _ = writeln!(
+            copyright,
+            "\nLicense: {}",
+            license_name(file, expression.as_deref())
+        );
+        for line in text.trim_end().lines() {
+            match line.trim_end() {
+                "" => copyright.push_str(" .\n"),
+                line => {
+                    let _ = writeln!(copyright, " {}", line);
+                }
+            }
+        }
+    }
+    Ok(Some(copyright))
+}
+
+/// SPDX expression in debian/copyright syntax: lowercase `or`/`and`, and
+/// Cargo's deprecated `/` separator as `or`
+fn debian_expression(expression: &str) -> String {
+    expression
+        .replace(" OR ", " or ")
+        .replace(" AND ", " and ")
+        .replace('/', " or ")
+}
+
+/// Short license name of `file`: the license of `expression` its suffix
+/// names (`LICENSE-APACHE` → `Apache-2.0`), else the suffix, else the whole
+/// expression
+fn license_name(file: &Path, expression: Option<&str>) -> String {
+    let ids: Vec<&str> = expression
+        .map(|expression| {
+            expression
+                .split(|c: char| c.is_whitespace() || "()".contains(c))
+                .filter(|id| {
+                    !id.is_empty() && !["or", "and", "with"].contains(&id.to_lowercase().as_str())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match stem.split_once(['-', '_']).map(|(_, suffix)| suffix) {
+        Some(suffix) => ids
+            .iter()
+            .find(|id| id.to_lowercase().starts_with(&suffix.to_lowercase()))
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| suffix.to_string()),
+        None => expression.unwrap_or("UNKNOWN").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::{BundleBinary, PackageSettings, SettingsBuilder};
+
+    #[test]
+    fn test_debian_copyright() {
+        let dir = tempfile::tempdir().unwrap();
+        let mit = dir.path().join("LICENSE-MIT");
+        let apache = dir.path().join("LICENSE-APACHE");
+        std::fs::write(&mit, "MIT License\n\nPermission is hereby granted\n").unwrap();
+        std::fs::write(&apache, "Apache License\nVersion 2.0\n").unwrap();
+
+        let settings = SettingsBuilder::new()
+            .project_out_directory(dir.path())
+            .package_settings(PackageSettings {
+                product_name: "app".into(),
+                version: "1.0.0".into(),
+                authors: Some(vec!["Jo Dev <jo@example.com>".into()]),
+                license: Some("MIT OR Apache-2.0".into()),
+                license_files: vec![apache, mit],
+                ..Default::default()
+            })
+            .binaries(vec![BundleBinary::new("app".into(), true)])
+            .build()
+            .unwrap();
+
+        let copyright = debian_copyright(&settings).unwrap().unwrap();
+        assert_eq!(
+            copyright,
+            format!(
+                "Format: {}\n\
+                 Upstream-Name: app\n\
+                 Upstream-Contact: Jo Dev <jo@example.com>\n\
+                 \n\
+                 Files: *\n\
+                 Copyright: Jo Dev <jo@example.com>\n\
+                 License: MIT or Apache-2.0\n\
+                 \n\
+                 License: Apache-2.0\n \
+                 Apache License\n \
+                 Version 2.0\n\
+                 \n\
+                 License: MIT\n \
+                 MIT License\n \
+                 .\n \
+                 Permission is hereby granted\n",
+                COPYRIGHT_FORMAT
+            )
+        );
+        assert_eq!(license_name(Path::new("COPYING"), Some("GPL-3.0")), "GPL-3.0");
+    }
+}
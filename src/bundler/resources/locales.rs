@@ -0,0 +1,201 @@
+//! Bundling of translations from the `localizations` glob patterns.
+//!
+//! Two kinds of translation are recognized, in matched paths or inside
+//! matched directories:
+//!
+//! - `.lproj` directories, copied into the macOS app's `Contents/Resources/`
+//!   (only the .app and .dmg use them)
+//! - gettext `.mo` catalogs, installed in the layout `bindtextdomain`
+//!   expects, `<lang>/LC_MESSAGES/<domain>.mo`, below:
+//!
+//! | Package | Locale directory |
+//! |---------|------------------|
+//! | .deb / .rpm / AppImage | `usr/share/locale/` |
+//! | .app / .dmg | `Contents/Resources/locale/` |
+//! | NSIS / portable ZIP | `locales\` next to the executable |
+//!
+//! A catalog already inside `<lang>/LC_MESSAGES/` keeps its language and
+//! domain. Otherwise the file name is the language (`po/de.mo`) and the
+//! product name the domain.
+
+use super::files::{ResourceFile, base_dir};
+use crate::bundler::error::{Context, Result};
+use crate::bundler::settings::Settings;
+use crate::bundler::utils::fs::copy_file;
+use std::path::{Path, PathBuf};
+
+/// Locale directory of Linux packages, relative to the filesystem root
+pub const LINUX_LOCALE_DIR: &str = "usr/share/locale";
+
+/// Locale directory of Windows packages, relative to the install directory
+pub const WINDOWS_LOCALE_DIR: &str = "locales";
+
+/// Translations matched by the `localizations` patterns
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Localizations {
+    /// `.lproj` directories
+    pub lproj_dirs: Vec<PathBuf>,
+
+    /// gettext catalogs, with targets relative to the locale directory
+    pub catalogs: Vec<ResourceFile>,
+}
+
+impl Localizations {
+    /// Catalogs with targets below `locale_dir`
+    pub fn catalogs_in(&self, locale_dir: &Path) -> Vec<ResourceFile> {
+        self.catalogs
+            .iter()
+            .map(|catalog| ResourceFile {
+                source: catalog.source.clone(),
+                target: locale_dir.join(&catalog.target),
+            })
+            .collect()
+    }
+}
+
+/// Expands the `localizations` patterns of `settings`.
+pub fn localizations(settings: &Settings) -> Result<Localizations> {
+    let Some(patterns) = &settings.bundle_settings().localizations else {
+        return Ok(Localizations::default());
+    };
+    expand(patterns, &base_dir(settings)?, settings.product_name())
+}
+
+/// Copies the gettext catalogs below `locale_dir`.
+pub async fn copy_catalogs(settings: &Settings, locale_dir: &Path) -> Result<()> {
+    for catalog in localizations(settings)?.catalogs_in(locale_dir) {
+        copy_file(&catalog.source, &catalog.target)
+            .await
+            .with_context(|| format!("failed to copy translation {}", catalog.source.display()))?;
+    }
+    Ok(())
+}
+
+/// Copies the `.lproj` directories and gettext catalogs into a macOS app's
+/// `Resources` directory.
+#[cfg(target_os = "macos")]
+pub async fn copy_macos_localizations(settings: &Settings, resources_dir: &Path) -> Result<()> {
+    let localizations = localizations(settings)?;
+    for lproj in &localizations.lproj_dirs {
+        let name = lproj.file_name().context("invalid .lproj path")?;
+        crate::bundler::utils::fs::copy_dir(lproj, &resources_dir.join(name))
+            .await
+            .with_context(|| format!("failed to copy {}", lproj.display()))?;
+    }
+    for catalog in localizations.catalogs_in(&resources_dir.join("locale")) {
+        copy_file(&catalog.source, &catalog.target)
+            .await
+            .with_context(|| format!("failed to copy translation {}", catalog.source.display()))?;
+    }
+    Ok(())
+}
+
+/// Expands `patterns` relative to `base`; `domain` names catalogs that are
+/// not in a `LC_MESSAGES` directory
+pub fn expand(patterns: &[String], base: &Path, domain: &str) -> Result<Localizations> {
+    let mut localizations = Localizations::default();
+    for path in super::glob_paths(patterns, base, "localization")? {
+        if path.is_dir() && !has_extension(&path, "lproj") {
+            let mut walker = walkdir::WalkDir::new(&path).into_iter();
+            while let Some(entry) = walker.next() {
+                let entry = entry?;
+                if entry.file_type().is_dir() && has_extension(entry.path(), "lproj") {
+                    walker.skip_current_dir();
+                }
+                add(&mut localizations, entry.path(), domain);
+            }
+        } else if !add(&mut localizations, &path, domain) {
+            tracing::warn!(
+                "Ignoring {}: translations must be .lproj directories or .mo files",
+                path.display()
+            );
+        }
+    }
+
+    localizations.lproj_dirs.sort();
+    localizations.lproj_dirs.dedup();
+    localizations.catalogs.sort_by(|a, b| a.target.cmp(&b.target));
+    localizations.catalogs.dedup_by(|a, b| a.target == b.target);
+    Ok(localizations)
+}
+
+/// Records `path` if it is a translation; returns whether it was one
+fn add(localizations: &mut Localizations, path: &Path, domain: &str) -> bool {
+    if path.is_dir() && has_extension(path, "lproj") {
+        localizations.lproj_dirs.push(path.to_path_buf());
+        true
+    } else if path.is_file() && has_extension(path, "mo") {
+        localizations.catalogs.push(ResourceFile {
+            source: path.to_path_buf(),
+            target: catalog_target(path, domain),
+        });
+        true
+    } else {
+        false
+    }
+}
+
+/// `<lang>/LC_MESSAGES/<domain>.mo` for a catalog
+fn catalog_target(path: &Path, domain: &str) -> PathBuf {
+    let parent = path.parent();
+    if let Some(lang_dir) = parent
+        .filter(|dir| dir.file_name().is_some_and(|name| name == "LC_MESSAGES"))
+        .and_then(Path::parent)
+        .and_then(Path::file_name)
+        && let Some(file_name) = path.file_name()
+    {
+        return Path::new(lang_dir).join("LC_MESSAGES").join(file_name);
+    }
+    let lang = path.file_stem().unwrap_or_default();
+    Path::new(lang)
+        .join("LC_MESSAGES")
+        .join(format!("{}.mo", domain))
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        for file in [
+            "macos/de.lproj/Localizable.strings",
+            "macos/fr.lproj/Localizable.strings",
+            "po/de.mo",
+            "locale/pt_BR/LC_MESSAGES/helper.mo",
+            "po/README",
+        ] {
+            let path = base.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+
+        let patterns = ["macos/*.lproj".to_string(), "po/*".to_string(), "locale".to_string()];
+        let localizations = expand(&patterns, base, "app").unwrap();
+        assert_eq!(
+            localizations.lproj_dirs,
+            [base.join("macos/de.lproj"), base.join("macos/fr.lproj")]
+        );
+        let targets: Vec<_> = localizations
+            .catalogs_in(Path::new(LINUX_LOCALE_DIR))
+            .into_iter()
+            .map(|catalog| catalog.target)
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                PathBuf::from("usr/share/locale/de/LC_MESSAGES/app.mo"),
+                PathBuf::from("usr/share/locale/pt_BR/LC_MESSAGES/helper.mo"),
+            ]
+        );
+
+        assert!(expand(&["i18n/*.mo".to_string()], base, "app").is_err());
+    }
+}
@@ -1,3 +1,39 @@
 //! Resource and icon management for bundling.
 
+pub mod files;
 pub mod icons;
+pub mod license;
+pub mod locales;
+pub mod svg;
+
+use crate::bundler::error::{Error, Result};
+use std::path::{Path, PathBuf};
+
+/// Paths matched by `patterns` relative to `base`, pattern by pattern
+///
+/// A pattern matching nothing is an error. `what` names the patterns in
+/// error messages ("resource", "localization").
+pub(crate) fn glob_paths(patterns: &[String], base: &Path, what: &str) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let full = base.join(pattern);
+        let matches = glob::glob(&full.to_string_lossy())
+            .map_err(|e| Error::GenericError(format!("invalid {} pattern {:?}: {}", what, pattern, e)))?;
+
+        let matched = paths.len();
+        for entry in matches {
+            paths.push(entry.map_err(|e| {
+                Error::GenericError(format!("reading {} {}: {}", what, e.path().display(), e))
+            })?);
+        }
+        if paths.len() == matched {
+            return Err(Error::GenericError(format!(
+                "{} pattern {:?} matched no files in {}",
+                what,
+                pattern,
+                base.display()
+            )));
+        }
+    }
+    Ok(paths)
+}
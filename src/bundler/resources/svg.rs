@@ -0,0 +1,136 @@
+//! SVG icon rasterization.
+//!
+//! A single `icon.svg` can replace the hand-exported PNG sizes. Before any
+//! package is built, every `.svg` in the icon list is rendered with resvg
+//! into PNGs at each size the platform formats need:
+//!
+//! | Size | Used by |
+//! |------|---------|
+//! | 16, 32, 64, 128, 256, 512, 1024 | macOS ICNS |
+//! | 16, 24, 32, 48, 64, 128, 256 | Windows ICO |
+//! | 32, 64, 128, 256, 512 | Linux hicolor theme |
+//!
+//! The PNGs are written to `bundle/icons/` in the output directory and take
+//! the SVG's place in the icon list, so the ICNS, ICO and Linux icon code
+//! works on them unchanged. Non-square drawings are centered.
+
+use super::icons::{ICNS_SIZES, ICO_SIZES};
+use crate::bundler::error::{Error, ErrorExt, Result};
+use crate::bundler::settings::Settings;
+use resvg::{tiny_skia, usvg};
+use std::path::{Path, PathBuf};
+
+/// Returns whether `path` is an SVG icon.
+pub fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"))
+}
+
+/// Pixel sizes every SVG icon is rendered at, ascending.
+pub fn raster_sizes() -> Vec<u32> {
+    let mut sizes: Vec<u32> = ICNS_SIZES.iter().chain(&ICO_SIZES).copied().collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
+/// Replaces the SVG icons of `settings` with rendered PNGs.
+///
+/// Does nothing when no SVG icon is configured.
+pub async fn rasterize_icons(settings: &mut Settings) -> Result<()> {
+    let Some(icons) = settings.bundle_settings().icon.clone() else {
+        return Ok(());
+    };
+    if !icons.iter().any(|icon| is_svg(icon)) {
+        return Ok(());
+    }
+
+    let output_dir = settings.project_out_directory().join("bundle/icons");
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .fs_context("creating icon output directory", &output_dir)?;
+
+    let mut rasterized = Vec::new();
+    for icon in icons {
+        if !is_svg(&icon) {
+            rasterized.push(icon);
+            continue;
+        }
+        let dir = output_dir.clone();
+        let pngs = tokio::task::spawn_blocking(move || rasterize(&icon, &dir))
+            .await
+            .map_err(|e| Error::GenericError(format!("SVG rasterization task failed: {}", e)))??;
+        rasterized.extend(pngs);
+    }
+    settings.set_icons(rasterized);
+    Ok(())
+}
+
+/// Parses an SVG file.
+pub fn load(svg: &Path) -> Result<usvg::Tree> {
+    let data = std::fs::read(svg).fs_context("reading SVG icon", svg)?;
+    usvg::Tree::from_data(&data, &usvg::Options::default())
+        .map_err(|e| Error::GenericError(format!("parsing SVG icon {}: {}", svg.display(), e)))
+}
+
+/// Renders `svg` to `<stem>_<size>x<size>.png` in `output_dir` at every
+/// raster size and returns the written files.
+fn rasterize(svg: &Path, output_dir: &Path) -> Result<Vec<PathBuf>> {
+    let tree = load(svg)?;
+    let stem = svg
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "icon".to_string());
+    let (width, height) = (tree.size().width(), tree.size().height());
+
+    let mut pngs = Vec::new();
+    for size in raster_sizes() {
+        let mut pixmap = tiny_skia::Pixmap::new(size, size)
+            .ok_or_else(|| Error::GenericError(format!("allocating {}x{} icon", size, size)))?;
+        let scale = size as f32 / width.max(height);
+        let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+            (size as f32 - width * scale) / 2.0,
+            (size as f32 - height * scale) / 2.0,
+        );
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+        let png = output_dir.join(format!("{}_{}x{}.png", stem, size, size));
+        pixmap
+            .save_png(&png)
+            .map_err(|e| Error::GenericError(format!("writing {}: {}", png.display(), e)))?;
+        pngs.push(png);
+    }
+
+    tracing::info!(
+        "Rasterized {} into {} PNG icons",
+        svg.display(),
+        pngs.len()
+    );
+    Ok(pngs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize() {
+        let dir = tempfile::tempdir().unwrap();
+        let svg = dir.path().join("icon.svg");
+        std::fs::write(
+            &svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="20"><rect width="10" height="20" fill="#f00"/></svg>"##,
+        )
+        .unwrap();
+
+        let pngs = rasterize(&svg, dir.path()).unwrap();
+        assert_eq!(pngs.len(), raster_sizes().len());
+        assert_eq!(raster_sizes(), vec![16, 24, 32, 48, 64, 128, 256, 512, 1024]);
+
+        let png = image::open(dir.path().join("icon_64x64.png")).unwrap().to_rgba8();
+        assert_eq!(png.dimensions(), (64, 64));
+        // Centered: transparent left margin, red middle
+        assert_eq!(png.get_pixel(2, 32)[3], 0);
+        assert_eq!(png.get_pixel(32, 32).0, [255, 0, 0, 255]);
+    }
+}
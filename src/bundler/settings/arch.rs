@@ -9,7 +9,7 @@
 ///
 /// - ✅ Linux: All architectures supported
 /// - ✅ macOS: X86_64, AArch64, Universal
-/// - ✅ Windows: X86_64, X86
+/// - ✅ Windows: X86_64, X86, AArch64
 ///
 /// # Examples
 ///
@@ -0,0 +1,208 @@
+//! File association and URL scheme settings.
+//!
+//! Declared once in `[package.metadata.bundle]` and rendered into each
+//! platform's registration format (Info.plist on macOS, the desktop entry's
+//! `MimeType` on Linux, registry ProgIDs written by the NSIS installer on
+//! Windows).
+
+use std::path::PathBuf;
+
+/// A document type the application can open.
+///
+/// # Configuration
+///
+/// Add to `Cargo.toml`:
+///
+/// ```toml
+/// [[package.metadata.bundle.file_associations]]
+/// ext = ["kdg"]
+/// name = "Kodegen Project"
+/// role = "Editor"
+/// content_types = ["ai.kodegen.project"]
+/// exported = true
+/// mime_type = "application/x-kodegen-project"
+/// icon = "assets/project.icns"
+/// ```
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct FileAssociation {
+    /// File extensions without the leading dot.
+    ///
+    /// Example: `["kdg", "kdgx"]`
+    pub ext: Vec<String>,
+
+    /// Human-readable document type name (CFBundleTypeName).
+    ///
+    /// Default: None (first extension in upper case, e.g. "KDG Document")
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Description shown for exported types (UTTypeDescription).
+    ///
+    /// Default: None (uses `name`)
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// What the application does with the document (CFBundleTypeRole).
+    ///
+    /// Default: Editor
+    #[serde(default)]
+    pub role: BundleTypeRole,
+
+    /// How strongly the application claims the type (LSHandlerRank).
+    ///
+    /// Default: Default
+    #[serde(default)]
+    pub rank: HandlerRank,
+
+    /// Uniform Type Identifiers for the document (LSItemContentTypes).
+    ///
+    /// Example: `["ai.kodegen.project"]` or `["public.json"]`
+    ///
+    /// Default: Empty (matched by extension only)
+    #[serde(default)]
+    pub content_types: Vec<String>,
+
+    /// Declare `content_types` as owned by this application
+    /// (UTExportedTypeDeclarations).
+    ///
+    /// Required for custom UTIs, otherwise macOS does not know which
+    /// extensions they cover. Leave unset for system types like `public.json`.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub exported: bool,
+
+    /// MIME type of the document.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub mime_type: Option<String>,
+
+    /// Document icon (.icns on macOS, .ico on Windows), copied into the
+    /// bundle resources or installation directory.
+    ///
+    /// Default: None (the application icon is used)
+    #[serde(default)]
+    pub icon: Option<PathBuf>,
+}
+
+impl FileAssociation {
+    /// Document type name, falling back to one derived from the first extension
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let ext = self.ext.first().map(String::as_str).unwrap_or("Unknown");
+            format!("{} Document", ext.trim_start_matches('.').to_uppercase())
+        })
+    }
+
+    /// Extensions with any leading dot stripped
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.ext.iter().map(|e| e.trim_start_matches('.'))
+    }
+}
+
+/// A URL scheme the application handles (e.g. `kodegen://`).
+///
+/// # Configuration
+///
+/// ```toml
+/// [[package.metadata.bundle.url_schemes]]
+/// name = "Kodegen Link"
+/// schemes = ["kodegen"]
+/// ```
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct UrlScheme {
+    /// Scheme names without `://`.
+    pub schemes: Vec<String>,
+
+    /// Identifier for the URL type (CFBundleURLName).
+    ///
+    /// Default: None (bundle identifier)
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// What the application does with opened URLs (CFBundleTypeRole).
+    ///
+    /// Default: Editor
+    #[serde(default)]
+    pub role: BundleTypeRole,
+}
+
+/// Application role for a document or URL type (CFBundleTypeRole).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum BundleTypeRole {
+    /// Can read and modify the type
+    #[default]
+    Editor,
+    /// Can only read the type
+    Viewer,
+    /// Provides runtime services for the type
+    Shell,
+    /// Declares the type without handling it
+    None,
+}
+
+impl BundleTypeRole {
+    /// Value written to CFBundleTypeRole
+    pub fn as_plist_value(&self) -> &'static str {
+        match self {
+            BundleTypeRole::Editor => "Editor",
+            BundleTypeRole::Viewer => "Viewer",
+            BundleTypeRole::Shell => "Shell",
+            BundleTypeRole::None => "None",
+        }
+    }
+}
+
+/// Launch Services handler rank (LSHandlerRank).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum HandlerRank {
+    /// Normal claim; the user's choice or first-registered app wins
+    #[default]
+    Default,
+    /// This application created the type and is the preferred handler
+    Owner,
+    /// Secondary handler, never chosen as the default
+    Alternate,
+    /// Never used to open the type
+    None,
+}
+
+impl HandlerRank {
+    /// Value written to LSHandlerRank
+    pub fn as_plist_value(&self) -> &'static str {
+        match self {
+            HandlerRank::Default => "Default",
+            HandlerRank::Owner => "Owner",
+            HandlerRank::Alternate => "Alternate",
+            HandlerRank::None => "None",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_associations() {
+        let association: FileAssociation = toml::from_str(
+            r#"
+            ext = [".kdg"]
+            role = "Viewer"
+            rank = "Owner"
+            content_types = ["ai.kodegen.project"]
+            exported = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(association.role, BundleTypeRole::Viewer);
+        assert_eq!(association.rank, HandlerRank::Owner);
+        assert_eq!(association.extensions().collect::<Vec<_>>(), vec!["kdg"]);
+        assert_eq!(association.display_name(), "KDG Document");
+
+        let scheme: UrlScheme = toml::from_str(r#"schemes = ["kodegen"]"#).unwrap();
+        assert_eq!(scheme.role, BundleTypeRole::Editor);
+        assert!(scheme.name.is_none());
+    }
+}
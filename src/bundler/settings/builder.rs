@@ -1,6 +1,6 @@
 //! Builder for constructing Settings.
 
-use super::{BundleBinary, BundleSettings, PackageSettings, Settings};
+use super::{BundleBinary, BundleSettings, LintMode, PackageSettings, Settings, SkipPhases};
 use std::path::{Path, PathBuf};
 
 /// Builder for constructing [`Settings`].
@@ -41,6 +41,9 @@ pub struct SettingsBuilder {
     package_types: Option<Vec<crate::bundler::platform::PackageType>>,
     binaries: Vec<BundleBinary>,
     target: Option<String>,
+    skip_phases: SkipPhases,
+    lint: LintMode,
+    source_directory: Option<PathBuf>,
 }
 
 impl SettingsBuilder {
@@ -108,6 +111,34 @@ impl SettingsBuilder {
         self
     }
 
+    /// Sets pipeline phases to bypass.
+    ///
+    /// Default: No phases skipped
+    pub fn skip_phases(mut self, skip_phases: SkipPhases) -> Self {
+        self.skip_phases = skip_phases;
+        self
+    }
+
+    /// Sets the project source directory (the directory holding Cargo.toml).
+    ///
+    /// Needed by bundlers that package sources, such as SRPM generation, and
+    /// as the base of relative `resources` and `external_bin` paths (the
+    /// working directory otherwise).
+    ///
+    /// Default: None
+    pub fn source_directory<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.source_directory = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets post-bundle package validation.
+    ///
+    /// Default: [`LintMode::Off`]
+    pub fn lint(mut self, lint: LintMode) -> Self {
+        self.lint = lint;
+        self
+    }
+
     /// Builds the settings.
     ///
     /// # Errors
@@ -115,13 +146,27 @@ impl SettingsBuilder {
     /// Returns an error if required fields are missing:
     /// - `project_out_directory`
     /// - `package_settings`
+    ///
+    /// or if an `external_bin` sidecar has no file for the target.
     pub fn build(self) -> crate::bundler::Result<Settings> {
-        use crate::bundler::error::Context;
+        use crate::bundler::error::{Context, ErrorExt};
 
         let target = self.target.unwrap_or_else(|| {
             std::env::var("TARGET").unwrap_or_else(|_| std::env::consts::ARCH.to_string())
         });
 
+        let mut binaries = self.binaries;
+        if let Some(external_bin) = &self.bundle_settings.external_bin {
+            let base = match &self.source_directory {
+                Some(dir) => dir.clone(),
+                None => std::env::current_dir()
+                    .fs_context("resolving external_bin base directory", Path::new("."))?,
+            };
+            for entry in external_bin {
+                binaries.push(resolve_sidecar(entry, &base, &target)?);
+            }
+        }
+
         Ok(Settings::new(
             self.package_settings
                 .context("package_settings is required")?,
@@ -129,8 +174,74 @@ impl SettingsBuilder {
             self.project_out_directory
                 .context("project_out_directory is required")?,
             self.package_types,
-            self.binaries,
+            binaries,
             target,
+            self.skip_phases,
+            self.lint,
+            self.source_directory,
         ))
     }
 }
+
+/// Full target triple for `target`, which may be just an architecture when
+/// no `--target` was given; those are completed for the host OS.
+fn target_triple(target: &str) -> String {
+    if target.contains('-') {
+        return target.to_string();
+    }
+    match std::env::consts::OS {
+        "macos" => format!("{}-apple-darwin", target),
+        "windows" => format!("{}-pc-windows-msvc", target),
+        _ => format!("{}-unknown-linux-gnu", target),
+    }
+}
+
+/// Resolves an `external_bin` entry to `<entry>-<triple>[.exe]` below `base`.
+fn resolve_sidecar(entry: &str, base: &Path, target: &str) -> crate::bundler::Result<BundleBinary> {
+    let triple = target_triple(target);
+    let extension = if triple.contains("windows") { ".exe" } else { "" };
+    let path = base.join(format!("{}-{}{}", entry, triple, extension));
+    if !path.is_file() {
+        return Err(crate::bundler::Error::GenericError(format!(
+            "external_bin '{}' has no binary for {}: {} not found",
+            entry,
+            triple,
+            path.display()
+        )));
+    }
+
+    let name = Path::new(entry)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| {
+            crate::bundler::Error::GenericError(format!("invalid external_bin entry '{}'", entry))
+        })?;
+    Ok(BundleBinary::sidecar(name, path.to_string_lossy().into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("bin")).unwrap();
+        let linux = dir.path().join("bin/helper-aarch64-unknown-linux-gnu");
+        let windows = dir.path().join("bin/helper-x86_64-pc-windows-gnu.exe");
+        std::fs::write(&linux, b"elf").unwrap();
+        std::fs::write(&windows, b"pe").unwrap();
+
+        let sidecar = resolve_sidecar("bin/helper", dir.path(), "aarch64-unknown-linux-gnu").unwrap();
+        assert_eq!(sidecar.name(), "helper");
+        assert!(sidecar.is_sidecar() && !sidecar.main());
+        assert_eq!(sidecar.src_path().map(PathBuf::from), Some(linux));
+
+        let sidecar = resolve_sidecar("bin/helper", dir.path(), "x86_64-pc-windows-gnu").unwrap();
+        assert_eq!(sidecar.src_path().map(PathBuf::from), Some(windows));
+
+        let error = resolve_sidecar("bin/helper", dir.path(), "riscv64gc-unknown-linux-gnu").unwrap_err();
+        assert!(error.to_string().contains("has no binary for riscv64gc-unknown-linux-gnu"));
+        assert_eq!(target_triple("x86_64-apple-darwin"), "x86_64-apple-darwin");
+    }
+}
@@ -1,7 +1,8 @@
 //! Bundle configuration and binary definitions.
 
 use super::{
-    AppImageSettings, DebianSettings, DmgSettings, MacOsSettings, RpmSettings, WindowsSettings,
+    AppImageSettings, CompletionSettings, DebianSettings, DesktopEntrySettings, DmgSettings,
+    FileAssociation, MacOsSettings, RpmSettings, UrlScheme, WindowsSettings,
 };
 use std::path::PathBuf;
 
@@ -39,6 +40,52 @@ pub struct CategorySettings {
     pub windows: Option<String>,
 }
 
+/// Commands run around bundling stages.
+///
+/// Each hook is a shell command (`sh -c`, `cmd /C` on Windows) run in the
+/// project directory. Its output goes to stderr; a non-zero exit status fails
+/// the bundling. The environment describes the run:
+///
+/// | Variable | Hooks |
+/// |----------|-------|
+/// | `KODEGEN_HOOK` | all |
+/// | `KODEGEN_PRODUCT_NAME`, `KODEGEN_VERSION` | all |
+/// | `KODEGEN_BINARY_PATH` | `post_build` |
+/// | `KODEGEN_PACKAGE_TYPE` | `pre_bundle`, `post_bundle`, `post_sign` |
+/// | `KODEGEN_ARTIFACT_PATH` | `post_bundle`, `post_sign` |
+/// | `KODEGEN_ARTIFACT_PATHS` (newline-separated) | `post_bundle` |
+/// | `KODEGEN_ARTIFACT_SIZE`, `KODEGEN_ARTIFACT_SHA256` | `post_bundle` |
+///
+/// # Example
+///
+/// ```toml
+/// [package.metadata.bundle.hooks]
+/// pre_build = "scripts/generate-assets.sh"
+/// post_bundle = "scripts/report-artifact.sh \"$KODEGEN_ARTIFACT_PATH\""
+/// ```
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct HookSettings {
+    /// Before the binary is compiled.
+    #[serde(default)]
+    pub pre_build: Option<String>,
+
+    /// After the binary is compiled.
+    #[serde(default)]
+    pub post_build: Option<String>,
+
+    /// Before each package is created.
+    #[serde(default)]
+    pub pre_bundle: Option<String>,
+
+    /// After each package is created.
+    #[serde(default)]
+    pub post_bundle: Option<String>,
+
+    /// After an installer, executable or app bundle is signed.
+    #[serde(default)]
+    pub post_sign: Option<String>,
+}
+
 /// Bundle configuration for all platforms.
 ///
 /// Central configuration structure containing metadata and platform-specific settings.
@@ -107,14 +154,29 @@ pub struct BundleSettings {
     #[serde(default)]
     pub ico: Option<PathBuf>,
 
-    /// Resource glob patterns to bundle.
+    /// Resource glob patterns to bundle, relative to the project directory.
     ///
     /// Example: `["config/**/*", "templates/**/*"]`
     ///
+    /// Matched files keep their relative path below the package's resource
+    /// directory (see [`resources::files`](crate::bundler::resources::files)).
+    ///
     /// Default: None
     #[serde(default)]
     pub resources: Option<Vec<String>>,
 
+    /// Translation glob patterns to bundle, relative to the project directory.
+    ///
+    /// Example: `["macos/*.lproj", "po/*.mo"]`
+    ///
+    /// `.lproj` directories go into the macOS app, gettext `.mo` catalogs
+    /// into each package's locale directory (see
+    /// [`resources::locales`](crate::bundler::resources::locales)).
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub localizations: Option<Vec<String>>,
+
     /// Copyright notice string.
     ///
     /// Example: "Copyright © 2024 Example Inc."
@@ -154,15 +216,71 @@ pub struct BundleSettings {
 
     /// External binaries to bundle.
     ///
-    /// List of binary names (without path). Each must have a platform-specific
-    /// variant: `binary-{target}` or `binary-{target}.exe`
+    /// List of binary paths relative to the project directory. Each must have
+    /// a platform-specific variant: `binary-{target}` or `binary-{target}.exe`
     ///
-    /// Example: `["helper"]` expects `helper-x86_64-unknown-linux-gnu`, etc.
+    /// Example: `["bin/helper"]` expects `bin/helper-x86_64-unknown-linux-gnu`,
+    /// installed as `helper` next to the main executable.
     ///
     /// Default: None
     #[serde(default)]
     pub external_bin: Option<Vec<String>>,
 
+    /// Document types the application opens.
+    ///
+    /// See [`FileAssociation`] for details.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub file_associations: Vec<FileAssociation>,
+
+    /// URL schemes the application handles.
+    ///
+    /// See [`UrlScheme`] for details.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub url_schemes: Vec<UrlScheme>,
+
+    /// Man pages (roff files) installed by the deb and rpm packages.
+    ///
+    /// The section is taken from the file name (`kodegen.1` goes to
+    /// `/usr/share/man/man1/`). Pages are gzip-compressed unless they already
+    /// end in `.gz`.
+    ///
+    /// Example: `["docs/kodegen.1"]`
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub man_pages: Vec<PathBuf>,
+
+    /// Generate a section 1 man page from the main binary's `--help` output.
+    ///
+    /// Suited to clap-based CLIs whose help is already structured. Requires
+    /// the binary to run on the build host.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub generate_man_page: bool,
+
+    /// Shell completion scripts installed by the deb and rpm packages.
+    ///
+    /// See [`CompletionSettings`] for details.
+    #[serde(default)]
+    pub completions: CompletionSettings,
+
+    /// Commands run around bundling stages.
+    ///
+    /// See [`HookSettings`] for details.
+    #[serde(default)]
+    pub hooks: HookSettings,
+
+    /// Linux desktop entry settings.
+    ///
+    /// See [`DesktopEntrySettings`] for details.
+    #[serde(default)]
+    pub desktop: DesktopEntrySettings,
+
     /// Debian-specific settings.
     ///
     /// See [`DebianSettings`] for details.
@@ -218,6 +336,7 @@ pub struct BundleBinary {
     name: String,
     main: bool,
     src_path: Option<String>,
+    sidecar: bool,
 }
 
 impl BundleBinary {
@@ -232,6 +351,7 @@ impl BundleBinary {
             name,
             main,
             src_path: None,
+            sidecar: false,
         }
     }
 
@@ -247,6 +367,22 @@ impl BundleBinary {
             name,
             src_path,
             main,
+            sidecar: false,
+        }
+    }
+
+    /// Creates a prebuilt sidecar binary from `external_bin`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Installed binary name, without the target suffix
+    /// * `src_path` - Path of the target-specific file
+    pub fn sidecar(name: String, src_path: String) -> Self {
+        Self {
+            name,
+            main: false,
+            src_path: Some(src_path),
+            sidecar: true,
         }
     }
 
@@ -284,4 +420,9 @@ impl BundleBinary {
     pub fn src_path(&self) -> Option<&String> {
         self.src_path.as_ref()
     }
+
+    /// Returns whether this is a prebuilt sidecar from `external_bin`.
+    pub fn is_sidecar(&self) -> bool {
+        self.sidecar
+    }
 }
@@ -1,6 +1,7 @@
 //! Core Settings struct and implementations.
 
-use super::{Arch, BundleBinary, BundleSettings, PackageSettings};
+use super::{Arch, BundleBinary, BundleSettings, LintMode, PackageSettings, SkipPhases};
+use crate::bundler::progress::ProgressReporter;
 use std::path::{Path, PathBuf};
 
 /// Main settings for bundler operations.
@@ -57,6 +58,18 @@ pub struct Settings {
     ///
     /// Used for architecture detection.
     target: String,
+
+    /// Pipeline phases to bypass.
+    skip_phases: SkipPhases,
+
+    /// Post-bundle package validation.
+    lint: LintMode,
+
+    /// Project source directory, if known.
+    source_directory: Option<PathBuf>,
+
+    /// Receiver of bundling progress events.
+    progress: ProgressReporter,
 }
 
 impl Settings {
@@ -82,6 +95,11 @@ impl Settings {
         &self.project_out_directory
     }
 
+    /// Returns the target triple (or architecture) the binaries are built for.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
     /// Detects the binary architecture from the target triple.
     ///
     /// Automatically determines the target architecture based on the Rust
@@ -112,9 +130,13 @@ impl Settings {
         &self.binaries
     }
 
-    /// Returns the full path to a binary.
+    /// Returns the full path to a binary: its source path if set, otherwise
+    /// the binary in the output directory.
     pub fn binary_path(&self, binary: &BundleBinary) -> PathBuf {
-        self.project_out_directory.join(binary.name())
+        match binary.src_path() {
+            Some(path) => PathBuf::from(path),
+            None => self.project_out_directory.join(binary.name()),
+        }
     }
 
     /// Returns the bundle settings.
@@ -159,7 +181,48 @@ impl Settings {
         self.package.authors.as_deref()
     }
 
+    /// Returns the SPDX license expression of the package.
+    pub fn license(&self) -> Option<&str> {
+        self.package.license.as_deref()
+    }
+
+    /// Returns the license texts to install.
+    pub fn license_files(&self) -> &[PathBuf] {
+        &self.package.license_files
+    }
+
+    /// Returns the pipeline phases to bypass.
+    pub fn skip_phases(&self) -> &SkipPhases {
+        &self.skip_phases
+    }
+
+    /// Returns the post-bundle package validation mode.
+    pub fn lint(&self) -> LintMode {
+        self.lint
+    }
+
+    /// Returns the project source directory, if one was provided.
+    pub fn source_directory(&self) -> Option<&Path> {
+        self.source_directory.as_deref()
+    }
+
+    /// Returns the progress reporter platform bundlers report to.
+    pub fn progress(&self) -> &ProgressReporter {
+        &self.progress
+    }
+
+    /// Replaces the progress reporter (used by [`Bundler::on_progress`](crate::bundler::Bundler::on_progress)).
+    pub(crate) fn set_progress(&mut self, progress: ProgressReporter) {
+        self.progress = progress;
+    }
+
+    /// Replaces the icon list (used when SVG icons are rasterized).
+    pub(crate) fn set_icons(&mut self, icons: Vec<PathBuf>) {
+        self.bundle_settings.icon = Some(icons);
+    }
+
     /// Creates a new Settings instance (used by SettingsBuilder).
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         package: PackageSettings,
         bundle_settings: BundleSettings,
@@ -167,6 +230,9 @@ impl Settings {
         package_types: Option<Vec<crate::bundler::platform::PackageType>>,
         binaries: Vec<BundleBinary>,
         target: String,
+        skip_phases: SkipPhases,
+        lint: LintMode,
+        source_directory: Option<PathBuf>,
     ) -> Self {
         Self {
             package,
@@ -175,6 +241,10 @@ impl Settings {
             package_types,
             binaries,
             target,
+            skip_phases,
+            lint,
+            source_directory,
+            progress: ProgressReporter::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
 //! Linux platform-specific settings.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// Debian package (.deb) configuration.
@@ -52,6 +52,17 @@ pub struct DebianSettings {
     #[serde(default)]
     pub depends: Option<Vec<String>>,
 
+    /// Compute `Depends:` from the shared libraries the binaries link.
+    ///
+    /// Each library is mapped to its owning package with `dpkg -S` (glibc
+    /// gets the minimum version the binaries require) and merged after
+    /// `depends`; manual entries win for the same package. Requires building
+    /// on a Debian-based system.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub auto_depends: bool,
+
     /// Package recommendations (optional dependencies).
     ///
     /// These packages enhance functionality but aren't required.
@@ -112,12 +123,69 @@ pub struct DebianSettings {
     #[serde(default)]
     pub priority: Option<String>,
 
+    /// `Maintainer` field, as `Name <email>`.
+    ///
+    /// Default: None (the package authors)
+    #[serde(default)]
+    pub maintainer: Option<String>,
+
+    /// `Homepage` field.
+    ///
+    /// Default: None (the Cargo.toml `homepage`)
+    #[serde(default)]
+    pub homepage: Option<String>,
+
+    /// `Vcs-Git` field: the packaging repository's git URL.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub vcs_git: Option<String>,
+
+    /// `Bugs` field: the bug tracker, e.g. `https://github.com/org/app/issues`.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub bugs: Option<String>,
+
+    /// Extended description shown below the synopsis by `apt show`.
+    ///
+    /// Unlike `long_description`, lines keep their indentation, so lines
+    /// indented by two or more spaces are displayed verbatim (lists, code).
+    /// Blank lines separate paragraphs.
+    ///
+    /// Default: None (`long_description`, with indentation removed)
+    #[serde(default)]
+    pub extended_description: Option<String>,
+
+    /// Further control fields (field name -> value), written in name order.
+    ///
+    /// Fields the bundler generates, such as `Package` or `Depends`, have
+    /// their own settings and are rejected here.
+    ///
+    /// Example: `{ "X-Team" = "platform" }`
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub extra_fields: BTreeMap<String, String>,
+
     /// Path to Debian changelog file.
     ///
     /// Default: None (auto-generated)
     #[serde(default)]
     pub changelog: Option<PathBuf>,
 
+    /// Installed paths to mark as conffiles, which dpkg preserves when the
+    /// user has modified them.
+    ///
+    /// Every file installed under `/etc` is a conffile automatically, as with
+    /// debhelper; list other paths (or `/etc` files for clarity) here.
+    ///
+    /// Example: `["/etc/myapp/config.toml"]`
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub conffiles: Vec<PathBuf>,
+
     /// Pre-install script path (preinst).
     ///
     /// Executed before package installation.
@@ -149,6 +217,90 @@ pub struct DebianSettings {
     /// Default: None
     #[serde(default)]
     pub post_remove_script: Option<PathBuf>,
+
+    /// Bundle non-system shared libraries into `/usr/lib/<product>/`.
+    ///
+    /// The binaries get an RPATH of `$ORIGIN/../lib/<product>`. Prefer
+    /// `depends` when the distribution packages the library.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub bundle_shared_libs: bool,
+
+    /// Compression of `data.tar`: "gzip", "xz", "zstd" or "none".
+    ///
+    /// zstd needs dpkg 1.21.18 (Debian 12, Ubuntu 21.10) or newer to
+    /// install. Any other value is rejected when bundling.
+    ///
+    /// Default: None (gzip)
+    #[serde(default)]
+    pub compression: Option<String>,
+
+    /// Compression level: 0-9 for gzip and xz, 1-22 for zstd.
+    ///
+    /// Default: None (6 for gzip and xz, 19 for zstd)
+    #[serde(default)]
+    pub compression_level: Option<u32>,
+
+    /// Launchpad PPA that `--publish ppa` uploads a Debian source package to.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub ppa: Option<PpaSettings>,
+}
+
+/// Launchpad PPA publishing configuration.
+///
+/// With `--publish ppa`, after the .deb is built, verified and (with
+/// `--smoke-test`) tested, a Debian source package is generated from the
+/// committed sources (with cargo dependencies vendored, since Launchpad
+/// builders have no network access), signed by `dpkg-buildpackage` and
+/// uploaded with `dput` once per Ubuntu series.
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.deb.ppa]
+/// ppa = "kodegen/stable"
+/// series = ["jammy", "noble"]
+/// signing_key = "releases@kodegen.ai"
+/// ```
+///
+/// The signing key must be in the local GPG keyring and registered with the
+/// Launchpad account owning the PPA.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct PpaSettings {
+    /// PPA as `owner/name`.
+    pub ppa: String,
+
+    /// Ubuntu series to upload for, e.g. `["jammy", "noble"]`.
+    ///
+    /// Each upload gets the version `<version>-<revision>~<series>1`.
+    pub series: Vec<String>,
+
+    /// GPG key ID or user ID used to sign the uploads.
+    ///
+    /// Default: None (GPG's default key)
+    #[serde(default)]
+    pub signing_key: Option<String>,
+
+    /// `Maintainer` of the source package, as `Name <email>`.
+    ///
+    /// Must match a Launchpad identity. Default: `deb.maintainer`, else the
+    /// first package author
+    #[serde(default)]
+    pub maintainer: Option<String>,
+
+    /// Debian revision of the source package.
+    ///
+    /// Default: "1"
+    #[serde(default = "default_ppa_revision")]
+    pub revision: String,
+}
+
+/// Helper function for the PPA revision default
+fn default_ppa_revision() -> String {
+    "1".to_string()
 }
 
 /// RPM package (.rpm) configuration.
@@ -188,12 +340,48 @@ pub struct RpmSettings {
     #[serde(default)]
     pub depends: Option<Vec<String>>,
 
+    /// Generate `Requires:` from the ELF dependencies of packaged files.
+    ///
+    /// Emits the same sonames rpmbuild would (e.g. `libssl.so.3()(64bit)`,
+    /// `libc.so.6(GLIBC_2.34)(64bit)`) in addition to `depends`. Disable
+    /// when the generated requirements are wrong for the target distribution.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub auto_requires: bool,
+
     /// Package recommendations (weak dependencies).
     ///
+    /// Installed by default but may be removed (`Recommends:`).
+    ///
     /// Default: None
     #[serde(default)]
     pub recommends: Option<Vec<String>>,
 
+    /// Package suggestions (`Suggests:`).
+    ///
+    /// Weaker than `recommends`: shown to the user but not installed by default.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub suggests: Option<Vec<String>>,
+
+    /// Reverse recommendations (`Supplements:`).
+    ///
+    /// This package is pulled in when any of the listed packages is installed.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub supplements: Option<Vec<String>>,
+
+    /// Reverse suggestions (`Enhances:`).
+    ///
+    /// This package is suggested when any of the listed packages is installed.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub enhances: Option<Vec<String>>,
+
     /// Virtual packages this package provides.
     ///
     /// Default: None
@@ -229,13 +417,46 @@ pub struct RpmSettings {
     #[serde(default)]
     pub epoch: u32,
 
+    /// `Vendor` tag: the organization distributing the package.
+    ///
+    /// Default: None (the bundle `publisher`)
+    #[serde(default)]
+    pub vendor: Option<String>,
+
+    /// `Packager` tag, as `Name <email>`.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub packager: Option<String>,
+
+    /// `URL` tag.
+    ///
+    /// Default: None (the Cargo.toml `homepage`)
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// `License` tag, e.g. `"MIT OR Apache-2.0"`.
+    ///
+    /// Default: None (the Cargo.toml `license`, else "Unknown")
+    #[serde(default)]
+    pub license: Option<String>,
+
+    /// `Group` tag, e.g. `"Applications/System"`.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub group: Option<String>,
+
     /// Custom files to add to package (destination -> source).
     ///
     /// Default: Empty
     #[serde(default)]
     pub files: HashMap<PathBuf, PathBuf>,
 
-    /// Path to custom `.desktop` file template.
+    /// Path to a custom `.desktop` file.
+    ///
+    /// Installed as-is to `/usr/share/applications/<product>.desktop` in
+    /// place of the generated entry.
     ///
     /// Default: None (auto-generated)
     #[serde(default)]
@@ -265,11 +486,92 @@ pub struct RpmSettings {
     #[serde(default)]
     pub post_remove_script: Option<PathBuf>,
 
-    /// Compression algorithm: "gzip", "xz", "zstd", "bzip2".
+    /// Compression algorithm: "gzip", "xz", "zstd", "bzip2" or "none".
+    ///
+    /// Any other value is rejected when bundling.
     ///
-    /// Default: None (uses RPM default, typically "gzip")
+    /// Default: None (gzip)
     #[serde(default)]
     pub compression: Option<String>,
+
+    /// Bundle non-system shared libraries into `/usr/lib/<product>/`.
+    ///
+    /// The binaries get an RPATH of `$ORIGIN/../lib/<product>`. Prefer
+    /// `depends` when the distribution packages the library.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub bundle_shared_libs: bool,
+
+    /// Also build a source RPM (`.src.rpm`).
+    ///
+    /// The SRPM holds a `git archive` tarball of the committed sources and a
+    /// generated spec that builds with cargo, as Fedora and COPR build
+    /// systems expect. Requires `rpmbuild`.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub srpm: bool,
+
+    /// Submit the source RPM to Fedora COPR (builds it even if `srpm` is off).
+    ///
+    /// Default: None (no submission)
+    #[serde(default)]
+    pub copr: Option<CoprSettings>,
+}
+
+/// Fedora COPR build submission configuration.
+///
+/// The generated SRPM is uploaded to the project, which rebuilds it for
+/// each chroot (Fedora/EPEL release and architecture).
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.rpm.copr]
+/// project = "kodegen/kodegen"
+/// chroots = ["fedora-40-x86_64", "fedora-41-x86_64"]
+/// ```
+///
+/// # Credentials
+///
+/// Read from `COPR_LOGIN` (or `COPR_USERNAME` when it is unset) and
+/// `COPR_TOKEN`, falling back to the `copr-cli` config at `~/.config/copr` (from
+/// <https://copr.fedorainfracloud.org/api/>).
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct CoprSettings {
+    /// Project as `owner/name` (`@group/name` for group projects).
+    pub project: String,
+
+    /// Chroots to build for.
+    ///
+    /// Default: Empty (every chroot enabled in the project)
+    #[serde(default)]
+    pub chroots: Vec<String>,
+
+    /// Wait for the build to finish and fail the bundle if it fails.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub wait: bool,
+
+    /// Maximum time to wait for the build, in minutes.
+    ///
+    /// Default: 120
+    #[serde(default = "default_copr_timeout")]
+    pub timeout_minutes: u64,
+
+    /// COPR frontend URL.
+    ///
+    /// Default: None (`copr_url` from the config file, else
+    /// `https://copr.fedorainfracloud.org`)
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Helper function for the COPR wait timeout default
+fn default_copr_timeout() -> u64 {
+    120
 }
 
 /// Helper function for RPM release field default
@@ -277,16 +579,30 @@ fn default_rpm_release() -> String {
     "1".to_string()
 }
 
+/// Helper function for boolean fields that default to enabled
+fn default_true() -> bool {
+    true
+}
+
 impl Default for RpmSettings {
     fn default() -> Self {
         Self {
             depends: None,
+            auto_requires: true,
             recommends: None,
+            suggests: None,
+            supplements: None,
+            enhances: None,
             provides: None,
             conflicts: None,
             obsoletes: None,
             release: "1".to_string(),
             epoch: 0,
+            vendor: None,
+            packager: None,
+            url: None,
+            license: None,
+            group: None,
             files: HashMap::new(),
             desktop_template: None,
             pre_install_script: None,
@@ -294,6 +610,9 @@ impl Default for RpmSettings {
             pre_remove_script: None,
             post_remove_script: None,
             compression: None,
+            bundle_shared_libs: false,
+            srpm: false,
+            copr: None,
         }
     }
 }
@@ -317,7 +636,8 @@ impl Default for RpmSettings {
 /// AppImages are portable executables that:
 /// - Run on any Linux distribution
 /// - Don't require installation or root privileges
-/// - Bundle all dependencies internally
+/// - Bundle all dependencies internally (non-system shared libraries are
+///   copied into `usr/lib` unless dylib bundling is skipped)
 ///
 /// # See Also
 ///
@@ -333,17 +653,259 @@ pub struct AppImageSettings {
 
     /// Bundle GStreamer media framework.
     ///
-    /// Enable this if your application uses audio/video playback.
+    /// Enable this if your application uses audio/video playback. The host's
+    /// GStreamer plugins are deployed with linuxdeploy-plugin-gstreamer, so
+    /// this requires the linuxdeploy backend.
     ///
     /// Default: false
     #[serde(default)]
     pub bundle_media_framework: bool,
 
+    /// linuxdeploy-plugin-gstreamer commit to download instead of the
+    /// built-in pin.
+    ///
+    /// Other commits need the script's checksum in `tool_sha256`, as
+    /// `linuxdeploy-plugin-gstreamer-<commit>.sh`.
+    ///
+    /// Default: None (the pinned commit)
+    #[serde(default)]
+    pub gstreamer_plugin_commit: Option<String>,
+
     /// Bundle xdg-open binary for opening URLs/files.
     ///
     /// Enable this if your application needs to open web browsers or files.
+    /// The host's `xdg-open` (xdg-utils) is copied into `usr/bin`.
     ///
     /// Default: false
     #[serde(default)]
     pub bundle_xdg_open: bool,
+
+    /// Update information embedded for AppImageUpdate.
+    ///
+    /// One of the AppImage update formats, e.g.
+    /// `gh-releases-zsync|cyrup-ai|kodegen|latest|kodegen-*-{arch}.AppImage.zsync`.
+    /// `{arch}` is replaced with the AppImage architecture. A matching
+    /// `.zsync` file is written next to the AppImage; upload both to the
+    /// release.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub update_information: Option<String>,
+
+    /// Tool that produces the AppImage.
+    ///
+    /// Default: linuxdeploy
+    #[serde(default)]
+    pub backend: AppImageBackend,
+
+    /// linuxdeploy release tag to download instead of the built-in pin.
+    ///
+    /// Other releases need their checksum in `tool_sha256`.
+    ///
+    /// Example: `"1-alpha-20250213-2"`
+    ///
+    /// Default: None (the pinned release)
+    #[serde(default)]
+    pub linuxdeploy_version: Option<String>,
+
+    /// appimagetool release tag to download instead of the built-in pin.
+    ///
+    /// Other releases need their checksum in `tool_sha256`.
+    ///
+    /// Default: None (the pinned release)
+    #[serde(default)]
+    pub appimagetool_version: Option<String>,
+
+    /// Static type-2 runtime to embed (appimagetool `--runtime-file`).
+    ///
+    /// Setting this packs the AppDir with appimagetool even with the
+    /// linuxdeploy backend, which then only deploys the AppDir.
+    ///
+    /// Default: None (the runtime appimagetool downloads)
+    #[serde(default)]
+    pub runtime_file: Option<PathBuf>,
+
+    /// SHA-256 checksums of downloaded tools, by cached file name,
+    /// overriding the built-in pins.
+    ///
+    /// Needed for releases other than the pinned ones. A download, or a
+    /// cached file about to be extracted, that doesn't match is rejected,
+    /// and a tool without any checksum is never downloaded. Names are
+    /// `<tool>-<version>-<arch>.AppImage` and
+    /// `linuxdeploy-plugin-gstreamer-<commit>.sh`.
+    ///
+    /// Example:
+    /// ```toml
+    /// [package.metadata.bundle.appimage.tool_sha256]
+    /// "linuxdeploy-1-alpha-20250213-2-x86_64.AppImage" = "4c1f...e9a2"
+    /// ```
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub tool_sha256: HashMap<String, String>,
+}
+
+/// Tool used to build AppImages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppImageBackend {
+    /// linuxdeploy deploys the AppDir (ELF dependencies, AppRun) and packs it.
+    #[default]
+    Linuxdeploy,
+
+    /// appimagetool packs the AppDir directly; `AppRun` links to the main
+    /// binary. Suited to self-contained binaries.
+    Appimagetool,
+}
+
+/// Freedesktop.org desktop entry (`.desktop` file) configuration.
+///
+/// Shared by the AppImage, Debian and RPM bundlers. `MimeType` also
+/// includes the MIME types of `file_associations` and an
+/// `x-scheme-handler/<scheme>` entry for every `url_schemes` scheme.
+///
+/// # Configuration
+///
+/// Add to `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.bundle.desktop]
+/// generic_name = "Code Generator"
+/// keywords = ["code", "ai"]
+/// startup_wm_class = "kodegen"
+/// autostart = true
+/// autostart_args = "--minimized"
+///
+/// [[package.metadata.bundle.desktop.actions]]
+/// id = "new-window"
+/// name = "New Window"
+/// exec = "kodegen --new-window"
+/// ```
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct DesktopEntrySettings {
+    /// Generic name of the application (GenericName).
+    ///
+    /// Example: "Web Browser"
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub generic_name: Option<String>,
+
+    /// Search keywords (Keywords).
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub keywords: Vec<String>,
+
+    /// Additional MIME types the application opens (MimeType).
+    ///
+    /// Default: Empty (only those derived from file associations and URL schemes)
+    #[serde(default)]
+    pub mime_types: Vec<String>,
+
+    /// WM_CLASS of the application's windows (StartupWMClass).
+    ///
+    /// Lets docks match windows to the launcher.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub startup_wm_class: Option<String>,
+
+    /// Whether the application supports startup notification (StartupNotify).
+    ///
+    /// Default: None (not written)
+    #[serde(default)]
+    pub startup_notify: Option<bool>,
+
+    /// Run the application in a terminal (Terminal=true), for TUI apps.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub terminal: bool,
+
+    /// Additional launcher actions (Actions), e.g. "New Window".
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub actions: Vec<DesktopAction>,
+
+    /// Start the application at every login (deb and rpm).
+    ///
+    /// Installs an entry in `/etc/xdg/autostart/`. Its `TryExec` points at
+    /// the main binary, so sessions ignore an entry left behind by a removed
+    /// (not purged) deb; purging or erasing the package deletes it.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub autostart: bool,
+
+    /// Arguments the autostart entry passes to the main binary.
+    ///
+    /// Example: "--minimized"
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub autostart_args: Option<String>,
+}
+
+/// A desktop entry action shown in the launcher's context menu.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct DesktopAction {
+    /// Action identifier (`[Desktop Action <id>]`).
+    pub id: String,
+
+    /// Label shown in the menu.
+    pub name: String,
+
+    /// Command line to run.
+    pub exec: String,
+
+    /// Icon name for the action.
+    ///
+    /// Default: None (application icon)
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+/// Shell completion scripts installed by the deb and rpm packages.
+///
+/// Scripts are installed for the main binary to the distribution's vendor
+/// completion directories (e.g. `/usr/share/bash-completion/completions/`).
+///
+/// # Configuration
+///
+/// Add to `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.bundle.completions]
+/// bash = "completions/kodegen.bash"
+/// generate = true   # zsh and fish come from `kodegen --completions <shell>`
+/// ```
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct CompletionSettings {
+    /// Bash completion script.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub bash: Option<PathBuf>,
+
+    /// Zsh completion script (`#compdef` file).
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub zsh: Option<PathBuf>,
+
+    /// Fish completion script.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub fish: Option<PathBuf>,
+
+    /// Generate scripts for shells without a file by running the built main
+    /// binary with `--completions <shell>` (bash, zsh or fish) and capturing
+    /// stdout. Requires the binary to run on the build host.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub generate: bool,
 }
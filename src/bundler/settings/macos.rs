@@ -30,14 +30,31 @@ use std::path::PathBuf;
 /// - [`WindowsSettings`] - Windows installer configuration
 #[derive(Clone, Debug, Default, serde::Deserialize)]
 pub struct MacOsSettings {
-    /// System frameworks to bundle with the application.
+    /// Third-party frameworks and dylibs to bundle into `Contents/Frameworks`.
     ///
-    /// Example: `["WebKit.framework", "Security.framework"]`
+    /// Entries are paths to `.framework` directories or `.dylib` files, or
+    /// bare framework names looked up in `~/Library/Frameworks`,
+    /// `/Library/Frameworks` and `/Network/Library/Frameworks`. Bundled
+    /// frameworks get an `@rpath` install name and are re-signed.
+    ///
+    /// Example: `["vendor/Sparkle.framework", "Syphon"]`
     ///
     /// Default: None (no additional frameworks)
     #[serde(default)]
     pub frameworks: Option<Vec<String>>,
 
+    /// Extra directories searched for dylib dependencies that are not found
+    /// at their recorded path.
+    ///
+    /// Searched before the Homebrew (`/opt/homebrew/lib`, `/usr/local/lib`)
+    /// and MacPorts (`/opt/local/lib`) library directories.
+    ///
+    /// Example: `["/opt/vendor/lib"]`
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub dylib_search_paths: Vec<PathBuf>,
+
     /// Minimum macOS version required (LSMinimumSystemVersion).
     ///
     /// Example: "10.15", "11.0", "12.0"
@@ -64,6 +81,17 @@ pub struct MacOsSettings {
     #[serde(default)]
     pub entitlements: Option<PathBuf>,
 
+    /// Named entitlement presets used when no `entitlements` file is given.
+    ///
+    /// The bundler generates an entitlements.plist enabling every
+    /// entitlement of the listed presets.
+    ///
+    /// Example: `["network-client", "hardened-jit"]`
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub entitlement_presets: Vec<EntitlementPreset>,
+
     /// Custom files to include (destination -> source).
     ///
     /// Default: Empty
@@ -86,6 +114,202 @@ pub struct MacOsSettings {
     /// Default: false (stapling enabled)
     #[serde(default)]
     pub skip_stapling: bool,
+
+    /// Ad-hoc sign (`codesign -s -`) when no signing identity is available.
+    ///
+    /// Apple Silicon refuses to run unsigned binaries, so development builds
+    /// need at least an ad-hoc signature to launch locally. Ad-hoc signed
+    /// bundles cannot be notarized or distributed.
+    ///
+    /// Also enabled by the `--dev-sign` CLI flag.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub dev_sign: bool,
+
+    /// launchd jobs embedded in the .app for background services.
+    ///
+    /// Each job is written to `Contents/Library/LaunchAgents/<label>.plist`
+    /// (or `LaunchDaemons` for daemons), where the app registers it at
+    /// runtime with `SMAppService` (macOS 13+).
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub launch_agents: Vec<LaunchAgentSettings>,
+
+    /// Sparkle appcast generation for self-updating apps.
+    ///
+    /// See [`SparkleSettings`] for details.
+    ///
+    /// Default: None (no appcast)
+    #[serde(default)]
+    pub sparkle: Option<SparkleSettings>,
+}
+
+/// Sparkle update feed configuration.
+///
+/// When set, the DMG bundler writes an `appcast.xml` next to the DMG with an
+/// item for the new version, signed with Sparkle's EdDSA key.
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.macos.sparkle]
+/// download_url = "https://releases.example.com/{version}/{file}"
+/// release_notes_url = "https://example.com/changelog/{version}"
+/// ```
+///
+/// The private key is read from `SPARKLE_PRIVATE_KEY` (the base64 string
+/// printed by Sparkle's `generate_keys -x`).
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct SparkleSettings {
+    /// Download URL of the DMG.
+    ///
+    /// `{version}` and `{file}` are replaced with the package version and
+    /// the DMG file name.
+    pub download_url: String,
+
+    /// Release notes URL; supports the same placeholders as `download_url`.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub release_notes_url: Option<String>,
+
+    /// Minimum macOS version (sparkle:minimumSystemVersion).
+    ///
+    /// Default: None (uses `macos.minimum_system_version`)
+    #[serde(default)]
+    pub minimum_system_version: Option<String>,
+
+    /// Update channel (sparkle:channel), e.g. "beta".
+    ///
+    /// Default: None (default channel)
+    #[serde(default)]
+    pub channel: Option<String>,
+
+    /// Environment variable holding the EdDSA private key.
+    ///
+    /// Default: None (`SPARKLE_PRIVATE_KEY`)
+    #[serde(default)]
+    pub private_key_env: Option<String>,
+}
+
+/// Named set of code signing entitlements.
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.macos]
+/// entitlement_presets = ["network-client", "files-user-selected"]
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntitlementPreset {
+    /// Run inside the App Sandbox (required for the Mac App Store).
+    AppSandbox,
+    /// Outgoing network connections.
+    NetworkClient,
+    /// Incoming network connections.
+    NetworkServer,
+    /// JIT compilation under the hardened runtime (JavaScript engines, WASM).
+    HardenedJit,
+    /// Writable and executable memory without MAP_JIT (older JITs, FFI trampolines).
+    UnsignedExecutableMemory,
+    /// Load plug-ins and libraries signed by other teams.
+    DisableLibraryValidation,
+    /// Read and write files the user picks in open/save panels.
+    FilesUserSelected,
+    /// Read and write the Downloads folder.
+    FilesDownloads,
+    /// Camera access.
+    Camera,
+    /// Microphone access.
+    Microphone,
+    /// Send Apple Events to other apps (AppleScript automation).
+    AppleEvents,
+}
+
+impl EntitlementPreset {
+    /// Entitlement keys enabled by this preset
+    pub fn entitlements(&self) -> &'static [&'static str] {
+        match self {
+            EntitlementPreset::AppSandbox => &["com.apple.security.app-sandbox"],
+            EntitlementPreset::NetworkClient => &["com.apple.security.network.client"],
+            EntitlementPreset::NetworkServer => &["com.apple.security.network.server"],
+            EntitlementPreset::HardenedJit => &["com.apple.security.cs.allow-jit"],
+            EntitlementPreset::UnsignedExecutableMemory => {
+                &["com.apple.security.cs.allow-unsigned-executable-memory"]
+            }
+            EntitlementPreset::DisableLibraryValidation => {
+                &["com.apple.security.cs.disable-library-validation"]
+            }
+            EntitlementPreset::FilesUserSelected => {
+                &["com.apple.security.files.user-selected.read-write"]
+            }
+            EntitlementPreset::FilesDownloads => &["com.apple.security.files.downloads.read-write"],
+            EntitlementPreset::Camera => &["com.apple.security.device.camera"],
+            EntitlementPreset::Microphone => &["com.apple.security.device.audio-input"],
+            EntitlementPreset::AppleEvents => &["com.apple.security.automation.apple-events"],
+        }
+    }
+}
+
+/// A launchd job (LaunchAgent or LaunchDaemon) shipped inside the .app.
+///
+/// # Configuration
+///
+/// ```toml
+/// [[package.metadata.bundle.macos.launch_agents]]
+/// label = "ai.kodegen.daemon"
+/// program = "kodegend"
+/// arguments = ["--foreground"]
+/// run_at_load = true
+/// keep_alive = true
+/// ```
+///
+/// The app registers the job with
+/// `SMAppService.agent(plistName: "ai.kodegen.daemon.plist").register()`.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct LaunchAgentSettings {
+    /// launchd job label, also used as the plist file name.
+    pub label: String,
+
+    /// Bundled binary to run.
+    ///
+    /// Default: None (main binary)
+    #[serde(default)]
+    pub program: Option<String>,
+
+    /// Arguments passed after the program path.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub arguments: Vec<String>,
+
+    /// Start the job as soon as it is registered and at every login/boot.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub run_at_load: bool,
+
+    /// Restart the job whenever it exits.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub keep_alive: bool,
+
+    /// Install as a system-wide LaunchDaemon (runs as root, needs admin
+    /// approval) instead of a per-user LaunchAgent.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub daemon: bool,
+
+    /// Environment variables for the job.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
 }
 
 /// macOS DMG disk image configuration.
@@ -100,6 +324,13 @@ pub struct MacOsSettings {
 /// [package.metadata.bundle.dmg]
 /// background = "assets/dmg-background.png"
 /// window_size = [540, 380]
+/// license = "assets/LICENSE.rtf"
+/// volume_icon = "assets/volume.icns"
+/// app_position = [180, 170]
+/// applications_position = [480, 170]
+/// icon_size = 72
+/// format = "ULFO"
+/// filesystem = "APFS"
 /// ```
 ///
 /// # See Also
@@ -120,4 +351,125 @@ pub struct DmgSettings {
     /// Default: None (uses default size)
     #[serde(default)]
     pub window_size: Option<(u32, u32)>,
+
+    /// Path to a license agreement shown before the DMG mounts.
+    ///
+    /// Plain text (`.txt`) or RTF (`.rtf`). Finder displays the agreement
+    /// with Agree/Disagree buttons when the image is opened. Use RTF for
+    /// non-ASCII text.
+    ///
+    /// Default: None (no license agreement)
+    #[serde(default)]
+    pub license: Option<PathBuf>,
+
+    /// Path to an `.icns` icon shown for the mounted volume.
+    ///
+    /// Copied to `.VolumeIcon.icns` at the volume root, which is flagged
+    /// with the custom-icon Finder attribute so the mounted disk shows this
+    /// icon instead of the generic drive icon.
+    ///
+    /// Default: None (generic disk icon)
+    #[serde(default)]
+    pub volume_icon: Option<PathBuf>,
+
+    /// Position (x, y) of the .app icon in the DMG window, in pixels.
+    ///
+    /// Default: None (180, 170)
+    #[serde(default)]
+    pub app_position: Option<(u32, u32)>,
+
+    /// Position (x, y) of the Applications symlink in the DMG window, in pixels.
+    ///
+    /// Default: None (480, 170)
+    #[serde(default)]
+    pub applications_position: Option<(u32, u32)>,
+
+    /// Icon size in the DMG window, in pixels (16-512).
+    ///
+    /// Default: None (72)
+    #[serde(default)]
+    pub icon_size: Option<u16>,
+
+    /// Compressed image format of the final DMG.
+    ///
+    /// Default: UDZO
+    #[serde(default)]
+    pub format: DmgFormat,
+
+    /// Filesystem of the DMG volume.
+    ///
+    /// Default: HFS+
+    #[serde(default)]
+    pub filesystem: DmgFilesystem,
+}
+
+/// Compressed DMG image format.
+///
+/// # Comparison
+///
+/// | Format | Compression | Minimum macOS |
+/// |--------|-------------|---------------|
+/// | UDZO | zlib | any (default) |
+/// | ULFO | lzfse | 10.11 |
+/// | ULMO | lzma | 10.15 |
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.dmg]
+/// format = "ULFO"
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DmgFormat {
+    /// zlib compression - readable on every macOS version (default).
+    #[default]
+    Udzo,
+
+    /// lzfse compression - faster and smaller than zlib.
+    Ulfo,
+
+    /// lzma compression - smallest size, slowest to create.
+    Ulmo,
+}
+
+impl DmgFormat {
+    /// Format name as passed to `hdiutil -format`
+    pub fn as_hdiutil_format(&self) -> &'static str {
+        match self {
+            DmgFormat::Udzo => "UDZO",
+            DmgFormat::Ulfo => "ULFO",
+            DmgFormat::Ulmo => "ULMO",
+        }
+    }
+}
+
+/// DMG volume filesystem.
+///
+/// # Configuration
+///
+/// ```toml
+/// [package.metadata.bundle.dmg]
+/// filesystem = "APFS"  # or "HFS+"
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+pub enum DmgFilesystem {
+    /// HFS+ - mountable on every macOS version (default).
+    #[default]
+    #[serde(rename = "HFS+")]
+    HfsPlus,
+
+    /// APFS - requires macOS 10.13 or later to mount.
+    #[serde(rename = "APFS")]
+    Apfs,
+}
+
+impl DmgFilesystem {
+    /// Filesystem name as passed to `hdiutil -fs`
+    pub fn as_hdiutil_fs(&self) -> &'static str {
+        match self {
+            DmgFilesystem::HfsPlus => "HFS+",
+            DmgFilesystem::Apfs => "APFS",
+        }
+    }
 }
@@ -7,22 +7,34 @@
 #![allow(dead_code)] // Public API - Settings structs preserve all fields for external consumers
 
 mod arch;
+mod associations;
 mod builder;
 mod bundle;
 mod core;
 mod linux;
 mod macos;
 mod package;
+mod phases;
 mod windows;
 
 // Re-export all public types
 pub use arch::Arch;
+pub use associations::{BundleTypeRole, FileAssociation, HandlerRank, UrlScheme};
 pub use builder::SettingsBuilder;
-pub use bundle::{BundleBinary, BundleSettings};
+pub use bundle::{BundleBinary, BundleSettings, HookSettings};
 pub use core::Settings;
-pub use linux::{AppImageSettings, DebianSettings, RpmSettings};
+pub use linux::{
+    AppImageBackend, AppImageSettings, CompletionSettings, CoprSettings, DebianSettings,
+    DesktopEntrySettings, PpaSettings, RpmSettings,
+};
 pub use macos::{DmgSettings, MacOsSettings};
+// DMG, launchd, Sparkle and entitlement types are only consumed by the cfg-gated macOS bundlers
+#[cfg_attr(not(target_os = "macos"), allow(unused_imports))]
+pub use macos::{
+    DmgFilesystem, DmgFormat, EntitlementPreset, LaunchAgentSettings, SparkleSettings,
+};
 pub use package::PackageSettings;
+pub use phases::{LintMode, SkipPhases};
 // NSISInstallerMode and NsisCompression are unused on macOS (nsis module is cfg-gated)
 // but required on Linux for Windows bundling via Wine
 #[cfg_attr(target_os = "macos", allow(unused_imports))]
@@ -1,5 +1,7 @@
 //! Package metadata and configuration.
 
+use std::path::PathBuf;
+
 /// Package metadata and configuration.
 ///
 /// Contains core package information used across all bundling platforms.
@@ -17,6 +19,7 @@
 ///     homepage: Some("https://example.com".into()),
 ///     authors: Some(vec!["Author Name <email@example.com>".into()]),
 ///     default_run: Some("myapp".into()),
+///     ..Default::default()
 /// };
 /// ```
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -62,4 +65,18 @@ pub struct PackageSettings {
     /// Default: None (uses first binary)
     #[serde(default)]
     pub default_run: Option<String>,
+
+    /// SPDX license expression (e.g., "MIT OR Apache-2.0").
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub license: Option<String>,
+
+    /// License texts installed with the package.
+    ///
+    /// Usually Cargo.toml `license-file`, or the LICENSE files next to it.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub license_files: Vec<PathBuf>,
 }
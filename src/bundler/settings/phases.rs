@@ -0,0 +1,84 @@
+//! Pipeline phase toggles.
+
+/// Bundling pipeline phases that can be bypassed.
+///
+/// Every phase runs by default. Skipping a phase is intended for fast local
+/// iteration and for isolating which phase breaks an artifact; it never makes
+/// a bundle "more correct".
+///
+/// # Examples
+///
+/// ```no_run
+/// use kodegen_bundler_release::bundler::{SettingsBuilder, SkipPhases};
+///
+/// # fn example() -> kodegen_bundler_release::bundler::Result<()> {
+/// let settings = SettingsBuilder::new()
+///     .project_out_directory("target/release")
+///     .skip_phases(SkipPhases {
+///         sign: true,
+///         notarize: true,
+///         ..Default::default()
+///     })
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SkipPhases {
+    /// Skip code signing (macOS codesign, Windows Authenticode).
+    pub sign: bool,
+
+    /// Skip Apple notarization and stapling.
+    pub notarize: bool,
+
+    /// Skip discovery and bundling of non-system dylibs into the .app and of
+    /// non-system shared libraries into Linux packages.
+    pub dylib_bundling: bool,
+
+    /// Skip DMG appearance customization (background, window layout, volume icon).
+    pub customization: bool,
+}
+
+impl SkipPhases {
+    /// Returns true if any phase is skipped.
+    pub fn any(&self) -> bool {
+        self.sign || self.notarize || self.dylib_bundling || self.customization
+    }
+
+    /// Returns true if notarization is skipped.
+    ///
+    /// Apple rejects unsigned apps, so skipping signing skips notarization too.
+    pub fn skips_notarization(&self) -> bool {
+        self.sign || self.notarize
+    }
+}
+
+/// Post-bundle package validation with `lintian` (.deb) and `rpmlint` (.rpm).
+///
+/// Findings are reported in the bundler output. Validation is skipped with
+/// a warning when the linter is not installed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LintMode {
+    /// Do not run package linters.
+    #[default]
+    Off,
+
+    /// Run the linters and report errors and warnings.
+    Report,
+
+    /// Run the linters and fail the bundle when they report errors.
+    Strict,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_sign_skips_notarization() {
+        assert!(!SkipPhases::default().skips_notarization());
+        assert!(SkipPhases { notarize: true, ..Default::default() }.skips_notarization());
+        assert!(SkipPhases { sign: true, ..Default::default() }.skips_notarization());
+        assert!(!SkipPhases { customization: true, ..Default::default() }.skips_notarization());
+    }
+}
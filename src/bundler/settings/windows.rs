@@ -69,6 +69,28 @@ pub struct WindowsSettings {
     #[serde(default)]
     pub sign_command: Option<String>,
 
+    // === Executable Resources ===
+    /// Skip embedding version information and the icon into the built
+    /// executables.
+    ///
+    /// By default the binaries get a `VERSIONINFO` resource (product name,
+    /// version, publisher, copyright) and the `.ico` icon before packaging.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub skip_resources: bool,
+
+    // === Portable ZIP ===
+    /// Name of the marker file shipped next to the executables in the
+    /// portable ZIP.
+    ///
+    /// The application should store its settings beside the executable
+    /// when this file exists.
+    ///
+    /// Default: None (`portable.txt`)
+    #[serde(default)]
+    pub portable_marker: Option<String>,
+
     // === Installer Settings ===
     /// WiX MSI installer settings.
     ///
@@ -218,6 +240,10 @@ pub enum NSISInstallerMode {
     PerMachine,
 
     /// Let user choose during installation.
+    ///
+    /// Shows an install mode page (NSIS MultiUser) and records the choice so
+    /// the uninstaller and later upgrades use the same scope. Also selectable
+    /// with `/AllUsers` or `/CurrentUser` on the command line.
     Both,
 }
 
@@ -270,6 +296,11 @@ pub enum NsisCompression {
 /// installer_mode = "perMachine"
 /// compression = "lzma"
 /// languages = ["en-US", "de-DE"]
+/// license = "LICENSE"
+/// add_to_path = true
+/// plugins = ["installer/plugins"]
+/// custom_includes = ["installer/hooks.nsh"]
+/// preinstall_section = "installer/preinstall.nsh"
 /// ```
 ///
 /// # See Also
@@ -318,6 +349,9 @@ pub struct NsisSettings {
 
     /// Supported installer languages.
     ///
+    /// Locale codes or NSIS language names. With more than one, the
+    /// installer starts with a language selection dialog.
+    ///
     /// Example: `["en-US", "de-DE"]`
     ///
     /// Default: None (uses English)
@@ -329,4 +363,49 @@ pub struct NsisSettings {
     /// Default: None (uses [`NsisCompression::Zlib`])
     #[serde(default)]
     pub compression: Option<NsisCompression>,
+
+    /// Path to a license shown on a license page before installation.
+    ///
+    /// RTF files are used as-is; anything else (e.g. the crate's `LICENSE`)
+    /// is treated as UTF-8 text and converted to RTF.
+    ///
+    /// Default: None (no license page)
+    #[serde(default)]
+    pub license: Option<PathBuf>,
+
+    /// Offer to add the install directory to PATH.
+    ///
+    /// Adds an "Add to PATH" checkbox (checked by default) on a components
+    /// page. The user PATH is used for per-user installs, the system PATH
+    /// for per-machine installs. The uninstaller removes the entry again.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub add_to_path: bool,
+
+    /// NSIS scripts (.nsh) to `!include` after the standard headers.
+    ///
+    /// Use these to define functions, variables and custom pages. A
+    /// `CUSTOM_PAGES` macro defined here is inserted after the directory page.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub custom_includes: Vec<PathBuf>,
+
+    /// NSIS script included in a hidden section that runs before files
+    /// are copied.
+    ///
+    /// Example: stop a running instance before upgrading with
+    /// `nsProcess::_KillProcess "kodegen.exe"`.
+    ///
+    /// Default: None
+    #[serde(default)]
+    pub preinstall_section: Option<PathBuf>,
+
+    /// Directories of NSIS plugin DLLs (e.g. nsProcess), added with
+    /// `!addplugindir`.
+    ///
+    /// Default: Empty
+    #[serde(default)]
+    pub plugins: Vec<PathBuf>,
 }
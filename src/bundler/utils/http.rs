@@ -1,31 +1,163 @@
 //! HTTP utilities for downloading bundler tools.
 //!
-//! Provides functions for downloading files.
+//! Provides functions for downloading files, optionally verified against
+//! a pinned SHA-256 checksum, through a configured proxy and tool mirrors.
 
-#[cfg(target_os = "linux")]
-use crate::bundler::error::Result;
+use crate::bundler::error::{Error, Result};
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Tools whose download location can be redirected with a mirror
+pub const MIRRORED_TOOLS: [&str; 3] = ["linuxdeploy", "appimagetool", "linuxdeploy-plugin-gstreamer"];
+
+/// Set by `--offline`; downloads then fail immediately
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--download-proxy` and `--download-mirror`
+static SOURCES: OnceLock<DownloadSources> = OnceLock::new();
+
+/// Where downloads are fetched from
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct DownloadSources {
+    /// Proxy for all downloads; `HTTPS_PROXY` and friends apply without one
+    pub proxy: Option<String>,
+    /// Base URLs replacing the upstream download location, by tool name
+    pub mirrors: HashMap<String, String>,
+}
+
+/// Makes every later download fail instead of accessing the network
+pub fn set_offline() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
 
+/// Sets the proxy and mirrors for the rest of the process; later calls are ignored
+pub fn configure(sources: DownloadSources) {
+    let _ = SOURCES.set(sources);
+}
+
+/// URL of `path` under the mirror configured for `tool`, or under `upstream`
+///
+/// Mirrors replicate the upstream layout below their base URL, e.g.
+/// `<mirror>/<release>/linuxdeploy-x86_64.AppImage` for linuxdeploy.
 #[cfg(target_os = "linux")]
-use crate::bundler::error::Error;
+pub fn tool_url(tool: &str, upstream: &str, path: &str) -> String {
+    let base = SOURCES
+        .get()
+        .and_then(|sources| sources.mirrors.get(tool))
+        .map_or(upstream, String::as_str);
+    format!("{}/{}", base.trim_end_matches('/'), path)
+}
 
 /// Downloads a file from a URL.
 ///
-/// Returns the file contents as a byte vector.
+/// Returns the file contents as a byte vector. Connection failures, server
+/// errors and rate limiting are retried with backoff.
 ///
 /// Used by:
 /// - Linux: AppImage bundler (downloads linuxdeploy tool)
-#[cfg(target_os = "linux")]
+/// - `--delta-from` URLs (downloads previous releases)
 pub async fn download(url: &str) -> Result<Vec<u8>> {
-    log::info!("Downloading {}", url);
+    if OFFLINE.load(Ordering::Relaxed) {
+        return Err(Error::GenericError(format!(
+            "Cannot download {} with --offline; provide it before going offline",
+            url
+        )));
+    }
+
+    super::retry::retry(&format!("Download of {}", url), is_transient, || {
+        download_once(url)
+    })
+    .await
+    .map_err(|e| Error::GenericError(format!("Download failed: {}", e)))
+}
+
+async fn download_once(url: &str) -> reqwest::Result<Vec<u8>> {
+    tracing::info!("Downloading {}", url);
+
+    let mut response = client()?.get(url).send().await?.error_for_status()?;
+
+    let name = url.rsplit('/').next().unwrap_or(url);
+    let bar =
+        super::progress_bar::bytes(response.content_length(), format!("Downloading {}", name));
+    let mut data = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
+    while let Some(chunk) = response.chunk().await? {
+        data.extend_from_slice(&chunk);
+        bar.inc(chunk.len() as u64);
+    }
+    bar.finish_and_clear();
+
+    Ok(data)
+}
+
+/// HTTP client using the configured proxy
+fn client() -> reqwest::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = SOURCES.get().and_then(|sources| sources.proxy.as_deref()) {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build()
+}
+
+/// Whether a failed download may succeed when tried again
+fn is_transient(error: &reqwest::Error) -> bool {
+    match error.status() {
+        Some(status) => {
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        None => error.is_connect() || error.is_timeout() || error.is_request() || error.is_body(),
+    }
+}
+
+/// Downloads a file from a URL and checks it against `expected_sha256`.
+#[cfg(target_os = "linux")]
+pub async fn download_verified(url: &str, expected_sha256: &str) -> Result<Vec<u8>> {
+    let data = download(url).await?;
+    verify_sha256(url, &sha256_hex(&data), expected_sha256)?;
+    Ok(data)
+}
+
+/// Lowercase hex SHA-256 digest of `data`
+#[cfg(target_os = "linux")]
+pub fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data))
+}
+
+/// Fails unless `actual` matches the `expected` hex digest of `name`
+#[cfg(target_os = "linux")]
+pub fn verify_sha256(name: &str, actual: &str, expected: &str) -> Result<()> {
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(Error::GenericError(format!(
+            "Checksum mismatch for {}: expected sha256 {}, got {}",
+            name,
+            expected.trim(),
+            actual
+        )));
+    }
+    Ok(())
+}
 
-    let response = reqwest::get(url)
-        .await
-        .map_err(|e| Error::GenericError(format!("Download failed: {}", e)))?;
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| Error::GenericError(format!("Failed to read response: {}", e)))?;
+    #[test]
+    fn test_tool_url() {
+        // Without a mirror for the tool, the upstream location is used
+        assert_eq!(
+            tool_url("appimagetool", "https://example.com/releases/", "continuous/tool.AppImage"),
+            "https://example.com/releases/continuous/tool.AppImage"
+        );
+    }
 
-    Ok(bytes.to_vec())
+    #[test]
+    fn test_verify_sha256() {
+        let actual = sha256_hex(b"linuxdeploy");
+        assert!(verify_sha256("tool", &actual, &actual.to_uppercase()).is_ok());
+        let err = verify_sha256("tool", &actual, &sha256_hex(b"tampered")).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch for tool"));
+    }
 }
@@ -0,0 +1,173 @@
+//! Cleanup when the bundler is interrupted with SIGINT or SIGTERM.
+//!
+//! Resources that would outlive a killed bundler (child processes, mounted
+//! disk images, containers, temporary clones) register a cleanup action for
+//! as long as they exist. [`install`] waits for the signals, runs the pending
+//! actions newest first and exits with the conventional `128 + signal` code.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+type Action = Box<dyn FnOnce() + Send>;
+
+static REGISTRY: Registry = Registry::new();
+
+/// Exit code after SIGINT
+const EXIT_SIGINT: i32 = 130;
+/// Exit code after SIGTERM
+#[cfg(unix)]
+const EXIT_SIGTERM: i32 = 143;
+
+/// Pending cleanup actions by registration id
+struct Registry {
+    actions: Mutex<Vec<(u64, Action)>>,
+    next_id: AtomicU64,
+}
+
+impl Registry {
+    const fn new() -> Self {
+        Self {
+            actions: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    fn actions(&self) -> MutexGuard<'_, Vec<(u64, Action)>> {
+        // Actions are plain data; a panic elsewhere doesn't invalidate them
+        self.actions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn register(&self, action: Action) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.actions().push((id, action));
+        id
+    }
+
+    fn unregister(&self, id: u64) {
+        self.actions().retain(|(registered, _)| *registered != id);
+    }
+
+    /// Runs the registered actions, newest first
+    fn run_actions(&self) {
+        let pending = std::mem::take(&mut *self.actions());
+        for (_, action) in pending.into_iter().rev() {
+            action();
+        }
+    }
+}
+
+/// Registration of a cleanup action; dropping it unregisters the action
+#[must_use = "the cleanup action is unregistered when the guard is dropped"]
+pub struct CleanupGuard(u64);
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        REGISTRY.unregister(self.0);
+    }
+}
+
+/// Registers `action` to run if the bundler is interrupted
+pub fn on_interrupt(action: impl FnOnce() + Send + 'static) -> CleanupGuard {
+    CleanupGuard(REGISTRY.register(Box::new(action)))
+}
+
+/// Terminates `child` if the bundler is interrupted
+///
+/// A child spawned as a process group leader (`process_group(0)`) is
+/// terminated together with its whole group, e.g. cargo and its build
+/// scripts. Returns `None` when the child has already exited.
+pub fn kill_on_interrupt(child: &tokio::process::Child) -> Option<CleanupGuard> {
+    let pid = child.id()?;
+    Some(on_interrupt(move || terminate(pid)))
+}
+
+/// Removes the directory tree at `path` if the bundler is interrupted
+pub fn remove_dir_on_interrupt(path: &Path) -> CleanupGuard {
+    let path = path.to_path_buf();
+    on_interrupt(move || {
+        let _ = std::fs::remove_dir_all(&path);
+    })
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) {
+    use nix::sys::signal::{Signal, kill, killpg};
+    use nix::unistd::{Pid, getpgid};
+    let pid = Pid::from_raw(pid as i32);
+    if getpgid(Some(pid)) == Ok(pid) {
+        let _ = killpg(pid, Signal::SIGTERM);
+    } else {
+        let _ = kill(pid, Signal::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(pid: u32) {
+    let _ = std::process::Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+/// Spawns the signal listener; must be called within the Tokio runtime
+pub fn install() {
+    tokio::spawn(async {
+        let code = wait_for_signal().await;
+        eprintln!("\nInterrupted, cleaning up...");
+        REGISTRY.run_actions();
+        std::process::exit(code);
+    });
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> i32 {
+    use tokio::signal::unix::{SignalKind, signal};
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(e) => {
+            tracing::warn!("Failed to listen for SIGTERM: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+            return EXIT_SIGINT;
+        }
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => EXIT_SIGINT,
+        _ = terminate.recv() => EXIT_SIGTERM,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> i32 {
+    let _ = tokio::signal::ctrl_c().await;
+    EXIT_SIGINT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_run_actions() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let record = |n: u32| {
+            let order = order.clone();
+            move || order.lock().unwrap().push(n)
+        };
+
+        let registry = Registry::new();
+        registry.register(Box::new(record(1)));
+        let dropped = registry.register(Box::new(record(2)));
+        registry.register(Box::new(record(3)));
+        registry.unregister(dropped);
+
+        registry.run_actions();
+        assert_eq!(*order.lock().unwrap(), [3, 1]);
+
+        // Each action runs at most once
+        registry.run_actions();
+        assert_eq!(order.lock().unwrap().len(), 2);
+    }
+}
@@ -2,3 +2,8 @@
 
 pub mod fs;
 pub mod http;
+pub mod interrupt;
+pub mod progress_bar;
+pub mod retry;
+pub mod tool_pins;
+pub mod tools;
@@ -0,0 +1,144 @@
+//! Terminal progress bars for long-running operations.
+//!
+//! Bars and spinners are drawn on stdout only when it is an interactive
+//! terminal. Otherwise every helper degrades to a hidden bar, and callers
+//! keep printing their plain log lines.
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::future::Future;
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Whether progress bars are drawn
+pub fn enabled() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("TERM").is_none_or(|term| term != "dumb")
+}
+
+/// Byte progress bar for a download of `total` bytes, when known
+pub fn bytes(total: Option<u64>, message: impl Into<String>) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = match total {
+        Some(total) => ProgressBar::with_draw_target(Some(total), ProgressDrawTarget::stdout())
+            .with_style(
+                ProgressStyle::with_template(
+                    "{msg} [{bar:30}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+                )
+                .expect("valid progress template")
+                .progress_chars("=> "),
+            ),
+        None => spinner_bar().with_style(
+            ProgressStyle::with_template("{spinner} {msg} {bytes} ({bytes_per_sec})")
+                .expect("valid progress template"),
+        ),
+    };
+    bar.set_message(message.into());
+    bar
+}
+
+/// Spinner showing `message` and the elapsed time
+pub fn spinner(message: impl Into<String>) -> ProgressBar {
+    if !enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = spinner_bar().with_style(
+        ProgressStyle::with_template("{spinner} {wide_msg} [{elapsed}]")
+            .expect("valid progress template"),
+    );
+    bar.set_message(message.into());
+    bar
+}
+
+fn spinner_bar() -> ProgressBar {
+    let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stdout());
+    bar.enable_steady_tick(TICK_INTERVAL);
+    bar
+}
+
+/// Runs `future` behind a spinner showing `message`
+pub async fn spin<F: Future>(message: impl Into<String>, future: F) -> F::Output {
+    let bar = spinner(message);
+    let output = future.await;
+    bar.finish_and_clear();
+    output
+}
+
+/// Spinner following the output of an external command
+///
+/// On a terminal each output line replaces the spinner message instead of
+/// being printed, and the lines are kept so they can be shown if the
+/// command fails. Off a terminal nothing is captured and callers print the
+/// lines as they arrive.
+pub struct CommandProgress {
+    bar: Option<ProgressBar>,
+    captured: Mutex<Vec<String>>,
+}
+
+impl CommandProgress {
+    /// Starts following a command described by `label`, e.g. "cargo build"
+    pub fn new(label: impl Into<String>) -> Self {
+        let bar = enabled().then(|| {
+            let bar = spinner_bar().with_style(
+                ProgressStyle::with_template("{spinner} {prefix}: {wide_msg} [{elapsed}]")
+                    .expect("valid progress template"),
+            );
+            bar.set_prefix(label.into());
+            bar
+        });
+        Self {
+            bar,
+            captured: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records an output line; returns `false` when the caller should print it
+    pub fn line(&self, line: &str) -> bool {
+        let Some(bar) = &self.bar else {
+            return false;
+        };
+        bar.set_message(line.trim().to_string());
+        self.captured
+            .lock()
+            .expect("captured output lock poisoned")
+            .push(line.to_string());
+        true
+    }
+
+    /// Clears the spinner and returns the captured lines
+    pub fn finish(self) -> Vec<String> {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        self.captured
+            .into_inner()
+            .expect("captured output lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_progress() {
+        // Off a terminal lines are left to the caller
+        let progress = CommandProgress {
+            bar: None,
+            captured: Mutex::new(Vec::new()),
+        };
+        assert!(!progress.line("   Compiling kodegen v0.1.0"));
+        assert!(progress.finish().is_empty());
+
+        // On a terminal they are captured for replay
+        let progress = CommandProgress {
+            bar: Some(ProgressBar::hidden()),
+            captured: Mutex::new(Vec::new()),
+        };
+        assert!(progress.line("   Compiling kodegen v0.1.0"));
+        assert_eq!(progress.finish(), ["   Compiling kodegen v0.1.0"]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Retry with exponential backoff for network operations.
+//!
+//! Downloads, git clones and fetches, and notarization submissions go
+//! through [`retry`], so a transient network error costs a delay instead of
+//! the whole run. The policy is process-wide and set once from the command
+//! line (`--retries`, `--retry-delay`).
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How often and how patiently failed network operations are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Attempts after the first one; 0 disables retrying
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each further one
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before retry number `retry` (1-based)
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+static CONFIG: OnceLock<RetryConfig> = OnceLock::new();
+
+/// Sets the retry policy for the rest of the process; later calls are ignored
+pub fn configure(config: RetryConfig) {
+    let _ = CONFIG.set(config);
+}
+
+/// The configured retry policy, or the default one
+pub fn config() -> RetryConfig {
+    CONFIG.get().copied().unwrap_or_default()
+}
+
+/// Runs `attempt` until it succeeds, fails with an error `retryable` rejects,
+/// or the configured retries are used up, logging every failed attempt
+pub async fn retry<T, E, F, Fut>(
+    operation: &str,
+    retryable: impl Fn(&E) -> bool,
+    attempt: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_with(&config(), operation, retryable, attempt).await
+}
+
+async fn retry_with<T, E, F, Fut>(
+    config: &RetryConfig,
+    operation: &str,
+    retryable: impl Fn(&E) -> bool,
+    mut attempt: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retries < config.retries && retryable(&e) => {
+                retries += 1;
+                let delay = config.delay(retries);
+                tracing::warn!(
+                    "{} failed (attempt {}/{}): {}; retrying in {}s",
+                    operation,
+                    retries,
+                    config.retries + 1,
+                    e,
+                    delay.as_secs_f32()
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_delay_backoff() {
+        let config = RetryConfig::default();
+        assert_eq!(config.delay(1), Duration::from_secs(2));
+        assert_eq!(config.delay(2), Duration::from_secs(4));
+        assert_eq!(config.delay(3), Duration::from_secs(8));
+        assert_eq!(config.delay(10), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let config = RetryConfig {
+            retries: 3,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        };
+        let calls = Cell::new(0);
+        let result: Result<u32, String> = retry_with(&config, "op", |_| true, || {
+            calls.set(calls.get() + 1);
+            async { if calls.get() < 3 { Err("transient".to_string()) } else { Ok(calls.get()) } }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+
+        // Retries are bounded
+        calls.set(0);
+        let result: Result<u32, String> = retry_with(&config, "op", |_| true, || {
+            calls.set(calls.get() + 1);
+            async { Err("transient".to_string()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 4);
+
+        // Permanent errors fail immediately
+        calls.set(0);
+        let result: Result<u32, String> = retry_with(&config, "op", |e: &String| e != "permanent", || {
+            calls.set(calls.get() + 1);
+            async { Err("permanent".to_string()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,103 @@
+//! Pinned releases and checksums of downloaded bundler tools.
+//!
+//! A tool is only downloaded when its SHA-256 is known: pinned in
+//! `tool-checksums.sha256` (`sha256sum` format, keyed by cached file name)
+//! or configured in `appimage.tool_sha256` for other releases. The
+//! linuxdeploy-plugin-gstreamer script is pinned to the commit named by its
+//! entry there. After changing a release below, regenerate the checksums
+//! with `just pin-appimage-tools`, which also pins the plugin's latest commit.
+// Only consumed by the AppImage bundler, which is cfg-gated to Linux
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use std::collections::HashMap;
+
+/// linuxdeploy release downloaded unless `appimage.linuxdeploy_version` is set
+pub const LINUXDEPLOY_RELEASE: &str = "1-alpha-20250213-2";
+
+/// appimagetool release downloaded unless `appimage.appimagetool_version` is set
+pub const APPIMAGETOOL_RELEASE: &str = "1.9.0";
+
+/// Checksums of the pinned downloads
+const CHECKSUMS: &str = include_str!("tool-checksums.sha256");
+
+/// SHA-256 expected for the download cached as `file_name`: the entry in
+/// `overrides` (`appimage.tool_sha256`), else the built-in pin
+pub fn expected_sha256<'a>(file_name: &str, overrides: &'a HashMap<String, String>) -> Option<&'a str> {
+    overrides
+        .get(file_name)
+        .map(String::as_str)
+        .or_else(|| pinned_sha256(CHECKSUMS, file_name))
+}
+
+/// Commit of linuxdeploy-plugin-gstreamer with a pinned checksum, recorded
+/// as `linuxdeploy-plugin-gstreamer-<commit>.sh`
+pub fn pinned_gstreamer_plugin_commit() -> Option<&'static str> {
+    gstreamer_plugin_commit(CHECKSUMS)
+}
+
+fn gstreamer_plugin_commit(checksums: &str) -> Option<&str> {
+    checksums
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let (_, name) = line.split_once(char::is_whitespace)?;
+            name.trim_start()
+                .trim_start_matches('*')
+                .strip_prefix("linuxdeploy-plugin-gstreamer-")?
+                .strip_suffix(".sh")
+        })
+}
+
+/// Checksum of `file_name` in `sha256sum` output
+fn pinned_sha256<'a>(checksums: &'a str, file_name: &str) -> Option<&'a str> {
+    checksums
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let (sha256, name) = line.split_once(char::is_whitespace)?;
+            // `sha256sum -b` marks names with `*`
+            (name.trim_start().trim_start_matches('*') == file_name).then_some(sha256)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_sha256() {
+        let checksums = "# comment\nabc123  linuxdeploy-1-x86_64.AppImage\ndef456 *appimagetool-1.9.0-x86_64.AppImage\n\
+                         789abc  linuxdeploy-plugin-gstreamer-0123abcd.sh\n";
+        assert_eq!(gstreamer_plugin_commit(checksums), Some("0123abcd"));
+        assert_eq!(pinned_sha256(checksums, "linuxdeploy-1-x86_64.AppImage"), Some("abc123"));
+        assert_eq!(pinned_sha256(checksums, "appimagetool-1.9.0-x86_64.AppImage"), Some("def456"));
+        assert_eq!(pinned_sha256(checksums, "linuxdeploy-2-x86_64.AppImage"), None);
+
+        let overrides = HashMap::from([("custom.AppImage".to_string(), "fedcba".to_string())]);
+        assert_eq!(expected_sha256("custom.AppImage", &overrides), Some("fedcba"));
+    }
+
+    /// Fails until `just pin-appimage-tools` has recorded the default releases
+    #[test]
+    fn test_default_releases_pinned() {
+        for file_name in [
+            format!("linuxdeploy-{}-x86_64.AppImage", LINUXDEPLOY_RELEASE),
+            format!("appimagetool-{}-x86_64.AppImage", APPIMAGETOOL_RELEASE),
+        ] {
+            assert!(
+                pinned_sha256(CHECKSUMS, &file_name).is_some(),
+                "no checksum pinned for {}; run `just pin-appimage-tools`",
+                file_name
+            );
+        }
+    }
+
+    /// Fails until `just pin-appimage-tools` has pinned a plugin commit
+    #[test]
+    fn test_gstreamer_plugin_pinned() {
+        let commit = pinned_gstreamer_plugin_commit()
+            .expect("no linuxdeploy-plugin-gstreamer commit pinned; run `just pin-appimage-tools`");
+        let file_name = format!("linuxdeploy-plugin-gstreamer-{}.sh", commit);
+        assert!(pinned_sha256(CHECKSUMS, &file_name).is_some());
+    }
+}
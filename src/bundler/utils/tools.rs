@@ -0,0 +1,102 @@
+//! Detection of system-installed bundler tools.
+//!
+//! Used as a fallback when a tool cannot be downloaded (restricted networks,
+//! air-gapped build machines) and for tools that are always taken from the
+//! distribution (makensis on Linux/macOS).
+// Only consumed by the Linux and NSIS bundlers, which are cfg-gated off on macOS
+#![cfg_attr(target_os = "macos", allow(dead_code))]
+
+use std::path::PathBuf;
+
+/// Directory caching downloaded tools across projects and runs
+pub fn cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("kodegen-bundler").join("tools"))
+}
+
+/// A tool found on PATH together with the version it reports
+#[derive(Debug, Clone)]
+pub struct SystemTool {
+    /// Absolute path to the executable
+    pub path: PathBuf,
+    /// First line of the tool's version output
+    pub version: String,
+}
+
+impl SystemTool {
+    /// Major and minor version parsed from the version output, if any
+    pub fn version_number(&self) -> Option<(u32, u32)> {
+        parse_version(&self.version)
+    }
+}
+
+/// Locate the first of `names` on PATH that runs `version_arg` successfully
+///
+/// Returns `None` if no candidate is installed or none of them can report a
+/// version, which usually means a broken install.
+pub async fn find_system_tool(names: &[&str], version_arg: &str) -> Option<SystemTool> {
+    for name in names {
+        let Ok(path) = which::which(name) else {
+            continue;
+        };
+
+        let output = match tokio::process::Command::new(&path)
+            .arg(version_arg)
+            // System AppImages (e.g. linuxdeploy) need FUSE unless told to self-extract
+            .env("APPIMAGE_EXTRACT_AND_RUN", "1")
+            .output()
+            .await
+        {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                tracing::debug!("{} {} exited with {:?}", path.display(), version_arg, output.status.code());
+                continue;
+            }
+            Err(e) => {
+                tracing::debug!("Failed to run {} {}: {}", path.display(), version_arg, e);
+                continue;
+            }
+        };
+
+        // Some tools print their version to stderr
+        let text = if output.stdout.is_empty() { &output.stderr } else { &output.stdout };
+        let version = String::from_utf8_lossy(text)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        tracing::debug!("Found system {} at {} ({})", name, path.display(), version);
+        return Some(SystemTool { path, version });
+    }
+
+    None
+}
+
+/// Extract the first `major[.minor]` number from version output
+///
+/// Accepts forms such as `v3.08`, `3.10`, and `linuxdeploy version 1-alpha`.
+pub fn parse_version(output: &str) -> Option<(u32, u32)> {
+    let start = output.find(|c: char| c.is_ascii_digit())?;
+    let mut parts = output[start..]
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()?
+        .split('.');
+
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|m| m.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v3.08"), Some((3, 8)));
+        assert_eq!(parse_version("3.10"), Some((3, 10)));
+        assert_eq!(parse_version("linuxdeploy version 1-alpha (git commit ID 2b73a2)"), Some((1, 0)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+}
@@ -3,8 +3,21 @@
 //! This module provides comprehensive CLI argument parsing using clap,
 //! with proper validation and error handling.
 
-use clap::Parser;
-use std::path::PathBuf;
+use super::ColorWhen;
+use super::docker::RunTimeouts;
+use super::docker::image::DOCKER_BUILD_TIMEOUT;
+use super::docker::limits::ContainerLimits;
+use super::logging::LogFormat;
+use super::commands::PublishTarget;
+use crate::bundler::utils::http::{self, DownloadSources};
+use crate::bundler::utils::retry::RetryConfig;
+use crate::bundler::{LintMode, PackageType, SkipPhases};
+use crate::error::{BundlerError, CliError};
+use crate::source::{CheckoutOptions, RepositorySource};
+use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Platform package bundler for Rust binaries
 #[derive(Parser, Debug)]
@@ -12,7 +25,7 @@ use std::path::PathBuf;
     name = "kodegen_bundler_bundle",
     version,
     about = "Platform package bundler for Rust binaries",
-    long_about = "Creates platform-specific packages (.deb, .rpm, .dmg, AppImage, .exe) for Rust binaries.
+    long_about = "Creates platform-specific packages (.deb, .rpm, .dmg, AppImage, .exe, portable .zip) for Rust binaries.
 
 Clones repository from GitHub to tmp, builds binary, creates package, moves to output path.
 
@@ -20,35 +33,627 @@ Usage:
   kodegen_bundler_bundle --source . --platform deb --output-binary /tmp/myapp.deb
   kodegen_bundler_bundle --source cyrup-ai/kodegen --platform dmg --output-binary ./kodegen.dmg
   kodegen_bundler_bundle --source https://github.com/user/repo --platform nsis --output-binary setup.exe
+  kodegen_bundler_bundle --source . --platform dmg --output-dir ./dist
+  kodegen_bundler_bundle --source . --all-platforms --output-dir ./dist --publish github
+  kodegen_bundler_bundle --source . --platform appimage --output-dir ./dist --updater --updater-key ./app.key --publish github
+  kodegen_bundler_bundle gc --root ./dist --keep-last 5
+  kodegen_bundler_bundle clean --dry-run
+  kodegen_bundler_bundle verify-links --links ./links --artifact ./kodegen.dmg
+  kodegen_bundler_bundle repo --output ./apt --gpg-key releases@example.com ./kodegen.deb
+  kodegen_bundler_bundle install-test ./kodegen_1.0.0_amd64.deb --json
 
-Exit code 0 = artifact guaranteed to exist at output path."
+Exit code 0 = artifact guaranteed to exist at output path.",
+    args_conflicts_with_subcommands = true
 )]
-pub struct Args {
+pub struct Cli {
+    /// Maintenance subcommand; bundles when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Bundle arguments (required when no subcommand is given)
+    #[command(flatten)]
+    pub bundle: Option<Args>,
+
+    /// Format of diagnostic log records on stderr: text, json
+    ///
+    /// Verbosity is controlled by `RUST_LOG`, e.g. `RUST_LOG=info` also
+    /// logs how long each bundling phase took.
+    #[arg(long, global = true, value_enum, value_name = "FORMAT", default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// When to use colored output: auto, always, never
+    ///
+    /// `auto` honors NO_COLOR, CLICOLOR, CLICOLOR_FORCE and TERM=dumb, and
+    /// disables color when output is not a terminal.
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorWhen::Auto)]
+    pub color: ColorWhen,
+}
+
+impl Cli {
+    /// Parse command line arguments
+    pub fn parse_args() -> Self {
+        Self::parse()
+    }
+}
+
+/// Maintenance subcommands
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Prune old artifacts, bundle directories, build logs, downloaded tools and cached clones
+    ///
+    /// Keeps the newest `keep_last` entries under each root, then removes the
+    /// oldest remaining entries until the root fits within `max_total_size`.
+    /// Defaults come from the gc config file and are overridden by flags.
+    Gc(GcArgs),
+
+    /// Remove leftover temporary clones, the clone cache, downloaded tools and
+    /// the Docker build caches
+    ///
+    /// Clones still used by a running bundler are skipped. Unlike `gc`,
+    /// nothing is retained: every stale `kodegen-bundle-*` directory in the
+    /// system temp directory, every cached clone, every downloaded tool and
+    /// the cargo and target cache volumes go.
+    Clean(CleanArgs),
+
+    /// Verify in-toto link metadata recorded with `--in-toto-dir`
+    ///
+    /// Checks that the clone, build, package and sign links are present,
+    /// signed by the given key, and chained in order, and optionally that an
+    /// artifact matches the sign step's product. Unsigned links fail unless
+    /// `--allow-unsigned` is given.
+    VerifyLinks(VerifyLinksArgs),
+
+    /// Build a flat APT repository from .deb packages
+    ///
+    /// Copies the packages into the output directory and writes Packages,
+    /// Packages.gz and Release covering every .deb there, so repeated runs
+    /// add to the repository. With `--gpg-key`, also writes InRelease,
+    /// Release.gpg and the public key for `signed-by`.
+    Repo(RepoArgs),
+
+    /// Bundle the same source twice and compare the artifacts
+    ///
+    /// Both builds run with fresh temporary directories and without the
+    /// clone, target and container caches. Differing artifacts are compared
+    /// entry by entry where the format allows (deb, zip, tar.gz, .app) to
+    /// point at nondeterministic inputs. Exits with 1 when anything differs.
+    ReproCheck(ReproCheckArgs),
+
+    /// Check the bundle configuration in Cargo.toml without building
+    ///
+    /// Reports a missing or malformed macOS identifier, missing, unreadable
+    /// or mis-sized icons, and configured files (entitlements, scripts,
+    /// templates, installer images) that do not exist. Exits with 1 when
+    /// there are errors; warnings alone exit with 0.
+    Validate(ValidateArgs),
+
+    /// Install a built package into a clean container and run its binary
+    ///
+    /// The image follows the package type: debian:stable for .deb, fedora
+    /// for .rpm, ubuntu LTS for AppImage and Debian with Wine for NSIS
+    /// installers. Runs `<binary> --version` after installing and exits
+    /// with 1 when either step fails.
+    InstallTest(InstallTestArgs),
+}
+
+/// Arguments for the `gc` subcommand
+#[derive(clap::Args, Debug)]
+pub struct GcArgs {
+    /// Directory to prune (repeatable); entries directly inside it are candidates
+    #[arg(long = "root", value_name = "DIR")]
+    pub roots: Vec<PathBuf>,
+
+    /// Number of newest entries to keep in each root
+    #[arg(long, value_name = "N")]
+    pub keep_last: Option<usize>,
+
+    /// Maximum total size of each root (e.g. 500M, 10G)
+    #[arg(long, value_name = "SIZE")]
+    pub max_total_size: Option<String>,
+
+    /// Do not prune temporary clones in the system temp directory or the
+    /// persistent clone cache
+    #[arg(long)]
+    pub no_clones: bool,
+
+    /// Path to the gc config file (default: <config dir>/kodegen-bundler/gc.toml)
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Report what would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the `clean` subcommand
+#[derive(clap::Args, Debug)]
+pub struct CleanArgs {
+    /// Keep the Docker cargo, target and sccache cache volumes
+    #[arg(long)]
+    pub no_docker: bool,
+
+    /// Report what would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the `verify-links` subcommand
+#[derive(clap::Args, Debug)]
+pub struct VerifyLinksArgs {
+    /// Directory containing the link files
+    #[arg(long, value_name = "DIR")]
+    pub links: PathBuf,
+
+    /// Artifact to check against the sign step's products
+    #[arg(long, value_name = "PATH")]
+    pub artifact: Option<PathBuf>,
+
+    /// ed25519 public key (securesystemslib JSON) the links must be signed with
+    #[arg(long, value_name = "PATH")]
+    pub public_key: Option<PathBuf>,
+
+    /// Accept unsigned links when no --public-key is given
+    #[arg(long, conflicts_with = "public_key")]
+    pub allow_unsigned: bool,
+}
+
+/// Arguments for the `repro-check` subcommand
+#[derive(clap::Args, Debug)]
+pub struct ReproCheckArgs {
     /// Source repository (local path, GitHub org/repo, or GitHub URL)
     #[arg(short = 's', long, value_name = "SOURCE")]
     pub source: String,
 
-    /// Platform to bundle: deb, rpm, dmg, macos-bundle, nsis, appimage
+    /// Package type to build twice
     #[arg(short, long, value_name = "PLATFORM")]
     pub platform: String,
 
+    /// Keep both builds' artifacts and print their directory
+    #[arg(long)]
+    pub keep: bool,
+
+    /// Further bundle flags for both builds, after `--`
+    #[arg(last = true, value_name = "BUNDLE_ARGS")]
+    pub bundle_args: Vec<String>,
+}
+
+/// Arguments for the `validate` subcommand
+#[derive(clap::Args, Debug)]
+pub struct ValidateArgs {
+    /// Project directory or Cargo.toml to check
+    #[arg(short = 's', long, value_name = "PATH", default_value = ".")]
+    pub source: PathBuf,
+
+    /// Package type to check for (repeatable); defaults to all
+    #[arg(short, long = "platform", value_name = "PLATFORM")]
+    pub platforms: Vec<String>,
+}
+
+/// Arguments for the `install-test` subcommand
+#[derive(clap::Args, Debug)]
+pub struct InstallTestArgs {
+    /// Package to test: .deb, .rpm, .AppImage or NSIS setup .exe
+    #[arg(value_name = "ARTIFACT")]
+    pub artifact: PathBuf,
+
+    /// Executable to run after installing (default: the package's first executable)
+    #[arg(long, value_name = "NAME")]
+    pub binary: Option<String>,
+
+    /// Image to install into, e.g. ubuntu:22.04 for a .deb
+    ///
+    /// Must use the package's package manager (apt for .deb and NSIS, dnf
+    /// for .rpm). Default: debian:stable-slim for .deb and NSIS,
+    /// fedora:latest for .rpm, ubuntu:24.04 for AppImage.
+    #[arg(long, value_name = "IMAGE")]
+    pub image: Option<String>,
+
+    /// Print the result as JSON on stdout
+    #[arg(long)]
+    pub json: bool,
+
+    /// Start Docker Desktop, colima or the docker service if the daemon is down
+    #[arg(long, env = "KODEGEN_AUTO_START_DOCKER")]
+    pub auto_start_docker: bool,
+}
+
+/// Arguments for the `repo` subcommand
+#[derive(clap::Args, Debug)]
+pub struct RepoArgs {
+    /// .deb packages to add to the repository
+    #[arg(value_name = "DEB", required = true)]
+    pub debs: Vec<PathBuf>,
+
+    /// Repository directory (created if missing)
+    #[arg(short = 'o', long, value_name = "DIR")]
+    pub output: PathBuf,
+
+    /// GPG key ID or user ID to sign the Release file with
+    #[arg(long, value_name = "KEY")]
+    pub gpg_key: Option<String>,
+
+    /// Origin field of the Release file
+    #[arg(long, value_name = "NAME")]
+    pub origin: Option<String>,
+
+    /// Label field of the Release file
+    #[arg(long, value_name = "NAME")]
+    pub label: Option<String>,
+
+    /// Suite field of the Release file
+    #[arg(long, value_name = "NAME", default_value = "stable")]
+    pub suite: String,
+}
+
+/// Arguments for bundling a repository into a platform package
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Source repository (local path, GitHub org/repo, or GitHub URL)
+    #[arg(short = 's', long, value_name = "SOURCE")]
+    pub source: String,
+
+    /// Platform to bundle: deb, rpm, appimage, app (macos-bundle), dmg, exe (nsis), portable
+    #[arg(
+        short,
+        long,
+        value_name = "PLATFORM",
+        required_unless_present = "all_platforms",
+        conflicts_with = "all_platforms"
+    )]
+    pub platform: Option<String>,
+
+    /// Bundle every platform buildable from this host
+    ///
+    /// Native package types are built locally while cross-platform types are
+    /// built in Docker at the same time. `--output-binary` names the
+    /// directory receiving all artifacts. Ends with a summary of built,
+    /// skipped and failed platforms; exits non-zero if any build failed.
+    #[arg(long)]
+    pub all_platforms: bool,
+
     /// Output path for the created artifact
     ///
     /// The bundler will move the created artifact to this exact path.
     /// The bundler will create parent directories if they don't exist.
     /// The filename should include the architecture (e.g., kodegen_0.1.0_arm64.deb).
+    /// With `--all-platforms` this is the output directory instead.
     ///
     /// Contract: Exit code 0 guarantees the artifact exists at this path.
-    #[arg(short = 'o', long, value_name = "PATH")]
-    pub output_binary: PathBuf,
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "PATH",
+        required_unless_present = "output_dir",
+        conflicts_with = "output_dir"
+    )]
+    pub output_binary: Option<PathBuf>,
+
+    /// Output directory receiving every created artifact
+    ///
+    /// Use instead of `--output-binary` when the bundler produces several
+    /// files (e.g. .app and .dmg, or a package and its signature). All of
+    /// them are moved here under their original names and each path is
+    /// printed on its own line.
+    ///
+    /// Contract: Exit code 0 guarantees every printed path exists.
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Product name to package under instead of the Cargo.toml package name
+    ///
+    /// For shipping the same binary under different brands; the binary
+    /// itself keeps its name.
+    #[arg(long, value_name = "NAME")]
+    pub product_name: Option<String>,
+
+    /// Package version instead of the Cargo.toml version (e.g. 1.2.3+build.45)
+    #[arg(long, value_name = "VERSION")]
+    pub app_version: Option<String>,
+
+    /// Ad-hoc sign the macOS .app and its dylibs when no signing identity is configured
+    ///
+    /// Apple Silicon refuses to run unsigned binaries; an ad-hoc signature
+    /// (`codesign -s -`) is enough to launch a development build locally.
+    #[arg(long)]
+    pub dev_sign: bool,
+
+    /// Skip code signing (macOS codesign, Windows Authenticode)
+    #[arg(long)]
+    pub skip_sign: bool,
+
+    /// Skip Apple notarization and stapling
+    #[arg(long)]
+    pub skip_notarize: bool,
+
+    /// Skip bundling non-system dylibs into the macOS .app and shared libraries into Linux packages
+    #[arg(long)]
+    pub skip_dylib_bundling: bool,
+
+    /// Skip DMG appearance customization (background, window layout, volume icon)
+    #[arg(long)]
+    pub skip_customization: bool,
+
+    /// Validate .deb packages with lintian and .rpm packages with rpmlint
+    ///
+    /// Errors and warnings are reported but do not fail the build. Skipped
+    /// with a warning when the linter is not installed.
+    #[arg(long)]
+    pub lint: bool,
+
+    /// Like --lint, but fail the build when the linter reports errors
+    #[arg(long)]
+    pub lint_strict: bool,
+
+    /// Install each produced package into a clean container and run
+    /// `<binary> --version`, failing the bundle if either step fails
+    ///
+    /// Covers .deb (Debian), .rpm (Fedora), AppImage (Ubuntu) and NSIS
+    /// setups (Wine); other artifacts are not tested. Needs Docker or Podman.
+    #[arg(long)]
+    pub smoke_test: bool,
+
+    /// Rust target triple for Windows (exe, portable) builds
+    ///
+    /// Defaults to x86_64-pc-windows-gnu. Use aarch64-pc-windows-msvc for
+    /// Windows on ARM; MSVC targets are cross-compiled with cargo-xwin.
+    #[arg(long, value_name = "TRIPLE")]
+    pub target: Option<String>,
+
+    /// OTLP/HTTP collector to export run metrics and traces to (e.g. http://localhost:4318)
+    ///
+    /// Reports run duration, outcome by platform and container OOM kills.
+    /// Export failures are logged and never affect the exit code.
+    #[cfg(feature = "otlp")]
+    #[arg(long, value_name = "URL", env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+
+    /// Write the artifact's metadata next to it as `<output>.artifact.json`
+    ///
+    /// Includes the package type, checksum, size, target, identifier,
+    /// version, build time, git commit and the versions of the tools used.
+    #[arg(long)]
+    pub artifact_json: bool,
+
+    /// Write in-toto link metadata for each pipeline step to this directory
+    ///
+    /// Records the clone, build, package and sign steps with their materials
+    /// and products. Verify with the `verify-links` subcommand.
+    #[arg(long, value_name = "DIR")]
+    pub in_toto_dir: Option<PathBuf>,
+
+    /// ed25519 private key (securesystemslib JSON) used to sign in-toto links
+    #[arg(long, value_name = "PATH", requires = "in_toto_dir")]
+    pub in_toto_key: Option<PathBuf>,
+
+    /// Publish the artifacts after bundling; comma-separated or repeatable
+    ///
+    /// `github` creates or updates the GitHub release for the current tag
+    /// and uploads every artifact plus a SHA256SUMS file. Requires
+    /// GITHUB_TOKEN. `ppa` uploads a source package to the Launchpad PPA
+    /// configured in `deb.ppa`; .deb builds on Linux only.
+    #[arg(long, value_enum, value_name = "TARGET", value_delimiter = ',')]
+    pub publish: Vec<PublishTarget>,
+
+    /// Release tag for `--publish`
+    ///
+    /// Defaults to the tag of GITHUB_REF, or the tag pointing at HEAD of a
+    /// local source.
+    #[arg(long, value_name = "TAG", requires = "publish")]
+    pub release_tag: Option<String>,
+
+    /// Write a Tauri updater `latest.json` next to the artifacts
+    ///
+    /// Each updater-installable artifact (AppImage, deb, rpm, NSIS setup,
+    /// .app) is signed with minisign and listed per platform with its
+    /// download URL and signature. The `.sig` files and `latest.json` are
+    /// uploaded with `--publish`.
+    #[arg(long)]
+    pub updater: bool,
+
+    /// minisign secret key for `--updater` (path or key contents)
+    ///
+    /// Encrypted keys are unlocked with TAURI_SIGNING_PRIVATE_KEY_PASSWORD.
+    #[arg(
+        long,
+        value_name = "KEY",
+        env = "TAURI_SIGNING_PRIVATE_KEY",
+        hide_env_values = true
+    )]
+    pub updater_key: Option<String>,
+
+    /// Base download URL of the artifacts listed in `latest.json`
+    ///
+    /// Defaults to the GitHub release download URL with `--publish github`.
+    #[arg(long, value_name = "URL", requires = "updater")]
+    pub updater_url: Option<String>,
+
+    /// Write a cargo-dist compatible `dist-manifest.json` next to the artifacts
+    ///
+    /// Lists every downloadable artifact with its kind, target triple,
+    /// SHA-256 checksum and install hint, for cargo-dist and axoupdater
+    /// tooling. Uploaded with `--publish`.
+    #[arg(long)]
+    pub dist_manifest: bool,
+
+    /// Generate delta update artifacts
+    ///
+    /// AppImages get a `.zsync` control file for zsync/AppImageUpdate. NSIS
+    /// setups and DMGs get a bsdiff patch against each `--delta-from`
+    /// artifact of the same type.
+    #[arg(long)]
+    pub delta: bool,
+
+    /// Previous release artifact (path or URL) to diff against; repeatable
+    #[arg(long, value_name = "PATH|URL", requires = "delta")]
+    pub delta_from: Vec<String>,
+
+    /// Branch or tag to bundle (default: the repository's default branch)
+    #[arg(long = "ref", value_name = "REF")]
+    pub git_ref: Option<String>,
+
+    /// Clone to a temporary directory instead of reusing the clone cache
+    ///
+    /// By default the repository is cloned once per ref into
+    /// `<cache dir>/kodegen-bundler/clones/<repo>@<ref>`, then updated with
+    /// `git fetch` and built incrementally in its preserved `target/`.
+    /// Docker builds likewise skip the target directory cache volume.
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Cache compiled crates with sccache (installed with cargo if missing)
+    ///
+    /// Speeds up repeat bundling of the same commit across package types.
+    /// Uses sccache's local cache natively and a persistent volume in
+    /// Docker; set SCCACHE_BUCKET (plus SCCACHE_REGION, AWS credentials)
+    /// to share an S3 cache instead. Those variables reach container
+    /// builds too.
+    #[arg(long)]
+    pub sccache: bool,
+
+    /// Start Docker Desktop, colima or the docker service if the daemon is down
+    ///
+    /// Waits up to two minutes for the daemon to respond.
+    #[arg(long, env = "KODEGEN_AUTO_START_DOCKER")]
+    pub auto_start_docker: bool,
+
+    /// Builder image for cross-platform builds (e.g. ghcr.io/acme/builder:1.2)
+    ///
+    /// Skips building the image from the embedded Dockerfile; the image is
+    /// pulled if missing. It must provide the same tools, including
+    /// `kodegen_bundler_bundle` on PATH.
+    #[arg(long, value_name = "IMAGE", env = "KODEGEN_DOCKER_IMAGE")]
+    pub docker_image: Option<String>,
+
+    /// Memory limit for the builder container (e.g. 8g, 6144m)
+    ///
+    /// Defaults to half the host memory, between 2 and 16 GB.
+    #[arg(long, value_name = "SIZE", env = "KODEGEN_DOCKER_MEMORY")]
+    pub docker_memory: Option<String>,
+
+    /// Memory plus swap limit for the builder container (default: memory + 2g)
+    #[arg(long, value_name = "SIZE", env = "KODEGEN_DOCKER_MEMORY_SWAP")]
+    pub docker_memory_swap: Option<String>,
+
+    /// CPUs available to the builder container, fractions allowed (e.g. 1.5)
+    ///
+    /// Defaults to half the host cores, at least 2.
+    #[arg(long, value_name = "N", env = "KODEGEN_DOCKER_CPUS")]
+    pub docker_cpus: Option<String>,
+
+    /// Maximum number of processes in the builder container (default: 1000)
+    #[arg(long, value_name = "N", env = "KODEGEN_DOCKER_PIDS_LIMIT")]
+    pub docker_pids_limit: Option<u32>,
+
+    /// Kill a container build that prints nothing for this many minutes
+    /// (default: 20, 0 disables)
+    #[arg(long, value_name = "MINUTES", env = "KODEGEN_DOCKER_IDLE_TIMEOUT")]
+    pub docker_idle_timeout: Option<u64>,
+
+    /// Kill a container build running longer than this many minutes
+    /// (default: no limit)
+    #[arg(long, value_name = "MINUTES", env = "KODEGEN_DOCKER_TIMEOUT")]
+    pub docker_timeout: Option<u64>,
+
+    /// Time limit in minutes for building the builder image
+    /// (default: 30, 0 disables)
+    #[arg(long, value_name = "MINUTES", env = "KODEGEN_DOCKER_BUILD_TIMEOUT")]
+    pub docker_build_timeout: Option<u64>,
+
+    /// Retry failed downloads, git clones and fetches, and notarization
+    /// submissions this many times (default: 3, 0 disables)
+    #[arg(long, value_name = "N", env = "KODEGEN_RETRIES")]
+    pub retries: Option<u32>,
+
+    /// Seconds to wait before the first retry, doubled for each further one
+    /// up to 30 (default: 2)
+    #[arg(long, value_name = "SECONDS", env = "KODEGEN_RETRY_DELAY")]
+    pub retry_delay: Option<u64>,
+
+    /// Proxy for tool downloads, e.g. `http://proxy.corp:3128`
+    ///
+    /// Without it, the usual `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY`
+    /// environment variables apply.
+    #[arg(long, value_name = "URL", env = "KODEGEN_DOWNLOAD_PROXY")]
+    pub download_proxy: Option<String>,
+
+    /// Download a tool from a mirror instead of GitHub; repeatable
+    ///
+    /// TOOL is linuxdeploy, appimagetool or linuxdeploy-plugin-gstreamer.
+    /// The mirror must replicate the upstream layout below URL, e.g.
+    /// `URL/<release>/linuxdeploy-x86_64.AppImage` for linuxdeploy.
+    #[arg(
+        long,
+        value_name = "TOOL=URL",
+        env = "KODEGEN_DOWNLOAD_MIRRORS",
+        value_delimiter = ','
+    )]
+    pub download_mirror: Vec<String>,
+
+    /// Bundle a local --source directory as is, without cloning its repository
+    ///
+    /// Nothing is pushed to the repository either.
+    #[arg(long)]
+    pub no_clone: bool,
+
+    /// Bundle without any network access, for air-gapped machines
+    ///
+    /// Requires a local --source (bundled in place), its dependencies in the
+    /// cargo cache, a locally present builder image for container builds
+    /// (which run --isolated) and already downloaded AppImage tools, and
+    /// rejects --publish, --delta-from URLs, --smoke-test and COPR
+    /// uploads. Missing prerequisites are listed up front. Skips notarization.
+    #[arg(long, env = "KODEGEN_OFFLINE")]
+    pub offline: bool,
+
+    /// Build in containers without network access
+    ///
+    /// Dependencies are vendored with `cargo vendor` on the host and mounted
+    /// read-only with the source into a container started with
+    /// `--network none`. Tools the bundler would download, such as
+    /// linuxdeploy for AppImages, must already be in the builder image.
+    /// Requires a local container engine.
+    #[arg(long)]
+    pub isolated: bool,
+
+    /// Keep the temporary repository clone after the run, for debugging
+    ///
+    /// Implies `--no-cache`. Its path is printed; remove leftover clones
+    /// with the `clean` subcommand.
+    #[arg(long)]
+    pub keep_temp: bool,
+
+    /// Keep the stopped container when a container build fails
+    ///
+    /// Prints commands to inspect it; remove it afterwards with `docker rm`.
+    #[arg(long)]
+    pub keep_container: bool,
+
+    /// Save the build directory of a failed container build as a tarball
+    ///
+    /// Written as `<container>-build.tar` in the output directory.
+    #[arg(long)]
+    pub export_build: bool,
 }
 
-impl Args {
-    /// Parse command line arguments
-    pub fn parse_args() -> Self {
-        Self::parse()
+/// Where created artifacts are moved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactOutput {
+    /// The main artifact, at this exact path (`--output-binary`)
+    File(PathBuf),
+    /// Every artifact, under its own name, in this directory (`--output-dir`)
+    Directory(PathBuf),
+}
+
+impl ArtifactOutput {
+    /// The file or directory path given on the command line
+    pub fn path(&self) -> &Path {
+        match self {
+            ArtifactOutput::File(path) | ArtifactOutput::Directory(path) => path,
+        }
     }
+}
 
+impl Args {
     /// Validate arguments for consistency
     pub fn validate(&self) -> Result<(), String> {
         // Validate source format (basic validation - full validation happens during resolve)
@@ -56,18 +661,259 @@ impl Args {
             return Err("Source cannot be empty".to_string());
         }
 
-        // Validate platform
-        let valid_platforms = ["deb", "rpm", "dmg", "macos-bundle", "exe", "appimage"];
-        if !valid_platforms.contains(&self.platform.as_str()) {
-            return Err(format!(
-                "Invalid platform: {}. Valid platforms: {}",
-                self.platform,
-                valid_platforms.join(", ")
-            ));
+        if self.output_binary.is_none() && self.output_dir.is_none() {
+            return Err("Either --output-binary or --output-dir is required".to_string());
+        }
+
+        // Validate platform (clap guarantees either --platform or --all-platforms)
+        if let Some(platform) = &self.platform {
+            platform.parse::<PackageType>().map_err(|e| e.to_string())?;
+        }
+
+        let overrides = [
+            ("--product-name", &self.product_name),
+            ("--app-version", &self.app_version),
+        ];
+        for (flag, value) in overrides {
+            let Some(value) = value else { continue };
+            if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '/') {
+                return Err(format!("{} must be non-empty without spaces or '/': {:?}", flag, value));
+            }
+        }
+
+        self.download_sources()?;
+
+        if self.publish.contains(&PublishTarget::Ppa) {
+            if std::env::consts::OS != "linux" {
+                return Err("--publish ppa requires a Linux host".to_string());
+            }
+            if self.platform.as_deref().is_some_and(|p| p.parse() != Ok(PackageType::Deb)) {
+                return Err("--publish ppa is only supported with --platform deb".to_string());
+            }
+        }
+
+        if self.no_clone && !RepositorySource::parse(&self.source).is_ok_and(|source| source.is_local()) {
+            return Err("--no-clone requires a local --source path".to_string());
+        }
+
+        if let Some(target) = &self.target {
+            let is_windows = matches!(
+                self.platform
+                    .as_deref()
+                    .and_then(|p| p.parse::<PackageType>().ok()),
+                Some(PackageType::Exe | PackageType::Portable)
+            );
+            if !is_windows {
+                return Err(
+                    "--target is only supported with --platform exe or portable".to_string()
+                );
+            }
+            if !target.contains("-windows-") {
+                return Err(format!("--target {} is not a Windows target triple", target));
+            }
         }
 
         Ok(())
     }
+
+    /// Destination requested by `--output-binary` or `--output-dir`
+    pub fn artifact_output(&self) -> ArtifactOutput {
+        match (&self.output_dir, &self.output_binary) {
+            (Some(dir), _) => ArtifactOutput::Directory(dir.clone()),
+            (None, path) => ArtifactOutput::File(path.clone().unwrap_or_default()),
+        }
+    }
+
+    /// Pipeline phases to bypass, as requested by the `--skip-*` flags
+    pub fn skip_phases(&self) -> SkipPhases {
+        SkipPhases {
+            sign: self.skip_sign,
+            notarize: self.skip_notarize || self.offline,
+            dylib_bundling: self.skip_dylib_bundling,
+            customization: self.skip_customization,
+        }
+    }
+
+    /// How the source repository is checked out
+    pub fn checkout_options(&self) -> CheckoutOptions {
+        CheckoutOptions {
+            git_ref: self.git_ref.clone(),
+            cache: !self.no_cache && !self.keep_temp,
+            keep_temp: self.keep_temp,
+            in_place: self.no_clone || self.offline,
+        }
+    }
+
+    /// Builder container limits: detected defaults, overridden by `--docker-*` flags
+    pub fn container_limits(&self) -> crate::error::Result<ContainerLimits> {
+        let defaults = ContainerLimits::detect_safe_limits();
+        if self.docker_memory.is_none()
+            && self.docker_memory_swap.is_none()
+            && self.docker_cpus.is_none()
+            && self.docker_pids_limit.is_none()
+        {
+            return Ok(defaults);
+        }
+
+        // An explicit memory limit gets the default swap headroom on top of it
+        let memory_swap = match &self.docker_memory {
+            Some(_) => self.docker_memory_swap.clone(),
+            None => Some(self.docker_memory_swap.clone().unwrap_or(defaults.memory_swap)),
+        };
+        ContainerLimits::from_cli(
+            self.docker_memory.clone().unwrap_or(defaults.memory),
+            memory_swap,
+            Some(self.docker_cpus.clone().unwrap_or(defaults.cpus)),
+            self.docker_pids_limit.unwrap_or(defaults.pids_limit),
+        )
+        .map_err(|reason| BundlerError::Cli(CliError::InvalidArguments { reason }))
+    }
+
+    /// When a container build is killed, from `--docker-timeout` / `--docker-idle-timeout`
+    pub fn run_timeouts(&self) -> RunTimeouts {
+        let minutes = |value: u64| (value > 0).then(|| Duration::from_secs(value * 60));
+        let defaults = RunTimeouts::default();
+        RunTimeouts {
+            total: self.docker_timeout.map_or(defaults.total, minutes),
+            idle: self.docker_idle_timeout.map_or(defaults.idle, minutes),
+        }
+    }
+
+    /// Time limit for building the builder image (`--docker-build-timeout`)
+    ///
+    /// `None` when disabled with 0.
+    pub fn image_build_timeout(&self) -> Option<Duration> {
+        self.docker_build_timeout.map_or(Some(DOCKER_BUILD_TIMEOUT), |minutes| {
+            (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+        })
+    }
+
+    /// Retry policy for network operations (`--retries`, `--retry-delay`)
+    pub fn retry_config(&self) -> RetryConfig {
+        let defaults = RetryConfig::default();
+        let initial_delay = self.retry_delay.map_or(defaults.initial_delay, Duration::from_secs);
+        RetryConfig {
+            retries: self.retries.unwrap_or(defaults.retries),
+            initial_delay,
+            max_delay: defaults.max_delay.max(initial_delay),
+        }
+    }
+
+    /// Proxy and mirrors for tool downloads (`--download-proxy`, `--download-mirror`)
+    pub fn download_sources(&self) -> Result<DownloadSources, String> {
+        let check_url = |url: &str, flag: &str| {
+            url::Url::parse(url)
+                .map(drop)
+                .map_err(|e| format!("Invalid {} URL {}: {}", flag, url, e))
+        };
+        if let Some(proxy) = &self.download_proxy {
+            check_url(proxy, "--download-proxy")?;
+        }
+
+        let mut mirrors = std::collections::HashMap::new();
+        for mirror in &self.download_mirror {
+            let (tool, url) = mirror
+                .split_once('=')
+                .ok_or_else(|| format!("--download-mirror {} is not of the form TOOL=URL", mirror))?;
+            if !http::MIRRORED_TOOLS.contains(&tool) {
+                return Err(format!(
+                    "Unknown --download-mirror tool {} (expected one of: {})",
+                    tool,
+                    http::MIRRORED_TOOLS.join(", ")
+                ));
+            }
+            check_url(url, "--download-mirror")?;
+            mirrors.insert(tool.to_string(), url.to_string());
+        }
+
+        Ok(DownloadSources {
+            proxy: self.download_proxy.clone(),
+            mirrors,
+        })
+    }
+
+    /// Package validation requested by `--lint` / `--lint-strict`
+    pub fn lint_mode(&self) -> LintMode {
+        if self.lint_strict {
+            LintMode::Strict
+        } else if self.lint {
+            LintMode::Report
+        } else {
+            LintMode::Off
+        }
+    }
+
+    /// Flags to forward to the bundler running inside a Docker container
+    ///
+    /// The container invokes this same binary, so any flag that changes
+    /// bundling behavior must be passed through to take effect there.
+    pub fn container_passthrough_args(&self, color: ColorWhen) -> Vec<String> {
+        let mut forwarded = Vec::new();
+
+        // Container stdout is always a pipe, so resolve color against the host terminal
+        let color = match color.resolve(std::io::stdout().is_terminal()) {
+            termcolor::ColorChoice::Never => "never",
+            _ => "always",
+        };
+        forwarded.push(format!("--color={}", color));
+        forwarded.push(super::logging::passthrough_arg());
+
+        let flags = [
+            (self.skip_sign, "--skip-sign"),
+            (self.skip_notarize, "--skip-notarize"),
+            (self.skip_dylib_bundling, "--skip-dylib-bundling"),
+            (self.skip_customization, "--skip-customization"),
+            (self.lint, "--lint"),
+            (self.lint_strict, "--lint-strict"),
+            (self.sccache, "--sccache"),
+            (self.no_cache, "--no-cache"),
+            (self.keep_temp, "--keep-temp"),
+            (self.offline, "--offline"),
+            (self.artifact_json, "--artifact-json"),
+            // Unused inside the container, but reach `--all-platforms` children
+            (self.keep_container, "--keep-container"),
+            (self.export_build, "--export-build"),
+        ];
+        for (enabled, flag) in flags {
+            if enabled {
+                forwarded.push(flag.to_string());
+            }
+        }
+        if let Some(target) = &self.target {
+            forwarded.push(format!("--target={}", target));
+        }
+        if let Some(git_ref) = &self.git_ref {
+            forwarded.push(format!("--ref={}", git_ref));
+        }
+        // Unused inside the container, but reaches `--all-platforms` children
+        if let Some(image) = &self.docker_image {
+            forwarded.push(format!("--docker-image={}", image));
+        }
+        let limits = [
+            ("--docker-memory", self.docker_memory.clone()),
+            ("--docker-memory-swap", self.docker_memory_swap.clone()),
+            ("--docker-cpus", self.docker_cpus.clone()),
+            ("--docker-pids-limit", self.docker_pids_limit.map(|n| n.to_string())),
+            ("--docker-idle-timeout", self.docker_idle_timeout.map(|n| n.to_string())),
+            ("--docker-timeout", self.docker_timeout.map(|n| n.to_string())),
+            ("--docker-build-timeout", self.docker_build_timeout.map(|n| n.to_string())),
+            ("--retries", self.retries.map(|n| n.to_string())),
+            ("--retry-delay", self.retry_delay.map(|n| n.to_string())),
+            ("--download-proxy", self.download_proxy.clone()),
+            ("--product-name", self.product_name.clone()),
+            ("--app-version", self.app_version.clone()),
+        ];
+        for (flag, value) in limits {
+            if let Some(value) = value {
+                forwarded.push(format!("{}={}", flag, value));
+            }
+        }
+        for mirror in &self.download_mirror {
+            forwarded.push(format!("--download-mirror={}", mirror));
+        }
+
+        forwarded
+    }
 }
 
 /// Configuration derived from command line arguments
@@ -77,18 +923,29 @@ pub struct RuntimeConfig {
     output: super::OutputManager,
 }
 
-impl From<&Args> for RuntimeConfig {
-    fn from(_args: &Args) -> Self {
-        let output = super::OutputManager::new(
+impl RuntimeConfig {
+    /// Create a runtime configuration with the given color preference
+    pub fn with_color(color: ColorWhen) -> Self {
+        let output = super::OutputManager::with_color(
             true,  // Always verbose
             false, // Never quiet
+            color,
         );
 
         Self { output }
     }
-}
 
-impl RuntimeConfig {
+    /// Create a runtime configuration that suppresses progress output
+    pub fn quiet(color: ColorWhen) -> Self {
+        let output = super::OutputManager::with_color(true, true, color);
+        Self { output }
+    }
+
+    /// Returns the configured color mode
+    pub fn color(&self) -> ColorWhen {
+        self.output.color()
+    }
+
     /// Get a reference to the output manager
     pub fn output(&self) -> &super::OutputManager {
         &self.output
@@ -0,0 +1,415 @@
+//! `--all-platforms`: bundle every package type buildable from this host.
+//!
+//! Each platform runs as a child bundler process so builds are isolated
+//! (own clone, own cargo invocation) and the existing single-artifact
+//! contract applies to each of them. Native types run one after another
+//! while Docker types run concurrently with them, and the run ends with a
+//! summary of built, skipped and failed platforms.
+
+use super::dist_manifest::DistManifest;
+use super::publish::{GithubRelease, PublishTarget};
+use super::updater::Updater;
+use crate::bundler::PackageType;
+use crate::bundler::utils::interrupt;
+use crate::cli::args::{Args, RuntimeConfig};
+use crate::cli::docker::image::ensure_docker_running;
+use crate::cli::github_actions;
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::RepositorySource;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Result of one platform in an `--all-platforms` run
+#[derive(Debug)]
+enum Outcome {
+    Built(PathBuf),
+    Skipped(String),
+    Failed(String),
+}
+
+/// Bundle all platforms into the `--output-dir` (or `--output-binary`) directory.
+///
+/// Returns exit code 1 if any platform failed; skipped platforms do not
+/// count as failures.
+pub async fn execute_all_platforms(
+    args: &Args,
+    release: Option<&GithubRelease>,
+    updater: Option<&Updater>,
+    dist_manifest: Option<&DistManifest>,
+    runtime_config: &RuntimeConfig,
+) -> Result<i32> {
+    let output = args.artifact_output();
+    let output_dir = output.path();
+    if output_dir.is_file() {
+        return Err(BundlerError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "--all-platforms writes several artifacts; the output path must be a directory, not {}",
+                output_dir.display()
+            ),
+        }));
+    }
+    tokio::fs::create_dir_all(output_dir).await.map_err(|e| {
+        BundlerError::Cli(CliError::ExecutionFailed {
+            command: "create output directory".to_string(),
+            reason: format!("Failed to create {}: {}", output_dir.display(), e),
+        })
+    })?;
+
+    let stem = artifact_stem(
+        &args.source,
+        args.product_name.as_deref(),
+        args.app_version.as_deref(),
+    );
+    let docker_available = if PackageType::ALL.iter().any(super::needs_docker) {
+        ensure_docker_running(args.auto_start_docker, runtime_config)
+            .await
+            .is_ok()
+    } else {
+        false
+    };
+
+    let mut outcomes: Vec<(PackageType, Outcome)> = Vec::new();
+    let mut native = Vec::new();
+    let mut containerized = Vec::new();
+    for package_type in PackageType::ALL {
+        let uses_docker = super::needs_docker(&package_type);
+        match skip_reason(
+            package_type,
+            std::env::consts::OS,
+            uses_docker,
+            docker_available,
+        ) {
+            Some(reason) => outcomes.push((package_type, Outcome::Skipped(reason))),
+            None if uses_docker => containerized.push(package_type),
+            None => native.push(package_type),
+        }
+    }
+
+    runtime_config
+        .section(&format!(
+            "📦 Bundling {} platform(s): {} native, {} in Docker",
+            native.len() + containerized.len(),
+            native.len(),
+            containerized.len()
+        ))
+        .expect("Failed to write to stdout");
+
+    let (native_results, container_results) = tokio::join!(
+        run_group(args, native, &stem, runtime_config),
+        run_group(args, containerized, &stem, runtime_config)
+    );
+    outcomes.extend(native_results);
+    outcomes.extend(container_results);
+    outcomes
+        .sort_by_key(|(package_type, _)| PackageType::ALL.iter().position(|t| t == package_type));
+
+    print_summary(&outcomes, runtime_config);
+
+    // Artifact paths on stdout, one per line, as in single-platform mode
+    for (_, outcome) in &outcomes {
+        if let Outcome::Built(path) = outcome {
+            println!("{}", path.display());
+        }
+    }
+
+    let failed = outcomes
+        .iter()
+        .any(|(_, outcome)| matches!(outcome, Outcome::Failed(_)));
+
+    for (package_type, outcome) in &outcomes {
+        if let Outcome::Failed(reason) = outcome {
+            github_actions::error(
+                &format!("{} bundle failed", package_type.short_name()),
+                reason,
+            );
+        }
+    }
+    let built_outputs: Vec<_> = outcomes
+        .iter()
+        .filter_map(|(package_type, outcome)| match outcome {
+            Outcome::Built(path) => Some((package_type.short_name(), path.as_path())),
+            _ => None,
+        })
+        .collect();
+    github_actions::write_outputs(super::package_version(args).as_deref(), &built_outputs);
+
+    // A release or update missing some platforms is worse than none
+    if failed {
+        if release.is_some() || updater.is_some() || dist_manifest.is_some() || args.delta {
+            runtime_config
+                .warn("Not publishing because some platforms failed")
+                .expect("Failed to write to stdout");
+        }
+    } else {
+        let built: Vec<PathBuf> = built_outputs
+            .iter()
+            .map(|(_, path)| path.to_path_buf())
+            .collect();
+        let mut uploads = built.clone();
+        if args.delta {
+            uploads.extend(
+                super::delta::write_deltas(&built, &args.delta_from, runtime_config).await?,
+            );
+        }
+        if let Some(updater) = updater {
+            uploads.extend(
+                updater
+                    .write_manifest(&built, None, &args.artifact_output(), runtime_config)
+                    .await?,
+            );
+        }
+        if let Some(dist_manifest) = dist_manifest {
+            uploads.extend(
+                dist_manifest
+                    .write(&built, None, None, &args.artifact_output(), runtime_config)
+                    .await?,
+            );
+        }
+        if let Some(release) = release {
+            release.publish(&uploads, runtime_config).await?;
+        }
+    }
+    Ok(if failed { 1 } else { 0 })
+}
+
+/// Why `package_type` cannot be built from `host_os`, if it cannot
+fn skip_reason(
+    package_type: PackageType,
+    host_os: &str,
+    uses_docker: bool,
+    docker_available: bool,
+) -> Option<String> {
+    if package_type == PackageType::MacOsBundle {
+        return Some("included in the dmg".to_string());
+    }
+    if package_type.required_host_os() == "macos" && host_os != "macos" {
+        return Some("requires a macOS host (cannot be built in containers)".to_string());
+    }
+    if uses_docker && !docker_available {
+        return Some("Docker is not available".to_string());
+    }
+    None
+}
+
+/// Base file name for artifacts of `source`
+///
+/// Local sources use `<name>-<version>` from Cargo.toml; remote sources
+/// use the repository name, since their manifest is only known after
+/// cloning. `product_name` and `version` (`--product-name`,
+/// `--app-version`) take precedence.
+fn artifact_stem(source: &str, product_name: Option<&str>, version: Option<&str>) -> String {
+    let (name, manifest_version) = match RepositorySource::parse(source) {
+        Ok(RepositorySource::Local(path)) => {
+            match crate::metadata::load_manifest(&path.join("Cargo.toml")) {
+                Ok(manifest) => (manifest.metadata.name, Some(manifest.metadata.version)),
+                Err(_) => (
+                    path.canonicalize()
+                        .ok()
+                        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+                        .unwrap_or_else(|| "artifact".to_string()),
+                    None,
+                ),
+            }
+        }
+        Ok(RepositorySource::GitHub { repo, .. }) => (repo, None),
+        Ok(RepositorySource::GitHubUrl(url)) => (
+            url.trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .map(|name| name.trim_end_matches(".git").to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| "artifact".to_string()),
+            None,
+        ),
+        Err(_) => ("artifact".to_string(), None),
+    };
+
+    let name = product_name.map_or(name, str::to_string);
+    match version.map(str::to_string).or(manifest_version) {
+        Some(version) => format!("{}-{}", name, version),
+        None => name,
+    }
+}
+
+/// Run `types` one after another, writing `<output-dir>/<stem>.<ext>`
+async fn run_group(
+    args: &Args,
+    types: Vec<PackageType>,
+    stem: &str,
+    runtime_config: &RuntimeConfig,
+) -> Vec<(PackageType, Outcome)> {
+    let mut results = Vec::with_capacity(types.len());
+    for package_type in types {
+        let output_path =
+            args.artifact_output()
+                .path()
+                .join(format!("{}.{}", stem, package_type.extension()));
+        let outcome = run_platform(args, package_type, &output_path, runtime_config).await;
+        results.push((package_type, outcome));
+    }
+    results
+}
+
+/// Run the bundler for one platform in a child process
+///
+/// Child output is streamed with a `[platform]` prefix. The last line the
+/// child writes to stderr is kept as the failure reason.
+async fn run_platform(
+    args: &Args,
+    package_type: PackageType,
+    output_path: &Path,
+    runtime_config: &RuntimeConfig,
+) -> Outcome {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return Outcome::Failed(format!("cannot locate bundler executable: {}", e)),
+    };
+
+    let mut command = tokio::process::Command::new(exe);
+    command
+        .arg("--source")
+        .arg(&args.source)
+        .arg("--platform")
+        .arg(package_type.short_name())
+        .arg("--output-binary")
+        .arg(output_path)
+        .args(args.container_passthrough_args(runtime_config.color()))
+        // This process reports the combined results to GitHub Actions
+        .env_remove("GITHUB_ACTIONS")
+        .env_remove("GITHUB_OUTPUT");
+    if args.dev_sign {
+        command.arg("--dev-sign");
+    }
+    // Not a container passthrough flag: packages are tested from the host
+    if args.smoke_test {
+        command.arg("--smoke-test");
+    }
+    // Not container passthrough flags: the container gets its own source
+    if args.no_clone {
+        command.arg("--no-clone");
+    }
+    if args.isolated {
+        command.arg("--isolated");
+    }
+    // The source package is uploaded by the .deb run, after its checks
+    if package_type == PackageType::Deb && args.publish.contains(&PublishTarget::Ppa) {
+        command.args(["--publish", "ppa"]);
+    }
+    if let Some(dir) = &args.in_toto_dir {
+        command
+            .arg("--in-toto-dir")
+            .arg(dir.join(package_type.short_name()));
+        if let Some(key) = &args.in_toto_key {
+            command.arg("--in-toto-key").arg(key);
+        }
+    }
+
+    let mut child = match command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return Outcome::Failed(format!("failed to start bundler: {}", e)),
+    };
+    // The child cleans up after itself on SIGTERM
+    let _interrupt = interrupt::kill_on_interrupt(&child);
+
+    let prefix = format!("[{}]", package_type.short_name());
+    let (_, last_error) = tokio::join!(
+        async {
+            if let Some(stdout) = child.stdout.take() {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    runtime_config
+                        .indent(&format!("{} {}", prefix, line))
+                        .expect("Failed to write to stdout");
+                }
+            }
+        },
+        async {
+            let mut last = None;
+            if let Some(stderr) = child.stderr.take() {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    runtime_config
+                        .indent(&format!("{} {}", prefix, line))
+                        .expect("Failed to write to stdout");
+                    if !line.trim().is_empty() {
+                        last = Some(line.trim().to_string());
+                    }
+                }
+            }
+            last
+        }
+    );
+
+    match child.wait().await {
+        Ok(status) if status.success() && output_path.exists() => {
+            Outcome::Built(output_path.to_path_buf())
+        }
+        Ok(status) if status.success() => Outcome::Failed(format!(
+            "exited successfully but {} does not exist",
+            output_path.display()
+        )),
+        Ok(status) => Outcome::Failed(
+            last_error.unwrap_or_else(|| format!("exited with code {:?}", status.code())),
+        ),
+        Err(e) => Outcome::Failed(format!("failed to wait for bundler: {}", e)),
+    }
+}
+
+fn print_summary(outcomes: &[(PackageType, Outcome)], runtime_config: &RuntimeConfig) {
+    runtime_config
+        .section("📋 Summary")
+        .expect("Failed to write to stdout");
+    for (package_type, outcome) in outcomes {
+        let (status, detail) = match outcome {
+            Outcome::Built(path) => ("✓ built", path.display().to_string()),
+            Outcome::Skipped(reason) => ("- skipped", reason.clone()),
+            Outcome::Failed(reason) => ("✗ failed", reason.clone()),
+        };
+        runtime_config
+            .indent(&format!(
+                "{:<10} {:<10} {}",
+                package_type.short_name(),
+                status,
+                detail
+            ))
+            .expect("Failed to write to stdout");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_reason() {
+        assert!(skip_reason(PackageType::MacOsBundle, "macos", false, true).is_some());
+        assert!(skip_reason(PackageType::Dmg, "linux", true, true).is_some());
+        assert!(skip_reason(PackageType::Dmg, "macos", false, false).is_none());
+        assert!(skip_reason(PackageType::Deb, "macos", true, false).is_some());
+        assert!(skip_reason(PackageType::Deb, "macos", true, true).is_none());
+        assert!(skip_reason(PackageType::Deb, "linux", false, false).is_none());
+    }
+
+    #[test]
+    fn test_artifact_stem_remote() {
+        assert_eq!(artifact_stem("cyrup-ai/kodegen", None, None), "kodegen");
+        assert_eq!(
+            artifact_stem("https://github.com/cyrup-ai/kodegen.git", None, None),
+            "kodegen"
+        );
+        assert_eq!(
+            artifact_stem("https://github.com/cyrup-ai/kodegen/", None, None),
+            "kodegen"
+        );
+        assert_eq!(
+            artifact_stem("cyrup-ai/kodegen", Some("acme"), Some("1.2.3+build.45")),
+            "acme-1.2.3+build.45"
+        );
+    }
+}
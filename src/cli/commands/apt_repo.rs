@@ -0,0 +1,406 @@
+//! Flat APT repository generation.
+//!
+//! The `repo` subcommand turns a directory of .deb packages into a flat
+//! repository (`deb [signed-by=...] https://example.com/apt ./`) that can be
+//! uploaded as-is to S3, GitHub Pages or any static file host:
+//!
+//! ```text
+//! apt/
+//! ├── kodegen_1.0.0_amd64.deb
+//! ├── Packages
+//! ├── Packages.gz
+//! ├── Release
+//! ├── InRelease      (with --gpg-key)
+//! ├── Release.gpg    (with --gpg-key)
+//! └── key.gpg        (with --gpg-key)
+//! ```
+//!
+//! Signing shells out to `gpg`, so the key must be in the user's keyring
+//! (or the one selected by `GNUPGHOME`).
+
+use crate::cli::{RepoArgs, RuntimeConfig};
+use crate::error::{BundlerError, CliError, Result};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// A package index entry for one .deb
+#[derive(Debug)]
+struct PackageEntry {
+    /// Contents of the package's `control` file
+    control: String,
+    /// Path relative to the repository root
+    filename: String,
+    size: u64,
+    md5: String,
+    sha1: String,
+    sha256: String,
+}
+
+impl PackageEntry {
+    fn from_deb(path: &Path, filename: String) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        let control = read_control(&data).map_err(|reason| {
+            BundlerError::Cli(CliError::InvalidArguments {
+                reason: format!("{}: {}", path.display(), reason),
+            })
+        })?;
+        Ok(Self {
+            control,
+            filename,
+            size: data.len() as u64,
+            md5: format!("{:x}", md5::compute(&data)),
+            sha1: hex::encode(Sha1::digest(&data)),
+            sha256: hex::encode(Sha256::digest(&data)),
+        })
+    }
+
+    /// Value of a control field, if present
+    fn field(&self, name: &str) -> Option<&str> {
+        self.control.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    /// The entry's stanza in the Packages index
+    fn stanza(&self) -> String {
+        format!(
+            "{}\nFilename: {}\nSize: {}\nMD5sum: {}\nSHA1: {}\nSHA256: {}\n",
+            self.control.trim_end(),
+            self.filename,
+            self.size,
+            self.md5,
+            self.sha1,
+            self.sha256
+        )
+    }
+}
+
+/// Execute the `repo` subcommand
+pub async fn execute_repo(args: RepoArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    runtime_config
+        .section(&format!(
+            "📚 Building APT repository in {}",
+            args.output.display()
+        ))
+        .expect("Failed to write to stdout");
+
+    std::fs::create_dir_all(&args.output)?;
+
+    for deb in &args.debs {
+        let file_name = deb.file_name().ok_or_else(|| {
+            BundlerError::Cli(CliError::InvalidArguments {
+                reason: format!("Invalid package path: {}", deb.display()),
+            })
+        })?;
+        let dest = args.output.join(file_name);
+        if deb.canonicalize()? != dest.canonicalize().unwrap_or_default() {
+            std::fs::copy(deb, &dest)?;
+        }
+    }
+
+    // Index every package in the repository, not just the ones added now
+    let mut debs: Vec<_> = std::fs::read_dir(&args.output)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "deb"))
+        .collect();
+    debs.sort();
+
+    let mut entries = Vec::new();
+    for deb in &debs {
+        let name = deb.file_name().unwrap_or_default().to_string_lossy();
+        let entry = PackageEntry::from_deb(deb, format!("./{}", name))?;
+        runtime_config
+            .indent(&format!(
+                "✓ {} {} ({})",
+                entry.field("Package").unwrap_or("?"),
+                entry.field("Version").unwrap_or("?"),
+                entry.field("Architecture").unwrap_or("?")
+            ))
+            .expect("Failed to write to stdout");
+        entries.push(entry);
+    }
+
+    let packages = render_packages(&entries);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(packages.as_bytes())?;
+    let packages_gz = encoder.finish()?;
+
+    std::fs::write(args.output.join("Packages"), &packages)?;
+    std::fs::write(args.output.join("Packages.gz"), &packages_gz)?;
+
+    let architectures: BTreeSet<&str> = entries
+        .iter()
+        .filter_map(|entry| entry.field("Architecture"))
+        .collect();
+    let release = render_release(
+        &args,
+        &architectures.into_iter().collect::<Vec<_>>(),
+        &[
+            ("Packages", packages.as_bytes()),
+            ("Packages.gz", &packages_gz),
+        ],
+        chrono::Utc::now(),
+    );
+    let release_path = args.output.join("Release");
+    std::fs::write(&release_path, release)?;
+
+    match &args.gpg_key {
+        Some(key) => {
+            sign_release(&args.output, key).await?;
+            runtime_config
+                .success_println(&format!(
+                    "✓ Signed repository with {} ({} package(s))",
+                    key,
+                    entries.len()
+                ))
+                .expect("Failed to write to stdout");
+        }
+        None => {
+            runtime_config
+                .warn("Repository is unsigned; apt only accepts it with [trusted=yes]")
+                .expect("Failed to write to stdout");
+            runtime_config
+                .success_println(&format!(
+                    "✓ Wrote repository ({} package(s))",
+                    entries.len()
+                ))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    Ok(0)
+}
+
+fn render_packages(entries: &[PackageEntry]) -> String {
+    entries
+        .iter()
+        .map(PackageEntry::stanza)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Hex digest function for a Release checksum field
+type HashFn = fn(&[u8]) -> String;
+
+fn render_release(
+    args: &RepoArgs,
+    architectures: &[&str],
+    indices: &[(&str, &[u8])],
+    date: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let mut release = String::new();
+    if let Some(origin) = &args.origin {
+        release.push_str(&format!("Origin: {}\n", origin));
+    }
+    if let Some(label) = &args.label {
+        release.push_str(&format!("Label: {}\n", label));
+    }
+    release.push_str(&format!("Suite: {}\n", args.suite));
+    release.push_str(&format!(
+        "Date: {}\n",
+        date.format("%a, %d %b %Y %H:%M:%S UTC")
+    ));
+    if !architectures.is_empty() {
+        release.push_str(&format!("Architectures: {}\n", architectures.join(" ")));
+    }
+
+    let hashes: [(&str, HashFn); 3] = [
+        ("MD5Sum", |data| format!("{:x}", md5::compute(data))),
+        ("SHA1", |data| hex::encode(Sha1::digest(data))),
+        ("SHA256", |data| hex::encode(Sha256::digest(data))),
+    ];
+    for (field, hash) in hashes {
+        release.push_str(&format!("{}:\n", field));
+        for (name, data) in indices {
+            release.push_str(&format!(" {} {:>8} {}\n", hash(data), data.len(), name));
+        }
+    }
+    release
+}
+
+/// Writes InRelease, Release.gpg and the exported public key
+async fn sign_release(repo: &Path, key: &str) -> Result<()> {
+    let steps: [(&str, &[&str]); 3] = [
+        ("InRelease", &["--clearsign"]),
+        ("Release.gpg", &["--armor", "--detach-sign"]),
+        ("key.gpg", &["--export"]),
+    ];
+    for (output, action) in steps {
+        let mut command = tokio::process::Command::new("gpg");
+        command
+            .current_dir(repo)
+            .args(["--batch", "--yes", "--local-user", key, "--output", output])
+            .args(action);
+        if output == "key.gpg" {
+            command.arg(key);
+        } else {
+            command.arg("Release");
+        }
+
+        let result = command.output().await.map_err(|e| {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command: "gpg".to_string(),
+                reason: e.to_string(),
+            })
+        })?;
+        if !result.status.success() {
+            return Err(BundlerError::Cli(CliError::ExecutionFailed {
+                command: format!("gpg {}", action.join(" ")),
+                reason: String::from_utf8_lossy(&result.stderr).trim().to_string(),
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Extracts the `control` file from a .deb (an ar archive)
+fn read_control(deb: &[u8]) -> std::result::Result<String, String> {
+    let mut rest = deb
+        .strip_prefix(b"!<arch>\n")
+        .ok_or("not a Debian package (missing ar header)")?;
+
+    while rest.len() >= 60 {
+        let (header, body) = rest.split_at(60);
+        let name = String::from_utf8_lossy(&header[..16]);
+        let name = name.trim_end().trim_end_matches('/');
+        let size: usize = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse()
+            .map_err(|_| format!("corrupt ar member header for {}", name))?;
+        let member = body
+            .get(..size)
+            .ok_or_else(|| format!("truncated ar member {}", name))?;
+
+        match name {
+            "control.tar.gz" => return control_from_tar(GzDecoder::new(member)),
+            "control.tar.xz" => return control_from_tar(liblzma::read::XzDecoder::new(member)),
+            "control.tar.zst" => {
+                let decoder = zstd::Decoder::new(member).map_err(|e| e.to_string())?;
+                return control_from_tar(decoder);
+            }
+            "control.tar" => return control_from_tar(member),
+            _ if name.starts_with("control.tar") => {
+                return Err(format!(
+                    "unsupported control archive compression ({})",
+                    name
+                ));
+            }
+            _ => {}
+        }
+        // Members are padded to an even size
+        rest = body.get(size + size % 2..).unwrap_or_default();
+    }
+    Err("no control archive found".to_string())
+}
+
+fn control_from_tar(reader: impl Read) -> std::result::Result<String, String> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path().map_err(|e| e.to_string())?;
+        if path.to_string_lossy().trim_start_matches("./") == "control" {
+            let mut control = String::new();
+            entry
+                .read_to_string(&mut control)
+                .map_err(|e| e.to_string())?;
+            return Ok(control);
+        }
+    }
+    Err("control archive has no control file".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a minimal .deb with the given control file
+    fn control_tar(control: &str) -> Vec<u8> {
+        let mut tar = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(control.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, "./control", control.as_bytes())
+            .unwrap();
+        tar.into_inner().unwrap()
+    }
+
+    fn deb_with_control(control: &str) -> Vec<u8> {
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&control_tar(control)).unwrap();
+        deb_with_member("control.tar.gz", &gz.finish().unwrap())
+    }
+
+    fn deb_with_member(member: &str, control_archive: &[u8]) -> Vec<u8> {
+        let mut deb = b"!<arch>\n".to_vec();
+        for (name, data) in [("debian-binary", &b"2.0\n"[..]), (member, control_archive)] {
+            deb.extend(
+                format!(
+                    "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                    name,
+                    0,
+                    0,
+                    0,
+                    100644,
+                    data.len()
+                )
+                .bytes(),
+            );
+            deb.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                deb.push(b'\n');
+            }
+        }
+        deb
+    }
+
+    #[test]
+    fn test_read_control() {
+        let control = "Package: kodegen\nVersion: 1.0.0\nArchitecture: amd64\n";
+        assert_eq!(read_control(&deb_with_control(control)).unwrap(), control);
+        assert!(read_control(b"not a deb").is_err());
+    }
+
+    #[test]
+    fn test_read_control_xz_zst() {
+        let control = "Package: kodegen\nVersion: 1.0.0\nArchitecture: arm64\n";
+
+        let mut xz = liblzma::write::XzEncoder::new(Vec::new(), 6);
+        xz.write_all(&control_tar(control)).unwrap();
+        let deb = deb_with_member("control.tar.xz", &xz.finish().unwrap());
+        assert_eq!(read_control(&deb).unwrap(), control);
+
+        let zst = zstd::encode_all(&control_tar(control)[..], 19).unwrap();
+        assert_eq!(
+            read_control(&deb_with_member("control.tar.zst", &zst)).unwrap(),
+            control
+        );
+
+        let err = read_control(&deb_with_member("control.tar.bz2", b"")).unwrap_err();
+        assert!(err.contains("control.tar.bz2"), "{}", err);
+    }
+
+    #[test]
+    fn test_package_stanza() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kodegen_1.0.0_amd64.deb");
+        std::fs::write(
+            &path,
+            deb_with_control("Package: kodegen\nArchitecture: amd64\n"),
+        )
+        .unwrap();
+
+        let entry = PackageEntry::from_deb(&path, "./kodegen_1.0.0_amd64.deb".into()).unwrap();
+        assert_eq!(entry.field("Architecture"), Some("amd64"));
+        let stanza = entry.stanza();
+        assert!(stanza.starts_with(
+            "Package: kodegen\nArchitecture: amd64\nFilename: ./kodegen_1.0.0_amd64.deb\n"
+        ));
+        assert!(stanza.contains(&format!("SHA256: {}\n", entry.sha256)));
+    }
+}
@@ -0,0 +1,72 @@
+//! Artifact metadata files.
+//!
+//! `--artifact-json` writes the [`BundledArtifact`] of each package next to
+//! its main output as `<output file>.artifact.json`, for downstream
+//! automation. Paths in the file are relative to its directory, so it stays
+//! valid when the output directory is copied out of a container or uploaded.
+
+use crate::bundler::BundledArtifact;
+use crate::cli::args::RuntimeConfig;
+use crate::error::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Writes the metadata of `artifacts`, whose files were moved from
+/// `built[i]` to `outputs[i]`, and returns the written files.
+pub async fn write(
+    artifacts: &[BundledArtifact],
+    built: &[PathBuf],
+    outputs: &[PathBuf],
+    runtime_config: &RuntimeConfig,
+) -> Result<Vec<PathBuf>> {
+    let moved: HashMap<&Path, &Path> = built
+        .iter()
+        .map(PathBuf::as_path)
+        .zip(outputs.iter().map(PathBuf::as_path))
+        .collect();
+
+    let mut written = Vec::new();
+    for artifact in artifacts {
+        let output_paths: Vec<&Path> = artifact
+            .paths
+            .iter()
+            .filter_map(|path| moved.get(path.as_path()).copied())
+            .collect();
+        let Some(main) = output_paths.first() else {
+            continue;
+        };
+
+        let mut metadata = artifact.clone();
+        metadata.paths = output_paths
+            .iter()
+            .filter_map(|path| path.file_name().map(PathBuf::from))
+            .collect();
+        let json_path = json_path(main);
+        tokio::fs::write(&json_path, serde_json::to_vec_pretty(&metadata)?).await?;
+        runtime_config
+            .verbose_println(&format!("   ✓ Artifact metadata written to {}", json_path.display()))
+            .expect("Failed to write to stdout");
+        written.push(json_path);
+    }
+    Ok(written)
+}
+
+/// Metadata file of the artifact at `output`
+fn json_path(output: &Path) -> PathBuf {
+    let mut name = output.file_name().unwrap_or_default().to_os_string();
+    name.push(".artifact.json");
+    output.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_path() {
+        assert_eq!(
+            json_path(Path::new("/dist/kodegen_1.0.0_amd64.deb")),
+            PathBuf::from("/dist/kodegen_1.0.0_amd64.deb.artifact.json")
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Removal of leftover temporary clones and build caches.
+//!
+//! Runs without the clone cache clone the source into
+//! `$TMPDIR/kodegen-bundle-<uuid>` and remove it afterwards, but clones
+//! survive `--keep-temp` and killed bundlers. The `clean` subcommand removes
+//! them and the clone cache, except clones still locked by a running
+//! bundler, the downloaded tool cache (AppImage tools, WiX, NSIS, ...)
+//! and the Docker volumes caching cargo downloads, build output and
+//! sccache entries between container runs.
+
+use super::gc::{format_size, scan_root};
+use crate::bundler::utils::tools;
+use crate::cli::docker::image::check_docker_available;
+use crate::cli::docker::runtime::runtime;
+use crate::cli::docker::{CARGO_CACHE_VOLUME, SCCACHE_VOLUME, TARGET_CACHE_VOLUME};
+use crate::cli::{CleanArgs, RuntimeConfig};
+use crate::error::Result;
+use crate::source::{CLONE_PREFIX, clone_cache_dir, clone_in_use, lock_path};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// Execute the `clean` subcommand
+pub async fn execute_clean(args: CleanArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    let action = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removing"
+    };
+    let mut failures = 0usize;
+    let mut freed = 0u64;
+
+    runtime_config
+        .section("🧹 Removing temporary and cached clones")
+        .expect("Failed to write to stdout");
+    let temp_dir = std::env::temp_dir();
+    let cache_dir = clone_cache_dir().filter(|dir| dir.is_dir());
+    let mut clones = scan_root(&temp_dir, Some(CLONE_PREFIX))?;
+    if let Some(cache_dir) = &cache_dir {
+        clones.extend(
+            scan_root(cache_dir, None)?
+                .into_iter()
+                .filter(|entry| entry.path.is_dir() && !clone_in_use(&entry.path)),
+        );
+    }
+
+    for clone in clones {
+        runtime_config
+            .indent(&format!(
+                "{} {} ({})",
+                action,
+                clone.path.display(),
+                format_size(clone.size)
+            ))
+            .expect("Failed to write to stdout");
+        if args.dry_run {
+            freed += clone.size;
+            continue;
+        }
+
+        match tokio::fs::remove_dir_all(&clone.path).await {
+            Ok(()) => {
+                freed += clone.size;
+                let _ = tokio::fs::remove_file(lock_path(&clone.path)).await;
+            }
+            Err(e) => {
+                failures += 1;
+                runtime_config
+                    .warn(&format!("Failed to remove {}: {}", clone.path.display(), e))
+                    .expect("Failed to write to stdout");
+            }
+        }
+    }
+    let mut locks = orphaned_locks(&temp_dir, Some(CLONE_PREFIX))?;
+    if let Some(cache_dir) = &cache_dir {
+        locks.extend(orphaned_locks(cache_dir, None)?);
+    }
+    for lock in locks {
+        if !args.dry_run {
+            let _ = tokio::fs::remove_file(&lock).await;
+        }
+    }
+
+    runtime_config
+        .section("🧰 Removing downloaded tools")
+        .expect("Failed to write to stdout");
+    let tools_dir = tools::cache_dir().filter(|dir| dir.is_dir());
+    let downloads = match &tools_dir {
+        Some(dir) => scan_root(dir, None)?,
+        None => Vec::new(),
+    };
+    for tool in downloads {
+        runtime_config
+            .indent(&format!(
+                "{} {} ({})",
+                action,
+                tool.path.display(),
+                format_size(tool.size)
+            ))
+            .expect("Failed to write to stdout");
+        if args.dry_run {
+            freed += tool.size;
+            continue;
+        }
+
+        let removed = if tool.path.is_dir() {
+            tokio::fs::remove_dir_all(&tool.path).await
+        } else {
+            tokio::fs::remove_file(&tool.path).await
+        };
+        match removed {
+            Ok(()) => freed += tool.size,
+            Err(e) => {
+                failures += 1;
+                runtime_config
+                    .warn(&format!("Failed to remove {}: {}", tool.path.display(), e))
+                    .expect("Failed to write to stdout");
+            }
+        }
+    }
+
+    let verb = if args.dry_run { "Would free" } else { "Freed" };
+    runtime_config
+        .success_println(&format!("{} {}", verb, format_size(freed)))
+        .expect("Failed to write to stdout");
+
+    if args.no_docker {
+        return Ok(if failures == 0 { 0 } else { 1 });
+    }
+
+    runtime_config
+        .section("🐳 Removing Docker build caches")
+        .expect("Failed to write to stdout");
+    if let Err(e) = check_docker_available().await {
+        runtime_config
+            .warn(&format!("Skipping cache volumes: {}", e))
+            .expect("Failed to write to stdout");
+        return Ok(if failures == 0 { 0 } else { 1 });
+    }
+
+    for volume in [CARGO_CACHE_VOLUME, TARGET_CACHE_VOLUME, SCCACHE_VOLUME] {
+        if !volume_exists(volume).await {
+            runtime_config
+                .verbose_println(&format!("   {} does not exist", volume))
+                .expect("Failed to write to stdout");
+            continue;
+        }
+        runtime_config
+            .indent(&format!("{} volume {}", action, volume))
+            .expect("Failed to write to stdout");
+        if args.dry_run {
+            continue;
+        }
+
+        let output = runtime()
+            .command()
+            .args(["volume", "rm", volume])
+            .stdout(Stdio::null())
+            .output()
+            .await?;
+        if !output.status.success() {
+            failures += 1;
+            runtime_config
+                .warn(&format!(
+                    "Failed to remove volume {}: {}",
+                    volume,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    Ok(if failures == 0 { 0 } else { 1 })
+}
+
+/// Lock files in `dir`, optionally filtered by name prefix, whose clone no longer exists
+fn orphaned_locks(dir: &Path, prefix: Option<&str>) -> Result<Vec<PathBuf>> {
+    let mut locks = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(clone) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .filter(|n| prefix.is_none_or(|prefix| n.starts_with(prefix)))
+            .and_then(|n| n.strip_suffix(".lock"))
+            .map(|n| dir.join(n))
+        else {
+            continue;
+        };
+        if path.is_file() && !clone.exists() && !clone_in_use(&clone) {
+            locks.push(path);
+        }
+    }
+    Ok(locks)
+}
+
+/// Whether the Docker volume `name` exists
+async fn volume_exists(name: &str) -> bool {
+    runtime()
+        .command()
+        .args(["volume", "inspect", name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
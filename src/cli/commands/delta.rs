@@ -0,0 +1,196 @@
+//! Delta update artifacts.
+//!
+//! `--delta` adds artifacts that let clients update without downloading the
+//! full package:
+//!
+//! - AppImage: a `.zsync` control file from `zsyncmake`, unless the AppImage
+//!   bundler wrote one for its update information. zsync and AppImageUpdate fetch only the
+//!   blocks that differ from the copy already installed, so no previous
+//!   release is needed.
+//! - NSIS setup and DMG: a classic `BSDIFF40` patch (apply with `bspatch`)
+//!   from every `--delta-from` artifact with the same extension, named
+//!   `<new file>.from-<old file stem>.bsdiff`.
+
+use crate::cli::args::RuntimeConfig;
+use crate::error::{BundlerError, CliError, Result};
+use std::path::{Path, PathBuf};
+
+/// Writes delta artifacts for `artifacts` next to them.
+///
+/// `previous` lists earlier releases as paths or http(s) URLs. Returns the
+/// generated files.
+pub async fn write_deltas(
+    artifacts: &[PathBuf],
+    previous: &[String],
+    runtime_config: &RuntimeConfig,
+) -> Result<Vec<PathBuf>> {
+    runtime_config
+        .section("📉 Generating delta updates")
+        .expect("Failed to write to stdout");
+
+    let download_dir = tempfile::tempdir()?;
+    let mut previous_paths = Vec::with_capacity(previous.len());
+    for source in previous {
+        previous_paths.push(fetch_previous(source, download_dir.path()).await?);
+    }
+
+    let mut generated = Vec::new();
+    for artifact in artifacts {
+        match extension(artifact).as_str() {
+            #[cfg(target_os = "linux")]
+            "appimage" => {
+                // AppImages with update information already come with one
+                let existing = PathBuf::from(format!("{}.zsync", artifact.display()));
+                if artifacts.contains(&existing) {
+                    continue;
+                }
+                let zsync =
+                    crate::bundler::platform::linux::appimage::ensure_zsync(artifact).await?;
+                runtime_config
+                    .indent(&format!("✓ {}", zsync.display()))
+                    .expect("Failed to write to stdout");
+                generated.push(zsync);
+            }
+            #[cfg(not(target_os = "linux"))]
+            "appimage" => {
+                let existing = PathBuf::from(format!("{}.zsync", artifact.display()));
+                if !artifacts.contains(&existing) {
+                    runtime_config
+                        .warn(&format!(
+                            "Cannot generate {} on this host; zsyncmake requires Linux",
+                            existing.display()
+                        ))
+                        .expect("Failed to write to stdout");
+                }
+            }
+            ext @ ("exe" | "dmg") => {
+                let sources: Vec<_> = previous_paths
+                    .iter()
+                    .filter(|old| extension(old) == ext)
+                    .collect();
+                if sources.is_empty() {
+                    runtime_config
+                        .verbose_println(&format!(
+                            "   No --delta-from artifact for {}; skipping patch",
+                            artifact.display()
+                        ))
+                        .expect("Failed to write to stdout");
+                }
+                for old in sources {
+                    let (old, new) = (old.clone(), artifact.clone());
+                    let (patch, size) =
+                        tokio::task::spawn_blocking(move || write_patch(&old, &new))
+                            .await
+                            .map_err(|e| delta_error(format!("bsdiff task failed: {}", e)))??;
+                    let full_size = tokio::fs::metadata(artifact).await?.len();
+                    runtime_config
+                        .indent(&format!(
+                            "✓ {} ({} of {} bytes)",
+                            patch.display(),
+                            size,
+                            full_size
+                        ))
+                        .expect("Failed to write to stdout");
+                    generated.push(patch);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if generated.is_empty() {
+        runtime_config
+            .warn("No delta artifacts generated")
+            .expect("Failed to write to stdout");
+    }
+    Ok(generated)
+}
+
+/// Lowercase file extension
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Resolves a previous artifact, downloading URLs into `dir`
+async fn fetch_previous(source: &str, dir: &Path) -> Result<PathBuf> {
+    if !(source.starts_with("https://") || source.starts_with("http://")) {
+        let path = PathBuf::from(source);
+        if !path.is_file() {
+            return Err(delta_error(format!(
+                "previous artifact {} does not exist",
+                source
+            )));
+        }
+        return Ok(path);
+    }
+
+    let name = source
+        .split(['?', '#'])
+        .next()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| delta_error(format!("cannot derive a file name from {}", source)))?;
+    let data = crate::bundler::utils::http::download(source).await?;
+
+    let path = dir.join(name);
+    tokio::fs::write(&path, data).await?;
+    Ok(path)
+}
+
+/// Writes the patch from `old` to `new`, returning its path and size
+fn write_patch(old: &Path, new: &Path) -> Result<(PathBuf, u64)> {
+    let old_data = std::fs::read(old)?;
+    let new_data = std::fs::read(new)?;
+    let mut patch = Vec::new();
+    qbsdiff::Bsdiff::new(&old_data, &new_data).compare(&mut patch)?;
+
+    let new_name = new.file_name().unwrap_or_default().to_string_lossy();
+    let old_stem = old.file_stem().unwrap_or_default().to_string_lossy();
+    let patch_path = new.with_file_name(format!("{}.from-{}.bsdiff", new_name, old_stem));
+    std::fs::write(&patch_path, &patch)?;
+    Ok((patch_path, patch.len() as u64))
+}
+
+fn delta_error(reason: impl Into<String>) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "delta".to_string(),
+        reason: reason.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_patch() {
+        let dir = tempfile::tempdir().unwrap();
+        let old: Vec<u8> = (0..20_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        let mut new = old.clone();
+        new[100..110].copy_from_slice(b"0123456789");
+        new.splice(5000..5000, b"inserted".iter().copied());
+        new.truncate(18_000);
+        new.extend_from_slice(b"appended tail");
+
+        let old_path = dir.path().join("app-1.0.dmg");
+        let new_path = dir.path().join("app-1.1.dmg");
+        std::fs::write(&old_path, &old).unwrap();
+        std::fs::write(&new_path, &new).unwrap();
+
+        let (patch_path, size) = write_patch(&old_path, &new_path).unwrap();
+        assert_eq!(patch_path, dir.path().join("app-1.1.dmg.from-app-1.0.bsdiff"));
+        let patch = std::fs::read(&patch_path).unwrap();
+        assert_eq!(patch.len() as u64, size);
+        assert!(patch.starts_with(b"BSDIFF40"));
+        assert!(patch.len() < new.len() / 4);
+
+        let mut patched = Vec::new();
+        qbsdiff::Bspatch::new(&patch)
+            .unwrap()
+            .apply(&old, &mut patched)
+            .unwrap();
+        assert_eq!(patched, new);
+    }
+}
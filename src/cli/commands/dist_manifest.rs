@@ -0,0 +1,255 @@
+//! cargo-dist compatible release manifest (`dist-manifest.json`).
+//!
+//! `--dist-manifest` describes the artifacts of a run in the format
+//! cargo-dist writes, so tooling built around it (axoupdater, installer
+//! generators, release dashboards) can consume bundler output directly.
+//! Every distributable file is listed with its kind, target triple, SHA-256
+//! checksum and an install hint:
+//!
+//! | Artifact | Kind | Install hint |
+//! |----------|------|--------------|
+//! | deb | `installer` | `sudo apt install ./<file>` |
+//! | rpm | `installer` | `sudo dnf install ./<file>` |
+//! | AppImage | `installer` | `chmod +x <file> && ./<file>` |
+//! | dmg | `installer` | `open <file>` |
+//! | NSIS setup | `installer` | `.\<file>` |
+//! | portable zip | `executable-zip` | `Expand-Archive <file>` |
+//!
+//! `.app` directories are not downloadable and are left out.
+
+use super::publish::GithubRelease;
+use crate::cli::args::{Args, ArtifactOutput, RuntimeConfig};
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::RepositorySource;
+use serde_json::{Map, json};
+use std::path::{Path, PathBuf};
+
+/// Name of the written manifest
+const MANIFEST_NAME: &str = "dist-manifest.json";
+
+/// cargo-dist release whose manifest format is written
+const DIST_VERSION: &str = "0.28.0";
+
+/// Writes `dist-manifest.json`
+pub struct DistManifest {
+    /// Target triple of Windows artifacts (they may be cross-compiled)
+    windows_target: String,
+    /// App name used when the caller does not know the manifest name
+    fallback_name: Option<String>,
+    /// Version used when the caller does not know the manifest version
+    fallback_version: Option<String>,
+    /// Release tag and artifact download URL with `--publish github`
+    release: Option<(String, String)>,
+}
+
+impl DistManifest {
+    /// Settings for `--dist-manifest`; `None` without the flag
+    pub fn resolve(args: &Args, release: Option<&GithubRelease>) -> Option<Self> {
+        if !args.dist_manifest {
+            return None;
+        }
+        let local_name = match RepositorySource::parse(&args.source) {
+            Ok(RepositorySource::Local(path)) => crate::metadata::load_manifest(&path.join("Cargo.toml"))
+                .ok()
+                .map(|manifest| manifest.metadata.name),
+            _ => None,
+        };
+        Some(Self {
+            windows_target: args
+                .target
+                .clone()
+                .unwrap_or_else(|| super::DEFAULT_WINDOWS_TARGET.to_string()),
+            fallback_name: args.product_name.clone().or(local_name),
+            fallback_version: super::package_version(args).or_else(|| {
+                release.map(|release| release.tag().trim_start_matches('v').to_string())
+            }),
+            release: release.map(|release| (release.tag().to_string(), release.download_base())),
+        })
+    }
+
+    /// Writes the manifest for `artifacts` beside the output and returns it
+    /// so it can be published with the artifacts
+    pub async fn write(
+        &self,
+        artifacts: &[PathBuf],
+        name: Option<&str>,
+        version: Option<&str>,
+        output: &ArtifactOutput,
+        runtime_config: &RuntimeConfig,
+    ) -> Result<Vec<PathBuf>> {
+        let name = name
+            .map(String::from)
+            .or_else(|| self.fallback_name.clone())
+            .ok_or_else(|| {
+                dist_error("cannot determine the app name for dist-manifest.json; use a local source or --product-name")
+            })?;
+        let version = version
+            .map(String::from)
+            .or_else(|| self.fallback_version.clone())
+            .ok_or_else(|| {
+                dist_error(
+                    "cannot determine the version for dist-manifest.json; use a local source, --app-version or --publish with a release tag",
+                )
+            })?;
+
+        let host_arch = std::env::consts::ARCH;
+        let mut entries = Map::new();
+        for artifact in artifacts {
+            let Some(target) = dist_target(artifact) else {
+                runtime_config
+                    .verbose_println(&format!(
+                        "   {} is not a downloadable artifact; not listed in {}",
+                        artifact.display(),
+                        MANIFEST_NAME
+                    ))
+                    .expect("Failed to write to stdout");
+                continue;
+            };
+            let file_name = artifact
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .ok_or_else(|| dist_error(format!("invalid artifact path {}", artifact.display())))?;
+            let triple = match target.os {
+                "windows" => self.windows_target.clone(),
+                "macos" => format!("{}-apple-darwin", host_arch),
+                _ => format!("{}-unknown-linux-gnu", host_arch),
+            };
+            let path = artifact.clone();
+            let sha256 = tokio::task::spawn_blocking(move || super::in_toto::hash_file(&path))
+                .await
+                .map_err(|e| dist_error(format!("hashing task failed: {}", e)))??;
+
+            entries.insert(
+                file_name.clone(),
+                json!({
+                    "name": file_name,
+                    "kind": target.kind,
+                    "target_triples": [triple],
+                    "install_hint": target.install_hint.replace("{}", &file_name),
+                    "description": target.description,
+                    "checksums": { "sha256": sha256 },
+                }),
+            );
+        }
+
+        let (tag, hosting) = match &self.release {
+            Some((tag, download_base)) => (
+                tag.clone(),
+                json!({ "github": { "artifact_download_url": download_base } }),
+            ),
+            None => (format!("v{}", version), json!({})),
+        };
+        let is_prerelease = semver::Version::parse(&version).is_ok_and(|v| !v.pre.is_empty());
+        let manifest = json!({
+            "dist_version": DIST_VERSION,
+            "announcement_tag": tag,
+            "announcement_tag_is_implicit": self.release.is_none(),
+            "announcement_is_prerelease": is_prerelease,
+            "announcement_title": tag,
+            "releases": [{
+                "app_name": name,
+                "app_version": version,
+                "artifacts": entries.keys().collect::<Vec<_>>(),
+                "hosting": hosting,
+            }],
+            "artifacts": entries,
+        });
+
+        let dir = match output {
+            ArtifactOutput::Directory(dir) => dir.as_path(),
+            ArtifactOutput::File(path) => path.parent().unwrap_or(Path::new(".")),
+        };
+        let manifest_path = dir.join(MANIFEST_NAME);
+        tokio::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?).await?;
+
+        runtime_config
+            .success_println(&format!("✓ dist manifest at: {}", manifest_path.display()))
+            .expect("Failed to write to stdout");
+        Ok(vec![manifest_path])
+    }
+}
+
+/// How cargo-dist consumers should treat an artifact
+#[derive(Debug, PartialEq, Eq)]
+struct DistTarget {
+    os: &'static str,
+    kind: &'static str,
+    description: &'static str,
+    /// `{}` is replaced with the file name
+    install_hint: &'static str,
+}
+
+/// cargo-dist description of an artifact, if it is a downloadable file
+fn dist_target(path: &Path) -> Option<DistTarget> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    let (os, kind, description, install_hint) = match extension.as_str() {
+        "deb" => ("linux", "installer", "Install with apt", "sudo apt install ./{}"),
+        "rpm" => ("linux", "installer", "Install with dnf", "sudo dnf install ./{}"),
+        "appimage" => ("linux", "installer", "Run the AppImage", "chmod +x {} && ./{}"),
+        "dmg" => ("macos", "installer", "Open the disk image", "open {}"),
+        "exe" => ("windows", "installer", "Run the setup", ".\\{}"),
+        "zip" => ("windows", "executable-zip", "Extract the portable app", "Expand-Archive {}"),
+        _ => return None,
+    };
+    Some(DistTarget {
+        os,
+        kind,
+        description,
+        install_hint,
+    })
+}
+
+fn dist_error(reason: impl Into<String>) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "dist-manifest".to_string(),
+        reason: reason.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dist_target() {
+        let deb = dist_target(Path::new("app_1.0.0_amd64.deb")).unwrap();
+        assert_eq!((deb.os, deb.kind), ("linux", "installer"));
+        assert_eq!(dist_target(Path::new("App.AppImage")).unwrap().os, "linux");
+        assert_eq!(dist_target(Path::new("app.zip")).unwrap().kind, "executable-zip");
+        assert_eq!(dist_target(Path::new("App.app")), None);
+    }
+
+    #[tokio::test]
+    async fn test_write_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let deb = dir.path().join("app_1.0.0-rc.1_amd64.deb");
+        std::fs::write(&deb, b"deb").unwrap();
+        let app = dir.path().join("App.app");
+        std::fs::create_dir(&app).unwrap();
+
+        let dist = DistManifest {
+            windows_target: super::super::DEFAULT_WINDOWS_TARGET.to_string(),
+            fallback_name: None,
+            fallback_version: None,
+            release: None,
+        };
+        let output = ArtifactOutput::Directory(dir.path().to_path_buf());
+        let runtime_config = RuntimeConfig::with_color(crate::cli::ColorWhen::Never);
+        let written = dist
+            .write(&[deb, app], Some("app"), Some("1.0.0-rc.1"), &output, &runtime_config)
+            .await
+            .unwrap();
+
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&written[0]).unwrap()).unwrap();
+        assert_eq!(manifest["announcement_tag"], "v1.0.0-rc.1");
+        assert_eq!(manifest["announcement_is_prerelease"], true);
+        assert_eq!(manifest["releases"][0]["artifacts"], json!(["app_1.0.0-rc.1_amd64.deb"]));
+        let entry = &manifest["artifacts"]["app_1.0.0-rc.1_amd64.deb"];
+        assert_eq!(entry["install_hint"], "sudo apt install ./app_1.0.0-rc.1_amd64.deb");
+        assert_eq!(
+            entry["checksums"]["sha256"],
+            "9cfa1468c93fc18652e34a000f0c6614b0fa18f6f4887477ad9b0d36ca6a7eaa"
+        );
+    }
+}
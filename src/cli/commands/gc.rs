@@ -0,0 +1,398 @@
+//! Artifact retention and workspace garbage collection.
+//!
+//! Build machines accumulate artifacts, bundle directories, container build
+//! logs (`./tmp`), downloaded tools and repository clones
+//! (`$TMPDIR/kodegen-bundle-<uuid>` and the persistent clone cache) without
+//! bound. The `gc` subcommand prunes the entries directly inside each
+//! configured root, the build log directory and the tool cache according to
+//! a [`RetentionPolicy`].
+//!
+//! ## Config file
+//!
+//! Defaults are read from `<config dir>/kodegen-bundler/gc.toml` (or
+//! `--config`); command line flags take precedence:
+//!
+//! ```toml
+//! keep_last = 5
+//! max_total_size = "10G"
+//! roots = ["/srv/releases/dist"]
+//! clones = true
+//! ```
+
+use crate::bundler::utils::tools;
+use crate::cli::docker::LOG_DIR;
+use crate::cli::{GcArgs, RuntimeConfig};
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::{CLONE_PREFIX, clone_cache_dir, clone_in_use, lock_path};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Entries kept per root when neither config nor flags say otherwise
+const DEFAULT_KEEP_LAST: usize = 5;
+
+/// Garbage collection settings loaded from the gc config file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GcConfig {
+    /// Number of newest entries to keep in each root
+    pub keep_last: Option<usize>,
+    /// Maximum total size of each root (e.g. "10G")
+    pub max_total_size: Option<String>,
+    /// Directories to prune
+    pub roots: Vec<PathBuf>,
+    /// Whether to prune cached repository clones (default: true)
+    pub clones: Option<bool>,
+}
+
+impl GcConfig {
+    /// Load the config file, returning defaults if the default path is absent
+    ///
+    /// An explicitly requested path must exist.
+    pub fn load(explicit: Option<&Path>) -> Result<Self> {
+        let path = match explicit {
+            Some(path) => path.to_path_buf(),
+            None => match dirs::config_dir() {
+                Some(dir) => dir.join("kodegen-bundler").join("gc.toml"),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        if explicit.is_none() && !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            BundlerError::Cli(CliError::InvalidArguments {
+                reason: format!("Failed to read gc config {}: {}", path.display(), e),
+            })
+        })?;
+        Ok(toml::from_str(&contents)?)
+    }
+}
+
+/// How many entries to retain in a root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Number of newest entries to keep
+    pub keep_last: usize,
+    /// Maximum combined size in bytes of the kept entries
+    pub max_total_size: Option<u64>,
+}
+
+/// A prunable file or directory directly inside a root
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Path of the entry
+    pub path: PathBuf,
+    /// Last modification time
+    pub modified: SystemTime,
+    /// Size in bytes (recursive for directories)
+    pub size: u64,
+}
+
+/// Execute the `gc` subcommand
+pub async fn execute_gc(args: GcArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    let config = GcConfig::load(args.config.as_deref())?;
+
+    let max_total_size = args
+        .max_total_size
+        .as_deref()
+        .or(config.max_total_size.as_deref())
+        .map(parse_size)
+        .transpose()
+        .map_err(|reason| BundlerError::Cli(CliError::InvalidArguments { reason }))?;
+    let policy = RetentionPolicy {
+        keep_last: args
+            .keep_last
+            .or(config.keep_last)
+            .unwrap_or(DEFAULT_KEEP_LAST),
+        max_total_size,
+    };
+
+    let mut roots = if args.roots.is_empty() {
+        config.roots
+    } else {
+        args.roots
+    };
+    // Container build logs and downloaded tools grow with every run too
+    let implicit_roots = std::iter::once(PathBuf::from(LOG_DIR)).chain(tools::cache_dir());
+    for root in implicit_roots {
+        if root.is_dir() && !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    let prune_clones = !args.no_clones && config.clones.unwrap_or(true);
+
+    runtime_config
+        .section(&format!(
+            "🧹 Garbage collecting (keep last {}{})",
+            policy.keep_last,
+            policy
+                .max_total_size
+                .map(|size| format!(", max {} per root", format_size(size)))
+                .unwrap_or_default()
+        ))
+        .expect("Failed to write to stdout");
+
+    let mut candidates = Vec::new();
+    for root in roots {
+        if !root.is_dir() {
+            runtime_config
+                .warn(&format!("Skipping {}: not a directory", root.display()))
+                .expect("Failed to write to stdout");
+            continue;
+        }
+        candidates.extend(plan_prune(scan_root(&root, None)?, policy));
+    }
+    // Clones go last so their lock files can be removed with them
+    let first_clone = candidates.len();
+    if prune_clones {
+        candidates.extend(plan_prune(
+            scan_root(&std::env::temp_dir(), Some(CLONE_PREFIX))?,
+            policy,
+        ));
+        if let Some(cache_dir) = clone_cache_dir().filter(|dir| dir.is_dir()) {
+            let cached = scan_root(&cache_dir, None)?
+                .into_iter()
+                .filter(|entry| entry.path.is_dir() && !clone_in_use(&entry.path))
+                .collect();
+            candidates.extend(plan_prune(cached, policy));
+        }
+    }
+
+    let mut freed = 0u64;
+    let mut failures = 0usize;
+    for (index, entry) in candidates.iter().enumerate() {
+        let action = if args.dry_run {
+            "Would remove"
+        } else {
+            "Removing"
+        };
+        runtime_config
+            .indent(&format!(
+                "{} {} ({})",
+                action,
+                entry.path.display(),
+                format_size(entry.size)
+            ))
+            .expect("Failed to write to stdout");
+
+        if args.dry_run {
+            freed += entry.size;
+            continue;
+        }
+
+        let removed = if std::fs::symlink_metadata(&entry.path).is_ok_and(|m| m.is_dir()) {
+            tokio::fs::remove_dir_all(&entry.path).await
+        } else {
+            tokio::fs::remove_file(&entry.path).await
+        };
+        match removed {
+            Ok(()) => {
+                freed += entry.size;
+                if index >= first_clone {
+                    let _ = tokio::fs::remove_file(lock_path(&entry.path)).await;
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                runtime_config
+                    .warn(&format!("Failed to remove {}: {}", entry.path.display(), e))
+                    .expect("Failed to write to stdout");
+            }
+        }
+    }
+
+    let verb = if args.dry_run { "Would free" } else { "Freed" };
+    runtime_config
+        .success_println(&format!(
+            "{} {} across {} entr{}",
+            verb,
+            format_size(freed),
+            candidates.len() - failures,
+            if candidates.len() - failures == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        ))
+        .expect("Failed to write to stdout");
+
+    Ok(if failures == 0 { 0 } else { 1 })
+}
+
+/// List the entries directly inside `root`, optionally filtered by name prefix
+pub(super) fn scan_root(root: &Path, prefix: Option<&str>) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    for dir_entry in std::fs::read_dir(root)? {
+        let dir_entry = dir_entry?;
+        let path = dir_entry.path();
+
+        if let Some(prefix) = prefix {
+            let matches = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(prefix));
+            // Clones of running bundlers are locked
+            if !matches || !path.is_dir() || clone_in_use(&path) {
+                continue;
+            }
+        }
+
+        let metadata = dir_entry.metadata()?;
+        let size = if metadata.is_dir() {
+            walkdir::WalkDir::new(&path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        } else {
+            metadata.len()
+        };
+
+        entries.push(Entry {
+            path,
+            modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            size,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Select the entries to remove under a retention policy
+///
+/// Entries are ranked newest first. An entry is kept while it is within the
+/// newest `keep_last` and the running total stays under `max_total_size`.
+/// The newest entry is always kept so the most recent build survives.
+pub fn plan_prune(mut entries: Vec<Entry>, policy: RetentionPolicy) -> Vec<Entry> {
+    entries.sort_by_key(|e| std::cmp::Reverse(e.modified));
+
+    let mut total = 0u64;
+    let mut pruned = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        total = total.saturating_add(entry.size);
+        let within_count = index < policy.keep_last.max(1);
+        let within_size = policy.max_total_size.is_none_or(|max| total <= max);
+
+        if index == 0 || (within_count && within_size) {
+            continue;
+        }
+        pruned.push(entry);
+    }
+
+    pruned
+}
+
+/// Parse a size like "500M", "10G" or "1.5GB" into bytes (binary units)
+pub fn parse_size(size: &str) -> std::result::Result<u64, String> {
+    let normalized = size.trim().to_lowercase();
+    let normalized = normalized
+        .strip_suffix("ib")
+        .or_else(|| normalized.strip_suffix('b'))
+        .unwrap_or(&normalized);
+
+    let (value_str, multiplier) = match normalized.char_indices().last() {
+        Some((i, 'k')) => (&normalized[..i], 1u64 << 10),
+        Some((i, 'm')) => (&normalized[..i], 1u64 << 20),
+        Some((i, 'g')) => (&normalized[..i], 1u64 << 30),
+        Some((i, 't')) => (&normalized[..i], 1u64 << 40),
+        _ => (normalized, 1),
+    };
+
+    let value: f64 = value_str
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid size: '{}'", size))?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(format!("Invalid size: '{}'", size));
+    }
+
+    let bytes = (value * multiplier as f64).round();
+    if bytes > u64::MAX as f64 {
+        return Err(format!("Size too large: '{}'", size));
+    }
+
+    Ok(bytes as u64)
+}
+
+/// Format a byte count for display
+pub(super) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn entry(name: &str, age_secs: u64, size: u64) -> Entry {
+        Entry {
+            path: PathBuf::from(name),
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs),
+            size,
+        }
+    }
+
+    fn names(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|e| e.path.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_plan_prune_keep_last() {
+        let entries = vec![
+            entry("c", 30, 1),
+            entry("a", 10, 1),
+            entry("d", 40, 1),
+            entry("b", 20, 1),
+        ];
+        let policy = RetentionPolicy {
+            keep_last: 2,
+            max_total_size: None,
+        };
+        assert_eq!(names(&plan_prune(entries, policy)), ["c", "d"]);
+    }
+
+    #[test]
+    fn test_plan_prune_max_total_size() {
+        let entries = vec![entry("a", 10, 60), entry("b", 20, 30), entry("c", 30, 20)];
+        let policy = RetentionPolicy {
+            keep_last: 5,
+            max_total_size: Some(100),
+        };
+        assert_eq!(names(&plan_prune(entries, policy)), ["c"]);
+
+        // The newest entry survives even when it alone exceeds the limit
+        let entries = vec![entry("a", 10, 500), entry("b", 20, 1)];
+        let policy = RetentionPolicy {
+            keep_last: 0,
+            max_total_size: Some(100),
+        };
+        assert_eq!(names(&plan_prune(entries, policy)), ["b"]);
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("500M").unwrap(), 500 << 20);
+        assert_eq!(parse_size("10gb").unwrap(), 10 << 30);
+        assert_eq!(parse_size("1.5GiB").unwrap(), 3 << 29);
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("-1G").is_err());
+    }
+}
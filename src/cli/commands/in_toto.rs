@@ -0,0 +1,620 @@
+//! in-toto link metadata for the release pipeline.
+//!
+//! When `--in-toto-dir` is given, each pipeline step writes an in-toto link
+//! (`<step>.link`, or `<step>.<keyid prefix>.link` when signed) recording the
+//! artifacts it consumed (materials) and produced (products):
+//!
+//! 1. `clone` - repository checkout; products are the source files
+//! 2. `build` - `cargo build`; materials are the sources, products the binary
+//! 3. `package` - bundling; products are the artifacts as packaged, before
+//!    they are signed
+//! 4. `sign` - codesign, notarization or Authenticode; materials are the
+//!    packaged artifacts, products the final ones. Artifacts that are not
+//!    signed have the same digests in both.
+//!
+//! Signing runs inside the bundler, so the packaged digests are taken when
+//! it reports [`BundleStage::Signing`] for an artifact.
+//!
+//! Links are signed with an ed25519 key in securesystemslib JSON format
+//! (`--in-toto-key`). The `verify-links` subcommand checks that every step
+//! is present, signed by the expected key, chained in order (each step's
+//! materials are the previous step's products) and that the final artifact
+//! matches the `sign` product.
+
+use crate::bundler::{BundleEvent, BundleStage};
+use crate::cli::{RuntimeConfig, VerifyLinksArgs};
+use crate::error::{BundlerError, CliError, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Pipeline steps in the order they must have run
+pub const STEPS: [&str; 4] = ["clone", "build", "package", "sign"];
+
+/// Artifact path (relative to the step's working directory) to SHA-256 hex digest
+pub type ArtifactDigests = BTreeMap<String, String>;
+
+/// Directories excluded when hashing a source checkout
+const EXCLUDED_DIRS: [&str; 2] = [".git", "target"];
+
+/// An ed25519 key in securesystemslib format together with its keyid
+struct LinkKey {
+    keyid: String,
+    signing_key: Option<SigningKey>,
+    verifying_key: VerifyingKey,
+}
+
+impl LinkKey {
+    /// Load a securesystemslib ed25519 key file
+    ///
+    /// Private key files sign and verify; public key files only verify.
+    fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| in_toto_error(format!("Failed to read key {}: {}", path.display(), e)))?;
+        let key: Value = serde_json::from_str(&contents)?;
+
+        if key["keytype"] != "ed25519" {
+            return Err(in_toto_error(format!(
+                "Unsupported key type in {}: only ed25519 keys are supported",
+                path.display()
+            )));
+        }
+
+        let public_hex = key["keyval"]["public"]
+            .as_str()
+            .ok_or_else(|| in_toto_error(format!("Missing keyval.public in {}", path.display())))?;
+        let verifying_key = VerifyingKey::from_bytes(&decode_key_bytes(public_hex, path)?)
+            .map_err(|e| {
+                in_toto_error(format!("Invalid public key in {}: {}", path.display(), e))
+            })?;
+
+        let signing_key = match key["keyval"]["private"].as_str() {
+            Some(private_hex) if !private_hex.is_empty() => Some(SigningKey::from_bytes(
+                &decode_key_bytes(private_hex, path)?,
+            )),
+            _ => None,
+        };
+
+        // keyid is the SHA-256 of the canonical public portion of the key
+        let hash_algorithms = key
+            .get("keyid_hash_algorithms")
+            .cloned()
+            .unwrap_or_else(|| json!(["sha256", "sha512"]));
+        let public_portion = json!({
+            "keytype": "ed25519",
+            "scheme": key.get("scheme").cloned().unwrap_or_else(|| json!("ed25519")),
+            "keyid_hash_algorithms": hash_algorithms,
+            "keyval": { "public": public_hex },
+        });
+        let keyid = hex::encode(Sha256::digest(canonical_json(&public_portion).as_bytes()));
+
+        Ok(Self {
+            keyid,
+            signing_key,
+            verifying_key,
+        })
+    }
+}
+
+/// Writes in-toto link files for pipeline steps
+pub struct LinkRecorder {
+    dir: PathBuf,
+    key: Option<LinkKey>,
+}
+
+impl LinkRecorder {
+    /// Create a recorder writing into `dir`, signing with `key_path` if given
+    pub fn new(dir: &Path, key_path: Option<&Path>) -> Result<Self> {
+        let key = key_path.map(LinkKey::load).transpose()?;
+        if let Some(key) = &key
+            && key.signing_key.is_none()
+        {
+            return Err(in_toto_error(format!(
+                "in-toto key {} has no private part and cannot sign links",
+                key_path.unwrap_or(dir).display()
+            )));
+        }
+
+        std::fs::create_dir_all(dir)
+            .map_err(|e| in_toto_error(format!("Failed to create {}: {}", dir.display(), e)))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            key,
+        })
+    }
+
+    /// Write the link for one step and return its path
+    pub fn record(
+        &self,
+        step: &str,
+        command: &[String],
+        materials: &ArtifactDigests,
+        products: &ArtifactDigests,
+    ) -> Result<PathBuf> {
+        let signed = json!({
+            "_type": "link",
+            "name": step,
+            "command": command,
+            "materials": digest_map(materials),
+            "products": digest_map(products),
+            "byproducts": {},
+            "environment": {},
+        });
+
+        let (signatures, file_name) = match &self.key {
+            Some(LinkKey {
+                keyid,
+                signing_key: Some(signing_key),
+                ..
+            }) => {
+                let sig = signing_key.sign(canonical_json(&signed).as_bytes());
+                (
+                    json!([{ "keyid": keyid, "sig": hex::encode(sig.to_bytes()) }]),
+                    format!("{}.{}.link", step, &keyid[..8]),
+                )
+            }
+            _ => (json!([]), format!("{}.link", step)),
+        };
+
+        let link = json!({ "signed": signed, "signatures": signatures });
+        let path = self.dir.join(file_name);
+        std::fs::write(&path, serde_json::to_string_pretty(&link)?)
+            .map_err(|e| in_toto_error(format!("Failed to write {}: {}", path.display(), e)))?;
+
+        Ok(path)
+    }
+}
+
+/// Hash every file in a source checkout, keyed by path relative to `root`
+///
+/// `.git` and `target` are excluded so the digests describe the sources only.
+pub fn hash_tree(root: &Path) -> Result<ArtifactDigests> {
+    let mut digests = ArtifactDigests::new();
+
+    let walker = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !(entry.file_type().is_dir()
+                    && EXCLUDED_DIRS.iter().any(|d| entry.file_name() == *d))
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| in_toto_error(format!("Failed to walk sources: {}", e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        digests.insert(relative, hash_file(entry.path())?);
+    }
+
+    Ok(digests)
+}
+
+/// Digests of the artifact at `path` under `name`: the file itself, or each
+/// file of a directory as `<name>/<path>`
+pub fn hash_artifact(path: &Path, name: &str) -> Result<ArtifactDigests> {
+    if path.is_dir() {
+        Ok(hash_tree(path)?
+            .into_iter()
+            .map(|(file, digest)| (format!("{}/{}", name, file), digest))
+            .collect())
+    } else {
+        Ok(ArtifactDigests::from([(
+            name.to_string(),
+            hash_file(path)?,
+        )]))
+    }
+}
+
+/// Digests of artifacts taken before the bundler first signs them
+#[derive(Clone, Default)]
+pub struct UnsignedDigests(Arc<Mutex<BTreeMap<PathBuf, ArtifactDigests>>>);
+
+impl UnsignedDigests {
+    /// Progress callback hashing the artifact of each [`BundleStage::Signing`]
+    /// event the first time it is seen
+    pub fn observe(&self, event: &BundleEvent) {
+        let Some(path) = event
+            .path
+            .as_ref()
+            .filter(|_| event.stage == BundleStage::Signing)
+        else {
+            return;
+        };
+        let mut digests = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if digests.contains_key(path) {
+            return;
+        }
+        // Stored without a name; `get` adds the artifact's final name
+        match hash_artifact(path, "") {
+            Ok(unsigned) => {
+                digests.insert(path.clone(), unsigned);
+            }
+            Err(e) => tracing::warn!("Failed to hash {} before signing: {}", path.display(), e),
+        }
+    }
+
+    /// Digests of the artifact bundled at `path` before signing, under `name`
+    pub fn get(&self, path: &Path, name: &str) -> Option<ArtifactDigests> {
+        let digests = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        digests.get(path).map(|unsigned| {
+            unsigned
+                .iter()
+                .map(|(file, digest)| (format!("{}{}", name, file), digest.clone()))
+                .collect()
+        })
+    }
+}
+
+/// SHA-256 hex digest of a single file
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Execute the `verify-links` subcommand
+pub async fn execute_verify_links(
+    args: VerifyLinksArgs,
+    runtime_config: RuntimeConfig,
+) -> Result<i32> {
+    let key = args.public_key.as_deref().map(LinkKey::load).transpose()?;
+
+    runtime_config
+        .section("🔏 Verifying in-toto links...")
+        .expect("Failed to write to stdout");
+
+    let mut failures = Vec::new();
+    let mut previous: Option<(&str, Map<String, Value>)> = None;
+    let mut final_products = None;
+
+    for step in STEPS {
+        let link = match load_link(&args.links, step) {
+            Ok(link) => link,
+            Err(e) => {
+                failures.push(e);
+                previous = None;
+                continue;
+            }
+        };
+        let signed = &link["signed"];
+
+        if let Some(key) = &key {
+            if let Err(e) = verify_signature(&link, key) {
+                failures.push(format!("{}: {}", step, e));
+            }
+        } else if link["signatures"].as_array().is_none_or(|s| s.is_empty()) {
+            if args.allow_unsigned {
+                runtime_config
+                    .warn(&format!("{}: link is unsigned", step))
+                    .expect("Failed to write to stdout");
+            } else {
+                failures.push(format!(
+                    "{}: link is unsigned (pass --allow-unsigned to accept unsigned links)",
+                    step
+                ));
+            }
+        } else {
+            runtime_config
+                .warn(&format!(
+                    "{}: signature not checked without --public-key",
+                    step
+                ))
+                .expect("Failed to write to stdout");
+        }
+
+        let materials = signed["materials"].as_object().cloned().unwrap_or_default();
+        let products = signed["products"].as_object().cloned().unwrap_or_default();
+
+        if let Some((previous_step, previous_products)) = &previous
+            && &materials != previous_products
+        {
+            failures.push(format!(
+                "{}: materials do not match the products of {}",
+                step, previous_step
+            ));
+        }
+
+        runtime_config
+            .indent(&format!(
+                "✓ {} ({} materials, {} products)",
+                step,
+                materials.len(),
+                products.len()
+            ))
+            .expect("Failed to write to stdout");
+
+        if step == STEPS[STEPS.len() - 1] {
+            final_products = Some(products.clone());
+        }
+        previous = Some((step, products));
+    }
+
+    if let Some(artifact) = &args.artifact {
+        let name = artifact
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let expected = final_products
+            .as_ref()
+            .and_then(|products| products.get(&name))
+            .and_then(|digest| digest["sha256"].as_str())
+            .map(String::from);
+
+        match expected {
+            Some(expected) if expected == hash_file(artifact)? => {
+                runtime_config
+                    .indent(&format!("✓ {} matches sign product", name))
+                    .expect("Failed to write to stdout");
+            }
+            Some(_) => failures.push(format!("{}: digest does not match sign product", name)),
+            None => failures.push(format!("{}: not a product of the sign step", name)),
+        }
+    }
+
+    if failures.is_empty() {
+        runtime_config
+            .success_println("in-toto verification passed")
+            .expect("Failed to write to stdout");
+        Ok(0)
+    } else {
+        for failure in &failures {
+            runtime_config
+                .warn(failure)
+                .expect("Failed to write to stdout");
+        }
+        runtime_config
+            .warn(&format!(
+                "in-toto verification failed ({} problem(s))",
+                failures.len()
+            ))
+            .expect("Failed to write to stdout");
+        Ok(1)
+    }
+}
+
+/// Load the link for `step` (`<step>.link` or `<step>.<keyid>.link`)
+fn load_link(dir: &Path, step: &str) -> std::result::Result<Value, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let path = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&format!("{}.", step)) && n.ends_with(".link"))
+        })
+        .ok_or_else(|| format!("{}: no link file found in {}", step, dir.display()))?;
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let link: Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Invalid link {}: {}", path.display(), e))?;
+
+    if link["signed"]["_type"] != "link" || link["signed"]["name"] != step {
+        return Err(format!(
+            "{}: {} is not a link for this step",
+            step,
+            path.display()
+        ));
+    }
+
+    Ok(link)
+}
+
+/// Check that a link carries a valid signature from `key`
+fn verify_signature(link: &Value, key: &LinkKey) -> std::result::Result<(), String> {
+    let signature = link["signatures"]
+        .as_array()
+        .and_then(|sigs| sigs.iter().find(|s| s["keyid"] == key.keyid.as_str()))
+        .ok_or_else(|| format!("no signature by key {}", key.keyid))?;
+
+    let sig_bytes: [u8; 64] = signature["sig"]
+        .as_str()
+        .and_then(|s| hex::decode(s).ok())
+        .and_then(|b| b.try_into().ok())
+        .ok_or("malformed signature")?;
+
+    key.verifying_key
+        .verify(
+            canonical_json(&link["signed"]).as_bytes(),
+            &Signature::from_bytes(&sig_bytes),
+        )
+        .map_err(|_| "signature verification failed".to_string())
+}
+
+/// Render `{path: {"sha256": digest}}` as in-toto expects
+fn digest_map(digests: &ArtifactDigests) -> Value {
+    Value::Object(
+        digests
+            .iter()
+            .map(|(path, digest)| (path.clone(), json!({ "sha256": digest })))
+            .collect(),
+    )
+}
+
+/// Serialize a value as OLPC canonical JSON (the form in-toto signs)
+///
+/// Object keys are sorted, no insignificant whitespace is emitted, and only
+/// `"` and `\` are escaped in strings.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&n.to_string()),
+        Value::String(s) => write_canonical_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_string(key, out);
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_canonical_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+}
+
+fn decode_key_bytes(hex_str: &str, path: &Path) -> Result<[u8; 32]> {
+    hex::decode(hex_str)
+        .ok()
+        .and_then(|bytes| bytes.get(..32).and_then(|b| b.try_into().ok()))
+        .ok_or_else(|| {
+            in_toto_error(format!(
+                "Malformed ed25519 key material in {}",
+                path.display()
+            ))
+        })
+}
+
+fn in_toto_error(reason: String) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "in-toto".to_string(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_key(dir: &Path) -> PathBuf {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let key = json!({
+            "keytype": "ed25519",
+            "scheme": "ed25519",
+            "keyval": {
+                "public": hex::encode(signing_key.verifying_key().to_bytes()),
+                "private": hex::encode(signing_key.to_bytes()),
+            },
+        });
+        let path = dir.join("key.json");
+        std::fs::write(&path, key.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_and_escapes() {
+        let value = json!({ "b": [1, true, null], "a": "x\"y\\z\n" });
+        assert_eq!(
+            canonical_json(&value),
+            "{\"a\":\"x\\\"y\\\\z\n\",\"b\":[1,true,null]}"
+        );
+    }
+
+    #[test]
+    fn test_signed_link_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_path = write_key(dir.path());
+        let recorder = LinkRecorder::new(&dir.path().join("links"), Some(&key_path)).unwrap();
+
+        let products = ArtifactDigests::from([("app".to_string(), "ab".repeat(32))]);
+        let link_path = recorder
+            .record(
+                "build",
+                &["cargo".to_string(), "build".to_string()],
+                &ArtifactDigests::new(),
+                &products,
+            )
+            .unwrap();
+
+        let key = LinkKey::load(&key_path).unwrap();
+        assert!(
+            link_path
+                .to_str()
+                .unwrap()
+                .ends_with(&format!("build.{}.link", &key.keyid[..8]))
+        );
+
+        let mut link = load_link(&dir.path().join("links"), "build").unwrap();
+        assert!(verify_signature(&link, &key).is_ok());
+
+        link["signed"]["products"]["app"]["sha256"] = json!("00".repeat(32));
+        assert!(verify_signature(&link, &key).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_digests() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = dir.path().join("setup.exe");
+        std::fs::write(&installer, b"unsigned").unwrap();
+        let event = |stage, path: Option<&Path>| BundleEvent {
+            package_type: crate::bundler::PackageType::Exe,
+            stage,
+            percent: None,
+            message: String::new(),
+            path: path.map(Path::to_path_buf),
+        };
+
+        let unsigned = UnsignedDigests::default();
+        unsigned.observe(&event(BundleStage::Compressing, Some(&installer)));
+        assert_eq!(unsigned.get(&installer, "app.exe"), None);
+
+        unsigned.observe(&event(BundleStage::Signing, Some(&installer)));
+        std::fs::write(&installer, b"signed").unwrap();
+        // A second signing step (e.g. notarization) keeps the first digests
+        unsigned.observe(&event(BundleStage::Signing, Some(&installer)));
+        let expected = ArtifactDigests::from([(
+            "app.exe".to_string(),
+            hex::encode(Sha256::digest(b"unsigned")),
+        )]);
+        assert_eq!(unsigned.get(&installer, "app.exe"), Some(expected));
+
+        let app = dir.path().join("App.app");
+        std::fs::create_dir_all(app.join("Contents")).unwrap();
+        std::fs::write(app.join("Contents/Info.plist"), b"plist").unwrap();
+        unsigned.observe(&event(BundleStage::Signing, Some(&app)));
+        assert_eq!(
+            unsigned
+                .get(&app, "App.app")
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            ["App.app/Contents/Info.plist"]
+        );
+    }
+}
@@ -3,21 +3,52 @@
 //! This module provides devcontainer management for Docker-based builds.
 
 // Submodules
+mod all_platforms;
+mod apt_repo;
+mod artifact_json;
+mod delta;
 mod devcontainer;
+mod dist_manifest;
+mod clean;
+mod gc;
 mod git_push;
+mod in_toto;
+mod offline;
+mod publish;
+mod repro_check;
+mod sccache;
+mod smoke_test;
+mod updater;
+mod validate;
 
 // Re-export public API
+pub use apt_repo::execute_repo;
+pub use clean::execute_clean;
 pub use devcontainer::copy_embedded_devcontainer;
-
-use crate::bundler::{BundleBinary, Bundler, PackageSettings, PackageType, SettingsBuilder};
-use crate::cli::args::{Args, RuntimeConfig};
+pub use gc::execute_gc;
+pub use in_toto::execute_verify_links;
+pub use repro_check::execute_repro_check;
+pub use smoke_test::execute_install_test;
+pub use publish::PublishTarget;
+pub use validate::execute_validate;
+
+use crate::bundler::{
+    BundleBinary, Bundler, PackageSettings, PackageType, ParsePackageTypeError, SettingsBuilder,
+};
+use crate::bundler::hooks::{self, Hook};
+use crate::bundler::utils::{interrupt, progress_bar::CommandProgress};
+use crate::cli::args::{Args, ArtifactOutput, RuntimeConfig};
 use crate::cli::docker::bundler::ContainerBundler;
-use crate::cli::docker::image::ensure_image_built;
-use crate::cli::docker::limits::ContainerLimits;
+use crate::cli::github_actions;
+use crate::cli::docker::isolation::VendoredSource;
+use crate::cli::docker::image::{ensure_docker_running, ensure_image_available, ensure_image_built};
 use crate::error::{BundlerError, CliError, Result};
 use crate::metadata::load_manifest;
 use crate::source::RepositorySource;
 
+/// Target triple for Windows builds when `--target` is not given
+const DEFAULT_WINDOWS_TARGET: &str = "x86_64-pc-windows-gnu";
+
 /// Execute the bundle command with parsed arguments
 ///
 /// This is the main entry point that connects CLI args to the bundler library.
@@ -31,66 +62,215 @@ use crate::source::RepositorySource;
 /// 6. Create Bundler and call bundle()
 /// 7. Output artifact paths to stdout (one per line)
 /// 8. Return exit code 0 on success, 1 on error
+#[tracing::instrument(
+    skip_all,
+    fields(source = %args.source, platform = args.platform.as_deref().unwrap_or("all"))
+)]
 pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Result<i32> {
     // Step 1: Validate arguments
     args.validate()
         .map_err(|e| BundlerError::Cli(CliError::InvalidArguments { reason: e }))?;
+    crate::bundler::utils::retry::configure(args.retry_config());
+    crate::bundler::utils::http::configure(
+        args.download_sources()
+            .map_err(|e| BundlerError::Cli(CliError::InvalidArguments { reason: e }))?,
+    );
+
+    if args.offline {
+        // `--all-platforms` children check their own package type
+        let package_types = match args.platform.as_deref() {
+            Some(platform) => vec![parse_platform_string(platform)?],
+            None => Vec::new(),
+        };
+        offline::check_prerequisites(&args, &package_types).await?;
+        crate::bundler::utils::http::set_offline();
+    }
+
+    // Resolve the release up front so publishing problems surface before building
+    let release = if args.publish.contains(&PublishTarget::Github) {
+        Some(publish::GithubRelease::resolve(&args.source, args.release_tag.as_deref()).await?)
+    } else {
+        None
+    };
+    let updater = updater::Updater::resolve(&args, release.as_ref()).await?;
+    let dist_manifest = dist_manifest::DistManifest::resolve(&args, release.as_ref());
+
+    let Some(platform) = args.platform.as_deref() else {
+        // clap requires --platform unless --all-platforms is set
+        return all_platforms::execute_all_platforms(
+            &args,
+            release.as_ref(),
+            updater.as_ref(),
+            dist_manifest.as_ref(),
+            &runtime_config,
+        )
+        .await;
+    };
 
     runtime_config.verbose_println(&format!(
         "📦 Bundler starting for platform: {}",
-        args.platform
+        platform
     )).expect("Failed to write to stdout");
 
     // Step 2: Parse platform to determine build target
-    let package_type = parse_platform_string(&args.platform)?;
+    let package_type = parse_platform_string(platform)?;
     runtime_config.verbose_println(&format!("   Package type: {:?}", package_type)).expect("Failed to write to stdout");
 
+    let skip_phases = args.skip_phases();
+    if skip_phases.any() {
+        runtime_config.verbose_println(&format!("   Skipping phases: {:?}", skip_phases)).expect("Failed to write to stdout");
+    }
+
     // Step 3: Check if Docker is needed BEFORE doing any work
     if needs_docker(&package_type) {
         runtime_config.verbose_println(&format!(
             "   Cross-platform build detected (current: {}, required: {})",
             std::env::consts::OS,
-            required_os_for_package(&package_type)
+            package_type.required_host_os()
         )).expect("Failed to write to stdout");
         runtime_config.verbose_println("   Using Docker container for bundling...").expect("Failed to write to stdout");
 
-        // Ensure Docker image is built before attempting to use it
-        ensure_image_built(false, &runtime_config).await?;
+        if args.in_toto_dir.is_some() {
+            runtime_config.warn("in-toto links are only recorded for native builds; skipping").expect("Failed to write to stdout");
+        }
+
+        ensure_docker_running(args.auto_start_docker, &runtime_config).await?;
+
+        // Ensure Docker image is built (or pulled, with --docker-image) before attempting to use it.
+        // Offline, its presence was checked with the other prerequisites
+        match &args.docker_image {
+            _ if args.offline => {}
+            Some(image) => ensure_image_available(image, &runtime_config).await?,
+            None => ensure_image_built(false, args.image_build_timeout(), &runtime_config).await?,
+        }
+
+        // Isolated builds check out and vendor on the host, since the container has no network
+        let isolated_source = if args.isolated || args.offline {
+            let checkout = RepositorySource::parse(&args.source)?
+                .resolve(&args.checkout_options())
+                .await?;
+            Some(VendoredSource::prepare(checkout, args.offline, &runtime_config).await?)
+        } else {
+            None
+        };
 
         // Pass the bundling task to Docker container
         // Container will clone, build, and bundle internally
-        let limits = ContainerLimits::default();
+        let limits = args.container_limits()?;
         let container_bundler = ContainerBundler::new(
             args.source.clone(),
-            args.output_binary.clone(),
+            args.artifact_output(),
             limits,
-        );
+        )
+        .with_passthrough_args(args.container_passthrough_args(runtime_config.color()))
+        .with_target_cache(!args.no_cache)
+        .with_sccache(args.sccache)
+        .with_timeouts(args.run_timeouts())
+        .with_debugging(args.keep_container, args.export_build);
+        let container_bundler = match isolated_source {
+            Some(source) => container_bundler.with_isolated_source(source),
+            None => container_bundler,
+        };
+        let container_bundler = match &args.docker_image {
+            Some(image) => container_bundler.with_image(image.clone()),
+            None => container_bundler,
+        };
 
-        let artifact_path = container_bundler
+        let artifact_paths = container_bundler
             .bundle(package_type, &runtime_config)
             .await?;
 
-        // Verify artifact exists at specified output path
-        if !artifact_path.exists() {
+        // Verify artifacts exist at specified output path
+        let verify_span = tracing::info_span!("verify", artifacts = artifact_paths.len()).entered();
+        if artifact_paths.is_empty() {
             return Err(BundlerError::Cli(CliError::ExecutionFailed {
                 command: "docker container bundle".to_string(),
                 reason: format!(
-                    "Container bundling completed but artifact not found at {}",
-                    artifact_path.display()
+                    "Container bundling completed but reported no artifacts in {}",
+                    args.artifact_output().path().display()
                 ),
             }));
         }
+        for artifact_path in &artifact_paths {
+            if !artifact_path.exists() {
+                return Err(BundlerError::Cli(CliError::ExecutionFailed {
+                    command: "docker container bundle".to_string(),
+                    reason: format!(
+                        "Container bundling completed but artifact not found at {}",
+                        artifact_path.display()
+                    ),
+                }));
+            }
+        }
+        drop(verify_span);
+
+        if args.smoke_test {
+            smoke_test::smoke_test_artifacts(&args, &artifact_paths, None, &runtime_config).await?;
+        }
 
-        runtime_config.success_println(&format!("✓ ✓ Artifact at: {}", artifact_path.display())).expect("Failed to write to stdout");
-        println!("{}", artifact_path.display());
+        for artifact_path in &artifact_paths {
+            runtime_config.success_println(&format!("✓ ✓ Artifact at: {}", artifact_path.display())).expect("Failed to write to stdout");
+        }
+        for artifact_path in &artifact_paths {
+            println!("{}", artifact_path.display());
+        }
+        let outputs: Vec<_> = artifact_paths.iter().map(|path| (platform, path.as_path())).collect();
+        github_actions::write_outputs(package_version(&args).as_deref(), &outputs);
+
+        let mut uploads = artifact_paths.clone();
+        if args.delta {
+            uploads.extend(
+                delta::write_deltas(&artifact_paths, &args.delta_from, &runtime_config).await?,
+            );
+        }
+        if let Some(updater) = &updater {
+            uploads.extend(
+                updater
+                    .write_manifest(&artifact_paths, None, &args.artifact_output(), &runtime_config)
+                    .await?,
+            );
+        }
+        if let Some(dist_manifest) = &dist_manifest {
+            uploads.extend(
+                dist_manifest
+                    .write(&artifact_paths, None, None, &args.artifact_output(), &runtime_config)
+                    .await?,
+            );
+        }
+        if let Some(release) = &release {
+            release.publish(&uploads, &runtime_config).await?;
+        }
         return Ok(0);
     }
 
     // Step 4: Native platform execution - resolve source, build, and bundle
     let source = RepositorySource::parse(&args.source)?;
-    let repo_path = source.resolve().await?;
+    // Temporary clones are removed when the run ends, unless `--keep-temp` was given
+    let checkout = source.resolve(&args.checkout_options()).await?;
+    let repo_path = checkout.path().to_path_buf();
 
     runtime_config.verbose_println(&format!("   Repository: {}", repo_path.display())).expect("Failed to write to stdout");
+    if checkout.is_cached() {
+        runtime_config.verbose_println("   Reusing cached clone (disable with --no-cache)").expect("Failed to write to stdout");
+    } else if args.keep_temp {
+        runtime_config.verbose_println(&format!("   Keeping clone after the run: {}", repo_path.display())).expect("Failed to write to stdout");
+    }
+
+    // in-toto step 1: record the source checkout
+    let link_recorder = args
+        .in_toto_dir
+        .as_deref()
+        .map(|dir| in_toto::LinkRecorder::new(dir, args.in_toto_key.as_deref()))
+        .transpose()?;
+    let source_digests = match &link_recorder {
+        Some(recorder) => {
+            let digests = in_toto::hash_tree(&repo_path)?;
+            let command = ["git".to_string(), "clone".to_string(), args.source.clone()];
+            recorder.record("clone", &command, &in_toto::ArtifactDigests::new(), &digests)?;
+            digests
+        }
+        None => in_toto::ArtifactDigests::new(),
+    };
 
     // Step 5: Load Cargo.toml metadata
     let cargo_toml = repo_path.join("Cargo.toml");
@@ -100,7 +280,14 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
         }));
     }
 
-    let manifest = load_manifest(&cargo_toml)?;
+    let mut manifest = load_manifest(&cargo_toml)?;
+    // Rebranded or build-stamped packages keep the binary from Cargo.toml
+    if let Some(name) = &args.product_name {
+        manifest.metadata.name = name.clone();
+    }
+    if let Some(version) = &args.app_version {
+        manifest.metadata.version = version.clone();
+    }
     runtime_config.verbose_println(&format!(
         "   Loaded manifest: {} v{}",
         manifest.metadata.name, manifest.metadata.version
@@ -108,14 +295,19 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
     runtime_config.verbose_println(&format!("   Binary: {}", manifest.binary_name)).expect("Failed to write to stdout");
 
     // Step 4: Determine cross-compilation target for NSIS on non-Windows
-    let cross_compile_target = if package_type == PackageType::Exe && std::env::consts::OS != "windows" {
-        Some("x86_64-pc-windows-gnu")
-    } else {
-        None
+    let cross_compile_target = match package_type {
+        PackageType::Exe | PackageType::Portable if std::env::consts::OS != "windows" => {
+            Some(args.target.as_deref().unwrap_or(DEFAULT_WINDOWS_TARGET))
+        }
+        PackageType::Exe | PackageType::Portable => args.target.as_deref(),
+        _ => None,
     };
+    // MSVC targets need the Windows SDK, which cargo-xwin provides off Windows
+    let use_xwin = std::env::consts::OS != "windows"
+        && cross_compile_target.is_some_and(|target| target.ends_with("-msvc"));
 
     // Step 4.5: Push version changes to GitHub (NEW STEP)
-    if source.is_local() {  // Only push if source is local path
+    if source.is_local() && !args.checkout_options().in_place {  // Only push if source is a local clone
         runtime_config.section("📤 Pushing version changes to GitHub...").expect("Failed to write to stdout");
         git_push::push_version_changes(&repo_path, &runtime_config).await?;
     }
@@ -123,16 +315,25 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
     // Step 5: Build binary
     runtime_config.section("🔨 Building binary...").expect("Failed to write to stdout");
 
-    let mut cmd = tokio::process::Command::new("cargo");
-    cmd.arg("build")
-        .arg("--release")
-        .arg("--bin")
-        .arg(&manifest.binary_name);
+    let mut build_args = Vec::new();
+    if use_xwin {
+        build_args.push("xwin".to_string());
+    }
+    build_args.extend([
+        "build".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        manifest.binary_name.clone(),
+    ]);
 
     // Add cross-compilation target if needed
     if let Some(target) = cross_compile_target {
         runtime_config.verbose_println(&format!("   Cross-compiling for {}", target)).expect("Failed to write to stdout");
-        cmd.arg("--target").arg(target);
+        build_args.extend(["--target".to_string(), target.to_string()]);
+    }
+
+    if args.offline {
+        build_args.push("--offline".to_string());
     }
 
     // Add platform-specific GPU features for kodegen-candle-agent
@@ -140,76 +341,37 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
         let feature = match package_type {
             PackageType::Dmg | PackageType::MacOsBundle => Some("metal"),
             PackageType::Deb | PackageType::Rpm | PackageType::AppImage => Some("cuda"),
-            PackageType::Exe => None, // Windows doesn't have Metal or CUDA support yet
+            PackageType::Exe | PackageType::Portable => None, // Windows doesn't have Metal or CUDA support yet
+            _ => None,
         };
         
         if let Some(feature_name) = feature {
             runtime_config.verbose_println(&format!("   Enabling GPU feature: {}", feature_name)).expect("Failed to write to stdout");
-            cmd.arg("--features").arg(feature_name);
+            build_args.extend(["--features".to_string(), feature_name.to_string()]);
         }
     }
 
-    // Pipe stdout and stderr to capture output
-    let mut child = cmd
-        .current_dir(&repo_path)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| {
-            BundlerError::Cli(CliError::ExecutionFailed {
-                command: "cargo build".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
-
-    // Stream both stdout and stderr concurrently through OutputManager
-    tokio::join!(
-        async {
-            if let Some(stdout) = child.stdout.take() {
-                use tokio::io::{AsyncBufReadExt, BufReader};
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config.indent(&line).expect("Failed to write cargo output");
-                }
-            }
-        },
-        async {
-            if let Some(stderr) = child.stderr.take() {
-                use tokio::io::{AsyncBufReadExt, BufReader};
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config.indent(&line).expect("Failed to write cargo output");
-                }
-            }
-        }
-    );
-
-    // Wait for build to complete
-    let build_status = child.wait().await.map_err(|e| {
-        BundlerError::Cli(CliError::ExecutionFailed {
-            command: "cargo build".to_string(),
-            reason: e.to_string(),
-        })
-    })?;
-
-    if !build_status.success() {
-        return Err(BundlerError::Cli(CliError::ExecutionFailed {
-            command: "cargo build".to_string(),
-            reason: format!("Build failed with exit code: {:?}", build_status.code()),
-        }));
-    }
+    let rustc_wrapper = if args.sccache {
+        Some(sccache::ensure_installed(&runtime_config).await?)
+    } else {
+        None
+    };
+    let hook_env = [
+        ("KODEGEN_PRODUCT_NAME", manifest.metadata.name.clone()),
+        ("KODEGEN_VERSION", manifest.metadata.version.clone()),
+    ];
+    hooks::run(&manifest.bundle_settings.hooks, Hook::PreBuild, &repo_path, &hook_env).await?;
+    run_cargo_build(&build_args, &repo_path, rustc_wrapper.as_deref(), &runtime_config).await?;
 
     runtime_config.verbose_println("   ✓ Build completed").expect("Failed to write to stdout");
 
     // Step 6: Determine binary path
     let target_dir = if let Some(target) = cross_compile_target {
         // Cross-compilation (e.g., NSIS builds for Windows on macOS)
-        repo_path.join("target").join(target).join("release")
+        cargo_target_dir(&repo_path).join(target).join("release")
     } else {
         // Default native macOS build
-        repo_path.join("target").join("release")
+        cargo_target_dir(&repo_path).join("release")
     };
     
     // Windows binaries have .exe extension
@@ -256,6 +418,25 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
         binary_metadata.len()
     )).expect("Failed to write to stdout");
 
+    let mut post_build_env = hook_env.to_vec();
+    post_build_env.push(("KODEGEN_BINARY_PATH", binary_path.display().to_string()));
+    hooks::run(&manifest.bundle_settings.hooks, Hook::PostBuild, &repo_path, &post_build_env).await?;
+
+    // in-toto step 2: record the build (sources -> binary)
+    let binary_digests = match &link_recorder {
+        Some(recorder) => {
+            let relative = binary_path.strip_prefix(&repo_path).unwrap_or(&binary_path);
+            let digests = in_toto::ArtifactDigests::from([(
+                relative.to_string_lossy().replace('\\', "/"),
+                in_toto::hash_file(&binary_path)?,
+            )]);
+            let command: Vec<String> = std::iter::once("cargo".to_string()).chain(build_args).collect();
+            recorder.record("build", &command, &source_digests, &digests)?;
+            digests
+        }
+        None => in_toto::ArtifactDigests::new(),
+    };
+
     // Step 6: Create PackageSettings from metadata
     let package_settings = PackageSettings {
         product_name: manifest.metadata.name.clone(),
@@ -264,32 +445,57 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
         homepage: manifest.metadata.homepage.clone(),
         authors: Some(manifest.metadata.authors.clone()),
         default_run: Some(manifest.binary_name.clone()),
+        license: manifest.metadata.license.clone(),
+        license_files: manifest.metadata.license_files.clone(),
     };
 
     // Step 7: Create BundleBinary
     let bundle_binary = BundleBinary::new(manifest.binary_name.clone(), true);
 
     // Step 8: Build Settings via SettingsBuilder
-    let settings = SettingsBuilder::new()
+    let mut bundle_settings = manifest.bundle_settings;
+    if args.dev_sign {
+        bundle_settings.macos.dev_sign = true;
+    }
+
+    let mut settings_builder = SettingsBuilder::new();
+    if let Some(target) = cross_compile_target {
+        settings_builder = settings_builder.target(target.to_string());
+    }
+    let settings = settings_builder
         .project_out_directory(&target_dir)
+        .source_directory(&repo_path)
         .package_settings(package_settings)
-        .bundle_settings(manifest.bundle_settings)
+        .bundle_settings(bundle_settings)
         .binaries(vec![bundle_binary])
         .package_types(vec![package_type])
+        .skip_phases(skip_phases)
+        .lint(args.lint_mode())
         .build()?;
+    let ppa = args.publish.contains(&PublishTarget::Ppa);
+    if ppa {
+        publish::check_ppa(&settings)?;
+    }
 
     runtime_config.section(&format!(
         "📦 Creating {} package...",
-        platform_display_name(&package_type)
+        package_type.display_name()
     )).expect("Failed to write to stdout");
 
     // Step 9: Bundle - native platform only (Docker handled earlier)
     runtime_config.verbose_println("   Native platform build").expect("Failed to write to stdout");
-    let bundler = Bundler::new(settings).await?;
+    let mut bundler = Bundler::new(settings).await?;
+    // in-toto: artifacts are hashed before signing for the package link
+    let unsigned = in_toto::UnsignedDigests::default();
+    if link_recorder.is_some() {
+        let unsigned = unsigned.clone();
+        bundler = bundler.on_progress(move |event| unsigned.observe(event));
+    }
     let artifacts = bundler.bundle().await?;
 
     // Extract paths from artifacts
-    let artifact_paths: Vec<std::path::PathBuf> = artifacts.into_iter().flat_map(|a| a.paths).collect();
+    let artifact_paths: Vec<std::path::PathBuf> =
+        artifacts.iter().flat_map(|a| a.paths.iter().cloned()).collect();
 
     // Step 10: Handle output
     if artifact_paths.is_empty() {
@@ -299,17 +505,247 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
 
     runtime_config.success_println(&format!("✓ Created {} artifact(s)", artifact_paths.len())).expect("Failed to write to stdout");
 
-    // Step 11: Move artifact to specified output path
-    let output_path = &args.output_binary;
+    // Step 11: Move artifacts to the requested output location
+    let output_paths = match args.artifact_output() {
+        ArtifactOutput::File(output_path) => {
+            // Get the main artifact path (first path)
+            let source_path = artifact_paths.first().ok_or_else(|| {
+                BundlerError::Cli(CliError::ExecutionFailed {
+                    command: "get artifact path".to_string(),
+                    reason: "No artifact paths returned from bundler".to_string(),
+                })
+            })?;
+            move_artifact(source_path, &output_path, &runtime_config).await?;
+            vec![output_path]
+        }
+        ArtifactOutput::Directory(output_dir) => {
+            let mut output_paths = Vec::with_capacity(artifact_paths.len());
+            for source_path in &artifact_paths {
+                let file_name = source_path.file_name().ok_or_else(|| {
+                    BundlerError::Cli(CliError::ExecutionFailed {
+                        command: "get artifact path".to_string(),
+                        reason: format!("Invalid artifact path: {}", source_path.display()),
+                    })
+                })?;
+                let output_path = output_dir.join(file_name);
+                move_artifact(source_path, &output_path, &runtime_config).await?;
+                output_paths.push(output_path);
+            }
+            output_paths
+        }
+    };
+
+    if args.artifact_json {
+        artifact_json::write(&artifacts, &artifact_paths, &output_paths, &runtime_config).await?;
+    }
+
+    // in-toto steps 3 and 4: record packaging (binary -> unsigned artifacts)
+    // and signing (unsigned -> final artifacts)
+    if let Some(recorder) = &link_recorder {
+        let mut packaged = in_toto::ArtifactDigests::new();
+        let mut signed = in_toto::ArtifactDigests::new();
+        for (artifact_path, output_path) in artifact_paths.iter().zip(&output_paths) {
+            let name = output_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let digests = in_toto::hash_artifact(output_path, &name)?;
+            packaged.extend(unsigned.get(artifact_path, &name).unwrap_or_else(|| digests.clone()));
+            signed.extend(digests);
+        }
+        let command: Vec<String> = std::env::args().collect();
+        recorder.record("package", &command, &binary_digests, &packaged)?;
+        let link = recorder.record("sign", &command, &packaged, &signed)?;
+        runtime_config.verbose_println(&format!("   ✓ in-toto links written to {}", link.parent().unwrap_or(&link).display())).expect("Failed to write to stdout");
+    }
+
+    if args.smoke_test {
+        smoke_test::smoke_test_artifacts(
+            &args,
+            &output_paths,
+            Some(&manifest.binary_name),
+            &runtime_config,
+        )
+        .await?;
+    }
+
+    for output_path in &output_paths {
+        runtime_config.success_println(&format!("✓ Artifact at: {}", output_path.display())).expect("Failed to write to stdout");
+    }
 
-    // Get the main artifact path (first path)
-    let source_path = artifact_paths.first().ok_or_else(|| {
+    // Output the final paths to stdout, one per line (for diagnostics)
+    for output_path in &output_paths {
+        println!("{}", output_path.display());
+    }
+
+    let outputs: Vec<_> = output_paths.iter().map(|path| (platform, path.as_path())).collect();
+    github_actions::write_outputs(Some(&manifest.metadata.version), &outputs);
+
+    // Step 12: Delta updates, updater and dist manifests, and publishing
+    let mut uploads = output_paths.clone();
+    if args.delta {
+        uploads.extend(delta::write_deltas(&output_paths, &args.delta_from, &runtime_config).await?);
+    }
+    if let Some(updater) = &updater {
+        uploads.extend(
+            updater
+                .write_manifest(
+                    &output_paths,
+                    Some(&manifest.metadata.version),
+                    &args.artifact_output(),
+                    &runtime_config,
+                )
+                .await?,
+        );
+    }
+    if let Some(dist_manifest) = &dist_manifest {
+        uploads.extend(
+            dist_manifest
+                .write(
+                    &output_paths,
+                    Some(&manifest.metadata.name),
+                    Some(&manifest.metadata.version),
+                    &args.artifact_output(),
+                    &runtime_config,
+                )
+                .await?,
+        );
+    }
+    if let Some(release) = &release {
+        release.publish(&uploads, &runtime_config).await?;
+    }
+    if ppa {
+        publish::publish_ppa(bundler.settings(), &runtime_config).await?;
+    }
+
+    Ok(0)
+}
+
+/// Target directory of a cargo build run in `repo_path`
+///
+/// Honors `CARGO_TARGET_DIR`, which the container sets to its target cache
+/// volume. Relative paths are resolved against `repo_path` like cargo does.
+fn cargo_target_dir(repo_path: &std::path::Path) -> std::path::PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) if !dir.is_empty() => repo_path.join(dir),
+        _ => repo_path.join("target"),
+    }
+}
+
+/// Runs `cargo <build_args>` in `repo_path`, streaming its output
+///
+/// `rustc_wrapper` (e.g. sccache) is passed to cargo as `RUSTC_WRAPPER`.
+#[tracing::instrument(name = "build", skip_all, fields(command = %build_args.join(" ")))]
+async fn run_cargo_build(
+    build_args: &[String],
+    repo_path: &std::path::Path,
+    rustc_wrapper: Option<&std::path::Path>,
+    runtime_config: &RuntimeConfig,
+) -> Result<()> {
+    // Pipe stdout and stderr to capture output
+    let mut command = tokio::process::Command::new("cargo");
+    command
+        .args(build_args)
+        .current_dir(repo_path)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    if let Some(wrapper) = rustc_wrapper {
+        command.env("RUSTC_WRAPPER", wrapper);
+    }
+    // Own process group, so an interrupt also stops rustc and build scripts
+    #[cfg(unix)]
+    command.process_group(0);
+    let mut child = command
+        .spawn()
+        .map_err(|e| {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command: "cargo build".to_string(),
+                reason: e.to_string(),
+            })
+        })?;
+
+    let _interrupt = interrupt::kill_on_interrupt(&child);
+
+    // Stream both stdout and stderr concurrently through OutputManager,
+    // or behind a spinner on a terminal
+    let progress = CommandProgress::new("cargo build");
+    tokio::join!(
+        async {
+            if let Some(stdout) = child.stdout.take() {
+                use tokio::io::{AsyncBufReadExt, BufReader};
+                let reader = BufReader::new(stdout);
+                let mut lines = reader.lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if !progress.line(&line) {
+                        runtime_config.indent(&line).expect("Failed to write cargo output");
+                    }
+                }
+            }
+        },
+        async {
+            if let Some(stderr) = child.stderr.take() {
+                use tokio::io::{AsyncBufReadExt, BufReader};
+                let reader = BufReader::new(stderr);
+                let mut lines = reader.lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if !progress.line(&line) {
+                        runtime_config.indent(&line).expect("Failed to write cargo output");
+                    }
+                }
+            }
+        }
+    );
+
+    // Wait for build to complete
+    let build_status = child.wait().await.map_err(|e| {
         BundlerError::Cli(CliError::ExecutionFailed {
-            command: "get artifact path".to_string(),
-            reason: "No artifact paths returned from bundler".to_string(),
+            command: "cargo build".to_string(),
+            reason: e.to_string(),
         })
     })?;
 
+    let captured = progress.finish();
+    if !build_status.success() {
+        for line in &captured {
+            runtime_config.indent(line).expect("Failed to write cargo output");
+        }
+        return Err(BundlerError::Cli(CliError::ExecutionFailed {
+            command: "cargo build".to_string(),
+            reason: format!("Build failed with exit code: {:?}", build_status.code()),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Package version being bundled: `--app-version`, or the version of a
+/// local source
+fn package_version(args: &Args) -> Option<String> {
+    args.app_version.clone().or_else(|| local_version(&args.source))
+}
+
+/// Package version of a local source, read from its Cargo.toml
+///
+/// Remote sources are cloned inside the child process or container, so
+/// their version is not known here.
+fn local_version(source: &str) -> Option<String> {
+    match RepositorySource::parse(source) {
+        Ok(RepositorySource::Local(path)) => load_manifest(&path.join("Cargo.toml"))
+            .ok()
+            .map(|manifest| manifest.metadata.version),
+        _ => None,
+    }
+}
+
+/// Moves an artifact (file or directory, e.g. a .app bundle) to `output_path`
+///
+/// Copies then removes the source, so moves across filesystems work.
+/// Contract: returns Ok only if the artifact exists at `output_path`.
+async fn move_artifact(
+    source_path: &std::path::Path,
+    output_path: &std::path::Path,
+    runtime_config: &RuntimeConfig,
+) -> Result<()> {
     runtime_config.verbose_println(&format!(
         "   Moving artifact:\n      from: {}\n      to:   {}",
         source_path.display(),
@@ -326,36 +762,47 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
         })?;
     }
 
-    // Move artifact to specified output path (handles cross-filesystem moves)
-    tokio::fs::copy(source_path, output_path)
-        .await
-        .map_err(|e| {
-            BundlerError::Cli(CliError::ExecutionFailed {
-                command: "copy artifact".to_string(),
-                reason: format!(
-                    "Failed to copy artifact from {} to {}: {}",
-                    source_path.display(),
-                    output_path.display(),
-                    e
-                ),
-            })
-        })?;
+    let copy_error = |e: String| {
+        BundlerError::Cli(CliError::ExecutionFailed {
+            command: "copy artifact".to_string(),
+            reason: format!(
+                "Failed to copy artifact from {} to {}: {}",
+                source_path.display(),
+                output_path.display(),
+                e
+            ),
+        })
+    };
+    let remove_error = |e: std::io::Error| {
+        BundlerError::Cli(CliError::ExecutionFailed {
+            command: "remove source artifact".to_string(),
+            reason: format!(
+                "Failed to remove source artifact {}: {}",
+                source_path.display(),
+                e
+            ),
+        })
+    };
 
-    // Remove source file after successful copy
-    tokio::fs::remove_file(source_path)
-        .await
-        .map_err(|e| {
-            BundlerError::Cli(CliError::ExecutionFailed {
-                command: "remove source artifact".to_string(),
-                reason: format!(
-                    "Failed to remove source artifact {}: {}",
-                    source_path.display(),
-                    e
-                ),
-            })
-        })?;
+    if source_path.is_dir() {
+        if output_path.exists() {
+            tokio::fs::remove_dir_all(output_path)
+                .await
+                .map_err(|e| copy_error(e.to_string()))?;
+        }
+        crate::bundler::utils::fs::copy_dir(source_path, output_path)
+            .await
+            .map_err(|e| copy_error(e.to_string()))?;
+        tokio::fs::remove_dir_all(source_path).await.map_err(remove_error)?;
+    } else {
+        // Handles cross-filesystem moves
+        tokio::fs::copy(source_path, output_path)
+            .await
+            .map_err(|e| copy_error(e.to_string()))?;
+        tokio::fs::remove_file(source_path).await.map_err(remove_error)?;
+    }
 
-    // Contract enforcement: verify file exists at destination
+    // Contract enforcement: verify artifact exists at destination
     if !output_path.exists() {
         return Err(BundlerError::Cli(CliError::ExecutionFailed {
             command: "verify output".to_string(),
@@ -365,57 +812,23 @@ pub async fn execute_command(args: Args, runtime_config: RuntimeConfig) -> Resul
             ),
         }));
     }
-
-    runtime_config.success_println(&format!("✓ Artifact at: {}", output_path.display())).expect("Failed to write to stdout");
-
-    // Output the final path to stdout (for diagnostics)
-    println!("{}", output_path.display());
-
-    Ok(0)
+    Ok(())
 }
 
 /// Parse platform string to PackageType enum
 fn parse_platform_string(platform: &str) -> Result<PackageType> {
-    match platform.to_lowercase().as_str() {
-        "deb" => Ok(PackageType::Deb),
-        "rpm" => Ok(PackageType::Rpm),
-        "appimage" => Ok(PackageType::AppImage),
-        "dmg" => Ok(PackageType::Dmg),
-        "exe" => Ok(PackageType::Exe),
-        _ => Err(BundlerError::Cli(CliError::InvalidArguments {
-            reason: format!(
-                "Unsupported platform '{}'. Valid: deb, rpm, appimage, dmg, nsis",
-                platform
-            ),
-        })),
-    }
-}
-
-/// Get human-readable platform name
-fn platform_display_name(package_type: &PackageType) -> &'static str {
-    match package_type {
-        PackageType::Deb => "Debian Package (.deb)",
-        PackageType::Rpm => "RedHat Package (.rpm)",
-        PackageType::AppImage => "Linux AppImage",
-        PackageType::Dmg => "macOS Disk Image (.dmg)",
-        PackageType::MacOsBundle => "macOS Application Bundle (.app)",
-        PackageType::Exe => "Windows NSIS Installer (.exe)",
-    }
-}
-
-/// Determine which host OS is required for a package type
-fn required_os_for_package(package_type: &PackageType) -> &'static str {
-    match package_type {
-        PackageType::Deb | PackageType::Rpm | PackageType::AppImage => "linux",
-        PackageType::Dmg | PackageType::MacOsBundle => "macos",
-        PackageType::Exe => "windows",
-    }
+    platform.parse().map_err(|e: ParsePackageTypeError| {
+        BundlerError::Cli(CliError::InvalidArguments {
+            reason: e.to_string(),
+        })
+    })
 }
 
 /// Check if Docker is needed for cross-platform bundling
 ///
 /// Returns false if:
-/// - Already running inside Docker (detected via /.dockerenv, cgroup, or env var)
+/// - Already running inside Docker or Podman (detected via /.dockerenv,
+///   /run/.containerenv, cgroup, or env var)
 /// - Package type matches current OS (native build)
 ///
 /// Returns true if:
@@ -424,8 +837,10 @@ fn needs_docker(package_type: &PackageType) -> bool {
     // Auto-detect if we're already inside a Docker container
     // If so, use native tools (container has all required tooling installed)
     let in_docker = {
-        // Check 1: /.dockerenv file exists (standard Docker indicator)
-        if std::path::Path::new("/.dockerenv").exists() {
+        // Check 1: /.dockerenv or Podman's /run/.containerenv file exists
+        if std::path::Path::new("/.dockerenv").exists()
+            || std::path::Path::new("/run/.containerenv").exists()
+        {
             true
         }
         // Check 2: /proc/1/cgroup contains "docker" or "buildkit"
@@ -444,7 +859,7 @@ fn needs_docker(package_type: &PackageType) -> bool {
     }
 
     // On host system: use Docker for cross-platform builds
-    let required_os = required_os_for_package(package_type);
+    let required_os = package_type.required_host_os();
     let current_os = std::env::consts::OS;
     required_os != current_os
 }
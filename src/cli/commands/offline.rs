@@ -0,0 +1,198 @@
+//! Prerequisite checks for air-gapped bundling (`--offline`).
+//!
+//! Everything the bundler would otherwise fetch must already be on the
+//! machine. All missing prerequisites are reported at once, before any work
+//! starts, instead of one download hanging or failing at a time.
+
+use super::needs_docker;
+use crate::bundler::{BundleSettings, PackageType};
+use crate::bundler::utils::tools;
+use crate::cli::args::Args;
+use crate::cli::docker::image::{BUILDER_IMAGE_NAME, check_docker_available, image_present};
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::RepositorySource;
+use std::path::Path;
+
+/// Fails with the list of everything missing for bundling `package_types` offline
+///
+/// Checks the source, publishing and other options that need the network,
+/// COPR uploads, the cargo dependency cache, sccache, the builder
+/// image for container builds and the AppImage tools.
+pub async fn check_prerequisites(args: &Args, package_types: &[PackageType]) -> Result<()> {
+    let mut missing = Vec::new();
+
+    let local_source = RepositorySource::parse(&args.source)
+        .ok()
+        .and_then(|source| match source {
+            RepositorySource::Local(path) => Some(path),
+            _ => None,
+        });
+    match &local_source {
+        None => missing.push(format!(
+            "a local --source path ({} would be cloned)",
+            args.source
+        )),
+        Some(path) if !dependencies_cached(path).await => missing.push(format!(
+            "dependencies of {} in the cargo cache (run `cargo fetch` while online)",
+            path.display()
+        )),
+        Some(_) => {}
+    }
+
+    missing.extend(network_options(args));
+
+    // A manifest that fails to load is reported by the build itself
+    if let Some(manifest) = local_source
+        .as_ref()
+        .and_then(|path| crate::metadata::load_manifest(&path.join("Cargo.toml")).ok())
+    {
+        missing.extend(manifest_uploads(&manifest.bundle_settings));
+    }
+
+    if args.sccache && tools::find_system_tool(&["sccache"], "--version").await.is_none() {
+        missing.push("sccache on PATH (--sccache would install it with cargo)".to_string());
+    }
+
+    let containerized = package_types.iter().any(needs_docker);
+    if containerized {
+        let image = args.docker_image.as_deref().unwrap_or(BUILDER_IMAGE_NAME);
+        if check_docker_available().await.is_err() {
+            missing.push("a running container engine for cross-platform builds".to_string());
+        } else if !image_present(image).await {
+            missing.push(format!(
+                "the builder image {} present locally (build or pull it while online)",
+                image
+            ));
+        }
+    }
+
+    let native_appimage = package_types
+        .iter()
+        .any(|package_type| *package_type == PackageType::AppImage && !needs_docker(package_type));
+    if native_appimage && !appimage_tools_available() {
+        missing.push(
+            "linuxdeploy or appimagetool, downloaded by an earlier AppImage build or on PATH".to_string(),
+        );
+    }
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(BundlerError::Cli(CliError::InvalidArguments {
+        reason: format!(
+            "--offline forbids network access, but these prerequisites are missing:\n{}",
+            missing
+                .iter()
+                .map(|item| format!("  • {}", item))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }))
+}
+
+/// Command-line options that download or upload
+fn network_options(args: &Args) -> Vec<String> {
+    let mut forbidden = Vec::new();
+    if !args.publish.is_empty() {
+        forbidden.push("no --publish (publishing uploads the artifacts)".to_string());
+    }
+    for url in args
+        .delta_from
+        .iter()
+        .filter(|from| from.starts_with("https://") || from.starts_with("http://"))
+    {
+        forbidden.push(format!("no --delta-from URL ({} would be downloaded)", url));
+    }
+    if args.smoke_test {
+        forbidden.push("no --smoke-test (it pulls distribution images to install into)".to_string());
+    }
+    forbidden
+}
+
+/// Uploads configured in `[package.metadata.bundle]`
+fn manifest_uploads(bundle_settings: &BundleSettings) -> Vec<String> {
+    let mut forbidden = Vec::new();
+    if bundle_settings.rpm.copr.is_some() {
+        forbidden.push("no rpm.copr (the source RPM would be submitted to COPR)".to_string());
+    }
+    forbidden
+}
+
+/// Whether cargo can resolve the dependencies of the project at `path` without the network
+async fn dependencies_cached(path: &Path) -> bool {
+    tokio::process::Command::new("cargo")
+        .args(["metadata", "--offline", "--format-version", "1", "--manifest-path"])
+        .arg(path.join("Cargo.toml"))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+/// Whether an AppImage tool is in the tool cache or on PATH
+fn appimage_tools_available() -> bool {
+    const TOOLS: [&str; 2] = ["linuxdeploy", "appimagetool"];
+    let cached = tools::cache_dir()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.ends_with("-extracted") && TOOLS.iter().any(|tool| name.starts_with(tool))
+        });
+    cached || TOOLS.iter().any(|tool| which::which(tool).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::Cli;
+    use clap::Parser;
+
+    fn args(extra: &[&str]) -> Args {
+        let base = ["kodegen_bundler_bundle", "--source", ".", "--platform", "deb", "--output-dir", "dist", "--offline"];
+        Cli::try_parse_from(base.iter().chain(extra)).unwrap().bundle.unwrap()
+    }
+
+    #[test]
+    fn test_network_options() {
+        assert!(network_options(&args(&[])).is_empty());
+        assert!(network_options(&args(&["--delta", "--delta-from", "old/app.AppImage"])).is_empty());
+
+        let forbidden = network_options(&args(&[
+            "--publish",
+            "github,ppa",
+            "--delta",
+            "--delta-from",
+            "https://example.com/app-1.0.AppImage",
+            "--smoke-test",
+        ]));
+        assert_eq!(forbidden.len(), 3, "{:?}", forbidden);
+        assert!(forbidden[0].contains("--publish"));
+        assert!(forbidden[1].contains("https://example.com/app-1.0.AppImage"));
+        assert!(forbidden[2].contains("--smoke-test"));
+    }
+
+    #[test]
+    fn test_manifest_uploads() {
+        assert!(manifest_uploads(&BundleSettings::default()).is_empty());
+
+        let bundle_settings: BundleSettings = toml::from_str(
+            r#"
+            [rpm.copr]
+            project = "kodegen"
+
+            [deb.ppa]
+            ppa = "kodegen/stable"
+            series = ["noble"]
+            "#,
+        )
+        .unwrap();
+        // The PPA is only uploaded to with --publish ppa
+        let forbidden = manifest_uploads(&bundle_settings);
+        assert_eq!(forbidden.len(), 1, "{:?}", forbidden);
+        assert!(forbidden[0].contains("COPR"));
+    }
+}
@@ -0,0 +1,513 @@
+//! GitHub Releases and Launchpad PPA publishing.
+//!
+//! `--publish github` creates the release for the current tag (or reuses the
+//! existing one) and uploads every artifact together with a `SHA256SUMS`
+//! file. Assets that already exist under the same name are replaced, so a
+//! release job can be re-run safely. The checksums of an existing
+//! `SHA256SUMS` are kept, so per-platform jobs can publish to the same tag.
+//!
+//! Authenticates with `GITHUB_TOKEN` (or `GH_TOKEN`). `GITHUB_API_URL`
+//! and `GITHUB_SERVER_URL` select a GitHub Enterprise server, as they do
+//! in GitHub Actions.
+//!
+//! `--publish ppa` uploads a Debian source package to the PPA configured in
+//! `deb.ppa`, once the .deb has been verified (and smoke-tested, if asked).
+
+use crate::bundler::Settings;
+use crate::cli::RuntimeConfig;
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::RepositorySource;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Public GitHub API
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Public GitHub web server, hosting release downloads
+const DEFAULT_SERVER_URL: &str = "https://github.com";
+
+/// Name of the uploaded checksum file
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Where to publish
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PublishTarget {
+    /// GitHub Releases of the source repository
+    Github,
+    /// Launchpad PPA configured in `deb.ppa` (.deb builds on Linux)
+    Ppa,
+}
+
+/// Fails unless `deb.ppa` is configured, so `--publish ppa` problems surface before building
+pub fn check_ppa(settings: &Settings) -> Result<()> {
+    if settings.bundle_settings().deb.ppa.is_none() {
+        return Err(BundlerError::Cli(CliError::InvalidArguments {
+            reason: "--publish ppa needs a [package.metadata.bundle.deb.ppa] section".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// Uploads a source package to the PPA configured in `deb.ppa`
+#[cfg(target_os = "linux")]
+pub async fn publish_ppa(settings: &Settings, runtime_config: &RuntimeConfig) -> Result<()> {
+    check_ppa(settings)?;
+    let Some(ppa) = &settings.bundle_settings().deb.ppa else {
+        return Ok(());
+    };
+    runtime_config
+        .progress(&format!("Uploading source package to ppa:{}", ppa.ppa))
+        .expect("Failed to write to stdout");
+    crate::bundler::platform::linux::ppa::publish_ppa(settings, ppa).await?;
+    Ok(())
+}
+
+/// Uploads a source package to the PPA configured in `deb.ppa`
+#[cfg(not(target_os = "linux"))]
+pub async fn publish_ppa(_settings: &Settings, _runtime_config: &RuntimeConfig) -> Result<()> {
+    Err(BundlerError::Cli(CliError::InvalidArguments {
+        reason: "--publish ppa requires a Linux host".to_string(),
+    }))
+}
+
+/// A GitHub release resolved from the source and environment
+#[derive(Debug, Clone)]
+pub struct GithubRelease {
+    /// `owner/name`
+    repository: String,
+    tag: String,
+    token: String,
+    api_url: String,
+    server_url: String,
+}
+
+impl GithubRelease {
+    /// Resolves the repository, tag and token.
+    ///
+    /// Called before bundling so a misconfigured release fails fast. The tag
+    /// is `tag` if given, else the tag of `GITHUB_REF`, else the tag
+    /// pointing at `HEAD` of a local source.
+    pub async fn resolve(source: &str, tag: Option<&str>) -> Result<Self> {
+        let token = std::env::var("GITHUB_TOKEN")
+            .or_else(|_| std::env::var("GH_TOKEN"))
+            .map_err(|_| publish_error("GITHUB_TOKEN is not set"))?;
+
+        let source = RepositorySource::parse(source)?;
+        let repository = match &source {
+            RepositorySource::GitHub { org, repo } => Some(format!("{}/{}", org, repo)),
+            RepositorySource::GitHubUrl(url) => github_repository(url),
+            RepositorySource::Local(path) => {
+                crate::metadata::load_manifest(&path.join("Cargo.toml"))
+                    .ok()
+                    .and_then(|manifest| manifest.metadata.repository)
+                    .and_then(|url| github_repository(&url))
+            }
+        }
+        .or_else(|| std::env::var("GITHUB_REPOSITORY").ok())
+        .ok_or_else(|| {
+            publish_error("cannot determine the GitHub repository; set GITHUB_REPOSITORY")
+        })?;
+
+        let tag = match tag {
+            Some(tag) => Some(tag.to_string()),
+            None => match std::env::var("GITHUB_REF")
+                .ok()
+                .and_then(|r| r.strip_prefix("refs/tags/").map(String::from))
+            {
+                Some(tag) => Some(tag),
+                None => match &source {
+                    RepositorySource::Local(path) => head_tag(path).await,
+                    _ => None,
+                },
+            },
+        }
+        .ok_or_else(|| {
+            publish_error("cannot determine the release tag; tag HEAD or pass --release-tag")
+        })?;
+
+        let api_url = std::env::var("GITHUB_API_URL")
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let server_url = std::env::var("GITHUB_SERVER_URL")
+            .unwrap_or_else(|_| DEFAULT_SERVER_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Self {
+            repository,
+            tag,
+            token,
+            api_url,
+            server_url,
+        })
+    }
+
+    /// Base URL that release assets are downloaded from
+    pub fn download_base(&self) -> String {
+        format!(
+            "{}/{}/releases/download/{}",
+            self.server_url, self.repository, self.tag
+        )
+    }
+
+    /// Release tag, e.g. `v1.2.3`
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Creates or updates the release and uploads `artifacts` plus checksums
+    pub async fn publish(
+        &self,
+        artifacts: &[PathBuf],
+        runtime_config: &RuntimeConfig,
+    ) -> Result<()> {
+        runtime_config
+            .section(&format!(
+                "🚀 Publishing to GitHub release {} of {}",
+                self.tag, self.repository
+            ))
+            .expect("Failed to write to stdout");
+
+        let client = reqwest::Client::builder()
+            .user_agent(concat!(
+                "kodegen_bundler_bundle/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .map_err(|e| publish_error(e.to_string()))?;
+
+        let release = self.find_or_create_release(&client).await?;
+        let upload_url = release["upload_url"]
+            .as_str()
+            .and_then(|url| url.split('{').next())
+            .ok_or_else(|| publish_error("GitHub release response has no upload_url"))?
+            .to_string();
+        let existing: Vec<(String, u64)> = release["assets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|asset| Some((asset["name"].as_str()?.to_string(), asset["id"].as_u64()?)))
+            .collect();
+
+        let mut checksums = Vec::new();
+        for path in artifacts {
+            if !path.is_file() {
+                runtime_config
+                    .warn(&format!(
+                        "Skipping {}: only files can be release assets",
+                        path.display()
+                    ))
+                    .expect("Failed to write to stdout");
+                continue;
+            }
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .ok_or_else(|| {
+                    publish_error(format!("invalid artifact path {}", path.display()))
+                })?;
+            checksums.push((name.clone(), sha256_file(path).await?));
+
+            let file = tokio::fs::File::open(path).await?;
+            let size = file.metadata().await?.len();
+            let body = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(file));
+            self.upload_asset(&client, &upload_url, &existing, &name, body, size)
+                .await?;
+            runtime_config
+                .indent(&format!("✓ Uploaded {}", name))
+                .expect("Failed to write to stdout");
+        }
+
+        // Other jobs may have published to this release already
+        let previous = match existing.iter().find(|(name, _)| name == CHECKSUMS_ASSET) {
+            Some((_, id)) => self.download_asset(&client, *id).await?,
+            None => String::new(),
+        };
+        let sums = render_checksums(&merge_checksums(&previous, &checksums)).into_bytes();
+        let size = sums.len() as u64;
+        self.upload_asset(
+            &client,
+            &upload_url,
+            &existing,
+            CHECKSUMS_ASSET,
+            sums.into(),
+            size,
+        )
+        .await?;
+
+        let html_url = release["html_url"].as_str().unwrap_or_default();
+        runtime_config
+            .success_println(&format!(
+                "✓ Published {} asset(s) to {}",
+                checksums.len() + 1,
+                html_url
+            ))
+            .expect("Failed to write to stdout");
+        Ok(())
+    }
+
+    /// Returns the release for the tag, creating it if it does not exist
+    async fn find_or_create_release(&self, client: &reqwest::Client) -> Result<Value> {
+        let response = self
+            .request(
+                client,
+                reqwest::Method::GET,
+                &format!("releases/tags/{}", self.tag),
+            )
+            .send()
+            .await
+            .map_err(|e| publish_error(e.to_string()))?;
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            return api_response(response).await;
+        }
+
+        let response = self
+            .request(client, reqwest::Method::POST, "releases")
+            .json(&json!({
+                "tag_name": self.tag,
+                "name": self.tag,
+                "prerelease": self.tag.contains('-'),
+                "generate_release_notes": true,
+            }))
+            .send()
+            .await
+            .map_err(|e| publish_error(e.to_string()))?;
+        api_response(response).await
+    }
+
+    /// Downloads the contents of a text asset of the release
+    async fn download_asset(&self, client: &reqwest::Client, id: u64) -> Result<String> {
+        let response = client
+            .get(format!(
+                "{}/repos/{}/releases/assets/{}",
+                self.api_url, self.repository, id
+            ))
+            .bearer_auth(&self.token)
+            .header(reqwest::header::ACCEPT, "application/octet-stream")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| publish_error(format!("downloading {}: {}", CHECKSUMS_ASSET, e)))?;
+        response
+            .text()
+            .await
+            .map_err(|e| publish_error(format!("downloading {}: {}", CHECKSUMS_ASSET, e)))
+    }
+
+    /// Uploads one asset of `size` bytes, deleting an existing asset of the
+    /// same name first
+    async fn upload_asset(
+        &self,
+        client: &reqwest::Client,
+        upload_url: &str,
+        existing: &[(String, u64)],
+        name: &str,
+        body: reqwest::Body,
+        size: u64,
+    ) -> Result<()> {
+        if let Some((_, id)) = existing.iter().find(|(existing, _)| existing == name) {
+            let response = self
+                .request(
+                    client,
+                    reqwest::Method::DELETE,
+                    &format!("releases/assets/{}", id),
+                )
+                .send()
+                .await
+                .map_err(|e| publish_error(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(publish_error(format!(
+                    "deleting existing asset {} returned {}",
+                    name,
+                    response.status()
+                )));
+            }
+        }
+
+        let response = client
+            .post(upload_url)
+            .query(&[("name", name)])
+            .bearer_auth(&self.token)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header(reqwest::header::CONTENT_LENGTH, size)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| publish_error(e.to_string()))?;
+        api_response(response).await.map(|_| ())
+    }
+
+    fn request(
+        &self,
+        client: &reqwest::Client,
+        method: reqwest::Method,
+        path: &str,
+    ) -> reqwest::RequestBuilder {
+        client
+            .request(
+                method,
+                format!("{}/repos/{}/{}", self.api_url, self.repository, path),
+            )
+            .bearer_auth(&self.token)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+}
+
+/// Returns the JSON body, turning GitHub error replies into errors
+async fn api_response(response: reqwest::Response) -> Result<Value> {
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = body["message"].as_str().unwrap_or("no error message");
+        return Err(publish_error(format!(
+            "GitHub API returned {}: {}",
+            status, message
+        )));
+    }
+    Ok(body)
+}
+
+/// `owner/name` of a GitHub repository URL (https, ssh or scp-like)
+fn github_repository(url: &str) -> Option<String> {
+    let path = [
+        "https://github.com/",
+        "http://github.com/",
+        "ssh://git@github.com/",
+        "git@github.com:",
+    ]
+    .iter()
+    .find_map(|prefix| url.strip_prefix(prefix))?;
+    let mut parts = path
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .split('/');
+    let (owner, name) = (parts.next()?, parts.next()?);
+    (!owner.is_empty() && !name.is_empty()).then(|| format!("{}/{}", owner, name))
+}
+
+/// The tag pointing exactly at `HEAD` of the repository at `path`
+async fn head_tag(path: &Path) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(["describe", "--tags", "--exact-match", "HEAD"])
+        .current_dir(path)
+        .output()
+        .await
+        .ok()?;
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !tag.is_empty()).then_some(tag)
+}
+
+/// Hex SHA-256 of the file at `path`, read in chunks
+async fn sha256_file(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<String> {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut std::fs::File::open(&path)?, &mut hasher)?;
+        Ok(hex::encode(hasher.finalize()))
+    })
+    .await
+    .map_err(|e| publish_error(format!("checksum task failed: {}", e)))?
+}
+
+/// Entries of the `sha256sum` listing `previous` with `checksums` added,
+/// replacing entries of the same file name, sorted by name
+fn merge_checksums(previous: &str, checksums: &[(String, String)]) -> Vec<(String, String)> {
+    let mut merged: BTreeMap<String, String> = previous
+        .lines()
+        .filter_map(|line| {
+            let (sha256, name) = line.split_once(char::is_whitespace)?;
+            // `sha256sum -b` marks names with `*`
+            let name = name.trim_start().trim_start_matches('*');
+            (!name.is_empty()).then(|| (name.to_string(), sha256.to_string()))
+        })
+        .collect();
+    merged.extend(checksums.iter().cloned());
+    merged.into_iter().collect()
+}
+
+/// `sha256sum`-compatible checksum listing
+fn render_checksums(checksums: &[(String, String)]) -> String {
+    checksums
+        .iter()
+        .map(|(name, sha256)| format!("{}  {}\n", sha256, name))
+        .collect()
+}
+
+fn publish_error(reason: impl Into<String>) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "publish github".to_string(),
+        reason: reason.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_repository() {
+        for url in [
+            "https://github.com/cyrup-ai/kodegen",
+            "https://github.com/cyrup-ai/kodegen.git",
+            "https://github.com/cyrup-ai/kodegen/",
+            "git@github.com:cyrup-ai/kodegen.git",
+            "ssh://git@github.com/cyrup-ai/kodegen",
+        ] {
+            assert_eq!(
+                github_repository(url).as_deref(),
+                Some("cyrup-ai/kodegen"),
+                "{}",
+                url
+            );
+        }
+        assert_eq!(
+            github_repository("https://gitlab.com/cyrup-ai/kodegen"),
+            None
+        );
+        assert_eq!(github_repository("https://github.com/cyrup-ai"), None);
+    }
+
+    #[test]
+    fn test_merge_checksums() {
+        let previous = format!(
+            "{}  kodegen.rpm\n{} *kodegen.dmg\n",
+            "aa".repeat(32),
+            "bb".repeat(32)
+        );
+        let checksums = [
+            ("kodegen.deb".to_string(), "cc".repeat(32)),
+            ("kodegen.rpm".to_string(), "dd".repeat(32)),
+        ];
+        assert_eq!(
+            merge_checksums(&previous, &checksums),
+            [
+                ("kodegen.deb".to_string(), "cc".repeat(32)),
+                ("kodegen.dmg".to_string(), "bb".repeat(32)),
+                ("kodegen.rpm".to_string(), "dd".repeat(32)),
+            ]
+        );
+        assert_eq!(merge_checksums("", &checksums), checksums);
+    }
+
+    #[test]
+    fn test_render_checksums() {
+        let checksums = [
+            ("kodegen.deb".to_string(), "ab".repeat(32)),
+            ("kodegen.rpm".to_string(), "cd".repeat(32)),
+        ];
+        assert_eq!(
+            render_checksums(&checksums),
+            format!(
+                "{}  kodegen.deb\n{}  kodegen.rpm\n",
+                "ab".repeat(32),
+                "cd".repeat(32)
+            )
+        );
+    }
+}
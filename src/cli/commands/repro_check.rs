@@ -0,0 +1,382 @@
+//! Reproducibility check (`repro-check` subcommand).
+//!
+//! Bundles the same source twice in child processes, each with fresh
+//! temporary directories and without the clone, target or container caches,
+//! then compares the artifacts. Artifacts that differ are compared entry by
+//! entry where the format allows (deb, zip, tar.gz, app bundles), so the
+//! report points at the nondeterministic input: a timestamp, file mode,
+//! owner or content.
+
+use super::in_toto::{hash_file, hash_tree};
+use crate::cli::{ReproCheckArgs, RuntimeConfig};
+use crate::error::{BundlerError, CliError, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Differing entries listed per artifact before the rest is summarized
+const MAX_REPORTED_ENTRIES: usize = 20;
+
+/// Execute the `repro-check` subcommand
+pub async fn execute_repro_check(args: ReproCheckArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    let work_dir = tempfile::Builder::new().prefix("kodegen-repro-").tempdir()?;
+    let runs = [work_dir.path().join("run-1"), work_dir.path().join("run-2")];
+
+    for (index, output_dir) in runs.iter().enumerate() {
+        runtime_config
+            .section(&format!("🔁 Build {} of 2...", index + 1))
+            .expect("Failed to write to stdout");
+        bundle(&args, output_dir).await?;
+    }
+
+    runtime_config.section("🔍 Comparing artifacts...").expect("Failed to write to stdout");
+    let first = artifact_names(&runs[0])?;
+    let second = artifact_names(&runs[1])?;
+    let mut differing = 0;
+    for name in first.union(&second) {
+        let (a, b) = (runs[0].join(name), runs[1].join(name));
+        if !first.contains(name) || !second.contains(name) {
+            differing += 1;
+            runtime_config
+                .warn(&format!("{} was only produced by one build", name))
+                .expect("Failed to write to stdout");
+            continue;
+        }
+
+        let (hash_a, hash_b) = (digest(&a)?, digest(&b)?);
+        if hash_a == hash_b {
+            runtime_config
+                .indent(&format!("✓ {} is reproducible (sha256 {})", name, hash_a))
+                .expect("Failed to write to stdout");
+            continue;
+        }
+
+        differing += 1;
+        runtime_config
+            .warn(&format!("{} differs: sha256 {} vs {}", name, hash_a, hash_b))
+            .expect("Failed to write to stdout");
+        match (entries(&a)?, entries(&b)?) {
+            (Some(entries_a), Some(entries_b)) => {
+                let differences = diff_entries(&entries_a, &entries_b);
+                for difference in differences.iter().take(MAX_REPORTED_ENTRIES) {
+                    runtime_config.indent(difference).expect("Failed to write to stdout");
+                }
+                if differences.len() > MAX_REPORTED_ENTRIES {
+                    runtime_config
+                        .indent(&format!(
+                            "... and {} more differing entries",
+                            differences.len() - MAX_REPORTED_ENTRIES
+                        ))
+                        .expect("Failed to write to stdout");
+                }
+                if differences.is_empty() {
+                    runtime_config
+                        .indent("entries are identical; the container format itself differs (ordering or archive metadata)")
+                        .expect("Failed to write to stdout");
+                }
+            }
+            _ => runtime_config
+                .indent("no entry listing for this format")
+                .expect("Failed to write to stdout"),
+        }
+    }
+
+    if args.keep {
+        let kept = work_dir.keep();
+        runtime_config
+            .indent(&format!("Kept both builds in {}", kept.display()))
+            .expect("Failed to write to stdout");
+    }
+
+    if differing == 0 {
+        runtime_config
+            .success_println(&format!("✓ {} artifact(s) are reproducible", first.len()))
+            .expect("Failed to write to stdout");
+        Ok(0)
+    } else {
+        runtime_config
+            .warn(&format!("{} artifact(s) are not reproducible", differing))
+            .expect("Failed to write to stdout");
+        Ok(1)
+    }
+}
+
+/// Bundles `args.source` into `output_dir` in a child process without caches
+async fn bundle(args: &ReproCheckArgs, output_dir: &Path) -> Result<()> {
+    let exe = std::env::current_exe()?;
+    let status = tokio::process::Command::new(exe)
+        .arg("--source")
+        .arg(&args.source)
+        .arg("--platform")
+        .arg(&args.platform)
+        .arg("--output-dir")
+        .arg(output_dir)
+        .arg("--no-cache")
+        .args(&args.bundle_args)
+        // A shared target directory would let the second build reuse the first
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("GITHUB_ACTIONS")
+        .env_remove("GITHUB_OUTPUT")
+        // Artifact paths on stdout would be mistaken for this command's output
+        .stdout(std::io::stderr())
+        .kill_on_drop(true)
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(BundlerError::Cli(CliError::ExecutionFailed {
+            command: format!("bundle --platform {}", args.platform),
+            reason: format!("exited with {}", status),
+        }));
+    }
+    Ok(())
+}
+
+/// Names of the artifacts a build wrote to `dir`
+fn artifact_names(dir: &Path) -> Result<std::collections::BTreeSet<String>> {
+    let mut names = std::collections::BTreeSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        names.insert(entry?.file_name().to_string_lossy().into_owned());
+    }
+    Ok(names)
+}
+
+/// SHA-256 of a file, or of the file listing and contents of a directory
+fn digest(path: &Path) -> Result<String> {
+    if !path.is_dir() {
+        return hash_file(path);
+    }
+    let mut hasher = Sha256::new();
+    for (file, hash) in hash_tree(path)? {
+        hasher.update(format!("{} {}\n", file, hash));
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Entries of an archive or directory, each described by its content hash
+/// and metadata, or `None` for formats without an entry listing
+fn entries(path: &Path) -> Result<Option<BTreeMap<String, String>>> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+    if path.is_dir() {
+        return Ok(Some(directory_entries(path)?));
+    }
+    if name.ends_with(".zip") {
+        return Ok(Some(zip_entries(path)?));
+    }
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let decoder = flate2::read::GzDecoder::new(std::fs::File::open(path)?);
+        return Ok(Some(tar_entries(decoder, "")?));
+    }
+    #[cfg(target_os = "linux")]
+    if name.ends_with(".deb") {
+        return Ok(Some(deb_entries(path)?));
+    }
+    Ok(None)
+}
+
+fn directory_entries(root: &Path) -> Result<BTreeMap<String, String>> {
+    let mut entries = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root).sort_by_file_name() {
+        let entry = entry.map_err(|e| std::io::Error::other(e.to_string()))?;
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let metadata = entry.metadata().map_err(|e| std::io::Error::other(e.to_string()))?;
+        let content = if entry.file_type().is_file() {
+            hash_file(entry.path())?
+        } else {
+            "-".to_string()
+        };
+        #[cfg(unix)]
+        let mode = std::os::unix::fs::PermissionsExt::mode(&metadata.permissions());
+        #[cfg(not(unix))]
+        let mode = u32::from(metadata.permissions().readonly());
+        entries.insert(
+            relative.to_string_lossy().replace('\\', "/"),
+            describe(&content, mode, None, None),
+        );
+    }
+    Ok(entries)
+}
+
+fn zip_entries(path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    let mut entries = BTreeMap::new();
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        let mtime = file.last_modified().map(|time| time.to_string());
+        let mode = file.unix_mode().unwrap_or(0);
+        let name = file.name().to_string();
+        let content = hash_reader(&mut file)?;
+        entries.insert(name, describe(&content, mode, mtime, None));
+    }
+    Ok(entries)
+}
+
+fn tar_entries(reader: impl Read, prefix: &str) -> Result<BTreeMap<String, String>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let header = entry.header();
+        let mode = header.mode()?;
+        let mtime = header.mtime()?.to_string();
+        let owner = format!("{}:{}", header.uid()?, header.gid()?);
+        let name = format!("{}{}", prefix, entry.path()?.display());
+        let content = hash_reader(&mut entry)?;
+        entries.insert(name, describe(&content, mode, Some(mtime), Some(owner)));
+    }
+    Ok(entries)
+}
+
+/// Members of a .deb, with the tarballs listed entry by entry
+#[cfg(target_os = "linux")]
+fn deb_entries(path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut archive = ar::Archive::new(std::fs::File::open(path)?);
+    let mut entries = BTreeMap::new();
+    while let Some(member) = archive.next_entry() {
+        let mut member = member?;
+        let header = member.header();
+        let name = String::from_utf8_lossy(header.identifier()).into_owned();
+        let (mode, mtime, owner) = (
+            header.mode(),
+            header.mtime().to_string(),
+            format!("{}:{}", header.uid(), header.gid()),
+        );
+        let prefix = format!("{}/", name);
+        if name.ends_with(".tar.gz") {
+            let decoder = flate2::read::GzDecoder::new(&mut member);
+            entries.extend(tar_entries(decoder, &prefix)?);
+        } else if name.ends_with(".tar.xz") {
+            let decoder = liblzma::read::XzDecoder::new(&mut member);
+            entries.extend(tar_entries(decoder, &prefix)?);
+        } else if name.ends_with(".tar.zst") {
+            let decoder = zstd::Decoder::new(&mut member)?;
+            entries.extend(tar_entries(decoder, &prefix)?);
+        } else if name.ends_with(".tar") {
+            entries.extend(tar_entries(&mut member, &prefix)?);
+        } else {
+            let content = hash_reader(&mut member)?;
+            entries.insert(name, describe(&content, mode, Some(mtime), Some(owner)));
+        }
+    }
+    Ok(entries)
+}
+
+fn hash_reader(reader: &mut impl Read) -> Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(reader, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn describe(content: &str, mode: u32, mtime: Option<String>, owner: Option<String>) -> String {
+    let mut description = format!("sha256 {}, mode {:o}", content, mode);
+    if let Some(mtime) = mtime {
+        description.push_str(&format!(", mtime {}", mtime));
+    }
+    if let Some(owner) = owner {
+        description.push_str(&format!(", owner {}", owner));
+    }
+    description
+}
+
+/// Human-readable differences between two entry listings
+fn diff_entries(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> Vec<String> {
+    let names: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| match (a.get(name), b.get(name)) {
+            (Some(first), Some(second)) if first != second => {
+                Some(format!("{}: {} vs {}", name, first, second))
+            }
+            (Some(_), None) => Some(format!("{}: only in the first build", name)),
+            (None, Some(_)) => Some(format!("{}: only in the second build", name)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tar_entries_diff() {
+        let archive = |mtime: u64| {
+            let mut builder = tar::Builder::new(Vec::new());
+            let mut header = tar::Header::new_gnu();
+            header.set_size(5);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            header.set_uid(0);
+            header.set_gid(0);
+            header.set_cksum();
+            builder.append_data(&mut header, "usr/bin/app", &b"hello"[..]).unwrap();
+            builder.into_inner().unwrap()
+        };
+        let first = tar_entries(&archive(1)[..], "").unwrap();
+        let second = tar_entries(&archive(2)[..], "").unwrap();
+
+        assert!(diff_entries(&first, &first).is_empty());
+        let differences = diff_entries(&first, &second);
+        assert_eq!(differences.len(), 1);
+        assert!(differences[0].starts_with("usr/bin/app: "));
+        assert!(differences[0].contains("mtime 1, owner 0:0 vs"));
+        assert!(differences[0].ends_with("mtime 2, owner 0:0"));
+    }
+
+    #[test]
+    fn test_entries_by_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let rpm = dir.path().join("app.rpm");
+        std::fs::write(&rpm, b"rpm").unwrap();
+        assert!(entries(&rpm).unwrap().is_none());
+
+        let app = dir.path().join("App.app");
+        std::fs::create_dir_all(app.join("Contents")).unwrap();
+        std::fs::write(app.join("Contents/Info.plist"), b"plist").unwrap();
+        let listing = entries(&app).unwrap().unwrap();
+        assert!(listing.contains_key("Contents/Info.plist"));
+        assert_eq!(digest(&app).unwrap(), digest(&app).unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_deb_entries_by_compression() {
+        let mut tar = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o755);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_mtime(0);
+        header.set_cksum();
+        tar.append_data(&mut header, "usr/bin/app", &b"hello"[..]).unwrap();
+        let tar = tar.into_inner().unwrap();
+        let mut xz = liblzma::write::XzEncoder::new(Vec::new(), 6);
+        std::io::Write::write_all(&mut xz, &tar).unwrap();
+        let members = [
+            ("debian-binary", b"2.0\n".to_vec()),
+            ("control.tar", tar.clone()),
+            ("data.tar.xz", xz.finish().unwrap()),
+            ("data.tar.zst", zstd::encode_all(&tar[..], 19).unwrap()),
+        ];
+
+        let dir = tempfile::tempdir().unwrap();
+        let deb = dir.path().join("app.deb");
+        let mut builder = ar::Builder::new(std::fs::File::create(&deb).unwrap());
+        for (name, data) in &members {
+            let header = ar::Header::new(name.as_bytes().to_vec(), data.len() as u64);
+            builder.append(&header, &data[..]).unwrap();
+        }
+        drop(builder);
+
+        let listing = deb_entries(&deb).unwrap();
+        assert!(listing.contains_key("debian-binary"));
+        for member in ["control.tar", "data.tar.xz", "data.tar.zst"] {
+            let entry = format!("{}/usr/bin/app", member);
+            assert!(listing[&entry].starts_with("sha256 "), "{:?}", listing);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Compiler caching with sccache (`--sccache`).
+//!
+//! The build runs with `RUSTC_WRAPPER` pointing at sccache, so repeat
+//! bundling of the same commit, e.g. for several package types, reuses
+//! compiled crates. The cache lives in sccache's default local directory,
+//! in a Docker volume for container builds, or in S3 when `SCCACHE_BUCKET`
+//! and the other `SCCACHE_*`/`AWS_*` variables are set; container builds
+//! receive those variables from the host.
+
+use crate::bundler::utils::tools::find_system_tool;
+use crate::cli::RuntimeConfig;
+use crate::error::{BundlerError, CliError, Result};
+use std::path::PathBuf;
+
+/// Path of the sccache executable, installing it with cargo if missing
+pub async fn ensure_installed(runtime_config: &RuntimeConfig) -> Result<PathBuf> {
+    if let Some(tool) = find_system_tool(&["sccache"], "--version").await {
+        runtime_config.verbose_println(&format!("   Using {} ({})", tool.path.display(), tool.version)).expect("Failed to write to stdout");
+        return Ok(tool.path);
+    }
+
+    runtime_config.progress("sccache not found, installing with cargo install...").expect("Failed to write to stdout");
+    let status = tokio::process::Command::new("cargo")
+        .args(["install", "--locked", "sccache"])
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(BundlerError::Cli(CliError::ExecutionFailed {
+            command: "cargo install sccache".to_string(),
+            reason: format!("Installation failed with exit code: {:?}", status.code()),
+        }));
+    }
+
+    // cargo installs into $CARGO_HOME/bin, which may not be on PATH
+    if let Some(tool) = find_system_tool(&["sccache"], "--version").await {
+        return Ok(tool.path);
+    }
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+    let path = cargo_home
+        .map(|dir| dir.join("bin").join(format!("sccache{}", std::env::consts::EXE_SUFFIX)))
+        .filter(|path| path.is_file())
+        .ok_or_else(|| {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command: "cargo install sccache".to_string(),
+                reason: "sccache was installed but cannot be found; add cargo's bin directory to PATH".to_string(),
+            })
+        })?;
+    Ok(path)
+}
@@ -0,0 +1,137 @@
+//! Installation smoke tests: `--smoke-test` installs every produced package
+//! into a clean container and runs its binary before anything is published,
+//! and the `install-test` subcommand does the same for an existing artifact.
+
+use crate::cli::InstallTestArgs;
+use crate::cli::args::{Args, RuntimeConfig};
+use crate::cli::docker::image::ensure_docker_running;
+use crate::cli::docker::smoke_test::{SmokeTestKind, SmokeTestOutcome, SmokeTestReport, smoke_test};
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::RepositorySource;
+use std::path::PathBuf;
+
+/// Lines of container output included in a failure
+const LOG_TAIL_LINES: usize = 20;
+
+/// Smoke tests `artifacts`, failing on the first package that does not
+/// install or run.
+///
+/// `binary` is the executable to run; when unknown it is read from a local
+/// source's Cargo.toml, else the package's first executable is used.
+pub async fn smoke_test_artifacts(
+    args: &Args,
+    artifacts: &[PathBuf],
+    binary: Option<&str>,
+    runtime_config: &RuntimeConfig,
+) -> Result<()> {
+    let testable: Vec<_> = artifacts
+        .iter()
+        .filter_map(|artifact| match SmokeTestKind::of(artifact) {
+            Some(kind) => Some((artifact, kind)),
+            None => {
+                runtime_config
+                    .verbose_println(&format!("   {} cannot be smoke tested; skipping", artifact.display()))
+                    .expect("Failed to write to stdout");
+                None
+            }
+        })
+        .collect();
+    if testable.is_empty() {
+        return Ok(());
+    }
+    ensure_docker_running(args.auto_start_docker, runtime_config).await?;
+
+    let local_binary = match binary {
+        Some(_) => None,
+        None => match RepositorySource::parse(&args.source) {
+            Ok(RepositorySource::Local(path)) => crate::metadata::load_manifest(&path.join("Cargo.toml"))
+                .ok()
+                .map(|manifest| manifest.binary_name),
+            _ => None,
+        },
+    };
+    let binary = binary.or(local_binary.as_deref());
+
+    for (artifact, kind) in testable {
+        let report = smoke_test(artifact, kind, binary, None, runtime_config).await?;
+        let Some(failure) = failure(&report) else {
+            runtime_config
+                .success_println(&format!("✓ Smoke test passed: {}", report.artifact.display()))
+                .expect("Failed to write to stdout");
+            continue;
+        };
+        return Err(BundlerError::Cli(CliError::ExecutionFailed {
+            command: "smoke test".to_string(),
+            reason: format!(
+                "{}: {}\n{}",
+                report.artifact.display(),
+                failure,
+                report.log_tail(LOG_TAIL_LINES)
+            ),
+        }));
+    }
+    Ok(())
+}
+
+/// Execute the `install-test` subcommand
+///
+/// Exits with 0 when the artifact installed and ran, 1 otherwise. With
+/// `--json` the report is printed to stdout as JSON instead.
+pub async fn execute_install_test(args: InstallTestArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    if !args.artifact.is_file() {
+        return Err(BundlerError::Cli(CliError::InvalidArguments {
+            reason: format!("{} does not exist", args.artifact.display()),
+        }));
+    }
+    let kind = SmokeTestKind::of(&args.artifact).ok_or_else(|| {
+        BundlerError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "{} cannot be install tested; expected a .deb, .rpm, .AppImage or NSIS .exe",
+                args.artifact.display()
+            ),
+        })
+    })?;
+    ensure_docker_running(args.auto_start_docker, &runtime_config).await?;
+
+    let report = smoke_test(
+        &args.artifact,
+        kind,
+        args.binary.as_deref(),
+        args.image.as_deref(),
+        &runtime_config,
+    )
+    .await?;
+    let failure = failure(&report);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if let Some(failure) = &failure {
+        runtime_config
+            .warn(&format!("{}: {}", report.artifact.display(), failure))
+            .expect("Failed to write to stdout");
+        for line in report.log_tail(LOG_TAIL_LINES).lines() {
+            runtime_config.indent(line).expect("Failed to write to stdout");
+        }
+    } else {
+        runtime_config
+            .success_println(&format!(
+                "✓ {} installed and ran in {}",
+                report.artifact.display(),
+                report.image
+            ))
+            .expect("Failed to write to stdout");
+    }
+    Ok(if failure.is_some() { 1 } else { 0 })
+}
+
+/// Why `report` failed; `None` when it passed
+fn failure(report: &SmokeTestReport) -> Option<String> {
+    match report.outcome {
+        SmokeTestOutcome::Passed => None,
+        SmokeTestOutcome::InstallFailed => Some("installation failed".to_string()),
+        SmokeTestOutcome::RunFailed => Some("the installed binary failed to run".to_string()),
+        SmokeTestOutcome::SetupFailed => Some(format!(
+            "preparing the {} container failed (exit code {:?})",
+            report.image, report.exit_code
+        )),
+    }
+}
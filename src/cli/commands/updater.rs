@@ -0,0 +1,520 @@
+//! Tauri updater manifest (`latest.json`) generation.
+//!
+//! `--updater` signs every artifact the Tauri updater can install with a
+//! minisign key and lists them per platform:
+//!
+//! | Artifact | Platform keys |
+//! |----------|---------------|
+//! | AppImage | `linux-<arch>`, `linux-<arch>-appimage` |
+//! | deb / rpm | `linux-<arch>-deb`, `linux-<arch>-rpm` |
+//! | NSIS setup | `windows-<arch>`, `windows-<arch>-nsis` |
+//! | .app | `darwin-<arch>`, `darwin-<arch>-app` (packed as `.app.tar.gz`) |
+//!
+//! Signatures use minisign's prehashed (BLAKE2b) ed25519 scheme and are
+//! encoded like `tauri signer sign`: the `.sig` file and the manifest hold
+//! the base64 of the minisign signature text. Secret keys generated by
+//! `tauri signer generate` or `minisign -G` are accepted, including
+//! password-protected ones.
+
+use super::publish::GithubRelease;
+use crate::cli::args::{Args, ArtifactOutput, RuntimeConfig};
+use crate::error::{BundlerError, CliError, Result};
+use base64::Engine;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Blake2b512, Digest};
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::{Map, json};
+use std::path::{Path, PathBuf};
+
+/// Name of the written manifest
+const MANIFEST_NAME: &str = "latest.json";
+
+/// Environment variable holding the password of an encrypted key
+const PASSWORD_ENV: &str = "TAURI_SIGNING_PRIVATE_KEY_PASSWORD";
+
+/// Signs artifacts and writes `latest.json`
+pub struct Updater {
+    key: SecretKey,
+    /// Artifact URLs are `<download_base>/<file name>`
+    download_base: String,
+    /// Tauri architecture of Windows artifacts (they may be cross-compiled)
+    windows_arch: String,
+    /// Version used when the caller does not know the manifest version
+    fallback_version: Option<String>,
+}
+
+impl Updater {
+    /// Loads the signing key for `--updater`; `None` without the flag.
+    ///
+    /// Called before bundling so a missing key or wrong password fails fast.
+    pub async fn resolve(args: &Args, release: Option<&GithubRelease>) -> Result<Option<Self>> {
+        if !args.updater {
+            return Ok(None);
+        }
+        let key_arg = args.updater_key.as_deref().ok_or_else(|| {
+            updater_error(
+                "--updater needs a minisign secret key; pass --updater-key or set TAURI_SIGNING_PRIVATE_KEY",
+            )
+        })?;
+        let download_base = match (&args.updater_url, release) {
+            (Some(url), _) => url.trim_end_matches('/').to_string(),
+            (None, Some(release)) => release.download_base(),
+            (None, None) => {
+                return Err(updater_error(
+                    "--updater needs --updater-url or --publish github to build download URLs",
+                ));
+            }
+        };
+
+        let key_text = if Path::new(key_arg).is_file() {
+            tokio::fs::read_to_string(key_arg).await?
+        } else {
+            key_arg.to_string()
+        };
+        let password = std::env::var(PASSWORD_ENV).ok();
+        // Unlocking runs scrypt with minisign's 1 GiB work factor
+        let key =
+            tokio::task::spawn_blocking(move || SecretKey::parse(&key_text, password.as_deref()))
+                .await
+                .map_err(|e| updater_error(format!("key decryption task failed: {}", e)))??;
+
+        let windows_target = args
+            .target
+            .as_deref()
+            .unwrap_or(super::DEFAULT_WINDOWS_TARGET);
+        Ok(Some(Self {
+            key,
+            download_base,
+            windows_arch: tauri_arch(windows_target.split('-').next().unwrap_or_default()),
+            fallback_version: super::package_version(args).or_else(|| {
+                release.map(|release| release.tag().trim_start_matches('v').to_string())
+            }),
+        }))
+    }
+
+    /// Signs `artifacts` and writes `latest.json` beside the output.
+    ///
+    /// Returns the generated files (archives, `.sig` files and the
+    /// manifest) so they can be published with the artifacts.
+    pub async fn write_manifest(
+        &self,
+        artifacts: &[PathBuf],
+        version: Option<&str>,
+        output: &ArtifactOutput,
+        runtime_config: &RuntimeConfig,
+    ) -> Result<Vec<PathBuf>> {
+        let version = version
+            .map(String::from)
+            .or_else(|| self.fallback_version.clone())
+            .ok_or_else(|| {
+                updater_error(
+                    "cannot determine the version for latest.json; use a local source or --publish with a release tag",
+                )
+            })?;
+        runtime_config
+            .section(&format!("🔄 Writing updater manifest for v{}", version))
+            .expect("Failed to write to stdout");
+
+        let host_arch = tauri_arch(std::env::consts::ARCH);
+        let mut platforms = Map::new();
+        let mut generated = Vec::new();
+        for artifact in artifacts {
+            let Some((os, installer)) = updater_target(artifact) else {
+                runtime_config
+                    .verbose_println(&format!(
+                        "   {} cannot be installed by the updater; skipping",
+                        artifact.display()
+                    ))
+                    .expect("Failed to write to stdout");
+                continue;
+            };
+            let arch = if os == "windows" {
+                &self.windows_arch
+            } else {
+                &host_arch
+            };
+
+            let payload = if installer == "app" {
+                let archive = pack_app(artifact).await?;
+                generated.push(archive.clone());
+                archive
+            } else {
+                artifact.clone()
+            };
+
+            let key = self.key.clone();
+            let path = payload.clone();
+            let signature = tokio::task::spawn_blocking(move || key.sign_file(&path))
+                .await
+                .map_err(|e| updater_error(format!("signing task failed: {}", e)))??;
+            let sig_path = PathBuf::from(format!("{}.sig", payload.display()));
+            tokio::fs::write(&sig_path, &signature).await?;
+            generated.push(sig_path);
+
+            let file_name = payload
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .ok_or_else(|| {
+                    updater_error(format!("invalid artifact path {}", payload.display()))
+                })?;
+            let entry = json!({
+                "signature": signature,
+                "url": format!("{}/{}", self.download_base, file_name),
+            });
+            // AppImage, NSIS and .app are the default update of their OS
+            if matches!(installer, "appimage" | "nsis" | "app") {
+                platforms.insert(format!("{}-{}", os, arch), entry.clone());
+            }
+            platforms.insert(format!("{}-{}-{}", os, arch, installer), entry);
+            runtime_config
+                .indent(&format!(
+                    "✓ Signed {} ({}-{}-{})",
+                    file_name, os, arch, installer
+                ))
+                .expect("Failed to write to stdout");
+        }
+
+        if platforms.is_empty() {
+            runtime_config
+                .warn("No updater-installable artifacts; latest.json not written")
+                .expect("Failed to write to stdout");
+            return Ok(generated);
+        }
+
+        let manifest = json!({
+            "version": version,
+            "pub_date": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "platforms": platforms,
+        });
+        let dir = match output {
+            ArtifactOutput::Directory(dir) => dir.as_path(),
+            ArtifactOutput::File(path) => path.parent().unwrap_or(Path::new(".")),
+        };
+        let manifest_path = dir.join(MANIFEST_NAME);
+        tokio::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?).await?;
+        generated.push(manifest_path.clone());
+
+        runtime_config
+            .success_println(&format!(
+                "✓ Updater manifest at: {}",
+                manifest_path.display()
+            ))
+            .expect("Failed to write to stdout");
+        Ok(generated)
+    }
+}
+
+/// Updater OS and installer name of an artifact, if the updater can install it
+fn updater_target(path: &Path) -> Option<(&'static str, &'static str)> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    match extension.as_str() {
+        "appimage" => Some(("linux", "appimage")),
+        "deb" => Some(("linux", "deb")),
+        "rpm" => Some(("linux", "rpm")),
+        "exe" => Some(("windows", "nsis")),
+        "app" => Some(("darwin", "app")),
+        _ => None,
+    }
+}
+
+/// Tauri's name for a Rust architecture (`std::env::consts::ARCH` or a triple prefix)
+fn tauri_arch(arch: &str) -> String {
+    match arch {
+        "x86" | "i586" | "i686" => "i686".to_string(),
+        "arm" | "armv7" | "thumbv7neon" => "armv7".to_string(),
+        arch if arch.starts_with("armv7") => "armv7".to_string(),
+        arch => arch.to_string(),
+    }
+}
+
+/// Packs a .app bundle into `<name>.app.tar.gz` next to it
+async fn pack_app(app: &Path) -> Result<PathBuf> {
+    let name = app
+        .file_name()
+        .ok_or_else(|| updater_error(format!("invalid app path {}", app.display())))?
+        .to_owned();
+    let archive = app.with_file_name(format!("{}.tar.gz", name.to_string_lossy()));
+    let (source, dest) = (app.to_path_buf(), archive.clone());
+    tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+        let file = std::fs::File::create(&dest)?;
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+        tar.append_dir_all(&name, &source)?;
+        tar.into_inner()?.finish()?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| updater_error(format!("archive task failed: {}", e)))??;
+    Ok(archive)
+}
+
+/// A decrypted minisign secret key
+#[derive(Clone)]
+struct SecretKey {
+    key_id: [u8; 8],
+    signing_key: SigningKey,
+}
+
+impl SecretKey {
+    /// Parses a minisign secret key file, or its base64 as used by Tauri
+    ///
+    /// Layout of the decoded key: signature algorithm `Ed`, KDF algorithm
+    /// (`Sc` for scrypt, zero when unencrypted), checksum algorithm `B2`,
+    /// KDF salt, opslimit and memlimit, then key id, secret key and
+    /// checksum (XORed with the scrypt stream when encrypted).
+    fn parse(text: &str, password: Option<&str>) -> Result<Self> {
+        let base64 = base64::engine::general_purpose::STANDARD;
+        let text = if text.trim_start().starts_with("untrusted comment:") {
+            text.to_string()
+        } else {
+            let decoded = base64
+                .decode(text.trim())
+                .map_err(|e| updater_error(format!("invalid minisign secret key: {}", e)))?;
+            String::from_utf8_lossy(&decoded).into_owned()
+        };
+        let encoded = text
+            .lines()
+            .nth(1)
+            .ok_or_else(|| updater_error("invalid minisign secret key: missing key line"))?;
+        let bytes = base64
+            .decode(encoded.trim())
+            .map_err(|e| updater_error(format!("invalid minisign secret key: {}", e)))?;
+        if bytes.len() != 158 || &bytes[..2] != b"Ed" || &bytes[4..6] != b"B2" {
+            return Err(updater_error(
+                "invalid minisign secret key: unsupported format",
+            ));
+        }
+
+        let mut keynum = bytes[54..].to_vec();
+        match &bytes[2..4] {
+            b"Sc" => {
+                let opslimit = u64::from_le_bytes(bytes[38..46].try_into().expect("8 bytes"));
+                let memlimit = u64::from_le_bytes(bytes[46..54].try_into().expect("8 bytes"));
+                let (n_log2, r, p) = scrypt_params(opslimit, memlimit);
+                // Tauri encrypts keys generated without a password with ""
+                let stream = scrypt(
+                    password.unwrap_or_default().as_bytes(),
+                    &bytes[6..38],
+                    n_log2,
+                    r,
+                    p,
+                    keynum.len(),
+                )?;
+                keynum.iter_mut().zip(stream).for_each(|(b, s)| *b ^= s);
+            }
+            [0, 0] => {}
+            _ => {
+                return Err(updater_error(
+                    "invalid minisign secret key: unsupported key derivation",
+                ));
+            }
+        }
+
+        let (key_id, rest) = keynum.split_at(8);
+        let (secret, checksum) = rest.split_at(64);
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(b"Ed");
+        hasher.update(key_id);
+        hasher.update(secret);
+        if hasher.finalize().as_slice() != checksum {
+            return Err(updater_error(format!(
+                "cannot unlock minisign secret key: wrong password in {}?",
+                PASSWORD_ENV
+            )));
+        }
+
+        let signing_key = SigningKey::from_keypair_bytes(secret.try_into().expect("64 bytes"))
+            .map_err(|e| updater_error(format!("invalid minisign secret key: {}", e)))?;
+        Ok(Self {
+            key_id: key_id.try_into().expect("8 bytes"),
+            signing_key,
+        })
+    }
+
+    /// Signs the file at `path`, returning the base64 minisign signature
+    fn sign_file(&self, path: &Path) -> Result<String> {
+        let mut hasher = Blake2b512::new();
+        std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+        let signature = self.signing_key.sign(&hasher.finalize()).to_bytes();
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let trusted_comment = format!(
+            "timestamp:{}\tfile:{}",
+            chrono::Utc::now().timestamp(),
+            file_name
+        );
+        let global_signature = self
+            .signing_key
+            .sign(&[&signature[..], trusted_comment.as_bytes()].concat())
+            .to_bytes();
+
+        let base64 = base64::engine::general_purpose::STANDARD;
+        let text = format!(
+            "untrusted comment: signature from kodegen_bundler_bundle secret key\n{}\ntrusted comment: {}\n{}\n",
+            base64.encode([&b"ED"[..], &self.key_id, &signature].concat()),
+            trusted_comment,
+            base64.encode(global_signature)
+        );
+        Ok(base64.encode(text))
+    }
+}
+
+/// scrypt parameters (log2 N, r, p) for libsodium's opslimit and memlimit
+fn scrypt_params(opslimit: u64, memlimit: u64) -> (u32, usize, usize) {
+    let opslimit = opslimit.max(32768);
+    let r = 8u64;
+    let n_log2_below = |max_n: u64| (1..63).find(|n| (1u64 << n) > max_n / 2).unwrap_or(63);
+    if opslimit < memlimit / 32 {
+        (n_log2_below(opslimit / (r * 4)), r as usize, 1)
+    } else {
+        let n_log2 = n_log2_below(memlimit / (r * 128));
+        let max_rp = ((opslimit / 4) >> n_log2).min(0x3fff_ffff);
+        (n_log2, r as usize, (max_rp / r) as usize)
+    }
+}
+
+/// scrypt key derivation (RFC 7914) with minisign's parameters
+fn scrypt(password: &[u8], salt: &[u8], n_log2: u32, r: usize, p: usize, len: usize) -> Result<Vec<u8>> {
+    let params = u8::try_from(n_log2)
+        .ok()
+        .zip(u32::try_from(r).ok())
+        .zip(u32::try_from(p).ok())
+        .and_then(|((log_n, r), p)| scrypt::Params::new(log_n, r, p, scrypt::Params::RECOMMENDED_LEN).ok())
+        .ok_or_else(|| {
+            updater_error(format!(
+                "invalid minisign secret key: unsupported scrypt parameters (N = 2^{}, r = {}, p = {})",
+                n_log2, r, p
+            ))
+        })?;
+    // The output length is taken from the buffer, not from the parameters
+    let mut output = vec![0u8; len];
+    scrypt::scrypt(password, salt, &params, &mut output)
+        .map_err(|e| updater_error(format!("scrypt key derivation failed: {}", e)))?;
+    Ok(output)
+}
+
+fn updater_error(reason: impl Into<String>) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "updater manifest".to_string(),
+        reason: reason.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    #[test]
+    fn test_scrypt() {
+        // RFC 7914, section 12
+        assert_eq!(
+            hex::encode(scrypt(b"", b"", 4, 1, 1, 64).unwrap()),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+        assert_eq!(
+            hex::encode(scrypt(b"password", b"NaCl", 10, 8, 16, 64).unwrap()),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+        assert_eq!(scrypt_params(33554432, 1073741824), (20, 8, 1));
+    }
+
+    /// Encodes a secret key the way `minisign -G` does
+    fn encode_key(seed: [u8; 32], key_id: [u8; 8], password: Option<&str>) -> String {
+        let signing_key = SigningKey::from_bytes(&seed);
+        let mut keynum = key_id.to_vec();
+        keynum.extend(signing_key.to_keypair_bytes());
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(b"Ed");
+        hasher.update(&keynum);
+        keynum.extend(hasher.finalize());
+
+        let salt = [7u8; 32];
+        let (opslimit, memlimit) = (32768u64, u64::MAX);
+        if let Some(password) = password {
+            let (n_log2, r, p) = scrypt_params(opslimit, memlimit);
+            let stream = scrypt(password.as_bytes(), &salt, n_log2, r, p, keynum.len()).unwrap();
+            keynum.iter_mut().zip(stream).for_each(|(b, s)| *b ^= s);
+        }
+        let mut bytes = b"Ed".to_vec();
+        bytes.extend(if password.is_some() { *b"Sc" } else { [0, 0] });
+        bytes.extend(b"B2");
+        bytes.extend(salt);
+        bytes.extend(opslimit.to_le_bytes());
+        bytes.extend(memlimit.to_le_bytes());
+        bytes.extend(keynum);
+        format!(
+            "untrusted comment: minisign encrypted secret key\n{}\n",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    }
+
+    #[test]
+    fn test_parse_secret_key() {
+        let plain = encode_key([1; 32], [2; 8], None);
+        let key = SecretKey::parse(&plain, None).unwrap();
+        assert_eq!(key.key_id, [2; 8]);
+        assert_eq!(key.signing_key.to_bytes(), [1; 32]);
+
+        // Tauri stores the key file base64-encoded
+        let encrypted = base64::engine::general_purpose::STANDARD.encode(encode_key(
+            [1; 32],
+            [2; 8],
+            Some("secret"),
+        ));
+        let key = SecretKey::parse(&encrypted, Some("secret")).unwrap();
+        assert_eq!(key.signing_key.to_bytes(), [1; 32]);
+        assert!(SecretKey::parse(&encrypted, Some("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_sign_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.AppImage");
+        std::fs::write(&path, b"payload").unwrap();
+        let key = SecretKey::parse(&encode_key([1; 32], [2; 8], None), None).unwrap();
+
+        let base64 = base64::engine::general_purpose::STANDARD;
+        let text =
+            String::from_utf8(base64.decode(key.sign_file(&path).unwrap()).unwrap()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[2].starts_with("trusted comment: timestamp:"));
+        assert!(lines[2].ends_with("\tfile:app.AppImage"));
+
+        let signature = base64.decode(lines[1]).unwrap();
+        assert_eq!(&signature[..2], b"ED");
+        assert_eq!(&signature[2..10], &[2; 8]);
+        let verifying_key = key.signing_key.verifying_key();
+        let sig = Signature::from_slice(&signature[10..]).unwrap();
+        verifying_key
+            .verify(&Blake2b512::digest(b"payload"), &sig)
+            .unwrap();
+
+        let global = Signature::from_slice(&base64.decode(lines[3]).unwrap()).unwrap();
+        let trusted = lines[2].trim_start_matches("trusted comment: ");
+        verifying_key
+            .verify(&[&signature[10..], trusted.as_bytes()].concat(), &global)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_updater_target() {
+        assert_eq!(
+            updater_target(Path::new("a.AppImage")),
+            Some(("linux", "appimage"))
+        );
+        assert_eq!(
+            updater_target(Path::new("a.exe")),
+            Some(("windows", "nsis"))
+        );
+        assert_eq!(updater_target(Path::new("A.app")), Some(("darwin", "app")));
+        assert_eq!(updater_target(Path::new("a.dmg")), None);
+        assert_eq!(tauri_arch("x86"), "i686");
+        assert_eq!(tauri_arch("aarch64"), "aarch64");
+    }
+}
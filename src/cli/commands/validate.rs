@@ -0,0 +1,506 @@
+//! Bundle configuration checks without building.
+//!
+//! The `validate` subcommand loads Cargo.toml the way a bundle run does,
+//! including icon and entitlements discovery under `assets/`, and reports
+//! the problems in `[package.metadata.bundle]` each package type would hit:
+//! a missing or malformed macOS identifier, missing or mis-sized icons,
+//! referenced files that do not exist and keys no setting accepts (usually
+//! typos). A bad configuration otherwise only surfaces minutes into a
+//! container build.
+
+use crate::bundler::resources::icons::{ICNS_SIZES, IconInfo, icon_problem, missing_sizes};
+use crate::bundler::resources::{files, locales, svg};
+use crate::bundler::{BundleSettings, PackageType};
+use crate::cli::{RuntimeConfig, ValidateArgs};
+use crate::error::{BundlerError, CliError, Result};
+use crate::metadata::load_manifest;
+use std::path::{Path, PathBuf};
+
+/// How serious a configuration problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    /// Bundling fails or produces a broken package
+    Error,
+    /// Bundling succeeds with a degraded result
+    Warning,
+}
+
+/// A configuration problem and the package types it affects
+#[derive(Debug)]
+struct Problem {
+    severity: Severity,
+    platforms: String,
+    field: String,
+    message: String,
+}
+
+/// Collects problems for the selected package types
+struct Checker<'a> {
+    root: &'a Path,
+    package_types: &'a [PackageType],
+    problems: Vec<Problem>,
+}
+
+impl<'a> Checker<'a> {
+    /// Package types of `group` being checked, as shown in the table, or
+    /// `None` when none of them are
+    fn scope(&self, group: &[PackageType]) -> Option<String> {
+        let selected: Vec<_> = group
+            .iter()
+            .filter(|package_type| self.package_types.contains(package_type))
+            .collect();
+        if selected.is_empty() {
+            None
+        } else if selected.len() == PackageType::ALL.len() {
+            Some("all".to_string())
+        } else {
+            Some(
+                selected
+                    .iter()
+                    .map(|package_type| package_type.short_name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        }
+    }
+
+    fn push(&mut self, severity: Severity, group: &[PackageType], field: &str, message: String) {
+        if let Some(platforms) = self.scope(group) {
+            self.problems.push(Problem {
+                severity,
+                platforms,
+                field: field.to_string(),
+                message,
+            });
+        }
+    }
+
+    /// Path of a configured file, relative paths taken from the package root
+    fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Reports `path` as an error when it is set but does not exist
+    fn file(&mut self, group: &[PackageType], field: &str, path: Option<&Path>) {
+        if let Some(path) = path
+            && !self.resolve(path).exists()
+        {
+            self.push(
+                Severity::Error,
+                group,
+                field,
+                format!("{} does not exist", path.display()),
+            );
+        }
+    }
+}
+
+/// Checks the bundle configuration of a local project
+pub async fn execute_validate(args: ValidateArgs, runtime_config: RuntimeConfig) -> Result<i32> {
+    let cargo_toml = if args.source.is_dir() {
+        args.source.join("Cargo.toml")
+    } else {
+        args.source.clone()
+    };
+    let package_types = if args.platforms.is_empty() {
+        PackageType::ALL.to_vec()
+    } else {
+        args.platforms
+            .iter()
+            .map(|platform| platform.parse::<PackageType>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                BundlerError::Cli(CliError::InvalidArguments {
+                    reason: e.to_string(),
+                })
+            })?
+    };
+
+    runtime_config
+        .section(&format!("🔎 Validating {}...", cargo_toml.display()))
+        .expect("Failed to write to stdout");
+
+    let problems = match load_manifest(&cargo_toml) {
+        Ok(manifest) => {
+            let root = cargo_toml.parent().unwrap_or_else(|| Path::new("."));
+            let mut problems = check(&manifest.bundle_settings, root, &package_types);
+            problems.extend(manifest.unknown_keys.into_iter().map(|key| Problem {
+                severity: Severity::Warning,
+                platforms: "all".to_string(),
+                message: match key.suggestion {
+                    Some(suggestion) => {
+                        format!("unknown key, ignored; did you mean `{}`?", suggestion)
+                    }
+                    None => "unknown key, ignored".to_string(),
+                },
+                field: key.path,
+            }));
+            problems
+        }
+        Err(e) => vec![Problem {
+            severity: Severity::Error,
+            platforms: "all".to_string(),
+            field: "Cargo.toml".to_string(),
+            message: e.to_string(),
+        }],
+    };
+
+    if problems.is_empty() {
+        runtime_config
+            .success_println("Bundle configuration is valid")
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    runtime_config
+        .indent(&format!(
+            "{:<8} {:<20} {:<28} {}",
+            "SEVERITY", "PLATFORMS", "FIELD", "PROBLEM"
+        ))
+        .expect("Failed to write to stdout");
+    for problem in &problems {
+        let severity = match problem.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        runtime_config
+            .indent(&format!(
+                "{:<8} {:<20} {:<28} {}",
+                severity, problem.platforms, problem.field, problem.message
+            ))
+            .expect("Failed to write to stdout");
+    }
+
+    let errors = problems
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .count();
+    runtime_config
+        .warn(&format!(
+            "{} error(s), {} warning(s)",
+            errors,
+            problems.len() - errors
+        ))
+        .expect("Failed to write to stdout");
+    Ok(if errors > 0 { 1 } else { 0 })
+}
+
+/// Problems in `settings` for `package_types`, with relative paths taken
+/// from the package `root`
+fn check(settings: &BundleSettings, root: &Path, package_types: &[PackageType]) -> Vec<Problem> {
+    use PackageType::*;
+
+    let mut checker = Checker {
+        root,
+        package_types,
+        problems: Vec::new(),
+    };
+    let macos = [MacOsBundle, Dmg];
+    let linux = [Deb, Rpm, AppImage];
+    let windows = [Exe, Portable];
+
+    // Icons, shared by every package type
+    let mut largest_icon = None;
+    let mut sources = Vec::new();
+    for path in settings.icon.iter().flatten() {
+        let field = format!("icon[{}]", path.display());
+        let resolved = checker.resolve(path);
+        if let Some(problem) = icon_problem(&resolved) {
+            checker.push(Severity::Error, &PackageType::ALL, &field, problem);
+        } else if svg::is_svg(path) {
+            // Rendered into every size at bundle time
+            for size in svg::raster_sizes() {
+                largest_icon = largest_icon.max(Some(size));
+                sources.push(IconInfo {
+                    path: path.clone(),
+                    width: size,
+                    height: size,
+                });
+            }
+        }
+        // Mis-sized PNGs still end up in the icon set
+        if !svg::is_svg(path)
+            && let Ok((width, height)) = image::image_dimensions(&resolved)
+        {
+            largest_icon = largest_icon.max(Some(width.min(height)));
+            sources.push(IconInfo {
+                path: path.clone(),
+                width,
+                height,
+            });
+        }
+    }
+
+    // macOS
+    match settings.identifier.as_deref() {
+        None => checker.push(
+            Severity::Error,
+            &macos,
+            "identifier",
+            "required for macOS bundles".to_string(),
+        ),
+        Some(identifier) if !is_valid_identifier(identifier) => checker.push(
+            Severity::Error,
+            &macos,
+            "identifier",
+            format!(
+                "{:?} is not a reverse-DNS identifier (letters, digits, '-' and '.', e.g. com.example.app)",
+                identifier
+            ),
+        ),
+        Some(_) => {}
+    }
+    match (&settings.icns, largest_icon) {
+        (Some(icns), _) => checker.file(&macos, "icns", Some(icns)),
+        (None, None) => checker.push(
+            Severity::Error,
+            &macos,
+            "icon",
+            "no assets/img/icon.icns or PNG icons".to_string(),
+        ),
+        (None, Some(_)) => {
+            let missing: Vec<String> = missing_sizes(&sources, &ICNS_SIZES)
+                .iter()
+                .map(|size| format!("{}x{}", size, size))
+                .collect();
+            if !missing.is_empty() {
+                checker.push(
+                    Severity::Warning,
+                    &macos,
+                    "icon",
+                    format!(
+                        "no {} PNG; the generated .icns scales the nearest size instead",
+                        missing.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+    checker.file(
+        &macos,
+        "macos.entitlements",
+        settings.macos.entitlements.as_deref(),
+    );
+    checker.file(&[Dmg], "dmg.background", settings.dmg.background.as_deref());
+    checker.file(&[Dmg], "dmg.license", settings.dmg.license.as_deref());
+    checker.file(
+        &[Dmg],
+        "dmg.volume_icon",
+        settings.dmg.volume_icon.as_deref(),
+    );
+
+    // Resources, bundled into every package type
+    if let Some(patterns) = &settings.resources
+        && let Err(e) = files::expand(patterns, root)
+    {
+        checker.push(
+            Severity::Error,
+            &PackageType::ALL,
+            "resources",
+            e.to_string(),
+        );
+    }
+    if let Some(patterns) = &settings.localizations
+        && let Err(e) = locales::expand(patterns, root, "")
+    {
+        checker.push(
+            Severity::Error,
+            &PackageType::ALL,
+            "localizations",
+            e.to_string(),
+        );
+    }
+
+    // Linux
+    if largest_icon.is_none() {
+        checker.push(
+            Severity::Error,
+            &[AppImage],
+            "icon",
+            "AppImages require a PNG icon".to_string(),
+        );
+        checker.push(
+            Severity::Warning,
+            &[Deb, Rpm],
+            "icon",
+            "installed without an icon".to_string(),
+        );
+    }
+    for (group, name, files) in [
+        ([Deb], "deb", &settings.deb.files),
+        ([Rpm], "rpm", &settings.rpm.files),
+        ([AppImage], "appimage", &settings.appimage.files),
+    ] {
+        for (destination, source) in files {
+            checker.file(
+                &group,
+                &format!("{}.files[{}]", name, destination.display()),
+                Some(source),
+            );
+        }
+    }
+    let deb = &settings.deb;
+    checker.file(
+        &[Deb],
+        "deb.desktop_template",
+        deb.desktop_template.as_deref(),
+    );
+    checker.file(&[Deb], "deb.changelog", deb.changelog.as_deref());
+    checker.file(
+        &[Deb],
+        "deb.pre_install_script",
+        deb.pre_install_script.as_deref(),
+    );
+    checker.file(
+        &[Deb],
+        "deb.post_install_script",
+        deb.post_install_script.as_deref(),
+    );
+    checker.file(
+        &[Deb],
+        "deb.pre_remove_script",
+        deb.pre_remove_script.as_deref(),
+    );
+    checker.file(
+        &[Deb],
+        "deb.post_remove_script",
+        deb.post_remove_script.as_deref(),
+    );
+    let rpm = &settings.rpm;
+    checker.file(
+        &[Rpm],
+        "rpm.desktop_template",
+        rpm.desktop_template.as_deref(),
+    );
+    checker.file(
+        &[Rpm],
+        "rpm.pre_install_script",
+        rpm.pre_install_script.as_deref(),
+    );
+    checker.file(
+        &[Rpm],
+        "rpm.post_install_script",
+        rpm.post_install_script.as_deref(),
+    );
+    checker.file(
+        &[Rpm],
+        "rpm.pre_remove_script",
+        rpm.pre_remove_script.as_deref(),
+    );
+    checker.file(
+        &[Rpm],
+        "rpm.post_remove_script",
+        rpm.post_remove_script.as_deref(),
+    );
+    checker.file(
+        &[AppImage],
+        "appimage.runtime_file",
+        settings.appimage.runtime_file.as_deref(),
+    );
+    for man_page in &settings.man_pages {
+        checker.file(&linux, "man_pages", Some(man_page));
+    }
+    let completions = &settings.completions;
+    checker.file(&linux, "completions.bash", completions.bash.as_deref());
+    checker.file(&linux, "completions.zsh", completions.zsh.as_deref());
+    checker.file(&linux, "completions.fish", completions.fish.as_deref());
+
+    // Windows
+    match &settings.ico {
+        Some(ico) => checker.file(&windows, "ico", Some(ico)),
+        None => checker.push(
+            Severity::Warning,
+            &windows,
+            "ico",
+            "no assets/img/icon.ico; the executable keeps the default icon".to_string(),
+        ),
+    }
+    checker.file(
+        &windows,
+        "windows.cert_path",
+        settings.windows.cert_path.as_deref(),
+    );
+    checker.file(
+        &windows,
+        "windows.key_path",
+        settings.windows.key_path.as_deref(),
+    );
+    let nsis = &settings.windows.nsis;
+    checker.file(&[Exe], "windows.nsis.template", nsis.template.as_deref());
+    checker.file(
+        &[Exe],
+        "windows.nsis.header_image",
+        nsis.header_image.as_deref(),
+    );
+    checker.file(
+        &[Exe],
+        "windows.nsis.sidebar_image",
+        nsis.sidebar_image.as_deref(),
+    );
+    checker.file(
+        &[Exe],
+        "windows.nsis.installer_icon",
+        nsis.installer_icon.as_deref(),
+    );
+    checker.file(&[Exe], "windows.nsis.license", nsis.license.as_deref());
+    checker.file(
+        &[Exe],
+        "windows.nsis.preinstall_section",
+        nsis.preinstall_section.as_deref(),
+    );
+    for include in &nsis.custom_includes {
+        checker.file(&[Exe], "windows.nsis.custom_includes", Some(include));
+    }
+    for plugin in &nsis.plugins {
+        checker.file(&[Exe], "windows.nsis.plugins", Some(plugin));
+    }
+
+    checker.problems
+}
+
+/// Whether `identifier` is a valid CFBundleIdentifier
+fn is_valid_identifier(identifier: &str) -> bool {
+    identifier.contains('.')
+        && identifier.split('.').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let root = tempfile::tempdir().unwrap();
+        image::RgbaImage::new(32, 32)
+            .save(root.path().join("icon_16x16.png"))
+            .unwrap();
+        let mut settings = BundleSettings {
+            identifier: Some("my app".into()),
+            icon: Some(vec![PathBuf::from("icon_16x16.png")]),
+            ..Default::default()
+        };
+        settings.macos.entitlements = Some(PathBuf::from("missing.plist"));
+
+        let problems = check(&settings, root.path(), &PackageType::ALL);
+        let find = |field: &str| problems.iter().find(|problem| problem.field == field);
+        assert_eq!(
+            find("icon[icon_16x16.png]").unwrap().message,
+            "is 32x32, expected 16x16"
+        );
+        assert_eq!(find("identifier").unwrap().platforms, "app, dmg");
+        assert!(
+            find("macos.entitlements")
+                .unwrap()
+                .message
+                .contains("missing.plist")
+        );
+        assert_eq!(find("icon").unwrap().severity, Severity::Warning);
+
+        // Only problems of the selected package types are reported
+        let problems = check(&settings, root.path(), &[PackageType::Deb]);
+        assert!(problems.iter().all(|problem| problem.platforms == "deb"));
+        assert!(!problems.iter().any(|problem| problem.field == "identifier"));
+    }
+}
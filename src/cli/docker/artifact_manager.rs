@@ -38,7 +38,7 @@ impl ArtifactManager {
         platform: PackageType,
         runtime_config: &crate::cli::RuntimeConfig,
     ) -> Result<Vec<PathBuf>, BundlerError> {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
         let bundle_dir = temp_target_dir
             .join("release")
             .join("bundle")
@@ -105,21 +105,8 @@ impl ArtifactManager {
                     }
 
                     // Validate file extension matches platform
-                    let extension = path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .map(|e| e.to_lowercase());
-
-                    let is_valid = match platform {
-                        PackageType::Deb => extension.as_deref() == Some("deb"),
-                        PackageType::Rpm => extension.as_deref() == Some("rpm"),
-                        PackageType::AppImage => {
-                            extension.is_none() || extension.as_deref() == Some("appimage")
-                        }
-                        PackageType::Exe => extension.as_deref() == Some("exe"),
-                        PackageType::Dmg => extension.as_deref() == Some("dmg"),
-                        PackageType::MacOsBundle => extension.as_deref() == Some("app"),
-                    };
+                    let extension = path.extension().and_then(|e| e.to_str());
+                    let is_valid = platform.matches_extension(extension);
 
                     if is_valid {
                         runtime_config.verbose_println(&format!("  ✓ Artifact: {}", path.display())).expect("Failed to write to stdout");
@@ -158,7 +145,7 @@ impl ArtifactManager {
         bundle_dir: &Path,
         platform: PackageType,
     ) -> Result<BundlerError, BundlerError> {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
 
         let dir_contents = {
             let bundle_dir = bundle_dir.to_path_buf();
@@ -253,7 +240,7 @@ impl ArtifactManager {
         platform: PackageType,
         runtime_config: &crate::cli::RuntimeConfig,
     ) -> Result<Vec<PathBuf>, BundlerError> {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
 
         // Verify artifacts before moving
         verify_artifacts(&artifacts, runtime_config)?;
@@ -5,16 +5,22 @@
 //! Manages Docker container lifecycle for building packages on platforms
 //! other than the host OS.
 
-use super::container_runner::ContainerRunner;
+use super::container_runner::{CONTAINER_BUILD_DIR, ContainerRunner, RunTimeouts};
 use super::guard::ContainerGuard;
+use super::isolation::VendoredSource;
 use super::limits::ContainerLimits;
 use super::oom_detector::OomDetector;
 use super::platform::platform_emoji;
+use super::runtime::runtime;
 use crate::bundler::PackageType;
-use crate::error::BundlerError;
-use std::path::PathBuf;
+use crate::cli::ArtifactOutput;
+use crate::error::{BundlerError, CliError};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+/// Directory, relative to the working directory, receiving container build logs
+pub const LOG_DIR: &str = "tmp";
+
 /// Docker container bundler for cross-platform builds.
 ///
 /// Manages Docker container lifecycle for building packages on platforms
@@ -23,30 +29,97 @@ use uuid::Uuid;
 pub struct ContainerBundler {
     image_name: String,
     source: String,
-    output_path: PathBuf,
+    output: ArtifactOutput,
     pub limits: ContainerLimits,
+    passthrough_args: Vec<String>,
+    target_cache: bool,
+    sccache: bool,
+    timeouts: RunTimeouts,
+    keep_container: bool,
+    export_build: bool,
+    isolated_source: Option<VendoredSource>,
 }
 
 impl ContainerBundler {
     /// Creates a container bundler for end-to-end bundling.
     ///
     /// The container will clone, build, and bundle internally, writing the
-    /// artifact to the specified output path.
+    /// artifacts to the specified output file or directory.
     ///
     /// # Arguments
     ///
     /// * `source` - Source specification (local path, GitHub org/repo, or GitHub URL)
-    /// * `output_path` - Path where the bundled artifact should be written
+    /// * `output` - Where the bundled artifacts should be written
     /// * `limits` - Resource limits for the container
-    pub fn new(source: String, output_path: PathBuf, limits: ContainerLimits) -> Self {
+    pub fn new(source: String, output: ArtifactOutput, limits: ContainerLimits) -> Self {
         Self {
             image_name: super::image::BUILDER_IMAGE_NAME.to_string(),
             source,
-            output_path,
+            output,
             limits,
+            passthrough_args: Vec::new(),
+            target_cache: true,
+            sccache: false,
+            timeouts: RunTimeouts::default(),
+            keep_container: false,
+            export_build: false,
+            isolated_source: None,
         }
     }
 
+    /// Builds `source` without network access instead of cloning in the container.
+    pub fn with_isolated_source(mut self, source: VendoredSource) -> Self {
+        self.isolated_source = Some(source);
+        self
+    }
+
+    /// Sets what is preserved when the container build fails.
+    ///
+    /// * `keep_container` - Leave the stopped container for inspection
+    /// * `export_build` - Save the container's build directory as a tarball
+    ///   in the output directory
+    pub fn with_debugging(mut self, keep_container: bool, export_build: bool) -> Self {
+        self.keep_container = keep_container;
+        self.export_build = export_build;
+        self
+    }
+
+    /// Sets when a hanging or overlong container build is killed.
+    pub fn with_timeouts(mut self, timeouts: RunTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Sets extra CLI flags forwarded to the bundler inside the container.
+    ///
+    /// Used for flags such as `--skip-sign` that must reach the in-container
+    /// invocation to take effect.
+    pub fn with_passthrough_args(mut self, args: Vec<String>) -> Self {
+        self.passthrough_args = args;
+        self
+    }
+
+    /// Runs the container from `image` instead of the locally built builder image.
+    pub fn with_image(mut self, image: String) -> Self {
+        self.image_name = image;
+        self
+    }
+
+    /// Sets whether the container reuses the target directory cache volume.
+    pub fn with_target_cache(mut self, target_cache: bool) -> Self {
+        self.target_cache = target_cache;
+        self
+    }
+
+    /// Sets whether the container build caches compiler output with sccache.
+    ///
+    /// The in-container bundler must also receive `--sccache` through the
+    /// passthrough args to use it.
+    pub fn with_sccache(mut self, sccache: bool) -> Self {
+        self.sccache = sccache;
+        self
+    }
+
     /// Bundles a package in a Docker container (end-to-end).
     ///
     /// The container receives the source and output path, then:
@@ -62,14 +135,15 @@ impl ContainerBundler {
     ///
     /// # Returns
     ///
-    /// * `Ok(PathBuf)` - Path to created artifact (same as self.output_path)
+    /// * `Ok(Vec<PathBuf>)` - Paths of the created artifacts on the host
     /// * `Err` - Container execution failed
+    #[tracing::instrument(name = "bundle", skip_all, fields(package_type = %platform, container = true))]
     pub async fn bundle(
         &self,
         platform: PackageType,
         runtime_config: &crate::cli::RuntimeConfig,
-    ) -> Result<PathBuf, BundlerError> {
-        let platform_str = super::platform::platform_type_to_string(platform);
+    ) -> Result<Vec<PathBuf>, BundlerError> {
+        let platform_str = platform.short_name();
 
         runtime_config.indent(&format!(
             "{} Building {} package in container...",
@@ -82,50 +156,93 @@ impl ContainerBundler {
         let container_name = format!("kodegen-bundle-{}", build_uuid);
 
         // Create RAII guard to ensure cleanup on failure
-        let _guard = ContainerGuard {
-            name: container_name.clone(),
-            output: runtime_config.output().clone(),
-        };
+        let mut guard = ContainerGuard::new(container_name.clone(), runtime_config.output().clone());
 
         // Create temp output directory on host
-        let output_parent = self.output_path.parent().ok_or_else(|| {
-            use crate::error::CliError;
-            BundlerError::Cli(CliError::ExecutionFailed {
-                command: "determine output directory".to_string(),
-                reason: format!("Output path has no parent directory: {}", self.output_path.display()),
-            })
-        })?;
+        let output_parent = match &self.output {
+            ArtifactOutput::File(path) => path.parent().ok_or_else(|| {
+                BundlerError::Cli(CliError::ExecutionFailed {
+                    command: "determine output directory".to_string(),
+                    reason: format!("Output path has no parent directory: {}", path.display()),
+                })
+            })?,
+            ArtifactOutput::Directory(dir) => dir.as_path(),
+        };
 
         std::fs::create_dir_all(output_parent).map_err(|e| {
-            use crate::error::CliError;
             BundlerError::Cli(CliError::ExecutionFailed {
                 command: "create output directory".to_string(),
                 reason: format!("Failed to create {}: {}", output_parent.display(), e),
             })
         })?;
 
+        // Remote engines can't mount host directories; artifacts are copied
+        // out instead. Docker Desktop silently mounts unshared directories as empty.
+        let remote = runtime().is_remote();
+        if remote && self.isolated_source.is_some() {
+            return Err(BundlerError::Cli(CliError::InvalidArguments {
+                reason: format!(
+                    "--isolated mounts the source into the container, which a remote {} engine cannot do",
+                    runtime().name()
+                ),
+            }));
+        }
+        let output_parent = if remote {
+            runtime_config.verbose_println(&format!(
+                "   Remote {} engine: copying artifacts out of the container",
+                runtime().name()
+            )).expect("Failed to write to stdout");
+            output_parent.to_path_buf()
+        } else {
+            super::mount_check::validate_output_mount(output_parent, &self.image_name).await?
+        };
+
+        // Keep the full output for post-mortems; scrollback rarely holds it
+        let log_file = Path::new(LOG_DIR).join(format!("bundle-{}-{}.log", platform_str, build_uuid));
+        runtime_config.verbose_println(&format!("   Logging container output to {}", log_file.display())).expect("Failed to write to stdout");
+
         // Create container runner
         let runner = ContainerRunner::new(
             self.image_name.clone(),
-            output_parent.to_path_buf(),
+            output_parent,
             self.limits.memory.clone(),
             self.limits.memory_swap.clone(),
             self.limits.cpus.clone(),
             self.limits.pids_limit,
-        );
+        )
+        .with_target_cache(self.target_cache)
+        .with_sccache(self.sccache)
+        .with_copy_output(remote)
+        .with_timeouts(self.timeouts)
+        .with_log_file(log_file.clone());
+        let runner = match &self.isolated_source {
+            Some(source) => runner.with_isolated_source(
+                source.vendor_dir().to_path_buf(),
+                source.source_dir().to_path_buf(),
+            ),
+            None => runner,
+        };
 
-        let docker_args = runner.build_docker_args_for_full_bundle(
+        let mut docker_args = runner.build_docker_args_for_full_bundle(
             &container_name,
             &self.source,
-            &self.output_path,
+            &self.output,
             platform,
         );
+        docker_args.extend(self.passthrough_args.iter().cloned());
 
         // Run container and capture output
-        let result = runner.run_container(docker_args, runtime_config).await?;
+        let result = match runner.run_container(docker_args, runtime_config).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.preserve_failed(&mut guard, &container_name, runner.output_dir(), runtime_config).await;
+                return Err(with_log_hint(e, &log_file));
+            }
+        };
 
         // Check for OOM or other failures
         if !result.status.success() {
+            self.preserve_failed(&mut guard, &container_name, runner.output_dir(), runtime_config).await;
             return self
                 .handle_container_failure(
                     platform,
@@ -134,12 +251,60 @@ impl ContainerBundler {
                     &container_name,
                 )
                 .await
-                .map(|_| unreachable!());
+                .map(|_| unreachable!())
+                .map_err(|e| with_log_hint(e, &log_file));
+        }
+
+        if remote {
+            runner.copy_output(&container_name).await?;
         }
 
         runtime_config.indent(&format!("✓ Created {} package", platform_str)).expect("Failed to write to stdout");
 
-        Ok(self.output_path.clone())
+        match &self.output {
+            ArtifactOutput::File(path) => Ok(vec![path.clone()]),
+            ArtifactOutput::Directory(dir) => Ok(container_artifacts(
+                &result.stdout_lines,
+                runner.container_output_dir(),
+                dir,
+            )),
+        }
+    }
+
+    /// Exports the build directory and keeps the container of a failed build,
+    /// as requested with [`Self::with_debugging`]
+    async fn preserve_failed(
+        &self,
+        guard: &mut ContainerGuard,
+        container_name: &str,
+        output_dir: &Path,
+        runtime_config: &crate::cli::RuntimeConfig,
+    ) {
+        let docker = runtime().name();
+
+        if self.export_build {
+            let tarball = output_dir.join(format!("{}-build.tar", container_name));
+            match export_dir(container_name, CONTAINER_BUILD_DIR, &tarball).await {
+                Ok(()) => runtime_config.indent(&format!("Build directory saved to {}", tarball.display())),
+                Err(reason) => runtime_config.warn(&format!("Failed to export build directory: {}", reason)),
+            }
+            .expect("Failed to write to stdout");
+        }
+
+        if self.keep_container {
+            guard.keep();
+            let name = container_name;
+            let hints = [
+                format!("Kept container {} for debugging:", name),
+                format!("  {} logs {}              # full output", docker, name),
+                format!("  {} start -ai {}         # rerun the build", docker, name),
+                format!("  {} commit {} {}-debug && {} run -it --rm --entrypoint bash {}-debug", docker, name, name, docker, name),
+                format!("  {} rm -f {}             # remove when done", docker, name),
+            ];
+            for hint in hints {
+                runtime_config.indent(&hint).expect("Failed to write to stdout");
+            }
+        }
     }
 
     /// Handles container execution failures with OOM detection.
@@ -157,6 +322,9 @@ impl ContainerBundler {
             .is_oom_failure(exit_code, stderr_lines, container_name)
             .await
         {
+            #[cfg(feature = "otlp")]
+            crate::cli::telemetry::record_container_oom();
+
             Err(detector
                 .format_oom_error(platform, stderr_lines, exit_code, container_name)
                 .await)
@@ -168,3 +336,66 @@ impl ContainerBundler {
         }
     }
 }
+
+/// Points the reason of a container failure at the full build log
+fn with_log_hint(error: BundlerError, log_file: &Path) -> BundlerError {
+    match error {
+        BundlerError::Cli(CliError::ExecutionFailed { command, reason }) => {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command,
+                reason: format!("{}\n\nFull build log: {}", reason, log_file.display()),
+            })
+        }
+        other => other,
+    }
+}
+
+/// Writes directory `dir` of container `container_name` to the tar archive `tarball`
+async fn export_dir(container_name: &str, dir: &str, tarball: &Path) -> Result<(), String> {
+    let file = std::fs::File::create(tarball).map_err(|e| e.to_string())?;
+    let output = runtime()
+        .command()
+        .args(["cp", &format!("{}:{}", container_name, dir), "-"])
+        .stdout(file)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(tarball);
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// Maps the artifact paths printed by the in-container bundler (under
+/// `container_dir`) to paths in the host output directory
+fn container_artifacts(stdout_lines: &[String], container_dir: &str, output_dir: &Path) -> Vec<PathBuf> {
+    stdout_lines
+        .iter()
+        .filter_map(|line| line.trim().strip_prefix(container_dir)?.strip_prefix('/'))
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+        .map(|name| output_dir.join(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_artifacts() {
+        let lines = [
+            "✓ Artifact at: /output/kodegen.app".to_string(),
+            "/output/kodegen.app".to_string(),
+            "/output/kodegen_1.0.0_x64.dmg".to_string(),
+            "/output/nested/file".to_string(),
+        ];
+        assert_eq!(
+            container_artifacts(&lines, "/output", Path::new("/dist")),
+            [
+                PathBuf::from("/dist/kodegen.app"),
+                PathBuf::from("/dist/kodegen_1.0.0_x64.dmg"),
+            ]
+        );
+    }
+}
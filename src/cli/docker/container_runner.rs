@@ -1,16 +1,110 @@
 //! Docker container execution and process management.
 
+use super::isolation::{CONTAINER_ISOLATED_DIR, CONTAINER_SOURCE_DIR};
+use super::runtime::runtime;
 use crate::bundler::PackageType;
+use crate::bundler::utils::interrupt;
+use crate::cli::ArtifactOutput;
 use crate::error::{BundlerError, CliError};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::process::Stdio;
-use std::time::Duration;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
 
-/// Timeout for Docker container run operations (20 minutes)
-/// Container bundling involves full cargo builds which can be slow
-pub const DOCKER_RUN_TIMEOUT: Duration = Duration::from_secs(1200);
+/// Default time a container build may go without printing anything (20 minutes)
+///
+/// A build that is still compiling keeps printing, so silence this long
+/// means it hangs, however long the whole build takes.
+pub const DOCKER_IDLE_TIMEOUT: Duration = Duration::from_secs(1200);
+
+/// How long a container build may run before it is killed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunTimeouts {
+    /// Wall clock limit for the whole run, none by default
+    pub total: Option<Duration>,
+    /// Limit on the time without any stdout or stderr output
+    pub idle: Option<Duration>,
+}
+
+impl Default for RunTimeouts {
+    fn default() -> Self {
+        Self {
+            total: None,
+            idle: Some(DOCKER_IDLE_TIMEOUT),
+        }
+    }
+}
+
+impl RunTimeouts {
+    /// Waits until a timeout expires, returning a description of which one.
+    ///
+    /// `last_output` is updated by the output readers; never returns when
+    /// both timeouts are disabled.
+    async fn expired(&self, started: Instant, last_output: &Mutex<Instant>) -> String {
+        loop {
+            let idle_deadline = self
+                .idle
+                .map(|idle| *last_output.lock().unwrap_or_else(|e| e.into_inner()) + idle);
+            let total_deadline = self.total.map(|total| started + total);
+            let Some(deadline) = idle_deadline.into_iter().chain(total_deadline).min() else {
+                return std::future::pending().await;
+            };
+            tokio::time::sleep_until(deadline.into()).await;
+
+            let now = Instant::now();
+            if let Some(total) = self.total.filter(|total| now >= started + *total) {
+                return format!("timed out after {} minutes", total.as_secs() / 60);
+            }
+            let silent = now.duration_since(*last_output.lock().unwrap_or_else(|e| e.into_inner()));
+            if let Some(idle) = self.idle.filter(|idle| silent >= *idle) {
+                return format!("printed no output for {} minutes", idle.as_secs() / 60);
+            }
+        }
+    }
+}
+
+/// Docker volume caching the cargo registry and git checkouts across runs
+pub const CARGO_CACHE_VOLUME: &str = "kodegen-bundler-cargo-cache";
+
+/// Docker volume caching the container's target directory across runs
+pub const TARGET_CACHE_VOLUME: &str = "kodegen-bundler-target-cache";
+
+/// Docker volume holding the local sccache cache across runs
+pub const SCCACHE_VOLUME: &str = "kodegen-bundler-sccache";
+
+/// sccache configuration forwarded by name, selecting e.g. the S3 backend
+const SCCACHE_ENV: [&str; 9] = [
+    "SCCACHE_BUCKET",
+    "SCCACHE_REGION",
+    "SCCACHE_ENDPOINT",
+    "SCCACHE_S3_KEY_PREFIX",
+    "SCCACHE_S3_USE_SSL",
+    "SCCACHE_CACHE_SIZE",
+    "AWS_ACCESS_KEY_ID",
+    "AWS_SECRET_ACCESS_KEY",
+    "AWS_SESSION_TOKEN",
+];
+
+/// `SCCACHE_DIR` inside the container, backed by [`SCCACHE_VOLUME`]
+const CONTAINER_SCCACHE_DIR: &str = "/tmp/sccache";
+
+/// Working directory of the in-container bundler
+pub const CONTAINER_BUILD_DIR: &str = "/tmp/kodegen-build";
+
+/// Output directory inside the container, bind mounted from the host
+const CONTAINER_OUTPUT_DIR: &str = "/output";
+
+/// Output directory inside the container when it is copied out instead
+///
+/// Unlike `/`, `/tmp` is writable by the image's builder user.
+const CONTAINER_COPY_OUTPUT_DIR: &str = "/tmp/kodegen-output";
+
+/// `CARGO_HOME` inside the container, backed by [`CARGO_CACHE_VOLUME`]
+const CONTAINER_CARGO_HOME: &str = "/tmp/cargo";
+
+/// `CARGO_TARGET_DIR` inside the container, backed by [`TARGET_CACHE_VOLUME`]
+const CONTAINER_TARGET_DIR: &str = "/tmp/kodegen-target";
 
 /// Result of container execution
 pub struct ContainerRunResult {
@@ -18,6 +112,8 @@ pub struct ContainerRunResult {
     pub status: std::process::ExitStatus,
     /// Captured stderr lines
     pub stderr_lines: Vec<String>,
+    /// Captured stdout lines (also streamed to the terminal)
+    pub stdout_lines: Vec<String>,
 }
 
 /// Docker container runner for executing bundling operations.
@@ -28,6 +124,12 @@ pub struct ContainerRunner {
     memory_swap: String,
     cpus_limit: String,
     pids_limit: u32,
+    target_cache: bool,
+    sccache: bool,
+    copy_output: bool,
+    timeouts: RunTimeouts,
+    log_file: Option<PathBuf>,
+    isolated_source: Option<(PathBuf, PathBuf)>,
 }
 
 impl ContainerRunner {
@@ -56,9 +158,75 @@ impl ContainerRunner {
             memory_swap,
             cpus_limit,
             pids_limit,
+            target_cache: true,
+            sccache: false,
+            copy_output: false,
+            timeouts: RunTimeouts::default(),
+            log_file: None,
+            isolated_source: None,
         }
     }
 
+    /// Builds from a host checkout and its vendored dependencies without
+    /// network access (see [`super::isolation`]).
+    ///
+    /// * `vendor_dir` - Mounted read-only at [`CONTAINER_ISOLATED_DIR`]
+    /// * `source_dir` - Mounted read-only at [`CONTAINER_SOURCE_DIR`] and bundled in place
+    pub fn with_isolated_source(mut self, vendor_dir: PathBuf, source_dir: PathBuf) -> Self {
+        self.isolated_source = Some((vendor_dir, source_dir));
+        self
+    }
+
+    /// Sets a file receiving the container's stdout and stderr, each line
+    /// prefixed with a timestamp and the stream name.
+    pub fn with_log_file(mut self, path: PathBuf) -> Self {
+        self.log_file = Some(path);
+        self
+    }
+
+    /// Sets when a hanging or overlong container run is killed.
+    pub fn with_timeouts(mut self, timeouts: RunTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Sets whether artifacts are copied out of the container afterwards
+    /// (see [`Self::copy_output`]) instead of written to a bind mount.
+    ///
+    /// Required for remote engines, which can't mount host directories.
+    pub fn with_copy_output(mut self, copy_output: bool) -> Self {
+        self.copy_output = copy_output;
+        self
+    }
+
+    /// Host directory receiving the artifacts
+    pub fn output_dir(&self) -> &std::path::Path {
+        &self.workspace_path
+    }
+
+    /// Directory inside the container receiving the artifacts
+    pub fn container_output_dir(&self) -> &'static str {
+        if self.copy_output {
+            CONTAINER_COPY_OUTPUT_DIR
+        } else {
+            CONTAINER_OUTPUT_DIR
+        }
+    }
+
+    /// Sets whether builds reuse the target directory cache volume.
+    ///
+    /// The cargo registry cache is always mounted; it only holds downloads.
+    pub fn with_target_cache(mut self, target_cache: bool) -> Self {
+        self.target_cache = target_cache;
+        self
+    }
+
+    /// Sets whether the sccache cache volume and configuration are provided.
+    pub fn with_sccache(mut self, sccache: bool) -> Self {
+        self.sccache = sccache;
+        self
+    }
+
     /// Builds Docker command arguments for end-to-end bundling.
     ///
     /// Container receives source and output path, clones internally,
@@ -68,7 +236,7 @@ impl ContainerRunner {
     ///
     /// * `container_name` - Unique container name
     /// * `source` - Source specification (unchanged from user input)
-    /// * `output_path` - Final output path on host
+    /// * `output` - Final output file or directory on host
     /// * `platform` - Platform to bundle
     ///
     /// # Returns
@@ -78,19 +246,11 @@ impl ContainerRunner {
         &self,
         container_name: &str,
         source: &str,
-        output_path: &Path,
+        output: &ArtifactOutput,
         platform: PackageType,
     ) -> Vec<String> {
-        let platform_str = super::platform::platform_type_to_string(platform);
-
-        // Extract output filename
-        let output_filename = output_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("output.bin");
-
-        // Mount output directory (self.workspace_path is actually output_parent in new flow)
-        let output_mount = format!("{}:/output:rw", self.workspace_path.display());
+        let platform_str = platform.short_name();
+        let output_dir = self.container_output_dir();
 
         let mut docker_args = vec![
             "run".to_string(),
@@ -110,25 +270,89 @@ impl ContainerRunner {
             // CPU limits
             "--cpus".to_string(),
             self.cpus_limit.clone(),
-            // Process limits
-            "--pids-limit".to_string(),
-            self.pids_limit.to_string(),
-            // Mount cargo cache volume for persistent dependencies
+            // Mount cargo cache volume for the persistent registry index,
+            // downloaded crates and git dependencies
             "-v".to_string(),
-            "kodegen-bundler-cargo-cache:/tmp/cargo".to_string(),
-            // Mount target cache volume for compiled artifacts
-            "-v".to_string(),
-            "kodegen-bundler-target-cache:/tmp/kodegen-build/target".to_string(),
-            // Mount output directory
-            "-v".to_string(),
-            output_mount,
+            format!("{}:{}", CARGO_CACHE_VOLUME, CONTAINER_CARGO_HOME),
             // Working directory in /tmp (not /workspace)
             "-w".to_string(),
-            "/tmp/kodegen-build".to_string(),
+            CONTAINER_BUILD_DIR.to_string(),
             // Environment
             "-e".to_string(),
-            "CARGO_HOME=/tmp/cargo".to_string(),
+            format!("CARGO_HOME={}", CONTAINER_CARGO_HOME),
         ];
+        // Mount output directory (self.workspace_path is actually output_parent in new flow)
+        if !self.copy_output {
+            docker_args.push("-v".to_string());
+            docker_args.push(format!(
+                "{}:{}:{}",
+                self.workspace_path.display(),
+                CONTAINER_OUTPUT_DIR,
+                runtime().bind_mount_options()
+            ));
+        }
+        // Vendored build: no network, read-only source, writable target dir
+        if let Some((vendor_dir, source_dir)) = &self.isolated_source {
+            docker_args.extend([
+                "--network".to_string(),
+                "none".to_string(),
+                "-v".to_string(),
+                format!("{}:{}:ro", vendor_dir.display(), CONTAINER_ISOLATED_DIR),
+                "-v".to_string(),
+                format!("{}:{}:ro", source_dir.display(), CONTAINER_SOURCE_DIR),
+                "-e".to_string(),
+                "CARGO_NET_OFFLINE=true".to_string(),
+            ]);
+            if !self.target_cache {
+                docker_args.push("-e".to_string());
+                docker_args.push(format!("CARGO_TARGET_DIR={}", CONTAINER_TARGET_DIR));
+            }
+        }
+        // Process limits
+        docker_args.extend(runtime().pids_limit_args(self.pids_limit));
+        docker_args.extend(runtime().run_args());
+
+        // Mount target cache volume so dependencies compile once across runs
+        if self.target_cache {
+            docker_args.extend([
+                "-v".to_string(),
+                format!("{}:{}", TARGET_CACHE_VOLUME, CONTAINER_TARGET_DIR),
+                "-e".to_string(),
+                format!("CARGO_TARGET_DIR={}", CONTAINER_TARGET_DIR),
+            ]);
+        }
+
+        // Mount the sccache volume; credentials are forwarded by name so
+        // their values stay out of the process list
+        if self.sccache {
+            docker_args.extend([
+                "-v".to_string(),
+                format!("{}:{}", SCCACHE_VOLUME, CONTAINER_SCCACHE_DIR),
+                "-e".to_string(),
+                format!("SCCACHE_DIR={}", CONTAINER_SCCACHE_DIR),
+            ]);
+            for var in SCCACHE_ENV {
+                if std::env::var_os(var).is_some() {
+                    docker_args.push("-e".to_string());
+                    docker_args.push(var.to_string());
+                }
+            }
+        }
+
+        // Forward COPR credentials by name so their values stay out of the
+        // process list
+        for var in ["COPR_LOGIN", "COPR_USERNAME", "COPR_TOKEN"] {
+            if std::env::var_os(var).is_some() {
+                docker_args.push("-e".to_string());
+                docker_args.push(var.to_string());
+            }
+        }
+
+        // Apply the host's log filter inside the container
+        if std::env::var_os("RUST_LOG").is_some() {
+            docker_args.push("-e".to_string());
+            docker_args.push("RUST_LOG".to_string());
+        }
 
         // Image runs as builder user (UID 1000, GID 1000) by default
         // No --user flag needed
@@ -137,15 +361,58 @@ impl ContainerRunner {
         docker_args.push(self.image_name.clone());
         docker_args.push("kodegen_bundler_bundle".to_string());
         docker_args.push("--source".to_string());
-        docker_args.push(source.to_string());
+        if self.isolated_source.is_some() {
+            docker_args.push(CONTAINER_SOURCE_DIR.to_string());
+            docker_args.push("--no-clone".to_string());
+        } else {
+            docker_args.push(source.to_string());
+        }
         docker_args.push("--platform".to_string());
         docker_args.push(platform_str.to_string());
-        docker_args.push("--output-binary".to_string());
-        docker_args.push(format!("/output/{}", output_filename));
+        match output {
+            ArtifactOutput::File(path) => {
+                let output_filename = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("output.bin");
+                docker_args.push("--output-binary".to_string());
+                docker_args.push(format!("{}/{}", output_dir, output_filename));
+            }
+            ArtifactOutput::Directory(_) => {
+                docker_args.push("--output-dir".to_string());
+                docker_args.push(output_dir.to_string());
+            }
+        }
 
         docker_args
     }
 
+    /// Copies the artifacts of the exited container `container_name` to the host
+    /// output directory.
+    pub async fn copy_output(&self, container_name: &str) -> Result<(), BundlerError> {
+        let source = format!("{}:{}/.", container_name, CONTAINER_COPY_OUTPUT_DIR);
+        let output = runtime()
+            .command()
+            .arg("cp")
+            .arg(&source)
+            .arg(&self.workspace_path)
+            .output()
+            .await
+            .map_err(|e| {
+                BundlerError::Cli(CliError::ExecutionFailed {
+                    command: format!("{} cp", runtime().name()),
+                    reason: e.to_string(),
+                })
+            })?;
+        if !output.status.success() {
+            return Err(BundlerError::Cli(CliError::ExecutionFailed {
+                command: format!("{} cp {} {}", runtime().name(), source, self.workspace_path.display()),
+                reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }));
+        }
+        Ok(())
+    }
+
     /// Runs a Docker container and streams output.
     ///
     /// # Arguments
@@ -162,7 +429,8 @@ impl ContainerRunner {
         runtime_config: &crate::cli::RuntimeConfig,
     ) -> Result<ContainerRunResult, BundlerError> {
         // Spawn docker process with both stdout/stderr piped
-        let mut child = Command::new("docker")
+        let mut child = runtime()
+            .command()
             .args(&docker_args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -173,55 +441,74 @@ impl ContainerRunner {
                     reason: e.to_string(),
                 })
             })?;
+        let _interrupt = interrupt::kill_on_interrupt(&child);
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let started = Instant::now();
+        let last_output = Mutex::new(started);
+        let touch = || *last_output.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+        let log = match self.log_file.as_deref() {
+            Some(path) => match open_log(path).await {
+                Ok(file) => Some(tokio::sync::Mutex::new(file)),
+                Err(e) => {
+                    runtime_config.warn(&format!("Cannot write build log {}: {}", path.display(), e)).expect("Failed to write to stdout");
+                    None
+                }
+            },
+            None => None,
+        };
 
         // Process both stdout and stderr concurrently to avoid race conditions
         // Both streams must complete before we check exit status
-        let (_, stderr_result) = tokio::join!(
-            // Process stdout: stream in real-time
-            async {
-                if let Some(stdout) = child.stdout.take() {
-                    let reader = BufReader::new(stdout);
-                    let mut lines = reader.lines();
-
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        runtime_config.indent(&line).expect("Failed to write docker output");
+        let streams = async {
+            tokio::join!(
+                // Process stdout: stream in real-time
+                async {
+                    let mut captured_lines = Vec::new();
+                    if let Some(stdout) = stdout {
+                        let mut lines = BufReader::new(stdout).lines();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            touch();
+                            log_line(&log, "stdout", &line).await;
+                            runtime_config.indent(&line).expect("Failed to write docker output");
+                            captured_lines.push(line);
+                        }
                     }
-                }
-            },
-            // Process stderr: capture for OOM detection
-            async {
-                if let Some(stderr) = child.stderr.take() {
-                    let reader = BufReader::new(stderr);
-                    let mut lines = reader.lines();
+                    captured_lines
+                },
+                // Process stderr: capture for OOM detection
+                async {
                     let mut captured_lines = Vec::new();
-
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        captured_lines.push(line);
+                    if let Some(stderr) = stderr {
+                        let mut lines = BufReader::new(stderr).lines();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            touch();
+                            log_line(&log, "stderr", &line).await;
+                            captured_lines.push(line);
+                        }
                     }
-
-                    Some(captured_lines)
-                } else {
-                    None
+                    captured_lines
                 }
-            }
-        );
+            )
+        };
 
-        // Wait for child process completion with timeout
-        let status = tokio::time::timeout(DOCKER_RUN_TIMEOUT, child.wait()).await;
+        // The streams end when the container exits; a hanging build keeps
+        // them open, so the timeout watchdog runs alongside
+        let outcome = tokio::select! {
+            lines = streams => Ok(lines),
+            reason = self.timeouts.expired(started, &last_output) => Err(reason),
+        };
+        if let Some(log) = &log {
+            let _ = log.lock().await.flush().await;
+        }
 
-        let status = match status {
-            Ok(Ok(status)) => status,
-            Ok(Err(e)) => {
-                return Err(BundlerError::Cli(CliError::ExecutionFailed {
-                    command: format!("docker run {}", docker_args.join(" ")),
-                    reason: e.to_string(),
-                }));
-            }
-            Err(_elapsed) => {
+        let (stdout_lines, stderr_lines) = match outcome {
+            Ok(lines) => lines,
+            Err(reason) => {
                 // Timeout - kill the process
                 runtime_config.warn(&format!(
-                    "Docker bundling timed out after {} minutes, terminating...",
-                    DOCKER_RUN_TIMEOUT.as_secs() / 60
+                    "Docker bundling {}, terminating...",
+                    reason
                 )).expect("Failed to write to stdout");
 
                 if let Err(e) = child.kill().await {
@@ -233,29 +520,151 @@ impl ContainerRunner {
                 return Err(BundlerError::Cli(CliError::ExecutionFailed {
                     command: "docker run".to_string(),
                     reason: format!(
-                        "Docker bundling timed out after {} minutes.\n\
+                        "Docker bundling {}.\n\
                          \n\
                          This usually indicates:\n\
                          • Very slow build (large dependency downloads)\n\
+                         • A build step waiting for input or a lock\n\
                          • System resource constraints\n\
                          • Network issues\n\
                          \n\
                          Try:\n\
+                         • Raise or disable the limit (--docker-idle-timeout, --docker-timeout; 0 disables)\n\
                          • Increase container resource limits\n\
-                         • Check available system memory/CPU\n\
-                         • Use --build flag to reuse cached builds",
-                        DOCKER_RUN_TIMEOUT.as_secs() / 60
+                         • Check available system memory/CPU",
+                        reason
                     ),
                 }));
             }
         };
 
-        // Extract captured stderr lines (both streams already completed via tokio::join!)
-        let stderr_lines = stderr_result.unwrap_or_default();
+        let status = child.wait().await.map_err(|e| {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command: format!("docker run {}", docker_args.join(" ")),
+                reason: e.to_string(),
+            })
+        })?;
 
         Ok(ContainerRunResult {
             status,
             stderr_lines,
+            stdout_lines,
         })
     }
 }
+
+/// Creates the log file at `path` and its parent directories
+async fn open_log(path: &std::path::Path) -> std::io::Result<BufWriter<tokio::fs::File>> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    Ok(BufWriter::new(tokio::fs::File::create(path).await?))
+}
+
+/// Appends a timestamped line from `stream` to the build log, if any
+async fn log_line(log: &Option<tokio::sync::Mutex<BufWriter<tokio::fs::File>>>, stream: &str, line: &str) {
+    if let Some(log) = log {
+        let timestamp = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f");
+        let record = format!("{} [{}] {}\n", timestamp, stream, line);
+        // Best effort: a full disk must not fail the build
+        let _ = log.lock().await.write_all(record.as_bytes()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner() -> ContainerRunner {
+        ContainerRunner::new(
+            "kodegen-builder".to_string(),
+            PathBuf::from("/dist"),
+            "4g".to_string(),
+            "8g".to_string(),
+            "2.0".to_string(),
+            1000,
+        )
+    }
+
+    fn docker_args(runner: ContainerRunner) -> Vec<String> {
+        runner.build_docker_args_for_full_bundle(
+            "kodegen-bundle-test",
+            "cyrup-ai/kodegen",
+            &ArtifactOutput::Directory(PathBuf::from("/dist")),
+            PackageType::Deb,
+        )
+    }
+
+    #[test]
+    fn test_cache_volumes() {
+        let args = docker_args(runner());
+        assert!(args.contains(&"kodegen-bundler-cargo-cache:/tmp/cargo".to_string()));
+        assert!(args.contains(&"kodegen-bundler-target-cache:/tmp/kodegen-target".to_string()));
+        assert!(args.contains(&"CARGO_TARGET_DIR=/tmp/kodegen-target".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains(SCCACHE_VOLUME)));
+
+        // Downloads stay cached without the target cache
+        let args = docker_args(runner().with_target_cache(false));
+        assert!(args.contains(&"kodegen-bundler-cargo-cache:/tmp/cargo".to_string()));
+        assert!(!args.iter().any(|arg| arg.contains(TARGET_CACHE_VOLUME)));
+        assert!(!args.iter().any(|arg| arg.starts_with("CARGO_TARGET_DIR=")));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts() {
+        let started = Instant::now();
+        let last_output = Mutex::new(started);
+        let idle = RunTimeouts {
+            total: Some(Duration::from_secs(3600)),
+            idle: Some(Duration::from_millis(20)),
+        };
+        assert!(idle.expired(started, &last_output).await.contains("no output"));
+
+        let total = RunTimeouts {
+            total: Some(Duration::from_millis(20)),
+            idle: None,
+        };
+        assert!(total.expired(started, &last_output).await.contains("timed out"));
+
+        // Disabled timeouts never expire
+        let disabled = RunTimeouts {
+            total: None,
+            idle: None,
+        };
+        let expired = tokio::time::timeout(Duration::from_millis(50), disabled.expired(started, &last_output));
+        assert!(expired.await.is_err());
+    }
+
+    #[test]
+    fn test_copy_output() {
+        let args = docker_args(runner());
+        assert!(args.iter().any(|arg| arg.starts_with("/dist:/output:")));
+        assert_eq!(args.last().unwrap(), "/output");
+
+        // Remote engines can't mount the host directory
+        let args = docker_args(runner().with_copy_output(true));
+        assert!(!args.iter().any(|arg| arg.starts_with("/dist:")));
+        assert_eq!(args.last().unwrap(), "/tmp/kodegen-output");
+    }
+
+    #[test]
+    fn test_isolated_source() {
+        let runner = runner()
+            .with_target_cache(false)
+            .with_isolated_source(PathBuf::from("/tmp/vendor"), PathBuf::from("/src/app"));
+        let args = docker_args(runner);
+        assert!(args.windows(2).any(|pair| pair == ["--network", "none"]));
+        assert!(args.contains(&"/tmp/vendor:/tmp/kodegen-isolated:ro".to_string()));
+        assert!(args.contains(&"/src/app:/tmp/kodegen-isolated/src:ro".to_string()));
+        assert!(args.contains(&"CARGO_TARGET_DIR=/tmp/kodegen-target".to_string()));
+        assert!(args.windows(3).any(|args| args == ["--source", "/tmp/kodegen-isolated/src", "--no-clone"]));
+        assert!(!args.contains(&"cyrup-ai/kodegen".to_string()));
+    }
+
+    #[test]
+    fn test_sccache_volume() {
+        let args = docker_args(runner().with_sccache(true));
+        assert!(args.contains(&"kodegen-bundler-sccache:/tmp/sccache".to_string()));
+        assert!(args.contains(&"SCCACHE_DIR=/tmp/sccache".to_string()));
+    }
+}
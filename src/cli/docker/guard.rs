@@ -1,7 +1,10 @@
 //! RAII guard for Docker container cleanup.
 //!
-//! Ensures containers are properly cleaned up even on panic or error.
+//! Ensures containers are properly cleaned up even on panic, error or
+//! interruption (SIGINT/SIGTERM).
 
+use super::runtime::runtime;
+use crate::bundler::utils::interrupt::{self, CleanupGuard};
 use std::time::Duration;
 use wait_timeout::ChildExt;
 
@@ -10,83 +13,116 @@ use wait_timeout::ChildExt;
 /// Automatically removes containers when dropped, ensuring cleanup even on panic or error.
 /// Uses bounded timeout to prevent infinite hangs if Docker daemon becomes unresponsive.
 pub(super) struct ContainerGuard {
-    pub(super) name: String,
-    pub(super) output: crate::cli::OutputManager,
+    name: String,
+    output: crate::cli::OutputManager,
+    keep: bool,
+    _interrupt: CleanupGuard,
+}
+
+impl ContainerGuard {
+    /// Guards the container `name`, also removing it if the bundler is interrupted
+    pub(super) fn new(name: String, output: crate::cli::OutputManager) -> Self {
+        let interrupt = {
+            let (name, output) = (name.clone(), output.clone());
+            interrupt::on_interrupt(move || remove_container(&name, &output))
+        };
+        Self {
+            name,
+            output,
+            keep: false,
+            _interrupt: interrupt,
+        }
+    }
+
+    /// Leaves the container in place when the guard is dropped, for debugging
+    ///
+    /// It is still removed if the bundler is interrupted before that.
+    pub(super) fn keep(&mut self) {
+        self.keep = true;
+    }
 }
 
 impl Drop for ContainerGuard {
     fn drop(&mut self) {
-        // Best-effort cleanup with timeout protection
-        // We use spawn() + wait_timeout() instead of output() to avoid infinite hangs
+        if self.keep {
+            return;
+        }
+        remove_container(&self.name, &self.output);
+    }
+}
+
+/// Force-removes container `name`, warning on failure
+fn remove_container(name: &str, output: &crate::cli::OutputManager) {
+    // Best-effort cleanup with timeout protection
+    // We use spawn() + wait_timeout() instead of output() to avoid infinite hangs
 
-        // Attempt to spawn docker command
-        let mut child = match std::process::Command::new("docker")
-            .args(["rm", "-f", &self.name])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .spawn()
-        {
-            Ok(child) => child,
-            Err(_) => {
-                // Can't even spawn docker command (e.g., binary not found)
-                // Nothing we can do, give up gracefully
-                return;
+    // Attempt to spawn docker command
+    let mut child = match runtime().std_command()
+        .args(["rm", "-f", name])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => {
+            // Can't even spawn docker command (e.g., binary not found)
+            // Nothing we can do, give up gracefully
+            return;
+        }
+    };
+
+    // Wait up to 5 seconds for cleanup to complete
+    // Docker daemon should respond instantly if alive (just removing a container entry)
+    let timeout = Duration::from_secs(5);
+    match child.wait_timeout(timeout) {
+        Ok(Some(status)) => {
+            // Command completed (successfully or with error)
+            // Either way, we're done - this is best-effort cleanup
+            if !status.success() {
+                // Optional: Log cleanup failure for debugging
+                // We don't panic or propagate error since we're already in cleanup path
+                let _ = output.warn(&format!(
+                    "Failed to cleanup container '{}' (exit code: {})",
+                    name,
+                    status.code().unwrap_or(-1)
+                ));
             }
-        };
+        }
+        Ok(None) => {
+            // Timeout reached
+            // First, check if process exited naturally during timeout
+            match child.try_wait() {
+                Ok(Some(_status)) => {
+                    // Process already exited - no need to kill
+                    // This is not an error, just slow completion
+                }
+                Ok(None) => {
+                    // Process still running - kill it
+                    let _ = child.kill();
+                    let _ = child.wait();
 
-        // Wait up to 5 seconds for cleanup to complete
-        // Docker daemon should respond instantly if alive (just removing a container entry)
-        let timeout = Duration::from_secs(5);
-        match child.wait_timeout(timeout) {
-            Ok(Some(status)) => {
-                // Command completed (successfully or with error)
-                // Either way, we're done - this is best-effort cleanup
-                if !status.success() {
-                    // Optional: Log cleanup failure for debugging
-                    // We don't panic or propagate error since we're already in cleanup path
-                    let _ = self.output.warn(&format!(
-                        "Failed to cleanup container '{}' (exit code: {})",
-                        self.name,
-                        status.code().unwrap_or(-1)
+                    let _ = output.warn(&format!(
+                        "Timed out cleaning up container '{}' after {} seconds. \
+                         Docker daemon may be unresponsive.",
+                        name,
+                        timeout.as_secs()
                     ));
                 }
-            }
-            Ok(None) => {
-                // Timeout reached
-                // First, check if process exited naturally during timeout
-                match child.try_wait() {
-                    Ok(Some(_status)) => {
-                        // Process already exited - no need to kill
-                        // This is not an error, just slow completion
-                    }
-                    Ok(None) => {
-                        // Process still running - kill it
-                        let _ = child.kill();
-                        let _ = child.wait();
-
-                        let _ = self.output.warn(&format!(
-                            "Timed out cleaning up container '{}' after {} seconds. \
-                             Docker daemon may be unresponsive.",
-                            self.name,
-                            timeout.as_secs()
-                        ));
-                    }
-                    Err(_) => {
-                        // Can't determine state - try killing anyway
-                        let _ = child.kill();
-                        let _ = child.wait();
-                    }
+                Err(_) => {
+                    // Can't determine state - try killing anyway
+                    let _ = child.kill();
+                    let _ = child.wait();
                 }
             }
-            Err(_) => {
-                // Error while waiting (rare)
-                // Try to kill the process to prevent zombie
-                let _ = child.kill();
-                let _ = child.wait();
-            }
         }
-
-        // Note: We deliberately ignore all errors and don't panic
-        // Drop must never panic, and we're already in an error/cleanup path
+        Err(_) => {
+            // Error while waiting (rare)
+            // Try to kill the process to prevent zombie
+            let _ = child.kill();
+            let _ = child.wait();
+        }
     }
+
+    // Note: We deliberately ignore all errors and don't panic
+    // Drop must never panic, and we're already in an error/cleanup path
 }
@@ -1,11 +1,13 @@
 //! Docker daemon availability checking.
 
+use crate::bundler::utils::progress_bar;
 use crate::error::{BundlerError, CliError};
 use std::process::Stdio;
-use tokio::process::Command;
+use std::time::{Duration, Instant};
+use crate::cli::docker::runtime::runtime;
 use tokio::time::timeout;
 
-use super::config::{DOCKER_INFO_TIMEOUT, DOCKER_START_HELP};
+use super::config::{DOCKER_INFO_TIMEOUT, DOCKER_START_HELP, DOCKER_START_TIMEOUT};
 
 /// Checks if Docker is installed and the daemon is running.
 ///
@@ -16,7 +18,7 @@ use super::config::{DOCKER_INFO_TIMEOUT, DOCKER_START_HELP};
 pub async fn check_docker_available() -> Result<(), BundlerError> {
     let status_result = timeout(
         DOCKER_INFO_TIMEOUT,
-        Command::new("docker")
+        runtime().command()
             .arg("info")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -78,3 +80,100 @@ pub async fn check_docker_available() -> Result<(), BundlerError> {
         })),
     }
 }
+
+/// Checks that the Docker daemon is running, starting it first when
+/// `auto_start` is set (`--auto-start-docker`).
+///
+/// Starts the engine the way this host provides (Docker Desktop, colima,
+/// `podman machine` or the systemd service), then polls with backoff for
+/// up to [`DOCKER_START_TIMEOUT`].
+pub async fn ensure_docker_running(
+    auto_start: bool,
+    runtime_config: &crate::cli::RuntimeConfig,
+) -> Result<(), BundlerError> {
+    let error = match check_docker_available().await {
+        Ok(()) => return Ok(()),
+        Err(e) if !auto_start => return Err(e),
+        Err(e) => e,
+    };
+
+    let started: Vec<String> = start_commands()
+        .into_iter()
+        .filter(|(program, _)| which::which(program).is_ok())
+        .filter_map(|(program, args)| {
+            let command = format!("{} {}", program, args.join(" "));
+            runtime_config.progress(&format!("Starting container engine: {}", command)).expect("Failed to write to stdout");
+            // Desktop apps and `colima start` keep running or return once
+            // launched; readiness is polled below either way
+            let spawned = std::process::Command::new(program)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match spawned {
+                Ok(_) => Some(command),
+                Err(e) => {
+                    tracing::debug!("Failed to run {}: {}", command, e);
+                    None
+                }
+            }
+        })
+        .collect();
+    if started.is_empty() {
+        runtime_config.warn("--auto-start-docker: no Docker Desktop, colima, podman machine or systemd service found").expect("Failed to write to stdout");
+        return Err(error);
+    }
+
+    let wait = async {
+        let deadline = Instant::now() + DOCKER_START_TIMEOUT;
+        let mut delay = Duration::from_secs(1);
+        while Instant::now() < deadline {
+            tokio::time::sleep(delay).await;
+            if check_docker_available().await.is_ok() {
+                return true;
+            }
+            delay = (delay * 2).min(Duration::from_secs(10));
+        }
+        false
+    };
+    if progress_bar::spin(format!("Waiting for {} to start", runtime().name()), wait).await {
+        runtime_config.success(&format!("{} is running", runtime().name())).expect("Failed to write to stdout");
+        return Ok(());
+    }
+
+    Err(BundlerError::Cli(CliError::ExecutionFailed {
+        command: started.join(", "),
+        reason: format!(
+            "The container engine did not respond within {} seconds of starting it.\n\
+             \n\
+             {}",
+            DOCKER_START_TIMEOUT.as_secs(),
+            DOCKER_START_HELP
+        ),
+    }))
+}
+
+/// Commands that start a container engine on this host, in order of preference
+fn start_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if runtime().name() == "podman" {
+        // Podman on Linux is daemonless; elsewhere it runs in a VM
+        return if cfg!(target_os = "linux") {
+            Vec::new()
+        } else {
+            vec![("podman", &["machine", "start"])]
+        };
+    }
+    if cfg!(target_os = "macos") {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::path::Path::new("/Applications/Docker.app").exists() {
+            commands.push(("open", &["-a", "Docker"]));
+        } else {
+            commands.push(("colima", &["start"]));
+        }
+        commands
+    } else {
+        // Never prompt for a password; fails without the needed privileges
+        vec![("systemctl", &["start", "--no-ask-password", "docker"])]
+    }
+}
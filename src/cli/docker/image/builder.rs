@@ -5,9 +5,10 @@ use std::path::Path;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
 
-use super::config::{BUILDER_IMAGE_NAME, DOCKER_BUILD_TIMEOUT};
+use super::config::BUILDER_IMAGE_NAME;
+use crate::cli::docker::runtime::runtime;
+use crate::bundler::utils::{interrupt, progress_bar::CommandProgress};
 
 /// Builds the Docker image from embedded Dockerfile.
 ///
@@ -15,6 +16,8 @@ use super::config::{BUILDER_IMAGE_NAME, DOCKER_BUILD_TIMEOUT};
 ///
 /// * `docker_build_context` - Path to directory containing .devcontainer/Dockerfile
 ///   (typically a temp directory where embedded Dockerfile was extracted)
+/// * `timeout` - Time limit for the build, [`super::config::DOCKER_BUILD_TIMEOUT`] by default;
+///   `None` for no limit
 /// * `runtime_config` - Runtime configuration for output
 ///
 /// # Returns
@@ -23,23 +26,23 @@ use super::config::{BUILDER_IMAGE_NAME, DOCKER_BUILD_TIMEOUT};
 /// * `Err` - Build failed
 pub async fn build_docker_image(
     docker_build_context: &Path,
+    timeout: Option<Duration>,
     runtime_config: &crate::cli::RuntimeConfig,
 ) -> Result<(), BundlerError> {
     let dockerfile_dir = docker_build_context.join(".devcontainer");
 
     runtime_config.progress(&format!("Building Docker image: {}", BUILDER_IMAGE_NAME)).expect("Failed to write to stdout");
 
+    let mut command = runtime().command();
+    command.args(["build", "--pull", "-t", BUILDER_IMAGE_NAME, "-f", "Dockerfile"]);
+
+    // Reuse layers of earlier builds, so a Dockerfile tweak doesn't start from zero
+    let (cache_env, cache_args) = runtime().build_cache(BUILDER_IMAGE_NAME);
+    command.envs(cache_env).args(cache_args);
+
     // Spawn with piped stdout and stderr for streaming
-    let mut child = Command::new("docker")
-        .args([
-            "build",
-            "--pull",
-            "-t",
-            BUILDER_IMAGE_NAME,
-            "-f",
-            "Dockerfile",
-            ".",
-        ])
+    let mut child = command
+        .arg(".")
         .current_dir(&dockerfile_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -51,14 +54,20 @@ pub async fn build_docker_image(
             })
         })?;
 
-    // Stream both stdout and stderr concurrently through OutputManager
+    let _interrupt = interrupt::kill_on_interrupt(&child);
+
+    // Stream both stdout and stderr concurrently through OutputManager,
+    // or behind a spinner on a terminal
+    let progress = CommandProgress::new("docker build");
     tokio::join!(
         async {
             if let Some(stdout) = child.stdout.take() {
                 let reader = BufReader::new(stdout);
                 let mut lines = reader.lines();
                 while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config.indent(&line).expect("Failed to write docker build output");
+                    if !progress.line(&line) {
+                        runtime_config.indent(&line).expect("Failed to write docker build output");
+                    }
                 }
             }
         },
@@ -67,19 +76,31 @@ pub async fn build_docker_image(
                 let reader = BufReader::new(stderr);
                 let mut lines = reader.lines();
                 while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config.indent(&line).expect("Failed to write docker build output");
+                    if !progress.line(&line) {
+                        runtime_config.indent(&line).expect("Failed to write docker build output");
+                    }
                 }
             }
         }
     );
 
     // Wait with timeout - handle timeout explicitly to kill child
-    let status = tokio::time::timeout(DOCKER_BUILD_TIMEOUT, child.wait()).await;
+    let status = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, child.wait()).await,
+        None => Ok(child.wait().await),
+    };
+    let captured = progress.finish();
+    let replay = || {
+        for line in &captured {
+            runtime_config.indent(line).expect("Failed to write docker build output");
+        }
+    };
 
     let status = match status {
         Ok(Ok(status)) => status, // Completed normally
         Ok(Err(e)) => {
             // Wait failed (process error)
+            replay();
             return Err(BundlerError::Cli(CliError::ExecutionFailed {
                 command: "docker build".to_string(),
                 reason: e.to_string(),
@@ -87,6 +108,7 @@ pub async fn build_docker_image(
         }
         Err(_elapsed) => {
             // Timeout occurred - kill the process before returning error
+            replay();
             runtime_config.warn("Docker build timed out, terminating process...").expect("Failed to write to stdout");
 
             // Kill process (SIGKILL)
@@ -109,16 +131,17 @@ pub async fn build_docker_image(
                      \n\
                      Solutions:\n\
                      • Check network connection\n\
-                     • Increase timeout if build is legitimately slow\n\
+                     • Increase --docker-build-timeout if the build is legitimately slow\n\
                      • Optimize Dockerfile (fewer layers, smaller base images)\n\
                      • Use local registry/cache",
-                    DOCKER_BUILD_TIMEOUT.as_secs() / 60
+                    timeout.map_or(0, |timeout| timeout.as_secs() / 60)
                 ),
             }));
         }
     };
 
     if !status.success() {
+        replay();
         return Err(BundlerError::Cli(CliError::ExecutionFailed {
             command: "docker build".to_string(),
             reason: format!(
@@ -13,6 +13,9 @@ pub const DOCKER_INFO_TIMEOUT: Duration = Duration::from_secs(5);
 /// Image builds can take a long time due to base image downloads, apt updates, etc.
 pub const DOCKER_BUILD_TIMEOUT: Duration = Duration::from_secs(1800);
 
+/// How long `--auto-start-docker` waits for a started daemon to respond
+pub const DOCKER_START_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// Platform-specific Docker startup instructions
 #[cfg(target_os = "macos")]
 pub const DOCKER_START_HELP: &str = "Start Docker Desktop from Applications or Spotlight";
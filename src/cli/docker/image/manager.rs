@@ -2,7 +2,8 @@
 
 use crate::error::{BundlerError, CliError};
 use std::time::Duration;
-use tokio::process::Command;
+use crate::bundler::utils::progress_bar;
+use crate::cli::docker::runtime::runtime;
 use tokio::time::timeout;
 
 use super::builder::build_docker_image;
@@ -23,8 +24,8 @@ async fn check_docker_responsive() -> Result<(), BundlerError> {
     // Use 'docker version' which is faster and simpler than 'images'
     let result = timeout(
         Duration::from_secs(3), // Very short timeout
-        Command::new("docker")
-            .args(["version", "--format", "{{.Server.Version}}"])
+        runtime().command()
+            .args(runtime().version_args())
             .output(),
     )
     .await;
@@ -70,6 +71,59 @@ async fn check_docker_responsive() -> Result<(), BundlerError> {
     }
 }
 
+/// Whether `image` exists locally
+pub async fn image_present(image: &str) -> bool {
+    runtime()
+        .command()
+        .args(["image", "inspect", image])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+/// Ensures a preprovisioned builder image (`--docker-image`) is present locally.
+///
+/// The image is pulled when missing and used as-is otherwise; it is never
+/// built or checked for staleness. It must provide the same tools as the
+/// embedded Dockerfile, including `kodegen_bundler_bundle` on PATH.
+///
+/// # Arguments
+///
+/// * `image` - Image reference, e.g. `ghcr.io/acme/builder:1.2`
+/// * `runtime_config` - Runtime configuration for output
+pub async fn ensure_image_available(
+    image: &str,
+    runtime_config: &crate::cli::RuntimeConfig,
+) -> Result<(), BundlerError> {
+    check_docker_responsive().await?;
+
+    if image_present(image).await {
+        runtime_config.verbose_println(&format!("Using builder image {}", image)).expect("Failed to write to stdout");
+        return Ok(());
+    }
+
+    runtime_config.progress(&format!("Pulling builder image {}...", image)).expect("Failed to write to stdout");
+    let pull = runtime().command().args(["pull", image]).output();
+    let output = progress_bar::spin(format!("Pulling {}", image), pull)
+        .await
+        .map_err(|e| {
+            BundlerError::Cli(CliError::ExecutionFailed {
+                command: format!("{} pull {}", runtime().name(), image),
+                reason: e.to_string(),
+            })
+        })?;
+    if !output.status.success() {
+        return Err(BundlerError::Cli(CliError::ExecutionFailed {
+            command: format!("{} pull {}", runtime().name(), image),
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
 /// Ensures the builder Docker image is built and up-to-date.
 ///
 /// Uses the bundler's embedded Dockerfile (no external dependencies required).
@@ -79,6 +133,7 @@ async fn check_docker_responsive() -> Result<(), BundlerError> {
 /// # Arguments
 ///
 /// * `force_rebuild` - If true, rebuild image unconditionally
+/// * `build_timeout` - Time limit for building the image, `None` for no limit
 /// * `runtime_config` - Runtime configuration for output
 ///
 /// # Returns
@@ -87,6 +142,7 @@ async fn check_docker_responsive() -> Result<(), BundlerError> {
 /// * `Err` - Failed to build or check image
 pub async fn ensure_image_built(
     force_rebuild: bool,
+    build_timeout: Option<Duration>,
     runtime_config: &crate::cli::RuntimeConfig,
 ) -> Result<(), BundlerError> {
     // Fast pre-flight check to ensure Docker daemon is responsive
@@ -109,13 +165,13 @@ pub async fn ensure_image_built(
     // Force rebuild if requested
     if force_rebuild {
         runtime_config.progress("Force rebuilding Docker image (--rebuild-image)...").expect("Failed to write to stdout");
-        return build_docker_image(temp_dir.path(), runtime_config).await;
+        return build_docker_image(temp_dir.path(), build_timeout, runtime_config).await;
     }
 
     // Check if image exists
     let check_output = timeout(
         Duration::from_secs(10), // Image check should be fast
-        Command::new("docker")
+        runtime().command()
             .args(["images", "-q", BUILDER_IMAGE_NAME])
             .output(),
     )
@@ -166,7 +222,7 @@ pub async fn ensure_image_built(
                         "Docker image is {} days old - rebuilding to get base image updates",
                         age_days
                     )).expect("Failed to write to stdout");
-                    return build_docker_image(temp_dir.path(), runtime_config).await;
+                    return build_docker_image(temp_dir.path(), build_timeout, runtime_config).await;
                 }
 
                 runtime_config.verbose_println("Docker image is up-to-date").expect("Failed to write to stdout");
@@ -178,7 +234,7 @@ pub async fn ensure_image_built(
                     BUILDER_IMAGE_NAME
                 )).expect("Failed to write to stdout");
                 runtime_config.progress("Rebuilding Docker image...").expect("Failed to write to stdout");
-                return build_docker_image(temp_dir.path(), runtime_config).await;
+                return build_docker_image(temp_dir.path(), build_timeout, runtime_config).await;
             }
             Err(e) => {
                 // If we can't determine staleness, be conservative and rebuild
@@ -186,7 +242,7 @@ pub async fn ensure_image_built(
                     "Could not verify image freshness: {}\nRebuilding to be safe...",
                     e
                 )).expect("Failed to write to stdout");
-                return build_docker_image(temp_dir.path(), runtime_config).await;
+                return build_docker_image(temp_dir.path(), build_timeout, runtime_config).await;
             }
         }
     }
@@ -196,5 +252,5 @@ pub async fn ensure_image_built(
         "Building {} Docker image (this may take a few minutes)...",
         BUILDER_IMAGE_NAME
     )).expect("Failed to write to stdout");
-    build_docker_image(temp_dir.path(), runtime_config).await
+    build_docker_image(temp_dir.path(), build_timeout, runtime_config).await
 }
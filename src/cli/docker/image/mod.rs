@@ -13,5 +13,6 @@ mod staleness;
 mod utils;
 
 // Re-export public API
-pub use config::BUILDER_IMAGE_NAME;
-pub use manager::ensure_image_built;
+pub use availability::{check_docker_available, ensure_docker_running};
+pub use config::{BUILDER_IMAGE_NAME, DOCKER_BUILD_TIMEOUT};
+pub use manager::{ensure_image_available, ensure_image_built, image_present};
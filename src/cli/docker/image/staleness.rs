@@ -3,7 +3,7 @@
 use crate::error::{BundlerError, CliError};
 use chrono::{DateTime, Utc};
 use std::path::Path;
-use tokio::process::Command;
+use crate::cli::docker::runtime::runtime;
 
 use super::utils::humanize_duration;
 
@@ -41,7 +41,7 @@ pub async fn is_image_up_to_date(
     runtime_config: &crate::cli::RuntimeConfig,
 ) -> Result<bool, BundlerError> {
     // Get image creation timestamp from Docker
-    let inspect_output = Command::new("docker")
+    let inspect_output = runtime().command()
         .args(["inspect", "-f", "{{.Created}}", image_id])
         .output()
         .await
@@ -145,7 +145,7 @@ pub async fn is_image_up_to_date(
 /// This prevents negative age values from bypassing rebuild checks.
 pub async fn get_image_age_days(image_id: &str) -> Result<u64, BundlerError> {
     // Get image creation timestamp from Docker
-    let inspect_output = Command::new("docker")
+    let inspect_output = runtime().command()
         .args(["inspect", "-f", "{{.Created}}", image_id])
         .output()
         .await
@@ -181,7 +181,7 @@ pub async fn get_image_age_days(image_id: &str) -> Result<u64, BundlerError> {
 
     // Detect clock skew: image timestamp is in the future
     if created_utc > now {
-        log::warn!(
+        tracing::warn!(
             "Docker image timestamp ({}) is in the future (current time: {}). \
              This indicates system clock is incorrect or out of sync. \
              Treating image as brand new (age 0 days) to avoid rebuild errors.",
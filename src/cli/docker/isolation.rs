@@ -0,0 +1,93 @@
+//! Network-isolated container builds (`--isolated`).
+//!
+//! The source is checked out and its dependencies vendored with
+//! `cargo vendor` on the host. Both are mounted read-only into a container
+//! started with `--network none`:
+//!
+//! ```text
+//! /tmp/kodegen-isolated/                  vendor directory, read-only
+//! ├── .cargo/config.toml                  source replacement from `cargo vendor`
+//! ├── vendor/                             vendored crates
+//! └── src/                                the checkout, read-only
+//! ```
+//!
+//! Cargo finds the source replacement in the parent directory of the
+//! checkout, so the repository's own configuration still applies.
+
+use crate::error::{BundlerError, CliError, Result};
+use crate::source::Checkout;
+use std::path::Path;
+
+/// Mount point of the vendor directory inside the container
+pub const CONTAINER_ISOLATED_DIR: &str = "/tmp/kodegen-isolated";
+
+/// Mount point of the checkout inside the container
+pub const CONTAINER_SOURCE_DIR: &str = "/tmp/kodegen-isolated/src";
+
+/// Host-side checkout and vendored dependencies of an isolated build
+pub struct VendoredSource {
+    checkout: Checkout,
+    dir: tempfile::TempDir,
+}
+
+impl std::fmt::Debug for VendoredSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VendoredSource")
+            .field("source_dir", &self.checkout.path())
+            .field("vendor_dir", &self.dir.path())
+            .finish()
+    }
+}
+
+impl VendoredSource {
+    /// Vendors the dependencies of `checkout` into a new temporary directory,
+    /// from the cargo cache alone when `offline`
+    pub async fn prepare(
+        checkout: Checkout,
+        offline: bool,
+        runtime_config: &crate::cli::RuntimeConfig,
+    ) -> Result<Self> {
+        let dir = tempfile::Builder::new().prefix("kodegen-vendor-").tempdir()?;
+        runtime_config.progress("Vendoring dependencies for the isolated build...").expect("Failed to write to stdout");
+
+        // Run in the vendor directory so the printed configuration refers to
+        // `vendor` relative to it
+        let manifest = checkout.path().join("Cargo.toml");
+        let mut command = tokio::process::Command::new("cargo");
+        command.arg("vendor");
+        if offline {
+            command.arg("--offline");
+        }
+        let output = command
+            .arg("--manifest-path")
+            .arg(&manifest)
+            .arg("vendor")
+            .current_dir(dir.path())
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(BundlerError::Cli(CliError::ExecutionFailed {
+                command: format!("cargo vendor --manifest-path {}", manifest.display()),
+                reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }));
+        }
+
+        let cargo_dir = dir.path().join(".cargo");
+        std::fs::create_dir_all(&cargo_dir)?;
+        std::fs::write(cargo_dir.join("config.toml"), &output.stdout)?;
+        // Mount point of the checkout, which is read-only from inside
+        std::fs::create_dir_all(dir.path().join("src"))?;
+
+        Ok(Self { checkout, dir })
+    }
+
+    /// Host directory mounted at [`CONTAINER_ISOLATED_DIR`]
+    pub fn vendor_dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Host checkout mounted at [`CONTAINER_SOURCE_DIR`]
+    pub fn source_dir(&self) -> &Path {
+        self.checkout.path()
+    }
+}
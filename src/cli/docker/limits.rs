@@ -61,7 +61,6 @@ impl ContainerLimits {
 
     /// Parse memory string like "4g", "4096m", "4G", "2048M" to megabytes.
     /// Supports decimal values like "4.5g", "1.5gb", "512.5m".
-    #[allow(dead_code)] // Used by from_cli, kept for future CLI argument parsing
     fn parse_memory_to_mb(memory: &str) -> Result<u64, String> {
         let memory = memory.trim().to_lowercase();
 
@@ -106,7 +105,6 @@ impl ContainerLimits {
     /// Creates limits from CLI arguments.
     ///
     /// Validates that memory_swap >= memory.
-    #[allow(dead_code)] // Public API with extensive tests, intended for future CLI integration
     pub fn from_cli(
         memory: String,
         memory_swap: Option<String>,
@@ -5,9 +5,10 @@
 //!
 //! # Example
 //!
-//! On macOS, running `bundle --all-platforms` will:
-//! - Build macOS packages (.app, .dmg) natively
-//! - Build Linux/Windows packages (.deb, .rpm, AppImage, .msi, .exe) in a Linux container with Wine
+//! On macOS, running `kodegen_bundler_bundle --all-platforms -o dist/` will:
+//! - Build macOS packages (.dmg) natively
+//! - Build Linux/Windows packages (.deb, .rpm, AppImage, .exe) in a Linux container,
+//!   concurrently with the native builds
 //!
 //! # Architecture
 //!
@@ -26,9 +27,13 @@
 //! - `container_runner` - Docker container execution and process streaming
 //! - `guard` - RAII guard for container cleanup
 //! - `image` - Docker image management and building
+//! - `isolation` - Vendored sources for network-isolated builds
 //! - `limits` - Resource limits for containers
+//! - `mount_check` - Output bind-mount validation for Docker Desktop file sharing
 //! - `oom_detector` - Out-of-memory detection and error reporting
 //! - `platform` - Platform detection and classification
+//! - `runtime` - Container runtime detection (Docker or Podman)
+//! - `smoke_test` - Installing produced packages into clean containers
 
 mod artifact_manager;
 mod artifacts;
@@ -36,8 +41,14 @@ pub mod bundler;
 mod container_runner;
 mod guard;
 pub mod image;
+pub mod isolation;
 pub mod limits;
+mod mount_check;
 mod oom_detector;
 mod platform;
+pub mod runtime;
+pub mod smoke_test;
 
 // Re-export public API
+pub use bundler::LOG_DIR;
+pub use container_runner::{CARGO_CACHE_VOLUME, RunTimeouts, SCCACHE_VOLUME, TARGET_CACHE_VOLUME};
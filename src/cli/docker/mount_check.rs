@@ -0,0 +1,196 @@
+//! Output bind-mount validation for Docker Desktop.
+//!
+//! Docker Desktop (macOS/Windows) only shares configured host directories
+//! with its VM. Mounting a directory outside them does not fail; the
+//! container just sees an empty `/output` and the artifact silently never
+//! reaches the host. Before running the bundle container we check the output
+//! directory against Docker Desktop's file-sharing settings and, when those
+//! cannot be read, run a short probe container that must see a marker file.
+
+#![cfg_attr(target_os = "linux", allow(dead_code))]
+
+use crate::error::{BundlerError, CliError};
+use super::runtime::runtime;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Upper bound for the probe container (image is already built at this point)
+const PROBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Docker Desktop settings files, newest format first, relative to the home directory
+const DOCKER_DESKTOP_SETTINGS: [&str; 2] = [
+    "Library/Group Containers/group.com.docker/settings-store.json",
+    "Library/Group Containers/group.com.docker/settings.json",
+];
+
+/// Validate that `output_dir` can be bind-mounted into a container.
+///
+/// Returns the absolute, canonical output directory to mount. Docker treats
+/// a relative `-v` source as a named volume, so the canonical path is
+/// required even where no file sharing restrictions apply.
+pub async fn validate_output_mount(
+    output_dir: &Path,
+    image_name: &str,
+) -> Result<PathBuf, BundlerError> {
+    let output_dir = output_dir.canonicalize().map_err(|e| {
+        mount_error(format!("Failed to resolve output directory {}: {}", output_dir.display(), e))
+    })?;
+
+    // Native Docker Engine on Linux shares the whole host filesystem
+    if cfg!(target_os = "linux") {
+        return Ok(output_dir);
+    }
+
+    if let Some(shared_dirs) = docker_desktop_shared_dirs() {
+        if is_shared(&output_dir, &shared_dirs) {
+            return Ok(output_dir);
+        }
+        return Err(not_shared_error(&output_dir, Some(&shared_dirs)));
+    }
+
+    // Settings unavailable: fall back to a write-probe through a real mount
+    if probe_mount(&output_dir, image_name).await? {
+        Ok(output_dir)
+    } else {
+        Err(not_shared_error(&output_dir, None))
+    }
+}
+
+/// Read the file-sharing directories from Docker Desktop's settings
+///
+/// Returns `None` when Docker Desktop is not installed or the settings do
+/// not list shared directories (e.g. VirtioFS setups managed elsewhere).
+fn docker_desktop_shared_dirs() -> Option<Vec<PathBuf>> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+
+    let home = dirs::home_dir()?;
+    DOCKER_DESKTOP_SETTINGS.iter().find_map(|relative| {
+        let contents = std::fs::read_to_string(home.join(relative)).ok()?;
+        parse_shared_dirs(&contents)
+    })
+}
+
+/// Extract the shared directory list from a Docker Desktop settings file
+fn parse_shared_dirs(settings_json: &str) -> Option<Vec<PathBuf>> {
+    let settings: serde_json::Value = serde_json::from_str(settings_json).ok()?;
+    let dirs = settings
+        .get("FilesharingDirectories")
+        .or_else(|| settings.get("filesharingDirectories"))?
+        .as_array()?;
+
+    let dirs: Vec<PathBuf> = dirs
+        .iter()
+        .filter_map(|d| d.as_str())
+        .map(PathBuf::from)
+        .collect();
+    (!dirs.is_empty()).then_some(dirs)
+}
+
+/// Check whether `path` is inside one of the shared directories
+///
+/// Shared entries are compared both as written and canonicalized, since
+/// Docker Desktop lists `/tmp` while macOS resolves it to `/private/tmp`.
+fn is_shared(path: &Path, shared_dirs: &[PathBuf]) -> bool {
+    shared_dirs.iter().any(|shared| {
+        path.starts_with(shared)
+            || shared
+                .canonicalize()
+                .is_ok_and(|canonical| path.starts_with(canonical))
+    })
+}
+
+/// Mount `output_dir` read-only in a throwaway container and check that a
+/// marker file written on the host is visible inside it.
+async fn probe_mount(output_dir: &Path, image_name: &str) -> Result<bool, BundlerError> {
+    let token = uuid::Uuid::new_v4().to_string();
+    let marker_name = format!(".kodegen-mount-probe-{}", token);
+    let marker = output_dir.join(&marker_name);
+
+    std::fs::write(&marker, &token).map_err(|e| {
+        mount_error(format!("Failed to write mount probe {}: {}", marker.display(), e))
+    })?;
+
+    let probe = runtime().command()
+        .args(["run", "--rm", "--entrypoint", "cat", "-v"])
+        .arg(format!("{}:/probe:ro", output_dir.display()))
+        .arg(image_name)
+        .arg(format!("/probe/{}", marker_name))
+        .kill_on_drop(true)
+        .output();
+    let result = tokio::time::timeout(PROBE_TIMEOUT, probe).await;
+
+    let _ = std::fs::remove_file(&marker);
+
+    match result {
+        Ok(Ok(output)) => Ok(output.status.success()
+            && String::from_utf8_lossy(&output.stdout).trim() == token),
+        Ok(Err(e)) => Err(mount_error(format!("Failed to run mount probe container: {}", e))),
+        Err(_) => Err(mount_error(format!(
+            "Mount probe container did not finish within {}s",
+            PROBE_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+fn not_shared_error(output_dir: &Path, shared_dirs: Option<&[PathBuf]>) -> BundlerError {
+    let shared = match shared_dirs {
+        Some(dirs) => format!(
+            "\n\nCurrently shared directories:\n{}",
+            dirs.iter()
+                .map(|d| format!("  - {}", d.display()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        None => String::new(),
+    };
+
+    mount_error(format!(
+        "Output directory {} is not shared with Docker Desktop; the container \
+         would write the artifact to an empty mount and it would never reach the host.\n\
+         \n\
+         Fix one of:\n\
+         - Docker Desktop → Settings → Resources → File sharing: add {}\n\
+         - Choose an --output-binary path under a shared directory (e.g. your home directory){}",
+        output_dir.display(),
+        output_dir.display(),
+        shared
+    ))
+}
+
+fn mount_error(reason: String) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "validate docker output mount".to_string(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shared_dirs() {
+        let current = r#"{"FilesharingDirectories": ["/Users", "/Volumes", "/tmp"]}"#;
+        assert_eq!(
+            parse_shared_dirs(current).unwrap(),
+            vec![PathBuf::from("/Users"), PathBuf::from("/Volumes"), PathBuf::from("/tmp")]
+        );
+
+        let legacy = r#"{"filesharingDirectories": ["/Users"]}"#;
+        assert_eq!(parse_shared_dirs(legacy).unwrap(), vec![PathBuf::from("/Users")]);
+
+        assert!(parse_shared_dirs(r#"{"FilesharingDirectories": []}"#).is_none());
+        assert!(parse_shared_dirs("not json").is_none());
+    }
+
+    #[test]
+    fn test_is_shared() {
+        let shared = vec![PathBuf::from("/Users"), PathBuf::from("/Volumes")];
+        assert!(is_shared(Path::new("/Users/dev/dist"), &shared));
+        assert!(!is_shared(Path::new("/opt/releases"), &shared));
+        // Component-wise prefix, not string prefix
+        assert!(!is_shared(Path::new("/UsersExtra/dist"), &shared));
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::bundler::PackageType;
 use crate::error::{BundlerError, CliError};
-use tokio::process::Command;
+use crate::cli::docker::runtime::runtime;
 
 /// Out-of-memory detector for Docker containers.
 pub struct OomDetector {
@@ -34,7 +34,7 @@ impl OomDetector {
     ///
     /// `true` if container was OOM killed, `false` otherwise
     pub async fn check_container_oom_status(container_name: &str) -> Result<bool, std::io::Error> {
-        let output = Command::new("docker")
+        let output = runtime().command()
             .args([
                 "inspect",
                 container_name,
@@ -49,14 +49,14 @@ impl OomDetector {
 
             // Distinguish "not found" from other errors
             if stderr.contains("No such container") || stderr.contains("No such object") {
-                log::debug!(
+                tracing::debug!(
                     "Container {} already removed (possibly OOM-killed with --rm)",
                     container_name
                 );
                 return Ok(false);
             }
 
-            log::warn!(
+            tracing::warn!(
                 "Docker inspect failed for {}: {}",
                 container_name,
                 stderr
@@ -132,7 +132,7 @@ impl OomDetector {
             
             // If stderr is completely empty with exit 137, be conservative
             if stderr_str.trim().is_empty() {
-                log::warn!(
+                tracing::warn!(
                     "Container exited with 137 (SIGKILL) but no stderr output. \
                      Could be OOM, user kill, or system shutdown. Not diagnosing as OOM \
                      without stronger evidence."
@@ -142,7 +142,7 @@ impl OomDetector {
             
             // If we have stderr but no clear OOM or non-OOM markers,
             // be conservative - don't assume OOM
-            log::debug!(
+            tracing::debug!(
                 "Container killed with SIGKILL (137), no clear OOM evidence. \
                  First stderr lines: {}",
                 stderr_str.lines().take(3).collect::<Vec<_>>().join("; ")
@@ -172,7 +172,7 @@ impl OomDetector {
         exit_code: i32,
         container_name: &str,
     ) -> BundlerError {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
 
         // Get system memory info
         let mut sys = sysinfo::System::new();
@@ -204,7 +204,7 @@ impl OomDetector {
              \n\
              Solutions:\n\
              1. Increase memory limit:\n\
-                cargo run -p kodegen_bundler_release -- bundle --platform {} --docker-memory 8g\n\
+                kodegen_bundler_bundle --platform {} --docker-memory 8g\n\
              \n\
              2. Build fewer platforms in parallel (run multiple times with --platform)\n\
              \n\
@@ -249,7 +249,7 @@ impl OomDetector {
         exit_code: i32,
         stderr_lines: &[String],
     ) -> BundlerError {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
         let stderr_str = stderr_lines.join("\n");
 
         let error_output = if !stderr_str.is_empty() {
@@ -282,7 +282,7 @@ impl OomDetector {
         platform: PackageType,
         stderr_lines: &[String],
     ) -> BundlerError {
-        let platform_str = super::platform::platform_type_to_string(platform);
+        let platform_str = platform.short_name();
         let stderr_str = stderr_lines.join("\n");
 
         let error_output = if !stderr_str.is_empty() {
@@ -306,7 +306,7 @@ impl OomDetector {
                  To diagnose:\n\
                  • Check system logs: journalctl -u docker (Linux) or Console.app (macOS)\n\
                  • Check kernel logs: dmesg | grep -i oom\n\
-                 • Verify container limits: --docker-memory, --cpus, --pids-limit\n\
+                 • Verify container limits: --docker-memory, --docker-cpus, --docker-pids-limit\n\
                  • Check if you manually killed the process\n\
                  \n\
                  {}",
@@ -65,33 +65,25 @@ fn is_native_platform(platform: PackageType) -> bool {
         ("macos", MacOsBundle | Dmg) => true,
 
         // Linux native packages (Windows .exe works natively via makensis)
-        ("linux", Deb | Rpm | AppImage | Exe) => true,
+        ("linux", Deb | Rpm | AppImage | Exe | Portable) => true,
 
         // Windows native packages
-        ("windows", Exe) => true,
+        ("windows", Exe | Portable) => true,
+
+        // Custom bundlers run wherever they are registered
+        (_, Custom(_)) => true,
 
         // Everything else needs Docker
         _ => false,
     }
 }
 
-/// Converts PackageType to string for CLI arguments.
-pub fn platform_type_to_string(platform: PackageType) -> &'static str {
-    match platform {
-        PackageType::Deb => "deb",
-        PackageType::Rpm => "rpm",
-        PackageType::AppImage => "appimage",
-        PackageType::MacOsBundle => "app",
-        PackageType::Dmg => "dmg",
-        PackageType::Exe => "exe",
-    }
-}
-
 /// Returns emoji for platform type (for pretty output).
 pub fn platform_emoji(platform: PackageType) -> &'static str {
     match platform {
         PackageType::Deb | PackageType::Rpm | PackageType::AppImage => "🐧",
         PackageType::MacOsBundle | PackageType::Dmg => "🍎",
-        PackageType::Exe => "🪟",
+        PackageType::Exe | PackageType::Portable => "🪟",
+        _ => "📦",
     }
 }
@@ -0,0 +1,308 @@
+//! Container runtime abstraction (Docker or Podman).
+//!
+//! Both runtimes accept the same CLI for everything the bundler does (`run`,
+//! `build`, `inspect`, `rm`, `volume`), so callers build their commands from
+//! [`runtime()`] and only the behavioral differences live behind
+//! [`ContainerRuntime`]:
+//!
+//! - Rootless Podman maps the host user to root inside the container, so the
+//!   `builder` user (UID 1000) could not write to the output bind mount.
+//!   `--userns=keep-id` maps the host user to `builder` instead.
+//! - Podman enforces `--pids-limit` through the pids cgroup controller, which
+//!   rootless containers on cgroups v1 don't have; the flag is dropped there.
+//! - Layer caching: Docker builds with BuildKit and inline cache, Podman
+//!   (buildah) caches layers locally by default.
+//!
+//! Either runtime may talk to a remote engine (`DOCKER_HOST`, a docker
+//! context, or `CONTAINER_HOST` for Podman). Host directories can't be bind
+//! mounted there, so artifacts are copied out of the container instead.
+//!
+//! The runtime is detected once: `KODEGEN_CONTAINER_RUNTIME=docker|podman`
+//! wins, then `docker` on PATH (unless it is Podman's `docker` shim), then
+//! `podman`.
+
+use std::sync::OnceLock;
+
+/// Container image user that owns the build and output directories
+const BUILDER_UID: u32 = 1000;
+
+/// Operations that differ between container runtimes
+pub trait ContainerRuntime: Send + Sync + std::fmt::Debug {
+    /// Executable name, also used in messages
+    fn name(&self) -> &'static str;
+
+    /// `version` arguments that fail unless the engine is reachable
+    fn version_args(&self) -> &'static [&'static str];
+
+    /// Extra `run` flags for the output bind mount to be writable by the builder user
+    fn run_args(&self) -> Vec<String>;
+
+    /// Options appended to the output bind mount, e.g. SELinux relabeling
+    fn bind_mount_options(&self) -> &'static str;
+
+    /// `run` flags limiting the number of processes, empty where unsupported
+    fn pids_limit_args(&self, limit: u32) -> Vec<String>;
+
+    /// Environment and flags enabling layer caching for `build` of `image`
+    fn build_cache(&self, image: &str) -> (Vec<(&'static str, &'static str)>, Vec<String>);
+
+    /// Whether the engine runs on another machine, so host paths can't be mounted
+    fn is_remote(&self) -> bool;
+
+    /// Async command running the runtime executable
+    fn command(&self) -> tokio::process::Command {
+        tokio::process::Command::new(self.name())
+    }
+
+    /// Blocking command running the runtime executable, for `Drop` paths
+    fn std_command(&self) -> std::process::Command {
+        std::process::Command::new(self.name())
+    }
+}
+
+/// Docker Engine or Docker Desktop
+#[derive(Debug)]
+pub struct Docker {
+    /// Whether the buildx plugin needed for BuildKit is installed
+    buildkit: bool,
+    /// Whether `DOCKER_HOST` or the current context points at another machine
+    remote: bool,
+}
+
+impl Docker {
+    fn detect() -> Self {
+        let buildkit = succeeds("docker", &["buildx", "version"]);
+        // DOCKER_HOST overrides the context; otherwise ask the current context
+        let endpoint = std::env::var("DOCKER_HOST").ok().filter(|host| !host.is_empty()).or_else(|| {
+            std::process::Command::new("docker")
+                .args(["context", "inspect", "--format", "{{.Endpoints.docker.Host}}"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        });
+        let remote = endpoint.as_deref().is_some_and(is_remote_endpoint);
+        Self { buildkit, remote }
+    }
+}
+
+impl ContainerRuntime for Docker {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn version_args(&self) -> &'static [&'static str] {
+        &["version", "--format", "{{.Server.Version}}"]
+    }
+
+    fn run_args(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn bind_mount_options(&self) -> &'static str {
+        "rw"
+    }
+
+    fn pids_limit_args(&self, limit: u32) -> Vec<String> {
+        vec!["--pids-limit".to_string(), limit.to_string()]
+    }
+
+    fn build_cache(&self, image: &str) -> (Vec<(&'static str, &'static str)>, Vec<String>) {
+        if !self.buildkit {
+            tracing::debug!("BuildKit unavailable (docker buildx not installed), using the legacy builder");
+            return (Vec::new(), Vec::new());
+        }
+        // The previous image is the inline cache source, so a Dockerfile tweak
+        // only rebuilds the layers after it, even once the build cache was pruned
+        let args = [
+            "--progress=plain",
+            "--build-arg",
+            "BUILDKIT_INLINE_CACHE=1",
+            "--cache-from",
+            image,
+        ];
+        (vec![("DOCKER_BUILDKIT", "1")], args.map(String::from).to_vec())
+    }
+
+    fn is_remote(&self) -> bool {
+        self.remote
+    }
+}
+
+/// Podman, rootful or rootless
+#[derive(Debug)]
+pub struct Podman {
+    rootless: bool,
+    cgroups_v2: bool,
+    /// Whether `CONTAINER_HOST` points at another machine
+    remote: bool,
+}
+
+impl Podman {
+    fn detect() -> Self {
+        let info = std::process::Command::new("podman")
+            .args(["info", "--format", "{{.Host.Security.Rootless}} {{.Host.CgroupsVersion}}"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_default();
+        let (rootless, cgroups) = parse_podman_info(&info);
+        let remote = std::env::var("CONTAINER_HOST").is_ok_and(|host| is_remote_endpoint(&host));
+        Self {
+            rootless,
+            cgroups_v2: cgroups != "v1",
+            remote,
+        }
+    }
+}
+
+impl ContainerRuntime for Podman {
+    fn name(&self) -> &'static str {
+        "podman"
+    }
+
+    fn version_args(&self) -> &'static [&'static str] {
+        // `.Server` is only populated for remote connections
+        &["version", "--format", "{{.Client.Version}}"]
+    }
+
+    fn run_args(&self) -> Vec<String> {
+        if self.rootless {
+            vec![format!("--userns=keep-id:uid={},gid={}", BUILDER_UID, BUILDER_UID)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn bind_mount_options(&self) -> &'static str {
+        // Shared SELinux label, otherwise enforcing hosts deny access
+        "rw,z"
+    }
+
+    fn pids_limit_args(&self, limit: u32) -> Vec<String> {
+        if self.rootless && !self.cgroups_v2 {
+            tracing::debug!("Rootless podman on cgroups v1 cannot limit pids; skipping --pids-limit");
+            return Vec::new();
+        }
+        vec!["--pids-limit".to_string(), limit.to_string()]
+    }
+
+    fn build_cache(&self, _image: &str) -> (Vec<(&'static str, &'static str)>, Vec<String>) {
+        // buildah caches intermediate layers unless told otherwise
+        (Vec::new(), vec!["--layers".to_string()])
+    }
+
+    fn is_remote(&self) -> bool {
+        self.remote
+    }
+}
+
+/// The container runtime used for all container operations
+pub fn runtime() -> &'static dyn ContainerRuntime {
+    static RUNTIME: OnceLock<Box<dyn ContainerRuntime>> = OnceLock::new();
+    RUNTIME.get_or_init(detect).as_ref()
+}
+
+fn detect() -> Box<dyn ContainerRuntime> {
+    match std::env::var("KODEGEN_CONTAINER_RUNTIME").as_deref() {
+        Ok("docker") => return Box::new(Docker::detect()),
+        Ok("podman") => return Box::new(Podman::detect()),
+        Ok(other) => tracing::warn!("Ignoring unknown KODEGEN_CONTAINER_RUNTIME '{}'", other),
+        Err(_) => {}
+    }
+
+    let runtime: Box<dyn ContainerRuntime> = if which::which("docker").is_ok() {
+        // podman-docker installs a `docker` wrapper around podman
+        let is_podman = std::process::Command::new("docker")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).to_lowercase().contains("podman"));
+        if is_podman { Box::new(Podman::detect()) } else { Box::new(Docker::detect()) }
+    } else if which::which("podman").is_ok() {
+        Box::new(Podman::detect())
+    } else {
+        // Not installed; the availability check reports it
+        Box::new(Docker {
+            buildkit: false,
+            remote: false,
+        })
+    };
+    tracing::debug!("Using container runtime {:?}", runtime);
+    runtime
+}
+
+/// Whether `program args` exits successfully
+fn succeeds(program: &str, args: &[&str]) -> bool {
+    std::process::Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Whether an engine endpoint such as `ssh://builder` is on another machine
+///
+/// `unix://` and `npipe://` sockets are local; so is a TCP endpoint on the
+/// loopback interface.
+fn is_remote_endpoint(endpoint: &str) -> bool {
+    let Some((scheme, rest)) = endpoint.split_once("://") else {
+        return false;
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    match scheme {
+        "ssh" => true,
+        "tcp" | "http" | "https" => !matches!(host, "localhost" | "127.0.0.1" | "::1"),
+        _ => false,
+    }
+}
+
+/// Parse `podman info` output of the form `<rootless> <cgroups version>`
+fn parse_podman_info(info: &str) -> (bool, &str) {
+    let mut fields = info.split_whitespace();
+    let rootless = fields.next() == Some("true");
+    (rootless, fields.next().unwrap_or("v2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_podman_rootless_cgroups_v1() {
+        let (rootless, cgroups) = parse_podman_info("true v1");
+        let podman = Podman {
+            rootless,
+            cgroups_v2: cgroups != "v1",
+            remote: false,
+        };
+        assert!(podman.pids_limit_args(1000).is_empty());
+        assert_eq!(podman.run_args(), ["--userns=keep-id:uid=1000,gid=1000"]);
+
+        let (rootless, cgroups) = parse_podman_info("false v2");
+        let podman = Podman {
+            rootless,
+            cgroups_v2: cgroups != "v1",
+            remote: false,
+        };
+        assert_eq!(podman.pids_limit_args(1000), ["--pids-limit", "1000"]);
+        assert!(podman.run_args().is_empty());
+    }
+
+    #[test]
+    fn test_is_remote_endpoint() {
+        assert!(is_remote_endpoint("ssh://builder@linux-box"));
+        assert!(is_remote_endpoint("tcp://10.0.0.5:2376"));
+        assert!(is_remote_endpoint("tcp://[2001:db8::1]:2376"));
+        assert!(!is_remote_endpoint("tcp://127.0.0.1:2375"));
+        assert!(!is_remote_endpoint("tcp://[::1]:2375"));
+        assert!(!is_remote_endpoint("unix:///var/run/docker.sock"));
+        assert!(!is_remote_endpoint("npipe:////./pipe/docker_engine"));
+    }
+}
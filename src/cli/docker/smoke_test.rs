@@ -0,0 +1,285 @@
+//! Post-bundle installation smoke test.
+//!
+//! Installs a produced package into a fresh container and runs the
+//! installed `<binary> --version`, which catches packages that install but
+//! cannot start (undeclared shared libraries, a broken AppRun, an installer
+//! that drops the executable):
+//!
+//! | Package | Image | Install |
+//! |---------|-------|---------|
+//! | .deb | `debian:stable-slim` | `apt-get install ./<file>` (resolves `Depends`) |
+//! | .rpm | `fedora:latest` | `dnf install ./<file>` (resolves `Requires`) |
+//! | AppImage | `ubuntu:24.04` | none; runs extracted, without FUSE |
+//! | NSIS setup | `debian:stable-slim` + Wine | `setup.exe /S /D=C:\app` |
+//!
+//! The artifact is copied into the container rather than bind mounted, so
+//! remote engines work too. Other artifact types are not tested.
+
+use super::guard::ContainerGuard;
+use super::runtime::runtime;
+use crate::error::{BundlerError, CliError};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Time limit for installing and running one package, image pull included
+const SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Exit code of the container script when installation fails
+const INSTALL_FAILED: i32 = 90;
+
+/// Exit code of the container script when `--version` fails
+const RUN_FAILED: i32 = 91;
+
+/// Package types the smoke test can install
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmokeTestKind {
+    Deb,
+    Rpm,
+    AppImage,
+    Nsis,
+}
+
+impl SmokeTestKind {
+    /// Kind of `artifact`, by extension; `None` for untestable artifacts
+    pub fn of(artifact: &Path) -> Option<Self> {
+        let extension = artifact.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "deb" => Some(Self::Deb),
+            "rpm" => Some(Self::Rpm),
+            "appimage" => Some(Self::AppImage),
+            "exe" => Some(Self::Nsis),
+            _ => None,
+        }
+    }
+
+    /// Clean image the package is installed into by default
+    pub fn image(&self) -> &'static str {
+        match self {
+            Self::Deb | Self::Nsis => "debian:stable-slim",
+            Self::Rpm => "fedora:latest",
+            Self::AppImage => "ubuntu:24.04",
+        }
+    }
+
+    /// Path the artifact is copied to inside the container
+    fn container_path(&self) -> &'static str {
+        match self {
+            Self::Deb => "/tmp/package.deb",
+            Self::Rpm => "/tmp/package.rpm",
+            Self::AppImage => "/tmp/package.AppImage",
+            Self::Nsis => "/tmp/setup.exe",
+        }
+    }
+}
+
+/// How a smoke test ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmokeTestOutcome {
+    /// Installed, and `--version` exited successfully
+    Passed,
+    /// The package manager or installer failed
+    InstallFailed,
+    /// The installed binary failed to run
+    RunFailed,
+    /// Preparing the container failed (e.g. no network for the package index)
+    SetupFailed,
+}
+
+/// Result of smoke testing one artifact
+#[derive(Debug, Serialize)]
+pub struct SmokeTestReport {
+    pub artifact: PathBuf,
+    pub kind: SmokeTestKind,
+    pub image: String,
+    pub outcome: SmokeTestOutcome,
+    /// Exit code of the container script; `None` when it was killed
+    pub exit_code: Option<i32>,
+    /// Combined container output
+    pub log: String,
+}
+
+impl SmokeTestReport {
+    /// Last lines of the container output, for error messages
+    pub fn log_tail(&self, lines: usize) -> String {
+        let all: Vec<&str> = self.log.lines().collect();
+        all[all.len().saturating_sub(lines)..].join("\n")
+    }
+}
+
+/// Installs `artifact` into a clean container and runs `binary --version`.
+///
+/// Without `binary` the first executable the package installs is run, and
+/// without `image` the kind's default image is used. An `Err` means the test itself could not run; a failed installation or run
+/// is reported through [`SmokeTestReport::outcome`].
+pub async fn smoke_test(
+    artifact: &Path,
+    kind: SmokeTestKind,
+    binary: Option<&str>,
+    image: Option<&str>,
+    runtime_config: &crate::cli::RuntimeConfig,
+) -> Result<SmokeTestReport, BundlerError> {
+    let image = image.unwrap_or(kind.image());
+    let container_name = format!("kodegen-smoke-{}", Uuid::new_v4());
+    let _guard = ContainerGuard::new(container_name.clone(), runtime_config.output().clone());
+
+    runtime_config.indent(&format!(
+        "🧪 Smoke testing {} in {}...",
+        artifact.display(),
+        image
+    )).expect("Failed to write to stdout");
+
+    let script = script(kind, binary);
+    run(&["create", "--name", &container_name, image, "sh", "-c", &script]).await?;
+    let destination = format!("{}:{}", container_name, kind.container_path());
+    run(&["cp", &artifact.to_string_lossy(), &destination]).await?;
+
+    let start = runtime()
+        .command()
+        .args(["start", "--attach", &container_name])
+        .output();
+    let output = tokio::time::timeout(SMOKE_TEST_TIMEOUT, start)
+        .await
+        .map_err(|_| {
+            smoke_error(format!(
+                "timed out after {} minutes",
+                SMOKE_TEST_TIMEOUT.as_secs() / 60
+            ))
+        })?
+        .map_err(|e| smoke_error(e.to_string()))?;
+
+    let mut log = String::from_utf8_lossy(&output.stdout).into_owned();
+    log.push_str(&String::from_utf8_lossy(&output.stderr));
+    let exit_code = output.status.code();
+    let outcome = match exit_code {
+        Some(0) => SmokeTestOutcome::Passed,
+        Some(INSTALL_FAILED) => SmokeTestOutcome::InstallFailed,
+        Some(RUN_FAILED) => SmokeTestOutcome::RunFailed,
+        _ => SmokeTestOutcome::SetupFailed,
+    };
+    Ok(SmokeTestReport {
+        artifact: artifact.to_path_buf(),
+        kind,
+        image: image.to_string(),
+        outcome,
+        exit_code,
+        log,
+    })
+}
+
+/// Shell script installing and running the package inside the container
+fn script(kind: SmokeTestKind, binary: Option<&str>) -> String {
+    let package = kind.container_path();
+    let binary = binary.map(shell_quote);
+    let (setup, install, locate) = match kind {
+        SmokeTestKind::Deb => (
+            "export DEBIAN_FRONTEND=noninteractive\napt-get update -qq".to_string(),
+            format!("apt-get install -y -qq {}", package),
+            match &binary {
+                Some(binary) => format!("BIN=$(command -v {})", binary),
+                None => format!(
+                    "BIN=$(dpkg -L \"$(dpkg-deb -f {} Package)\" | grep '^/usr/bin/' | head -n1)",
+                    package
+                ),
+            },
+        ),
+        SmokeTestKind::Rpm => (
+            String::new(),
+            format!("dnf install -y -q {}", package),
+            match &binary {
+                Some(binary) => format!("BIN=$(command -v {})", binary),
+                None => format!("BIN=$(rpm -qlp {} | grep '^/usr/bin/' | head -n1)", package),
+            },
+        ),
+        SmokeTestKind::AppImage => (
+            // No FUSE in containers; the runtime extracts itself instead
+            "export APPIMAGE_EXTRACT_AND_RUN=1".to_string(),
+            format!("chmod +x {}", package),
+            format!("BIN={}", package),
+        ),
+        SmokeTestKind::Nsis => (
+            "export DEBIAN_FRONTEND=noninteractive WINEDEBUG=-all\n\
+             dpkg --add-architecture i386\n\
+             apt-get update -qq\n\
+             apt-get install -y -qq --no-install-recommends wine wine32 wine64"
+                .to_string(),
+            format!("wine {} /S '/D=C:\\app'", package),
+            match &binary {
+                Some(binary) => format!(
+                    "BIN=\"$HOME/.wine/drive_c/app/\"{}.exe",
+                    binary
+                ),
+                None => "BIN=$(ls \"$HOME\"/.wine/drive_c/app/*.exe | grep -v Uninstall | head -n1)"
+                    .to_string(),
+            },
+        ),
+    };
+    let run = if kind == SmokeTestKind::Nsis {
+        "wine \"$BIN\" --version"
+    } else {
+        "\"$BIN\" --version"
+    };
+
+    format!(
+        "set -e\n{setup}\n\
+         {install} || exit {INSTALL_FAILED}\n\
+         {locate} || exit {INSTALL_FAILED}\n\
+         [ -n \"$BIN\" ] || {{ echo 'no installed executable found'; exit {INSTALL_FAILED}; }}\n\
+         echo \"Running $BIN --version\"\n\
+         {run} || exit {RUN_FAILED}\n"
+    )
+}
+
+/// Quotes `value` for `sh`
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Runs a container runtime command, failing on a non-zero exit
+async fn run(args: &[&str]) -> Result<(), BundlerError> {
+    let output = runtime()
+        .command()
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| smoke_error(format!("{} {}: {}", runtime().name(), args[0], e)))?;
+    if !output.status.success() {
+        return Err(smoke_error(format!(
+            "{} {} failed: {}",
+            runtime().name(),
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+fn smoke_error(reason: impl Into<String>) -> BundlerError {
+    BundlerError::Cli(CliError::ExecutionFailed {
+        command: "smoke test".to_string(),
+        reason: reason.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script() {
+        assert_eq!(SmokeTestKind::of(Path::new("App.AppImage")), Some(SmokeTestKind::AppImage));
+        assert_eq!(SmokeTestKind::of(Path::new("app.dmg")), None);
+
+        let deb = script(SmokeTestKind::Deb, Some("my'app"));
+        assert!(deb.contains("apt-get install -y -qq /tmp/package.deb || exit 90\n"));
+        assert!(deb.contains("BIN=$(command -v 'my'\\''app') || exit 90\n"));
+        assert!(deb.ends_with("\"$BIN\" --version || exit 91\n"));
+
+        let nsis = script(SmokeTestKind::Nsis, None);
+        assert!(nsis.contains("wine /tmp/setup.exe /S '/D=C:\\app' || exit 90\n"));
+        assert!(nsis.ends_with("wine \"$BIN\" --version || exit 91\n"));
+    }
+}
@@ -0,0 +1,161 @@
+//! GitHub Actions integration.
+//!
+//! When `GITHUB_ACTIONS=true`, bundle results are written to
+//! `$GITHUB_OUTPUT` so later steps can use them without parsing logs:
+//!
+//! | Output | Value |
+//! |--------|-------|
+//! | `version` | Package version (when known) |
+//! | `artifacts` | Artifact paths, one per line |
+//! | `checksums` | `sha256sum`-style lines for the artifact files |
+//! | `<platform>` | Path of the platform's artifact, e.g. `deb` |
+//! | `<platform>-sha256` | SHA-256 of that artifact |
+//!
+//! Problems are reported as `::error` / `::notice` workflow commands. They
+//! go to stderr, keeping stdout limited to artifact paths.
+
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+
+/// Whether the bundler runs inside a GitHub Actions job
+pub fn is_enabled() -> bool {
+    std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true")
+}
+
+/// Emits an `::error` annotation
+pub fn error(title: &str, message: &str) {
+    annotate("error", title, message);
+}
+
+/// Emits a `::notice` annotation
+pub fn notice(title: &str, message: &str) {
+    annotate("notice", title, message);
+}
+
+fn annotate(level: &str, title: &str, message: &str) {
+    if is_enabled() {
+        eprintln!("{}", workflow_command(level, title, message));
+    }
+}
+
+/// Writes outputs for `artifacts` (platform name and path) to `$GITHUB_OUTPUT`
+///
+/// Does nothing outside GitHub Actions. Failures only produce a warning
+/// annotation, since the artifacts themselves were built.
+pub fn write_outputs(version: Option<&str>, artifacts: &[(&str, &Path)]) {
+    if !is_enabled() {
+        return;
+    }
+    for (_, path) in artifacts {
+        notice("Bundled artifact", &path.display().to_string());
+    }
+
+    let Some(output_file) = std::env::var_os("GITHUB_OUTPUT") else {
+        return;
+    };
+    let result = render_outputs(version, artifacts).and_then(|outputs| {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&output_file)?
+            .write_all(outputs.as_bytes())
+    });
+    if let Err(e) = result {
+        annotate(
+            "warning",
+            "GITHUB_OUTPUT",
+            &format!("Failed to write outputs: {}", e),
+        );
+    }
+}
+
+fn render_outputs(version: Option<&str>, artifacts: &[(&str, &Path)]) -> std::io::Result<String> {
+    let mut outputs = String::new();
+    if let Some(version) = version {
+        outputs.push_str(&format!("version={}\n", version));
+    }
+
+    let mut paths = String::new();
+    let mut checksums = String::new();
+    let mut platforms = Vec::new();
+    for (platform, path) in artifacts {
+        paths.push_str(&format!("{}\n", path.display()));
+        let sha256 = if path.is_file() {
+            let digest = hash_file(path)?;
+            checksums.push_str(&format!("{}  {}\n", digest, path.display()));
+            Some(digest)
+        } else {
+            None
+        };
+
+        // The first artifact of each platform is its main one
+        if !platforms.contains(platform) {
+            platforms.push(platform);
+            outputs.push_str(&format!("{}={}\n", platform, path.display()));
+            if let Some(sha256) = sha256 {
+                outputs.push_str(&format!("{}-sha256={}\n", platform, sha256));
+            }
+        }
+    }
+    outputs.push_str(&multiline("artifacts", &paths));
+    outputs.push_str(&multiline("checksums", &checksums));
+    Ok(outputs)
+}
+
+/// Multi-line output using the heredoc-style delimiter syntax
+fn multiline(name: &str, value: &str) -> String {
+    let delimiter = format!("ghadelimiter_{}", uuid::Uuid::new_v4().simple());
+    format!("{}<<{}\n{}{}\n", name, delimiter, value, delimiter)
+}
+
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Formats a workflow command, escaping its property and message
+fn workflow_command(level: &str, title: &str, message: &str) -> String {
+    let escape_data = |s: &str| {
+        s.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    };
+    let title = escape_data(title).replace(':', "%3A").replace(',', "%2C");
+    format!("::{} title={}::{}", level, title, escape_data(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_command() {
+        assert_eq!(
+            workflow_command("error", "deb: build, failed", "line 1\nline 2 100%"),
+            "::error title=deb%3A build%2C failed::line 1%0Aline 2 100%25"
+        );
+    }
+
+    #[test]
+    fn test_render_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let deb = dir.path().join("kodegen.deb");
+        std::fs::write(&deb, b"deb").unwrap();
+        let sha256 = hex::encode(Sha256::digest(b"deb"));
+
+        let outputs = render_outputs(Some("1.2.3"), &[("deb", &deb)]).unwrap();
+        let lines: Vec<&str> = outputs.lines().collect();
+        assert_eq!(lines[0], "version=1.2.3");
+        assert_eq!(lines[1], format!("deb={}", deb.display()));
+        assert_eq!(lines[2], format!("deb-sha256={}", sha256));
+
+        let delimiter = lines[3].strip_prefix("artifacts<<").unwrap();
+        assert_eq!(lines[4], deb.display().to_string());
+        assert_eq!(lines[5], delimiter);
+        assert!(lines[6].starts_with("checksums<<"));
+        assert_eq!(lines[7], format!("{}  {}", sha256, deb.display()));
+    }
+}
@@ -0,0 +1,72 @@
+//! Diagnostic logging via `tracing`.
+//!
+//! Log records go to stderr and are filtered with `RUST_LOG` (errors only by
+//! default). Bundling phases run in spans named `clone`, `build`, `bundle`,
+//! `sign` and `verify`; at `RUST_LOG=info` each span logs its busy and idle
+//! time when it closes, so stages can be timed:
+//!
+//! ```text
+//! RUST_LOG=info kodegen_bundler_bundle --log-format json --source . --platform deb -o app.deb
+//! ```
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Log record format (`--log-format`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, including the enclosing spans
+    Json,
+}
+
+impl LogFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+static FORMAT: OnceLock<LogFormat> = OnceLock::new();
+
+/// Installs the global subscriber; later calls have no effect
+pub fn init(format: LogFormat) {
+    if FORMAT.set(format).is_err() {
+        return;
+    }
+
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::ERROR.into())
+        .from_env_lossy();
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_span_events(FmtSpan::CLOSE);
+
+    let result = match format {
+        LogFormat::Text => builder
+            .with_ansi(std::io::stderr().is_terminal())
+            .try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to initialize logging: {}", e);
+    }
+}
+
+/// `--log-format` flag reproducing the current format in a child bundler
+pub fn passthrough_arg() -> String {
+    let format = FORMAT.get().copied().unwrap_or_default();
+    format!("--log-format={}", format.as_str())
+}
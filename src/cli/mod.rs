@@ -6,26 +6,100 @@
 mod args;
 pub mod commands;
 mod docker;
+pub mod github_actions;
+mod logging;
 mod output;
+#[cfg(feature = "otlp")]
+pub mod telemetry;
 
-pub use args::{Args, RuntimeConfig};
-pub use output::OutputManager;
+pub use args::{
+    Args, ArtifactOutput, CleanArgs, Cli, Command, GcArgs, InstallTestArgs, RepoArgs, ReproCheckArgs,
+    RuntimeConfig, ValidateArgs, VerifyLinksArgs,
+};
+pub use output::{ColorWhen, OutputManager};
 
 use crate::error::Result;
 
 /// Main CLI entry point
 pub async fn run() -> Result<i32> {
-    let args = Args::parse_args();
-    let runtime_config = RuntimeConfig::from(&args);
+    let cli = Cli::parse_args();
+    logging::init(cli.log_format);
+
+    let args = match (cli.command, cli.bundle) {
+        (Some(Command::Gc(gc_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_gc(gc_args, runtime_config).await;
+        }
+        (Some(Command::Clean(clean_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_clean(clean_args, runtime_config).await;
+        }
+        (Some(Command::VerifyLinks(verify_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_verify_links(verify_args, runtime_config).await;
+        }
+        (Some(Command::Repo(repo_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_repo(repo_args, runtime_config).await;
+        }
+        (Some(Command::ReproCheck(repro_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_repro_check(repro_args, runtime_config).await;
+        }
+        (Some(Command::Validate(validate_args)), _) => {
+            let runtime_config = RuntimeConfig::with_color(cli.color);
+            return commands::execute_validate(validate_args, runtime_config).await;
+        }
+        (Some(Command::InstallTest(install_test_args)), _) => {
+            // Progress output would interleave with the JSON report on stdout
+            let runtime_config = if install_test_args.json {
+                RuntimeConfig::quiet(cli.color)
+            } else {
+                RuntimeConfig::with_color(cli.color)
+            };
+            return commands::execute_install_test(install_test_args, runtime_config).await;
+        }
+        (None, Some(args)) => args,
+        // clap enforces the bundle arguments when no subcommand is given
+        (None, None) => unreachable!("bundle arguments are required without a subcommand"),
+    };
+    let runtime_config = RuntimeConfig::with_color(cli.color);
+
+    #[cfg(feature = "otlp")]
+    let telemetry = args
+        .otlp_endpoint
+        .as_deref()
+        .map(|endpoint| {
+            let platform = args.platform.clone().unwrap_or_else(|| "all".to_string());
+            (telemetry::OtlpExporter::new(endpoint), platform)
+        });
+    #[cfg(feature = "otlp")]
+    let started = std::time::SystemTime::now();
 
     // Execute the bundle command
-    commands::execute_command(args, runtime_config).await
+    let result = commands::execute_command(args, runtime_config).await;
+    if let Err(e) = &result {
+        github_actions::error("Bundling failed", &e.to_string());
+    }
+
+    #[cfg(feature = "otlp")]
+    if let Some((exporter, platform)) = telemetry {
+        let report = telemetry::RunReport {
+            platform,
+            started,
+            finished: std::time::SystemTime::now(),
+            success: matches!(result, Ok(0)),
+        };
+        exporter.export(&report).await;
+    }
+
+    result
 }
 
 /// Parse arguments without executing (for testing)
 #[allow(dead_code)] // Public API - preserved for external consumers
-pub fn parse_args() -> Args {
-    Args::parse_args()
+pub fn parse_args() -> Cli {
+    Cli::parse_args()
 }
 
 /// Validate arguments without executing (for testing)
@@ -34,8 +108,8 @@ pub fn validate_args(args: &Args) -> std::result::Result<(), String> {
     args.validate()
 }
 
-/// Create runtime configuration from arguments
+/// Create runtime configuration for the given color preference
 #[allow(dead_code)] // Public API - preserved for external consumers
-pub fn create_runtime_config(args: &Args) -> RuntimeConfig {
-    RuntimeConfig::from(args)
+pub fn create_runtime_config(color: ColorWhen) -> RuntimeConfig {
+    RuntimeConfig::with_color(color)
 }
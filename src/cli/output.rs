@@ -4,32 +4,87 @@
 //!
 //! Provides consistent, colored CLI output with proper formatting
 
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
+/// When to emit ANSI color codes (`--color`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorWhen {
+    /// Color if the stream is a terminal and the environment allows it
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorWhen {
+    /// Resolve to a concrete color choice for a stream
+    ///
+    /// `Auto` follows the common conventions, in order of precedence:
+    /// - `NO_COLOR` (non-empty) disables color
+    /// - `CLICOLOR_FORCE` (non-empty, not "0") forces color
+    /// - `CLICOLOR=0` disables color
+    /// - `TERM=dumb` disables color
+    /// - otherwise color only if the stream is a terminal
+    pub fn resolve(self, is_terminal: bool) -> ColorChoice {
+        self.resolve_with(is_terminal, |name| {
+            std::env::var_os(name).map(|v| v.to_string_lossy().into_owned())
+        })
+    }
+
+    /// [`resolve`](Self::resolve) with the environment looked up through `var`
+    fn resolve_with(self, is_terminal: bool, var: impl Fn(&str) -> Option<String>) -> ColorChoice {
+        match self {
+            ColorWhen::Always => ColorChoice::Always,
+            ColorWhen::Never => ColorChoice::Never,
+            ColorWhen::Auto => {
+                let env_set = |name: &str| var(name).filter(|v| !v.is_empty());
+
+                if env_set("NO_COLOR").is_some() {
+                    ColorChoice::Never
+                } else if env_set("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+                    ColorChoice::Always
+                } else if env_set("CLICOLOR").is_some_and(|v| v == "0")
+                    || env_set("TERM").is_some_and(|v| v == "dumb")
+                    || !is_terminal
+                {
+                    ColorChoice::Never
+                } else {
+                    ColorChoice::Auto
+                }
+            }
+        }
+    }
+}
+
 /// Output manager for consistent colored terminal output
 #[derive(Debug)]
 pub struct OutputManager {
     bufwtr: BufferWriter,
+    color: ColorWhen,
     verbose: bool,
     quiet: bool,
 }
 
 impl Clone for OutputManager {
     fn clone(&self) -> Self {
-        Self {
-            bufwtr: BufferWriter::stdout(ColorChoice::Auto),
-            verbose: self.verbose,
-            quiet: self.quiet,
-        }
+        Self::with_color(self.verbose, self.quiet, self.color)
     }
 }
 
 impl OutputManager {
     /// Create a new output manager
     pub fn new(verbose: bool, quiet: bool) -> Self {
+        Self::with_color(verbose, quiet, ColorWhen::Auto)
+    }
+
+    /// Create a new output manager with an explicit color mode
+    pub fn with_color(verbose: bool, quiet: bool, color: ColorWhen) -> Self {
         Self {
-            bufwtr: BufferWriter::stdout(ColorChoice::Auto),
+            bufwtr: BufferWriter::stdout(color.resolve(std::io::stdout().is_terminal())),
+            color,
             verbose,
             quiet,
         }
@@ -81,7 +136,7 @@ impl OutputManager {
 
     /// Print an error message (always shown)
     pub fn error(&self, message: &str) {
-        let bufwtr = BufferWriter::stderr(ColorChoice::Auto);
+        let bufwtr = BufferWriter::stderr(self.color.resolve(std::io::stderr().is_terminal()));
         let mut buffer = bufwtr.buffer();
 
         // Try colored output to stderr
@@ -173,8 +228,58 @@ impl OutputManager {
         self.verbose
     }
 
+    /// Returns the configured color mode
+    pub fn color(&self) -> ColorWhen {
+        self.color
+    }
+
     /// Check if quiet mode is enabled
     pub fn is_quiet(&self) -> bool {
         self.quiet
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_color_ignores_terminal() {
+        assert_eq!(ColorWhen::Always.resolve(false), ColorChoice::Always);
+        assert_eq!(ColorWhen::Never.resolve(true), ColorChoice::Never);
+    }
+
+    /// Environment variables as name-value pairs
+    type Env = &'static [(&'static str, &'static str)];
+
+    #[test]
+    fn test_auto_honors_environment() {
+        let cases: [(Env, bool, ColorChoice); 11] = [
+            (&[], true, ColorChoice::Auto),
+            (&[], false, ColorChoice::Never),
+            (&[("NO_COLOR", "1")], true, ColorChoice::Never),
+            (&[("NO_COLOR", "")], true, ColorChoice::Auto),
+            (&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true, ColorChoice::Never),
+            (&[("CLICOLOR_FORCE", "1")], false, ColorChoice::Always),
+            (&[("CLICOLOR_FORCE", "0")], false, ColorChoice::Never),
+            (&[("CLICOLOR_FORCE", "1"), ("TERM", "dumb")], true, ColorChoice::Always),
+            (&[("CLICOLOR", "0")], true, ColorChoice::Never),
+            (&[("CLICOLOR", "1")], true, ColorChoice::Auto),
+            (&[("TERM", "dumb")], true, ColorChoice::Never),
+        ];
+        for (env, is_terminal, expected) in cases {
+            let var = |name: &str| {
+                env.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            };
+            assert_eq!(
+                ColorWhen::Auto.resolve_with(is_terminal, var),
+                expected,
+                "{:?}, terminal: {}",
+                env,
+                is_terminal
+            );
+        }
+    }
+}
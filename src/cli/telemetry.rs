@@ -0,0 +1,292 @@
+//! OTLP metric and trace export for fleet usage reporting.
+//!
+//! Enabled with the `otlp` cargo feature and activated at runtime by
+//! `--otlp-endpoint` (or `OTEL_EXPORTER_OTLP_ENDPOINT`). Each bundler run
+//! exports one batch over OTLP/HTTP using the JSON encoding:
+//!
+//! - `bundler.runs` - counter of runs by platform and outcome
+//! - `bundler.run.duration` - histogram of run durations in seconds
+//! - `bundler.container.oom` - counter of Docker containers killed for memory
+//! - a single `bundle` span covering the whole run
+//!
+//! Export is best-effort: failures are logged and never change the exit code.
+
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Instrumentation scope and `service.name` reported with every batch
+const SERVICE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// Upper bound on time spent exporting so telemetry never stalls a release
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Duration histogram bucket bounds in seconds (1 minute to 1 hour)
+const DURATION_BOUNDS: [f64; 6] = [60.0, 120.0, 300.0, 600.0, 1200.0, 3600.0];
+
+/// OTLP `AGGREGATION_TEMPORALITY_DELTA`: each run reports only its own counts
+const TEMPORALITY_DELTA: u8 = 1;
+
+/// Containers killed by the OOM killer during this process
+static CONTAINER_OOMS: AtomicU64 = AtomicU64::new(0);
+
+/// Record that a bundling container was killed for exceeding its memory limit
+pub fn record_container_oom() {
+    CONTAINER_OOMS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Outcome of a single bundler run, as exported to the collector
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    /// Platform requested on the command line (e.g. "deb", "dmg")
+    pub platform: String,
+    /// Wall-clock start of the run
+    pub started: SystemTime,
+    /// Wall-clock end of the run
+    pub finished: SystemTime,
+    /// Whether the run produced its artifact
+    pub success: bool,
+}
+
+impl RunReport {
+    fn duration_secs(&self) -> f64 {
+        self.finished
+            .duration_since(self.started)
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+
+    fn outcome(&self) -> &'static str {
+        if self.success { "success" } else { "failure" }
+    }
+}
+
+/// Exports run metrics and traces to an OTLP/HTTP collector
+#[derive(Debug, Clone)]
+pub struct OtlpExporter {
+    endpoint: String,
+}
+
+impl OtlpExporter {
+    /// Create an exporter for a collector base URL (e.g. `http://localhost:4318`)
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Export the metrics and span for a finished run
+    ///
+    /// Errors are logged as warnings rather than returned.
+    pub async fn export(&self, report: &RunReport) {
+        let client = match reqwest::Client::builder().timeout(EXPORT_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("OTLP export disabled: failed to create HTTP client: {}", e);
+                return;
+            }
+        };
+
+        let ooms = CONTAINER_OOMS.load(Ordering::Relaxed);
+        let batches = [
+            ("v1/metrics", metrics_payload(report, ooms)),
+            ("v1/traces", trace_payload(report)),
+        ];
+
+        for (path, body) in batches {
+            let url = format!("{}/{}", self.endpoint, path);
+            match client.post(&url).json(&body).send().await {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!("Exported OTLP batch to {}", url);
+                }
+                Ok(response) => {
+                    tracing::warn!("OTLP collector rejected {}: HTTP {}", url, response.status());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to export OTLP batch to {}: {}", url, e);
+                }
+            }
+        }
+    }
+}
+
+/// Build an OTLP `ExportMetricsServiceRequest` for one run
+fn metrics_payload(report: &RunReport, container_ooms: u64) -> Value {
+    let start = unix_nanos(report.started);
+    let end = unix_nanos(report.finished);
+    let attributes = json!([
+        string_attribute("platform", &report.platform),
+        string_attribute("outcome", report.outcome()),
+    ]);
+
+    let duration = report.duration_secs();
+    let mut bucket_counts = vec!["0".to_string(); DURATION_BOUNDS.len() + 1];
+    let bucket = DURATION_BOUNDS
+        .iter()
+        .position(|bound| duration <= *bound)
+        .unwrap_or(DURATION_BOUNDS.len());
+    bucket_counts[bucket] = "1".to_string();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(),
+            "scopeMetrics": [{
+                "scope": scope(),
+                "metrics": [
+                    {
+                        "name": "bundler.runs",
+                        "description": "Bundler runs by platform and outcome",
+                        "unit": "{run}",
+                        "sum": {
+                            "aggregationTemporality": TEMPORALITY_DELTA,
+                            "isMonotonic": true,
+                            "dataPoints": [{
+                                "attributes": attributes,
+                                "startTimeUnixNano": start,
+                                "timeUnixNano": end,
+                                "asInt": "1",
+                            }],
+                        },
+                    },
+                    {
+                        "name": "bundler.run.duration",
+                        "description": "Wall-clock duration of a bundler run",
+                        "unit": "s",
+                        "histogram": {
+                            "aggregationTemporality": TEMPORALITY_DELTA,
+                            "dataPoints": [{
+                                "attributes": attributes,
+                                "startTimeUnixNano": start,
+                                "timeUnixNano": end,
+                                "count": "1",
+                                "sum": duration,
+                                "bucketCounts": bucket_counts,
+                                "explicitBounds": DURATION_BOUNDS,
+                            }],
+                        },
+                    },
+                    {
+                        "name": "bundler.container.oom",
+                        "description": "Bundling containers killed for exceeding their memory limit",
+                        "unit": "{container}",
+                        "sum": {
+                            "aggregationTemporality": TEMPORALITY_DELTA,
+                            "isMonotonic": true,
+                            "dataPoints": [{
+                                "attributes": [string_attribute("platform", &report.platform)],
+                                "startTimeUnixNano": start,
+                                "timeUnixNano": end,
+                                "asInt": container_ooms.to_string(),
+                            }],
+                        },
+                    },
+                ],
+            }],
+        }],
+    })
+}
+
+/// Build an OTLP `ExportTraceServiceRequest` with a single span for the run
+fn trace_payload(report: &RunReport) -> Value {
+    let trace_id = uuid::Uuid::new_v4().simple().to_string();
+    let span_id = uuid::Uuid::new_v4().simple().to_string()[..16].to_string();
+
+    // STATUS_CODE_OK = 1, STATUS_CODE_ERROR = 2
+    let status_code = if report.success { 1 } else { 2 };
+
+    json!({
+        "resourceSpans": [{
+            "resource": resource(),
+            "scopeSpans": [{
+                "scope": scope(),
+                "spans": [{
+                    "traceId": trace_id,
+                    "spanId": span_id,
+                    "name": "bundle",
+                    "kind": 1,
+                    "startTimeUnixNano": unix_nanos(report.started),
+                    "endTimeUnixNano": unix_nanos(report.finished),
+                    "attributes": [
+                        string_attribute("platform", &report.platform),
+                        string_attribute("outcome", report.outcome()),
+                    ],
+                    "status": { "code": status_code },
+                }],
+            }],
+        }],
+    })
+}
+
+fn resource() -> Value {
+    json!({
+        "attributes": [
+            string_attribute("service.name", SERVICE_NAME),
+            string_attribute("service.version", env!("CARGO_PKG_VERSION")),
+            string_attribute("host.arch", std::env::consts::ARCH),
+            string_attribute("os.type", std::env::consts::OS),
+        ],
+    })
+}
+
+fn scope() -> Value {
+    json!({ "name": SERVICE_NAME, "version": env!("CARGO_PKG_VERSION") })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP JSON encodes 64-bit integers as decimal strings
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(duration_secs: u64, success: bool) -> RunReport {
+        let started = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        RunReport {
+            platform: "deb".to_string(),
+            started,
+            finished: started + Duration::from_secs(duration_secs),
+            success,
+        }
+    }
+
+    #[test]
+    fn test_metrics_payload_buckets_duration() {
+        let payload = metrics_payload(&report(150, false), 2);
+        let metrics = &payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+
+        let runs = &metrics[0]["sum"]["dataPoints"][0];
+        assert_eq!(runs["attributes"][1]["value"]["stringValue"], "failure");
+
+        let histogram = &metrics[1]["histogram"]["dataPoints"][0];
+        assert_eq!(histogram["bucketCounts"][2], "1");
+        assert_eq!(histogram["sum"], 150.0);
+
+        assert_eq!(metrics[2]["sum"]["dataPoints"][0]["asInt"], "2");
+    }
+
+    #[test]
+    fn test_trace_payload_ids() {
+        let payload = trace_payload(&report(10, true));
+        let span = &payload["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(span["status"]["code"], 1);
+        assert_eq!(span["startTimeUnixNano"], "1700000000000000000");
+    }
+
+    #[test]
+    fn test_exporter_trims_trailing_slash() {
+        let exporter = OtlpExporter::new("http://localhost:4318/");
+        assert_eq!(exporter.endpoint, "http://localhost:4318");
+    }
+}
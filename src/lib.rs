@@ -0,0 +1,7 @@
+//! Kodegen Bundler Bundle - platform package bundling as a library.
+//!
+//! The [`bundler`] module is the API the `kodegen_bundler_bundle` binary is
+//! built on. Downstream crates use it to bundle programmatically and to add
+//! package formats with [`bundler::PlatformBundler`].
+
+pub mod bundler;
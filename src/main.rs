@@ -3,7 +3,7 @@
 //! This binary creates platform-specific packages (.deb, .rpm, .dmg, .msi, AppImage)
 //! from Rust binaries with proper error handling and artifact verification.
 
-mod bundler;
+use kodegen_bundler_release::bundler;
 mod cli;
 mod error;
 mod metadata;
@@ -13,9 +13,8 @@ use std::process;
 
 #[tokio::main]
 async fn main() {
-    // Initialize logging
-    env_logger::init();
-
+    // Logging is initialized by the CLI once --log-format is parsed
+    bundler::utils::interrupt::install();
     // Run CLI and get exit code
     let exit_code = match cli::run().await {
         Ok(code) => code,
@@ -0,0 +1,151 @@
+//! Compatibility with classic cargo-bundle metadata.
+//!
+//! cargo-bundle keeps every setting flat in `[package.metadata.bundle]`
+//! (`deb_depends`, `osx_frameworks`, ...) and takes the category as a single
+//! name. [`migrate`] moves those keys to the sections they correspond to
+//! here before the settings are deserialized, so crates bundled with
+//! cargo-bundle work unchanged. Keys already set in their section here win.
+
+use super::tauri;
+use toml::{Table, Value};
+
+/// Flat cargo-bundle keys and the (section, key) they move to
+const MOVED_KEYS: [(&str, &str, &str); 5] = [
+    ("deb_depends", "deb", "depends"),
+    ("osx_frameworks", "macos", "frameworks"),
+    ("osx_minimum_system_version", "macos", "minimum_system_version"),
+    ("linux_mime_types", "desktop", "mime_types"),
+    ("linux_use_terminal", "desktop", "terminal"),
+];
+
+/// Package name and version set by cargo-bundle's `name` and `version` keys
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PackageOverrides {
+    /// Name instead of the package name
+    pub name: Option<String>,
+
+    /// Version instead of the package version
+    pub version: Option<String>,
+}
+
+/// Rewrites the cargo-bundle keys of a `[package.metadata.bundle]` table in
+/// place, returning the name and version it overrides
+///
+/// The name becomes the package name of every artifact, so like
+/// `--product-name` it is rejected if it is empty or contains spaces or '/'.
+pub fn migrate(bundle: &mut Table) -> Result<PackageOverrides, String> {
+    for (legacy, section, key) in MOVED_KEYS {
+        let Some(value) = bundle.remove(legacy) else {
+            continue;
+        };
+        let Some(section) = bundle
+            .entry(section)
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+        else {
+            continue;
+        };
+        if section.contains_key(key) {
+            tracing::warn!("Ignoring cargo-bundle key `{}`: `{}` is also set", legacy, key);
+        } else {
+            section.insert(key.to_string(), value);
+        }
+    }
+
+    // `osx_url_schemes = ["myapp"]` is a single URL type
+    if let Some(schemes) = bundle.remove("osx_url_schemes") {
+        let mut url_type = Table::new();
+        url_type.insert("schemes".to_string(), schemes);
+        let url_schemes = bundle
+            .entry("url_schemes")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Some(url_schemes) = url_schemes.as_array_mut() {
+            url_schemes.push(Value::Table(url_type));
+        }
+    }
+
+    // `category = "Developer Tool"` names one category for every platform
+    if let Some(Value::String(name)) = bundle.get("category") {
+        match tauri::category(name) {
+            Some((linux, macos)) => {
+                let mut category = Table::new();
+                category.insert("linux".to_string(), Value::String(linux.to_string()));
+                category.insert("macos".to_string(), Value::String(macos));
+                bundle.insert("category".to_string(), Value::Table(category));
+            }
+            None => {
+                tracing::warn!("Ignoring unknown cargo-bundle category {:?}", name);
+                bundle.remove("category");
+            }
+        }
+    }
+
+    let mut take_string = |key: &str| match bundle.remove(key) {
+        Some(Value::String(value)) => Some(value),
+        _ => None,
+    };
+    let overrides = PackageOverrides {
+        name: take_string("name"),
+        version: take_string("version"),
+    };
+    if let Some(name) = overrides
+        .name
+        .as_deref()
+        .filter(|name| name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '/'))
+    {
+        return Err(format!(
+            "cargo-bundle `name` must be non-empty without spaces or '/': {:?} \
+             (it replaces the package name; use `--product-name` or remove it)",
+            name
+        ));
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::BundleSettings;
+
+    #[test]
+    fn test_migrate() {
+        let mut bundle: Table = toml::from_str(
+            r#"
+            name = "example-app"
+            identifier = "com.example.app"
+            category = "Developer Tool"
+            deb_depends = ["libgtk-3-0"]
+            osx_frameworks = ["SDL2"]
+            osx_minimum_system_version = "10.13"
+            osx_url_schemes = ["example"]
+            linux_use_terminal = true
+
+            [macos]
+            minimum_system_version = "11.0"
+            "#,
+        )
+        .unwrap();
+
+        let overrides = migrate(&mut bundle).unwrap();
+        assert_eq!(overrides.name.as_deref(), Some("example-app"));
+        assert_eq!(overrides.version, None);
+
+        let settings: BundleSettings = Value::Table(bundle).try_into().unwrap();
+        assert_eq!(settings.deb.depends, Some(vec!["libgtk-3-0".to_string()]));
+        assert_eq!(settings.macos.frameworks, Some(vec!["SDL2".to_string()]));
+        // The section's own key wins
+        assert_eq!(settings.macos.minimum_system_version.as_deref(), Some("11.0"));
+        assert_eq!(settings.url_schemes[0].schemes, vec!["example".to_string()]);
+        assert!(settings.desktop.terminal);
+        let category = settings.category.unwrap();
+        assert_eq!(category.linux.as_deref(), Some("Development"));
+        assert_eq!(category.macos.as_deref(), Some("public.app-category.developer-tools"));
+    }
+
+    #[test]
+    fn test_migrate_rejects_name_with_space() {
+        let mut bundle: Table = toml::from_str(r#"name = "Example App""#).unwrap();
+        let err = migrate(&mut bundle).unwrap_err();
+        assert!(err.contains("\"Example App\""), "{}", err);
+    }
+}
@@ -1,8 +1,14 @@
 //! Metadata and binary discovery from single Cargo.toml
 
+mod cargo_bundle;
+mod tauri;
+mod unknown_keys;
+
+pub use unknown_keys::UnknownKey;
+
 use crate::bundler::BundleSettings;
 use crate::error::{BundlerError, CliError, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Package metadata extracted from Cargo.toml
 #[allow(dead_code)] // Public API - preserved for external consumers
@@ -22,6 +28,9 @@ pub struct PackageMetadata {
     /// SPDX license identifier (e.g., "Apache-2.0 OR MIT")
     pub license: Option<String>,
 
+    /// License texts: `license-file`, or LICENSE*/COPYING* next to Cargo.toml
+    pub license_files: Vec<PathBuf>,
+
     /// Homepage URL if specified in Cargo.toml
     pub homepage: Option<String>,
 
@@ -40,6 +49,9 @@ pub struct CargoManifest {
 
     /// Bundle settings (from [package.metadata.bundle] section + asset discovery)
     pub bundle_settings: BundleSettings,
+
+    /// Keys in [package.metadata.bundle] that no setting accepts (likely typos)
+    pub unknown_keys: Vec<UnknownKey>,
 }
 
 /// Load complete manifest from Cargo.toml (single read + parse)
@@ -64,13 +76,25 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
     })?;
 
     // Step 2: Parse TOML once
-    let toml_value: toml::Value = toml::from_str(&manifest).map_err(|e| {
+    let mut toml_value: toml::Value = toml::from_str(&manifest).map_err(|e| {
         BundlerError::Cli(CliError::ExecutionFailed {
             command: "parse_cargo_toml".to_string(),
             reason: format!("Failed to parse Cargo.toml: {}", e),
         })
     })?;
 
+    // Rewrite classic cargo-bundle keys to their sections
+    let overrides = match toml_value
+        .get_mut("package")
+        .and_then(|p| p.get_mut("metadata"))
+        .and_then(|m| m.get_mut("bundle"))
+        .and_then(|b| b.as_table_mut())
+    {
+        Some(bundle) => cargo_bundle::migrate(bundle)
+            .map_err(|reason| BundlerError::Cli(CliError::InvalidArguments { reason }))?,
+        None => cargo_bundle::PackageOverrides::default(),
+    };
+
     let package = toml_value.get("package").ok_or_else(|| {
         BundlerError::Cli(CliError::InvalidArguments {
             reason: "No [package] section in Cargo.toml".to_string(),
@@ -78,7 +102,7 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
     })?;
 
     // Step 3: Extract metadata from parsed TOML (no additional I/O)
-    let metadata = PackageMetadata {
+    let mut metadata = PackageMetadata {
         name: package
             .get("name")
             .and_then(|v| v.as_str())
@@ -120,6 +144,8 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
             .and_then(|v| v.as_str())
             .map(String::from),
 
+        license_files: Vec::new(),
+
         homepage: package
             .get("homepage")
             .and_then(|v| v.as_str())
@@ -131,6 +157,13 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
             .map(String::from),
     };
 
+    if let Some(name) = overrides.name {
+        metadata.name = name;
+    }
+    if let Some(version) = overrides.version {
+        metadata.version = version;
+    }
+
     // Step 4: Discover binary name from parsed TOML (no additional I/O)
     // Try [[bin]] section first
     let binary_name = toml_value
@@ -160,7 +193,15 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
         })
     })?;
 
-    let mut bundle_settings = parse_bundle_settings(&toml_value)?;
+    metadata.license_files = discover_license_files(package, cargo_dir)?;
+
+    let (mut bundle_settings, unknown_keys) = parse_bundle_settings(&toml_value)?;
+
+    // Projects migrating from Tauri reuse their tauri.conf.json
+    let tauri_conf = cargo_dir.join(tauri::CONFIG_FILE);
+    if !has_bundle_section(&toml_value) && tauri_conf.exists() {
+        bundle_settings = tauri::import(&tauri_conf)?;
+    }
 
     // Step 6: Discover assets from conventional location
     discover_bundle_assets(cargo_dir, &mut bundle_settings)?;
@@ -169,14 +210,59 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<CargoManifest> {
         metadata,
         binary_name,
         bundle_settings,
+        unknown_keys,
     })
 }
 
+/// License texts of the package
+///
+/// `license-file` wins when set. Otherwise every LICENSE*, LICENCE* and
+/// COPYING* file next to Cargo.toml is used, which covers the usual
+/// `LICENSE-MIT` + `LICENSE-APACHE` pair of dual-licensed crates.
+fn discover_license_files(package: &toml::Value, cargo_dir: &Path) -> Result<Vec<PathBuf>> {
+    if let Some(license_file) = package.get("license-file").and_then(|v| v.as_str()) {
+        let path = cargo_dir.join(license_file);
+        if !path.is_file() {
+            return Err(BundlerError::Cli(CliError::InvalidArguments {
+                reason: format!("license-file {} does not exist", path.display()),
+            }));
+        }
+        return Ok(vec![path]);
+    }
+
+    let Ok(entries) = std::fs::read_dir(cargo_dir) else {
+        return Ok(Vec::new());
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_uppercase())
+                .unwrap_or_default();
+            path.is_file() && ["LICENSE", "LICENCE", "COPYING"].iter().any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Whether Cargo.toml has a [package.metadata.bundle] section
+fn has_bundle_section(toml_value: &toml::Value) -> bool {
+    toml_value
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("bundle"))
+        .is_some()
+}
+
 /// Parse bundle settings from [package.metadata.bundle] section
 ///
 /// Extracts configuration for platform-specific bundling including required
-/// bundle identifier for macOS.
-fn parse_bundle_settings(toml_value: &toml::Value) -> Result<BundleSettings> {
+/// bundle identifier for macOS. Unknown keys are ignored, but returned and
+/// logged as warnings with the closest known key.
+fn parse_bundle_settings(toml_value: &toml::Value) -> Result<(BundleSettings, Vec<UnknownKey>)> {
     // Extract the [package.metadata.bundle] section
     let bundle_value = toml_value
         .get("package")
@@ -185,42 +271,43 @@ fn parse_bundle_settings(toml_value: &toml::Value) -> Result<BundleSettings> {
 
     // If no bundle metadata, return defaults (this is valid - not all packages need bundling)
     let Some(bundle_value) = bundle_value else {
-        log::debug!("No [package.metadata.bundle] section found, using defaults");
-        return Ok(BundleSettings::default());
+        tracing::debug!("No [package.metadata.bundle] section found, using defaults");
+        return Ok((BundleSettings::default(), Vec::new()));
     };
 
-    // Use TOML crate's native deserialization via try_into()
-    // This automatically parses ALL fields including nested platform sections
-    let settings: BundleSettings =
-        bundle_value
-            .clone()
-            .try_into()
-            .map_err(|e: toml::de::Error| {
+    // Serde deserialization parses ALL fields including nested platform
+    // sections, noting the keys none of them claimed
+    let (settings, unknown_keys): (BundleSettings, _) =
+        unknown_keys::deserialize(bundle_value.clone(), "package.metadata.bundle")
+            .map_err(|e| {
                 BundlerError::Cli(CliError::InvalidArguments {
                     reason: format!("Failed to parse [package.metadata.bundle] settings: {}", e),
                 })
             })?;
+    for key in &unknown_keys {
+        tracing::warn!("Cargo.toml: {}", key);
+    }
 
     // Optional: Debug logging to verify parsing
-    log::debug!("Parsed bundle settings:");
-    log::debug!("  identifier: {:?}", settings.identifier);
-    log::debug!("  publisher: {:?}", settings.publisher);
-    log::debug!("  debian depends: {:?}", settings.deb.depends);
-    log::debug!("  debian files: {:?}", settings.deb.files);
-    log::debug!("  rpm depends: {:?}", settings.rpm.depends);
-    log::debug!("  rpm release: {}", settings.rpm.release);
-    log::debug!(
+    tracing::debug!("Parsed bundle settings:");
+    tracing::debug!("  identifier: {:?}", settings.identifier);
+    tracing::debug!("  publisher: {:?}", settings.publisher);
+    tracing::debug!("  debian depends: {:?}", settings.deb.depends);
+    tracing::debug!("  debian files: {:?}", settings.deb.files);
+    tracing::debug!("  rpm depends: {:?}", settings.rpm.depends);
+    tracing::debug!("  rpm release: {}", settings.rpm.release);
+    tracing::debug!(
         "  macos signing_identity: {:?}",
         settings.macos.signing_identity
     );
-    log::debug!("  macos entitlements: {:?}", settings.macos.entitlements);
-    log::debug!("  windows cert_path: {:?}", settings.windows.cert_path);
-    log::debug!(
+    tracing::debug!("  macos entitlements: {:?}", settings.macos.entitlements);
+    tracing::debug!("  windows cert_path: {:?}", settings.windows.cert_path);
+    tracing::debug!(
         "  nsis install_mode: {:?}",
         settings.windows.nsis.install_mode
     );
 
-    Ok(settings)
+    Ok((settings, unknown_keys))
 }
 
 /// Discover bundle assets from conventional directory structure
@@ -229,6 +316,7 @@ fn parse_bundle_settings(toml_value: &toml::Value) -> Result<BundleSettings> {
 /// - icon.icns (macOS)
 /// - icon.ico (Windows)
 /// - icon_*x*.png (Linux - multiple sizes including @2x variants)
+/// - icon.svg (rasterized into every size at bundle time)
 ///
 /// Files are only added if they exist. Platform-specific bundlers will
 /// error if their required icon is missing.
@@ -236,8 +324,8 @@ fn discover_bundle_assets(package_root: &Path, settings: &mut BundleSettings) ->
     let assets_dir = package_root.join("assets").join("img");
 
     if !assets_dir.exists() {
-        log::warn!("Assets directory not found: {}", assets_dir.display());
-        log::warn!("Expected platform-specific icons in assets/img/");
+        tracing::warn!("Assets directory not found: {}", assets_dir.display());
+        tracing::warn!("Expected platform-specific icons in assets/img/");
         return Ok(());
     }
 
@@ -246,18 +334,25 @@ fn discover_bundle_assets(package_root: &Path, settings: &mut BundleSettings) ->
     // Check for pre-made platform-specific icons
     let icns_path = assets_dir.join("icon.icns");
     if icns_path.exists() {
-        log::info!("Found pre-made macOS icon: {}", icns_path.display());
+        tracing::info!("Found pre-made macOS icon: {}", icns_path.display());
         settings.icns = Some(icns_path);
     }
 
     let ico_path = assets_dir.join("icon.ico");
     if ico_path.exists() {
-        log::info!("Found pre-made Windows icon: {}", ico_path.display());
+        tracing::info!("Found pre-made Windows icon: {}", ico_path.display());
         settings.ico = Some(ico_path.clone());
         // Also set NSIS installer icon
         settings.windows.nsis.installer_icon = Some(ico_path);
     }
 
+    // Scalable source, rendered into every PNG size by the bundler
+    let svg_path = assets_dir.join("icon.svg");
+    if svg_path.exists() {
+        tracing::info!("Found SVG icon: {}", svg_path.display());
+        icons.push(svg_path);
+    }
+
     // Linux PNG icons (multiple sizes + @2x variants)
     let linux_icon_sizes = [
         "icon_16x16.png",
@@ -276,33 +371,36 @@ fn discover_bundle_assets(package_root: &Path, settings: &mut BundleSettings) ->
     for filename in linux_icon_sizes {
         let icon_path = assets_dir.join(filename);
         if icon_path.exists() {
-            log::debug!("Found Linux icon: {}", filename);
+            tracing::debug!("Found Linux icon: {}", filename);
             icons.push(icon_path);
             linux_icons_found += 1;
         }
     }
 
     if linux_icons_found > 0 {
-        log::info!("Found {} Linux PNG icons", linux_icons_found);
+        tracing::info!("Found {} Linux PNG icons", linux_icons_found);
     } else {
-        log::debug!("No Linux PNG icons found");
+        tracing::debug!("No Linux PNG icons found");
     }
 
     if !icons.is_empty() {
         let icon_count = icons.len();
         settings.icon = Some(icons);
-        log::info!("Discovered {} total icon files", icon_count);
+        tracing::info!("Discovered {} total icon files", icon_count);
+        if settings.icns.is_none() {
+            tracing::info!("No icon.icns; the macOS icon will be generated from the PNG icons");
+        }
     } else {
-        log::warn!("No icon files found in assets/img/");
+        tracing::warn!("No icon files found in assets/img/");
     }
 
     // Discover entitlements.plist from conventional location
     let entitlements_path = package_root.join("assets").join("entitlements.plist");
     if entitlements_path.exists() {
-        log::info!("Found entitlements: {}", entitlements_path.display());
+        tracing::info!("Found entitlements: {}", entitlements_path.display());
         settings.macos.entitlements = Some(entitlements_path);
     } else {
-        log::debug!("No entitlements file found at assets/entitlements.plist");
+        tracing::debug!("No entitlements file found at assets/entitlements.plist");
     }
 
     Ok(())
@@ -0,0 +1,559 @@
+//! Bundle settings imported from a Tauri project.
+//!
+//! Projects migrating from Tauri's bundler keep their `tauri.conf.json`: when
+//! Cargo.toml has no `[package.metadata.bundle]` section, the bundle section
+//! of a `tauri.conf.json` next to it is mapped onto [`BundleSettings`]. Both
+//! the Tauri 2 layout (`bundle`, with `bundle.linux.deb` etc.) and the Tauri 1
+//! layout (`tauri.bundle`, with `bundle.deb` etc.) are read. Relative paths
+//! are resolved against the directory of the config file, as Tauri does.
+//!
+//! Settings without an equivalent here (updater, webview installation,
+//! certificate thumbprints) are ignored.
+
+use crate::bundler::BundleSettings;
+use crate::error::{BundlerError, CliError, Result};
+use serde_json::{Map, Value, json};
+use std::path::Path;
+
+/// File name of the Tauri configuration
+pub const CONFIG_FILE: &str = "tauri.conf.json";
+
+/// Tauri and cargo-bundle app categories as (normalized name, freedesktop
+/// category, macOS LSApplicationCategoryType suffix)
+const CATEGORIES: [(&str, &str, &str); 21] = [
+    ("business", "Office", "business"),
+    ("developertool", "Development", "developer-tools"),
+    ("education", "Education", "education"),
+    ("entertainment", "AudioVideo", "entertainment"),
+    ("finance", "Office", "finance"),
+    ("game", "Game", "games"),
+    ("graphicsanddesign", "Graphics", "graphics-design"),
+    ("healthcareandfitness", "Utility", "healthcare-fitness"),
+    ("lifestyle", "Utility", "lifestyle"),
+    ("medical", "Education", "medical"),
+    ("music", "AudioVideo", "music"),
+    ("news", "Network", "news"),
+    ("photography", "Graphics", "photography"),
+    ("productivity", "Office", "productivity"),
+    ("reference", "Education", "reference"),
+    ("socialnetworking", "Network", "social-networking"),
+    ("sports", "Game", "sports"),
+    ("travel", "Utility", "travel"),
+    ("utility", "Utility", "utilities"),
+    ("video", "AudioVideo", "video"),
+    ("weather", "Utility", "weather"),
+];
+
+/// NSIS compressors supported here, out of Tauri's
+const NSIS_COMPRESSIONS: [&str; 3] = ["none", "zlib", "lzma"];
+
+/// Reads the bundle settings from the Tauri config at `path`
+pub fn import(path: &Path) -> Result<BundleSettings> {
+    let import_error = |reason: String| {
+        BundlerError::Cli(CliError::InvalidArguments {
+            reason: format!("Failed to import {}: {}", path.display(), reason),
+        })
+    };
+
+    let contents = std::fs::read_to_string(path).map_err(|e| import_error(e.to_string()))?;
+    let conf: Value = serde_json::from_str(&contents).map_err(|e| import_error(e.to_string()))?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    tracing::info!("Importing bundle settings from {}", path.display());
+    serde_json::from_value(convert(&conf, dir)).map_err(|e| import_error(e.to_string()))
+}
+
+/// Freedesktop category and macOS LSApplicationCategoryType of an app
+/// category name such as `DeveloperTool`, `Developer Tool` or
+/// `public.app-category.developer-tools`
+pub(super) fn category(name: &str) -> Option<(&'static str, String)> {
+    let found = match name.strip_prefix("public.app-category.") {
+        Some(macos) => CATEGORIES.iter().find(|(_, _, suffix)| *suffix == macos),
+        None => {
+            let normalized = name
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_lowercase();
+            CATEGORIES.iter().find(|(key, _, _)| *key == normalized)
+        }
+    };
+    found.map(|(_, linux, macos)| (*linux, format!("public.app-category.{}", macos)))
+}
+
+/// Maps a Tauri config onto the `[package.metadata.bundle]` layout
+fn convert(conf: &Value, dir: &Path) -> Value {
+    // Tauri 1 nests the bundle section, and the identifier, under `tauri`
+    let bundle = conf
+        .get("bundle")
+        .or_else(|| conf.pointer("/tauri/bundle"))
+        .unwrap_or(&Value::Null);
+    // Tauri 2 groups the Linux formats under `linux`; Tauri 1 does not
+    let linux = |format: &str| {
+        bundle
+            .pointer(&format!("/linux/{}", format))
+            .or_else(|| bundle.get(format))
+            .unwrap_or(&Value::Null)
+    };
+    let macos = bundle.get("macOS").unwrap_or(&Value::Null);
+    let windows = bundle.get("windows").unwrap_or(&Value::Null);
+    let path = |value: &Value| value.as_str().map(|p| json!(dir.join(p)));
+
+    let mut settings = Map::new();
+    if let Some(identifier) = conf.get("identifier").or_else(|| bundle.get("identifier")) {
+        settings.insert("identifier".into(), identifier.clone());
+    }
+    copy(
+        bundle,
+        &mut settings,
+        &[
+            ("publisher", "publisher"),
+            ("copyright", "copyright"),
+            ("shortDescription", "short_description"),
+            ("longDescription", "long_description"),
+            ("externalBin", "external_bin"),
+        ],
+    );
+    match bundle.get("resources") {
+        // `{ "source": "target" }` maps resources to other locations, which
+        // are not supported here; the sources are still bundled
+        Some(Value::Object(resources)) => {
+            settings.insert(
+                "resources".into(),
+                json!(resources.keys().collect::<Vec<_>>()),
+            );
+        }
+        Some(resources) => {
+            settings.insert("resources".into(), resources.clone());
+        }
+        None => {}
+    }
+
+    let mut pngs = Vec::new();
+    for icon in bundle
+        .get("icon")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(icon) = icon.as_str() else { continue };
+        match Path::new(icon).extension().and_then(|ext| ext.to_str()) {
+            Some("icns") => {
+                settings.entry("icns").or_insert(json!(dir.join(icon)));
+            }
+            Some("ico") => {
+                settings.entry("ico").or_insert(json!(dir.join(icon)));
+            }
+            Some("png") => pngs.push(json!(dir.join(icon))),
+            _ => {}
+        }
+    }
+    if !pngs.is_empty() {
+        settings.insert("icon".into(), Value::Array(pngs));
+    }
+
+    if let Some(name) = bundle.get("category").and_then(Value::as_str) {
+        match category(name) {
+            Some((linux, macos)) => {
+                settings.insert("category".into(), json!({ "linux": linux, "macos": macos }));
+            }
+            None => tracing::warn!("Ignoring unknown Tauri category {:?}", name),
+        }
+    }
+
+    let associations: Vec<Value> = bundle
+        .get("fileAssociations")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|association| {
+            let mut converted = Map::new();
+            if let Some(ext) = association.get("ext") {
+                let ext = if ext.is_string() {
+                    json!([ext])
+                } else {
+                    ext.clone()
+                };
+                converted.insert("ext".into(), ext);
+            }
+            copy(
+                association,
+                &mut converted,
+                &[
+                    ("name", "name"),
+                    ("description", "description"),
+                    ("role", "role"),
+                    ("rank", "rank"),
+                    ("mimeType", "mime_type"),
+                    ("contentTypes", "content_types"),
+                ],
+            );
+            Value::Object(converted)
+        })
+        .collect();
+    if !associations.is_empty() {
+        settings.insert("file_associations".into(), Value::Array(associations));
+    }
+
+    // Linux
+    let scripts = [
+        ("desktopTemplate", "desktop_template"),
+        ("preInstallScript", "pre_install_script"),
+        ("postInstallScript", "post_install_script"),
+        ("preRemoveScript", "pre_remove_script"),
+        ("postRemoveScript", "post_remove_script"),
+    ];
+    let mut deb = Map::new();
+    copy(
+        linux("deb"),
+        &mut deb,
+        &[
+            ("depends", "depends"),
+            ("recommends", "recommends"),
+            ("provides", "provides"),
+            ("conflicts", "conflicts"),
+            ("replaces", "replaces"),
+            ("section", "section"),
+            ("priority", "priority"),
+        ],
+    );
+    copy_paths(linux("deb"), &mut deb, &scripts, dir);
+    copy_paths(linux("deb"), &mut deb, &[("changelog", "changelog")], dir);
+    copy_files(linux("deb"), &mut deb, dir);
+    insert_section(&mut settings, "deb", deb);
+
+    let mut rpm = Map::new();
+    copy(
+        linux("rpm"),
+        &mut rpm,
+        &[
+            ("depends", "depends"),
+            ("recommends", "recommends"),
+            ("provides", "provides"),
+            ("conflicts", "conflicts"),
+            ("obsoletes", "obsoletes"),
+            ("release", "release"),
+            ("epoch", "epoch"),
+        ],
+    );
+    copy_paths(linux("rpm"), &mut rpm, &scripts, dir);
+    copy_files(linux("rpm"), &mut rpm, dir);
+    insert_section(&mut settings, "rpm", rpm);
+
+    let mut appimage = Map::new();
+    copy(
+        linux("appimage"),
+        &mut appimage,
+        &[("bundleMediaFramework", "bundle_media_framework")],
+    );
+    copy_files(linux("appimage"), &mut appimage, dir);
+    insert_section(&mut settings, "appimage", appimage);
+
+    // macOS
+    let mut macos_settings = Map::new();
+    copy(
+        macos,
+        &mut macos_settings,
+        &[
+            ("frameworks", "frameworks"),
+            ("minimumSystemVersion", "minimum_system_version"),
+            ("signingIdentity", "signing_identity"),
+        ],
+    );
+    copy_paths(
+        macos,
+        &mut macos_settings,
+        &[("entitlements", "entitlements")],
+        dir,
+    );
+    copy_files(macos, &mut macos_settings, dir);
+    insert_section(&mut settings, "macos", macos_settings);
+
+    let dmg = macos
+        .get("dmg")
+        .or_else(|| bundle.get("dmg"))
+        .unwrap_or(&Value::Null);
+    let mut dmg_settings = Map::new();
+    copy_paths(dmg, &mut dmg_settings, &[("background", "background")], dir);
+    for (tauri_key, key, x, y) in [
+        ("windowSize", "window_size", "width", "height"),
+        ("appPosition", "app_position", "x", "y"),
+        (
+            "applicationFolderPosition",
+            "applications_position",
+            "x",
+            "y",
+        ),
+    ] {
+        if let Some(value) = dmg.get(tauri_key) {
+            dmg_settings.insert(key.into(), json!([value[x], value[y]]));
+        }
+    }
+    insert_section(&mut settings, "dmg", dmg_settings);
+
+    // Windows
+    let mut windows_settings = Map::new();
+    copy(
+        windows,
+        &mut windows_settings,
+        &[("timestampUrl", "timestamp_url")],
+    );
+    match windows.get("signCommand") {
+        Some(Value::String(command)) => {
+            windows_settings.insert("sign_command".into(), json!(command));
+        }
+        Some(Value::Object(command)) => {
+            let parts: Vec<&str> = command
+                .get("cmd")
+                .into_iter()
+                .chain(
+                    command
+                        .get("args")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten(),
+                )
+                .filter_map(Value::as_str)
+                .collect();
+            windows_settings.insert("sign_command".into(), json!(parts.join(" ")));
+        }
+        _ => {}
+    }
+
+    let nsis = windows.get("nsis").unwrap_or(&Value::Null);
+    let mut nsis_settings = Map::new();
+    copy(
+        nsis,
+        &mut nsis_settings,
+        &[("installMode", "install_mode"), ("languages", "languages")],
+    );
+    copy_paths(
+        nsis,
+        &mut nsis_settings,
+        &[
+            ("template", "template"),
+            ("headerImage", "header_image"),
+            ("sidebarImage", "sidebar_image"),
+            ("installerIcon", "installer_icon"),
+        ],
+        dir,
+    );
+    if let Some(compression) = nsis.get("compression").and_then(Value::as_str) {
+        if NSIS_COMPRESSIONS.contains(&compression) {
+            nsis_settings.insert("compression".into(), json!(compression));
+        } else {
+            tracing::warn!("Ignoring unsupported NSIS compression {:?}", compression);
+        }
+    }
+
+    let wix = windows.get("wix").unwrap_or(&Value::Null);
+    let mut wix_settings = Map::new();
+    copy(
+        wix,
+        &mut wix_settings,
+        &[
+            ("componentGroupRefs", "component_group_refs"),
+            ("componentRefs", "component_refs"),
+            ("featureGroupRefs", "feature_group_refs"),
+            ("featureRefs", "feature_refs"),
+            ("mergeRefs", "merge_refs"),
+        ],
+    );
+    copy_paths(
+        wix,
+        &mut wix_settings,
+        &[
+            ("template", "template"),
+            ("bannerPath", "banner_path"),
+            ("dialogImagePath", "dialog_image_path"),
+        ],
+        dir,
+    );
+    if let Some(fragments) = wix.get("fragmentPaths").and_then(Value::as_array) {
+        let fragments: Vec<Value> = fragments.iter().filter_map(path).collect();
+        wix_settings.insert("fragment_paths".into(), Value::Array(fragments));
+    }
+    // A language name, a list of them, or a map from name to locale settings
+    let languages: Vec<&str> = match wix.get("language") {
+        Some(Value::String(language)) => vec![language.as_str()],
+        Some(Value::Array(languages)) => languages.iter().filter_map(Value::as_str).collect(),
+        Some(Value::Object(languages)) => languages.keys().map(String::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !languages.is_empty() {
+        wix_settings.insert("language".into(), json!(languages));
+    }
+
+    if let Some(license) = bundle.get("licenseFile").and_then(path) {
+        nsis_settings.insert("license".into(), license.clone());
+        wix_settings.insert("license".into(), license);
+    }
+    insert_section(&mut windows_settings, "nsis", nsis_settings);
+    insert_section(&mut windows_settings, "wix", wix_settings);
+    insert_section(&mut settings, "windows", windows_settings);
+
+    Value::Object(settings)
+}
+
+/// Copies the `(tauri key, key)` pairs present in `from` to `to`
+fn copy(from: &Value, to: &mut Map<String, Value>, keys: &[(&str, &str)]) {
+    for (tauri_key, key) in keys {
+        if let Some(value) = from.get(tauri_key) {
+            to.insert((*key).into(), value.clone());
+        }
+    }
+}
+
+/// Copies the path-valued `(tauri key, key)` pairs present in `from` to
+/// `to`, resolved against `dir`
+fn copy_paths(from: &Value, to: &mut Map<String, Value>, keys: &[(&str, &str)], dir: &Path) {
+    for (tauri_key, key) in keys {
+        if let Some(path) = from.get(tauri_key).and_then(Value::as_str) {
+            to.insert((*key).into(), json!(dir.join(path)));
+        }
+    }
+}
+
+/// Copies a `files` map (destination to source) with sources resolved
+/// against `dir`
+fn copy_files(from: &Value, to: &mut Map<String, Value>, dir: &Path) {
+    if let Some(files) = from.get("files").and_then(Value::as_object) {
+        let files: Map<String, Value> = files
+            .iter()
+            .filter_map(|(dest, source)| Some((dest.clone(), json!(dir.join(source.as_str()?)))))
+            .collect();
+        to.insert("files".into(), Value::Object(files));
+    }
+}
+
+/// Adds `section` under `key` unless it is empty
+fn insert_section(settings: &mut Map<String, Value>, key: &str, section: Map<String, Value>) {
+    if !section.is_empty() {
+        settings.insert(key.into(), Value::Object(section));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_import_tauri_v2() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(
+            &path,
+            r#"{
+                "productName": "Example",
+                "identifier": "com.example.app",
+                "bundle": {
+                    "active": true,
+                    "icon": ["icons/32x32.png", "icons/128x128.png", "icons/icon.icns", "icons/icon.ico"],
+                    "category": "DeveloperTool",
+                    "publisher": "Example Inc.",
+                    "licenseFile": "LICENSE",
+                    "fileAssociations": [{ "ext": "exdoc", "role": "Editor", "mimeType": "text/x-exdoc" }],
+                    "linux": {
+                        "deb": { "depends": ["libwebkit2gtk-4.1-0"], "files": { "/usr/share/doc/example/README": "README.md" } },
+                        "rpm": { "release": "2", "epoch": 1 }
+                    },
+                    "macOS": {
+                        "minimumSystemVersion": "11.0",
+                        "entitlements": "Entitlements.plist",
+                        "dmg": { "windowSize": { "width": 660, "height": 400 }, "appPosition": { "x": 180, "y": 170 } }
+                    },
+                    "windows": {
+                        "signCommand": { "cmd": "trusted-signing-cli", "args": ["-e", "https://example.com", "%1"] },
+                        "nsis": { "installMode": "perMachine", "compression": "bzip2", "installerIcon": "icons/icon.ico" },
+                        "wix": { "language": { "en-US": {}, "de-DE": {} } }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let settings = import(&path).unwrap();
+        let root = dir.path();
+        assert_eq!(settings.identifier.as_deref(), Some("com.example.app"));
+        assert_eq!(settings.publisher.as_deref(), Some("Example Inc."));
+        assert_eq!(
+            settings.icon,
+            Some(vec![
+                root.join("icons/32x32.png"),
+                root.join("icons/128x128.png")
+            ])
+        );
+        assert_eq!(settings.icns, Some(root.join("icons/icon.icns")));
+        assert_eq!(settings.ico, Some(root.join("icons/icon.ico")));
+        let category = settings.category.unwrap();
+        assert_eq!(category.linux.as_deref(), Some("Development"));
+        assert_eq!(
+            category.macos.as_deref(),
+            Some("public.app-category.developer-tools")
+        );
+        assert_eq!(settings.file_associations[0].ext, vec!["exdoc".to_string()]);
+        assert_eq!(
+            settings.file_associations[0].mime_type.as_deref(),
+            Some("text/x-exdoc")
+        );
+
+        assert_eq!(
+            settings.deb.depends,
+            Some(vec!["libwebkit2gtk-4.1-0".to_string()])
+        );
+        assert_eq!(
+            settings
+                .deb
+                .files
+                .get(&PathBuf::from("/usr/share/doc/example/README")),
+            Some(&root.join("README.md"))
+        );
+        assert_eq!(settings.rpm.release, "2");
+        assert_eq!(settings.rpm.epoch, 1);
+
+        assert_eq!(
+            settings.macos.minimum_system_version.as_deref(),
+            Some("11.0")
+        );
+        assert_eq!(
+            settings.macos.entitlements,
+            Some(root.join("Entitlements.plist"))
+        );
+        assert_eq!(settings.dmg.window_size, Some((660, 400)));
+        assert_eq!(settings.dmg.app_position, Some((180, 170)));
+
+        let windows = &settings.windows;
+        assert_eq!(
+            windows.sign_command.as_deref(),
+            Some("trusted-signing-cli -e https://example.com %1")
+        );
+        assert!(windows.nsis.compression.is_none());
+        assert_eq!(
+            windows.nsis.installer_icon,
+            Some(root.join("icons/icon.ico"))
+        );
+        assert_eq!(windows.nsis.license, Some(root.join("LICENSE")));
+        let mut languages = windows.wix.language.clone();
+        languages.sort();
+        assert_eq!(languages, vec!["de-DE".to_string(), "en-US".to_string()]);
+    }
+
+    #[test]
+    fn test_convert_tauri_v1() {
+        let conf = json!({
+            "tauri": {
+                "bundle": {
+                    "identifier": "com.example.legacy",
+                    "deb": { "depends": ["libgtk-3-0"] },
+                    "appimage": { "bundleMediaFramework": true }
+                }
+            }
+        });
+        let settings: BundleSettings =
+            serde_json::from_value(convert(&conf, Path::new("/app"))).unwrap();
+        assert_eq!(settings.identifier.as_deref(), Some("com.example.legacy"));
+        assert_eq!(settings.deb.depends, Some(vec!["libgtk-3-0".to_string()]));
+        assert!(settings.appimage.bundle_media_framework);
+    }
+}
@@ -0,0 +1,237 @@
+//! Unknown key detection for `[package.metadata.bundle]`.
+//!
+//! Bundle settings ignore keys they do not know, so a typo such as
+//! `[package.metadata.bundle.window]` silently drops the whole section.
+//! [`deserialize`] parses leniently as before, but walks the TOML tree
+//! alongside the settings structs and records every key no struct field
+//! claimed, together with the closest field name as a suggestion.
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::fmt;
+
+/// Minimum Jaro similarity for a field name to be suggested
+const SUGGESTION_THRESHOLD: f64 = 0.7;
+
+/// A key no settings field accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Dotted path of the key (e.g. `package.metadata.bundle.window`)
+    pub path: String,
+
+    /// Closest known key at the same level
+    pub suggestion: Option<&'static str>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown key `{}`", self.path)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, "; did you mean `{}`?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes `value`, found at `path`, and returns it with the keys that
+/// were ignored
+pub fn deserialize<T: DeserializeOwned>(
+    value: toml::Value,
+    path: &str,
+) -> Result<(T, Vec<UnknownKey>), toml::de::Error> {
+    let unknown = RefCell::new(Vec::new());
+    let parsed = T::deserialize(Tracked {
+        value,
+        path: path.to_string(),
+        unknown: &unknown,
+    })?;
+    Ok((parsed, unknown.into_inner()))
+}
+
+/// Closest of `fields` to `key`, if any is close enough
+fn suggest(key: &str, fields: &'static [&'static str]) -> Option<&'static str> {
+    fields
+        .iter()
+        .map(|field| (strsim::jaro(key, field), *field))
+        .filter(|(similarity, _)| *similarity > SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, field)| field)
+}
+
+/// A TOML value that reports unknown keys of the structs it becomes
+struct Tracked<'a> {
+    value: toml::Value,
+    path: String,
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> de::Deserializer<'de> for Tracked<'_> {
+    type Error = toml::de::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            toml::Value::Table(table) => visitor.visit_map(TableAccess {
+                entries: table.into_iter(),
+                value: None,
+                path: self.path,
+                unknown: self.unknown,
+            }),
+            toml::Value::Array(array) => visitor.visit_seq(ArrayAccess {
+                items: array.into_iter().enumerate(),
+                path: self.path,
+                unknown: self.unknown,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if let toml::Value::Table(table) = &self.value {
+            let mut unknown = self.unknown.borrow_mut();
+            for key in table.keys().filter(|key| !fields.contains(&key.as_str())) {
+                unknown.push(UnknownKey {
+                    path: format!("{}.{}", self.path, key),
+                    suggestion: suggest(key, fields),
+                });
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // TOML has no null; a present key is always Some
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+        ignored_any
+    }
+}
+
+struct TableAccess<'a> {
+    entries: <toml::Table as IntoIterator>::IntoIter,
+    value: Option<(String, toml::Value)>,
+    path: String,
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> MapAccess<'de> for TableAccess<'_> {
+    type Error = toml::de::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let parsed = seed.deserialize(key.clone().into_deserializer())?;
+        self.value = Some((key, value));
+        Ok(Some(parsed))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| <toml::de::Error as de::Error>::custom("value requested before key"))?;
+        seed.deserialize(Tracked {
+            value,
+            path: format!("{}.{}", self.path, key),
+            unknown: self.unknown,
+        })
+    }
+}
+
+struct ArrayAccess<'a> {
+    items: std::iter::Enumerate<<toml::value::Array as IntoIterator>::IntoIter>,
+    path: String,
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess<'_> {
+    type Error = toml::de::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.items.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(Tracked {
+            value,
+            path: format!("{}[{}]", self.path, index),
+            unknown: self.unknown,
+        })
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::BundleSettings;
+
+    #[test]
+    fn test_unknown_keys() {
+        let value: toml::Value = toml::from_str(
+            r#"
+            identifier = "com.example.app"
+            publisher = "Example"
+            identifer = "typo"
+
+            [window]
+            nsis = {}
+
+            [deb]
+            depends = ["libc6"]
+            dependz = ["libssl3"]
+
+            [[macos.launch_agents]]
+            label = "com.example.agent"
+            run_at_lod = true
+            "#,
+        )
+        .unwrap();
+
+        let (settings, unknown): (BundleSettings, _) =
+            deserialize(value, "package.metadata.bundle").unwrap();
+        assert_eq!(settings.identifier.as_deref(), Some("com.example.app"));
+        assert_eq!(settings.deb.depends, Some(vec!["libc6".to_string()]));
+
+        let found: Vec<_> = unknown.iter().map(ToString::to_string).collect();
+        assert!(found.contains(
+            &"unknown key `package.metadata.bundle.identifer`; did you mean `identifier`?".to_string()
+        ));
+        assert!(found.contains(
+            &"unknown key `package.metadata.bundle.window`; did you mean `windows`?".to_string()
+        ));
+        assert!(found.contains(
+            &"unknown key `package.metadata.bundle.deb.dependz`; did you mean `depends`?".to_string()
+        ));
+        assert!(found.contains(
+            &"unknown key `package.metadata.bundle.macos.launch_agents[0].run_at_lod`; did you mean `run_at_load`?"
+                .to_string()
+        ));
+        assert_eq!(unknown.len(), 4);
+    }
+}
@@ -1,8 +1,187 @@
 //! Source repository resolution
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::bundler::utils::interrupt::{self, CleanupGuard};
+use crate::bundler::utils::retry::retry;
 use crate::error::Result;
 
+/// Directory name prefix of repository clones in the system temp directory
+pub const CLONE_PREFIX: &str = "kodegen-bundle-";
+
+/// How [`RepositorySource::resolve`] checks out the repository
+#[derive(Debug, Clone, Default)]
+pub struct CheckoutOptions {
+    /// Branch or tag to check out; the remote's default branch when unset
+    pub git_ref: Option<String>,
+    /// Reuse a persistent clone (and its `target/`) from the cache directory
+    pub cache: bool,
+    /// Leave a temporary clone on disk after the run
+    pub keep_temp: bool,
+    /// Use a local source path as is instead of cloning its repository
+    pub in_place: bool,
+}
+
+/// Working tree of the repository being bundled
+///
+/// Either a persistent clone in the cache directory, or a clone in the
+/// system temp directory that is removed when dropped. While it exists the
+/// `<clone>.lock` file is held with an exclusive `flock`, so concurrent runs
+/// never share a cached clone and `clean` can tell clones of running builds
+/// from stale ones.
+pub struct Checkout {
+    path: PathBuf,
+    cached: bool,
+    keep: bool,
+    #[cfg(unix)]
+    _lock: Option<nix::fcntl::Flock<std::fs::File>>,
+    _interrupt: Option<CleanupGuard>,
+}
+
+impl Checkout {
+    /// Creates an empty temporary clone directory; `keep` leaves it on disk after the run
+    fn temp(keep: bool) -> Result<Self> {
+        let path = std::env::temp_dir()
+            .join(format!("{}{}", CLONE_PREFIX, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)?;
+
+        #[cfg(unix)]
+        let _lock = match lock(&path)? {
+            Some(lock) => Some(lock),
+            None => {
+                tracing::debug!("Failed to lock {}", path.display());
+                None
+            }
+        };
+        let _interrupt = (!keep).then(|| interrupt::remove_dir_on_interrupt(&path));
+
+        Ok(Self {
+            path,
+            cached: false,
+            keep,
+            #[cfg(unix)]
+            _lock,
+            _interrupt,
+        })
+    }
+
+    /// Locks the cached clone at `path`; `None` when another run holds it
+    fn cached(path: PathBuf) -> Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        #[cfg(unix)]
+        let Some(lock) = lock(&path)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            path,
+            cached: true,
+            keep: true,
+            #[cfg(unix)]
+            _lock: Some(lock),
+            _interrupt: None,
+        }))
+    }
+
+    /// Uses the existing working tree at `path`, which is left untouched
+    fn in_place(path: PathBuf) -> Self {
+        Self {
+            path,
+            cached: false,
+            keep: true,
+            #[cfg(unix)]
+            _lock: None,
+            _interrupt: None,
+        }
+    }
+
+    /// Path of the working tree
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the working tree is a persistent clone from the cache directory
+    pub fn is_cached(&self) -> bool {
+        self.cached
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        if self.keep {
+            return;
+        }
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            tracing::warn!("Failed to remove temporary clone {}: {}", self.path.display(), e);
+        }
+        let _ = std::fs::remove_file(lock_path(&self.path));
+    }
+}
+
+/// Takes the exclusive lock of the clone at `clone`; `None` when it is held
+#[cfg(unix)]
+fn lock(clone: &Path) -> Result<Option<nix::fcntl::Flock<std::fs::File>>> {
+    use nix::errno::Errno;
+    use nix::fcntl::{Flock, FlockArg};
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(clone))?;
+    match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(lock) => Ok(Some(lock)),
+        Err((_, Errno::EWOULDBLOCK)) => Ok(None),
+        Err((_, errno)) => Err(std::io::Error::from(errno).into()),
+    }
+}
+
+/// Directory holding the persistent clones reused across runs
+pub fn clone_cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("kodegen-bundler").join("clones"))
+}
+
+/// Cache key of a clone of `url` at `git_ref`, e.g. `github.com_cyrup-ai_kodegen@main`
+fn cache_key(url: &str, git_ref: Option<&str>) -> String {
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '.') { c } else { '_' })
+            .collect()
+    };
+    let repo = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let repo = repo.trim_end_matches('/').trim_end_matches(".git");
+    format!("{}@{}", sanitize(repo), sanitize(git_ref.unwrap_or("HEAD")))
+}
+
+/// Lock file marking the clone at `clone` as in use
+pub fn lock_path(clone: &Path) -> PathBuf {
+    let mut name = clone.as_os_str().to_os_string();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+/// Whether a running bundler holds the clone at `clone`
+pub fn clone_in_use(clone: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use nix::errno::Errno;
+        use nix::fcntl::{Flock, FlockArg};
+        let Ok(file) = std::fs::File::open(lock_path(clone)) else {
+            return false;
+        };
+        matches!(
+            Flock::lock(file, FlockArg::LockExclusiveNonblock),
+            Err((_, Errno::EWOULDBLOCK))
+        )
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = clone;
+        false
+    }
+}
+
 pub enum RepositorySource {
     Local(PathBuf),
     GitHub { org: String, repo: String },
@@ -37,7 +216,9 @@ impl RepositorySource {
         Ok(Self::Local(PathBuf::from(source)))
     }
     
-    pub async fn resolve(&self) -> Result<PathBuf> {
+    /// Checks out the repository as described by `options`
+    #[tracing::instrument(name = "clone", skip_all)]
+    pub async fn resolve(&self, options: &CheckoutOptions) -> Result<Checkout> {
         match self {
             Self::Local(path) => {
                 // Local path: read Cargo.toml to get repository URL, then clone from GitHub
@@ -57,6 +238,10 @@ impl RepositorySource {
                         }
                     ));
                 }
+
+                if options.in_place {
+                    return Ok(Checkout::in_place(path.clone()));
+                }
                 
                 // Read repository URL from Cargo.toml
                 let manifest = crate::metadata::load_manifest(&cargo_toml_path)?;
@@ -71,41 +256,191 @@ impl RepositorySource {
                 })?;
                 
                 // Clone from GitHub to tmp
-                clone_repo(&repo_url).await
+                checkout(&repo_url, options).await
             }
             Self::GitHub { org, repo } => {
                 let url = format!("https://github.com/{}/{}.git", org, repo);
-                clone_repo(&url).await
+                checkout(&url, options).await
             }
-            Self::GitHubUrl(url) => clone_repo(url).await,
+            Self::GitHubUrl(url) => checkout(url, options).await,
         }
     }
 }
 
-async fn clone_repo(url: &str) -> Result<PathBuf> {
-    let temp_dir = std::env::temp_dir()
-        .join(format!("kodegen-bundle-{}", uuid::Uuid::new_v4()));
-    tokio::fs::create_dir_all(&temp_dir).await?;
-    
-    let temp_dir_str = temp_dir.to_str().ok_or_else(|| {
-        crate::error::BundlerError::Cli(crate::error::CliError::InvalidArguments {
-            reason: format!("Temp directory path contains invalid UTF-8: {}", temp_dir.display()),
-        })
-    })?;
-    
+/// Checks out `url`, reusing the cached clone when enabled and available
+async fn checkout(url: &str, options: &CheckoutOptions) -> Result<Checkout> {
+    let git_ref = options.git_ref.as_deref();
+
+    if options.cache {
+        match clone_cache_dir() {
+            Some(dir) => {
+                let path = dir.join(cache_key(url, git_ref));
+                match Checkout::cached(path.clone())? {
+                    Some(checkout) => {
+                        update_cached(url, git_ref, checkout.path()).await?;
+                        return Ok(checkout);
+                    }
+                    None => tracing::warn!(
+                        "Cached clone {} is in use by another run, cloning to a temporary directory",
+                        path.display()
+                    ),
+                }
+            }
+            None => tracing::warn!("No cache directory available, cloning to a temporary directory"),
+        }
+    }
+
+    let checkout = Checkout::temp(options.keep_temp)?;
+    clone_repo(url, git_ref, checkout.path()).await?;
+    Ok(checkout)
+}
+
+/// Brings the cached clone at `path` up to date, cloning afresh when needed
+///
+/// An existing clone is fetched and hard-reset to the fetched commit. Untracked
+/// and ignored files are removed except `target/`, so incremental builds
+/// survive while stale sources do not.
+async fn update_cached(url: &str, git_ref: Option<&str>, path: &Path) -> Result<()> {
+    if path.join(".git").is_dir() {
+        let refspec = git_ref.unwrap_or("HEAD");
+        let updated = async {
+            let fetch = ["fetch", "--depth=1", "origin", refspec];
+            retry("git fetch", is_transient_git_error, || git(path, &fetch)).await?;
+            git(path, &["reset", "--hard", "FETCH_HEAD"]).await?;
+            git(path, &["clean", "-ffdx", "-e", "/target"]).await
+        };
+        match updated.await {
+            Ok(()) => return Ok(()),
+            Err(e) => tracing::warn!("Failed to update cached clone {}, cloning afresh: {}", path.display(), e),
+        }
+    }
+
+    if path.exists() {
+        tokio::fs::remove_dir_all(path).await?;
+    }
+    clone_repo(url, git_ref, path).await
+}
+
+/// Runs `git <args>` in `repo`
+async fn git(repo: &Path, args: &[&str]) -> Result<()> {
     let output = tokio::process::Command::new("git")
-        .args(["clone", "--depth=1", url, temp_dir_str])
+        .arg("-C")
+        .arg(repo)
+        .args(args)
         .output()
         .await?;
-    
+
     if !output.status.success() {
         return Err(crate::error::BundlerError::Cli(
             crate::error::CliError::ExecutionFailed {
-                command: "git clone".to_string(),
+                command: format!("git {}", args.join(" ")),
                 reason: String::from_utf8_lossy(&output.stderr).to_string(),
             }
         ));
     }
+    Ok(())
+}
+
+/// Shallow-clones `url` at `git_ref` into `dest`
+async fn clone_repo(url: &str, git_ref: Option<&str>, dest: &Path) -> Result<()> {
+    let dest_str = dest.to_str().ok_or_else(|| {
+        crate::error::BundlerError::Cli(crate::error::CliError::InvalidArguments {
+            reason: format!("Clone directory path contains invalid UTF-8: {}", dest.display()),
+        })
+    })?;
+    
     
-    Ok(temp_dir)
+    retry(&format!("git clone {}", url), is_transient_git_error, || async {
+        // A failed attempt may leave a partial checkout behind
+        if let Ok(mut entries) = tokio::fs::read_dir(dest).await {
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir() {
+                    tokio::fs::remove_dir_all(entry.path()).await?;
+                } else {
+                    tokio::fs::remove_file(entry.path()).await?;
+                }
+            }
+        }
+
+        let mut command = tokio::process::Command::new("git");
+        command.args(["clone", "--depth=1"]);
+        if let Some(git_ref) = git_ref {
+            command.args(["--branch", git_ref]);
+        }
+        let output = command.args([url, dest_str]).output().await?;
+
+        if !output.status.success() {
+            return Err(crate::error::BundlerError::Cli(
+                crate::error::CliError::ExecutionFailed {
+                    command: "git clone".to_string(),
+                    reason: String::from_utf8_lossy(&output.stderr).to_string(),
+                }
+            ));
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Whether a failed git network operation may succeed when tried again
+///
+/// Missing repositories or refs and rejected credentials are permanent.
+fn is_transient_git_error(error: &crate::error::BundlerError) -> bool {
+    const PERMANENT: [&str; 4] = [
+        "not found",
+        "does not exist",
+        "Authentication failed",
+        "couldn't find remote ref",
+    ];
+    match error {
+        crate::error::BundlerError::Cli(crate::error::CliError::ExecutionFailed { reason, .. }) => {
+            !PERMANENT.iter().any(|message| reason.contains(message))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_clone_lifecycle() {
+        let clone = Checkout::temp(false).unwrap();
+        let path = clone.path().to_path_buf();
+        assert!(path.is_dir());
+        #[cfg(unix)]
+        assert!(clone_in_use(&path));
+
+        drop(clone);
+        assert!(!path.exists());
+        assert!(!lock_path(&path).exists());
+        assert!(!clone_in_use(&path));
+    }
+
+    #[test]
+    fn test_temp_clone_keep() {
+        let clone = Checkout::temp(true).unwrap();
+        let path = clone.path().to_path_buf();
+        drop(clone);
+
+        // Kept clones are no longer locked, so `clean` may remove them
+        assert!(path.is_dir());
+        assert!(!clone_in_use(&path));
+        std::fs::remove_dir_all(&path).unwrap();
+        std::fs::remove_file(lock_path(&path)).unwrap();
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(
+            cache_key("https://github.com/cyrup-ai/kodegen.git", None),
+            "github.com_cyrup-ai_kodegen@HEAD"
+        );
+        assert_eq!(
+            cache_key("git@github.com:cyrup-ai/kodegen", Some("release/v1")),
+            "git_github.com_cyrup-ai_kodegen@release_v1"
+        );
+    }
 }